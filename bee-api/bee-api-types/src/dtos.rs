@@ -19,6 +19,8 @@ pub struct PeerDto {
     pub connected: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gossip: Option<GossipDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ConnectionDto>,
 }
 
 #[cfg(feature = "peer")]
@@ -59,7 +61,24 @@ impl From<&Peer> for PeerDto {
                     sent_heartbeats: peer.metrics().heartbeats_sent(),
                     dropped_packets: 0,
                 },
+                bandwidth: BandwidthDto {
+                    received: PacketBytesDto {
+                        total: peer.metrics().bytes_received(),
+                        milestone_requests: peer.metrics().milestone_request_bytes_received(),
+                        blocks: peer.metrics().block_bytes_received(),
+                        block_requests: peer.metrics().block_request_bytes_received(),
+                        heartbeats: peer.metrics().heartbeat_bytes_received(),
+                    },
+                    sent: PacketBytesDto {
+                        total: peer.metrics().bytes_sent(),
+                        milestone_requests: peer.metrics().milestone_request_bytes_sent(),
+                        blocks: peer.metrics().block_bytes_sent(),
+                        block_requests: peer.metrics().block_request_bytes_sent(),
+                        heartbeats: peer.metrics().heartbeat_bytes_sent(),
+                    },
+                },
             }),
+            connection: None,
         }
     }
 }
@@ -69,6 +88,18 @@ impl From<&Peer> for PeerDto {
 pub struct GossipDto {
     pub heartbeat: HeartbeatDto,
     pub metrics: MetricsDto,
+    #[serde(default)]
+    pub bandwidth: BandwidthDto,
+}
+
+/// Describes the connection history of the peer.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionDto {
+    #[serde(rename = "lastConnected", skip_serializing_if = "Option::is_none")]
+    pub last_connected: Option<u64>,
+    #[serde(rename = "failedDials")]
+    pub failed_dials: u64,
+    pub uptime: u8,
 }
 
 /// Describes the relation with the peer.
@@ -124,6 +155,74 @@ pub struct MetricsDto {
     pub dropped_packets: u64,
 }
 
+/// Describes the bytes received from and sent to a peer over its gossip stream.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BandwidthDto {
+    pub received: PacketBytesDto,
+    pub sent: PacketBytesDto,
+}
+
+/// Describes a number of bytes, per packet type.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PacketBytesDto {
+    pub total: u64,
+    #[serde(rename = "milestoneRequests")]
+    pub milestone_requests: u64,
+    pub blocks: u64,
+    #[serde(rename = "blockRequests")]
+    pub block_requests: u64,
+    pub heartbeats: u64,
+}
+
+/// Describes a banned peer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BannedPeerDto {
+    pub id: String,
+    #[serde(rename = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Describes a banned address.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BannedAddressDto {
+    #[serde(rename = "multiAddress")]
+    pub multi_address: String,
+    #[serde(rename = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Describes the inbound connections rejected by the connection gater, per reason.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionGaterMetricsDto {
+    pub rejected: u64,
+    pub denied: u64,
+    #[serde(rename = "notAllowed")]
+    pub not_allowed: u64,
+    #[serde(rename = "pendingLimit")]
+    pub pending_limit: u64,
+    #[serde(rename = "ipLimit")]
+    pub ip_limit: u64,
+    #[serde(rename = "subnetLimit")]
+    pub subnet_limit: u64,
+}
+
+/// Describes the autopeering packets that were rejected, per reason.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AutopeeringMetricsDto {
+    #[serde(rename = "invalidPackets")]
+    pub invalid_packets: u64,
+    #[serde(rename = "invalidSignatures")]
+    pub invalid_signatures: u64,
+    #[serde(rename = "invalidMessages")]
+    pub invalid_messages: u64,
+    #[serde(rename = "rejectedMessages")]
+    pub rejected_messages: u64,
+}
+
 /// Describes a receipt.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ReceiptDto {
@@ -11,7 +11,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     body::BodyInner,
-    dtos::{LedgerInclusionStateDto, PeerDto, ReceiptDto},
+    dtos::{
+        AutopeeringMetricsDto, BannedAddressDto, BannedPeerDto, ConnectionGaterMetricsDto, LedgerInclusionStateDto,
+        PeerDto, ReceiptDto,
+    },
     error::Error,
 };
 
@@ -306,6 +309,24 @@ pub struct AddPeerResponse(pub PeerDto);
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PeerResponse(pub PeerDto);
 
+/// Response of GET /api/core/v2/bans.
+/// Returns the peers and addresses banned by the node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BansResponse {
+    pub peers: Vec<BannedPeerDto>,
+    pub addresses: Vec<BannedAddressDto>,
+}
+
+/// Response of GET /api/core/v2/network/metrics.
+/// Returns the metrics of the network layer of the node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetworkMetricsResponse {
+    #[serde(rename = "connectionGater")]
+    pub connection_gater: ConnectionGaterMetricsDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autopeering: Option<AutopeeringMetricsDto>,
+}
+
 /// Response of GET /api/plugins/debug/whiteflag.
 /// Returns the computed merkle tree hash for the given white flag traversal.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -355,6 +376,8 @@ mod axum_response {
         AddPeerResponse,
         PeersResponse,
         PeerResponse,
+        BansResponse,
+        NetworkMetricsResponse,
         WhiteFlagResponse
     );
 
@@ -36,6 +36,7 @@ lazy_static! {
             "/api/core/v2/milestones*",
             "/api/core/v2/outputs*",
             "/api/core/v2/peers*",
+            "/api/core/v2/network*",
         ];
         // Panic: unwrapping is fine because all strings in `routes` can be turned into valid regular expressions.
         RegexSet::new(routes.iter().map(|r| route_to_regex(r)).collect::<Vec<_>>()).unwrap()
@@ -39,6 +39,8 @@ pub enum DependencyError {
     #[error("{0}")]
     InvalidBlockSubmitted(#[from] bee_protocol::BlockSubmitterError),
     #[error("{0}")]
+    InvalidBlockPromotion(#[from] bee_protocol::BlockPromoterError),
+    #[error("{0}")]
     InvalidBlock(#[from] bee_block::Error),
     #[error("{0}")]
     InvalidDto(#[from] bee_block::DtoError),
@@ -23,11 +23,17 @@ use axum::{
     routing::get, Router,
 };
 pub use bee_api_types as types;
-use bee_gossip::{Keypair, NetworkCommandSender, PeerId};
-use bee_ledger::consensus::{ConsensusWorker, ConsensusWorkerCommand};
+use bee_autopeering::PacketMetrics;
+use bee_gossip::{
+    ConnectionGaterMetrics, Keypair, NetworkBanList, NetworkCommandSender, NetworkConnectionHistory, PeerId,
+};
+use bee_ledger::{
+    consensus::{ConsensusWorker, ConsensusWorkerCommand},
+    parameters::{ProtocolParametersManager, ProtocolParametersWorker},
+};
 use bee_protocol::{
-    config::ProtocolConfig, BlockRequesterWorker, BlockSubmitterWorker, BlockSubmitterWorkerEvent, PeerManager,
-    PeerManagerResWorker, RequestedBlocks,
+    BlockPromoterWorker, BlockPromoterWorkerEvent, BlockRequesterWorker, BlockSubmitterWorker,
+    BlockSubmitterWorkerEvent, PeerManager, PeerManagerResWorker, RequestedBlocks,
 };
 use bee_runtime::{
     event::Bus,
@@ -62,9 +68,9 @@ pub struct InitFullNodeConfig {
     pub node_id: PeerId,
     pub node_keypair: Keypair,
     pub rest_api_config: RestApiConfig,
-    pub protocol_config: ProtocolConfig,
     pub network_name: String,
     pub bech32_hrp: String,
+    pub autopeering_metrics: Option<PacketMetrics>,
     #[cfg(feature = "dashboard")]
     pub dashboard_username: String,
 }
@@ -93,17 +99,22 @@ pub struct ApiArgsFullNodeInner<B: StorageBackend> {
     pub(crate) node_id: PeerId,
     pub(crate) node_keypair: Keypair,
     pub(crate) rest_api_config: RestApiConfig,
-    pub(crate) protocol_config: ProtocolConfig,
     pub(crate) network_name: String,
     pub(crate) bech32_hrp: String,
     pub(crate) storage: ResourceHandle<B>,
     pub(crate) bus: ResourceHandle<Bus<'static>>,
     pub(crate) node_info: ResourceHandle<NodeInfo>,
     pub(crate) tangle: ResourceHandle<Tangle<B>>,
+    pub(crate) protocol_parameters: ResourceHandle<ProtocolParametersManager>,
     pub(crate) peer_manager: ResourceHandle<PeerManager>,
     pub(crate) requested_blocks: ResourceHandle<RequestedBlocks>,
     pub(crate) network_command_sender: ResourceHandle<NetworkCommandSender>,
+    pub(crate) network_ban_list: ResourceHandle<NetworkBanList>,
+    pub(crate) network_connection_history: ResourceHandle<NetworkConnectionHistory>,
+    pub(crate) connection_gater_metrics: ResourceHandle<ConnectionGaterMetrics>,
+    pub(crate) autopeering_metrics: Option<PacketMetrics>,
     pub(crate) block_submitter: mpsc::UnboundedSender<BlockSubmitterWorkerEvent>,
+    pub(crate) block_promoter: mpsc::Sender<BlockPromoterWorkerEvent>,
     pub(crate) block_requester: BlockRequesterWorker,
     pub(crate) consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
     #[cfg(feature = "dashboard")]
@@ -124,7 +135,9 @@ where
         vec![
             TypeId::of::<TangleWorker>(),
             TypeId::of::<BlockSubmitterWorker>(),
+            TypeId::of::<BlockPromoterWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
         ]
         .leak()
     }
@@ -134,17 +147,22 @@ where
             node_id: config.node_id,
             node_keypair: config.node_keypair,
             rest_api_config: config.rest_api_config,
-            protocol_config: config.protocol_config,
             network_name: config.network_name,
             bech32_hrp: config.bech32_hrp,
             storage: node.storage(),
             bus: node.bus(),
             node_info: node.info(),
             tangle: node.resource::<Tangle<N::Backend>>(),
+            protocol_parameters: node.resource::<ProtocolParametersManager>(),
             peer_manager: node.resource::<PeerManager>(),
             requested_blocks: node.resource::<RequestedBlocks>(),
             network_command_sender: node.resource::<NetworkCommandSender>(),
+            network_ban_list: node.resource::<NetworkBanList>(),
+            network_connection_history: node.resource::<NetworkConnectionHistory>(),
+            connection_gater_metrics: node.resource::<ConnectionGaterMetrics>(),
+            autopeering_metrics: config.autopeering_metrics,
             block_submitter: node.worker::<BlockSubmitterWorker>().unwrap().tx.clone(),
+            block_promoter: node.worker::<BlockPromoterWorker>().unwrap().tx.clone(),
             block_requester: node.worker::<BlockRequesterWorker>().unwrap().clone(),
             consensus_worker: node.worker::<ConsensusWorker>().unwrap().tx.clone(),
             #[cfg(feature = "dashboard")]
@@ -0,0 +1,44 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, routing::get, Router};
+
+use crate::{
+    storage::StorageBackend,
+    types::{
+        dtos::{BannedAddressDto, BannedPeerDto},
+        responses::BansResponse,
+    },
+    ApiArgsFullNode,
+};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/bans", get(bans::<B>))
+}
+
+async fn bans<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>) -> BansResponse {
+    let peers = args
+        .network_ban_list
+        .peers()
+        .await
+        .into_iter()
+        .map(|(peer_id, ban)| BannedPeerDto {
+            id: peer_id.to_string(),
+            expires_at: ban.expires_at(),
+            reason: ban.reason().map(ToString::to_string),
+        })
+        .collect();
+    let addresses = args
+        .network_ban_list
+        .addresses()
+        .await
+        .into_iter()
+        .map(|(address, ban)| BannedAddressDto {
+            multi_address: address.to_string(),
+            expires_at: ban.expires_at(),
+            reason: ban.reason().map(ToString::to_string),
+        })
+        .collect();
+
+    BansResponse { peers, addresses }
+}
@@ -0,0 +1,80 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, http::StatusCode, routing::post, Router};
+use bee_gossip::{
+    Command::{BanAddress, BanPeer},
+    Multiaddr, PeerId,
+};
+use log::error;
+use serde_json::Value;
+
+use crate::{error::ApiError, extractors::json::CustomJson, storage::StorageBackend, ApiArgsFullNode};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/bans", post(bans_add::<B>))
+}
+
+async fn bans_add<B: StorageBackend>(
+    CustomJson(value): CustomJson<Value>,
+    Extension(args): Extension<ApiArgsFullNode<B>>,
+) -> Result<StatusCode, ApiError> {
+    let peer_id_json = &value["peerId"];
+    let multiaddress_json = &value["multiAddress"];
+    let expires_at_json = &value["expiresAt"];
+    let reason_json = &value["reason"];
+
+    let expires_at = if expires_at_json.is_null() {
+        None
+    } else {
+        Some(
+            expires_at_json
+                .as_u64()
+                .ok_or(ApiError::BadRequest("invalid expiry: expected a unix timestamp"))?,
+        )
+    };
+
+    let reason = if reason_json.is_null() {
+        None
+    } else {
+        Some(
+            reason_json
+                .as_str()
+                .ok_or(ApiError::BadRequest("invalid reason: expected a string"))?
+                .to_string(),
+        )
+    };
+
+    let command = match (peer_id_json.is_null(), multiaddress_json.is_null()) {
+        (false, true) => BanPeer {
+            peer_id: peer_id_json
+                .as_str()
+                .ok_or(ApiError::BadRequest("invalid peer id"))?
+                .parse::<PeerId>()
+                .map_err(|_| ApiError::BadRequest("invalid peer id"))?,
+            expires_at,
+            reason,
+        },
+        (true, false) => BanAddress {
+            address: multiaddress_json
+                .as_str()
+                .ok_or(ApiError::BadRequest("invalid multiaddress"))?
+                .parse::<Multiaddr>()
+                .map_err(|_| ApiError::BadRequest("invalid multiaddress"))?,
+            expires_at,
+            reason,
+        },
+        _ => {
+            return Err(ApiError::BadRequest(
+                "expected either a peer id or a multiaddress to ban",
+            ));
+        }
+    };
+
+    if let Err(e) = args.network_command_sender.send(command) {
+        error!("cannot ban: {}", e);
+        return Err(ApiError::InternalServerError);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
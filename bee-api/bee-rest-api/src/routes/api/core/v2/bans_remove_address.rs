@@ -0,0 +1,29 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, http::StatusCode, routing::delete, Router};
+use bee_gossip::{Command::UnbanAddress, Multiaddr};
+use log::error;
+
+use crate::{error::ApiError, extractors::path::CustomPath, storage::StorageBackend, ApiArgsFullNode};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    // A multiaddress contains slashes, hence the wildcard.
+    Router::new().route("/bans/addresses/*multi_address", delete(bans_remove_address::<B>))
+}
+
+async fn bans_remove_address<B: StorageBackend>(
+    CustomPath(multi_address): CustomPath<String>,
+    Extension(args): Extension<ApiArgsFullNode<B>>,
+) -> Result<StatusCode, ApiError> {
+    let address = format!("/{}", multi_address.trim_start_matches('/'))
+        .parse::<Multiaddr>()
+        .map_err(|_| ApiError::BadRequest("invalid multiaddress"))?;
+
+    if let Err(e) = args.network_command_sender.send(UnbanAddress { address }) {
+        error!("cannot unban address: {}", e);
+        return Err(ApiError::InternalServerError);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,28 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, http::StatusCode, routing::delete, Router};
+use bee_gossip::{Command::UnbanPeer, PeerId};
+use log::error;
+
+use crate::{error::ApiError, extractors::path::CustomPath, storage::StorageBackend, ApiArgsFullNode};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/bans/peers/:peer_id", delete(bans_remove_peer::<B>))
+}
+
+async fn bans_remove_peer<B: StorageBackend>(
+    CustomPath(peer_id): CustomPath<String>,
+    Extension(args): Extension<ApiArgsFullNode<B>>,
+) -> Result<StatusCode, ApiError> {
+    let peer_id = peer_id
+        .parse::<PeerId>()
+        .map_err(|_| ApiError::BadRequest("invalid peer id"))?;
+
+    if let Err(e) = args.network_command_sender.send(UnbanPeer { peer_id }) {
+        error!("cannot unban peer: {}", e);
+        return Err(ApiError::InternalServerError);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
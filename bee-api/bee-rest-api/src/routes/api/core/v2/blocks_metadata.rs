@@ -3,6 +3,7 @@
 
 use axum::{extract::Extension, routing::get, Router};
 use bee_block::{payload::Payload, semantic::ConflictReason, BlockId};
+use bee_protocol::promotion_state;
 
 use crate::{
     error::ApiError,
@@ -26,10 +27,7 @@ async fn block_metadata<B: StorageBackend>(
 
     match args.tangle.get_block_and_metadata(&block_id) {
         Some((block, metadata)) => {
-            // TODO: access constants from URTS
-            let ybrsi_delta = 8;
-            let obrsi_delta = 13;
-            let below_max_depth = 15;
+            let promotion_state = promotion_state(&args.tangle, &block_id).await;
 
             let (
                 is_solid,
@@ -84,24 +82,8 @@ async fn block_metadata<B: StorageBackend>(
                     milestone_index = None;
                     ledger_inclusion_state = None;
                     conflict_reason = None;
-
-                    let cmi = *args.tangle.get_confirmed_milestone_index();
-                    // unwrap() of OBRSI/YBRSI is safe since block is solid
-                    let (obrsi, ybrsi) = metadata
-                        .omrsi_and_ymrsi()
-                        .map(|(o, y)| (*o.index(), *y.index()))
-                        .unwrap();
-
-                    if (cmi - obrsi) > below_max_depth {
-                        should_promote = Some(false);
-                        should_reattach = Some(true);
-                    } else if (cmi - ybrsi) > ybrsi_delta || (cmi - obrsi) > obrsi_delta {
-                        should_promote = Some(true);
-                        should_reattach = Some(false);
-                    } else {
-                        should_promote = Some(false);
-                        should_reattach = Some(false);
-                    };
+                    should_promote = promotion_state.map(|state| state.should_promote());
+                    should_reattach = promotion_state.map(|state| state.should_reattach());
                 } else {
                     // the block is not referenced by a milestone and not solid
                     is_solid = false;
@@ -109,8 +91,8 @@ async fn block_metadata<B: StorageBackend>(
                     milestone_index = None;
                     ledger_inclusion_state = None;
                     conflict_reason = None;
-                    should_reattach = Some(true);
-                    should_promote = Some(false);
+                    should_promote = promotion_state.map(|state| state.should_promote());
+                    should_reattach = promotion_state.map(|state| state.should_reattach());
                 }
 
                 (
@@ -119,8 +101,8 @@ async fn block_metadata<B: StorageBackend>(
                     milestone_index,
                     ledger_inclusion_state,
                     conflict_reason,
-                    should_reattach,
                     should_promote,
+                    should_reattach,
                 )
             };
 
@@ -0,0 +1,72 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, routing::post, Router};
+use bee_block::BlockId;
+use bee_protocol::{BlockPromoterCommand, BlockPromoterError, BlockPromoterWorkerEvent};
+use futures::channel::oneshot;
+use log::error;
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::{
+    error::{ApiError, DependencyError},
+    extractors::path::CustomPath,
+    storage::StorageBackend,
+    types::responses::SubmitBlockResponse,
+    ApiArgsFullNode, CONFIRMED_THRESHOLD,
+};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/blocks/:block_id/promote", post(blocks_promote::<B>))
+}
+
+async fn blocks_promote<B: StorageBackend>(
+    CustomPath(block_id): CustomPath<BlockId>,
+    Extension(args): Extension<ApiArgsFullNode<B>>,
+) -> Result<SubmitBlockResponse, ApiError> {
+    forward_to_block_promoter(block_id, BlockPromoterCommand::Promote, args).await
+}
+
+pub(crate) async fn forward_to_block_promoter<B: StorageBackend>(
+    block_id: BlockId,
+    command: BlockPromoterCommand,
+    args: ApiArgsFullNode<B>,
+) -> Result<SubmitBlockResponse, ApiError> {
+    if !args.tangle.is_confirmed_threshold(CONFIRMED_THRESHOLD) {
+        return Err(ApiError::ServiceUnavailable("the node is not synchronized"));
+    }
+
+    if !args.rest_api_config.feature_proof_of_work() {
+        return Err(ApiError::BadRequest("can not issue block: feature `PoW` not enabled"));
+    }
+
+    let (notifier, waiter) = oneshot::channel::<Result<BlockId, BlockPromoterError>>();
+
+    args.block_promoter
+        .try_send(BlockPromoterWorkerEvent {
+            block_id,
+            command,
+            notifier,
+        })
+        .map_err(|e| match e {
+            TrySendError::Full(_) => ApiError::ServiceUnavailable("too many pending promotions"),
+            TrySendError::Closed(_) => {
+                error!("cannot promote block: block promoter unavailable");
+                ApiError::InternalServerError
+            }
+        })?;
+
+    let result = waiter.await.map_err(|e| {
+        error!("cannot promote block: {}", e);
+        ApiError::InternalServerError
+    })?;
+
+    match result {
+        Ok(block_id) => Ok(SubmitBlockResponse {
+            block_id: block_id.to_string(),
+        }),
+        Err(BlockPromoterError::BlockNotFound(_)) => Err(ApiError::NotFound),
+        Err(BlockPromoterError::NoTips) => Err(ApiError::ServiceUnavailable("no tips available")),
+        Err(e) => Err(ApiError::DependencyError(DependencyError::InvalidBlockPromotion(e))),
+    }
+}
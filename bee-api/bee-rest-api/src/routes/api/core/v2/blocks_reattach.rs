@@ -0,0 +1,22 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, routing::post, Router};
+use bee_block::BlockId;
+use bee_protocol::BlockPromoterCommand;
+
+use crate::{
+    error::ApiError, extractors::path::CustomPath, routes::api::core::v2::blocks_promote::forward_to_block_promoter,
+    storage::StorageBackend, types::responses::SubmitBlockResponse, ApiArgsFullNode,
+};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/blocks/:block_id/reattach", post(blocks_reattach::<B>))
+}
+
+async fn blocks_reattach<B: StorageBackend>(
+    CustomPath(block_id): CustomPath<BlockId>,
+    Extension(args): Extension<ApiArgsFullNode<B>>,
+) -> Result<SubmitBlockResponse, ApiError> {
+    forward_to_block_promoter(block_id, BlockPromoterCommand::Reattach, args).await
+}
@@ -5,7 +5,6 @@ use axum::{body::Bytes, extract::Extension, http::header::HeaderMap, routing::po
 use bee_block::{
     parent::Parents,
     payload::{dto::PayloadDto, Payload},
-    protocol::ProtocolParameters,
     Block, BlockBuilder, BlockId,
 };
 use bee_pow::providers::{miner::MinerBuilder, NonceProviderBuilder};
@@ -48,10 +47,7 @@ pub(crate) async fn submit_block_json<B: StorageBackend>(
     value: Value,
     args: ApiArgsFullNode<B>,
 ) -> Result<SubmitBlockResponse, ApiError> {
-    // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-    // The node can't work properly with this.
-    // @thibault-martinez.
-    let protocol_parameters = ProtocolParameters::default();
+    let protocol_parameters = args.protocol_parameters.current();
 
     let protocol_version_json = &value["protocolVersion"];
     let parents_json = &value["parents"];
@@ -135,10 +131,7 @@ pub(crate) fn build_block<B: StorageBackend>(
     nonce: Option<u64>,
     args: ApiArgsFullNode<B>,
 ) -> Result<Block, ApiError> {
-    // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-    // The node can't work properly with this.
-    // @thibault-martinez.
-    let protocol_parameters = ProtocolParameters::default();
+    let protocol_parameters = args.protocol_parameters.current();
 
     let block = if let Some(nonce) = nonce {
         let mut builder = BlockBuilder::new(
@@ -2,14 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::{extract::Extension, routing::get, Router};
-use bee_block::protocol::ProtocolParameters;
+use bee_block::{payload::milestone::ParametersMilestoneOption, protocol::ProtocolParameters};
 
 use crate::{
     routes::health,
     storage::StorageBackend,
     types::responses::{
         BaseTokenResponse, ConfirmedMilestoneResponse, InfoResponse, LatestMilestoneResponse, MetricsResponse,
-        ProtocolResponse, RentStructureResponse, StatusResponse,
+        PendingProtocolParameter, ProtocolResponse, RentStructureResponse, StatusResponse,
     },
     ApiArgsFullNode,
 };
@@ -19,10 +19,7 @@ pub(crate) fn filter<B: StorageBackend>() -> Router {
 }
 
 async fn info<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>) -> InfoResponse {
-    // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-    // The node can't work properly with this.
-    // @thibault-martinez.
-    let protocol_parameters = ProtocolParameters::default();
+    let protocol_parameters = args.protocol_parameters.current();
 
     let (latest_milestone_index, latest_milestone_metadata) = {
         let latest_milestone_index = args.tangle.get_latest_milestone_index();
@@ -60,20 +57,18 @@ async fn info<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>)
             pruning_index: *args.tangle.get_pruning_index(),
         },
         supported_protocol_versions: vec![protocol_parameters.protocol_version()],
-        protocol: ProtocolResponse {
-            version: protocol_parameters.protocol_version(),
-            network_name: args.network_name.clone(),
-            bech32_hrp: args.bech32_hrp.clone(),
-            min_pow_score: args.protocol_config.minimum_pow_score() as u32,
-            below_max_depth: protocol_parameters.below_max_depth(),
-            rent_structure: RentStructureResponse {
-                v_byte_cost: args.protocol_config.rent().v_byte_cost,
-                v_byte_factor_key: args.protocol_config.rent().v_byte_factor_key,
-                v_byte_factor_data: args.protocol_config.rent().v_byte_factor_data,
-            },
-            token_supply: protocol_parameters.token_supply().to_string(),
-        },
-        pending_protocol_parameters: Vec::new(),
+        protocol: protocol_response(&protocol_parameters, args.network_name.clone(), args.bech32_hrp.clone()),
+        pending_protocol_parameters: args
+            .protocol_parameters
+            .pending()
+            .iter()
+            .map(|parameters| PendingProtocolParameter {
+                kind: ParametersMilestoneOption::KIND,
+                target_milestone_index: *parameters.target_milestone_index(),
+                protocol_version: parameters.protocol_version(),
+                params: prefix_hex::encode(parameters.binary_parameters()),
+            })
+            .collect(),
         base_token: BaseTokenResponse {
             name: "Shimmer".to_string(), // TODO: don't hardcode
             ticker_symbol: "SMR".to_string(),
@@ -96,3 +91,73 @@ async fn info<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>)
         },
     }
 }
+
+// The protocol parameters are the ones currently enforced by the node, which change when a milestone activates new ones.
+fn protocol_response(
+    protocol_parameters: &ProtocolParameters,
+    network_name: String,
+    bech32_hrp: String,
+) -> ProtocolResponse {
+    ProtocolResponse {
+        version: protocol_parameters.protocol_version(),
+        network_name,
+        bech32_hrp,
+        min_pow_score: protocol_parameters.min_pow_score(),
+        below_max_depth: protocol_parameters.below_max_depth(),
+        rent_structure: RentStructureResponse {
+            v_byte_cost: protocol_parameters.rent_structure().v_byte_cost,
+            v_byte_factor_key: protocol_parameters.rent_structure().v_byte_factor_key,
+            v_byte_factor_data: protocol_parameters.rent_structure().v_byte_factor_data,
+        },
+        token_supply: protocol_parameters.token_supply().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::{output::RentStructureBuilder, payload::milestone::MilestoneIndex};
+    use bee_ledger::parameters::ProtocolParametersManager;
+    use packable::PackableExt;
+
+    use super::*;
+
+    #[test]
+    fn protocol_response_follows_activated_parameters() {
+        let current = ProtocolParameters::default();
+        let pending = ProtocolParameters::new(
+            current.protocol_version(),
+            current.network_name().to_string(),
+            current.bech32_hrp().to_string(),
+            current.min_pow_score() * 2,
+            current.below_max_depth(),
+            RentStructureBuilder::new()
+                .byte_cost(current.rent_structure().v_byte_cost * 2)
+                .key_factor(current.rent_structure().v_byte_factor_key)
+                .data_factor(current.rent_structure().v_byte_factor_data)
+                .finish(),
+            current.token_supply(),
+        )
+        .unwrap();
+        let manager = ProtocolParametersManager::new(
+            current.clone(),
+            vec![
+                ParametersMilestoneOption::new(MilestoneIndex(10), pending.protocol_version(), pending.pack_to_vec())
+                    .unwrap(),
+            ],
+        );
+        let response = |manager: &ProtocolParametersManager| {
+            protocol_response(
+                &manager.current(),
+                current.network_name().to_string(),
+                current.bech32_hrp().to_string(),
+            )
+        };
+
+        assert_eq!(ProtocolParameters::try_from(response(&manager)).unwrap(), current);
+
+        manager.activate(MilestoneIndex(10)).unwrap();
+
+        assert_eq!(ProtocolParameters::try_from(response(&manager)).unwrap(), pending);
+        assert_eq!(response(&manager).min_pow_score, current.min_pow_score() * 2);
+    }
+}
@@ -1,12 +1,19 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bans;
+pub mod bans_add;
+pub mod bans_remove_address;
+pub mod bans_remove_peer;
 pub mod blocks;
 pub mod blocks_metadata;
+pub mod blocks_promote;
+pub mod blocks_reattach;
 pub mod blocks_submit;
 pub mod info;
 pub mod milestones_by_id;
 pub mod milestones_by_index;
+pub mod network_metrics;
 pub mod outputs;
 pub mod outputs_metadata;
 pub mod peers;
@@ -30,11 +37,18 @@ pub(crate) fn filter<B: StorageBackend>() -> Router {
     Router::new().nest(
         "/v2",
         info::filter::<B>()
+            .merge(bans::filter::<B>())
+            .merge(bans_add::filter::<B>())
+            .merge(bans_remove_address::filter::<B>())
+            .merge(bans_remove_peer::filter::<B>())
             .merge(blocks::filter::<B>())
             .merge(blocks_metadata::filter::<B>())
+            .merge(blocks_promote::filter::<B>())
+            .merge(blocks_reattach::filter::<B>())
             .merge(blocks_submit::filter::<B>())
             .merge(milestones_by_id::filter::<B>())
             .merge(milestones_by_index::filter::<B>())
+            .merge(network_metrics::filter::<B>())
             .merge(outputs::filter::<B>())
             .merge(outputs_metadata::filter::<B>())
             .merge(peers::filter::<B>())
@@ -0,0 +1,38 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{extract::Extension, routing::get, Router};
+
+use crate::{
+    storage::StorageBackend,
+    types::{
+        dtos::{AutopeeringMetricsDto, ConnectionGaterMetricsDto},
+        responses::NetworkMetricsResponse,
+    },
+    ApiArgsFullNode,
+};
+
+pub(crate) fn filter<B: StorageBackend>() -> Router {
+    Router::new().route("/network/metrics", get(network_metrics::<B>))
+}
+
+async fn network_metrics<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>) -> NetworkMetricsResponse {
+    let gater = &args.connection_gater_metrics;
+
+    NetworkMetricsResponse {
+        connection_gater: ConnectionGaterMetricsDto {
+            rejected: gater.rejected(),
+            denied: gater.rejected_denied(),
+            not_allowed: gater.rejected_not_allowed(),
+            pending_limit: gater.rejected_pending_limit(),
+            ip_limit: gater.rejected_ip_limit(),
+            subnet_limit: gater.rejected_subnet_limit(),
+        },
+        autopeering: args.autopeering_metrics.as_ref().map(|metrics| AutopeeringMetricsDto {
+            invalid_packets: metrics.invalid_packets(),
+            invalid_signatures: metrics.invalid_signatures(),
+            invalid_messages: metrics.invalid_messages(),
+            rejected_messages: metrics.rejected_messages(),
+        }),
+    }
+}
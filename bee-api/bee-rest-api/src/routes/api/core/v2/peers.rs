@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::{extract::Extension, routing::get, Router};
-use bee_gossip::PeerId;
+use bee_gossip::{ConnectionHistory, PeerId};
 
 use crate::{
     error::ApiError,
     extractors::path::CustomPath,
     storage::StorageBackend,
-    types::{dtos::PeerDto, responses::PeerResponse},
+    types::{
+        dtos::{ConnectionDto, PeerDto},
+        responses::PeerResponse,
+    },
     ApiArgsFullNode,
 };
 
@@ -24,9 +27,20 @@ async fn peers<B: StorageBackend>(
         .parse::<PeerId>()
         .map_err(|_| ApiError::BadRequest("invalid peer id"))?;
 
-    args.peer_manager
-        .get_map(&peer_id, |peer_entry| {
-            Ok(PeerResponse(PeerDto::from(peer_entry.0.as_ref())))
-        })
-        .unwrap_or(Err(ApiError::NotFound))
+    let mut peer = args
+        .peer_manager
+        .get_map(&peer_id, |peer_entry| PeerDto::from(peer_entry.0.as_ref()))
+        .ok_or(ApiError::NotFound)?;
+
+    peer.connection = args.network_connection_history.get(&peer_id).await.map(connection_dto);
+
+    Ok(PeerResponse(peer))
+}
+
+pub(crate) fn connection_dto(history: ConnectionHistory) -> ConnectionDto {
+    ConnectionDto {
+        last_connected: history.last_connected_at,
+        failed_dials: history.num_failed_dials,
+        uptime: history.uptime,
+    }
 }
@@ -76,6 +76,7 @@ async fn peers_add<B: StorageBackend>(
                 relation: RelationDto::Known,
                 connected: false,
                 gossip: None,
+                connection: None,
             }))
         })
 }
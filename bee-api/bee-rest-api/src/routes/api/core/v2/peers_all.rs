@@ -1,8 +1,12 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use axum::{extract::Extension, routing::get, Router};
+use bee_gossip::PeerId;
 
+use super::peers::connection_dto;
 use crate::{
     storage::StorageBackend,
     types::{dtos::PeerDto, responses::PeersResponse},
@@ -15,9 +19,17 @@ pub(crate) fn filter<B: StorageBackend>() -> Router {
 
 async fn peers_all<B: StorageBackend>(Extension(args): Extension<ApiArgsFullNode<B>>) -> PeersResponse {
     let mut peers = Vec::new();
+    let mut histories = args
+        .network_connection_history
+        .all()
+        .await
+        .into_iter()
+        .collect::<HashMap<PeerId, _>>();
 
     for peer in args.peer_manager.get_all() {
-        peers.push(PeerDto::from(peer.as_ref()));
+        let mut dto = PeerDto::from(peer.as_ref());
+        dto.connection = histories.remove(peer.id()).map(connection_dto);
+        peers.push(dto);
     }
 
     PeersResponse(peers)
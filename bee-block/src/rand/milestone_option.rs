@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    payload::milestone::ReceiptMilestoneOption,
+    payload::milestone::{ParametersMilestoneOption, ReceiptMilestoneOption},
     rand::{
-        bool::rand_bool, milestone::rand_milestone_index, payload::rand_treasury_transaction_payload,
-        receipt::rand_migrated_funds_entry,
+        bool::rand_bool, bytes::rand_bytes, milestone::rand_milestone_index, number::rand_number,
+        payload::rand_treasury_transaction_payload, receipt::rand_migrated_funds_entry,
     },
 };
 
@@ -20,3 +20,8 @@ pub fn rand_receipt_milestone_option(token_supply: u64) -> ReceiptMilestoneOptio
     )
     .unwrap()
 }
+
+/// Generates a random parameters milestone option.
+pub fn rand_parameters_milestone_option() -> ParametersMilestoneOption {
+    ParametersMilestoneOption::new(rand_milestone_index(), rand_number(), rand_bytes(32)).unwrap()
+}
@@ -27,6 +27,23 @@ impl SnapshotHeader {
     /// The length, in bytes, of a `SnapshotHeader`.
     pub const LENGTH: usize = 26;
 
+    /// Creates a new `SnapshotHeader`.
+    pub fn new(
+        kind: SnapshotKind,
+        timestamp: u32,
+        network_id: u64,
+        sep_index: MilestoneIndex,
+        ledger_index: MilestoneIndex,
+    ) -> Self {
+        Self {
+            kind,
+            timestamp,
+            network_id,
+            sep_index,
+            ledger_index,
+        }
+    }
+
     /// Returns the kind of a `SnapshotHeader`.
     pub fn kind(&self) -> SnapshotKind {
         self.kind
@@ -108,6 +125,23 @@ pub struct FullSnapshotHeader {
 }
 
 impl FullSnapshotHeader {
+    /// Creates a new `FullSnapshotHeader`.
+    pub fn new(
+        sep_count: u64,
+        output_count: u64,
+        milestone_diff_count: u64,
+        treasury_output_milestone_id: MilestoneId,
+        treasury_output_amount: u64,
+    ) -> Self {
+        Self {
+            sep_count,
+            output_count,
+            milestone_diff_count,
+            treasury_output_milestone_id,
+            treasury_output_amount,
+        }
+    }
+
     /// Returns the solid entry point count of a `FullSnapshotHeader`.
     pub fn sep_count(&self) -> u64 {
         self.sep_count
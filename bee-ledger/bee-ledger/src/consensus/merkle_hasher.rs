@@ -3,8 +3,8 @@
 
 use std::marker::PhantomData;
 
-use bee_block::BlockId;
-use crypto::hashes::{Digest, Output};
+use bee_block::{payload::milestone::MerkleRoot, BlockId};
+use crypto::hashes::{blake2b::Blake2b256, Digest, Output};
 
 /// Leaf domain separation prefix.
 const LEAF_HASH_PREFIX: u8 = 0x00;
@@ -68,6 +68,12 @@ impl<D: Default + Digest> MerkleHasher<D> {
     }
 }
 
+/// Computes the Merkle root of a list of block ids, as found in the inclusion and applied Merkle roots of milestones.
+pub fn merkle_root(block_ids: &[BlockId]) -> MerkleRoot {
+    // PANIC: unwrap is fine as Blake2b256 returns a hash of length MerkleRoot::LENGTH.
+    MerkleRoot::from(<[u8; MerkleRoot::LENGTH]>::try_from(MerkleHasher::<Blake2b256>::new().digest(block_ids)).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
 
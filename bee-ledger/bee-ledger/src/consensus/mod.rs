@@ -10,6 +10,7 @@ pub(crate) mod white_flag;
 pub(crate) mod worker;
 
 pub use self::{
+    merkle_hasher::merkle_root,
     metadata::WhiteFlagMetadata,
     white_flag::white_flag,
     worker::{ConsensusWorker, ConsensusWorkerCommand},
@@ -7,7 +7,6 @@ use bee_block::{
     input::Input,
     output::{Output, OutputId},
     payload::{
-        milestone::MerkleRoot,
         transaction::{RegularTransactionEssence, TransactionEssence, TransactionId, TransactionPayload},
         Payload,
     },
@@ -16,10 +15,9 @@ use bee_block::{
     Block, BlockId,
 };
 use bee_tangle::Tangle;
-use crypto::hashes::blake2b::Blake2b256;
 
 use crate::{
-    consensus::{merkle_hasher::MerkleHasher, metadata::WhiteFlagMetadata},
+    consensus::{merkle_hasher::merkle_root, metadata::WhiteFlagMetadata},
     error::Error,
     storage::{self, StorageBackend},
     types::{ConsumedOutput, CreatedOutput},
@@ -199,16 +197,8 @@ pub async fn white_flag<B: StorageBackend>(
 ) -> Result<(), Error> {
     traverse_past_cone(tangle, storage, block_ids.iter().rev().copied().collect(), metadata).await?;
 
-    // PANIC: unwrap is fine as Blake2b256 returns a hash of length MerkleRoot::LENGTH.
-    metadata.inclusion_merkle_root = MerkleRoot::from(
-        <[u8; MerkleRoot::LENGTH]>::try_from(MerkleHasher::<Blake2b256>::new().digest(&metadata.referenced_blocks))
-            .unwrap(),
-    );
-    // PANIC: unwrap is fine as Blake2b256 returns a hash of length MerkleRoot::LENGTH.
-    metadata.applied_merkle_root = MerkleRoot::from(
-        <[u8; MerkleRoot::LENGTH]>::try_from(MerkleHasher::<Blake2b256>::new().digest(&metadata.included_blocks))
-            .unwrap(),
-    );
+    metadata.inclusion_merkle_root = merkle_root(&metadata.referenced_blocks);
+    metadata.applied_merkle_root = merkle_root(&metadata.included_blocks);
 
     if *metadata.milestone_index != 1 && metadata.previous_milestone_id.is_some() && !metadata.found_previous_milestone
     {
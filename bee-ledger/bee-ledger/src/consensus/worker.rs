@@ -11,7 +11,6 @@ use bee_block::{
         transaction::TransactionId,
         Payload,
     },
-    protocol::ProtocolParameters,
     semantic::ConflictReason,
     BlockId,
 };
@@ -26,6 +25,7 @@ use crate::{
     consensus::{metadata::WhiteFlagMetadata, state::validate_ledger_state, white_flag},
     error::Error,
     event::{BlockReferenced, LedgerUpdated, MilestoneConfirmed, OutputConsumed, OutputCreated, ReceiptCreated},
+    parameters::{unpack_protocol_parameters, ProtocolParametersManager, ProtocolParametersWorker},
     pruning::{condition::should_prune, config::PruningConfig, prune},
     snapshot::{condition::should_snapshot, config::SnapshotConfig, worker::SnapshotWorker},
     storage::{self, StorageBackend},
@@ -76,7 +76,7 @@ async fn confirm<N: Node>(
     block_id: BlockId,
     ledger_index: &mut LedgerIndex,
     receipt_migrated_at: &mut MilestoneIndex,
-    token_supply: u64,
+    protocol_parameters: &ProtocolParametersManager,
 ) -> Result<(), Error>
 where
     N::Backend: StorageBackend,
@@ -95,6 +95,8 @@ where
         ));
     }
 
+    let token_supply = protocol_parameters.current().token_supply();
+
     let mut metadata = WhiteFlagMetadata::new(
         milestone.essence().index(),
         milestone.essence().timestamp(),
@@ -165,17 +167,51 @@ where
         None
     };
 
+    let parameters = milestone.essence().options().parameters();
+
+    if let Some(parameters) = parameters {
+        if parameters.target_milestone_index() <= milestone.essence().index() {
+            return Err(Error::PastProtocolParametersTarget(
+                milestone.essence().index(),
+                parameters.target_milestone_index(),
+            ));
+        }
+
+        unpack_protocol_parameters(parameters)?;
+    }
+
     storage::apply_milestone(
         storage,
         metadata.milestone_index,
         &metadata.created_outputs,
         &metadata.consumed_outputs,
         &migration,
+        parameters,
     )?;
 
     *ledger_index = LedgerIndex(milestone.essence().index());
     tangle.update_confirmed_milestone_index(milestone.essence().index());
 
+    if let Some(parameters) = parameters {
+        info!(
+            "Milestone {} announced protocol parameters with version {} for milestone {}.",
+            milestone.essence().index(),
+            parameters.protocol_version(),
+            parameters.target_milestone_index()
+        );
+        protocol_parameters.announce(parameters.clone());
+    }
+
+    if let Some(parameters) = protocol_parameters.activate(milestone.essence().index())? {
+        info!(
+            "Activated protocol parameters with version {} at milestone {}: min PoW score {}, token supply {}.",
+            parameters.protocol_version(),
+            milestone.essence().index(),
+            parameters.min_pow_score(),
+            parameters.token_supply()
+        );
+    }
+
     for block_id in metadata.excluded_no_transaction_blocks.iter() {
         tangle.update_metadata(block_id, |block_metadata| {
             block_metadata.set_conflict(ConflictReason::None);
@@ -263,21 +299,23 @@ where
     type Error = Error;
 
     fn dependencies() -> &'static [TypeId] {
-        vec![TypeId::of::<TangleWorker>(), TypeId::of::<SnapshotWorker>()].leak()
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<SnapshotWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
+        ]
+        .leak()
     }
 
     async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
-        // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-        // The node can't work properly with this.
-        // @thibault-martinez.
-        let protocol_parameters = ProtocolParameters::default();
         let (snapshot_config, pruning_config) = config;
         let (tx, rx) = mpsc::unbounded_channel();
         let tangle = node.resource::<Tangle<N::Backend>>();
         let storage = node.storage();
         let bus = node.bus();
+        let protocol_parameters = node.resource::<ProtocolParametersManager>();
 
-        validate_ledger_state(&*storage, protocol_parameters.token_supply())?;
+        validate_ledger_state(&*storage, protocol_parameters.current().token_supply())?;
 
         let bmd = tangle.config().below_max_depth();
 
@@ -325,7 +363,7 @@ where
                             block_id,
                             &mut ledger_index,
                             &mut receipt_migrated_at,
-                            protocol_parameters.token_supply(),
+                            &protocol_parameters,
                         )
                         .await
                         {
@@ -70,6 +70,12 @@ pub enum Error {
     StorageDepositReturnOverflow,
     #[error("previous milestone not found in the past cone")]
     PreviousMilestoneNotFound,
+    #[error("invalid protocol parameters targeting milestone {0}")]
+    InvalidProtocolParameters(MilestoneIndex),
+    #[error("protocol version mismatch: announced {0} != parameters {1}")]
+    ProtocolVersionMismatch(u8, u8),
+    #[error("protocol parameters announced by milestone {0} target past milestone {1}")]
+    PastProtocolParametersTarget(MilestoneIndex, MilestoneIndex),
 }
 
 impl<E: Into<Error>> From<UnpackError<E, std::io::Error>> for Error {
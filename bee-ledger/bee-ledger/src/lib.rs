@@ -9,16 +9,19 @@
 pub mod consensus;
 pub mod error;
 pub mod event;
+pub mod parameters;
 pub mod pruning;
 pub mod snapshot;
 pub mod storage;
 
+use bee_block::protocol::ProtocolParameters;
 pub use bee_ledger_types as types;
 use bee_runtime::node::{Node, NodeBuilder};
 
 pub use self::storage::StorageBackend;
 use self::{
     consensus::ConsensusWorker,
+    parameters::ProtocolParametersWorker,
     pruning::config::PruningConfig,
     snapshot::{config::SnapshotConfig, worker::SnapshotWorker},
 };
@@ -27,6 +30,7 @@ use self::{
 pub fn init<N>(
     node_builder: N::Builder,
     network_id: u64,
+    protocol_parameters: ProtocolParameters,
     snapshot_config: SnapshotConfig,
     pruning_config: PruningConfig,
 ) -> N::Builder
@@ -35,6 +39,7 @@ where
     N::Backend: StorageBackend,
 {
     node_builder
-        .with_worker_cfg::<SnapshotWorker>((network_id, snapshot_config.clone()))
+        .with_worker_cfg::<SnapshotWorker>((network_id, protocol_parameters.clone(), snapshot_config.clone()))
+        .with_worker_cfg::<ProtocolParametersWorker>(protocol_parameters)
         .with_worker_cfg::<ConsensusWorker>((snapshot_config, pruning_config))
 }
@@ -0,0 +1,152 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module tracking the protocol parameters announced by milestones.
+
+pub(crate) mod worker;
+
+use bee_block::{
+    payload::milestone::{MilestoneIndex, ParametersMilestoneOption},
+    protocol::ProtocolParameters,
+};
+use packable::PackableExt;
+use parking_lot::RwLock;
+
+pub use self::worker::ProtocolParametersWorker;
+use crate::error::Error;
+
+struct ProtocolParametersManagerInner {
+    current: ProtocolParameters,
+    // Sorted by target milestone index.
+    pending: Vec<ParametersMilestoneOption>,
+}
+
+/// Keeps track of the active protocol parameters and of the ones announced to become active at a later milestone.
+pub struct ProtocolParametersManager {
+    inner: RwLock<ProtocolParametersManagerInner>,
+}
+
+impl ProtocolParametersManager {
+    /// Creates a new [`ProtocolParametersManager`] from the active and the announced protocol parameters.
+    pub fn new(current: ProtocolParameters, mut pending: Vec<ParametersMilestoneOption>) -> Self {
+        pending.sort_by_key(ParametersMilestoneOption::target_milestone_index);
+
+        Self {
+            inner: RwLock::new(ProtocolParametersManagerInner { current, pending }),
+        }
+    }
+
+    /// Returns the currently active protocol parameters.
+    pub fn current(&self) -> ProtocolParameters {
+        self.inner.read().current.clone()
+    }
+
+    /// Returns the announced protocol parameters that are not active yet, sorted by target milestone index.
+    pub fn pending(&self) -> Vec<ParametersMilestoneOption> {
+        self.inner.read().pending.clone()
+    }
+
+    /// Registers protocol parameters announced by a milestone, replacing any previous announcement with the same
+    /// target milestone index.
+    pub(crate) fn announce(&self, parameters: ParametersMilestoneOption) {
+        let mut inner = self.inner.write();
+
+        match inner
+            .pending
+            .binary_search_by_key(&parameters.target_milestone_index(), |p| p.target_milestone_index())
+        {
+            Ok(i) => inner.pending[i] = parameters,
+            Err(i) => inner.pending.insert(i, parameters),
+        }
+    }
+
+    /// Activates all the protocol parameters targeting a milestone index up to the given one, and returns the last
+    /// activated ones, if any.
+    pub fn activate(&self, index: MilestoneIndex) -> Result<Option<ProtocolParameters>, Error> {
+        let mut inner = self.inner.write();
+        let mut activated = None;
+
+        while matches!(inner.pending.first(), Some(parameters) if parameters.target_milestone_index() <= index) {
+            let parameters = inner.pending.remove(0);
+            let protocol_parameters = unpack_protocol_parameters(&parameters)?;
+
+            inner.current = protocol_parameters.clone();
+            activated = Some(protocol_parameters);
+        }
+
+        Ok(activated)
+    }
+}
+
+/// Unpacks and verifies the protocol parameters carried by a parameters milestone option.
+pub(crate) fn unpack_protocol_parameters(parameters: &ParametersMilestoneOption) -> Result<ProtocolParameters, Error> {
+    let protocol_parameters = ProtocolParameters::unpack_verified(parameters.binary_parameters(), &())
+        .map_err(|_| Error::InvalidProtocolParameters(parameters.target_milestone_index()))?;
+
+    if protocol_parameters.protocol_version() != parameters.protocol_version() {
+        return Err(Error::ProtocolVersionMismatch(
+            parameters.protocol_version(),
+            protocol_parameters.protocol_version(),
+        ));
+    }
+
+    Ok(protocol_parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::output::RentStructure;
+
+    use super::*;
+
+    fn parameters(target_milestone_index: u32, min_pow_score: u32) -> ParametersMilestoneOption {
+        let protocol_parameters = ProtocolParameters::new(
+            ProtocolParameters::default().protocol_version(),
+            String::from("testnet"),
+            String::from("rms"),
+            min_pow_score,
+            15,
+            RentStructure::default(),
+            1_813_620_509_061_365,
+        )
+        .unwrap();
+
+        ParametersMilestoneOption::new(
+            MilestoneIndex(target_milestone_index),
+            protocol_parameters.protocol_version(),
+            protocol_parameters.pack_to_vec(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn activate_due_parameters() {
+        let manager = ProtocolParametersManager::new(ProtocolParameters::default(), vec![parameters(10, 2000)]);
+
+        assert!(manager.activate(MilestoneIndex(9)).unwrap().is_none());
+        assert_eq!(manager.current(), ProtocolParameters::default());
+
+        assert_eq!(
+            manager.activate(MilestoneIndex(10)).unwrap().unwrap().min_pow_score(),
+            2000
+        );
+        assert_eq!(manager.current().min_pow_score(), 2000);
+        assert!(manager.pending().is_empty());
+    }
+
+    #[test]
+    fn activate_all_due_parameters_at_once() {
+        let manager = ProtocolParametersManager::new(
+            ProtocolParameters::default(),
+            vec![parameters(12, 3000), parameters(10, 2000), parameters(20, 4000)],
+        );
+
+        assert_eq!(
+            manager.activate(MilestoneIndex(15)).unwrap().unwrap().min_pow_score(),
+            3000
+        );
+        assert_eq!(manager.current().min_pow_score(), 3000);
+        assert_eq!(manager.pending().len(), 1);
+        assert_eq!(manager.pending()[0].target_milestone_index(), MilestoneIndex(20));
+    }
+}
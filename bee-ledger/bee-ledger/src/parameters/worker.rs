@@ -0,0 +1,71 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::any::TypeId;
+
+use async_trait::async_trait;
+use bee_block::{payload::milestone::MilestoneIndex, protocol::ProtocolParameters};
+use bee_runtime::{node::Node, worker::Worker};
+use log::info;
+
+use crate::{
+    error::Error,
+    parameters::{unpack_protocol_parameters, ProtocolParametersManager},
+    snapshot::worker::SnapshotWorker,
+    storage::{self, StorageBackend},
+};
+
+/// Worker registering the [`ProtocolParametersManager`] resource, configured with the protocol parameters that are in
+/// effect until a milestone activates other ones.
+pub struct ProtocolParametersWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for ProtocolParametersWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ProtocolParameters;
+    type Error = Error;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<SnapshotWorker>()].leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let storage = node.storage();
+
+        // Unwrap is fine because ledger index was either inserted by the snapshot worker or already present in storage.
+        let ledger_index = MilestoneIndex(*storage::fetch_ledger_index(&*storage)?.unwrap());
+        let (applied, pending) = storage::fetch_protocol_parameters(&*storage)?
+            .into_iter()
+            .partition::<Vec<_>, _>(|parameters| parameters.target_milestone_index() <= ledger_index);
+
+        let current = match applied
+            .iter()
+            .max_by_key(|parameters| parameters.target_milestone_index())
+        {
+            Some(parameters) => {
+                info!(
+                    "Loaded protocol parameters activated at milestone {}.",
+                    parameters.target_milestone_index()
+                );
+                unpack_protocol_parameters(parameters)?
+            }
+            None => config,
+        };
+
+        if !pending.is_empty() {
+            info!("{} pending protocol parameters update(s).", pending.len());
+        }
+
+        node.register_resource(ProtocolParametersManager::new(current, pending));
+
+        Ok(Self {})
+    }
+
+    async fn stop(self, node: &mut N) -> Result<(), Self::Error> {
+        node.remove_resource::<ProtocolParametersManager>();
+
+        Ok(())
+    }
+}
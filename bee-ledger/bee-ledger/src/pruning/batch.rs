@@ -38,6 +38,7 @@ pub fn prune_confirmed_data<S: StorageBackend>(
     batch: &mut S::Batch,
     prune_index: MilestoneIndex,
     current_seps: &Seps,
+    invalidated: &mut Blocks,
 ) -> Result<(Seps, ConfirmedDataPruningMetrics), Error> {
     // We keep a list of already visited blocks.
     let mut visited = Blocks::with_capacity(512);
@@ -98,6 +99,7 @@ pub fn prune_confirmed_data<S: StorageBackend>(
         let parents = block.parents();
         for parent_id in parents.iter() {
             prune_edge(storage, batch, &(*parent_id, block_id))?;
+            invalidated.insert(*parent_id);
             metrics.prunable_edges += 1;
         }
 
@@ -109,6 +111,7 @@ pub fn prune_confirmed_data<S: StorageBackend>(
 
         // Delete its associated data.
         prune_block_and_metadata(storage, batch, &block_id)?;
+        invalidated.insert(block_id);
 
         // ---
         // Everything that follows is required to decide whether this block's id should be kept as a solid entry
@@ -206,6 +209,7 @@ pub fn prune_unconfirmed_data<S: StorageBackend>(
     storage: &S,
     batch: &mut S::Batch,
     prune_index: MilestoneIndex,
+    invalidated: &mut Blocks,
 ) -> Result<UnconfirmedDataPruningMetrics, Error> {
     let mut metrics = UnconfirmedDataPruningMetrics::default();
 
@@ -284,12 +288,14 @@ pub fn prune_unconfirmed_data<S: StorageBackend>(
 
                 // Add block data to the delete batch.
                 prune_block_and_metadata(storage, batch, unconf_block_id)?;
+                invalidated.insert(*unconf_block_id);
 
                 log::trace!("Pruned unconfirmed block {} at {}.", unconf_block_id, prune_index);
 
                 // Add prunable edges to the delete batch.
                 for parent in parents.iter() {
                     prune_edge(storage, batch, &(*parent, *unconf_block_id))?;
+                    invalidated.insert(*parent);
 
                     metrics.prunable_edges += 1;
                 }
@@ -15,7 +15,7 @@ use log::{debug, info};
 use crate::{
     event::PrunedIndex,
     pruning::{
-        batch,
+        batch::{self, Blocks},
         config::PruningConfig,
         error::Error,
         metrics::{PruningMetrics, Timings},
@@ -73,11 +73,14 @@ pub async fn prune<S: StorageBackend>(
         // Start a batch to make changes to the storage in a single atomic step.
         let mut batch = S::batch_begin();
 
+        // The blocks whose data or children are changed by the batch and must be dropped from the tangle caches.
+        let mut invalidated = Blocks::with_capacity(512);
+
         // Add confirmed data to the delete batch.
         // NOTE: This is the most costly thing during pruning, because it has to perform a past-cone traversal.
         let batch_confirmed_data = Instant::now();
         let (mut new_seps, confirmed_data_metrics) =
-            batch::prune_confirmed_data(tangle, storage, &mut batch, index, &curr_seps)?;
+            batch::prune_confirmed_data(tangle, storage, &mut batch, index, &curr_seps, &mut invalidated)?;
         timings.batch_confirmed_data = batch_confirmed_data.elapsed();
 
         metrics.new_seps = new_seps.len();
@@ -134,7 +137,7 @@ pub async fn prune<S: StorageBackend>(
 
         // Add unconfirmed data to the delete batch.
         let batch_unconfirmed_data = Instant::now();
-        let unconfirmed_data_metrics = batch::prune_unconfirmed_data(storage, &mut batch, index)?;
+        let unconfirmed_data_metrics = batch::prune_unconfirmed_data(storage, &mut batch, index, &mut invalidated)?;
         timings.batch_unconfirmed_data = batch_unconfirmed_data.elapsed();
 
         metrics.blocks += unconfirmed_data_metrics.prunable_blocks;
@@ -157,6 +160,8 @@ pub async fn prune<S: StorageBackend>(
             .map_err(|e| Error::Storage(Box::new(e)))?;
         timings.batch_commit = batch_commit.elapsed();
 
+        tangle.invalidate(&invalidated);
+
         // Update the pruning index.
         tangle.update_pruning_index(index);
 
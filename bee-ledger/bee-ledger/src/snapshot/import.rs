@@ -117,9 +117,23 @@ fn import_milestone_diffs<U: Unpacker<Error = std::io::Error>, B: StorageBackend
         };
 
         if index == MilestoneIndex(ledger_index + 1) {
-            apply_milestone(storage, index, diff.created(), &consumed, &migration)?;
+            apply_milestone(
+                storage,
+                index,
+                diff.created(),
+                &consumed,
+                &migration,
+                diff.milestone().essence().options().parameters(),
+            )?;
         } else if index == MilestoneIndex(ledger_index) {
-            rollback_milestone(storage, index, diff.created(), &consumed, &migration)?;
+            rollback_milestone(
+                storage,
+                index,
+                diff.created(),
+                &consumed,
+                &migration,
+                diff.milestone().essence().options().parameters(),
+            )?;
         } else {
             return Err(Error::Snapshot(SnapshotError::UnexpectedMilestoneDiffIndex(index)));
         }
@@ -285,16 +299,12 @@ fn import_delta_snapshot<B: StorageBackend>(
 pub(crate) async fn import_snapshots<B: StorageBackend>(
     storage: &B,
     network_id: u64,
+    protocol_parameters: &ProtocolParameters,
     config: &SnapshotConfig,
 ) -> Result<(), Error> {
     let full_exists = config.full_path().exists();
     let delta_exists = config.delta_path().map_or(false, Path::exists);
 
-    // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-    // The node can't work properly with this.
-    // @thibault-martinez.
-    let protocol_parameters = ProtocolParameters::default();
-
     if !full_exists && delta_exists {
         return Err(Error::Snapshot(SnapshotError::OnlyDeltaSnapshotFileExists));
     } else if !full_exists && !delta_exists {
@@ -307,11 +317,11 @@ pub(crate) async fn import_snapshots<B: StorageBackend>(
         .await?;
     }
 
-    import_full_snapshot(storage, config.full_path(), network_id, &protocol_parameters)?;
+    import_full_snapshot(storage, config.full_path(), network_id, protocol_parameters)?;
 
     if let Some(delta_path) = config.delta_path() {
         if delta_path.exists() {
-            import_delta_snapshot(storage, delta_path, network_id, &protocol_parameters)?;
+            import_delta_snapshot(storage, delta_path, network_id, protocol_parameters)?;
         }
     }
 
@@ -5,7 +5,11 @@
 
 use std::collections::HashMap;
 
-use bee_block::{output::OutputId, payload::milestone::MilestoneIndex, Block, BlockId};
+use bee_block::{
+    output::OutputId,
+    payload::milestone::{MilestoneIndex, ParametersMilestoneOption},
+    Block, BlockId,
+};
 use bee_storage::{
     access::{AsIterator, Batch, BatchBuilder, Exist, Fetch, Insert, Truncate},
     backend,
@@ -40,6 +44,7 @@ pub trait StorageBackend:
     + Batch<BlockId, Block>
     + Batch<BlockId, BlockMetadata>
     + Batch<MilestoneIndex, MilestoneMetadata>
+    + Batch<MilestoneIndex, ParametersMilestoneOption>
     + Exist<Unspent, ()>
     + Fetch<(), SnapshotInfo>
     + Fetch<OutputId, CreatedOutput>
@@ -55,6 +60,7 @@ pub trait StorageBackend:
     + Truncate<SolidEntryPoint, MilestoneIndex>
     + for<'a> AsIterator<'a, Unspent, ()>
     + for<'a> AsIterator<'a, SolidEntryPoint, MilestoneIndex>
+    + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
     + bee_tangle::storage::StorageBackend
 {
 }
@@ -75,6 +81,7 @@ impl<T> StorageBackend for T where
         + Batch<BlockId, Block>
         + Batch<BlockId, BlockMetadata>
         + Batch<MilestoneIndex, MilestoneMetadata>
+        + Batch<MilestoneIndex, ParametersMilestoneOption>
         + Exist<Unspent, ()>
         + Fetch<(), SnapshotInfo>
         + Fetch<OutputId, CreatedOutput>
@@ -90,6 +97,7 @@ impl<T> StorageBackend for T where
         + Truncate<SolidEntryPoint, MilestoneIndex>
         + for<'a> AsIterator<'a, Unspent, ()>
         + for<'a> AsIterator<'a, SolidEntryPoint, MilestoneIndex>
+        + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
         + bee_tangle::storage::StorageBackend
 {
 }
@@ -159,11 +167,16 @@ pub(crate) fn apply_milestone<B: StorageBackend>(
     created_outputs: &HashMap<OutputId, CreatedOutput>,
     consumed_outputs: &HashMap<OutputId, (CreatedOutput, ConsumedOutput)>,
     migration: &Option<Migration>,
+    parameters: Option<&ParametersMilestoneOption>,
 ) -> Result<(), Error> {
     let mut batch = B::batch_begin();
 
     insert_ledger_index_batch(storage, &mut batch, &index.into())?;
 
+    if let Some(parameters) = parameters {
+        insert_protocol_parameters_batch(storage, &mut batch, parameters)?;
+    }
+
     let created_output_ids = created_outputs
         .iter()
         .map::<Result<_, Error>, _>(|(output_id, output)| {
@@ -213,11 +226,16 @@ pub(crate) fn rollback_milestone<B: StorageBackend>(
     created_outputs: &HashMap<OutputId, CreatedOutput>,
     consumed_outputs: &HashMap<OutputId, (CreatedOutput, ConsumedOutput)>,
     migration: &Option<Migration>,
+    parameters: Option<&ParametersMilestoneOption>,
 ) -> Result<(), Error> {
     let mut batch = B::batch_begin();
 
     insert_ledger_index_batch(storage, &mut batch, &((index - 1).into()))?;
 
+    if let Some(parameters) = parameters {
+        delete_protocol_parameters_batch(storage, &mut batch, parameters)?;
+    }
+
     for (output_id, created_output) in created_outputs.iter() {
         delete_created_output_batch(storage, &mut batch, output_id, created_output)?;
     }
@@ -284,6 +302,46 @@ pub(crate) fn delete_receipt_batch<B: StorageBackend>(
     .map_err(|e| Error::Storage(Box::new(e)))
 }
 
+pub(crate) fn insert_protocol_parameters_batch<B: StorageBackend>(
+    storage: &B,
+    batch: &mut <B as BatchBuilder>::Batch,
+    parameters: &ParametersMilestoneOption,
+) -> Result<(), Error> {
+    Batch::<MilestoneIndex, ParametersMilestoneOption>::batch_insert(
+        storage,
+        batch,
+        &parameters.target_milestone_index(),
+        parameters,
+    )
+    .map_err(|e| Error::Storage(Box::new(e)))
+}
+
+pub(crate) fn delete_protocol_parameters_batch<B: StorageBackend>(
+    storage: &B,
+    batch: &mut <B as BatchBuilder>::Batch,
+    parameters: &ParametersMilestoneOption,
+) -> Result<(), Error> {
+    Batch::<MilestoneIndex, ParametersMilestoneOption>::batch_delete(
+        storage,
+        batch,
+        &parameters.target_milestone_index(),
+    )
+    .map_err(|e| Error::Storage(Box::new(e)))
+}
+
+pub(crate) fn fetch_protocol_parameters<B: StorageBackend>(
+    storage: &B,
+) -> Result<Vec<ParametersMilestoneOption>, Error> {
+    AsIterator::<MilestoneIndex, ParametersMilestoneOption>::iter(storage)
+        .map_err(|e| Error::Storage(Box::new(e)))?
+        .map(|result| {
+            result
+                .map(|(_, parameters)| parameters)
+                .map_err(|e| Error::Storage(Box::new(e)))
+        })
+        .collect()
+}
+
 pub(crate) fn insert_snapshot_info<B: StorageBackend>(storage: &B, snapshot_info: &SnapshotInfo) -> Result<(), Error> {
     Insert::<(), SnapshotInfo>::insert(storage, &(), snapshot_info).map_err(|e| Error::Storage(Box::new(e)))
 }
@@ -9,7 +9,7 @@ use bee_autopeering::{
     config::AutopeeringConfigBuilder, init, stores::InMemoryPeerStore, AutopeeringConfig, Event, Local,
     NeighborValidator, Peer, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
 };
-use libp2p_core::identity::ed25519::Keypair;
+use libp2p_identity::ed25519::Keypair;
 use log::LevelFilter;
 use serde_json::Value;
 use tokio::signal::ctrl_c;
@@ -55,7 +55,7 @@ async fn main() {
 
     // Set up a local peer, that provides the Autopeering service.
     let mut keypair = hex::decode(BS16_ED25519_PRIVATE_KEY).expect("error decoding keypair");
-    let local = Local::from_keypair(Keypair::decode(&mut keypair).expect("error decoding keypair"))
+    let local = Local::from_keypair(Keypair::try_from_bytes(&mut keypair).expect("error decoding keypair"))
         .expect("error creating local");
 
     local.add_service(
@@ -83,17 +83,18 @@ async fn main() {
     let term_signal = ctrl_c();
 
     // Initialize the Autopeering service.
-    let mut event_rx = bee_autopeering::init::<InMemoryPeerStore, _, _, GossipNeighborValidator>(
-        config.clone(),
-        version,
-        network_name,
-        local,
-        peer_store_config,
-        term_signal,
-        neighbor_validator,
-    )
-    .await
-    .expect("initializing autopeering system failed");
+    let (mut event_rx, _command_tx, _packet_metrics) =
+        bee_autopeering::init::<InMemoryPeerStore, _, _, GossipNeighborValidator>(
+            config.clone(),
+            version,
+            network_name,
+            local,
+            peer_store_config,
+            term_signal,
+            neighbor_validator,
+        )
+        .await
+        .expect("initializing autopeering system failed");
 
     // Print to what IP addresses the entry nodes resolved to.
     print_resolved_entry_nodes(config).await;
@@ -0,0 +1,35 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands sent by the user.
+
+use tokio::sync::mpsc;
+
+use crate::{peer::PeerId, peering::neighbor::Score};
+
+/// Autopeering related commands.
+#[derive(Debug)]
+pub enum Command {
+    /// Sets the score of a peer, reflecting the quality observed while it was a neighbor.
+    ///
+    /// The lower the score, the less likely the peer is selected as a neighbor again.
+    SetScore {
+        /// The identity of the scored peer.
+        peer_id: PeerId,
+        /// The score of the peer.
+        score: Score,
+    },
+    /// Drops a neighbor, e.g. because it misbehaved.
+    DropNeighbor {
+        /// The identity of the neighbor to drop.
+        peer_id: PeerId,
+    },
+}
+
+/// Allows sending autopeering related commands.
+pub type CommandTx = mpsc::UnboundedSender<Command>;
+pub(crate) type CommandRx = mpsc::UnboundedReceiver<Command>;
+
+pub(crate) fn command_chan() -> (CommandTx, CommandRx) {
+    mpsc::unbounded_channel::<Command>()
+}
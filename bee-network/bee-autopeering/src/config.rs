@@ -15,6 +15,7 @@
 //!          "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
 //!     ],
 //!     "entryNodesPreferIPv6": true,
+//!     "peerExpirationDays": 7
 //! }
 //! ```
 //!
@@ -30,23 +31,26 @@
 //!     "/dns/entry-mainnet.tanglebay.com/udp/14626/autopeering/iot4By1FD4pFLrGJ6AAe7YEeSu9RbW9xnPUmxMdQenC"
 //! ]
 //! entry_nodes_prefer_ipv6 = true
+//! peer_expiration_days = 7
 //! ```
 
 use std::{
     fmt::Debug,
     net::SocketAddr,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use serde::Deserialize;
 
-use crate::multiaddr::AutopeeringMultiaddr;
+use crate::{multiaddr::AutopeeringMultiaddr, time::DAY};
 
 const ENABLED_DEFAULT: bool = false;
 const ENTRY_NODES_PREFER_IPV6_DEFAULT: bool = false;
 const RUN_AS_ENTRY_NODE_DEFAULT: bool = false;
 const DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT: bool = false;
 const PEER_STORAGE_PATH_DEFAULT: &str = "./storage/mainnet/peers";
+const PEER_EXPIRATION_DAYS_DEFAULT: u64 = 7;
 
 /// The autopeering config.
 #[cfg_attr(test, derive(Eq, PartialEq))]
@@ -60,6 +64,7 @@ pub struct AutopeeringConfig {
     run_as_entry_node: bool,
     drop_neighbors_on_salt_update: bool,
     peer_storage_path: PathBuf,
+    peer_expiration_days: u64,
 }
 
 impl AutopeeringConfig {
@@ -107,6 +112,15 @@ impl AutopeeringConfig {
     pub fn peer_storage_path(&self) -> &Path {
         &self.peer_storage_path
     }
+
+    /// The number of days after which stored peers that weren't seen anymore are deleted.
+    pub fn peer_expiration_days(&self) -> u64 {
+        self.peer_expiration_days
+    }
+
+    pub(crate) fn peer_expiration(&self) -> Duration {
+        Duration::from_secs(self.peer_expiration_days * DAY)
+    }
 }
 
 // Note: In case someone wonders why we use `Option<bool>`: Although serde actually provides a way to allow for the
@@ -140,6 +154,9 @@ pub struct AutopeeringConfigBuilder {
     /// The peer storage path.
     #[serde(alias = "peerStoragePath")]
     pub peer_storage_path: Option<PathBuf>,
+    /// The number of days after which stored peers that weren't seen anymore are deleted.
+    #[serde(alias = "peerExpirationDays")]
+    pub peer_expiration_days: Option<u64>,
 }
 
 impl AutopeeringConfigBuilder {
@@ -165,6 +182,7 @@ impl AutopeeringConfigBuilder {
             peer_storage_path: self
                 .peer_storage_path
                 .unwrap_or_else(|| PEER_STORAGE_PATH_DEFAULT.into()),
+            peer_expiration_days: self.peer_expiration_days.unwrap_or(PEER_EXPIRATION_DAYS_DEFAULT),
         }
     }
 }
@@ -180,6 +198,7 @@ impl Default for AutopeeringConfigBuilder {
             run_as_entry_node: Some(RUN_AS_ENTRY_NODE_DEFAULT),
             drop_neighbors_on_salt_update: Some(DROP_NEIGHBORS_ON_SALT_UPDATE_DEFAULT),
             peer_storage_path: Some(PEER_STORAGE_PATH_DEFAULT.into()),
+            peer_expiration_days: Some(PEER_EXPIRATION_DAYS_DEFAULT),
         }
     }
 }
@@ -218,7 +237,8 @@ mod tests {
             "entryNodesPreferIPv6": true,
             "runAsEntryNode": false,
             "dropNeighborsOnSaltUpdate": false,
-            "peerStoragePath": "./storage/mainnet/peers"
+            "peerStoragePath": "./storage/mainnet/peers",
+            "peerExpirationDays": 3
         }"#;
 
         serde_json::from_str::<AutopeeringConfigBuilder>(config_json_str)
@@ -240,6 +260,7 @@ mod tests {
             run_as_entry_node = false
             drop_neighbors_on_salt_update = false
             peer_storage_path = "./storage/mainnet/peers"
+            peer_expiration_days = 3
         "#;
 
         toml::from_str::<AutopeeringConfigBuilder>(toml_config_str)
@@ -260,7 +281,8 @@ mod tests {
             entry_nodes_prefer_ipv6: true,
             run_as_entry_node: false,
             drop_neighbors_on_salt_update: false,
-            peer_storage_path: "./storage/mainnet/peers".into()
+            peer_storage_path: "./storage/mainnet/peers".into(),
+            peer_expiration_days: 3,
         }
     }
 
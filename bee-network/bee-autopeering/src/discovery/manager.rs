@@ -1,7 +1,11 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use rand::{seq::index, Rng as _};
 
@@ -14,6 +18,7 @@ use crate::{
         services::{ServiceMap, ServicePort, ServiceProtocol, AUTOPEERING_SERVICE_NAME},
         Local,
     },
+    metrics::PacketMetrics,
     multiaddr::{AddressKind, AutopeeringMultiaddr},
     packet::{IncomingPacket, MessageType, OutgoingPacket},
     peer::{
@@ -43,6 +48,7 @@ const MIN_VERIFIED_IN_RESPONSE: usize = 1;
 pub(crate) struct DiscoveryManagerConfig {
     pub(crate) entry_nodes: Vec<AutopeeringMultiaddr>,
     pub(crate) entry_nodes_prefer_ipv6: bool,
+    pub(crate) peer_expiration: Duration,
     pub(crate) version: u32,
     pub(crate) network_id: u32,
 }
@@ -52,6 +58,7 @@ impl DiscoveryManagerConfig {
         Self {
             entry_nodes: config.entry_nodes().to_vec(),
             entry_nodes_prefer_ipv6: config.entry_nodes_prefer_ipv6(),
+            peer_expiration: config.peer_expiration(),
             version,
             network_id,
         }
@@ -77,6 +84,8 @@ pub(crate) struct DiscoveryManager<S: PeerStore> {
     active_peers: ActivePeersList,
     // The list of replacement peers.
     replacements: ReplacementPeersList,
+    // Counts the rejected packets.
+    metrics: PacketMetrics,
 }
 
 impl<S: PeerStore + 'static> DiscoveryManager<S> {
@@ -91,6 +100,7 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
         active_peers: ActivePeersList,
         replacements: ReplacementPeersList,
         event_tx: EventTx,
+        metrics: PacketMetrics,
     ) -> Self {
         Self {
             config,
@@ -102,6 +112,7 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
             entry_peers,
             active_peers,
             replacements,
+            metrics,
         }
     }
 
@@ -116,18 +127,25 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
             entry_peers,
             active_peers,
             replacements,
+            metrics,
         } = self;
 
         let DiscoveryManagerConfig {
             mut entry_nodes,
             entry_nodes_prefer_ipv6,
+            peer_expiration,
             version,
             network_id,
         } = config;
 
         let ServerSocket { server_rx, server_tx } = socket;
 
-        // Add previously discovered peers from the peer store.
+        // Remove peers that haven't been seen for too long, then add the previously discovered ones from the peer store.
+        let num_expired = peer_store.delete_expired(peer_expiration)?;
+        if num_expired > 0 {
+            log::debug!("Deleted {} expired peer/s from the peer store.", num_expired);
+        }
+
         if add_peers_from_store(&peer_store, &active_peers, &replacements)? == 0 {
             // Add entry peers from the config **only** if we start with an empty peer store,
             // otherwise entry nodes would be contacted each time a node starts.
@@ -147,15 +165,18 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
         }
 
         let discovery_recv_handler = DiscoveryRecvHandler {
-            server_tx: server_tx.clone(),
             server_rx,
-            local: local.clone(),
-            version,
-            network_id,
-            request_mngr: request_mngr.clone(),
-            event_tx,
-            active_peers: active_peers.clone(),
-            replacements,
+            packet_handler: DiscoveryPacketHandler {
+                server_tx: server_tx.clone(),
+                local: local.clone(),
+                version,
+                network_id,
+                request_mngr: request_mngr.clone(),
+                event_tx,
+                active_peers: active_peers.clone(),
+                replacements,
+                metrics,
+            },
         };
 
         task_mngr.run::<DiscoveryRecvHandler>(discovery_recv_handler);
@@ -166,14 +187,7 @@ impl<S: PeerStore + 'static> DiscoveryManager<S> {
 
 struct DiscoveryRecvHandler {
     server_rx: ServerRx,
-    server_tx: ServerTx,
-    local: Local,
-    version: u32,
-    network_id: u32,
-    request_mngr: RequestManager,
-    event_tx: EventTx,
-    active_peers: ActivePeersList,
-    replacements: ReplacementPeersList,
+    packet_handler: DiscoveryPacketHandler,
 }
 
 #[async_trait::async_trait]
@@ -186,123 +200,147 @@ impl Runnable for DiscoveryRecvHandler {
     async fn run(self, mut shutdown_rx: Self::ShutdownSignal) {
         let DiscoveryRecvHandler {
             mut server_rx,
-            server_tx,
-            local,
-            version,
-            network_id,
-            request_mngr,
-            event_tx,
-            active_peers,
-            replacements,
-            ..
+            packet_handler,
         } = self;
 
         // Event loop.
-        'recv: loop {
+        loop {
             tokio::select! {
                 _ = &mut shutdown_rx => {
                     break;
                 }
                 p = server_rx.recv() => {
-                    if let Some(IncomingPacket {
-                        msg_type,
-                        msg_bytes,
-                        peer_addr,
-                        peer_id,
-                    }) = p
-                    {
-                        let ctx = RecvContext {
-                            peer_id: &peer_id,
-                            msg_bytes: &msg_bytes,
-                            server_tx: &server_tx,
-                            local: &local,
-                            request_mngr: &request_mngr,
-                            peer_addr,
-                            event_tx: &event_tx,
-                            active_peers: &active_peers,
-                            replacements: &replacements,
-                        };
-
-                        match msg_type {
-                            MessageType::VerificationRequest => {
-                                let verif_req = if let Ok(verif_req) = VerificationRequest::from_protobuf(&msg_bytes) {
-                                    verif_req
-                                } else {
-                                    log::debug!("Error decoding verification request from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                if let Err(e) = validate_verification_request(&verif_req, version, network_id) {
-                                    log::debug!("Received invalid verification request from {}. Reason: {}", &peer_id, e);
-                                    continue 'recv;
-                                } else {
-                                    log::trace!("Received valid verification request from {}.", &peer_id);
-
-                                    handle_verification_request(verif_req, ctx);
-                                }
-                            }
-                            MessageType::VerificationResponse => {
-                                let verif_res = if let Ok(verif_res) = VerificationResponse::from_protobuf(&msg_bytes) {
-                                    verif_res
-                                } else {
-                                    log::debug!("Error decoding verification response from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                match validate_verification_response(&verif_res, &request_mngr, &peer_id, peer_addr) {
-                                    Ok(verif_reqval) => {
-                                        log::trace!("Received valid verification response from {}.", &peer_id);
-
-                                        handle_verification_response(verif_res, verif_reqval, ctx);
-                                    }
-                                    Err(e) => {
-                                        log::debug!("Received invalid verification response from {}. Reason: {:?}", &peer_id, e);
-                                        continue 'recv;
-                                    }
-                                }
-                            }
-                            MessageType::DiscoveryRequest => {
-                                let disc_req = if let Ok(disc_req) = DiscoveryRequest::from_protobuf(&msg_bytes) {
-                                    disc_req
-                                } else {
-                                    log::debug!("Error decoding discovery request from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                if let Err(e) = validate_discovery_request(&disc_req) {
-                                    log::debug!("Received invalid discovery request from {}. Reason: {:?}", &peer_id, e);
-                                    continue 'recv;
-                                } else {
-                                    log::trace!("Received valid discovery request from {}.", &peer_id);
-
-                                    handle_discovery_request(disc_req, ctx);
-                                }
-                            }
-                            MessageType::DiscoveryResponse => {
-                                let disc_res = if let Ok(disc_res) = DiscoveryResponse::from_protobuf(&msg_bytes) {
-                                    disc_res
-                                } else {
-                                    log::debug!("Error decoding discovery response from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                match validate_discovery_response(&disc_res, &request_mngr, &peer_id) {
-                                    Ok(disc_reqval) => {
-                                        log::trace!("Received valid discovery response from {}.", &peer_id);
-
-                                        handle_discovery_response(disc_res, disc_reqval, ctx);
-                                    }
-                                    Err(e) => {
-                                        log::debug!("Received invalid discovery response from {}. Reason: {:?}", &peer_id, e);
-                                        continue 'recv;
-                                    }
-                                }
-                            }
-                            _ => log::debug!("Received unsupported discovery message type"),
-                        }
+                    if let Some(packet) = p {
+                        packet_handler.handle_packet(packet);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decodes, validates and handles discovery related packets.
+pub(crate) struct DiscoveryPacketHandler {
+    pub(crate) server_tx: ServerTx,
+    pub(crate) local: Local,
+    pub(crate) version: u32,
+    pub(crate) network_id: u32,
+    pub(crate) request_mngr: RequestManager,
+    pub(crate) event_tx: EventTx,
+    pub(crate) active_peers: ActivePeersList,
+    pub(crate) replacements: ReplacementPeersList,
+    pub(crate) metrics: PacketMetrics,
+}
+
+impl DiscoveryPacketHandler {
+    pub(crate) fn handle_packet(&self, packet: IncomingPacket) {
+        let IncomingPacket {
+            msg_type,
+            msg_bytes,
+            peer_addr,
+            peer_id,
+        } = packet;
+
+        let ctx = RecvContext {
+            peer_id: &peer_id,
+            msg_bytes: &msg_bytes,
+            server_tx: &self.server_tx,
+            local: &self.local,
+            request_mngr: &self.request_mngr,
+            peer_addr,
+            event_tx: &self.event_tx,
+            active_peers: &self.active_peers,
+            replacements: &self.replacements,
+        };
+
+        match msg_type {
+            MessageType::VerificationRequest => {
+                let verif_req = if let Ok(verif_req) = VerificationRequest::from_protobuf(&msg_bytes) {
+                    verif_req
+                } else {
+                    log::debug!("Error decoding verification request from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                if let Err(e) = validate_verification_request(&verif_req, self.version, self.network_id) {
+                    log::debug!("Received invalid verification request from {}. Reason: {}", &peer_id, e);
+                    self.metrics.rejected_messages_inc();
+                } else {
+                    log::trace!("Received valid verification request from {}.", &peer_id);
+
+                    handle_verification_request(verif_req, ctx);
+                }
+            }
+            MessageType::VerificationResponse => {
+                let verif_res = if let Ok(verif_res) = VerificationResponse::from_protobuf(&msg_bytes) {
+                    verif_res
+                } else {
+                    log::debug!("Error decoding verification response from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                match validate_verification_response(&verif_res, &self.request_mngr, &peer_id, peer_addr) {
+                    Ok(verif_reqval) => {
+                        log::trace!("Received valid verification response from {}.", &peer_id);
+
+                        handle_verification_response(verif_res, verif_reqval, ctx);
+                    }
+                    Err(e) => {
+                        log::debug!(
+                            "Received invalid verification response from {}. Reason: {:?}",
+                            &peer_id,
+                            e
+                        );
+                        self.metrics.rejected_messages_inc();
+                    }
+                }
+            }
+            MessageType::DiscoveryRequest => {
+                let disc_req = if let Ok(disc_req) = DiscoveryRequest::from_protobuf(&msg_bytes) {
+                    disc_req
+                } else {
+                    log::debug!("Error decoding discovery request from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                if let Err(e) = validate_discovery_request(&disc_req) {
+                    log::debug!("Received invalid discovery request from {}. Reason: {:?}", &peer_id, e);
+                    self.metrics.rejected_messages_inc();
+                } else {
+                    log::trace!("Received valid discovery request from {}.", &peer_id);
+
+                    handle_discovery_request(disc_req, ctx);
+                }
+            }
+            MessageType::DiscoveryResponse => {
+                let disc_res = match DiscoveryResponse::from_protobuf(&msg_bytes) {
+                    Ok(disc_res) => disc_res,
+                    Err(e) => {
+                        log::debug!("Error decoding discovery response from {}. Reason: {}", &peer_id, e);
+                        self.metrics.invalid_messages_inc();
+                        return;
+                    }
+                };
+
+                match validate_discovery_response(&disc_res, &self.request_mngr, &peer_id, peer_addr) {
+                    Ok(disc_reqval) => {
+                        log::trace!("Received valid discovery response from {}.", &peer_id);
+
+                        handle_discovery_response(disc_res, disc_reqval, ctx);
+                    }
+                    Err(e) => {
+                        log::debug!("Received invalid discovery response from {}. Reason: {:?}", &peer_id, e);
+                        self.metrics.rejected_messages_inc();
                     }
                 }
             }
+            _ => {
+                log::debug!("Received unsupported discovery message type");
+                self.metrics.invalid_messages_inc();
+            }
         }
     }
 }
@@ -521,6 +559,21 @@ pub(crate) enum ValidationError {
     // The service port must match with the detected port.
     #[error("service port mismatch; expected: {expected}, found: {found}")]
     ServicePortMismatch { expected: ServicePort, found: ServicePort },
+    // The source address must be one the peer can be reached at.
+    #[error("invalid source address: {0}")]
+    InvalidSourceAddress(SocketAddr),
+    // The response must not contain more peers than requested.
+    #[error("too many peers; max: {max}, received: {received}")]
+    TooManyPeers { max: usize, received: usize },
+    // The response must not contain a peer more than once.
+    #[error("duplicate peer")]
+    DuplicatePeer,
+    // The response must not contain the responding peer itself.
+    #[error("self-reference")]
+    SelfReference,
+    // The peers must have an address they can be reached at.
+    #[error("invalid peer address: {0}")]
+    InvalidPeerAddress(IpAddr),
 }
 
 fn validate_verification_request(
@@ -542,15 +595,19 @@ fn validate_verification_request(
         })
     } else if request::is_expired(verif_req.timestamp()) {
         Err(RequestExpired)
+    } else if !is_valid_source_addr(verif_req.source_addr()) {
+        // NOTE: the source address is the bind address of the peer, hence its ip address may well be unspecified,
+        // and can't be compared with the address the packet was received from.
+        Err(InvalidSourceAddress(verif_req.source_addr()))
     } else {
-        // NOTE: the validity of the transmitted source and target addresses is ensured through the
-        // `VerificationRequest` type.
-        // TODO: maybe add check whether the peer sent the correct source address in the packet.
-        // TODO: store own external IP address as perceived by the peer
         Ok(())
     }
 }
 
+fn is_valid_source_addr(addr: SocketAddr) -> bool {
+    addr.port() != 0 && !addr.ip().is_multicast()
+}
+
 fn validate_verification_response(
     verif_res: &VerificationResponse,
     request_mngr: &RequestManager,
@@ -596,15 +653,13 @@ fn validate_discovery_response(
     disc_res: &DiscoveryResponse,
     request_mngr: &RequestManager,
     peer_id: &PeerId,
+    peer_addr: SocketAddr,
 ) -> Result<RequestValue, ValidationError> {
     use ValidationError::*;
 
     if let Some(reqv) = request_mngr.remove_request::<DiscoveryRequest>(peer_id) {
         if disc_res.request_hash() == &reqv.request_hash[..] {
-            // TODO: consider performing some checks on the peers we received, for example:
-            // * does the peer have necessary services (autopeering, gossip, fpc, ...)
-            // * is the ip address valid (not a 0.0.0.0, etc)
-            // for peer in disc_res.peers() {}
+            validate_discovery_response_peers(disc_res.peers(), peer_id, peer_addr)?;
 
             Ok(reqv)
         } else {
@@ -615,6 +670,53 @@ fn validate_discovery_response(
     }
 }
 
+// NOTE: the local peer is not rejected, as it is likely part of the responder's active peers. It is skipped when
+// adding the peers instead.
+pub(crate) fn validate_discovery_response_peers(
+    peers: &[Peer],
+    peer_id: &PeerId,
+    peer_addr: SocketAddr,
+) -> Result<(), ValidationError> {
+    use ValidationError::*;
+
+    if peers.len() > MAX_PEERS_IN_RESPONSE {
+        return Err(TooManyPeers {
+            max: MAX_PEERS_IN_RESPONSE,
+            received: peers.len(),
+        });
+    }
+
+    let mut peer_ids = HashSet::with_capacity(peers.len());
+
+    for peer in peers {
+        if peer.peer_id() == peer_id {
+            return Err(SelfReference);
+        }
+        if !peer_ids.insert(peer.peer_id()) {
+            return Err(DuplicatePeer);
+        }
+        if !is_valid_peer_addr(peer.ip_address(), peer_addr.ip()) {
+            return Err(InvalidPeerAddress(peer.ip_address()));
+        }
+        match peer.services().get(AUTOPEERING_SERVICE_NAME) {
+            Some(autopeering_svc) if autopeering_svc.port() != 0 => {}
+            _ => return Err(NoAutopeeringService),
+        }
+    }
+
+    Ok(())
+}
+
+// Loopback addresses are only accepted from peers that are reached via loopback themselves.
+fn is_valid_peer_addr(addr: IpAddr, source_addr: IpAddr) -> bool {
+    let is_broadcast = matches!(addr, IpAddr::V4(addr) if addr.is_broadcast());
+
+    !addr.is_unspecified()
+        && !addr.is_multicast()
+        && !is_broadcast
+        && (!addr.is_loopback() || source_addr.is_loopback())
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
 // HANDLING
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -632,6 +734,11 @@ fn handle_verification_request(verif_req: VerificationRequest, ctx: RecvContext)
         ctx.local,
     );
 
+    // Only verified peers get a say about our external address.
+    if peer::is_verified(ctx.peer_id, ctx.active_peers) {
+        vote_external_addr(verif_req.target_addr(), &ctx);
+    }
+
     // Is this a known peer?
     if peer::is_known(ctx.peer_id, ctx.local, ctx.active_peers, ctx.replacements) {
         // Update verification request timestamp.
@@ -656,6 +763,8 @@ fn handle_verification_request(verif_req: VerificationRequest, ctx: RecvContext)
 fn handle_verification_response(verif_res: VerificationResponse, verif_reqval: RequestValue, ctx: RecvContext) {
     log::trace!("Handling verification response from {}.", ctx.peer_id);
 
+    vote_external_addr(verif_res.target_addr(), &ctx);
+
     if let Some(verified_count) = peer::set_front_and_update(ctx.peer_id, ctx.active_peers) {
         // If this is the first time the peer was verified:
         // * Update its services;
@@ -681,9 +790,23 @@ fn handle_verification_response(verif_res: VerificationResponse, verif_reqval: R
 
     // Send the response notification.
     if let Some(tx) = verif_reqval.response_tx {
+        // The requester may have stopped waiting for the response.
+        if tx.send(verif_res.to_protobuf().to_vec()).is_err() {
+            log::debug!("Verification response from {} arrived too late.", ctx.peer_id);
+        }
+    }
+}
+
+/// Records our external address as perceived by the peer, and announces when the majority of the peers agree on a
+/// different one.
+fn vote_external_addr(addr: IpAddr, ctx: &RecvContext) {
+    if let Some(address) = ctx.local.vote_external_addr(*ctx.peer_id, addr) {
+        log::info!("External address changed to {}.", address);
+
         // Panic: we don't allow channel send errors.
-        tx.send(verif_res.to_protobuf().to_vec())
-            .expect("error sending response signal");
+        ctx.event_tx
+            .send(Event::ExternalAddressChanged { address })
+            .expect("error publishing external-address-changed event");
     }
 }
 
@@ -725,19 +848,17 @@ fn handle_discovery_response(disc_res: DiscoveryResponse, disc_reqval: RequestVa
         }
     }
 
-    // Remember how many new peers were discovered thanks to that peer.
-    // Panic: we don't allow internal data inconsistencies.
-    ctx.active_peers
-        .write()
-        .find_mut(ctx.peer_id)
-        .expect("inconsistent active peers list")
-        .metrics_mut()
-        .set_last_new_peers(num_added);
+    // Remember how many new peers were discovered thanks to that peer, unless it was removed in the meantime.
+    if let Some(peer) = ctx.active_peers.write().find_mut(ctx.peer_id) {
+        peer.metrics_mut().set_last_new_peers(num_added);
+    }
 
     // Send the response notification.
     if let Some(tx) = disc_reqval.response_tx {
-        // Panic: we don't allow channel send errors.
-        tx.send(ctx.msg_bytes.to_vec()).expect("error sending response signal");
+        // The requester may have stopped waiting for the response.
+        if tx.send(ctx.msg_bytes.to_vec()).is_err() {
+            log::debug!("Discovery response from {} arrived too late.", ctx.peer_id);
+        }
     }
 }
 
@@ -1011,3 +1132,57 @@ pub(crate) fn get_verified_peers(active_peers: &ActivePeersList) -> Vec<ActivePe
 
     peers
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery_response_peers() {
+        let responder = Peer::new_test_peer(0);
+        let (local_addr, public_addr) = ("127.0.0.1:1337".parse().unwrap(), "1.2.3.4:1337".parse().unwrap());
+        let peers = (1..=MAX_PEERS_IN_RESPONSE as u8)
+            .map(Peer::new_test_peer)
+            .collect::<Vec<_>>();
+        let validate =
+            |peers: &[Peer], peer_addr| validate_discovery_response_peers(peers, responder.peer_id(), peer_addr);
+
+        assert!(validate(&peers, local_addr).is_ok());
+        // Loopback peers are only accepted from loopback responders.
+        assert!(matches!(
+            validate(&peers, public_addr),
+            Err(ValidationError::InvalidPeerAddress(_))
+        ));
+
+        let too_many = [&peers[..], &[Peer::new_test_peer(0)]].concat();
+        assert!(matches!(
+            validate(&too_many, local_addr),
+            Err(ValidationError::TooManyPeers { .. })
+        ));
+
+        let duplicate = [peers[0].clone(), peers[0].clone()];
+        assert!(matches!(
+            validate(&duplicate, local_addr),
+            Err(ValidationError::DuplicatePeer)
+        ));
+
+        let self_reference = [responder.clone()];
+        assert!(matches!(
+            validate(&self_reference, local_addr),
+            Err(ValidationError::SelfReference)
+        ));
+
+        let mut unspecified = Peer::new(IpAddr::from([0, 0, 0, 0]), *peers[0].public_key());
+        unspecified.add_service(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, 1337);
+        assert!(matches!(
+            validate(&[unspecified], local_addr),
+            Err(ValidationError::InvalidPeerAddress(_))
+        ));
+
+        let no_service = Peer::new(IpAddr::from([1, 2, 3, 5]), *peers[0].public_key());
+        assert!(matches!(
+            validate(&[no_service], public_addr),
+            Err(ValidationError::NoAutopeeringService)
+        ));
+    }
+}
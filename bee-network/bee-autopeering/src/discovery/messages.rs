@@ -49,6 +49,10 @@ impl VerificationRequest {
         self.source_addr
     }
 
+    pub(crate) fn target_addr(&self) -> IpAddr {
+        self.target_addr
+    }
+
     pub(crate) fn from_protobuf(bytes: &[u8]) -> Result<Self, Error> {
         let proto::Ping {
             version,
@@ -60,7 +64,7 @@ impl VerificationRequest {
         } = proto::Ping::decode(bytes)?;
 
         let ip_addr: IpAddr = src_addr.parse().map_err(Error::InvalidSourceIpAddress)?;
-        let port = src_port as u16;
+        let port = src_port.try_into().map_err(|_| Error::InvalidSourcePort(src_port))?;
 
         let source_addr = SocketAddr::new(ip_addr, port);
         let target_addr: IpAddr = dst_addr.parse().map_err(Error::InvalidTargetIpAddress)?;
@@ -132,6 +136,10 @@ impl VerificationResponse {
         &self.services
     }
 
+    pub(crate) fn target_addr(&self) -> IpAddr {
+        self.target_addr
+    }
+
     pub(crate) fn from_protobuf(bytes: &[u8]) -> Result<Self, Error> {
         let proto::Pong {
             req_hash,
@@ -243,15 +251,16 @@ impl DiscoveryResponse {
         &self.request_hash
     }
 
+    pub(crate) fn peers(&self) -> &[Peer] {
+        &self.peers
+    }
+
     pub(crate) fn from_protobuf(bytes: &[u8]) -> Result<Self, Error> {
         let proto::DiscoveryResponse { req_hash, peers } = proto::DiscoveryResponse::decode(bytes)?;
-        let peers = peers
-            .into_iter()
-            .filter_map(|p| proto::Peer::try_into(p).ok())
-            .collect();
+        let peers = peers.into_iter().map(proto::Peer::try_into).collect::<Result<_, _>>()?;
 
         Ok(Self {
-            request_hash: req_hash.try_into().expect("todo: error type"),
+            request_hash: req_hash.try_into().map_err(|_| Error::RestoreRequestHash)?,
             peers,
         })
     }
@@ -293,8 +302,12 @@ pub(crate) enum Error {
     MissingServices,
     #[error("invalid source ip address due to: {0}")]
     InvalidSourceIpAddress(AddrParseError),
+    #[error("invalid source port: {0}")]
+    InvalidSourcePort(u32),
     #[error("invalid target ip address due to: {0}")]
     InvalidTargetIpAddress(AddrParseError),
+    #[error("invalid peer: {0}")]
+    Peer(#[from] crate::peer::Error),
     #[error("invalid service description")]
     Service(#[from] crate::local::services::Error),
     #[error("{0}")]
@@ -3,7 +3,7 @@
 
 //! Events published to the user.
 
-use std::fmt;
+use std::{fmt, net::IpAddr};
 
 use tokio::sync::mpsc;
 
@@ -51,6 +51,11 @@ pub enum Event {
         /// The identity of the dropped peer.
         peer_id: PeerId,
     },
+    /// The majority of the verified peers perceive the local peer under a new external address.
+    ExternalAddressChanged {
+        /// The new external address.
+        address: IpAddr,
+    },
 }
 
 /// Exposes autopeering related events.
@@ -79,6 +84,7 @@ impl fmt::Display for Event {
             OutgoingPeering { peer, .. } => write!(f, "Peered: {} (outgoing).", peer.peer_id()),
             IncomingPeering { peer, .. } => write!(f, "Peered: {} (incoming).", peer.peer_id()),
             PeeringDropped { peer_id } => write!(f, "Dropped: {}.", peer_id),
+            ExternalAddressChanged { address } => write!(f, "External address changed: {}.", address),
         }
     }
 }
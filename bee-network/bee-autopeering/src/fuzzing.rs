@@ -0,0 +1,152 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Entry points for fuzzing the handling of received packets.
+
+use std::net::SocketAddr;
+
+use crypto::signatures::ed25519::SecretKey as PrivateKey;
+use tokio::sync::mpsc;
+
+use crate::{
+    discovery::{
+        manager::{self, DiscoveryPacketHandler},
+        messages::DiscoveryResponse,
+    },
+    event::{self, EventRx},
+    local::{
+        services::{ServiceProtocol, AUTOPEERING_SERVICE_NAME},
+        Local,
+    },
+    metrics::PacketMetrics,
+    packet::{IncomingPacket, OutgoingPacket},
+    peer::{
+        lists::{ActivePeersList, ReplacementPeersList},
+        PeerId,
+    },
+    peering::{
+        filter::NeighborFilter,
+        manager::{InboundNeighborhood, OutboundNeighborhood, PeeringPacketHandler},
+        NeighborValidator,
+    },
+    request::RequestManager,
+    server::{self, ServerTx},
+    Peer,
+};
+
+const VERSION: u32 = 1;
+const NETWORK_ID: u32 = 1;
+const BIND_ADDR: &str = "127.0.0.1:14626";
+const PEER_ADDR: &str = "127.0.0.1:14627";
+
+#[derive(Clone)]
+struct AcceptAll;
+
+impl NeighborValidator for AcceptAll {
+    fn is_valid<P: AsRef<Peer>>(&self, _: P) -> bool {
+        true
+    }
+}
+
+// Keeps the receiving halves of the channels alive, as sending to them must not fail.
+struct Context {
+    local: Local,
+    server_tx: ServerTx,
+    _server_rx: mpsc::UnboundedReceiver<OutgoingPacket>,
+    event_tx: event::EventTx,
+    _event_rx: EventRx,
+    request_mngr: RequestManager,
+    active_peers: ActivePeersList,
+    metrics: PacketMetrics,
+}
+
+impl Context {
+    fn new() -> Self {
+        let local = Local::generate();
+        let bind_addr: SocketAddr = BIND_ADDR.parse().unwrap();
+        local.add_service(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, bind_addr.port());
+
+        let (server_tx, server_rx) = server::server_chan();
+        let (event_tx, event_rx) = event::event_chan();
+        let request_mngr = RequestManager::new(VERSION, NETWORK_ID, Some(bind_addr), None, local.clone());
+
+        Self {
+            local,
+            server_tx,
+            _server_rx: server_rx,
+            event_tx,
+            _event_rx: event_rx,
+            request_mngr,
+            active_peers: ActivePeersList::default(),
+            metrics: PacketMetrics::default(),
+        }
+    }
+}
+
+// Creates an authenticated packet from a message, as signatures can't be forged by the fuzzer.
+fn incoming_packet(data: &[u8]) -> Option<IncomingPacket> {
+    let (msg_type, msg_bytes) = server::unmarshal(data).ok()?;
+
+    Some(IncomingPacket {
+        msg_type,
+        msg_bytes,
+        peer_addr: PEER_ADDR.parse().unwrap(),
+        peer_id: PeerId::from_public_key(PrivateKey::generate().unwrap().public_key()),
+    })
+}
+
+/// Decodes, unmarshals and authenticates a received packet.
+pub fn packet(data: &[u8]) {
+    let _ = server::recv_packet(data, PEER_ADDR.parse().unwrap(), &PacketMetrics::default());
+}
+
+/// Handles a received discovery message, i.e. a message type followed by the protobuf encoded message.
+pub fn discovery_message(data: &[u8]) {
+    let ctx = Context::new();
+
+    if let Some(packet) = incoming_packet(data) {
+        DiscoveryPacketHandler {
+            server_tx: ctx.server_tx.clone(),
+            local: ctx.local.clone(),
+            version: VERSION,
+            network_id: NETWORK_ID,
+            request_mngr: ctx.request_mngr.clone(),
+            event_tx: ctx.event_tx.clone(),
+            active_peers: ctx.active_peers.clone(),
+            replacements: ReplacementPeersList::default(),
+            metrics: ctx.metrics.clone(),
+        }
+        .handle_packet(packet);
+    }
+}
+
+/// Handles a received peering message, i.e. a message type followed by the protobuf encoded message.
+pub fn peering_message(data: &[u8]) {
+    let ctx = Context::new();
+
+    if let Some(packet) = incoming_packet(data) {
+        PeeringPacketHandler {
+            nb_filter: NeighborFilter::new(ctx.local.peer_id(), AcceptAll),
+            local: ctx.local.clone(),
+            request_mngr: ctx.request_mngr.clone(),
+            event_tx: ctx.event_tx.clone(),
+            active_peers: ctx.active_peers.clone(),
+            inbound_nbh: InboundNeighborhood::new(),
+            outbound_nbh: OutboundNeighborhood::new(),
+            server_tx: ctx.server_tx.clone(),
+            metrics: ctx.metrics.clone(),
+        }
+        .handle_packet(packet);
+    }
+}
+
+/// Decodes a received discovery response, and validates its peers.
+///
+/// Discovery responses are otherwise rejected, as the fuzzer can't produce the hash of the corresponding request.
+pub fn discovery_response(data: &[u8]) {
+    if let Ok(disc_res) = DiscoveryResponse::from_protobuf(data) {
+        let peer_id = PeerId::from_public_key(PrivateKey::generate().unwrap().public_key());
+
+        let _ = manager::validate_discovery_response_peers(disc_res.peers(), &peer_id, PEER_ADDR.parse().unwrap());
+    }
+}
@@ -6,6 +6,7 @@
 use std::{error, future::Future, iter, time::Duration};
 
 use crate::{
+    command::{self, CommandTx},
     config::AutopeeringConfig,
     delay,
     discovery::{
@@ -15,6 +16,7 @@ use crate::{
     event::{self, EventRx},
     hash,
     local::Local,
+    metrics::PacketMetrics,
     multiaddr,
     packet::IncomingPacket,
     peer::{
@@ -39,6 +41,9 @@ const BOOTSTRAP_QUERY_DELAY: Duration = Duration::from_secs(2 * SECOND);
 const BOOTSTRAP_UPDATE_DELAY: Duration = Duration::from_secs(4 * SECOND);
 
 /// Initializes the autopeering service.
+///
+/// Returns the receiver of the autopeering events, the sender of the commands to the autopeering, and the metrics of
+/// the received packets.
 pub async fn init<S, I, Q, V>(
     config: AutopeeringConfig,
     version: u32,
@@ -47,7 +52,7 @@ pub async fn init<S, I, Q, V>(
     peer_store_config: <S as PeerStore>::Config,
     term_signal: Q,
     neighbor_validator: V,
-) -> Result<EventRx, Box<dyn error::Error>>
+) -> Result<(EventRx, CommandTx, PacketMetrics), Box<dyn error::Error>>
 where
     S: PeerStore + 'static,
     I: AsRef<str>,
@@ -81,7 +86,12 @@ where
     let replacements = ReplacementPeersList::default();
 
     // Create a task manager to have good control over the tokio task spawning business.
-    let mut task_mngr = TaskManager::<_>::new(peer_store.clone(), active_peers.clone(), replacements.clone());
+    let mut task_mngr = TaskManager::<_>::new(
+        peer_store.clone(),
+        active_peers.clone(),
+        replacements.clone(),
+        config.peer_expiration(),
+    );
 
     // Create channels for inbound/outbound communication with the UDP server.
     let (discovery_tx, discovery_rx) = server_chan::<IncomingPacket>();
@@ -94,13 +104,25 @@ where
     // Event channel to publish events to the user.
     let (event_tx, event_rx) = event::event_chan();
 
+    // Command channel to receive commands from the user.
+    let (command_tx, command_rx) = command::command_chan();
+
+    // Metrics of the received packets, shared with the user.
+    let metrics = PacketMetrics::default();
+
     // Initialize the server managing the UDP socket I/O.
     let server_config = ServerConfig::new(&config);
-    let (server, server_tx) = Server::new(server_config, local.clone(), incoming_senders);
+    let (server, server_tx) = Server::new(server_config, local.clone(), incoming_senders, metrics.clone());
     server.init(&mut task_mngr).await;
 
     // Create a request manager that creates and keeps track of outgoing requests.
-    let request_mngr = RequestManager::new(version, network_id, config.bind_addr_v4(), config.bind_addr_v6());
+    let request_mngr = RequestManager::new(
+        version,
+        network_id,
+        config.bind_addr_v4(),
+        config.bind_addr_v6(),
+        local.clone(),
+    );
 
     // Create the discovery manager handling the discovery request/response protocol.
     let discovery_config = DiscoveryManagerConfig::new(&config, version, network_id);
@@ -116,6 +138,7 @@ where
         active_peers.clone(),
         replacements.clone(),
         event_tx.clone(),
+        metrics.clone(),
     );
     discovery_mngr.init(&mut task_mngr).await?;
 
@@ -136,6 +159,8 @@ where
         inbound_nbh.clone(),
         outbound_nbh.clone(),
         nb_filter.clone(),
+        command_rx,
+        metrics.clone(),
     );
     task_mngr.run(peering_mngr);
 
@@ -204,5 +229,5 @@ where
 
     log::debug!("Autopeering initialized.");
 
-    Ok(event_rx)
+    Ok((event_rx, command_tx, metrics))
 }
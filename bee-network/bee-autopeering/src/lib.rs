@@ -78,8 +78,9 @@
 //!     // You need to provide some form of shutdown signal (any `Future` impl is allowed).
 //!     let term_signal = tokio::signal::ctrl_c();
 //!
-//!     // With initializing the autopeering system you receive an event stream receiver.
-//!     let mut event_rx = bee_autopeering::init::<SledPeerStore, _, _, GossipNeighborValidator>(
+//!     // With initializing the autopeering system you receive an event stream receiver, a command sender
+//!     // that allows you to e.g. drop misbehaving neighbors, and the metrics of the received packets.
+//!     let (mut event_rx, command_tx, packet_metrics) = bee_autopeering::init::<SledPeerStore, _, _, GossipNeighborValidator>(
 //!         config.clone(),
 //!         VERSION,
 //!         NETWORK,
@@ -136,11 +137,17 @@ mod server;
 mod task;
 mod time;
 
+pub mod command;
 pub mod config;
 pub mod event;
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod fuzzing;
 pub mod init;
+pub mod metrics;
 
 pub use self::{
+    command::Command,
     config::AutopeeringConfig,
     event::Event,
     init::init,
@@ -148,6 +155,7 @@ pub use self::{
         services::{ServiceEndpoint, ServiceMap, ServiceName, ServiceProtocol, AUTOPEERING_SERVICE_NAME},
         Local,
     },
+    metrics::PacketMetrics,
     peer::{peer_id, peer_id::PeerId, stores, Peer},
-    peering::{Distance, NeighborValidator, Status},
+    peering::{Distance, NeighborValidator, Score, Status, MAX_SCORE},
 };
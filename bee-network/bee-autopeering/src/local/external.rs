@@ -0,0 +1,129 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use crate::{
+    peer::PeerId,
+    time::{self, Timestamp, HOUR},
+};
+
+// Time after which the external address reported by a peer is no longer taken into account.
+const VOTE_EXPIRATION_SECS: u64 = HOUR;
+// Minimum number of peers that need to report the same external address before it is adopted.
+const MIN_VOTES: usize = 2;
+
+/// Tallies the external addresses of the local peer, as perceived by the verified peers.
+///
+/// An address is adopted once the majority of the peers, that recently reported an address of the same family,
+/// agree on it.
+#[derive(Default)]
+pub(crate) struct ExternalAddressVotes {
+    votes: HashMap<PeerId, (IpAddr, Timestamp)>,
+    external_addr_v4: Option<Ipv4Addr>,
+    external_addr_v6: Option<Ipv6Addr>,
+}
+
+impl ExternalAddressVotes {
+    /// Returns the external address of the given family, if one was adopted.
+    pub(crate) fn get(&self, ipv6: bool) -> Option<IpAddr> {
+        if ipv6 {
+            self.external_addr_v6.map(IpAddr::V6)
+        } else {
+            self.external_addr_v4.map(IpAddr::V4)
+        }
+    }
+
+    /// Records the external address reported by a peer, and returns the newly adopted address if that vote changed
+    /// it.
+    pub(crate) fn vote(&mut self, peer_id: PeerId, addr: IpAddr, now: Timestamp) -> Option<IpAddr> {
+        if addr.is_unspecified() {
+            return None;
+        }
+
+        self.votes.insert(peer_id, (addr, now));
+        self.votes.retain(
+            |_, (_, voted_at)| !matches!(time::delta(*voted_at, now), Some(age) if age >= VOTE_EXPIRATION_SECS),
+        );
+
+        let majority = self.majority(addr.is_ipv6())?;
+
+        if self.get(addr.is_ipv6()) == Some(majority) {
+            return None;
+        }
+
+        match majority {
+            IpAddr::V4(addr) => self.external_addr_v4 = Some(addr),
+            IpAddr::V6(addr) => self.external_addr_v6 = Some(addr),
+        }
+
+        Some(majority)
+    }
+
+    fn majority(&self, ipv6: bool) -> Option<IpAddr> {
+        let mut counts = HashMap::<IpAddr, usize>::new();
+
+        for (addr, _) in self.votes.values().filter(|(addr, _)| addr.is_ipv6() == ipv6) {
+            *counts.entry(*addr).or_default() += 1;
+        }
+
+        let num_votes: usize = counts.values().sum();
+
+        counts
+            .into_iter()
+            .find(|(_, count)| *count >= MIN_VOTES && *count * 2 > num_votes)
+            .map(|(addr, _)| addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto::signatures::ed25519::SecretKey as PrivateKey;
+
+    use super::*;
+
+    fn peer_id() -> PeerId {
+        PeerId::from_public_key(PrivateKey::generate().unwrap().public_key())
+    }
+
+    #[test]
+    fn majority_vote() {
+        let mut votes = ExternalAddressVotes::default();
+        let (public, other): (IpAddr, IpAddr) = ("1.2.3.4".parse().unwrap(), "5.6.7.8".parse().unwrap());
+        let peers = [peer_id(), peer_id(), peer_id(), peer_id()];
+
+        // A single vote is not enough.
+        assert_eq!(votes.vote(peers[0], public, 0), None);
+        assert_eq!(votes.get(false), None);
+        // Unspecified addresses are ignored.
+        assert_eq!(votes.vote(peers[1], "0.0.0.0".parse().unwrap(), 0), None);
+
+        assert_eq!(votes.vote(peers[1], public, 0), Some(public));
+        assert_eq!(votes.get(false), Some(public));
+        assert_eq!(votes.get(true), None);
+
+        // No change without a majority.
+        assert_eq!(votes.vote(peers[2], other, 0), None);
+        assert_eq!(votes.vote(peers[3], other, 0), None);
+        assert_eq!(votes.get(false), Some(public));
+
+        // A peer changing its vote tips the balance.
+        assert_eq!(votes.vote(peers[0], other, 0), Some(other));
+    }
+
+    #[test]
+    fn expired_votes() {
+        let mut votes = ExternalAddressVotes::default();
+        let (public, other): (IpAddr, IpAddr) = ("1.2.3.4".parse().unwrap(), "5.6.7.8".parse().unwrap());
+        let peers = [peer_id(), peer_id(), peer_id(), peer_id()];
+
+        votes.vote(peers[0], public, 0);
+        votes.vote(peers[1], public, 0);
+        votes.vote(peers[2], other, VOTE_EXPIRATION_SECS);
+
+        assert_eq!(votes.vote(peers[3], other, VOTE_EXPIRATION_SECS), Some(other));
+    }
+}
@@ -1,22 +1,25 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod external;
 pub(crate) mod salt;
 pub mod services;
 
 use std::{
     fmt,
+    net::IpAddr,
     sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
 use crypto::signatures::ed25519::{PublicKey, SecretKey as PrivateKey, Signature, SECRET_KEY_LENGTH};
-use libp2p_core::identity::ed25519::Keypair;
+use libp2p_identity::ed25519::Keypair;
 
 use self::{
+    external::ExternalAddressVotes,
     salt::{Salt, SALT_LIFETIME_SECS},
     services::{ServiceMap, ServiceProtocol},
 };
-use crate::peer::PeerId;
+use crate::{peer::PeerId, time};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -47,6 +50,7 @@ pub struct LocalInner {
     private_key: PrivateKey,
     private_salt: Salt,
     services: ServiceMap,
+    external_addrs: ExternalAddressVotes,
 }
 
 impl Local {
@@ -62,6 +66,7 @@ impl Local {
             private_key,
             private_salt: Salt::default(),
             services: ServiceMap::default(),
+            external_addrs: ExternalAddressVotes::default(),
         };
 
         Self {
@@ -113,6 +118,7 @@ impl Local {
                 private_salt: Salt::new(SALT_LIFETIME_SECS),
                 public_salt: Salt::new(SALT_LIFETIME_SECS),
                 services: ServiceMap::default(),
+                external_addrs: ExternalAddressVotes::default(),
             })),
         }
     }
@@ -162,6 +168,22 @@ impl Local {
         self.read().services().clone()
     }
 
+    /// Returns the external address of the given family, as perceived by the majority of the verified peers.
+    pub fn external_addr(&self, ipv6: bool) -> Option<IpAddr> {
+        self.read().external_addrs.get(ipv6)
+    }
+
+    /// Records the external address reported by a verified peer, and returns the new external address if it changed,
+    /// the services being advertised under it from then on.
+    pub(crate) fn vote_external_addr(&self, peer_id: PeerId, addr: IpAddr) -> Option<IpAddr> {
+        let mut inner = self.write();
+        let external_addr = inner.external_addrs.vote(peer_id, addr, time::unix_now_secs())?;
+
+        inner.services.set_external_addr(external_addr);
+
+        Some(external_addr)
+    }
+
     fn read(&self) -> RwLockReadGuard<LocalInner> {
         // Panic: we do not allow the lock to be poisened.
         self.inner.read().expect("error getting read access")
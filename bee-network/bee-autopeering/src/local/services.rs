@@ -1,7 +1,12 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, fmt, io, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt, io,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
 
 use libp2p_core::multiaddr::Protocol;
 use serde::{Deserialize, Serialize};
@@ -17,23 +22,53 @@ pub const AUTOPEERING_SERVICE_NAME: &str = "peering";
 
 /// A mapping between a service name and its endpoint data.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct ServiceMap(HashMap<ServiceName, ServiceEndpoint>);
+#[serde(transparent)]
+pub struct ServiceMap {
+    services: HashMap<ServiceName, ServiceEndpoint>,
+    // The external addresses the local services are advertised under, never exchanged with other peers since those
+    // see them as the source address of our packets.
+    #[serde(skip)]
+    external_addr_v4: Option<IpAddr>,
+    #[serde(skip)]
+    external_addr_v6: Option<IpAddr>,
+}
 
 impl ServiceMap {
     /// Registers a service with its bind address.
     pub(crate) fn insert(&mut self, service_name: impl ToString, protocol: ServiceProtocol, port: ServicePort) {
-        self.0
+        self.services
             .insert(service_name.to_string(), ServiceEndpoint { protocol, port });
     }
 
     /// Returns the connection data associated with the given service name.
     pub fn get(&self, service_name: impl AsRef<str>) -> Option<ServiceEndpoint> {
-        self.0.get(service_name.as_ref()).copied()
+        self.services.get(service_name.as_ref()).copied()
     }
 
     /// Returns the number of services.
     pub(crate) fn len(&self) -> usize {
-        self.0.len()
+        self.services.len()
+    }
+
+    /// Advertises the services under the given external address, replacing the one of the same family.
+    pub(crate) fn set_external_addr(&mut self, addr: IpAddr) {
+        if addr.is_ipv6() {
+            self.external_addr_v6.replace(addr);
+        } else {
+            self.external_addr_v4.replace(addr);
+        }
+    }
+
+    /// Returns the external endpoint of the given service, if an external address of the given family is known.
+    pub fn external_endpoint(&self, service_name: impl AsRef<str>, ipv6: bool) -> Option<SocketAddr> {
+        let addr = if ipv6 {
+            self.external_addr_v6
+        } else {
+            self.external_addr_v4
+        }?;
+
+        self.get(service_name)
+            .map(|endpoint| SocketAddr::new(addr, endpoint.port()))
     }
 }
 
@@ -56,13 +91,16 @@ impl TryFrom<proto::ServiceMap> for ServiceMap {
             services.insert(service_name, ServiceEndpoint { protocol, port });
         }
 
-        Ok(Self(services))
+        Ok(Self {
+            services,
+            ..Default::default()
+        })
     }
 }
 
 impl From<&ServiceMap> for proto::ServiceMap {
     fn from(services: &ServiceMap) -> Self {
-        let ServiceMap(map) = services;
+        let ServiceMap { services: map, .. } = services;
 
         let mut services = HashMap::with_capacity(map.len());
 
@@ -85,7 +123,7 @@ impl fmt::Display for ServiceMap {
         write!(
             f,
             "{}",
-            self.0
+            self.services
                 .iter()
                 .map(|(service_name, service)| format!("{}/{}/{}", service_name, service.protocol, service.port))
                 .reduce(|acc, service_spec| acc + ";" + &service_spec)
@@ -191,4 +229,26 @@ mod tests {
         let services: &ServiceMap = &proto_services.try_into().unwrap();
         let _: proto::ServiceMap = services.into();
     }
+
+    #[test]
+    fn external_endpoints() {
+        let mut services = ServiceMap::default();
+        services.insert(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, 14626);
+
+        assert_eq!(services.external_endpoint(AUTOPEERING_SERVICE_NAME, false), None);
+
+        services.set_external_addr("1.2.3.4".parse().unwrap());
+
+        assert_eq!(
+            services.external_endpoint(AUTOPEERING_SERVICE_NAME, false),
+            Some("1.2.3.4:14626".parse().unwrap())
+        );
+        assert_eq!(services.external_endpoint(AUTOPEERING_SERVICE_NAME, true), None);
+        assert_eq!(services.external_endpoint("gossip", false), None);
+        // The external address is local only.
+        assert_eq!(
+            serde_json::to_string(&services).unwrap(),
+            r#"{"peering":{"protocol":"Udp","port":14626}}"#
+        );
+    }
 }
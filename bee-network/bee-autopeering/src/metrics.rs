@@ -0,0 +1,60 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metrics of the packets received by the autopeering.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Counts the received packets that were rejected.
+#[derive(Clone, Debug, Default)]
+pub struct PacketMetrics(Arc<PacketMetricsInner>);
+
+#[derive(Debug, Default)]
+struct PacketMetricsInner {
+    invalid_packets: AtomicU64,
+    invalid_signatures: AtomicU64,
+    invalid_messages: AtomicU64,
+    rejected_messages: AtomicU64,
+}
+
+impl PacketMetrics {
+    /// Returns the number of packets that couldn't be decoded.
+    pub fn invalid_packets(&self) -> u64 {
+        self.0.invalid_packets.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of packets with an invalid signature.
+    pub fn invalid_signatures(&self) -> u64 {
+        self.0.invalid_signatures.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages that couldn't be decoded.
+    pub fn invalid_messages(&self) -> u64 {
+        self.0.invalid_messages.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of decoded messages that failed validation, e.g. because they were expired, unsolicited
+    /// or announced invalid peers.
+    pub fn rejected_messages(&self) -> u64 {
+        self.0.rejected_messages.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn invalid_packets_inc(&self) -> u64 {
+        self.0.invalid_packets.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub(crate) fn invalid_signatures_inc(&self) -> u64 {
+        self.0.invalid_signatures.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub(crate) fn invalid_messages_inc(&self) -> u64 {
+        self.0.invalid_messages.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub(crate) fn rejected_messages_inc(&self) -> u64 {
+        self.0.rejected_messages.fetch_add(1, Ordering::Relaxed)
+    }
+}
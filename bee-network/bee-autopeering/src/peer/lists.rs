@@ -247,6 +247,10 @@ impl PeerMetrics {
         self.last_verif_request = time::unix_now_secs();
     }
 
+    pub(crate) fn last_verif_response(&self) -> Timestamp {
+        self.last_verif_response
+    }
+
     pub(crate) fn set_last_verif_response_timestamp(&mut self) {
         self.last_verif_response = time::unix_now_secs();
     }
@@ -24,6 +24,7 @@ pub struct InMemoryPeerStore {
 struct InMemoryPeerStoreInner {
     active_peers: HashMap<PeerId, ActivePeer>,
     replacements: HashMap<PeerId, Peer>,
+    last_seen: HashMap<PeerId, u64>,
 }
 
 impl InMemoryPeerStore {
@@ -112,8 +113,19 @@ impl PeerStore for InMemoryPeerStore {
         Ok(self.read().replacements.iter().map(|(_, p)| p).cloned().collect())
     }
 
+    fn store_last_seen(&self, peer_id: &PeerId, last_seen: u64) -> Result<(), Self::Error> {
+        let _ = self.write().last_seen.insert(*peer_id, last_seen);
+
+        Ok(())
+    }
+
+    fn fetch_last_seen(&self, peer_id: &PeerId) -> Result<Option<u64>, Self::Error> {
+        Ok(self.read().last_seen.get(peer_id).copied())
+    }
+
     fn delete(&self, peer_id: &PeerId) -> Result<bool, Self::Error> {
         let mut write = self.write();
+        let _ = write.last_seen.remove(peer_id);
         Ok(write.active_peers.remove(peer_id).is_some() || write.replacements.remove(peer_id).is_some())
     }
 
@@ -121,6 +133,7 @@ impl PeerStore for InMemoryPeerStore {
         let mut write = self.write();
         write.active_peers.clear();
         write.replacements.clear();
+        write.last_seen.clear();
 
         Ok(())
     }
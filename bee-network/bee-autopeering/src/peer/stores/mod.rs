@@ -5,15 +5,17 @@
 
 #[cfg(feature = "in-memory")]
 mod in_memory;
+mod records;
 #[cfg(feature = "rocksdb")]
 mod rocksdb;
 #[cfg(feature = "sled")]
 mod sled;
 
-use std::error::Error;
+use std::{error::Error, time::Duration};
 
 #[cfg(feature = "in-memory")]
 pub use self::in_memory::*;
+pub use self::records::{export_peers, import_peers, PeerRecord};
 #[cfg(feature = "rocksdb")]
 pub use self::rocksdb::*;
 #[cfg(feature = "sled")]
@@ -23,6 +25,7 @@ use super::{
     peer_id::PeerId,
     Peer,
 };
+use crate::time::{self, Timestamp};
 
 /// Mandatory functionality of any peer store.
 pub trait PeerStore: Clone + Send + Sync {
@@ -62,9 +65,101 @@ pub trait PeerStore: Clone + Send + Sync {
     /// Fetches all replacement peers.
     fn fetch_all_replacements(&self) -> Result<Vec<Peer>, Self::Error>;
 
+    /// Stores when a peer was last seen, i.e. verified, as a Unix timestamp in seconds.
+    fn store_last_seen(&self, peer_id: &PeerId, last_seen: u64) -> Result<(), Self::Error>;
+
+    /// Fetches when a peer was last seen, i.e. verified, as a Unix timestamp in seconds.
+    fn fetch_last_seen(&self, peer_id: &PeerId) -> Result<Option<u64>, Self::Error>;
+
     /// Deletes a stored peer.
     fn delete(&self, peer_id: &PeerId) -> Result<bool, Self::Error>;
 
     /// Deletes all stored peers.
     fn delete_all(&self) -> Result<(), Self::Error>;
+
+    /// Deletes all stored peers that weren't seen for at least `max_age`, and returns their number.
+    ///
+    /// Peers without a last-seen timestamp, e.g. because they were stored by an older version, start aging now.
+    fn delete_expired(&self, max_age: Duration) -> Result<usize, Self::Error> {
+        let now = time::unix_now_secs();
+        let max_age = max_age.as_secs();
+
+        let peer_ids = self
+            .fetch_all_active()?
+            .iter()
+            .map(|p| *p.peer_id())
+            .chain(self.fetch_all_replacements()?.iter().map(|p| *p.peer_id()))
+            .collect::<Vec<_>>();
+
+        let mut num_deleted = 0;
+
+        for peer_id in peer_ids {
+            match self.fetch_last_seen(&peer_id)? {
+                Some(last_seen) => {
+                    if matches!(time::delta(last_seen, now), Some(age) if age >= max_age) {
+                        self.delete(&peer_id)?;
+                        num_deleted += 1;
+                    }
+                }
+                None => self.store_last_seen(&peer_id, now)?,
+            }
+        }
+
+        Ok(num_deleted)
+    }
+}
+
+/// Stores the current peers together with when they were last seen, and deletes the expired ones.
+pub(crate) fn flush<S: PeerStore>(
+    peer_store: &S,
+    active_peers: &ActivePeersList,
+    replacements: &ReplacementPeersList,
+    max_age: Duration,
+) -> Result<(), S::Error> {
+    let now = time::unix_now_secs();
+
+    // Remember when the current peers were last seen, because clearing the store forgets it.
+    let mut last_seen_peers = Vec::new();
+
+    for active_peer in active_peers.read().iter() {
+        let peer_id = *active_peer.peer_id();
+        let last_seen = last_seen(peer_store.fetch_last_seen(&peer_id)?, active_peer, now);
+
+        last_seen_peers.push((peer_id, last_seen));
+    }
+
+    for peer in replacements.read().iter() {
+        let peer_id = *peer.peer_id();
+        let last_seen = peer_store.fetch_last_seen(&peer_id)?.unwrap_or(now);
+
+        last_seen_peers.push((peer_id, last_seen));
+    }
+
+    // The bulk stores only add peers, so clear the store first to not restore removed or demoted peers.
+    peer_store.delete_all()?;
+    peer_store.store_all_active(active_peers)?;
+    peer_store.store_all_replacements(replacements)?;
+
+    for (peer_id, last_seen) in last_seen_peers {
+        peer_store.store_last_seen(&peer_id, last_seen)?;
+    }
+
+    let num_deleted = peer_store.delete_expired(max_age)?;
+    if num_deleted > 0 {
+        log::debug!("Deleted {} expired peer/s from the peer store.", num_deleted);
+    }
+
+    Ok(())
+}
+
+// An active peer was last seen when it was last verified. Peers that were never verified are considered seen when they
+// were first stored.
+fn last_seen(stored: Option<Timestamp>, active_peer: &ActivePeer, now: Timestamp) -> Timestamp {
+    match stored
+        .unwrap_or_default()
+        .max(active_peer.metrics().last_verif_response())
+    {
+        0 => now,
+        last_seen => last_seen,
+    }
 }
@@ -0,0 +1,150 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use crypto::signatures::ed25519::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    local::services::ServiceMap,
+    peer::{lists::ActivePeer, stores::PeerStore, Peer},
+    time,
+};
+
+/// A stored peer in a format suitable for exporting and importing a peer store, e.g. as JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerRecord {
+    #[serde(with = "base58_public_key")]
+    public_key: PublicKey,
+    ip_address: IpAddr,
+    services: ServiceMap,
+    active: bool,
+    last_seen: u64,
+}
+
+impl PeerRecord {
+    fn new(peer: &Peer, active: bool, last_seen: u64) -> Self {
+        Self {
+            public_key: *peer.public_key(),
+            ip_address: peer.ip_address(),
+            services: peer.services().clone(),
+            active,
+            last_seen,
+        }
+    }
+
+    /// Returns the peer described by this record.
+    pub fn to_peer(&self) -> Peer {
+        let mut peer = Peer::new(self.ip_address, self.public_key);
+        peer.set_services(self.services.clone());
+        peer
+    }
+
+    /// Whether the peer was stored as an active peer, or as a replacement peer.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// When the peer was last seen, i.e. verified, as a Unix timestamp in seconds.
+    pub fn last_seen(&self) -> u64 {
+        self.last_seen
+    }
+}
+
+/// Exports all peers of a peer store.
+pub fn export_peers<S: PeerStore>(peer_store: &S) -> Result<Vec<PeerRecord>, S::Error> {
+    let now = time::unix_now_secs();
+    let mut records = Vec::new();
+
+    for active_peer in peer_store.fetch_all_active()? {
+        let last_seen = peer_store.fetch_last_seen(active_peer.peer_id())?.unwrap_or(now);
+        records.push(PeerRecord::new(active_peer.peer(), true, last_seen));
+    }
+
+    for peer in peer_store.fetch_all_replacements()? {
+        let last_seen = peer_store.fetch_last_seen(peer.peer_id())?.unwrap_or(now);
+        records.push(PeerRecord::new(&peer, false, last_seen));
+    }
+
+    Ok(records)
+}
+
+/// Imports peers into a peer store, replacing already stored peers with the same identity, and returns their number.
+pub fn import_peers<S: PeerStore>(peer_store: &S, records: &[PeerRecord]) -> Result<usize, S::Error> {
+    for record in records {
+        let peer = record.to_peer();
+        let peer_id = *peer.peer_id();
+
+        if record.active {
+            peer_store.store_active(ActivePeer::new(peer))?;
+        } else {
+            peer_store.store_replacement(peer)?;
+        }
+
+        peer_store.store_last_seen(&peer_id, record.last_seen)?;
+    }
+
+    Ok(records.len())
+}
+
+mod base58_public_key {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::PublicKey;
+    use crate::multiaddr;
+
+    pub(super) fn serialize<S: Serializer>(public_key: &PublicKey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&multiaddr::pubkey_to_base58(public_key))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PublicKey, D::Error> {
+        let base58_public_key = String::deserialize(deserializer)?;
+
+        multiaddr::base58_to_pubkey(base58_public_key).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(all(test, feature = "in-memory"))]
+mod tests {
+    use super::*;
+    use crate::peer::stores::InMemoryPeerStore;
+
+    #[test]
+    fn export_and_import_peers() {
+        let peer_store = InMemoryPeerStore::default();
+
+        let active_peer = ActivePeer::new(Peer::new_test_peer(0));
+        let active_peer_id = *active_peer.peer_id();
+        let replacement = Peer::new_test_peer(1);
+        let replacement_id = *replacement.peer_id();
+
+        peer_store.store_active(active_peer).unwrap();
+        peer_store.store_last_seen(&active_peer_id, 42).unwrap();
+        peer_store.store_replacement(replacement).unwrap();
+
+        let records = export_peers(&peer_store).unwrap();
+        let json = serde_json::to_string(&records).unwrap();
+        let records = serde_json::from_str::<Vec<PeerRecord>>(&json).unwrap();
+
+        let imported_peer_store = InMemoryPeerStore::default();
+        assert_eq!(import_peers(&imported_peer_store, &records).unwrap(), 2);
+
+        let imported_active_peer = imported_peer_store.fetch_active(&active_peer_id).unwrap().unwrap();
+        assert_eq!(imported_active_peer.peer().num_services(), 1);
+        assert_eq!(imported_peer_store.fetch_last_seen(&active_peer_id).unwrap(), Some(42));
+        assert!(imported_peer_store
+            .fetch_replacement(&replacement_id)
+            .unwrap()
+            .is_some());
+        assert!(imported_peer_store.fetch_last_seen(&replacement_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn reject_invalid_public_key() {
+        let json = r#"{"publicKey":"invalid","ipAddress":"127.0.0.1","services":{},"active":true,"lastSeen":0}"#;
+
+        assert!(serde_json::from_str::<PeerRecord>(json).is_err());
+    }
+}
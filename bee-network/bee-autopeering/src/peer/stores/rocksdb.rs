@@ -18,6 +18,7 @@ use crate::peer::{
 
 const ACTIVE_PEERS_CF: &str = "active_peers";
 const REPLACEMENTS_CF: &str = "replacements";
+const LAST_SEEN_CF: &str = "last_seen";
 
 /// The config for the RocksDB peer store.
 #[derive(Clone)]
@@ -54,16 +55,23 @@ impl PeerStore for RocksDbPeerStore {
     type Error = rocksdb::Error;
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
-        let db = DBWithThreadMode::open_cf(&config.options, &config.path, [ACTIVE_PEERS_CF, REPLACEMENTS_CF])?;
+        let db = DBWithThreadMode::open_cf(
+            &config.options,
+            &config.path,
+            [ACTIVE_PEERS_CF, REPLACEMENTS_CF, LAST_SEEN_CF],
+        )?;
 
         Ok(Self { db: Arc::new(db) })
     }
 
     fn store_active(&self, active_peer: ActivePeer) -> Result<(), Self::Error> {
-        let cf = self.open_cf(ACTIVE_PEERS_CF);
         let key = *active_peer.peer_id();
 
-        self.db.put_cf(&cf, key, active_peer.to_bytes())
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&self.open_cf(ACTIVE_PEERS_CF), key, active_peer.to_bytes());
+        batch.delete_cf(&self.open_cf(REPLACEMENTS_CF), key);
+
+        self.db.write(batch)
     }
 
     fn store_all_active(&self, active_peers: &ActivePeersList) -> Result<(), Self::Error> {
@@ -79,10 +87,13 @@ impl PeerStore for RocksDbPeerStore {
     }
 
     fn store_replacement(&self, peer: Peer) -> Result<(), Self::Error> {
-        let cf = self.open_cf(REPLACEMENTS_CF);
         let key = *peer.peer_id();
 
-        self.db.put_cf(&cf, key, peer.to_bytes())
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&self.open_cf(REPLACEMENTS_CF), key, peer.to_bytes());
+        batch.delete_cf(&self.open_cf(ACTIVE_PEERS_CF), key);
+
+        self.db.write(batch)
     }
 
     fn store_all_replacements(&self, replacements: &ReplacementPeersList) -> Result<(), Self::Error> {
@@ -139,8 +150,33 @@ impl PeerStore for RocksDbPeerStore {
             .collect::<Result<Vec<_>, _>>())?
     }
 
-    fn delete(&self, _: &PeerId) -> Result<bool, Self::Error> {
-        unimplemented!("no need for single entry removal at the moment")
+    fn store_last_seen(&self, peer_id: &PeerId, last_seen: u64) -> Result<(), Self::Error> {
+        let cf = self.open_cf(LAST_SEEN_CF);
+
+        self.db.put_cf(&cf, peer_id, last_seen.to_be_bytes())
+    }
+
+    fn fetch_last_seen(&self, peer_id: &PeerId) -> Result<Option<u64>, Self::Error> {
+        let cf = self.open_cf(LAST_SEEN_CF);
+
+        Ok(self
+            .db
+            .get_cf(&cf, peer_id)?
+            .and_then(|b| b.as_slice().try_into().ok())
+            .map(u64::from_be_bytes))
+    }
+
+    fn delete(&self, peer_id: &PeerId) -> Result<bool, Self::Error> {
+        let contained = self.contains(peer_id)?;
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(&self.open_cf(ACTIVE_PEERS_CF), peer_id);
+        batch.delete_cf(&self.open_cf(REPLACEMENTS_CF), peer_id);
+        batch.delete_cf(&self.open_cf(LAST_SEEN_CF), peer_id);
+
+        self.db.write(batch)?;
+
+        Ok(contained)
     }
 
     fn delete_all(&self) -> Result<(), Self::Error> {
@@ -150,6 +186,9 @@ impl PeerStore for RocksDbPeerStore {
         let cf = self.open_cf(REPLACEMENTS_CF);
         self.db.delete_range_cf(&cf, [0; 32], [0xff; 32])?;
 
+        let cf = self.open_cf(LAST_SEEN_CF);
+        self.db.delete_range_cf(&cf, [0; 32], [0xff; 32])?;
+
         Ok(())
     }
 }
@@ -229,4 +268,22 @@ mod tests {
 
         run_with_peer_store_in_path("rocksdb_store_and_delete_all", f)
     }
+
+    #[test]
+    fn store_and_delete() {
+        fn f(peer_store: RocksDbPeerStore) {
+            let peer = ActivePeer::new(Peer::new_test_peer(0));
+            let peer_id = *peer.peer_id();
+
+            peer_store.store_active(peer).unwrap();
+            peer_store.store_last_seen(&peer_id, 42).unwrap();
+
+            assert!(peer_store.delete(&peer_id).unwrap());
+
+            assert!(!peer_store.contains(&peer_id).unwrap());
+            assert!(peer_store.fetch_last_seen(&peer_id).unwrap().is_none());
+        }
+
+        run_with_peer_store_in_path("rocksdb_store_and_delete", f)
+    }
 }
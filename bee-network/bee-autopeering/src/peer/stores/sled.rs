@@ -12,6 +12,7 @@ use crate::peer::{
 
 const ACTIVE_PEERS_TREE: &str = "active_peers";
 const REPLACEMENTS_TREE: &str = "replacements";
+const LAST_SEEN_TREE: &str = "last_seen";
 
 /// The config for the Sled peer store.
 pub type SledPeerStoreConfig = sled::Config;
@@ -32,6 +33,7 @@ impl PeerStore for SledPeerStore {
 
         db.open_tree(ACTIVE_PEERS_TREE)?;
         db.open_tree(REPLACEMENTS_TREE)?;
+        db.open_tree(LAST_SEEN_TREE)?;
 
         Ok(Self { db })
     }
@@ -41,6 +43,7 @@ impl PeerStore for SledPeerStore {
         let key = *active_peer.peer_id();
 
         tree.insert(key, active_peer.to_bytes())?;
+        self.db.open_tree(REPLACEMENTS_TREE)?.remove(key)?;
 
         Ok(())
     }
@@ -64,6 +67,7 @@ impl PeerStore for SledPeerStore {
         let key = *peer.peer_id();
 
         tree.insert(key, peer)?;
+        self.db.open_tree(ACTIVE_PEERS_TREE)?.remove(key)?;
 
         Ok(())
     }
@@ -120,13 +124,36 @@ impl PeerStore for SledPeerStore {
             .collect::<Result<Vec<_>, _>>()
     }
 
-    fn delete(&self, _: &PeerId) -> Result<bool, Self::Error> {
-        unimplemented!("no need for single entry removal at the moment")
+    fn store_last_seen(&self, peer_id: &PeerId, last_seen: u64) -> Result<(), Self::Error> {
+        let tree = self.db.open_tree(LAST_SEEN_TREE)?;
+
+        tree.insert(peer_id, &last_seen.to_be_bytes()[..])?;
+
+        Ok(())
+    }
+
+    fn fetch_last_seen(&self, peer_id: &PeerId) -> Result<Option<u64>, Self::Error> {
+        let tree = self.db.open_tree(LAST_SEEN_TREE)?;
+
+        Ok(tree
+            .get(peer_id)?
+            .and_then(|b| b.as_ref().try_into().ok())
+            .map(u64::from_be_bytes))
+    }
+
+    fn delete(&self, peer_id: &PeerId) -> Result<bool, Self::Error> {
+        self.db.open_tree(LAST_SEEN_TREE)?.remove(peer_id)?;
+
+        let deleted_active = self.db.open_tree(ACTIVE_PEERS_TREE)?.remove(peer_id)?.is_some();
+        let deleted_replacement = self.db.open_tree(REPLACEMENTS_TREE)?.remove(peer_id)?.is_some();
+
+        Ok(deleted_active || deleted_replacement)
     }
 
     fn delete_all(&self) -> Result<(), Self::Error> {
         self.db.open_tree(ACTIVE_PEERS_TREE)?.clear()?;
         self.db.open_tree(REPLACEMENTS_TREE)?.clear()?;
+        self.db.open_tree(LAST_SEEN_TREE)?.clear()?;
 
         Ok(())
     }
@@ -134,7 +161,13 @@ impl PeerStore for SledPeerStore {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
+    use crate::{
+        peer::stores,
+        time::{self, DAY},
+    };
 
     fn create_temporary_sled_peer_store() -> SledPeerStore {
         let config = SledPeerStoreConfig::new().temporary(true);
@@ -168,4 +201,100 @@ mod tests {
 
         assert_eq!(peer_id, *fetched_peer.peer_id());
     }
+
+    #[test]
+    fn store_replacement_removes_active_peer() {
+        let peer_store = create_temporary_sled_peer_store();
+
+        let peer = Peer::new_test_peer(0);
+        let peer_id = *peer.peer_id();
+
+        peer_store.store_active(ActivePeer::new(peer.clone())).unwrap();
+        peer_store.store_replacement(peer).unwrap();
+
+        assert!(peer_store.fetch_active(&peer_id).unwrap().is_none());
+        assert!(peer_store.fetch_replacement(&peer_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn delete_expired_peers() {
+        let peer_store = create_temporary_sled_peer_store();
+
+        let expired_peer = ActivePeer::new(Peer::new_test_peer(0));
+        let expired_peer_id = *expired_peer.peer_id();
+        let recent_peer = Peer::new_test_peer(1);
+        let recent_peer_id = *recent_peer.peer_id();
+        let unseen_peer = Peer::new_test_peer(2);
+        let unseen_peer_id = *unseen_peer.peer_id();
+
+        peer_store.store_active(expired_peer).unwrap();
+        peer_store.store_last_seen(&expired_peer_id, 0).unwrap();
+        peer_store.store_replacement(recent_peer).unwrap();
+        peer_store
+            .store_last_seen(&recent_peer_id, time::unix_now_secs())
+            .unwrap();
+        peer_store.store_replacement(unseen_peer).unwrap();
+
+        assert_eq!(peer_store.delete_expired(Duration::from_secs(DAY)).unwrap(), 1);
+
+        assert!(!peer_store.contains(&expired_peer_id).unwrap());
+        assert!(peer_store.fetch_last_seen(&expired_peer_id).unwrap().is_none());
+        assert!(peer_store.contains(&recent_peer_id).unwrap());
+        assert!(peer_store.fetch_last_seen(&unseen_peer_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn flush_and_restart() {
+        let path = std::env::temp_dir().join("sled_flush_and_restart");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let open_peer_store = || SledPeerStore::new(SledPeerStoreConfig::new().path(&path)).unwrap();
+
+        let active_peer = ActivePeer::new(Peer::new_test_peer(0));
+        let active_peer_id = *active_peer.peer_id();
+        let demoted_peer = Peer::new_test_peer(1);
+        let demoted_peer_id = *demoted_peer.peer_id();
+        let removed_peer = Peer::new_test_peer(2);
+        let removed_peer_id = *removed_peer.peer_id();
+
+        let peer_store = open_peer_store();
+        peer_store.store_active(active_peer.clone()).unwrap();
+        peer_store.store_last_seen(&active_peer_id, 42).unwrap();
+        peer_store.store_active(ActivePeer::new(demoted_peer.clone())).unwrap();
+        peer_store.store_replacement(removed_peer).unwrap();
+        peer_store.store_last_seen(&removed_peer_id, 42).unwrap();
+
+        let active_peers = ActivePeersList::default();
+        active_peers.write().insert(active_peer);
+        let replacements = ReplacementPeersList::default();
+        replacements.write().insert(demoted_peer);
+
+        stores::flush(&peer_store, &active_peers, &replacements, Duration::from_secs(u64::MAX)).unwrap();
+        drop(peer_store);
+
+        let peer_store = open_peer_store();
+
+        let active_peer_ids = peer_store
+            .fetch_all_active()
+            .unwrap()
+            .iter()
+            .map(|p| *p.peer_id())
+            .collect::<Vec<_>>();
+        let replacement_ids = peer_store
+            .fetch_all_replacements()
+            .unwrap()
+            .iter()
+            .map(|p| *p.peer_id())
+            .collect::<Vec<_>>();
+
+        assert_eq!(active_peer_ids, vec![active_peer_id]);
+        assert_eq!(replacement_ids, vec![demoted_peer_id]);
+        assert!(!peer_store.contains(&removed_peer_id).unwrap());
+        assert!(peer_store.fetch_last_seen(&removed_peer_id).unwrap().is_none());
+        assert_eq!(peer_store.fetch_last_seen(&active_peer_id).unwrap(), Some(42));
+        assert!(peer_store.fetch_last_seen(&demoted_peer_id).unwrap().is_some());
+
+        drop(peer_store);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
 }
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
+use super::neighbor::{self, Distance, Score, MAX_SCORE};
 use crate::{
     peer::{peer_id::PeerId, Peer},
     NeighborValidator,
@@ -47,6 +48,21 @@ impl<V: NeighborValidator> NeighborFilter<V> {
         self.read().validator.is_valid(peer)
     }
 
+    /// Sets the score of a peer.
+    pub(crate) fn set_score(&self, peer_id: PeerId, score: Score) {
+        self.write().set_score(peer_id, score);
+    }
+
+    /// Forgets the scores of all peers.
+    pub(crate) fn clear_scores(&self) {
+        self.write().clear_scores();
+    }
+
+    /// Weights the salted distance to a peer with its score.
+    pub(crate) fn weighted_distance(&self, peer_id: &PeerId, distance: Distance) -> Distance {
+        self.read().weighted_distance(peer_id, distance)
+    }
+
     fn read(&self) -> RwLockReadGuard<NeighborFilterInner<V>> {
         self.inner.read().expect("error getting read access")
     }
@@ -59,6 +75,7 @@ impl<V: NeighborValidator> NeighborFilter<V> {
 pub(crate) struct NeighborFilterInner<V: NeighborValidator> {
     local_id: PeerId,
     rejected: HashSet<PeerId>,
+    scores: HashMap<PeerId, Score>,
     validator: V,
 }
 
@@ -67,6 +84,7 @@ impl<V: NeighborValidator> NeighborFilterInner<V> {
         Self {
             local_id,
             rejected: HashSet::new(),
+            scores: HashMap::new(),
             validator,
         }
     }
@@ -81,6 +99,25 @@ impl<V: NeighborValidator> NeighborFilterInner<V> {
         self.rejected.clear()
     }
 
+    /// Sets the score of a peer, forgetting it once it's back at the maximum.
+    fn set_score(&mut self, peer_id: PeerId, score: Score) {
+        if score >= MAX_SCORE {
+            self.scores.remove(&peer_id);
+        } else {
+            self.scores.insert(peer_id, score);
+        }
+    }
+
+    /// Forgets the scores of all peers.
+    fn clear_scores(&mut self) {
+        self.scores.clear()
+    }
+
+    /// Weights the salted distance to a peer with its score.
+    fn weighted_distance(&self, peer_id: &PeerId, distance: Distance) -> Distance {
+        neighbor::weighted_distance(distance, self.scores.get(peer_id).copied().unwrap_or(MAX_SCORE))
+    }
+
     /// Returns `true` if the filter is okay with the candidate, otherwise `false`.
     fn ok(&self, candidate: impl AsRef<Peer>) -> bool {
         let peer = candidate.as_ref();
@@ -147,4 +184,23 @@ mod tests {
         let included = filter.write().apply_list(&candidates);
         assert_eq!(1, included.len());
     }
+
+    #[test]
+    fn filter_scores() {
+        let (filter, peer1, peer2) = setup_scenario1();
+
+        filter.set_score(*peer1.peer_id(), 0);
+        assert_eq!(filter.weighted_distance(peer1.peer_id(), 1000), Distance::MAX);
+        assert_eq!(filter.weighted_distance(peer2.peer_id(), 1000), 1000);
+
+        // Scores survive the filter being reset, but are forgotten once they recovered.
+        filter.clear();
+        assert_eq!(filter.weighted_distance(peer1.peer_id(), 1000), Distance::MAX);
+        filter.set_score(*peer1.peer_id(), MAX_SCORE);
+        assert!(filter.read().scores.is_empty());
+
+        filter.set_score(*peer2.peer_id(), 0);
+        filter.clear_scores();
+        assert_eq!(filter.weighted_distance(peer2.peer_id(), 1000), 1000);
+    }
 }
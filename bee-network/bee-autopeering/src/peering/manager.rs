@@ -9,6 +9,7 @@ use super::{
     neighbor::{self, Neighborhood, SIZE_INBOUND, SIZE_OUTBOUND},
 };
 use crate::{
+    command::{Command, CommandRx},
     event::{Event, EventTx},
     hash::message_hash,
     local::{
@@ -16,6 +17,7 @@ use crate::{
         services::AUTOPEERING_SERVICE_NAME,
         Local,
     },
+    metrics::PacketMetrics,
     packet::{IncomingPacket, MessageType, OutgoingPacket},
     peer::{self, lists::ActivePeersList, peer_id::PeerId, Peer},
     peering::neighbor::{salt_distance, Neighbor},
@@ -54,6 +56,10 @@ pub(crate) struct PeeringManager<V: NeighborValidator> {
     outbound_nbh: OutboundNeighborhood,
     // The peer rejection filter.
     nb_filter: NeighborFilter<V>,
+    // Receives commands from the user.
+    command_rx: CommandRx,
+    // Counts the rejected packets.
+    metrics: PacketMetrics,
 }
 
 impl<V: NeighborValidator> PeeringManager<V> {
@@ -67,6 +73,8 @@ impl<V: NeighborValidator> PeeringManager<V> {
         inbound_nbh: InboundNeighborhood,
         outbound_nbh: OutboundNeighborhood,
         nb_filter: NeighborFilter<V>,
+        command_rx: CommandRx,
+        metrics: PacketMetrics,
     ) -> Self {
         Self {
             local,
@@ -77,6 +85,8 @@ impl<V: NeighborValidator> PeeringManager<V> {
             inbound_nbh,
             outbound_nbh,
             nb_filter,
+            command_rx,
+            metrics,
         }
     }
 }
@@ -98,6 +108,8 @@ impl<V: NeighborValidator> Runnable for PeeringManager<V> {
             inbound_nbh,
             outbound_nbh,
             nb_filter,
+            mut command_rx,
+            metrics,
         } = self;
 
         let ServerSocket {
@@ -105,89 +117,36 @@ impl<V: NeighborValidator> Runnable for PeeringManager<V> {
             server_tx,
         } = socket;
 
-        'recv: loop {
+        let packet_handler = PeeringPacketHandler {
+            local,
+            request_mngr,
+            event_tx,
+            active_peers,
+            inbound_nbh,
+            outbound_nbh,
+            nb_filter,
+            server_tx,
+            metrics,
+        };
+
+        loop {
             tokio::select! {
                 _ = &mut shutdown_rx => {
                     break;
                 }
+                Some(command) = command_rx.recv() => {
+                    handle_command(
+                        command,
+                        &packet_handler.nb_filter,
+                        &packet_handler.inbound_nbh,
+                        &packet_handler.outbound_nbh,
+                        &packet_handler.server_tx,
+                        &packet_handler.event_tx,
+                    );
+                }
                 p = server_rx.recv() => {
-                    if let Some(IncomingPacket {
-                        msg_type,
-                        msg_bytes,
-                        peer_addr,
-                        peer_id,
-                    }) = p
-                    {
-                        let ctx = RecvContext {
-                            peer_id: &peer_id,
-                            msg_bytes: &msg_bytes,
-                            server_tx: &server_tx,
-                            local: &local,
-                            active_peers: &active_peers,
-                            request_mngr: &request_mngr,
-                            peer_addr,
-                            event_tx: &event_tx,
-                            inbound_nbh: &inbound_nbh,
-                            outbound_nbh: &outbound_nbh,
-                        };
-
-                        match msg_type {
-                            MessageType::PeeringRequest => {
-                                let peer_req = if let Ok(peer_req) = PeeringRequest::from_protobuf(&msg_bytes) {
-                                    peer_req
-                                } else {
-                                    log::debug!("Error decoding peering request from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                if let Err(e) = validate_peering_request(&peer_req, &ctx) {
-                                    log::debug!("Received invalid peering request from {}. Reason: {:?}", &peer_id, e);
-                                    continue 'recv;
-                                } else {
-                                    log::trace!("Received valid peering request from {}.", &peer_id);
-
-                                    handle_peering_request(peer_req, ctx, &nb_filter);
-                                }
-                            }
-                            MessageType::PeeringResponse => {
-                                let peer_res = if let Ok(peer_res) = PeeringResponse::from_protobuf(&msg_bytes) {
-                                    peer_res
-                                } else {
-                                    log::debug!("Error decoding peering response from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                match validate_peering_response(&peer_res, &ctx) {
-                                    Ok(peer_reqval) => {
-                                        log::trace!("Received valid peering response from {}.", &peer_id);
-
-                                        handle_peering_response(peer_res, peer_reqval, ctx, &nb_filter);
-                                    }
-                                    Err(e) => {
-                                        log::debug!("Received invalid peering response from {}. Reason: {:?}", &peer_id, e);
-                                        continue 'recv;
-                                    }
-                                }
-                            }
-                            MessageType::DropRequest => {
-                                let drop_req = if let Ok(drop_req) = DropPeeringRequest::from_protobuf(&msg_bytes) {
-                                    drop_req
-                                } else {
-                                    log::debug!("Error decoding drop request from {}.", &peer_id);
-                                    continue 'recv;
-                                };
-
-                                if let Err(e) = validate_drop_request(&drop_req, &ctx) {
-                                    log::debug!("Received invalid drop request from {}. Reason: {:?}", &peer_id, e);
-                                    continue 'recv;
-                                } else {
-                                    log::trace!("Received valid drop request from {}.", &peer_id);
-
-                                    handle_drop_request(drop_req, ctx, &nb_filter);
-                                }
-                            }
-                            _ => log::debug!("Received unsupported peering message type"),
-                        }
+                    if let Some(packet) = p {
+                        packet_handler.handle_packet(packet);
                     }
                 }
             }
@@ -195,6 +154,107 @@ impl<V: NeighborValidator> Runnable for PeeringManager<V> {
     }
 }
 
+/// Decodes, validates and handles peering related packets.
+pub(crate) struct PeeringPacketHandler<V: NeighborValidator> {
+    pub(crate) local: Local,
+    pub(crate) request_mngr: RequestManager,
+    pub(crate) event_tx: EventTx,
+    pub(crate) active_peers: ActivePeersList,
+    pub(crate) inbound_nbh: InboundNeighborhood,
+    pub(crate) outbound_nbh: OutboundNeighborhood,
+    pub(crate) nb_filter: NeighborFilter<V>,
+    pub(crate) server_tx: ServerTx,
+    pub(crate) metrics: PacketMetrics,
+}
+
+impl<V: NeighborValidator> PeeringPacketHandler<V> {
+    pub(crate) fn handle_packet(&self, packet: IncomingPacket) {
+        let IncomingPacket {
+            msg_type,
+            msg_bytes,
+            peer_addr,
+            peer_id,
+        } = packet;
+
+        let ctx = RecvContext {
+            peer_id: &peer_id,
+            msg_bytes: &msg_bytes,
+            server_tx: &self.server_tx,
+            local: &self.local,
+            active_peers: &self.active_peers,
+            request_mngr: &self.request_mngr,
+            peer_addr,
+            event_tx: &self.event_tx,
+            inbound_nbh: &self.inbound_nbh,
+            outbound_nbh: &self.outbound_nbh,
+        };
+
+        match msg_type {
+            MessageType::PeeringRequest => {
+                let peer_req = if let Ok(peer_req) = PeeringRequest::from_protobuf(&msg_bytes) {
+                    peer_req
+                } else {
+                    log::debug!("Error decoding peering request from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                if let Err(e) = validate_peering_request(&peer_req, &ctx) {
+                    log::debug!("Received invalid peering request from {}. Reason: {:?}", &peer_id, e);
+                    self.metrics.rejected_messages_inc();
+                } else {
+                    log::trace!("Received valid peering request from {}.", &peer_id);
+
+                    handle_peering_request(peer_req, ctx, &self.nb_filter);
+                }
+            }
+            MessageType::PeeringResponse => {
+                let peer_res = if let Ok(peer_res) = PeeringResponse::from_protobuf(&msg_bytes) {
+                    peer_res
+                } else {
+                    log::debug!("Error decoding peering response from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                match validate_peering_response(&peer_res, &ctx) {
+                    Ok(peer_reqval) => {
+                        log::trace!("Received valid peering response from {}.", &peer_id);
+
+                        handle_peering_response(peer_res, peer_reqval, ctx, &self.nb_filter);
+                    }
+                    Err(e) => {
+                        log::debug!("Received invalid peering response from {}. Reason: {:?}", &peer_id, e);
+                        self.metrics.rejected_messages_inc();
+                    }
+                }
+            }
+            MessageType::DropRequest => {
+                let drop_req = if let Ok(drop_req) = DropPeeringRequest::from_protobuf(&msg_bytes) {
+                    drop_req
+                } else {
+                    log::debug!("Error decoding drop request from {}.", &peer_id);
+                    self.metrics.invalid_messages_inc();
+                    return;
+                };
+
+                if let Err(e) = validate_drop_request(&drop_req, &ctx) {
+                    log::debug!("Received invalid drop request from {}. Reason: {:?}", &peer_id, e);
+                    self.metrics.rejected_messages_inc();
+                } else {
+                    log::trace!("Received valid drop request from {}.", &peer_id);
+
+                    handle_drop_request(drop_req, ctx, &self.nb_filter);
+                }
+            }
+            _ => {
+                log::debug!("Received unsupported peering message type");
+                self.metrics.invalid_messages_inc();
+            }
+        }
+    }
+}
+
 pub(crate) struct RecvContext<'a> {
     peer_id: &'a PeerId,
     msg_bytes: &'a [u8],
@@ -290,9 +350,11 @@ fn handle_peering_request<V: NeighborValidator>(
         if nb_filter.is_valid_neighbor(&active_peer) {
             // The peer must not be a neighbor already.
             if !ctx.inbound_nbh.contains(ctx.peer_id) && !ctx.outbound_nbh.contains(ctx.peer_id) {
-                // Calculate the distance between the local peer and the potential neighbor.
-                let distance =
-                    neighbor::salt_distance(&ctx.local.peer_id(), active_peer.peer_id(), &ctx.local.private_salt());
+                // Calculate the distance between the local peer and the potential neighbor, weighted with its score.
+                let distance = nb_filter.weighted_distance(
+                    active_peer.peer_id(),
+                    neighbor::salt_distance(&ctx.local.peer_id(), active_peer.peer_id(), &ctx.local.private_salt()),
+                );
 
                 // Create a new neighbor.
                 let neighbor = Neighbor::new(active_peer.into_peer(), distance);
@@ -364,13 +426,16 @@ fn handle_peering_response<V: NeighborValidator>(
     if status {
         log::debug!("Peering accepted by {}.", ctx.peer_id);
 
-        let peer = ctx
-            .active_peers
-            .read()
-            .find(ctx.peer_id)
-            .cloned()
-            .expect("inconsistent peer list")
-            .into_peer();
+        let peer = match ctx.active_peers.read().find(ctx.peer_id).cloned() {
+            Some(active_peer) => active_peer.into_peer(),
+            None => {
+                log::debug!(
+                    "Ignoring peering response from {}: Peer is no longer active.",
+                    ctx.peer_id
+                );
+                return;
+            }
+        };
 
         // Hive.go: if the peer is already in inbound, do not add it and remove it from inbound
         // TODO: investigate why!
@@ -408,7 +473,10 @@ fn handle_peering_response<V: NeighborValidator>(
 
     // Send the response notification.
     if let Some(tx) = peer_reqval.response_tx {
-        tx.send(ctx.msg_bytes.to_vec()).expect("error sending response signal");
+        // The requester may have stopped waiting for the response.
+        if tx.send(ctx.msg_bytes.to_vec()).is_err() {
+            log::debug!("Peering response from {} arrived too late.", ctx.peer_id);
+        }
     }
 }
 
@@ -441,6 +509,37 @@ fn handle_drop_request<V: NeighborValidator>(
     }
 }
 
+fn handle_command<V: NeighborValidator>(
+    command: Command,
+    nb_filter: &NeighborFilter<V>,
+    inbound_nbh: &InboundNeighborhood,
+    outbound_nbh: &OutboundNeighborhood,
+    server_tx: &ServerTx,
+    event_tx: &EventTx,
+) {
+    log::trace!("Handling command {:?}.", command);
+
+    match command {
+        Command::SetScore { peer_id, score } => nb_filter.set_score(peer_id, score),
+        Command::DropNeighbor { peer_id } => {
+            let mut removed_nb = inbound_nbh.remove_neighbor(&peer_id);
+
+            if let Some(nb) = outbound_nbh.remove_neighbor(&peer_id) {
+                removed_nb.replace(nb);
+            }
+
+            if let Some(peer) = removed_nb {
+                // Don't select that peer again before the next salt update.
+                nb_filter.add(peer_id);
+
+                send_drop_peering_request_to_peer(peer, server_tx, event_tx, inbound_nbh, outbound_nbh);
+            } else {
+                log::debug!("Not dropping {}: Peer is not a neighbor.", peer_id);
+            }
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
 // SENDING
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -740,6 +839,9 @@ fn update_salts<V: NeighborValidator>(
         outbound_nbh.update_distances(local);
     }
 
+    // Forget the scores, so that they don't pile up for peers that are long gone. Neighbors are scored again anyway.
+    nb_filter.clear_scores();
+
     log::debug!(
         "Salts updated; private: {}, public: {}",
         private_salt_lifetime,
@@ -9,5 +9,5 @@ pub(crate) mod update;
 
 pub use self::{
     manager::Status,
-    neighbor::{Distance, NeighborValidator},
+    neighbor::{Distance, NeighborValidator, Score, MAX_SCORE},
 };
@@ -18,6 +18,12 @@ use crate::{
 /// The distance between the local entity and a neighbor.
 pub type Distance = u32;
 
+/// The quality of a neighbor, ranging from `0` (worst) to [`MAX_SCORE`] (best, or unknown).
+pub type Score = u8;
+
+/// The score of peers that are good neighbors, or that have not been scored yet.
+pub const MAX_SCORE: Score = 100;
+
 pub(crate) const SIZE_INBOUND: usize = 4;
 pub(crate) const SIZE_OUTBOUND: usize = 4;
 
@@ -295,6 +301,16 @@ pub(crate) fn salt_distance(peer1: &PeerId, peer2: &PeerId, salt: &Salt) -> Dist
     Bytes::copy_from_slice(&xored[..4]).get_u32_le()
 }
 
+// Moves the distance towards the maximum distance by the fraction of the score that is missing, so that the salted
+// distance keeps deciding amongst equally scored peers.
+pub(crate) fn weighted_distance(distance: Distance, score: Score) -> Distance {
+    let penalty = u64::from(MAX_SCORE - score.min(MAX_SCORE));
+    let headroom = u64::from(Distance::MAX - distance);
+
+    // Panic: the penalty is at most the headroom, which fits into a `Distance`.
+    distance + Distance::try_from(headroom * penalty / u64::from(MAX_SCORE)).unwrap()
+}
+
 fn concat<const N: usize, const M: usize>(bytes1: &[u8; N], bytes2: &[u8; M]) -> Vec<u8> {
     let l: usize = N + M;
     let mut bytes = vec![0u8; l];
@@ -355,4 +371,16 @@ mod tests {
         let salted_distance = salt_distance(&peer_id1, &peer_id2, &salt);
         assert_eq!(1184183819, salted_distance);
     }
+
+    #[test]
+    fn distance_weighting() {
+        assert_eq!(weighted_distance(1000, MAX_SCORE), 1000);
+        assert_eq!(weighted_distance(1000, 0), Distance::MAX);
+        assert_eq!(weighted_distance(Distance::MAX, 50), Distance::MAX);
+        assert_eq!(weighted_distance(0, 50), Distance::MAX / 2);
+
+        // A lower score never makes a peer closer.
+        assert!(weighted_distance(1000, 90) < weighted_distance(1000, 80));
+        assert!(weighted_distance(1000, 90) < weighted_distance(2000, 90));
+    }
 }
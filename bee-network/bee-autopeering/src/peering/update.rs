@@ -55,7 +55,9 @@ fn update_outbound<V: NeighborValidator + 'static>(ctx: &UpdateContext<V>) {
         .map(|p| {
             let peer = p.into_peer();
             let peer_id = *peer.peer_id();
-            Neighbor::new(peer, salt_distance(&local_id, &peer_id, &local_salt))
+            let distance = salt_distance(&local_id, &peer_id, &local_salt);
+
+            Neighbor::new(peer, ctx.nb_filter.weighted_distance(&peer_id, distance))
         })
         .collect::<Vec<_>>();
 
@@ -72,7 +74,7 @@ fn update_outbound<V: NeighborValidator + 'static>(ctx: &UpdateContext<V>) {
         return;
     }
 
-    // Sort candidates by their distance, so that we start with the closest candidate.
+    // Sort candidates by their distance weighted with their score, so that we start with the closest candidate.
     candidates.sort_unstable();
 
     // Hive.go: select new candidate
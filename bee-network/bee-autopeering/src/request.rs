@@ -17,7 +17,7 @@ use crate::{
     discovery::messages::{DiscoveryRequest, VerificationRequest},
     hash,
     hash::message_hash,
-    local::Local,
+    local::{services::AUTOPEERING_SERVICE_NAME, Local},
     packet::MessageType,
     peer::peer_id::PeerId,
     peering::messages::PeeringRequest,
@@ -55,6 +55,7 @@ pub(crate) struct RequestManager {
     network_id: u32,
     source_addr_v4: Option<SocketAddr>,
     source_addr_v6: Option<SocketAddr>,
+    local: Local,
     open_requests: Arc<RwLock<HashMap<RequestKey, RequestValue>>>,
 }
 
@@ -65,12 +66,14 @@ impl RequestManager {
         network_id: u32,
         source_addr_v4: Option<SocketAddr>,
         source_addr_v6: Option<SocketAddr>,
+        local: Local,
     ) -> Self {
         Self {
             version,
             network_id,
             source_addr_v4,
             source_addr_v6,
+            local,
             open_requests: Arc::new(RwLock::new(HashMap::default())),
         }
     }
@@ -94,6 +97,14 @@ impl RequestManager {
             self.source_addr_v4
         }?;
 
+        // Advertise the external peering endpoint perceived by the other peers if it differs from the bind address,
+        // e.g. behind a NAT.
+        let source_addr = self
+            .local
+            .services()
+            .external_endpoint(AUTOPEERING_SERVICE_NAME, peer_addr.is_ipv6())
+            .unwrap_or(source_addr);
+
         let verif_req = VerificationRequest::new(self.version, self.network_id, source_addr, peer_addr);
         let timestamp = verif_req.timestamp();
 
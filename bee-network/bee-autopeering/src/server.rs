@@ -9,6 +9,7 @@ use tokio::{net::UdpSocket, sync::mpsc};
 use crate::{
     config::AutopeeringConfig,
     local::Local,
+    metrics::PacketMetrics,
     packet::{
         IncomingPacket, MessageType, OutgoingPacket, Packet, DISCOVERY_MSG_TYPE_RANGE, MAX_PACKET_SIZE,
         PEERING_MSG_TYPE_RANGE,
@@ -55,10 +56,16 @@ pub(crate) struct Server {
     local: Local,
     incoming_senders: IncomingPacketSenders,
     outgoing_rx: OutgoingPacketRx,
+    metrics: PacketMetrics,
 }
 
 impl Server {
-    pub(crate) fn new(config: ServerConfig, local: Local, incoming_senders: IncomingPacketSenders) -> (Self, ServerTx) {
+    pub(crate) fn new(
+        config: ServerConfig,
+        local: Local,
+        incoming_senders: IncomingPacketSenders,
+        metrics: PacketMetrics,
+    ) -> (Self, ServerTx) {
         let (outgoing_tx, outgoing_rx) = server_chan::<OutgoingPacket>();
 
         (
@@ -67,6 +74,7 @@ impl Server {
                 local,
                 incoming_senders,
                 outgoing_rx,
+                metrics,
             },
             outgoing_tx,
         )
@@ -78,6 +86,7 @@ impl Server {
             local,
             incoming_senders,
             outgoing_rx,
+            metrics,
         } = self;
 
         let (outgoing_tx_v4, outgoing_rx_v4) = server_chan::<OutgoingPacket>();
@@ -100,6 +109,7 @@ impl Server {
                 incoming_senders.clone(),
                 local.clone(),
                 outgoing_rx_v4,
+                metrics.clone(),
                 task_mngr,
             )
             .await
@@ -113,8 +123,15 @@ impl Server {
 
         // Bind a socket to the given IPv6 address.
         if let Some(bind_addr_v6) = config.bind_addr_v6 {
-            if let Ok(local_addr) =
-                bind_socket::<_, IP_V6_FLAG>(bind_addr_v6, incoming_senders, local, outgoing_rx_v6, task_mngr).await
+            if let Ok(local_addr) = bind_socket::<_, IP_V6_FLAG>(
+                bind_addr_v6,
+                incoming_senders,
+                local,
+                outgoing_rx_v6,
+                metrics,
+                task_mngr,
+            )
+            .await
             {
                 log::debug!("Bound IPv6 socket to {}.", local_addr);
                 socket_bound = true;
@@ -135,6 +152,7 @@ async fn bind_socket<S: PeerStore, const USE_IP_V6: bool>(
     incoming_senders: IncomingPacketSenders,
     local: Local,
     outgoing_rx: OutgoingPacketRx,
+    metrics: PacketMetrics,
     task_mngr: &mut TaskManager<S>,
 ) -> Result<SocketAddr, Box<dyn std::error::Error>> {
     // Bind the UDP socket to the configured address.
@@ -150,6 +168,7 @@ async fn bind_socket<S: PeerStore, const USE_IP_V6: bool>(
         incoming_socket,
         incoming_senders,
         bind_addr,
+        metrics,
     };
 
     let outgoing_packet_handler = OutgoingPacketHandler {
@@ -169,6 +188,7 @@ struct IncomingPacketHandler<const USE_IP_V6: bool> {
     incoming_socket: Arc<UdpSocket>,
     incoming_senders: IncomingPacketSenders,
     bind_addr: SocketAddr,
+    metrics: PacketMetrics,
 }
 
 // Note: Invalid packets from peers are not logged as warnings because the fault is not on our side.
@@ -188,6 +208,7 @@ impl<const USE_IP_V6: bool> Runnable for IncomingPacketHandler<USE_IP_V6> {
             incoming_socket,
             incoming_senders,
             bind_addr,
+            metrics,
         } = self;
 
         let mut packet_bytes = [0; READ_BUFFER_SIZE];
@@ -217,44 +238,13 @@ impl<const USE_IP_V6: bool> Runnable for IncomingPacketHandler<USE_IP_V6> {
 
                             log::trace!("Received {} bytes from {}.", n, peer_addr);
 
-                            // Decode the packet.
-                            let packet = match Packet::from_protobuf(&packet_bytes[..n]) {
-                                Ok(packet) => packet,
-                                Err(e) => {
-                                    log::trace!("Error decoding incoming packet from {}. {:?}. Ignoring packet.", peer_addr, e);
-                                    continue 'recv;
-                                }
-                            };
-
-                            // Unmarshal the message.
-                            let (msg_type, msg_bytes) = match unmarshal(packet.msg_bytes()) {
-                                Ok((msg_type, msg_bytes)) => (msg_type, msg_bytes),
-                                Err(e) => {
-                                    log::trace!("Error unmarshalling incoming message from {}. {:?}. Ignoring packet.", peer_addr, e);
-                                    continue 'recv;
-                                }
-                            };
-
-                            // Restore the peer id.
-                            let peer_id = PeerId::from_public_key(*packet.public_key());
-
-                            // Verify the packet.
-                            let message = packet.msg_bytes();
-                            let signature = packet.signature();
-                            if !packet.public_key().verify(signature, message) {
-                                log::trace!("Received packet with invalid signature");
-                                continue 'recv;
-                            }
-
-                            let packet = IncomingPacket {
-                                msg_type,
-                                msg_bytes,
-                                peer_addr,
-                                peer_id,
+                            let packet = match recv_packet(&packet_bytes[..n], peer_addr, &metrics) {
+                                Some(packet) => packet,
+                                None => continue 'recv,
                             };
 
                             // Depending on the message type, forward it to the appropriate manager.
-                            match msg_type as u8 {
+                            match packet.msg_type as u8 {
                                 t if DISCOVERY_MSG_TYPE_RANGE.contains(&t) => {
                                     // Panic: We don't allow channel send failures.
                                     discovery_tx.send(packet).expect("channel send error: discovery");
@@ -278,6 +268,55 @@ impl<const USE_IP_V6: bool> Runnable for IncomingPacketHandler<USE_IP_V6> {
     }
 }
 
+/// Decodes and authenticates the bytes received from a peer.
+pub(crate) fn recv_packet(bytes: &[u8], peer_addr: SocketAddr, metrics: &PacketMetrics) -> Option<IncomingPacket> {
+    // Decode the packet.
+    let packet = match Packet::from_protobuf(bytes) {
+        Ok(packet) => packet,
+        Err(e) => {
+            log::trace!(
+                "Error decoding incoming packet from {}. {:?}. Ignoring packet.",
+                peer_addr,
+                e
+            );
+            metrics.invalid_packets_inc();
+            return None;
+        }
+    };
+
+    // Unmarshal the message.
+    let (msg_type, msg_bytes) = match unmarshal(packet.msg_bytes()) {
+        Ok((msg_type, msg_bytes)) => (msg_type, msg_bytes),
+        Err(e) => {
+            log::trace!(
+                "Error unmarshalling incoming message from {}. {:?}. Ignoring packet.",
+                peer_addr,
+                e
+            );
+            metrics.invalid_packets_inc();
+            return None;
+        }
+    };
+
+    // Verify the packet.
+    if !packet.public_key().verify(packet.signature(), packet.msg_bytes()) {
+        log::trace!(
+            "Received packet with invalid signature from {}. Ignoring packet.",
+            peer_addr
+        );
+        metrics.invalid_signatures_inc();
+        return None;
+    }
+
+    Some(IncomingPacket {
+        msg_type,
+        msg_bytes,
+        peer_addr,
+        // Restore the peer id.
+        peer_id: PeerId::from_public_key(*packet.public_key()),
+    })
+}
+
 struct OutgoingPacketManager {
     outgoing_rx: OutgoingPacketRx,
     outgoing_tx_v4: OutgoingPacketTx,
@@ -401,12 +440,11 @@ pub(crate) fn marshal(msg_type: MessageType, msg_bytes: &[u8]) -> Vec<u8> {
 
 // TODO: @pvdrz wants to optimize this.
 pub(crate) fn unmarshal(marshalled_bytes: &[u8]) -> Result<(MessageType, Vec<u8>), ()> {
-    let msg_type = num::FromPrimitive::from_u8(marshalled_bytes[0]).ok_or(())?;
-
-    let mut msg_bytes = vec![0u8; marshalled_bytes.len() - 1];
-    msg_bytes[..].copy_from_slice(&marshalled_bytes[1..]);
+    // Packets without any data are invalid.
+    let (msg_type, msg_bytes) = marshalled_bytes.split_first().ok_or(())?;
+    let msg_type = num::FromPrimitive::from_u8(*msg_type).ok_or(())?;
 
-    Ok((msg_type, msg_bytes))
+    Ok((msg_type, msg_bytes.to_vec()))
 }
 
 pub(crate) struct ServerSocket {
@@ -9,7 +9,7 @@ use tokio::{sync::oneshot, task::JoinHandle, time};
 use crate::{
     peer::{
         lists::{ActivePeersList, ReplacementPeersList},
-        PeerStore,
+        stores, PeerStore,
     },
     time::SECOND,
 };
@@ -41,10 +41,16 @@ pub(crate) struct TaskManager<S: PeerStore> {
     peer_store: S,
     active_peers: ActivePeersList,
     replacements: ReplacementPeersList,
+    peer_expiration: Duration,
 }
 
 impl<S: PeerStore> TaskManager<S> {
-    pub(crate) fn new(peer_store: S, active_peers: ActivePeersList, replacements: ReplacementPeersList) -> Self {
+    pub(crate) fn new(
+        peer_store: S,
+        active_peers: ActivePeersList,
+        replacements: ReplacementPeersList,
+        peer_expiration: Duration,
+    ) -> Self {
         Self {
             shutdown_handles: HashMap::default(),
             shutdown_senders: HashMap::default(),
@@ -52,6 +58,7 @@ impl<S: PeerStore> TaskManager<S> {
             peer_store,
             active_peers,
             replacements,
+            peer_expiration,
         }
     }
 
@@ -107,6 +114,7 @@ impl<S: PeerStore> TaskManager<S> {
             peer_store,
             active_peers,
             replacements,
+            peer_expiration,
         } = self;
 
         // Send the shutdown signal to all receivers.
@@ -146,9 +154,7 @@ impl<S: PeerStore> TaskManager<S> {
 
         log::debug!("Flushing data to peer store...");
 
-        peer_store.delete_all()?;
-        peer_store.store_all_active(&active_peers)?;
-        peer_store.store_all_replacements(&replacements)?;
+        stores::flush(&peer_store, &active_peers, &replacements, peer_expiration)?;
 
         log::info!("Done.");
 
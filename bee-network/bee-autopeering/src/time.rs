@@ -12,6 +12,8 @@ pub(crate) const SECOND: u64 = 1;
 pub(crate) const MINUTE: u64 = 60 * SECOND;
 /// Measured in seconds.
 pub(crate) const HOUR: u64 = 60 * MINUTE;
+/// Measured in seconds.
+pub(crate) const DAY: u64 = 24 * HOUR;
 
 pub(crate) fn unix_now_secs() -> Timestamp {
     unix_time_secs(SystemTime::now())
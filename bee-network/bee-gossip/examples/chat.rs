@@ -32,7 +32,7 @@ async fn main() {
         thread,
     };
 
-    use bee_gossip::{alias, standalone::init, Event, Multiaddr, NetworkConfig, Protocol};
+    use bee_gossip::{alias, standalone::init, stores::InMemoryBanStore, Event, Multiaddr, NetworkConfig, Protocol};
     use tokio::signal::ctrl_c;
     use tokio_stream::StreamExt;
 
@@ -74,7 +74,9 @@ async fn main() {
     }));
 
     let mut _my_local_id = None;
-    let (_tx, mut rx) = init(config, keys, network_id, shutdown).await.expect("init failed");
+    let (_tx, mut rx) = init::<InMemoryBanStore>(config, keys, network_id, (), shutdown)
+        .await
+        .expect("init failed");
 
     loop {
         if let Some(event) = rx.recv().await {
@@ -91,18 +93,16 @@ async fn main() {
                 } => {
                     println!("{} joined the chat.\n", alias!(peer_id));
 
-                    thread::spawn(move || {
-                        loop {
-                            print!("Me    : ");
-                            stdout().flush().unwrap();
+                    thread::spawn(move || loop {
+                        print!("Me    : ");
+                        stdout().flush().unwrap();
 
-                            let mut msg = String::new();
+                        let mut msg = String::new();
 
-                            stdin().read_line(&mut msg).unwrap();
-                            let msg = msg.trim_end().to_string();
+                        stdin().read_line(&mut msg).unwrap();
+                        let msg = msg.trim_end().to_string();
 
-                            gossip_out.send(msg.into_bytes()).expect("send message");
-                        }
+                        gossip_out.send(msg.into_bytes().into()).expect("send message");
                     });
 
                     loop {
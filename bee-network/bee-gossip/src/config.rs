@@ -3,8 +3,13 @@
 
 #![cfg(feature = "full")]
 
-use std::{borrow::Cow, collections::HashSet};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
+use ipnet::IpNet;
 use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
 use serde::Deserialize;
 
@@ -14,10 +19,20 @@ const DEFAULT_BIND_MULTIADDR: &str = "/ip4/0.0.0.0/tcp/15600";
 
 pub const DEFAULT_RECONNECT_INTERVAL_SECS: u64 = 30;
 const MIN_RECONNECT_INTERVAL_SECS: u64 = 1;
+pub const DEFAULT_MAX_RECONNECT_INTERVAL_SECS: u64 = 300;
 
 pub const DEFAULT_MAX_UNKNOWN_PEERS: usize = 4;
 pub const DEFAULT_MAX_DISCOVERED_PEERS: usize = 4;
 
+const DEFAULT_BAN_STORAGE_PATH: &str = "./storage/mainnet/bans";
+
+pub const DEFAULT_MAX_UPLOAD_BYTES_PER_SEC: u64 = 0;
+pub const DEFAULT_MAX_PEER_UPLOAD_BYTES_PER_SEC: u64 = 0;
+
+pub const DEFAULT_MAX_PENDING_INCOMING: usize = 16;
+pub const DEFAULT_MAX_CONNECTIONS_PER_IP: usize = 4;
+pub const DEFAULT_MAX_CONNECTIONS_PER_SUBNET: usize = 16;
+
 /// [`NetworkConfigBuilder`] errors.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -41,6 +56,10 @@ pub enum Error {
     #[error("invalid port protocol")]
     InvalidPortProtocol,
 
+    /// The provided [`Multiaddr`] requires a transport that is not supported.
+    #[error("unsupported transport: QUIC requires the `quic` feature and a `/udp/<port>/quic-v1` QUIC bind address")]
+    UnsupportedTransport,
+
     /// The peer was already added.
     #[error("static peer {} already added", alias!(.0))]
     DuplicateStaticPeer(PeerId),
@@ -56,16 +75,26 @@ pub enum Error {
     /// The provided [`Multiaddr`] lacks the P2p [`Protocol`].
     #[error("invalid p2p multiaddr Did you forget to add `.../p2p/12D3Koo...`?")]
     MissingP2pProtocol,
+
+    /// Parsing of a CIDR (e.g. `10.0.0.0/8`) failed.
+    #[error("parsing of '{0}' to a CIDR failed")]
+    InvalidCidr(String),
 }
 
 /// The network configuration.
 #[derive(Clone)]
 pub struct NetworkConfig {
     pub(crate) bind_multiaddr: Multiaddr,
+    pub(crate) quic_bind_multiaddr: Option<Multiaddr>,
     pub(crate) reconnect_interval_secs: u64,
+    pub(crate) max_reconnect_interval_secs: u64,
     pub(crate) max_unknown_peers: usize,
     pub(crate) max_discovered_peers: usize,
     pub(crate) static_peers: HashSet<Peer>,
+    pub(crate) ban_storage_path: PathBuf,
+    pub(crate) connection_gater: ConnectionGaterConfig,
+    pub(crate) max_upload_bytes_per_sec: u64,
+    pub(crate) max_peer_upload_bytes_per_sec: u64,
 }
 
 impl NetworkConfig {
@@ -80,7 +109,7 @@ impl NetworkConfig {
     }
 
     /// Returns an in-memory config builder to construct a [`NetworkConfig`] iteratively.
-    #[cfg(test)]
+    #[cfg(any(test, feature = "in-memory"))]
     pub fn build_in_memory() -> InMemoryNetworkConfigBuilder {
         InMemoryNetworkConfigBuilder::new()
     }
@@ -97,14 +126,7 @@ impl NetworkConfig {
             addr = resolve_dns_multiaddr(dns)?;
         }
 
-        // Panic:
-        // The builder ensures that the following unwraps are fine.
-        let port = self.bind_multiaddr.pop().unwrap();
-
-        let _ = self.bind_multiaddr.pop().unwrap();
-
-        self.bind_multiaddr.push(addr);
-        self.bind_multiaddr.push(port);
+        self.bind_multiaddr = replace_protocol(&self.bind_multiaddr, 0, addr);
 
         Ok(())
     }
@@ -117,8 +139,7 @@ impl NetworkConfig {
             return Err(Error::InvalidPortProtocol);
         }
 
-        self.bind_multiaddr.pop();
-        self.bind_multiaddr.push(port);
+        self.bind_multiaddr = replace_protocol(&self.bind_multiaddr, 1, port);
 
         Ok(())
     }
@@ -146,11 +167,21 @@ impl NetworkConfig {
         &self.bind_multiaddr
     }
 
+    /// Returns the configured QUIC bind address as a [`Multiaddr`], if any.
+    pub fn quic_bind_multiaddr(&self) -> Option<&Multiaddr> {
+        self.quic_bind_multiaddr.as_ref()
+    }
+
     /// Returns the number of seconds at which reconnect attempts occur.
     pub fn reconnect_interval_secs(&self) -> u64 {
         self.reconnect_interval_secs
     }
 
+    /// Returns the maximum number of seconds the reconnect attempts to a peer are backed off to.
+    pub fn max_reconnect_interval_secs(&self) -> u64 {
+        self.max_reconnect_interval_secs
+    }
+
     /// Returns the maximum number of unknown peers that are allowed to connect.
     pub fn max_unknown_peers(&self) -> usize {
         self.max_unknown_peers
@@ -165,6 +196,26 @@ impl NetworkConfig {
     pub fn static_peers(&self) -> &HashSet<Peer> {
         &self.static_peers
     }
+
+    /// Returns the path of the storage of banned peers and addresses.
+    pub fn ban_storage_path(&self) -> &Path {
+        &self.ban_storage_path
+    }
+
+    /// Returns the configuration of the inbound connection gating.
+    pub fn connection_gater(&self) -> &ConnectionGaterConfig {
+        &self.connection_gater
+    }
+
+    /// Returns the maximum number of bytes per second sent to all peers combined, 0 meaning unlimited.
+    pub fn max_upload_bytes_per_sec(&self) -> u64 {
+        self.max_upload_bytes_per_sec
+    }
+
+    /// Returns the maximum number of bytes per second sent to a single peer, 0 meaning unlimited.
+    pub fn max_peer_upload_bytes_per_sec(&self) -> u64 {
+        self.max_peer_upload_bytes_per_sec
+    }
 }
 
 fn resolve_dns_multiaddr(dns: Cow<'_, str>) -> Result<Protocol, Error> {
@@ -182,16 +233,98 @@ fn resolve_dns_multiaddr(dns: Cow<'_, str>) -> Result<Protocol, Error> {
     }
 }
 
+/// Validates a TCP (`/ip4/<ip>/tcp/<port>`) or QUIC (`/ip4/<ip>/udp/<port>/quic-v1`) bind address, resolving its
+/// domain name if necessary.
+fn validate_bind_multiaddr(mut multiaddr: Multiaddr, quic: bool) -> Result<Multiaddr, Error> {
+    let mut valid = false;
+    let mut is_dns = false;
+
+    for (i, p) in multiaddr.iter().enumerate() {
+        match i {
+            0 => {
+                if !matches!(p, Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Dns(_)) {
+                    return Err(Error::InvalidProtocol(0));
+                }
+
+                if matches!(p, Protocol::Dns(_)) {
+                    is_dns = true;
+                }
+            }
+            1 => match p {
+                Protocol::Tcp(_) if !quic => valid = true,
+                // QUIC runs on top of UDP, as in `/udp/<port>/quic-v1`.
+                Protocol::Udp(_) if quic => {}
+                Protocol::Udp(_) => return Err(Error::UnsupportedTransport),
+                _ => return Err(Error::InvalidProtocol(1)),
+            },
+            2 if quic => {
+                if !matches!(p, Protocol::QuicV1) {
+                    return Err(Error::UnsupportedTransport);
+                }
+                valid = true;
+            }
+            _ => return Err(Error::MultiaddrOverspecified),
+        }
+    }
+    if !valid {
+        return Err(Error::MultiaddrUnderspecified);
+    }
+
+    if is_dns {
+        // Panic:
+        // We know at this point, that `multiaddr` is valid, so unwrapping is fine.
+        let port = multiaddr
+            .iter()
+            .find_map(|p| match p {
+                Protocol::Tcp(port) | Protocol::Udp(port) => Some(port),
+                _ => None,
+            })
+            .unwrap();
+        let ip = if let Some(Protocol::Dns(dns)) = multiaddr.iter().next() {
+            let socket_dns = {
+                let mut socket_addr = String::with_capacity(16);
+                socket_addr.push_str(&dns);
+                socket_addr.push(':');
+                socket_addr.push_str(&port.to_string());
+                socket_addr
+            };
+
+            resolve_dns_multiaddr(socket_dns.into())?
+        } else {
+            unreachable!("already checked");
+        };
+
+        multiaddr = replace_protocol(&multiaddr, 0, ip);
+    }
+
+    Ok(multiaddr)
+}
+
+/// Returns a copy of the given [`Multiaddr`] with the protocol at the given position replaced.
+fn replace_protocol(multiaddr: &Multiaddr, position: usize, protocol: Protocol) -> Multiaddr {
+    multiaddr
+        .iter()
+        .enumerate()
+        .map(|(i, p)| if i == position { protocol.clone() } else { p })
+        .collect()
+}
+
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             // Panic:
             // Unwrapping is fine, because we made sure that the default is parsable.
             bind_multiaddr: DEFAULT_BIND_MULTIADDR.parse().unwrap(),
+            quic_bind_multiaddr: None,
             reconnect_interval_secs: DEFAULT_RECONNECT_INTERVAL_SECS,
+            max_reconnect_interval_secs: DEFAULT_MAX_RECONNECT_INTERVAL_SECS,
             max_unknown_peers: DEFAULT_MAX_UNKNOWN_PEERS,
             max_discovered_peers: DEFAULT_MAX_DISCOVERED_PEERS,
             static_peers: Default::default(),
+            ban_storage_path: DEFAULT_BAN_STORAGE_PATH.into(),
+            connection_gater: ConnectionGaterConfig::default(),
+            max_upload_bytes_per_sec: DEFAULT_MAX_UPLOAD_BYTES_PER_SEC,
+            max_peer_upload_bytes_per_sec: DEFAULT_MAX_PEER_UPLOAD_BYTES_PER_SEC,
         }
     }
 }
@@ -202,13 +335,25 @@ impl Default for NetworkConfig {
 pub struct NetworkConfigBuilder {
     #[serde(alias = "bindAddress", alias = "bind_address")]
     bind_multiaddr: Option<Multiaddr>,
+    #[serde(alias = "quicBindAddress", alias = "quic_bind_address")]
+    quic_bind_multiaddr: Option<Multiaddr>,
     #[serde(alias = "reconnectIntervalSecs")]
     reconnect_interval_secs: Option<u64>,
+    #[serde(alias = "maxReconnectIntervalSecs")]
+    max_reconnect_interval_secs: Option<u64>,
     #[serde(alias = "maxUnknownPeers")]
     max_unknown_peers: Option<usize>,
     #[serde(alias = "maxDiscoveredPeers")]
     max_discovered_peers: Option<usize>,
+    #[serde(alias = "banStoragePath")]
+    ban_storage_path: Option<PathBuf>,
     peering: ManualPeeringConfigBuilder,
+    #[serde(default, alias = "connectionGater")]
+    connection_gater: ConnectionGaterConfigBuilder,
+    #[serde(alias = "maxUploadBytesPerSec")]
+    max_upload_bytes_per_sec: Option<u64>,
+    #[serde(alias = "maxPeerUploadBytesPerSec")]
+    max_peer_upload_bytes_per_sec: Option<u64>,
 }
 
 impl NetworkConfigBuilder {
@@ -217,63 +362,25 @@ impl NetworkConfigBuilder {
         Self::default()
     }
 
-    /// Specifies the bind addresses.
-    pub fn with_bind_multiaddr(mut self, mut multiaddr: Multiaddr) -> Result<Self, Error> {
-        let mut valid = false;
-        let mut is_dns = false;
+    /// Specifies the TCP bind address.
+    pub fn with_bind_multiaddr(mut self, multiaddr: Multiaddr) -> Result<Self, Error> {
+        let multiaddr = validate_bind_multiaddr(multiaddr, false)?;
 
-        for (i, p) in multiaddr.iter().enumerate() {
-            match i {
-                0 => {
-                    if !matches!(p, Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Dns(_)) {
-                        return Err(Error::InvalidProtocol(0));
-                    }
+        self.bind_multiaddr.replace(multiaddr);
+        Ok(self)
+    }
 
-                    if matches!(p, Protocol::Dns(_)) {
-                        is_dns = true;
-                    }
-                }
-                1 => {
-                    if !matches!(p, Protocol::Tcp(_)) {
-                        return Err(Error::InvalidProtocol(1));
-                    }
-                    valid = true;
-                }
-                _ => return Err(Error::MultiaddrOverspecified),
-            }
-        }
-        if !valid {
-            return Err(Error::MultiaddrUnderspecified);
+    /// Specifies the QUIC bind address, listened on in addition to the TCP one.
+    ///
+    /// Requires the `quic` feature.
+    pub fn with_quic_bind_multiaddr(mut self, multiaddr: Multiaddr) -> Result<Self, Error> {
+        if cfg!(not(feature = "quic")) {
+            return Err(Error::UnsupportedTransport);
         }
 
-        if is_dns {
-            let port = multiaddr.pop().unwrap();
-            let port = if let Protocol::Tcp(port) = port {
-                port
-            } else {
-                unreachable!("already checked");
-            };
-            // Panic:
-            // We know at this point, that `multiaddr` is valid, so unwrapping is fine.
-            let ip = if let Protocol::Dns(dns) = multiaddr.pop().unwrap() {
-                let socket_dns = {
-                    let mut socket_addr = String::with_capacity(16);
-                    socket_addr.push_str(&dns);
-                    socket_addr.push(':');
-                    socket_addr.push_str(&port.to_string());
-                    socket_addr
-                };
-
-                resolve_dns_multiaddr(socket_dns.into())?
-            } else {
-                unreachable!("already checked");
-            };
-
-            multiaddr.push(ip);
-            multiaddr.push(Protocol::Tcp(port));
-        }
+        let multiaddr = validate_bind_multiaddr(multiaddr, true)?;
 
-        self.bind_multiaddr.replace(multiaddr);
+        self.quic_bind_multiaddr.replace(multiaddr);
         Ok(self)
     }
 
@@ -286,6 +393,14 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Specifies the maximum interval (in seconds) the reconnect attempts to an unresponsive peer are backed off to.
+    ///
+    /// Values below the reconnect interval are raised to it.
+    pub fn with_max_reconnect_interval_secs(mut self, secs: u64) -> Self {
+        self.max_reconnect_interval_secs.replace(secs);
+        self
+    }
+
     /// Specifies the maximum number of gossip connections with unknown peers.
     pub fn with_max_unknown_peers(mut self, n: usize) -> Self {
         self.max_unknown_peers.replace(n);
@@ -298,31 +413,76 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Specifies the path of the storage of banned peers and addresses.
+    pub fn with_ban_storage_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ban_storage_path.replace(path.into());
+        self
+    }
+
+    /// Specifies the gating of inbound connections.
+    pub fn with_connection_gater(mut self, builder: ConnectionGaterConfigBuilder) -> Self {
+        self.connection_gater = builder;
+        self
+    }
+
+    /// Specifies the maximum number of bytes per second sent to all peers combined, 0 disabling the limit.
+    pub fn with_max_upload_bytes_per_sec(mut self, bytes: u64) -> Self {
+        self.max_upload_bytes_per_sec.replace(bytes);
+        self
+    }
+
+    /// Specifies the maximum number of bytes per second sent to a single peer, 0 disabling the limit.
+    pub fn with_max_peer_upload_bytes_per_sec(mut self, bytes: u64) -> Self {
+        self.max_peer_upload_bytes_per_sec.replace(bytes);
+        self
+    }
+
     /// Builds the network config.
     pub fn finish(self) -> Result<NetworkConfig, Error> {
+        let reconnect_interval_secs = self.reconnect_interval_secs.unwrap_or(DEFAULT_RECONNECT_INTERVAL_SECS);
+        // A deserialized QUIC bind address hasn't been validated yet.
+        let quic_bind_multiaddr = match self.quic_bind_multiaddr {
+            Some(_) if cfg!(not(feature = "quic")) => return Err(Error::UnsupportedTransport),
+            Some(multiaddr) => Some(validate_bind_multiaddr(multiaddr, true)?),
+            None => None,
+        };
+
         Ok(NetworkConfig {
             bind_multiaddr: self
                 .bind_multiaddr
                 // Panic:
                 // We made sure that the default is parsable.
                 .unwrap_or_else(|| DEFAULT_BIND_MULTIADDR.parse().unwrap()),
-            reconnect_interval_secs: self.reconnect_interval_secs.unwrap_or(DEFAULT_RECONNECT_INTERVAL_SECS),
+            quic_bind_multiaddr,
+            reconnect_interval_secs,
+            max_reconnect_interval_secs: self
+                .max_reconnect_interval_secs
+                .unwrap_or(DEFAULT_MAX_RECONNECT_INTERVAL_SECS)
+                .max(reconnect_interval_secs),
             max_unknown_peers: self.max_unknown_peers.unwrap_or(DEFAULT_MAX_UNKNOWN_PEERS),
             max_discovered_peers: self.max_discovered_peers.unwrap_or(DEFAULT_MAX_DISCOVERED_PEERS),
             static_peers: self.peering.finish()?.peers,
+            ban_storage_path: self.ban_storage_path.unwrap_or_else(|| DEFAULT_BAN_STORAGE_PATH.into()),
+            connection_gater: self.connection_gater.finish()?,
+            max_upload_bytes_per_sec: self
+                .max_upload_bytes_per_sec
+                .unwrap_or(DEFAULT_MAX_UPLOAD_BYTES_PER_SEC),
+            max_peer_upload_bytes_per_sec: self
+                .max_peer_upload_bytes_per_sec
+                .unwrap_or(DEFAULT_MAX_PEER_UPLOAD_BYTES_PER_SEC),
         })
     }
 }
 
 /// An in-memory network config builder, that becomes useful as part of integration testing.
-#[cfg(test)]
+#[cfg(any(test, feature = "in-memory"))]
 #[derive(Default)]
 #[must_use]
 pub struct InMemoryNetworkConfigBuilder {
     bind_multiaddr: Option<Multiaddr>,
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "in-memory"))]
 impl InMemoryNetworkConfigBuilder {
     /// Creates a new default builder.
     pub fn new() -> Self {
@@ -354,14 +514,142 @@ impl InMemoryNetworkConfigBuilder {
             bind_multiaddr: self
                 .bind_multiaddr
                 .unwrap_or_else(|| DEFAULT_BIND_MULTIADDR_MEM.parse().unwrap()),
+            quic_bind_multiaddr: None,
             reconnect_interval_secs: DEFAULT_RECONNECT_INTERVAL_SECS,
+            max_reconnect_interval_secs: DEFAULT_MAX_RECONNECT_INTERVAL_SECS,
             max_unknown_peers: DEFAULT_MAX_UNKNOWN_PEERS,
             max_discovered_peers: DEFAULT_MAX_DISCOVERED_PEERS,
             static_peers: Default::default(),
+            ban_storage_path: DEFAULT_BAN_STORAGE_PATH.into(),
+            connection_gater: ConnectionGaterConfig::default(),
+            max_upload_bytes_per_sec: DEFAULT_MAX_UPLOAD_BYTES_PER_SEC,
+            max_peer_upload_bytes_per_sec: DEFAULT_MAX_PEER_UPLOAD_BYTES_PER_SEC,
+        }
+    }
+}
+
+/// The configuration of the gating of inbound connections.
+#[derive(Clone, Debug)]
+pub struct ConnectionGaterConfig {
+    pub(crate) max_pending_incoming: usize,
+    pub(crate) max_connections_per_ip: usize,
+    pub(crate) max_connections_per_subnet: usize,
+    pub(crate) allowed_cidrs: Vec<IpNet>,
+    pub(crate) denied_cidrs: Vec<IpNet>,
+}
+
+impl ConnectionGaterConfig {
+    /// Returns the maximum number of inbound connections that are allowed to be negotiated at the same time.
+    pub fn max_pending_incoming(&self) -> usize {
+        self.max_pending_incoming
+    }
+
+    /// Returns the maximum number of inbound connections from a single IP address.
+    pub fn max_connections_per_ip(&self) -> usize {
+        self.max_connections_per_ip
+    }
+
+    /// Returns the maximum number of inbound connections from a single subnet (`/24` for IPv4, `/48` for IPv6).
+    pub fn max_connections_per_subnet(&self) -> usize {
+        self.max_connections_per_subnet
+    }
+
+    /// Returns the CIDRs inbound connections are allowed from. If empty, all CIDRs are allowed.
+    pub fn allowed_cidrs(&self) -> &[IpNet] {
+        &self.allowed_cidrs
+    }
+
+    /// Returns the CIDRs inbound connections are denied from.
+    pub fn denied_cidrs(&self) -> &[IpNet] {
+        &self.denied_cidrs
+    }
+}
+
+impl Default for ConnectionGaterConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_incoming: DEFAULT_MAX_PENDING_INCOMING,
+            max_connections_per_ip: DEFAULT_MAX_CONNECTIONS_PER_IP,
+            max_connections_per_subnet: DEFAULT_MAX_CONNECTIONS_PER_SUBNET,
+            allowed_cidrs: Vec::new(),
+            denied_cidrs: Vec::new(),
         }
     }
 }
 
+/// A builder for the gating of inbound connections.
+#[derive(Default, Deserialize, Eq, PartialEq)]
+#[must_use]
+pub struct ConnectionGaterConfigBuilder {
+    #[serde(alias = "maxPendingIncoming")]
+    max_pending_incoming: Option<usize>,
+    #[serde(alias = "maxConnectionsPerIp")]
+    max_connections_per_ip: Option<usize>,
+    #[serde(alias = "maxConnectionsPerSubnet")]
+    max_connections_per_subnet: Option<usize>,
+    #[serde(alias = "allowedCidrs")]
+    allowed_cidrs: Option<Vec<String>>,
+    #[serde(alias = "deniedCidrs")]
+    denied_cidrs: Option<Vec<String>>,
+}
+
+impl ConnectionGaterConfigBuilder {
+    /// Creates a new default builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specifies the maximum number of inbound connections that are allowed to be negotiated at the same time.
+    pub fn with_max_pending_incoming(mut self, n: usize) -> Self {
+        self.max_pending_incoming.replace(n);
+        self
+    }
+
+    /// Specifies the maximum number of inbound connections from a single IP address.
+    pub fn with_max_connections_per_ip(mut self, n: usize) -> Self {
+        self.max_connections_per_ip.replace(n);
+        self
+    }
+
+    /// Specifies the maximum number of inbound connections from a single subnet.
+    pub fn with_max_connections_per_subnet(mut self, n: usize) -> Self {
+        self.max_connections_per_subnet.replace(n);
+        self
+    }
+
+    /// Specifies the CIDRs (e.g. `10.0.0.0/8`) inbound connections are allowed from.
+    pub fn with_allowed_cidrs(mut self, cidrs: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_cidrs.replace(cidrs.into_iter().collect());
+        self
+    }
+
+    /// Specifies the CIDRs (e.g. `10.0.0.0/8`) inbound connections are denied from.
+    pub fn with_denied_cidrs(mut self, cidrs: impl IntoIterator<Item = String>) -> Self {
+        self.denied_cidrs.replace(cidrs.into_iter().collect());
+        self
+    }
+
+    /// Builds the connection gater config.
+    pub fn finish(self) -> Result<ConnectionGaterConfig, Error> {
+        Ok(ConnectionGaterConfig {
+            max_pending_incoming: self.max_pending_incoming.unwrap_or(DEFAULT_MAX_PENDING_INCOMING),
+            max_connections_per_ip: self.max_connections_per_ip.unwrap_or(DEFAULT_MAX_CONNECTIONS_PER_IP),
+            max_connections_per_subnet: self
+                .max_connections_per_subnet
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS_PER_SUBNET),
+            allowed_cidrs: parse_cidrs(self.allowed_cidrs.unwrap_or_default())?,
+            denied_cidrs: parse_cidrs(self.denied_cidrs.unwrap_or_default())?,
+        })
+    }
+}
+
+fn parse_cidrs(cidrs: Vec<String>) -> Result<Vec<IpNet>, Error> {
+    cidrs
+        .into_iter()
+        .map(|cidr| cidr.parse().map_err(|_| Error::InvalidCidr(cidr)))
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct ManualPeeringConfig {
     pub peers: HashSet<Peer>,
@@ -495,6 +783,70 @@ mod tests {
             .finish();
     }
 
+    #[test]
+    fn create_with_builder_and_quic_bind_address_as_tcp_bind_address() {
+        let result = NetworkConfig::build().with_bind_multiaddr("/ip4/127.0.0.1/udp/1337/quic-v1".parse().unwrap());
+
+        assert!(matches!(result, Err(Error::UnsupportedTransport)));
+    }
+
+    #[test]
+    #[cfg(not(feature = "quic"))]
+    fn create_with_builder_and_quic_bind_address_without_quic_feature() {
+        let result =
+            NetworkConfig::build().with_quic_bind_multiaddr("/ip4/127.0.0.1/udp/1337/quic-v1".parse().unwrap());
+
+        assert!(matches!(result, Err(Error::UnsupportedTransport)));
+    }
+
+    #[test]
+    #[cfg(feature = "quic")]
+    fn create_with_builder_and_quic_draft_bind_address() {
+        let result = NetworkConfig::build().with_quic_bind_multiaddr("/ip4/127.0.0.1/udp/1337/quic".parse().unwrap());
+
+        assert!(matches!(result, Err(Error::UnsupportedTransport)));
+    }
+
+    #[test]
+    #[cfg(feature = "quic")]
+    fn create_with_builder_and_tcp_bind_address_as_quic_bind_address() {
+        let result = NetworkConfig::build().with_quic_bind_multiaddr("/ip4/127.0.0.1/tcp/1337".parse().unwrap());
+
+        assert!(matches!(result, Err(Error::InvalidProtocol(1))));
+    }
+
+    #[test]
+    #[cfg(feature = "quic")]
+    fn create_with_builder_and_valid_quic_bind_address() {
+        let config = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/127.0.0.1/tcp/1337".parse().unwrap())
+            .unwrap()
+            .with_quic_bind_multiaddr("/dns/localhost/udp/1337/quic-v1".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            config.bind_multiaddr(),
+            &"/ip4/127.0.0.1/tcp/1337".parse::<Multiaddr>().unwrap()
+        );
+        assert!(matches!(
+            config.quic_bind_multiaddr().unwrap().iter().collect::<Vec<_>>()[..],
+            [
+                Protocol::Ip4(_) | Protocol::Ip6(_),
+                Protocol::Udp(1337),
+                Protocol::QuicV1
+            ]
+        ));
+    }
+
+    #[test]
+    fn create_with_builder_without_quic_bind_address() {
+        let config = NetworkConfig::build().finish().unwrap();
+
+        assert!(config.quic_bind_multiaddr().is_none());
+    }
+
     #[test]
     #[should_panic]
     fn create_with_mem_builder_and_non_mem_multiaddr() {
@@ -14,6 +14,10 @@ pub enum Error {
     #[error("failed to announce static peers")]
     StaticPeersAnnouncementFailed,
 
+    /// Opening the ban store or loading the bans from it failed.
+    #[error("failed to load bans: {0}")]
+    LoadingBansFailed(String),
+
     /// Creating transport layer failed.
     #[error("failed to create transport layer")]
     CreatingTransportFailed,
@@ -3,15 +3,20 @@
 
 #![cfg(feature = "full")]
 
-use libp2p::identity;
+use std::sync::Arc;
+
+use libp2p::{identity, multiaddr::Protocol};
 use once_cell::sync::OnceCell;
 
 use super::{
     config::NetworkConfig,
     error::Error,
     peer::{
+        ban::NetworkBanList,
         info::{PeerInfo, PeerRelation},
-        list::{PeerList, PeerListWrapper},
+        list::{NetworkConnectionHistory, PeerList, PeerListWrapper},
+        peering::NetworkPeering,
+        stores::BanStore,
     },
     service::{
         command::{command_channel, NetworkCommandSender},
@@ -23,19 +28,24 @@ use crate::{
     alias,
     network::host::NetworkHostConfig,
     service::host::{self, ServiceHostConfig},
-    swarm::builder::build_swarm,
+    swarm::{
+        builder::build_swarm,
+        gater::{ConnectionGater, ConnectionGaterMetrics},
+        protocols::iota_gossip::limiter::UploadLimiter,
+    },
 };
 
 pub mod global {
     use super::*;
 
     static RECONNECT_INTERVAL_SECS: OnceCell<u64> = OnceCell::new();
+    static MAX_RECONNECT_INTERVAL_SECS: OnceCell<u64> = OnceCell::new();
     static NETWORK_ID: OnceCell<u64> = OnceCell::new();
     static MAX_UNKNOWN_PEERS: OnceCell<usize> = OnceCell::new();
     static MAX_DISCOVERED_PEERS: OnceCell<usize> = OnceCell::new();
 
     pub fn set_reconnect_interval_secs(reconnect_interval_secs: u64) {
-        if cfg!(test) {
+        if cfg!(any(test, feature = "in-memory")) {
             let _ = RECONNECT_INTERVAL_SECS.set(reconnect_interval_secs);
         } else {
             RECONNECT_INTERVAL_SECS
@@ -48,8 +58,22 @@ pub mod global {
         *RECONNECT_INTERVAL_SECS.get().expect("oncecell get")
     }
 
+    pub fn set_max_reconnect_interval_secs(max_reconnect_interval_secs: u64) {
+        if cfg!(any(test, feature = "in-memory")) {
+            let _ = MAX_RECONNECT_INTERVAL_SECS.set(max_reconnect_interval_secs);
+        } else {
+            MAX_RECONNECT_INTERVAL_SECS
+                .set(max_reconnect_interval_secs)
+                .expect("oncecell set");
+        }
+    }
+
+    pub fn max_reconnect_interval_secs() -> u64 {
+        *MAX_RECONNECT_INTERVAL_SECS.get().expect("oncecell get")
+    }
+
     pub fn set_network_id(network_id: u64) {
-        if cfg!(test) {
+        if cfg!(any(test, feature = "in-memory")) {
             let _ = NETWORK_ID.set(network_id);
         } else {
             NETWORK_ID.set(network_id).expect("oncecell set");
@@ -61,7 +85,7 @@ pub mod global {
     }
 
     pub fn set_max_unknown_peers(max_unknown_peers: usize) {
-        if cfg!(test) {
+        if cfg!(any(test, feature = "in-memory")) {
             let _ = MAX_UNKNOWN_PEERS.set(max_unknown_peers);
         } else {
             MAX_UNKNOWN_PEERS.set(max_unknown_peers).expect("oncecell set");
@@ -73,7 +97,7 @@ pub mod global {
     }
 
     pub fn set_max_discovered_peers(max_discovered_peers: usize) {
-        if cfg!(test) {
+        if cfg!(any(test, feature = "in-memory")) {
             let _ = MAX_DISCOVERED_PEERS.set(max_discovered_peers);
         } else {
             MAX_DISCOVERED_PEERS.set(max_discovered_peers).expect("oncecell set");
@@ -95,14 +119,15 @@ pub mod standalone {
     use crate::{network::host::standalone::NetworkHost, service::host::standalone::ServiceHost};
 
     /// Initializes the network.
-    pub async fn init(
+    pub async fn init<S: BanStore>(
         config: NetworkConfig,
         keys: Keypair,
         network_id: u64,
+        ban_store_config: S::Config,
         shutdown: impl Future + Send + Unpin + 'static,
     ) -> Result<(NetworkCommandSender, NetworkEventReceiver), Error> {
-        let (network_config, service_config, network_command_sender, network_event_receiver) =
-            super::init(config, keys, network_id)?;
+        let (network_config, service_config, network_command_sender, network_event_receiver, _, _, _, _) =
+            super::init::<S>(config, keys, network_id, ban_store_config)?;
 
         let (shutdown_signal_tx1, shutdown_signal_rx1) = oneshot::channel::<()>();
         let (shutdown_signal_tx2, shutdown_signal_rx2) = oneshot::channel::<()>();
@@ -129,46 +154,72 @@ pub mod integrated {
     use crate::{network::host::integrated::NetworkHost, service::host::integrated::ServiceHost};
 
     /// Initializes the network.
-    pub async fn init<N: Node>(
+    pub async fn init<N: Node, S: BanStore>(
         config: NetworkConfig,
         keys: Keypair,
         network_id: u64,
+        ban_store_config: S::Config,
         mut node_builder: N::Builder,
     ) -> Result<(N::Builder, NetworkEventReceiver), Error> {
-        let (host_config, service_config, network_command_sender, network_event_receiver) =
-            super::init(config, keys, network_id)?;
+        let (
+            host_config,
+            service_config,
+            network_command_sender,
+            network_event_receiver,
+            network_ban_list,
+            network_connection_history,
+            network_peering,
+            connection_gater_metrics,
+        ) = super::init::<S>(config, keys, network_id, ban_store_config)?;
 
         node_builder = node_builder
             .with_worker_cfg::<NetworkHost>(host_config)
             .with_worker_cfg::<ServiceHost>(service_config)
-            .with_resource(network_command_sender);
+            .with_resource(network_command_sender)
+            .with_resource(network_ban_list)
+            .with_resource(network_connection_history)
+            .with_resource(network_peering)
+            .with_resource(connection_gater_metrics);
 
         Ok((node_builder, network_event_receiver))
     }
 }
 
-fn init(
+#[allow(clippy::type_complexity)]
+fn init<S: BanStore>(
     config: NetworkConfig,
     keys: Keypair,
     network_id: u64,
+    ban_store_config: S::Config,
 ) -> Result<
     (
         NetworkHostConfig,
         ServiceHostConfig,
         NetworkCommandSender,
         NetworkEventReceiver,
+        NetworkBanList,
+        NetworkConnectionHistory,
+        NetworkPeering,
+        ConnectionGaterMetrics,
     ),
     Error,
 > {
     let NetworkConfig {
         bind_multiaddr,
+        quic_bind_multiaddr,
         reconnect_interval_secs,
+        max_reconnect_interval_secs,
         max_unknown_peers,
         max_discovered_peers,
         static_peers: peers,
+        ban_storage_path: _,
+        connection_gater,
+        max_upload_bytes_per_sec,
+        max_peer_upload_bytes_per_sec,
     } = config;
 
     global::set_reconnect_interval_secs(reconnect_interval_secs);
+    global::set_max_reconnect_interval_secs(max_reconnect_interval_secs);
     global::set_network_id(network_id);
     global::set_max_unknown_peers(max_unknown_peers);
     global::set_max_discovered_peers(max_discovered_peers);
@@ -179,7 +230,7 @@ fn init(
     let (event_sender, event_receiver) = event_channel::<Event>();
     let (internal_event_sender, internal_event_receiver) = event_channel::<InternalEvent>();
 
-    let local_keys = identity::Keypair::Ed25519(keys);
+    let local_keys = identity::Keypair::from(keys);
     let local_id = PeerId::from_public_key(&local_keys.public());
 
     event_sender
@@ -187,7 +238,26 @@ fn init(
         .map_err(|_| Error::LocalIdAnnouncementFailed)?;
 
     // TODO: rename to PeerStateMap.
-    let peerlist = PeerListWrapper::new(PeerList::from_peers(local_id, peers.iter().cloned().collect()));
+    let mut peerlist = PeerList::from_peers(local_id, peers.iter().cloned().collect());
+
+    // Restore the bans of previous runs. Expired ones are lifted by the peer state checker.
+    let ban_store = S::new(ban_store_config).map_err(|e| Error::LoadingBansFailed(e.to_string()))?;
+
+    for (peer_id, ban) in ban_store
+        .fetch_all_peer_bans()
+        .map_err(|e| Error::LoadingBansFailed(e.to_string()))?
+    {
+        let _ = peerlist.ban_peer(peer_id, ban);
+    }
+
+    for (address, ban) in ban_store
+        .fetch_all_address_bans()
+        .map_err(|e| Error::LoadingBansFailed(e.to_string()))?
+    {
+        let _ = peerlist.ban_address(address, ban);
+    }
+
+    let peerlist = PeerListWrapper::new(peerlist);
 
     // Publish which known peers were added initially.
     for peer in peers.into_iter() {
@@ -204,8 +274,12 @@ fn init(
             .map_err(|_| Error::StaticPeersAnnouncementFailed)?;
     }
 
-    // Create the transport layer.
-    let swarm = build_swarm(&local_keys).map_err(|_| Error::CreatingTransportFailed)?;
+    // Create the transport layer, in-memory if the node binds to a memory address.
+    let in_memory = matches!(bind_multiaddr.iter().next(), Some(Protocol::Memory(_)));
+    let connection_gater = ConnectionGater::new(connection_gater);
+    let connection_gater_metrics = connection_gater.metrics().clone();
+    let swarm =
+        build_swarm(&local_keys, in_memory, connection_gater.clone()).map_err(|_| Error::CreatingTransportFailed)?;
 
     let network_host_config = NetworkHostConfig {
         internal_event_sender: internal_event_sender.clone(),
@@ -213,6 +287,8 @@ fn init(
         peerlist: peerlist.clone(),
         swarm,
         bind_multiaddr,
+        quic_bind_multiaddr,
+        connection_gater,
     };
 
     let service_host_config = ServiceHostConfig {
@@ -226,7 +302,9 @@ fn init(
             commands: command_receiver,
             internal_events: internal_event_receiver,
         },
-        peerlist,
+        peerlist: peerlist.clone(),
+        ban_store: Arc::new(ban_store),
+        upload_limiter: UploadLimiter::new(max_upload_bytes_per_sec, max_peer_upload_bytes_per_sec),
     };
 
     let network_command_sender = NetworkCommandSender::new(command_sender);
@@ -237,5 +315,9 @@ fn init(
         service_host_config,
         network_command_sender,
         network_event_receiver,
+        NetworkBanList::new(peerlist.clone()),
+        NetworkConnectionHistory::new(peerlist.clone()),
+        NetworkPeering::new(peerlist),
+        connection_gater_metrics,
     ))
 }
@@ -22,27 +22,40 @@ mod tests;
 // Exported only with "full" feature flag.
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use libp2p_core::identity::{
+pub use libp2p_identity::{
     ed25519::{Keypair, SecretKey},
     PublicKey,
 };
 #[doc(inline)]
-pub use libp2p_core::{
-    multiaddr::{Multiaddr, Protocol},
-    PeerId,
-};
+pub use libp2p_core::multiaddr::{Multiaddr, Protocol};
+#[doc(inline)]
+pub use libp2p_identity::PeerId;
 
-pub use self::peer::info::{PeerInfo, PeerRelation};
+#[cfg(feature = "in-memory")]
+pub use self::config::InMemoryNetworkConfigBuilder;
+pub use self::peer::info::{ConnectionHistory, PeerInfo, PeerRelation};
 #[cfg(feature = "full")]
 pub use self::{
-    config::{NetworkConfig, NetworkConfigBuilder},
+    config::{ConnectionGaterConfig, ConnectionGaterConfigBuilder, NetworkConfig, NetworkConfigBuilder},
     error::Error,
     init::{integrated, standalone},
     network::{host::integrated::NetworkHost, origin::Origin},
+    peer::{
+        ban::{Ban, NetworkBanList},
+        list::NetworkConnectionHistory,
+        peering::{NetworkPeering, PeeringDiff},
+        stores,
+    },
     service::{
         command::{Command, NetworkCommandSender},
         event::{Event, NetworkEventReceiver},
         host::integrated::ServiceHost,
     },
-    swarm::protocols::iota_gossip::{GossipReceiver, GossipSender},
+    swarm::{
+        gater::ConnectionGaterMetrics,
+        protocols::{
+            iota_gossip::{GossipMessage, GossipReceiver, GossipSender},
+            iota_sync::SyncStream,
+        },
+    },
 };
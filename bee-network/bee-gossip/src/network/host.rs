@@ -1,8 +1,12 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Instant;
+
 use futures::{channel::oneshot, StreamExt};
-use libp2p::{identify::IdentifyEvent, swarm::SwarmEvent, Multiaddr, PeerId, Swarm};
+use libp2p::{
+    core::ConnectedPoint, identify::Event as IdentifyEvent, swarm::SwarmEvent, Multiaddr, PeerId, Swarm,
+};
 use log::*;
 
 use super::error::Error;
@@ -15,7 +19,8 @@ use crate::{
     },
     swarm::{
         behaviour::{SwarmBehaviour, SwarmBehaviourEvent},
-        protocols::iota_gossip::IotaGossipEvent,
+        gater::ConnectionGater,
+        protocols::{iota_gossip::IotaGossipEvent, iota_sync::IotaSyncEvent},
     },
 };
 
@@ -25,6 +30,8 @@ pub struct NetworkHostConfig {
     pub peerlist: PeerList,
     pub swarm: Swarm<SwarmBehaviour>,
     pub bind_multiaddr: Multiaddr,
+    pub quic_bind_multiaddr: Option<Multiaddr>,
+    pub(crate) connection_gater: ConnectionGater,
 }
 
 pub mod integrated {
@@ -105,11 +112,15 @@ async fn network_host_processor(
         peerlist,
         mut swarm,
         bind_multiaddr,
+        quic_bind_multiaddr,
+        connection_gater,
     } = config;
 
-    // Try binding to the configured bind address.
-    info!("Binding to: {}", bind_multiaddr);
-    let _listener_id = Swarm::listen_on(&mut swarm, bind_multiaddr)?;
+    // Try binding to the configured bind addresses.
+    for bind_multiaddr in std::iter::once(bind_multiaddr).chain(quic_bind_multiaddr) {
+        info!("Binding to: {}", bind_multiaddr);
+        let _listener_id = Swarm::listen_on(&mut swarm, bind_multiaddr)?;
+    }
 
     // Enter command/event loop.
     loop {
@@ -117,7 +128,7 @@ async fn network_host_processor(
             _ = &mut shutdown => break,
             event = swarm.next() => {
                 let event = event.ok_or(crate::Error::HostEventLoopError)?;
-                process_swarm_event(event, &internal_event_sender, &peerlist).await;
+                process_swarm_event(event, &internal_event_sender, &peerlist, &connection_gater).await;
             }
             command = internal_command_receiver.recv() => {
                 let command = command.ok_or(crate::Error::HostEventLoopError)?;
@@ -133,6 +144,7 @@ async fn process_swarm_event(
     event: SwarmEvent<SwarmBehaviourEvent, impl std::error::Error>,
     internal_event_sender: &InternalEventSender,
     peerlist: &PeerList,
+    connection_gater: &ConnectionGater,
 ) {
     match event {
         SwarmEvent::NewListenAddr { address, .. } => {
@@ -147,11 +159,19 @@ async fn process_swarm_event(
             // Note: We don't care if the inserted address is a duplicate.
             let _ = peerlist.0.write().await.add_local_addr(address);
         }
-        SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+        SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
             debug!("Swarm event: connection established with {}.", alias!(peer_id));
+
+            if let ConnectedPoint::Listener { send_back_addr, .. } = endpoint {
+                connection_gater.inbound_connection_established(&send_back_addr);
+            }
         }
-        SwarmEvent::ConnectionClosed { peer_id, .. } => {
+        SwarmEvent::ConnectionClosed { peer_id, endpoint, .. } => {
             debug!("Swarm event: connection closed with {}.", alias!(peer_id));
+
+            if let ConnectedPoint::Listener { send_back_addr, .. } = endpoint {
+                connection_gater.inbound_connection_closed(&send_back_addr);
+            }
         }
         SwarmEvent::ListenerError { error, .. } => {
             error!("Swarm event: listener error {}.", error);
@@ -160,6 +180,19 @@ async fn process_swarm_event(
             // TODO: strange, but this event is not actually fired when dialing. (open issue?)
             debug!("Swarm event: dialing {}.", alias!(peer_id));
         }
+        SwarmEvent::OutgoingConnectionError {
+            peer_id: Some(peer_id),
+            error,
+        } => {
+            debug!("Swarm event: dialing {} failed: {}.", alias!(peer_id), error);
+
+            // Note: We don't care if the peer was removed in the meantime.
+            let _ = peerlist
+                .0
+                .write()
+                .await
+                .update_metrics(&peer_id, |m| m.num_failed_dials += 1);
+        }
         SwarmEvent::IncomingConnection { send_back_addr, .. } => {
             debug!("Swarm event: being dialed from {}.", send_back_addr);
         }
@@ -169,6 +202,9 @@ async fn process_swarm_event(
         SwarmEvent::Behaviour(SwarmBehaviourEvent::Gossip(gossip_event)) => {
             handle_gossip_event(*gossip_event, internal_event_sender);
         }
+        SwarmEvent::Behaviour(SwarmBehaviourEvent::Sync(sync_event)) => {
+            handle_sync_event(*sync_event, internal_event_sender);
+        }
         _ => {}
     }
 }
@@ -215,8 +251,13 @@ fn handle_gossip_event(event: IotaGossipEvent, internal_event_sender: &InternalE
             peer_addr,
             origin,
             substream,
+            version,
         } => {
-            trace!("Successfully negotiated IOTA gossip protocol with {}.", alias!(peer_id));
+            trace!(
+                "Successfully negotiated IOTA gossip protocol version {} with {}.",
+                version,
+                alias!(peer_id)
+            );
 
             internal_event_sender
                 .send(InternalEvent::ProtocolEstablished {
@@ -224,6 +265,7 @@ fn handle_gossip_event(event: IotaGossipEvent, internal_event_sender: &InternalE
                     peer_addr,
                     origin,
                     substream,
+                    version,
                 })
                 .expect("send internal event");
         }
@@ -237,6 +279,35 @@ fn handle_gossip_event(event: IotaGossipEvent, internal_event_sender: &InternalE
     }
 }
 
+fn handle_sync_event(event: IotaSyncEvent, internal_event_sender: &InternalEventSender) {
+    match event {
+        IotaSyncEvent::StreamOpened {
+            peer_id,
+            origin,
+            stream,
+        } => {
+            trace!("Opened ({}) IOTA sync stream with {}.", origin, alias!(peer_id));
+
+            internal_event_sender
+                .send(InternalEvent::SyncStreamOpened {
+                    peer_id,
+                    origin,
+                    stream,
+                })
+                .expect("send internal event");
+        }
+        IotaSyncEvent::PeerNotConnected { peer_id } => {
+            debug!(
+                "Can not open IOTA sync stream with unconnected peer {}.",
+                alias!(peer_id)
+            );
+        }
+        IotaSyncEvent::UpgradeError { peer_id, error } => {
+            debug!("IOTA sync upgrade error with {}: Cause: {:?}.", alias!(peer_id), error);
+        }
+    }
+}
+
 async fn process_internal_command(internal_command: Command, swarm: &mut Swarm<SwarmBehaviour>, peerlist: &PeerList) {
     match internal_command {
         Command::DialAddress { address } => {
@@ -250,6 +321,7 @@ async fn process_internal_command(internal_command: Command, swarm: &mut Swarm<S
             }
         }
         Command::DisconnectPeer { peer_id } => hang_up(swarm, peer_id).await,
+        Command::OpenSyncStream { peer_id } => swarm.behaviour_mut().open_sync_stream(peer_id),
         _ => {}
     }
 }
@@ -286,7 +358,7 @@ async fn dial_peer(swarm: &mut Swarm<SwarmBehaviour>, peer_id: PeerId, peerlist:
         .write()
         .await
         .update_metrics(&peer_id, |m| {
-            m.num_dials += 1;
+            m.record_dial(Instant::now());
             dial_attempt = m.num_dials;
         })
         .expect("peer must exist");
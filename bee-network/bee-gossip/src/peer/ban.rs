@@ -0,0 +1,135 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "full")]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use libp2p::{Multiaddr, PeerId};
+
+use super::list::PeerListWrapper;
+
+/// A ban of a peer or an address.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Ban {
+    expires_at: Option<u64>,
+    reason: Option<String>,
+}
+
+impl Ban {
+    /// Creates a new [`Ban`] that expires at the given unix timestamp (in seconds), or never if `None`.
+    pub fn new(expires_at: Option<u64>, reason: Option<String>) -> Self {
+        Self { expires_at, reason }
+    }
+
+    /// Returns the unix timestamp (in seconds) at which the ban expires, if any.
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    /// Returns the reason of the ban, if any.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Returns whether the ban has expired at the given unix timestamp (in seconds).
+    pub fn is_expired(&self, now: u64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+
+    #[cfg(any(feature = "rocksdb", feature = "sled"))]
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        // A zero expiry stands for "never", an empty reason for "none".
+        let reason = self.reason.as_deref().unwrap_or_default().as_bytes();
+        let mut bytes = Vec::with_capacity(8 + reason.len());
+
+        bytes.extend_from_slice(&self.expires_at.unwrap_or_default().to_be_bytes());
+        bytes.extend_from_slice(reason);
+
+        bytes
+    }
+
+    #[cfg(any(feature = "rocksdb", feature = "sled"))]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 8 {
+            return None;
+        }
+
+        let (expires_at, reason) = bytes.split_at(8);
+        // Panic: the slice has exactly 8 bytes.
+        let expires_at = u64::from_be_bytes(expires_at.try_into().unwrap());
+        let reason = String::from_utf8(reason.to_vec()).ok()?;
+
+        Some(Self {
+            expires_at: if expires_at == 0 { None } else { Some(expires_at) },
+            reason: if reason.is_empty() { None } else { Some(reason) },
+        })
+    }
+}
+
+/// Gives read access to the peers and addresses banned by the network layer.
+#[derive(Clone, Debug)]
+pub struct NetworkBanList(PeerListWrapper);
+
+impl NetworkBanList {
+    pub(crate) fn new(peerlist: PeerListWrapper) -> Self {
+        Self(peerlist)
+    }
+
+    /// Returns the banned peers, including those whose ban expired but wasn't lifted yet.
+    pub async fn peers(&self) -> Vec<(PeerId, Ban)> {
+        self.0
+             .0
+            .read()
+            .await
+            .banned_peers()
+            .map(|(peer_id, ban)| (*peer_id, ban.clone()))
+            .collect()
+    }
+
+    /// Returns the banned addresses, including those whose ban expired but wasn't lifted yet.
+    pub async fn addresses(&self) -> Vec<(Multiaddr, Ban)> {
+        self.0
+             .0
+            .read()
+            .await
+            .banned_addrs()
+            .map(|(address, ban)| (address.clone(), ban.clone()))
+            .collect()
+    }
+}
+
+/// Returns the current unix timestamp (in seconds).
+pub(crate) fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "rocksdb", feature = "sled"))]
+    #[test]
+    fn ban_bytes_roundtrip() {
+        for ban in [
+            Ban::default(),
+            Ban::new(Some(1_662_000_000), None),
+            Ban::new(None, Some("spam".to_string())),
+            Ban::new(Some(1_662_000_000), Some("invalid blocks".to_string())),
+        ] {
+            assert_eq!(Ban::from_bytes(&ban.to_bytes()), Some(ban));
+        }
+
+        assert_eq!(Ban::from_bytes(&[0; 7]), None);
+    }
+
+    #[test]
+    fn ban_expiry() {
+        assert!(!Ban::default().is_expired(u64::MAX));
+        assert!(!Ban::new(Some(10), None).is_expired(9));
+        assert!(Ban::new(Some(10), None).is_expired(10));
+    }
+}
@@ -63,6 +63,17 @@ impl PeerRelation {
     }
 }
 
+/// The connection history of a peer.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConnectionHistory {
+    /// The unix timestamp (in seconds) of the last time the peer got connected, if ever.
+    pub last_connected_at: Option<u64>,
+    /// The number of failed attempts to dial the peer.
+    pub num_failed_dials: u64,
+    /// The percentage of time the peer was connected since it was added.
+    pub uptime: u8,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
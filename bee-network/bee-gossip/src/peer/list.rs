@@ -3,15 +3,21 @@
 
 #![cfg(feature = "full")]
 
-use std::{mem::take, sync::Arc};
+use std::{
+    mem::take,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use hashbrown::{HashMap, HashSet};
 use libp2p::{Multiaddr, PeerId};
+use rand::Rng;
 use tokio::sync::RwLock;
 
 use super::{
+    ban::{unix_now_secs, Ban},
     error::Error,
-    info::{PeerInfo, PeerRelation},
+    info::{ConnectionHistory, PeerInfo, PeerRelation},
 };
 use crate::{alias, config::Peer, init::global, swarm::protocols::iota_gossip::GossipSender};
 
@@ -28,13 +34,48 @@ impl PeerListWrapper {
     }
 }
 
+/// Gives read access to the connection history of the peers of the network layer.
+#[derive(Clone, Debug)]
+pub struct NetworkConnectionHistory(PeerListWrapper);
+
+impl NetworkConnectionHistory {
+    pub(crate) fn new(peerlist: PeerListWrapper) -> Self {
+        Self(peerlist)
+    }
+
+    /// Returns the connection history of a peer, if it is present.
+    pub async fn get(&self, peer_id: &PeerId) -> Option<ConnectionHistory> {
+        self.0
+             .0
+            .read()
+            .await
+            .metrics(peer_id)
+            .ok()
+            .map(|metrics| metrics.connection_history(Instant::now()))
+    }
+
+    /// Returns the connection history of all present peers.
+    pub async fn all(&self) -> Vec<(PeerId, ConnectionHistory)> {
+        let now = Instant::now();
+
+        self.0
+             .0
+            .read()
+            .await
+            .peers
+            .iter()
+            .map(|(peer_id, (_, _, metrics))| (*peer_id, metrics.connection_history(now)))
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 pub struct PeerList {
     local_id: PeerId,
     local_addrs: HashSet<Multiaddr>,
     peers: HashMap<PeerId, (PeerInfo, PeerState, PeerMetrics)>,
-    banned_peers: HashSet<PeerId>,
-    banned_addrs: HashSet<Multiaddr>,
+    banned_peers: HashMap<PeerId, Ban>,
+    banned_addrs: HashMap<Multiaddr, Ban>,
 }
 
 impl PeerList {
@@ -43,8 +84,8 @@ impl PeerList {
             local_id,
             local_addrs: HashSet::with_capacity(LOCAL_ADDRS_INITIAL_CAP),
             peers: HashMap::with_capacity(REMOTE_PEERS_INITIAL_CAP),
-            banned_peers: HashSet::default(),
-            banned_addrs: HashSet::default(),
+            banned_peers: HashMap::default(),
+            banned_addrs: HashMap::default(),
         }
     }
 
@@ -72,8 +113,8 @@ impl PeerList {
             local_id,
             local_addrs: HashSet::with_capacity(LOCAL_ADDRS_INITIAL_CAP),
             peers: p,
-            banned_peers: HashSet::default(),
-            banned_addrs: HashSet::default(),
+            banned_peers: HashMap::default(),
+            banned_addrs: HashMap::default(),
         }
     }
 
@@ -221,26 +262,28 @@ impl PeerList {
         self.banned_addrs.clear();
     }
 
-    pub fn ban_peer(&mut self, peer_id: PeerId) -> Result<(), Error> {
-        // TODO: use storage to persist banned peers
-        if self.banned_peers.insert(peer_id) {
-            Ok(())
-        } else {
-            Err(Error::PeerIsBanned(peer_id))
+    pub fn ban_peer(&mut self, peer_id: PeerId, ban: Ban) -> Result<(), Error> {
+        if self.is_peer_banned(&peer_id) {
+            return Err(Error::PeerIsBanned(peer_id));
         }
+
+        self.banned_peers.insert(peer_id, ban);
+
+        Ok(())
     }
 
-    pub fn ban_address(&mut self, address: Multiaddr) -> Result<(), Error> {
-        // TODO: use storage to persist banned addrs
-        if self.banned_addrs.insert(address.clone()) {
-            Ok(())
-        } else {
-            Err(Error::AddressIsBanned(address))
+    pub fn ban_address(&mut self, address: Multiaddr, ban: Ban) -> Result<(), Error> {
+        if self.is_addr_banned(&address) {
+            return Err(Error::AddressIsBanned(address));
         }
+
+        self.banned_addrs.insert(address, ban);
+
+        Ok(())
     }
 
     pub fn unban_peer(&mut self, peer_id: &PeerId) -> Result<(), Error> {
-        if self.banned_peers.remove(peer_id) {
+        if self.banned_peers.remove(peer_id).is_some() {
             Ok(())
         } else {
             Err(Error::PeerIsUnbanned(*peer_id))
@@ -248,7 +291,7 @@ impl PeerList {
     }
 
     pub fn unban_address(&mut self, addr: &Multiaddr) -> Result<(), Error> {
-        if self.banned_addrs.remove(addr) {
+        if self.banned_addrs.remove(addr).is_some() {
             Ok(())
         } else {
             Err(Error::AddressIsUnbanned(addr.clone()))
@@ -256,11 +299,42 @@ impl PeerList {
     }
 
     pub fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
-        self.banned_peers.contains(peer_id)
+        matches!(self.banned_peers.get(peer_id), Some(ban) if !ban.is_expired(unix_now_secs()))
     }
 
     pub fn is_addr_banned(&self, addr: &Multiaddr) -> bool {
-        self.banned_addrs.contains(addr)
+        matches!(self.banned_addrs.get(addr), Some(ban) if !ban.is_expired(unix_now_secs()))
+    }
+
+    pub fn banned_peers(&self) -> impl Iterator<Item = (&PeerId, &Ban)> {
+        self.banned_peers.iter()
+    }
+
+    pub fn banned_addrs(&self) -> impl Iterator<Item = (&Multiaddr, &Ban)> {
+        self.banned_addrs.iter()
+    }
+
+    /// Removes the bans that have expired at the given unix timestamp (in seconds), and returns what was unbanned.
+    pub fn remove_expired_bans(&mut self, now: u64) -> (Vec<PeerId>, Vec<Multiaddr>) {
+        let mut peer_ids = Vec::new();
+        let mut addrs = Vec::new();
+
+        self.banned_peers.retain(|peer_id, ban| {
+            let expired = ban.is_expired(now);
+            if expired {
+                peer_ids.push(*peer_id);
+            }
+            !expired
+        });
+        self.banned_addrs.retain(|addr, ban| {
+            let expired = ban.is_expired(now);
+            if expired {
+                addrs.push(addr.clone());
+            }
+            !expired
+        });
+
+        (peer_ids, addrs)
     }
 
     pub fn accepts_incoming_peer(&self, peer_id: &PeerId, peer_addr: &Multiaddr) -> Result<(), Error> {
@@ -276,9 +350,9 @@ impl PeerList {
             Err(Error::PeerIsLocal(*peer_id))
         } else if self.local_addrs.contains(peer_addr) {
             Err(Error::AddressIsLocal(peer_addr.clone()))
-        } else if self.banned_peers.contains(peer_id) {
+        } else if self.is_peer_banned(peer_id) {
             Err(Error::PeerIsBanned(*peer_id))
-        } else if self.banned_addrs.contains(peer_addr) {
+        } else if self.is_addr_banned(peer_addr) {
             Err(Error::AddressIsBanned(peer_addr.clone()))
         } else if self
             .satisfies(peer_id, |_, state, _| state.is_connected())
@@ -313,7 +387,7 @@ impl PeerList {
             Err(Error::PeerIsLocal(*peer_id))
         } else if !self.contains(peer_id) {
             Err(Error::PeerNotPresent(*peer_id))
-        } else if self.banned_peers.contains(peer_id) {
+        } else if self.is_peer_banned(peer_id) {
             Err(Error::PeerIsBanned(*peer_id))
         } else if self
             .satisfies(peer_id, |_, state, _| state.is_connected())
@@ -325,7 +399,7 @@ impl PeerList {
 
             if self.local_addrs.contains(&peer_info.address) {
                 Err(Error::AddressIsLocal(peer_info.address.clone()))
-            } else if self.banned_addrs.contains(&peer_info.address) {
+            } else if self.is_addr_banned(&peer_info.address) {
                 Err(Error::AddressIsBanned(peer_info.address.clone()))
             } else if peer_info.relation.is_unknown()
                 && self.filter_count(|info, status, _| info.relation.is_unknown() && status.is_connected())
@@ -351,7 +425,7 @@ impl PeerList {
         // - Deny dialing an already connected peer (with that address).
         if self.local_addrs.contains(addr) {
             Err(Error::AddressIsLocal(addr.clone()))
-        } else if self.banned_addrs.contains(addr) {
+        } else if self.is_addr_banned(addr) {
             Err(Error::AddressIsBanned(addr.clone()))
         } else if let Some(peer_id) = self.find_peer_if_connected(addr) {
             Err(Error::PeerIsConnected(peer_id))
@@ -456,6 +530,33 @@ mod tests {
         assert_eq!(0, pl.len());
     }
 
+    #[test]
+    fn expired_bans() {
+        let local_id = gen_constant_peer_id();
+        let mut pl = PeerList::new(local_id);
+
+        let peer_id = gen_random_peer_id();
+        let peer_info = gen_constant_peer_info();
+
+        pl.ban_peer(peer_id, Ban::new(Some(1), None)).unwrap();
+        pl.ban_address(peer_info.address.clone(), Ban::default()).unwrap();
+
+        // An expired ban is no longer enforced, and can be replaced.
+        assert!(!pl.is_peer_banned(&peer_id));
+        assert!(pl.is_addr_banned(&peer_info.address));
+        assert!(matches!(
+            pl.ban_address(peer_info.address.clone(), Ban::default()),
+            Err(Error::AddressIsBanned(_))
+        ));
+
+        assert_eq!(pl.remove_expired_bans(unix_now_secs()), (vec![peer_id], vec![]));
+        assert_eq!(pl.banned_peers().count(), 0);
+        assert_eq!(pl.banned_addrs().count(), 1);
+
+        pl.ban_peer(peer_id, Ban::new(None, Some("spam".to_string()))).unwrap();
+        assert!(pl.is_peer_banned(&peer_id));
+    }
+
     // ===== helpers =====
 
     pub fn gen_constant_peer_id() -> PeerId {
@@ -463,7 +564,7 @@ mod tests {
     }
 
     pub fn gen_random_peer_id() -> PeerId {
-        PeerId::from_public_key(&libp2p_core::PublicKey::Ed25519(Keypair::generate().public()))
+        PeerId::from_public_key(&libp2p_identity::PublicKey::from(Keypair::generate().public()))
     }
 
     pub fn gen_deterministic_peer_info(port: u16, relation: PeerRelation) -> PeerInfo {
@@ -496,10 +597,92 @@ pub enum PeerState {
     Connected(GossipSender),
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct PeerMetrics {
     pub(crate) num_dials: usize,
     pub(crate) identified_at: Option<u64>,
+    pub(crate) next_dial_at: Option<Instant>,
+    pub(crate) num_failed_dials: u64,
+    pub(crate) last_connected_at: Option<u64>,
+    pub(crate) tracked_since: Instant,
+    pub(crate) connected_since: Option<Instant>,
+    pub(crate) connected_for: Duration,
+}
+
+impl Default for PeerMetrics {
+    fn default() -> Self {
+        Self {
+            num_dials: 0,
+            identified_at: None,
+            next_dial_at: None,
+            num_failed_dials: 0,
+            last_connected_at: None,
+            tracked_since: Instant::now(),
+            connected_since: None,
+            connected_for: Duration::ZERO,
+        }
+    }
+}
+
+impl PeerMetrics {
+    /// Whether the reconnect back-off of that peer has elapsed.
+    pub fn is_dial_due(&self, now: Instant) -> bool {
+        !matches!(self.next_dial_at, Some(next_dial_at) if next_dial_at > now)
+    }
+
+    /// Records a dial attempt, and backs off exponentially until the next one.
+    pub fn record_dial(&mut self, now: Instant) {
+        self.num_dials += 1;
+        self.next_dial_at = Some(
+            now + reconnect_backoff(
+                self.num_dials,
+                global::reconnect_interval_secs(),
+                global::max_reconnect_interval_secs(),
+            ),
+        );
+    }
+
+    pub fn record_connected(&mut self, now: Instant) {
+        self.last_connected_at = Some(unix_now_secs());
+        self.connected_since.get_or_insert(now);
+    }
+
+    pub fn record_disconnected(&mut self, now: Instant) {
+        if let Some(connected_since) = self.connected_since.take() {
+            self.connected_for += now.saturating_duration_since(connected_since);
+        }
+    }
+
+    pub fn connection_history(&self, now: Instant) -> ConnectionHistory {
+        let connected_for = self.connected_for
+            + self.connected_since.map_or(Duration::ZERO, |connected_since| {
+                now.saturating_duration_since(connected_since)
+            });
+        let tracked_for = now.saturating_duration_since(self.tracked_since);
+
+        ConnectionHistory {
+            last_connected_at: self.last_connected_at,
+            num_failed_dials: self.num_failed_dials,
+            uptime: if tracked_for.is_zero() {
+                0
+            } else {
+                (connected_for.as_millis() * 100 / tracked_for.as_millis().max(1)).min(100) as u8
+            },
+        }
+    }
+}
+
+/// Returns the delay before redialing a peer that was dialed `num_dials` times without success.
+///
+/// The delay doubles with every attempt, starting at the reconnect interval and capped at the maximum reconnect
+/// interval. A random jitter of up to half the delay keeps peers from redialing each other in lockstep.
+fn reconnect_backoff(num_dials: usize, reconnect_interval_secs: u64, max_reconnect_interval_secs: u64) -> Duration {
+    let min = reconnect_interval_secs * 1000;
+    let max = (max_reconnect_interval_secs * 1000).max(min);
+    let exponent = num_dials.saturating_sub(1).min(u32::BITS as usize - 1) as u32;
+    let millis = min.saturating_mul(1 << exponent).min(max);
+
+    Duration::from_millis(rand::thread_rng().gen_range(millis / 2..=millis))
 }
 
 impl Default for PeerState {
@@ -533,7 +716,7 @@ impl PeerState {
 #[cfg(test)]
 mod peerstate_tests {
     use super::*;
-    use crate::swarm::protocols::iota_gossip::channel;
+    use crate::swarm::protocols::iota_gossip::outbound_channel;
 
     #[test]
     fn new_peer_state() {
@@ -545,7 +728,7 @@ mod peerstate_tests {
     #[test]
     fn peer_state_change() {
         let mut peerstate = PeerState::Disconnected;
-        let (tx, _rx) = channel();
+        let (tx, _rx) = outbound_channel();
 
         peerstate.set_connected(tx);
         assert!(peerstate.is_connected());
@@ -555,3 +738,49 @@ mod peerstate_tests {
         assert!(peerstate.set_disconnected().is_none());
     }
 }
+
+#[cfg(test)]
+mod peermetrics_tests {
+    use super::*;
+
+    #[test]
+    fn reconnect_backoff_grows_exponentially_up_to_max() {
+        for (num_dials, max_secs) in [(1, 30), (2, 60), (3, 120), (4, 240), (5, 300), (100, 300)] {
+            let backoff = reconnect_backoff(num_dials, 30, 300);
+
+            assert!(backoff >= Duration::from_secs(max_secs) / 2);
+            assert!(backoff <= Duration::from_secs(max_secs));
+        }
+
+        // A maximum below the reconnect interval is ignored.
+        assert!(reconnect_backoff(5, 30, 10) <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn connection_history() {
+        let mut metrics = PeerMetrics::default();
+        let start = metrics.tracked_since;
+
+        assert!(metrics.is_dial_due(start));
+        assert_eq!(metrics.connection_history(start), ConnectionHistory::default());
+
+        metrics.next_dial_at = Some(start + Duration::from_secs(10));
+        assert!(!metrics.is_dial_due(start + Duration::from_secs(9)));
+        assert!(metrics.is_dial_due(start + Duration::from_secs(10)));
+
+        metrics.num_failed_dials = 2;
+        metrics.record_connected(start + Duration::from_secs(10));
+        metrics.record_disconnected(start + Duration::from_secs(40));
+        metrics.record_disconnected(start + Duration::from_secs(50));
+
+        let history = metrics.connection_history(start + Duration::from_secs(100));
+
+        assert!(history.last_connected_at.is_some());
+        assert_eq!(history.num_failed_dials, 2);
+        assert_eq!(history.uptime, 30);
+
+        metrics.record_connected(start + Duration::from_secs(100));
+
+        assert_eq!(metrics.connection_history(start + Duration::from_secs(200)).uptime, 65);
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "full")]
+
+use std::fmt;
+
+use libp2p::PeerId;
+
+use super::{
+    info::PeerRelation,
+    list::{PeerList, PeerListWrapper},
+};
+use crate::{
+    alias,
+    config::{NetworkConfig, Peer},
+    service::command::Command,
+};
+
+/// The changes necessary to bring the peer list in line with a changed manual peering configuration.
+#[derive(Clone, Default)]
+pub struct PeeringDiff {
+    added: Vec<Peer>,
+    removed: Vec<PeerId>,
+    now_known: Vec<PeerId>,
+}
+
+impl PeeringDiff {
+    fn new(peerlist: &PeerList, previous_config: &NetworkConfig, config: &NetworkConfig) -> Self {
+        let mut diff = Self::default();
+
+        for peer in config.static_peers() {
+            match peerlist.info(&peer.peer_id) {
+                Err(_) => diff.added.push(peer.clone()),
+                // A changed address requires the peer to be re-added.
+                Ok(info) if info.address != peer.multiaddr => {
+                    diff.removed.push(peer.peer_id);
+                    diff.added.push(peer.clone());
+                }
+                Ok(info) if !info.relation.is_known() => diff.now_known.push(peer.peer_id),
+                Ok(_) => {}
+            }
+        }
+
+        // Only peers that were removed from the configuration are removed, known peers that were added otherwise, e.g.
+        // through the REST API, are kept.
+        diff.removed.extend(
+            previous_config
+                .static_peers()
+                .iter()
+                .map(|peer| peer.peer_id)
+                .filter(|peer_id| !config.static_peers().iter().any(|peer| peer.peer_id == *peer_id))
+                .filter(|peer_id| peerlist.contains(peer_id)),
+        );
+
+        diff
+    }
+
+    /// Returns whether the peer list already matches the configuration.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.now_known.is_empty()
+    }
+
+    /// Returns the commands that apply the diff, in the order they need to be sent.
+    pub fn into_commands(self) -> Vec<Command> {
+        let removals = self.removed.into_iter().map(|peer_id| Command::RemovePeer { peer_id });
+        let relation_changes = self.now_known.into_iter().map(|peer_id| Command::ChangeRelation {
+            peer_id,
+            to: PeerRelation::Known,
+        });
+        let additions = self.added.into_iter().map(|peer| Command::AddPeer {
+            peer_id: peer.peer_id,
+            multiaddr: peer.multiaddr,
+            alias: peer.alias,
+            relation: PeerRelation::Known,
+        });
+
+        removals.chain(relation_changes).chain(additions).collect()
+    }
+}
+
+impl fmt::Display for PeeringDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let added = self
+            .added
+            .iter()
+            .map(|peer| format!("+{} ({})", alias!(peer.peer_id), peer.multiaddr));
+        let removed = self.removed.iter().map(|peer_id| format!("-{}", alias!(peer_id)));
+        let now_known = self.now_known.iter().map(|peer_id| format!("~{}", alias!(peer_id)));
+
+        write!(
+            f,
+            "{}",
+            removed.chain(now_known).chain(added).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
+
+/// Allows to compare the peers of the network layer with a manual peering configuration.
+#[derive(Clone, Debug)]
+pub struct NetworkPeering(PeerListWrapper);
+
+impl NetworkPeering {
+    pub(crate) fn new(peerlist: PeerListWrapper) -> Self {
+        Self(peerlist)
+    }
+
+    /// Returns the changes necessary to bring the peers in line with the static peers of the given configuration,
+    /// which replaces the previous one.
+    ///
+    /// Peers that were removed from the configuration are removed, configured peers that are missing or have a
+    /// different address are (re-)added, and configured peers with a different relation become known.
+    pub async fn diff(&self, previous_config: &NetworkConfig, config: &NetworkConfig) -> PeeringDiff {
+        PeeringDiff::new(&*self.0 .0.read().await, previous_config, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::{identity::ed25519::Keypair, Multiaddr};
+
+    use super::*;
+    use crate::peer::info::PeerInfo;
+
+    fn peer_id() -> PeerId {
+        PeerId::from_public_key(&libp2p_identity::PublicKey::from(Keypair::generate().public()))
+    }
+
+    fn addr(port: u16) -> Multiaddr {
+        format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap()
+    }
+
+    fn info(port: u16, relation: PeerRelation) -> PeerInfo {
+        PeerInfo {
+            address: addr(port),
+            alias: String::new(),
+            relation,
+        }
+    }
+
+    fn config(peers: &[(PeerId, u16)]) -> NetworkConfig {
+        let mut config = NetworkConfig::default();
+
+        for (peer_id, port) in peers {
+            config.static_peers.insert(Peer {
+                peer_id: *peer_id,
+                multiaddr: addr(*port),
+                alias: None,
+            });
+        }
+
+        config
+    }
+
+    #[test]
+    fn unchanged_peering() {
+        let (unchanged, unknown) = (peer_id(), peer_id());
+        let mut peerlist = PeerList::new(peer_id());
+
+        peerlist.add(unchanged, info(1337, PeerRelation::Known)).unwrap();
+        peerlist.add(unknown, info(1338, PeerRelation::Unknown)).unwrap();
+
+        let config = config(&[(unchanged, 1337)]);
+        let diff = PeeringDiff::new(&peerlist, &config, &config);
+
+        assert!(diff.is_empty());
+        assert!(diff.into_commands().is_empty());
+    }
+
+    #[test]
+    fn changed_peering() {
+        let (added, removed, moved, discovered) = (peer_id(), peer_id(), peer_id(), peer_id());
+        let mut peerlist = PeerList::new(peer_id());
+
+        peerlist.add(removed, info(1337, PeerRelation::Known)).unwrap();
+        peerlist.add(moved, info(1338, PeerRelation::Known)).unwrap();
+        peerlist.add(discovered, info(1339, PeerRelation::Discovered)).unwrap();
+
+        let diff = PeeringDiff::new(
+            &peerlist,
+            &config(&[(removed, 1337), (moved, 1338)]),
+            &config(&[(added, 1336), (moved, 1340), (discovered, 1339)]),
+        );
+
+        assert!(!diff.is_empty());
+
+        let commands = diff.into_commands();
+
+        assert_eq!(commands.len(), 5);
+        assert!(commands[..2]
+            .iter()
+            .all(|c| matches!(c, Command::RemovePeer { peer_id } if *peer_id == removed || *peer_id == moved)));
+        assert!(
+            matches!(&commands[2], Command::ChangeRelation { peer_id, to } if *peer_id == discovered && to.is_known())
+        );
+        assert!(commands[3..].iter().all(|c| matches!(
+            c,
+            Command::AddPeer { peer_id, relation, .. } if (*peer_id == added || *peer_id == moved) && relation.is_known()
+        )));
+    }
+
+    #[test]
+    fn runtime_peers_survive_reload() {
+        let (configured, runtime) = (peer_id(), peer_id());
+        let mut peerlist = PeerList::new(peer_id());
+
+        peerlist.add(configured, info(1337, PeerRelation::Known)).unwrap();
+        peerlist.add(runtime, info(1338, PeerRelation::Known)).unwrap();
+
+        let config = config(&[(configured, 1337)]);
+        let diff = PeeringDiff::new(&peerlist, &config, &config);
+
+        assert!(diff.is_empty());
+
+        let diff = PeeringDiff::new(&peerlist, &config, &NetworkConfig::default());
+        let commands = diff.into_commands();
+
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(&commands[0], Command::RemovePeer { peer_id } if *peer_id == configured));
+    }
+}
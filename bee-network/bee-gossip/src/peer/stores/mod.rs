@@ -0,0 +1,84 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent storage of banned peers and addresses.
+
+#![cfg(feature = "full")]
+
+mod in_memory;
+#[cfg(feature = "rocksdb")]
+mod rocksdb;
+#[cfg(feature = "sled")]
+mod sled;
+
+use std::{error::Error, sync::Arc};
+
+use libp2p::{Multiaddr, PeerId};
+
+pub use self::in_memory::*;
+#[cfg(feature = "rocksdb")]
+pub use self::rocksdb::*;
+#[cfg(feature = "sled")]
+pub use self::sled::*;
+use super::ban::Ban;
+
+/// Mandatory functionality of any ban store.
+pub trait BanStore: Clone + Send + Sync + 'static {
+    /// The ban store configuration.
+    type Config;
+
+    /// Error raised when a ban store operation fails.
+    type Error: Error + Send;
+
+    /// Creates a new ban store from config.
+    fn new(config: Self::Config) -> Result<Self, Self::Error>;
+
+    /// Stores the ban of a peer.
+    fn store_peer_ban(&self, peer_id: &PeerId, ban: &Ban) -> Result<(), Self::Error>;
+
+    /// Stores the ban of an address.
+    fn store_address_ban(&self, address: &Multiaddr, ban: &Ban) -> Result<(), Self::Error>;
+
+    /// Fetches all banned peers.
+    fn fetch_all_peer_bans(&self) -> Result<Vec<(PeerId, Ban)>, Self::Error>;
+
+    /// Fetches all banned addresses.
+    fn fetch_all_address_bans(&self) -> Result<Vec<(Multiaddr, Ban)>, Self::Error>;
+
+    /// Deletes the ban of a peer.
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<bool, Self::Error>;
+
+    /// Deletes the ban of an address.
+    fn delete_address_ban(&self, address: &Multiaddr) -> Result<bool, Self::Error>;
+}
+
+/// An object-safe [`BanStore`], so that the network service doesn't need to be generic over it.
+pub(crate) trait DynBanStore: Send + Sync {
+    fn store_peer_ban(&self, peer_id: &PeerId, ban: &Ban) -> Result<(), String>;
+
+    fn store_address_ban(&self, address: &Multiaddr, ban: &Ban) -> Result<(), String>;
+
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<bool, String>;
+
+    fn delete_address_ban(&self, address: &Multiaddr) -> Result<bool, String>;
+}
+
+impl<S: BanStore> DynBanStore for S {
+    fn store_peer_ban(&self, peer_id: &PeerId, ban: &Ban) -> Result<(), String> {
+        BanStore::store_peer_ban(self, peer_id, ban).map_err(|e| e.to_string())
+    }
+
+    fn store_address_ban(&self, address: &Multiaddr, ban: &Ban) -> Result<(), String> {
+        BanStore::store_address_ban(self, address, ban).map_err(|e| e.to_string())
+    }
+
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<bool, String> {
+        BanStore::delete_peer_ban(self, peer_id).map_err(|e| e.to_string())
+    }
+
+    fn delete_address_ban(&self, address: &Multiaddr) -> Result<bool, String> {
+        BanStore::delete_address_ban(self, address).map_err(|e| e.to_string())
+    }
+}
+
+pub(crate) type BanStoreHandle = Arc<dyn DynBanStore>;
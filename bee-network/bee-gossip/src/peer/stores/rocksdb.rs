@@ -0,0 +1,157 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use libp2p::{Multiaddr, PeerId};
+pub use rocksdb::Options;
+use rocksdb::{AsColumnFamilyRef, DBWithThreadMode, IteratorMode, MultiThreaded};
+
+use crate::peer::{ban::Ban, stores::BanStore};
+
+const BANNED_PEERS_CF: &str = "banned_peers";
+const BANNED_ADDRESSES_CF: &str = "banned_addresses";
+
+/// The config for the RocksDB ban store.
+#[derive(Clone)]
+pub struct RocksDbBanStoreConfig {
+    path: PathBuf,
+    options: Options,
+}
+
+impl RocksDbBanStoreConfig {
+    /// Creates a new config for the RocksDB ban store.
+    pub fn new<P: AsRef<Path>>(path: P, options: Options) -> Self {
+        Self {
+            path: path.as_ref().to_owned(),
+            options,
+        }
+    }
+}
+
+/// The (persistent) RocksDb ban store.
+#[derive(Clone)]
+pub struct RocksDbBanStore {
+    db: Arc<DBWithThreadMode<MultiThreaded>>,
+}
+
+impl RocksDbBanStore {
+    fn open_cf(&self, cf_str: &'static str) -> impl AsColumnFamilyRef + '_ {
+        self.db.cf_handle(cf_str).unwrap()
+    }
+}
+
+impl BanStore for RocksDbBanStore {
+    type Config = RocksDbBanStoreConfig;
+
+    type Error = rocksdb::Error;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        let db = DBWithThreadMode::open_cf(&config.options, &config.path, [BANNED_PEERS_CF, BANNED_ADDRESSES_CF])?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn store_peer_ban(&self, peer_id: &PeerId, ban: &Ban) -> Result<(), Self::Error> {
+        let cf = self.open_cf(BANNED_PEERS_CF);
+
+        self.db.put_cf(&cf, peer_id.to_bytes(), ban.to_bytes())
+    }
+
+    fn store_address_ban(&self, address: &Multiaddr, ban: &Ban) -> Result<(), Self::Error> {
+        let cf = self.open_cf(BANNED_ADDRESSES_CF);
+
+        self.db.put_cf(&cf, address.to_vec(), ban.to_bytes())
+    }
+
+    fn fetch_all_peer_bans(&self) -> Result<Vec<(PeerId, Ban)>, Self::Error> {
+        let cf = self.open_cf(BANNED_PEERS_CF);
+
+        // Entries that can't be decoded are skipped rather than failing the whole fetch.
+        Ok(self
+            .db
+            .iterator_cf(&cf, IteratorMode::Start)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(k, v)| Some((PeerId::from_bytes(&k).ok()?, Ban::from_bytes(&v)?)))
+            .collect())
+    }
+
+    fn fetch_all_address_bans(&self) -> Result<Vec<(Multiaddr, Ban)>, Self::Error> {
+        let cf = self.open_cf(BANNED_ADDRESSES_CF);
+
+        Ok(self
+            .db
+            .iterator_cf(&cf, IteratorMode::Start)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(k, v)| Some((Multiaddr::try_from(k.to_vec()).ok()?, Ban::from_bytes(&v)?)))
+            .collect())
+    }
+
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<bool, Self::Error> {
+        let cf = self.open_cf(BANNED_PEERS_CF);
+        let key = peer_id.to_bytes();
+        let existed = self.db.get_cf(&cf, &key)?.is_some();
+
+        self.db.delete_cf(&cf, key)?;
+
+        Ok(existed)
+    }
+
+    fn delete_address_ban(&self, address: &Multiaddr) -> Result<bool, Self::Error> {
+        let cf = self.open_cf(BANNED_ADDRESSES_CF);
+        let key = address.to_vec();
+        let existed = self.db.get_cf(&cf, &key)?.is_some();
+
+        self.db.delete_cf(&cf, key)?;
+
+        Ok(existed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    struct Janitor<P: AsRef<Path>>(P);
+
+    impl<P: AsRef<Path>> Drop for Janitor<P> {
+        fn drop(&mut self) {
+            if let Err(e) = std::fs::remove_dir_all(self.0.as_ref()) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    panic!("{}", e);
+                }
+            }
+        }
+    }
+
+    use super::*;
+
+    #[test]
+    fn store_fetch_and_delete_address_ban() {
+        let path = "rocksdb_store_fetch_and_delete_address_ban";
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let ban_store = RocksDbBanStore::new(RocksDbBanStoreConfig::new(path, options)).unwrap();
+        let janitor = Janitor(path);
+
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/15600".parse().unwrap();
+        let ban = Ban::new(None, Some("spam".to_string()));
+
+        ban_store.store_address_ban(&address, &ban).unwrap();
+
+        assert_eq!(
+            ban_store.fetch_all_address_bans().unwrap(),
+            vec![(address.clone(), ban)]
+        );
+        assert!(ban_store.delete_address_ban(&address).unwrap());
+        assert!(!ban_store.delete_address_ban(&address).unwrap());
+
+        drop(ban_store);
+        drop(janitor);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use libp2p::{Multiaddr, PeerId};
+use sled::Db;
+
+use crate::peer::{ban::Ban, stores::BanStore};
+
+const BANNED_PEERS_TREE: &str = "banned_peers";
+const BANNED_ADDRESSES_TREE: &str = "banned_addresses";
+
+/// The config for the Sled ban store.
+pub type SledBanStoreConfig = sled::Config;
+
+/// The (persistent) Sled ban store.
+#[derive(Clone)]
+pub struct SledBanStore {
+    db: Db,
+}
+
+impl BanStore for SledBanStore {
+    type Config = SledBanStoreConfig;
+
+    type Error = sled::Error;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        let db = config.open()?;
+
+        db.open_tree(BANNED_PEERS_TREE)?;
+        db.open_tree(BANNED_ADDRESSES_TREE)?;
+
+        Ok(Self { db })
+    }
+
+    fn store_peer_ban(&self, peer_id: &PeerId, ban: &Ban) -> Result<(), Self::Error> {
+        let tree = self.db.open_tree(BANNED_PEERS_TREE)?;
+
+        tree.insert(peer_id.to_bytes(), ban.to_bytes())?;
+
+        Ok(())
+    }
+
+    fn store_address_ban(&self, address: &Multiaddr, ban: &Ban) -> Result<(), Self::Error> {
+        let tree = self.db.open_tree(BANNED_ADDRESSES_TREE)?;
+
+        tree.insert(address.to_vec(), ban.to_bytes())?;
+
+        Ok(())
+    }
+
+    fn fetch_all_peer_bans(&self) -> Result<Vec<(PeerId, Ban)>, Self::Error> {
+        let tree = self.db.open_tree(BANNED_PEERS_TREE)?;
+
+        // Entries that can't be decoded are skipped rather than failing the whole fetch.
+        Ok(tree
+            .iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(k, v)| Some((PeerId::from_bytes(&k).ok()?, Ban::from_bytes(&v)?)))
+            .collect())
+    }
+
+    fn fetch_all_address_bans(&self) -> Result<Vec<(Multiaddr, Ban)>, Self::Error> {
+        let tree = self.db.open_tree(BANNED_ADDRESSES_TREE)?;
+
+        Ok(tree
+            .iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(k, v)| Some((Multiaddr::try_from(k.to_vec()).ok()?, Ban::from_bytes(&v)?)))
+            .collect())
+    }
+
+    fn delete_peer_ban(&self, peer_id: &PeerId) -> Result<bool, Self::Error> {
+        let tree = self.db.open_tree(BANNED_PEERS_TREE)?;
+
+        Ok(tree.remove(peer_id.to_bytes())?.is_some())
+    }
+
+    fn delete_address_ban(&self, address: &Multiaddr) -> Result<bool, Self::Error> {
+        let tree = self.db.open_tree(BANNED_ADDRESSES_TREE)?;
+
+        Ok(tree.remove(address.to_vec())?.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::identity::ed25519::Keypair;
+
+    use super::*;
+
+    fn create_temporary_sled_ban_store() -> SledBanStore {
+        let config = SledBanStoreConfig::new().temporary(true);
+        SledBanStore::new(config).unwrap()
+    }
+
+    #[test]
+    fn store_fetch_and_delete_peer_ban() {
+        let ban_store = create_temporary_sled_ban_store();
+
+        let peer_id = PeerId::from_public_key(&libp2p_identity::PublicKey::from(Keypair::generate().public()));
+        let ban = Ban::new(Some(1_662_000_000), Some("spam".to_string()));
+
+        ban_store.store_peer_ban(&peer_id, &ban).unwrap();
+
+        assert_eq!(ban_store.fetch_all_peer_bans().unwrap(), vec![(peer_id, ban)]);
+        assert!(ban_store.delete_peer_ban(&peer_id).unwrap());
+        assert!(!ban_store.delete_peer_ban(&peer_id).unwrap());
+        assert!(ban_store.fetch_all_peer_bans().unwrap().is_empty());
+    }
+
+    #[test]
+    fn store_fetch_and_delete_address_ban() {
+        let ban_store = create_temporary_sled_ban_store();
+
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/15600".parse().unwrap();
+
+        ban_store.store_address_ban(&address, &Ban::default()).unwrap();
+
+        assert_eq!(
+            ban_store.fetch_all_address_bans().unwrap(),
+            vec![(address.clone(), Ban::default())]
+        );
+        assert!(ban_store.delete_address_ban(&address).unwrap());
+        assert!(ban_store.fetch_all_address_bans().unwrap().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use libp2p_core::{Multiaddr, PeerId};
+use libp2p_core::Multiaddr;
+use libp2p_identity::PeerId;
 use tokio::sync::mpsc;
 
 use super::error::Error;
@@ -53,6 +54,10 @@ pub enum Command {
     BanPeer {
         /// The peer's id.
         peer_id: PeerId,
+        /// The unix timestamp (in seconds) at which the ban expires, or `None` for a permanent ban.
+        expires_at: Option<u64>,
+        /// The optional reason of the ban.
+        reason: Option<String>,
     },
     /// Unbans a peer.
     UnbanPeer {
@@ -63,6 +68,10 @@ pub enum Command {
     BanAddress {
         /// The peer's address.
         address: Multiaddr,
+        /// The unix timestamp (in seconds) at which the ban expires, or `None` for a permanent ban.
+        expires_at: Option<u64>,
+        /// The optional reason of the ban.
+        reason: Option<String>,
     },
     /// Unbans an address.
     UnbanAddress {
@@ -76,6 +85,11 @@ pub enum Command {
         /// The peer's new relation.
         to: PeerRelation,
     },
+    /// Opens a dedicated stream to bulk synchronize the tangle with a connected peer.
+    OpenSyncStream {
+        /// The peer's id.
+        peer_id: PeerId,
+    },
 }
 
 /// Allows the user to send [`Command`]s to the network layer.
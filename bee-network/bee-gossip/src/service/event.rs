@@ -2,14 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libp2p::swarm::NegotiatedSubstream;
-use libp2p_core::{Multiaddr, PeerId};
+use libp2p_core::Multiaddr;
+use libp2p_identity::PeerId;
 use tokio::sync::mpsc;
 
 use super::command::Command;
 use crate::{
     network::origin::Origin,
     peer::{error::Error as PeerError, info::PeerInfo},
-    swarm::protocols::iota_gossip::{GossipReceiver, GossipSender},
+    swarm::protocols::{
+        iota_gossip::{GossipReceiver, GossipSender},
+        iota_sync::SyncStream,
+    },
 };
 
 pub type EventSender = mpsc::UnboundedSender<Event>;
@@ -81,6 +85,8 @@ pub enum Event {
         gossip_in: GossipReceiver,
         /// The peer's message send channel.
         gossip_out: GossipSender,
+        /// The negotiated version of the gossip protocol.
+        gossip_version: u8,
     },
 
     /// A peer was disconnected.
@@ -108,6 +114,16 @@ pub enum Event {
         /// The peer's info.
         peer_info: PeerInfo,
     },
+
+    /// A sync stream with a peer was opened.
+    SyncStreamOpened {
+        /// The peer's id.
+        peer_id: PeerId,
+        /// Whether the stream was requested by the peer or by us.
+        origin: Origin,
+        /// The negotiated stream.
+        stream: SyncStream,
+    },
 }
 
 /// Describes the internal events.
@@ -129,6 +145,8 @@ pub enum InternalEvent {
         origin: Origin,
         /// The negotiated substream the protocol is running on.
         substream: Box<NegotiatedSubstream>,
+        /// The negotiated version of the gossip protocol.
+        version: u8,
     },
 
     /// The gossip protocol with a peer was stopped.
@@ -148,6 +166,16 @@ pub enum InternalEvent {
         /// The peer's id.
         peer_id: PeerId,
     },
+
+    /// A sync stream with a peer was opened.
+    SyncStreamOpened {
+        /// The peer's id.
+        peer_id: PeerId,
+        /// Whether the stream was requested by the peer or by us.
+        origin: Origin,
+        /// The negotiated stream.
+        stream: SyncStream,
+    },
 }
 
 /// Allows the user to receive [`Event`]s published by the network layer.
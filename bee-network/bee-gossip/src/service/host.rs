@@ -24,11 +24,13 @@ use crate::{
     alias,
     init::global::{self, reconnect_interval_secs},
     peer::{
+        ban::{unix_now_secs, Ban},
         error::Error as PeerError,
         info::{PeerInfo, PeerRelation},
         list::PeerListWrapper as PeerList,
+        stores::BanStoreHandle,
     },
-    swarm::protocols::iota_gossip,
+    swarm::protocols::iota_gossip::{self, limiter::UploadLimiter, GossipMessage},
 };
 
 const MAX_PEER_STATE_CHECKER_DELAY_MILLIS: u64 = 2000;
@@ -39,6 +41,8 @@ pub struct ServiceHostConfig {
     pub senders: Senders,
     pub receivers: Receivers,
     pub peerlist: PeerList,
+    pub(crate) ban_store: BanStoreHandle,
+    pub(crate) upload_limiter: UploadLimiter,
 }
 
 #[derive(Clone)]
@@ -86,6 +90,8 @@ pub mod integrated {
                 senders,
                 receivers,
                 peerlist,
+                ban_store,
+                upload_limiter,
             } = config;
 
             let Receivers {
@@ -94,12 +100,18 @@ pub mod integrated {
             } = receivers;
 
             node.spawn::<Self, _, _>(|shutdown| {
-                command_processor(shutdown, commands, senders.clone(), peerlist.clone())
+                command_processor(shutdown, commands, senders.clone(), peerlist.clone(), ban_store.clone())
             });
             node.spawn::<Self, _, _>(|shutdown| {
-                event_processor(shutdown, internal_events, senders.clone(), peerlist.clone())
+                event_processor(
+                    shutdown,
+                    internal_events,
+                    senders.clone(),
+                    peerlist.clone(),
+                    upload_limiter,
+                )
             });
-            node.spawn::<Self, _, _>(|shutdown| peerstate_checker(shutdown, senders, peerlist));
+            node.spawn::<Self, _, _>(|shutdown| peerstate_checker(shutdown, senders, peerlist, ban_store));
 
             info!("Network service started.");
 
@@ -127,6 +139,8 @@ pub mod standalone {
                 senders,
                 receivers,
                 peerlist,
+                ban_store,
+                upload_limiter,
             } = config;
 
             let Receivers {
@@ -150,27 +164,35 @@ pub mod standalone {
                 commands,
                 senders.clone(),
                 peerlist.clone(),
+                ban_store.clone(),
             ));
             tokio::spawn(event_processor(
                 shutdown_rx2,
                 internal_events,
                 senders.clone(),
                 peerlist.clone(),
+                upload_limiter,
             ));
-            tokio::spawn(peerstate_checker(shutdown_rx3, senders, peerlist));
+            tokio::spawn(peerstate_checker(shutdown_rx3, senders, peerlist, ban_store));
 
             info!("Network service started.");
         }
     }
 }
 
-async fn command_processor(shutdown: Shutdown, commands: CommandReceiver, senders: Senders, peerlist: PeerList) {
+async fn command_processor(
+    shutdown: Shutdown,
+    commands: CommandReceiver,
+    senders: Senders,
+    peerlist: PeerList,
+    ban_store: BanStoreHandle,
+) {
     debug!("Command processor running.");
 
     let mut commands = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(commands));
 
     while let Some(command) = commands.next().await {
-        if let Err(e) = process_command(command, &senders, &peerlist).await {
+        if let Err(e) = process_command(command, &senders, &peerlist, &ban_store).await {
             // Note: commands are allowed to fail as the user may not be up-to-date.
             debug!("Command could not be executed. Cause: {}", e);
             continue;
@@ -180,13 +202,19 @@ async fn command_processor(shutdown: Shutdown, commands: CommandReceiver, sender
     debug!("Command processor stopped.");
 }
 
-async fn event_processor(shutdown: Shutdown, events: InternalEventReceiver, senders: Senders, peerlist: PeerList) {
+async fn event_processor(
+    shutdown: Shutdown,
+    events: InternalEventReceiver,
+    senders: Senders,
+    peerlist: PeerList,
+    upload_limiter: UploadLimiter,
+) {
     debug!("Event processor running.");
 
     let mut internal_events = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(events));
 
     while let Some(internal_event) = internal_events.next().await {
-        if let Err(e) = process_internal_event(internal_event, &senders, &peerlist).await {
+        if let Err(e) = process_internal_event(internal_event, &senders, &peerlist, &upload_limiter).await {
             error!("Error processing internal event. Cause: {}", e);
             continue;
         }
@@ -195,8 +223,7 @@ async fn event_processor(shutdown: Shutdown, events: InternalEventReceiver, send
     debug!("Event processor stopped.");
 }
 
-// TODO: implement exponential back-off to not spam the peer with reconnect attempts.
-async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerList) {
+async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerList, ban_store: BanStoreHandle) {
     debug!("Peer checker running.");
 
     // NOTE:
@@ -214,6 +241,8 @@ async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerL
     // Check, if there are any disconnected known peers, and schedule a reconnect attempt for each
     // of those.
     while interval.next().await.is_some() {
+        remove_expired_bans(&senders, &peerlist, &ban_store).await;
+
         let read = peerlist.0.read().await;
 
         // To how many known peers are we currently connected.
@@ -242,16 +271,32 @@ async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerL
             num_disconnected,
         );
 
+        let now = Instant::now().into_std();
+
         // Automatically try to reconnect known **and** discovered peers. The removal of discovered peers is a decision
         // that needs to be made in the autopeering service.
         for (peer_id, peer_info, peer_metrics) in read.filter(|info, state, _| {
             (info.relation.is_known() || info.relation.is_discovered()) && state.is_disconnected()
         }) {
+            // Back off from peers that didn't respond to our previous reconnect attempts.
+            if !peer_metrics.is_dial_due(now) {
+                continue;
+            }
+
             if peer_metrics.num_dials >= MAX_DIALS {
-                log::debug!("Peer {} is unreachable.", peer_id);
+                if peer_metrics.num_dials == MAX_DIALS || !peer_info.relation.is_known() {
+                    log::debug!("Peer {} is unreachable.", peer_id);
 
-                let _ = senders.events.send(Event::PeerUnreachable { peer_id, peer_info });
-                continue;
+                    let _ = senders.events.send(Event::PeerUnreachable {
+                        peer_id,
+                        peer_info: peer_info.clone(),
+                    });
+                }
+
+                // We never give up on known peers, but only dial them at the (maximum) back-off interval.
+                if !peer_info.relation.is_known() {
+                    continue;
+                }
             }
 
             debug!("Trying to reconnect to: {} ({}).", peer_info.alias, alias!(peer_id));
@@ -264,7 +309,36 @@ async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerL
     debug!("Peer checker stopped.");
 }
 
-async fn process_command(command: Command, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
+async fn remove_expired_bans(senders: &Senders, peerlist: &PeerList, ban_store: &BanStoreHandle) {
+    let (peer_ids, addresses) = peerlist.0.write().await.remove_expired_bans(unix_now_secs());
+
+    for peer_id in peer_ids {
+        info!("Ban of {} expired.", alias!(peer_id));
+
+        if let Err(e) = ban_store.delete_peer_ban(&peer_id) {
+            warn!("Deleting the ban of {} failed: {}.", alias!(peer_id), e);
+        }
+
+        let _ = senders.events.send(Event::PeerUnbanned { peer_id });
+    }
+
+    for address in addresses {
+        info!("Ban of {} expired.", address);
+
+        if let Err(e) = ban_store.delete_address_ban(&address) {
+            warn!("Deleting the ban of {} failed: {}.", address, e);
+        }
+
+        let _ = senders.events.send(Event::AddressUnbanned { address });
+    }
+}
+
+async fn process_command(
+    command: Command,
+    senders: &Senders,
+    peerlist: &PeerList,
+    ban_store: &BanStoreHandle,
+) -> Result<(), Error> {
     trace!("Received {:?}.", command);
 
     match command {
@@ -282,8 +356,19 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
             let _ = senders.internal_commands.send(Command::DialPeer { peer_id });
         }
 
-        Command::BanAddress { address } => {
-            peerlist.0.write().await.ban_address(address.clone())?;
+        Command::BanAddress {
+            address,
+            expires_at,
+            reason,
+        } => {
+            let ban = Ban::new(expires_at, reason);
+
+            peerlist.0.write().await.ban_address(address.clone(), ban.clone())?;
+
+            // The ban is in effect anyway, it just won't survive a restart.
+            if let Err(e) = ban_store.store_address_ban(&address, &ban) {
+                warn!("Persisting the ban of {} failed: {}.", address, e);
+            }
 
             senders
                 .events
@@ -291,8 +376,19 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
                 .map_err(|_| Error::SendingEventFailed)?;
         }
 
-        Command::BanPeer { peer_id } => {
-            peerlist.0.write().await.ban_peer(peer_id)?;
+        Command::BanPeer {
+            peer_id,
+            expires_at,
+            reason,
+        } => {
+            let ban = Ban::new(expires_at, reason);
+
+            peerlist.0.write().await.ban_peer(peer_id, ban.clone())?;
+
+            // The ban is in effect anyway, it just won't survive a restart.
+            if let Err(e) = ban_store.store_peer_ban(&peer_id, &ban) {
+                warn!("Persisting the ban of {} failed: {}.", alias!(peer_id), e);
+            }
 
             senders
                 .events
@@ -333,6 +429,10 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
         Command::UnbanAddress { address } => {
             peerlist.0.write().await.unban_address(&address)?;
 
+            if let Err(e) = ban_store.delete_address_ban(&address) {
+                warn!("Deleting the ban of {} failed: {}.", address, e);
+            }
+
             senders
                 .events
                 .send(Event::AddressUnbanned { address })
@@ -342,11 +442,31 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
         Command::UnbanPeer { peer_id } => {
             peerlist.0.write().await.unban_peer(&peer_id)?;
 
+            if let Err(e) = ban_store.delete_peer_ban(&peer_id) {
+                warn!("Deleting the ban of {} failed: {}.", alias!(peer_id), e);
+            }
+
             senders
                 .events
                 .send(Event::PeerUnbanned { peer_id })
                 .map_err(|_| Error::SendingEventFailed)?;
         }
+
+        Command::OpenSyncStream { peer_id } => {
+            if !peerlist
+                .0
+                .read()
+                .await
+                .satisfies(&peer_id, |_, state, _| state.is_connected())?
+            {
+                return Err(PeerError::PeerIsDisconnected(peer_id).into());
+            }
+
+            senders
+                .internal_commands
+                .send(Command::OpenSyncStream { peer_id })
+                .map_err(|_| Error::SendingCommandFailed)?;
+        }
     }
 
     Ok(())
@@ -356,6 +476,7 @@ async fn process_internal_event(
     internal_event: InternalEvent,
     senders: &Senders,
     peerlist: &PeerList,
+    upload_limiter: &UploadLimiter,
 ) -> Result<(), Error> {
     match internal_event {
         InternalEvent::AddressBound { address } => {
@@ -370,6 +491,7 @@ async fn process_internal_event(
 
             // Try to disconnect, but ignore errors in-case the peer was disconnected already.
             let _ = peerlist.update_state(&peer_id, |state| state.set_disconnected());
+            let _ = peerlist.update_metrics(&peer_id, |m| m.record_disconnected(Instant::now().into_std()));
 
             // Only remove unknown peers.
             // NOTE: discovered peers should be removed manually via command if the autopeering protocol suggests it.
@@ -404,6 +526,7 @@ async fn process_internal_event(
             peer_addr,
             origin,
             substream,
+            version,
         } => {
             let mut peerlist = peerlist.0.write().await;
             let mut peer_added = false;
@@ -436,8 +559,8 @@ async fn process_internal_event(
                 let inbound_gossip_rx = BufReader::with_capacity(IO_BUFFER_LEN, r);
                 let outbound_gossip_tx = BufWriter::with_capacity(IO_BUFFER_LEN, w);
 
-                let (inbound_gossip_tx, gossip_in) = iota_gossip::channel();
-                let (gossip_out, outbound_gossip_rx) = iota_gossip::channel();
+                let (inbound_gossip_tx, gossip_in) = iota_gossip::inbound_channel();
+                let (gossip_out, outbound_gossip_rx) = iota_gossip::outbound_channel();
 
                 iota_gossip::start_inbound_gossip_handler(
                     peer_id,
@@ -449,11 +572,13 @@ async fn process_internal_event(
                     peer_id,
                     outbound_gossip_tx,
                     outbound_gossip_rx,
+                    upload_limiter.peer(),
                     senders.internal_events.clone(),
                 );
 
                 // We store a clone of the gossip send channel in order to send a shutdown signal.
                 let _ = peerlist.update_state(&peer_id, |state| state.set_connected(gossip_out.clone()));
+                let _ = peerlist.update_metrics(&peer_id, |m| m.record_connected(Instant::now().into_std()));
 
                 // We no longer need to hold the lock.
                 drop(peerlist);
@@ -485,6 +610,7 @@ async fn process_internal_event(
                         info: peer_info,
                         gossip_in,
                         gossip_out,
+                        gossip_version: version,
                     })
                     .map_err(|_| Error::SendingEventFailed)?;
             } else {
@@ -505,8 +631,9 @@ async fn process_internal_event(
 
         InternalEvent::PeerIdentified { peer_id } => {
             let _ = peerlist.0.write().await.update_metrics(&peer_id, |m| {
-                // Reset dial count.
+                // Reset dial count and back-off.
                 m.num_dials = 0;
+                m.next_dial_at = None;
                 // Update Identify timestamp.
                 m.identified_at = Some(
                     SystemTime::now()
@@ -516,6 +643,32 @@ async fn process_internal_event(
                 );
             });
         }
+
+        InternalEvent::SyncStreamOpened {
+            peer_id,
+            origin,
+            stream,
+        } => {
+            // Sync streams are only served to peers we are gossiping with.
+            if peerlist
+                .0
+                .read()
+                .await
+                .satisfies(&peer_id, |_, state, _| state.is_connected())
+                .unwrap_or(false)
+            {
+                senders
+                    .events
+                    .send(Event::SyncStreamOpened {
+                        peer_id,
+                        origin,
+                        stream,
+                    })
+                    .map_err(|_| Error::SendingEventFailed)?;
+            } else {
+                debug!("Dropping sync stream with unconnected peer {}.", alias!(peer_id));
+            }
+        }
     }
 
     Ok(())
@@ -635,11 +788,10 @@ async fn remove_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList) ->
 }
 
 async fn disconnect_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
-    let state_update = peerlist
-        .0
-        .write()
-        .await
-        .update_state(&peer_id, |state| state.set_disconnected());
+    let mut write = peerlist.0.write().await;
+    let state_update = write.update_state(&peer_id, |state| state.set_disconnected());
+    let _ = write.update_metrics(&peer_id, |m| m.record_disconnected(Instant::now().into_std()));
+    drop(write);
 
     match state_update {
         Ok(Some(gossip_sender)) => {
@@ -651,9 +803,9 @@ async fn disconnect_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList
                 .send(Event::PeerDisconnected { peer_id })
                 .map_err(|_| Error::SendingEventFailed)?;
 
-            // Try to send the shutdown signal. It has to be a Vec<u8>, but it doesn't have to allocate.
+            // Try to send the shutdown signal. It has to be a message, but its bytes don't have to allocate.
             // We ignore the potential error in case that peer disconnected from us already in the meantime.
-            let _ = gossip_sender.send(Vec::new());
+            let _ = gossip_sender.send(GossipMessage::new(Vec::new()));
 
             Ok(())
         }
@@ -2,20 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libp2p::{
-    identify::{Identify, IdentifyConfig, IdentifyEvent},
-    NetworkBehaviour,
+    connection_limits::{Behaviour as ConnectionLimits, ConnectionLimits as ConnectionLimitsConfig},
+    identify::{Behaviour as Identify, Config as IdentifyConfig, Event as IdentifyEvent},
+    swarm::NetworkBehaviour,
 };
-use libp2p_core::identity::PublicKey;
+use libp2p_identity::{PeerId, PublicKey};
+use void::Void;
 
-use super::protocols::iota_gossip::{IotaGossipEvent, IotaGossipProtocol};
+use super::protocols::{
+    iota_gossip::{IotaGossipEvent, IotaGossipProtocol},
+    iota_sync::{IotaSyncEvent, IotaSyncProtocol},
+};
 
 const IOTA_PROTOCOL_VERSION: &str = "iota/0.1.0";
+const MAX_CONNECTIONS_PER_PEER: u32 = 1;
 
 #[derive(NetworkBehaviour)]
 #[behaviour(out_event = "SwarmBehaviourEvent")]
 pub struct SwarmBehaviour {
+    limits: ConnectionLimits,
     identify: Identify,
     gossip: IotaGossipProtocol,
+    sync: IotaSyncProtocol,
 }
 
 impl SwarmBehaviour {
@@ -23,16 +31,31 @@ impl SwarmBehaviour {
         let protocol_version = IOTA_PROTOCOL_VERSION.to_string();
         let config = IdentifyConfig::new(protocol_version, local_pk);
 
+        let limits = ConnectionLimitsConfig::default().with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
+
         Self {
+            limits: ConnectionLimits::new(limits),
             identify: Identify::new(config),
             gossip: IotaGossipProtocol::new(),
+            sync: IotaSyncProtocol::new(),
         }
     }
+
+    pub fn open_sync_stream(&mut self, peer_id: PeerId) {
+        self.sync.open_stream(peer_id);
+    }
 }
 
 pub enum SwarmBehaviourEvent {
     Identify(Box<IdentifyEvent>),
     Gossip(Box<IotaGossipEvent>),
+    Sync(Box<IotaSyncEvent>),
+}
+
+impl From<Void> for SwarmBehaviourEvent {
+    fn from(event: Void) -> Self {
+        void::unreachable(event)
+    }
 }
 
 impl From<IdentifyEvent> for SwarmBehaviourEvent {
@@ -46,3 +69,9 @@ impl From<IotaGossipEvent> for SwarmBehaviourEvent {
         SwarmBehaviourEvent::Gossip(Box::new(event))
     }
 }
+
+impl From<IotaSyncEvent> for SwarmBehaviourEvent {
+    fn from(event: IotaSyncEvent) -> Self {
+        SwarmBehaviourEvent::Sync(Box::new(event))
+    }
+}
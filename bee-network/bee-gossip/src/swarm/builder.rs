@@ -3,62 +3,80 @@
 
 use std::time::Duration;
 
+#[cfg(feature = "quic")]
+use futures::future::Either;
 use libp2p::{
-    core::upgrade::{self, SelectUpgrade},
-    dns, identity, mplex, noise,
-    swarm::{ConnectionLimits, SwarmBuilder},
+    core::{
+        muxing::StreamMuxerBox,
+        upgrade::{self, SelectUpgrade},
+    },
+    dns, identity, noise,
+    swarm::SwarmBuilder,
     tcp, yamux, Swarm, Transport,
 };
+use libp2p_mplex as mplex;
+#[cfg(feature = "quic")]
+use libp2p_quic as quic;
 
-use super::{behaviour::SwarmBehaviour, error::Error};
+use super::{
+    behaviour::SwarmBehaviour,
+    error::Error,
+    gater::{ConnectionGater, GatedTransport},
+};
 
-const MAX_CONNECTIONS_PER_PEER: u32 = 1;
 const DEFAULT_CONNECTION_TIMEOUT_SECS: u64 = 10;
 
-pub fn build_swarm(local_keys: &identity::Keypair) -> Result<Swarm<SwarmBehaviour>, Error> {
+pub fn build_swarm(
+    local_keys: &identity::Keypair,
+    in_memory: bool,
+    connection_gater: ConnectionGater,
+) -> Result<Swarm<SwarmBehaviour>, Error> {
     let local_pk = local_keys.public();
     let local_id = local_pk.to_peer_id();
 
-    let noise_keys = noise::Keypair::<noise::X25519Spec>::new()
-        .into_authentic(local_keys)
-        .map_err(|_| Error::CreatingNoiseKeysFailed)?;
-
-    let noi_config = noise::NoiseConfig::xx(noise_keys);
+    let noi_config = noise::Config::new(local_keys).map_err(|_| Error::CreatingNoiseKeysFailed)?;
     let mpx_config = mplex::MplexConfig::default();
-    let ymx_config = yamux::YamuxConfig::default();
+    let ymx_config = yamux::Config::default();
 
-    let transport = if cfg!(test) {
+    let transport = if in_memory {
         use libp2p_core::transport::MemoryTransport;
 
         MemoryTransport::default()
             .upgrade(upgrade::Version::V1Lazy)
-            .authenticate(noi_config.into_authenticated())
+            .authenticate(noi_config.clone())
             .multiplex(SelectUpgrade::new(ymx_config, mpx_config))
             .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS))
             .boxed()
     } else {
-        let tcp_transport = tcp::TokioTcpTransport::new(tcp::GenTcpConfig::new().nodelay(true).port_reuse(true));
-        let dns_config = dns::TokioDnsConfig::system(tcp_transport)?;
-
-        dns_config
+        let tcp_transport = tcp::tokio::Transport::new(tcp::Config::new().nodelay(true).port_reuse(true))
             .upgrade(upgrade::Version::V1Lazy)
-            .authenticate(noi_config.into_authenticated())
+            .authenticate(noi_config)
             .multiplex(SelectUpgrade::new(ymx_config, mpx_config))
-            .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS))
-            .boxed()
+            .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS));
+
+        // QUIC brings its own encryption and multiplexing, so it is only combined with the fully upgraded TCP
+        // transport.
+        #[cfg(feature = "quic")]
+        let transport = tcp_transport
+            .or_transport(quic::tokio::Transport::new(quic::Config::new(local_keys)))
+            .map(|output, _| match output {
+                Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                Either::Right((peer_id, connection)) => (peer_id, StreamMuxerBox::new(connection)),
+            });
+        #[cfg(not(feature = "quic"))]
+        let transport = tcp_transport.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+
+        dns::TokioDnsConfig::system(transport)?.boxed()
     };
 
+    // Inbound connections are gated before any protocol is negotiated on them.
+    let transport = GatedTransport::new(transport, connection_gater).boxed();
+
     let behaviour = SwarmBehaviour::new(local_pk);
-    let limits = ConnectionLimits::default().with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
 
-    let swarm = SwarmBuilder::new(transport, behaviour, local_id)
-        .connection_limits(limits)
-        // We want the connection background tasks to be spawned
-        // onto the tokio runtime.
-        .executor(Box::new(|fut| {
-            tokio::spawn(fut);
-        }))
-        .build();
+    // We want the connection background tasks to be spawned
+    // onto the tokio runtime.
+    let swarm = SwarmBuilder::with_tokio_executor(transport, behaviour, local_id).build();
 
     Ok(swarm)
 }
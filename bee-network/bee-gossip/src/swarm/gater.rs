@@ -0,0 +1,435 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    future::Future,
+    net::IpAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use hashbrown::HashMap;
+use ipnet::IpNet;
+use libp2p::{
+    core::transport::{ListenerId, TransportError, TransportEvent},
+    multiaddr::Protocol,
+    Multiaddr, Transport,
+};
+use log::debug;
+
+use crate::config::ConnectionGaterConfig;
+
+const IPV4_SUBNET_PREFIX_LEN: u8 = 24;
+const IPV6_SUBNET_PREFIX_LEN: u8 = 48;
+
+/// The reasons for rejecting an inbound connection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Rejection {
+    /// The address is part of a denied CIDR.
+    Denied,
+    /// The address is not part of any allowed CIDR.
+    NotAllowed,
+    /// Too many inbound connections are pending.
+    PendingLimit,
+    /// Too many inbound connections from the same IP address.
+    IpLimit,
+    /// Too many inbound connections from the same subnet.
+    SubnetLimit,
+}
+
+/// Counts the inbound connections rejected by the connection gater.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionGaterMetrics(Arc<ConnectionGaterMetricsInner>);
+
+#[derive(Debug, Default)]
+struct ConnectionGaterMetricsInner {
+    denied: AtomicU64,
+    not_allowed: AtomicU64,
+    pending_limit: AtomicU64,
+    ip_limit: AtomicU64,
+    subnet_limit: AtomicU64,
+}
+
+impl ConnectionGaterMetrics {
+    /// Returns the number of connections rejected because they originated from a denied CIDR.
+    pub fn rejected_denied(&self) -> u64 {
+        self.0.denied.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of connections rejected because they didn't originate from an allowed CIDR.
+    pub fn rejected_not_allowed(&self) -> u64 {
+        self.0.not_allowed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of connections rejected because too many inbound connections were pending.
+    pub fn rejected_pending_limit(&self) -> u64 {
+        self.0.pending_limit.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of connections rejected because of the limit per IP address.
+    pub fn rejected_ip_limit(&self) -> u64 {
+        self.0.ip_limit.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of connections rejected because of the limit per subnet.
+    pub fn rejected_subnet_limit(&self) -> u64 {
+        self.0.subnet_limit.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of rejected connections.
+    pub fn rejected(&self) -> u64 {
+        self.rejected_denied()
+            + self.rejected_not_allowed()
+            + self.rejected_pending_limit()
+            + self.rejected_ip_limit()
+            + self.rejected_subnet_limit()
+    }
+
+    fn count(&self, rejection: Rejection) {
+        let counter = match rejection {
+            Rejection::Denied => &self.0.denied,
+            Rejection::NotAllowed => &self.0.not_allowed,
+            Rejection::PendingLimit => &self.0.pending_limit,
+            Rejection::IpLimit => &self.0.ip_limit,
+            Rejection::SubnetLimit => &self.0.subnet_limit,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts the pending inbound connections, and the pending or established ones per IP address and subnet.
+#[derive(Default)]
+struct GaterState {
+    num_pending: usize,
+    per_ip: HashMap<IpAddr, usize>,
+    per_subnet: HashMap<IpNet, usize>,
+}
+
+impl GaterState {
+    fn add(&mut self, ip: IpAddr) {
+        *self.per_ip.entry(ip).or_default() += 1;
+        *self.per_subnet.entry(subnet_of(ip)).or_default() += 1;
+    }
+
+    fn remove(&mut self, ip: IpAddr) {
+        fn decrement<K: Eq + std::hash::Hash>(counts: &mut HashMap<K, usize>, key: K) {
+            if let hashbrown::hash_map::Entry::Occupied(mut entry) = counts.entry(key) {
+                *entry.get_mut() -= 1;
+
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+        }
+
+        decrement(&mut self.per_ip, ip);
+        decrement(&mut self.per_subnet, subnet_of(ip));
+    }
+}
+
+/// Decides whether inbound connections are accepted, before any protocol is negotiated on them.
+#[derive(Clone)]
+pub(crate) struct ConnectionGater {
+    config: Arc<ConnectionGaterConfig>,
+    state: Arc<Mutex<GaterState>>,
+    metrics: ConnectionGaterMetrics,
+}
+
+impl ConnectionGater {
+    pub(crate) fn new(config: ConnectionGaterConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Arc::default(),
+            metrics: ConnectionGaterMetrics::default(),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> &ConnectionGaterMetrics {
+        &self.metrics
+    }
+
+    /// Admits a new pending inbound connection from the given address, or rejects it.
+    fn admit(&self, addr: &Multiaddr) -> Result<PendingGuard, Rejection> {
+        let mut state = self.state.lock().expect("gater state");
+
+        if let Err(rejection) = self.check(&state, addr) {
+            self.metrics.count(rejection);
+            return Err(rejection);
+        }
+
+        // Pending connections count towards the limits per IP address and subnet as well, so that a burst of them
+        // can't slip through before any of them is established.
+        let ip = ip_of(addr);
+
+        state.num_pending += 1;
+        if let Some(ip) = ip {
+            state.add(ip);
+        }
+
+        Ok(PendingGuard {
+            gater: self.clone(),
+            ip,
+        })
+    }
+
+    fn check(&self, state: &GaterState, addr: &Multiaddr) -> Result<(), Rejection> {
+        if state.num_pending >= self.config.max_pending_incoming {
+            return Err(Rejection::PendingLimit);
+        }
+
+        // Only IP based addresses (i.e. no in-memory ones) are subject to the remaining rules.
+        let ip = match ip_of(addr) {
+            Some(ip) => ip,
+            None => return Ok(()),
+        };
+
+        if self.config.denied_cidrs.iter().any(|cidr| cidr.contains(&ip)) {
+            return Err(Rejection::Denied);
+        }
+
+        if !self.config.allowed_cidrs.is_empty() && !self.config.allowed_cidrs.iter().any(|cidr| cidr.contains(&ip)) {
+            return Err(Rejection::NotAllowed);
+        }
+
+        if state.per_ip.get(&ip).copied().unwrap_or_default() >= self.config.max_connections_per_ip {
+            return Err(Rejection::IpLimit);
+        }
+
+        if state.per_subnet.get(&subnet_of(ip)).copied().unwrap_or_default() >= self.config.max_connections_per_subnet {
+            return Err(Rejection::SubnetLimit);
+        }
+
+        Ok(())
+    }
+
+    /// Accounts for an established inbound connection from the given address.
+    pub(crate) fn inbound_connection_established(&self, addr: &Multiaddr) {
+        if let Some(ip) = ip_of(addr) {
+            self.state.lock().expect("gater state").add(ip);
+        }
+    }
+
+    /// Accounts for a closed inbound connection from the given address.
+    pub(crate) fn inbound_connection_closed(&self, addr: &Multiaddr) {
+        if let Some(ip) = ip_of(addr) {
+            self.state.lock().expect("gater state").remove(ip);
+        }
+    }
+}
+
+/// Keeps an inbound connection accounted as pending until it was negotiated (or failed to).
+struct PendingGuard {
+    gater: ConnectionGater,
+    ip: Option<IpAddr>,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        let mut state = self.gater.state.lock().expect("gater state");
+
+        state.num_pending -= 1;
+        if let Some(ip) = self.ip {
+            state.remove(ip);
+        }
+    }
+}
+
+fn ip_of(addr: &Multiaddr) -> Option<IpAddr> {
+    match addr.iter().next()? {
+        Protocol::Ip4(ip) => Some(ip.into()),
+        Protocol::Ip6(ip) => Some(ip.into()),
+        _ => None,
+    }
+}
+
+fn subnet_of(ip: IpAddr) -> IpNet {
+    let prefix_len = match ip {
+        IpAddr::V4(_) => IPV4_SUBNET_PREFIX_LEN,
+        IpAddr::V6(_) => IPV6_SUBNET_PREFIX_LEN,
+    };
+
+    // Panic: the prefix lengths are valid for their respective IP versions.
+    IpNet::new(ip, prefix_len).unwrap().trunc()
+}
+
+/// A [`Transport`] that drops inbound connections rejected by the [`ConnectionGater`].
+pub(crate) struct GatedTransport<T> {
+    inner: T,
+    gater: ConnectionGater,
+}
+
+impl<T> GatedTransport<T> {
+    pub(crate) fn new(inner: T, gater: ConnectionGater) -> Self {
+        Self { inner, gater }
+    }
+}
+
+impl<T> Transport for GatedTransport<T>
+where
+    T: Transport + Unpin,
+    T::ListenerUpgrade: Unpin,
+{
+    type Output = T::Output;
+    type Error = T::Error;
+    type ListenerUpgrade = GatedUpgrade<T::ListenerUpgrade>;
+    type Dial = T::Dial;
+
+    fn listen_on(&mut self, addr: Multiaddr) -> Result<ListenerId, TransportError<Self::Error>> {
+        self.inner.listen_on(addr)
+    }
+
+    fn remove_listener(&mut self, id: ListenerId) -> bool {
+        self.inner.remove_listener(id)
+    }
+
+    fn dial(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
+        self.inner.dial(addr)
+    }
+
+    fn dial_as_listener(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
+        self.inner.dial_as_listener(addr)
+    }
+
+    fn address_translation(&self, listen: &Multiaddr, observed: &Multiaddr) -> Option<Multiaddr> {
+        self.inner.address_translation(listen, observed)
+    }
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+        loop {
+            let event = match Pin::new(&mut self.inner).poll(cx) {
+                Poll::Ready(event) => event,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let pending = if let TransportEvent::Incoming { send_back_addr, .. } = &event {
+                match self.gater.admit(send_back_addr) {
+                    Ok(pending) => Some(pending),
+                    Err(rejection) => {
+                        debug!("Rejected inbound connection from {}: {:?}.", send_back_addr, rejection);
+                        // Dropping the upgrade closes the connection.
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+
+            return Poll::Ready(event.map_upgrade(|inner| GatedUpgrade {
+                inner,
+                _pending: pending,
+            }));
+        }
+    }
+}
+
+/// The upgrade of an admitted inbound connection.
+pub(crate) struct GatedUpgrade<U> {
+    inner: U,
+    _pending: Option<PendingGuard>,
+}
+
+impl<U: Future + Unpin> Future for GatedUpgrade<U> {
+    type Output = U::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConnectionGaterConfigBuilder;
+
+    fn gater(builder: ConnectionGaterConfigBuilder) -> ConnectionGater {
+        ConnectionGater::new(builder.finish().unwrap())
+    }
+
+    fn addr(ip: &str) -> Multiaddr {
+        format!("/ip4/{}/tcp/15600", ip).parse().unwrap()
+    }
+
+    #[test]
+    fn cidr_lists() {
+        let gater = gater(
+            ConnectionGaterConfigBuilder::new()
+                .with_allowed_cidrs(["10.0.0.0/8".to_string()])
+                .with_denied_cidrs(["10.1.0.0/16".to_string()]),
+        );
+
+        assert!(gater.admit(&addr("10.0.0.1")).is_ok());
+        assert_eq!(gater.admit(&addr("10.1.0.1")).err(), Some(Rejection::Denied));
+        assert_eq!(gater.admit(&addr("192.168.0.1")).err(), Some(Rejection::NotAllowed));
+        // In-memory addresses are not subject to the CIDR lists.
+        assert!(gater.admit(&"/memory/1337".parse().unwrap()).is_ok());
+
+        assert_eq!(gater.metrics().rejected_denied(), 1);
+        assert_eq!(gater.metrics().rejected_not_allowed(), 1);
+        assert_eq!(gater.metrics().rejected(), 2);
+    }
+
+    #[test]
+    fn pending_limit() {
+        let gater = gater(ConnectionGaterConfigBuilder::new().with_max_pending_incoming(1));
+
+        let pending = gater.admit(&addr("10.0.0.1")).unwrap();
+        assert_eq!(gater.admit(&addr("10.0.0.2")).err(), Some(Rejection::PendingLimit));
+
+        drop(pending);
+        assert!(gater.admit(&addr("10.0.0.2")).is_ok());
+        assert_eq!(gater.metrics().rejected_pending_limit(), 1);
+    }
+
+    #[test]
+    fn ip_and_subnet_limits() {
+        let gater = gater(
+            ConnectionGaterConfigBuilder::new()
+                .with_max_connections_per_ip(1)
+                .with_max_connections_per_subnet(2),
+        );
+
+        gater.inbound_connection_established(&addr("10.0.0.1"));
+        assert_eq!(gater.admit(&addr("10.0.0.1")).err(), Some(Rejection::IpLimit));
+
+        gater.inbound_connection_established(&addr("10.0.0.2"));
+        assert_eq!(gater.admit(&addr("10.0.0.3")).err(), Some(Rejection::SubnetLimit));
+        assert!(gater.admit(&addr("10.0.1.1")).is_ok());
+
+        gater.inbound_connection_closed(&addr("10.0.0.1"));
+        assert!(gater.admit(&addr("10.0.0.1")).is_ok());
+
+        assert_eq!(gater.metrics().rejected_ip_limit(), 1);
+        assert_eq!(gater.metrics().rejected_subnet_limit(), 1);
+    }
+
+    #[test]
+    fn pending_connections_count_towards_ip_and_subnet_limits() {
+        let gater = gater(
+            ConnectionGaterConfigBuilder::new()
+                .with_max_connections_per_ip(1)
+                .with_max_connections_per_subnet(2),
+        );
+
+        let pending1 = gater.admit(&addr("10.0.0.1")).unwrap();
+        assert_eq!(gater.admit(&addr("10.0.0.1")).err(), Some(Rejection::IpLimit));
+
+        let pending2 = gater.admit(&addr("10.0.0.2")).unwrap();
+        assert_eq!(gater.admit(&addr("10.0.0.3")).err(), Some(Rejection::SubnetLimit));
+
+        drop(pending1);
+        drop(pending2);
+        assert!(gater.admit(&addr("10.0.0.1")).is_ok());
+        assert!(gater.admit(&addr("10.0.0.3")).is_ok());
+
+        assert_eq!(gater.metrics().rejected_ip_limit(), 1);
+        assert_eq!(gater.metrics().rejected_subnet_limit(), 1);
+    }
+}
@@ -6,4 +6,5 @@
 pub mod behaviour;
 pub mod builder;
 pub mod error;
+pub mod gater;
 pub mod protocols;
@@ -25,6 +25,7 @@ pub enum IotaGossipEvent {
         peer_addr: Multiaddr,
         origin: Origin,
         substream: Box<NegotiatedSubstream>,
+        version: u8,
     },
 
     /// An error occurred during negotiation.
@@ -47,7 +48,10 @@ pub enum IotaGossipHandlerEvent {
     SentUpgradeRequest { to: PeerId },
 
     /// Successfully upgraded to the IOTA gossip protocol.
-    UpgradeCompleted { substream: Box<NegotiatedSubstream> },
+    UpgradeCompleted {
+        substream: Box<NegotiatedSubstream>,
+        version: u8,
+    },
 
     /// An error occurred during the upgrade.
     UpgradeError {
@@ -7,16 +7,9 @@ use std::{
     task::{Context, Poll},
 };
 
-use libp2p::{
-    core::upgrade::OutboundUpgrade,
-    swarm::{
-        handler::{
-            ConnectionHandler, ConnectionHandlerEvent, ConnectionHandlerUpgrErr, InboundUpgradeSend, KeepAlive,
-            SubstreamProtocol,
-        },
-        NegotiatedSubstream,
-    },
-    Multiaddr,
+use libp2p::swarm::handler::{
+    AddressChange, ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, DialUpgradeError, FullyNegotiatedInbound,
+    FullyNegotiatedOutbound, KeepAlive, ListenUpgradeError, SubstreamProtocol,
 };
 use log::*;
 
@@ -24,8 +17,8 @@ use super::{event::IotaGossipHandlerEvent, id::IotaGossipIdentifier, upgrade::Io
 use crate::network::origin::Origin;
 
 pub struct GossipProtocolHandler {
-    /// Exchanged protocol information necessary during negotiation.
-    info: IotaGossipIdentifier,
+    /// Exchanged protocol information necessary during negotiation, in order of preference.
+    info: Vec<IotaGossipIdentifier>,
 
     /// Keep alive setting.
     keep_alive: KeepAlive,
@@ -40,7 +33,7 @@ pub struct IotaGossipHandlerInEvent {
 }
 
 impl GossipProtocolHandler {
-    pub fn new(info: IotaGossipIdentifier) -> Self {
+    pub fn new(info: Vec<IotaGossipIdentifier>) -> Self {
         Self {
             info,
             keep_alive: KeepAlive::Yes,
@@ -70,13 +63,13 @@ impl ConnectionHandler for GossipProtocolHandler {
     fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
         debug!("gossip handler: responding to listen protocol request.");
 
-        SubstreamProtocol::new(IotaGossipProtocolUpgrade::new(self.info.clone()), ())
+        SubstreamProtocol::new(IotaGossipProtocolUpgrade::with_versions(self.info.clone()), ())
     }
 
     /// **libp2p docs**:
     ///
-    /// Injects an event coming from the outside in the handler.
-    fn inject_event(&mut self, incoming_event: IotaGossipHandlerInEvent) {
+    /// Informs the handler about an event from the [`NetworkBehaviour`](libp2p::swarm::NetworkBehaviour).
+    fn on_behaviour_event(&mut self, incoming_event: IotaGossipHandlerInEvent) {
         debug!("gossip handler: received in-event: {:?}", incoming_event);
 
         let IotaGossipHandlerInEvent { origin } = incoming_event;
@@ -84,7 +77,7 @@ impl ConnectionHandler for GossipProtocolHandler {
         // We only send the upgrade request if this handler belongs to an outbound connection.
         if origin == Origin::Outbound {
             let send_request = ConnectionHandlerEvent::OutboundSubstreamRequest {
-                protocol: SubstreamProtocol::new(IotaGossipProtocolUpgrade::new(self.info.clone()), ()),
+                protocol: SubstreamProtocol::new(IotaGossipProtocolUpgrade::with_versions(self.info.clone()), ()),
             };
 
             debug!("gossip handler: sending protocol upgrade request.");
@@ -95,72 +88,65 @@ impl ConnectionHandler for GossipProtocolHandler {
 
     /// **libp2p docs**:
     ///
-    /// Injects the output of a successful upgrade on a new inbound substream.
-    fn inject_fully_negotiated_inbound(&mut self, new_inbound: NegotiatedSubstream, _: Self::InboundOpenInfo) {
-        let negotiated_inbound = ConnectionHandlerEvent::Custom(IotaGossipHandlerEvent::UpgradeCompleted {
-            substream: Box::new(new_inbound),
-        });
-
-        debug!("gossip handler: fully negotiated inbound.");
-
-        self.events.push_back(negotiated_inbound);
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Injects the output of a successful upgrade on a new outbound substream.
-    ///
-    /// The second argument is the information that was previously passed to
-    /// [`ConnectionHandlerEvent::OutboundSubstreamRequest`].
-    fn inject_fully_negotiated_outbound(&mut self, new_outbound: NegotiatedSubstream, _: Self::OutboundOpenInfo) {
-        let negotiated_outbound = ConnectionHandlerEvent::Custom(IotaGossipHandlerEvent::UpgradeCompleted {
-            substream: Box::new(new_outbound),
-        });
-
-        debug!("gossip handler: fully negotiated outbound.");
-
-        self.events.push_back(negotiated_outbound);
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Notifies the handler of a change in the address of the remote.
-    fn inject_address_change(&mut self, new_address: &Multiaddr) {
-        debug!("gossip handler: new address: {}", new_address);
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Indicates to the handler that upgrading an outbound substream to the given protocol has failed.
-    fn inject_dial_upgrade_error(
+    /// Informs the handler about the negotiation, the failure or the address change of a substream.
+    fn on_connection_event(
         &mut self,
-        _: Self::OutboundOpenInfo,
-        e: ConnectionHandlerUpgrErr<<Self::OutboundProtocol as OutboundUpgrade<NegotiatedSubstream>>::Error>,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
     ) {
-        debug!("gossip handler: outbound upgrade error: {:?}", e);
-
-        // TODO: finish event management in case of an error.
-        // self.events.push_back(ProtocolsHandlerEvent::Close(e));
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Indicates to the handler that upgrading an inbound substream to the given protocol has failed.
-    fn inject_listen_upgrade_error(
-        &mut self,
-        _: Self::InboundOpenInfo,
-        e: ConnectionHandlerUpgrErr<<Self::InboundProtocol as InboundUpgradeSend>::Error>,
-    ) {
-        debug!("gossip handler: inbound upgrade error: {:?}", e);
-
-        // TODO: finish event management in case of an error.
-        // let err = match e {
-        //     ProtocolsHandlerUpgrErr::Timeout => io::Error::new(io::ErrorKind::TimedOut, "timeout"),
-        //     ProtocolsHandlerUpgrErr::Timer => io::Error::new(io::ErrorKind::TimedOut, "timer"),
-        //     ProtocolsHandlerUpgrErr::Upgrade(err) => err,
-        // };
-
-        // self.events.push_back(ProtocolsHandlerEvent::Close(err));
+        match event {
+            ConnectionEvent::FullyNegotiatedInbound(FullyNegotiatedInbound {
+                protocol: (new_inbound, id),
+                ..
+            }) => {
+                let negotiated_inbound = ConnectionHandlerEvent::Custom(IotaGossipHandlerEvent::UpgradeCompleted {
+                    substream: Box::new(new_inbound),
+                    version: id.version(),
+                });
+
+                debug!("gossip handler: fully negotiated inbound.");
+
+                self.events.push_back(negotiated_inbound);
+            }
+            ConnectionEvent::FullyNegotiatedOutbound(FullyNegotiatedOutbound {
+                protocol: (new_outbound, id),
+                ..
+            }) => {
+                let negotiated_outbound = ConnectionHandlerEvent::Custom(IotaGossipHandlerEvent::UpgradeCompleted {
+                    substream: Box::new(new_outbound),
+                    version: id.version(),
+                });
+
+                debug!("gossip handler: fully negotiated outbound.");
+
+                self.events.push_back(negotiated_outbound);
+            }
+            ConnectionEvent::AddressChange(AddressChange { new_address }) => {
+                debug!("gossip handler: new address: {}", new_address);
+            }
+            ConnectionEvent::DialUpgradeError(DialUpgradeError { error, .. }) => {
+                debug!("gossip handler: outbound upgrade error: {:?}", error);
+
+                // TODO: finish event management in case of an error.
+                // self.events.push_back(ProtocolsHandlerEvent::Close(e));
+            }
+            ConnectionEvent::ListenUpgradeError(ListenUpgradeError { error, .. }) => {
+                debug!("gossip handler: inbound upgrade error: {:?}", error);
+
+                // TODO: finish event management in case of an error.
+                // let err = match e {
+                //     ProtocolsHandlerUpgrErr::Timeout => io::Error::new(io::ErrorKind::TimedOut, "timeout"),
+                //     ProtocolsHandlerUpgrErr::Timer => io::Error::new(io::ErrorKind::TimedOut, "timer"),
+                //     ProtocolsHandlerUpgrErr::Upgrade(err) => err,
+                // };
+
+                // self.events.push_back(ProtocolsHandlerEvent::Close(err));
+            }
+        }
     }
 
     /// **libp2p docs**:
@@ -4,22 +4,33 @@
 use std::fmt;
 
 #[derive(Debug, Clone)]
-pub struct IotaGossipIdentifier(String);
+pub struct IotaGossipIdentifier {
+    id: String,
+    version: u8,
+}
 
 impl IotaGossipIdentifier {
-    pub fn new(name: impl AsRef<str>, network_id: u64, version: impl AsRef<str>) -> Self {
-        Self(format!("/{}/{}/{}", name.as_ref(), network_id, version.as_ref()))
+    pub fn new(name: impl AsRef<str>, network_id: u64, version: u8) -> Self {
+        Self {
+            id: format!("/{}/{}/{}.0.0", name.as_ref(), network_id, version),
+            version,
+        }
+    }
+
+    /// Returns the major version of the protocol.
+    pub fn version(&self) -> u8 {
+        self.version
     }
 }
 
 impl fmt::Display for IotaGossipIdentifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.id)
     }
 }
 
 impl AsRef<[u8]> for IotaGossipIdentifier {
     fn as_ref(&self) -> &[u8] {
-        self.0.as_ref()
+        self.id.as_ref()
     }
 }
@@ -10,6 +10,7 @@ use log::*;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+use super::limiter::PeerUploadLimiter;
 use crate::{
     alias,
     service::event::{InternalEvent, InternalEventSender},
@@ -17,13 +18,56 @@ use crate::{
 
 const MSG_BUFFER_LEN: usize = 32768;
 
-/// A type alias for an unbounded channel sender.
-pub type GossipSender = mpsc::UnboundedSender<Vec<u8>>;
+/// A message to be sent to a peer over its gossip stream.
+pub struct GossipMessage {
+    bytes: Vec<u8>,
+    droppable: bool,
+    on_sent: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl GossipMessage {
+    /// Creates a new `GossipMessage`, waiting for the upload limiter before being sent.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            droppable: false,
+            on_sent: None,
+        }
+    }
+
+    /// Drops the message instead of waiting if the upload limit is exceeded when it is due to be sent.
+    #[must_use]
+    pub fn droppable(mut self) -> Self {
+        self.droppable = true;
+        self
+    }
+
+    /// Calls `on_sent` once the message was written to the gossip stream.
+    #[must_use]
+    pub fn on_sent(mut self, on_sent: impl FnOnce() + Send + 'static) -> Self {
+        self.on_sent.replace(Box::new(on_sent));
+        self
+    }
+}
+
+impl From<Vec<u8>> for GossipMessage {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
 
-/// A type alias for an unbounded channel receiver.
+/// A type alias for an unbounded channel sender of outbound gossip.
+pub type GossipSender = mpsc::UnboundedSender<GossipMessage>;
+
+/// A type alias for an unbounded channel receiver of inbound gossip.
 pub type GossipReceiver = UnboundedReceiverStream<Vec<u8>>;
 
-pub fn channel() -> (GossipSender, GossipReceiver) {
+pub(crate) fn inbound_channel() -> (mpsc::UnboundedSender<Vec<u8>>, GossipReceiver) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    (sender, UnboundedReceiverStream::new(receiver))
+}
+
+pub(crate) fn outbound_channel() -> (GossipSender, UnboundedReceiverStream<GossipMessage>) {
     let (sender, receiver) = mpsc::unbounded_channel();
     (sender, UnboundedReceiverStream::new(receiver))
 }
@@ -31,7 +75,7 @@ pub fn channel() -> (GossipSender, GossipReceiver) {
 pub fn start_inbound_gossip_handler(
     peer_id: PeerId,
     mut inbound_gossip_rx: BufReader<ReadHalf<Box<NegotiatedSubstream>>>,
-    inbound_gossip_tx: GossipSender,
+    inbound_gossip_tx: mpsc::UnboundedSender<Vec<u8>>,
     internal_event_tx: InternalEventSender,
 ) {
     tokio::spawn(async move {
@@ -47,11 +91,16 @@ pub fn start_inbound_gossip_handler(
             } else {
                 debug!("Peer {} terminated gossip protocol.", alias!(peer_id));
 
-                // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
-                // host) through the worker dependencies, hence this can never panic.
-                internal_event_tx
+                // The service host is already gone if the stream ends while the node is shutting down.
+                if internal_event_tx
                     .send(InternalEvent::ProtocolStopped { peer_id })
-                    .expect("send internal event");
+                    .is_err()
+                {
+                    debug!(
+                        "Service host stopped before the gossip protocol with {}.",
+                        alias!(peer_id)
+                    );
+                }
 
                 break;
             }
@@ -64,7 +113,8 @@ pub fn start_inbound_gossip_handler(
 pub fn start_outbound_gossip_handler(
     peer_id: PeerId,
     mut outbound_gossip_tx: BufWriter<WriteHalf<Box<NegotiatedSubstream>>>,
-    outbound_gossip_rx: GossipReceiver,
+    outbound_gossip_rx: UnboundedReceiverStream<GossipMessage>,
+    mut upload_limiter: PeerUploadLimiter,
     internal_event_tx: InternalEventSender,
 ) {
     tokio::spawn(async move {
@@ -75,25 +125,51 @@ pub fn start_outbound_gossip_handler(
             // Note: Instead of polling another shutdown channel, we use an empty message
             // to signal that we want to end the connection. We use this "trick" whenever the network
             // receives the `DisconnectPeer` command to enforce that the connection will be dropped.
-            if message.is_empty() {
+            if message.bytes.is_empty() {
                 debug!(
                     "Terminating gossip protocol with {} (received shutdown signal).",
                     alias!(peer_id)
                 );
 
-                // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
-                // host) through the worker dependencies, hence this can never panic.
-                internal_event_tx
+                // The service host is already gone if the stream ends while the node is shutting down.
+                if internal_event_tx
                     .send(InternalEvent::ProtocolStopped { peer_id })
-                    .expect("send internal event");
+                    .is_err()
+                {
+                    debug!(
+                        "Service host stopped before the gossip protocol with {}.",
+                        alias!(peer_id)
+                    );
+                }
 
                 break;
-            } else if outbound_gossip_tx.write_all(&message).await.is_err() || outbound_gossip_tx.flush().await.is_err()
+            }
+
+            // Droppable messages are shed rather than queued behind the upload limit, which would otherwise let the
+            // channel grow without bounds.
+            if message.droppable {
+                if !upload_limiter.try_acquire(message.bytes.len()) {
+                    trace!(
+                        "Dropped gossip message to {} exceeding the upload limit.",
+                        alias!(peer_id)
+                    );
+
+                    continue;
+                }
+            } else {
+                upload_limiter.throttle(message.bytes.len()).await;
+            }
+
+            if outbound_gossip_tx.write_all(&message.bytes).await.is_err() || outbound_gossip_tx.flush().await.is_err()
             {
                 debug!("Peer {} terminated gossip protocol.", alias!(peer_id));
 
                 break;
             }
+
+            if let Some(on_sent) = message.on_sent {
+                on_sent();
+            }
         }
 
         trace!("Dropping gossip stream writer for {}.", alias!(peer_id));
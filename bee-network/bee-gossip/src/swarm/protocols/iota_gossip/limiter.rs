@@ -0,0 +1,197 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// Number of seconds worth of bytes that may be sent in a burst.
+const BURST_SECONDS: f64 = 1.0;
+
+/// A token bucket of bytes refilled at a constant rate.
+///
+/// Sending more bytes than available puts the bucket into debt, which has to be waited off before sending again.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_sec: u64, now: Instant) -> Self {
+        let rate = bytes_per_sec as f64;
+
+        Self {
+            rate,
+            tokens: rate * BURST_SECONDS,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        self.tokens = (self.tokens + self.rate * now.saturating_duration_since(self.refilled_at).as_secs_f64())
+            .min(self.rate * BURST_SECONDS);
+        self.refilled_at = now;
+    }
+
+    /// Returns whether `len` bytes may be sent right away, a full bucket letting any number of bytes through.
+    fn has_tokens(&mut self, len: usize, now: Instant) -> bool {
+        self.refill(now);
+
+        self.tokens >= (len as f64).min(self.rate * BURST_SECONDS)
+    }
+
+    /// Takes `len` bytes from the bucket and returns how long to wait before they may be sent.
+    fn reserve(&mut self, len: usize, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= len as f64;
+
+        if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Caps the bandwidth used to send gossip, globally and per peer.
+#[derive(Clone)]
+pub(crate) struct UploadLimiter {
+    global: Option<Arc<Mutex<TokenBucket>>>,
+    max_peer_upload_bytes_per_sec: u64,
+}
+
+impl UploadLimiter {
+    /// Creates a new `UploadLimiter`, a limit of 0 disabling it.
+    pub(crate) fn new(max_upload_bytes_per_sec: u64, max_peer_upload_bytes_per_sec: u64) -> Self {
+        Self {
+            global: (max_upload_bytes_per_sec > 0)
+                .then(|| Arc::new(Mutex::new(TokenBucket::new(max_upload_bytes_per_sec, Instant::now())))),
+            max_peer_upload_bytes_per_sec,
+        }
+    }
+
+    /// Returns the limiter of the gossip sent to a newly connected peer.
+    pub(crate) fn peer(&self) -> PeerUploadLimiter {
+        PeerUploadLimiter {
+            global: self.global.clone(),
+            peer: (self.max_peer_upload_bytes_per_sec > 0)
+                .then(|| TokenBucket::new(self.max_peer_upload_bytes_per_sec, Instant::now())),
+        }
+    }
+}
+
+/// Caps the bandwidth used to send gossip to a single peer.
+pub(crate) struct PeerUploadLimiter {
+    global: Option<Arc<Mutex<TokenBucket>>>,
+    peer: Option<TokenBucket>,
+}
+
+impl PeerUploadLimiter {
+    /// Returns how long to wait before `len` bytes may be sent to the peer.
+    fn reserve(&mut self, len: usize, now: Instant) -> Duration {
+        let peer_delay = self
+            .peer
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.reserve(len, now));
+        // Panic: the lock is never held across a panic.
+        let global_delay = self
+            .global
+            .as_ref()
+            .map_or(Duration::ZERO, |bucket| bucket.lock().unwrap().reserve(len, now));
+
+        peer_delay.max(global_delay)
+    }
+
+    /// Takes `len` bytes from the buckets only if they may be sent to the peer right away.
+    fn try_reserve(&mut self, len: usize, now: Instant) -> bool {
+        // Panic: the lock is never held across a panic.
+        let mut global = self.global.as_ref().map(|bucket| bucket.lock().unwrap());
+
+        if self.peer.as_mut().map(|bucket| bucket.has_tokens(len, now)) == Some(false)
+            || global.as_mut().map(|bucket| bucket.has_tokens(len, now)) == Some(false)
+        {
+            return false;
+        }
+
+        if let Some(bucket) = self.peer.as_mut() {
+            bucket.reserve(len, now);
+        }
+        if let Some(bucket) = global.as_mut() {
+            bucket.reserve(len, now);
+        }
+
+        true
+    }
+
+    /// Returns whether `len` bytes may be sent to the peer right away, taking them from the buckets if so.
+    pub(crate) fn try_acquire(&mut self, len: usize) -> bool {
+        self.try_reserve(len, Instant::now())
+    }
+
+    /// Waits until `len` bytes may be sent to the peer.
+    pub(crate) async fn throttle(&mut self, len: usize) {
+        let delay = self.reserve(len, Instant::now());
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited() {
+        let mut limiter = UploadLimiter::new(0, 0).peer();
+
+        assert_eq!(limiter.reserve(usize::MAX, Instant::now()), Duration::ZERO);
+    }
+
+    #[test]
+    fn peer_limit() {
+        let limiter = UploadLimiter::new(0, 1000);
+        let (mut peer1, mut peer2) = (limiter.peer(), limiter.peer());
+        let now = Instant::now();
+
+        // The burst is sent right away, the excess has to wait.
+        assert_eq!(peer1.reserve(1000, now), Duration::ZERO);
+        assert_eq!(peer1.reserve(500, now), Duration::from_millis(500));
+        // The debt is waited off over time.
+        assert_eq!(
+            peer1.reserve(500, now + Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+        // Other peers are not affected.
+        assert_eq!(peer2.reserve(1000, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn global_limit() {
+        let limiter = UploadLimiter::new(1000, 0);
+        let (mut peer1, mut peer2) = (limiter.peer(), limiter.peer());
+        let now = Instant::now();
+
+        assert_eq!(peer1.reserve(600, now), Duration::ZERO);
+        assert_eq!(peer2.reserve(600, now), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn try_reserve_without_debt() {
+        let limiter = UploadLimiter::new(1000, 500);
+        let (mut peer1, mut peer2) = (limiter.peer(), limiter.peer());
+        let now = Instant::now();
+
+        // A full bucket lets any number of bytes through.
+        assert!(peer1.try_reserve(2000, now));
+        // Nothing more is taken while in debt.
+        assert!(!peer1.try_reserve(1, now + Duration::from_secs(1)));
+        assert!(peer1.try_reserve(500, now + Duration::from_secs(4)));
+        // The global bucket is shared with the other peers.
+        assert!(!peer2.try_reserve(600, now + Duration::from_secs(4)));
+        assert!(peer2.try_reserve(600, now + Duration::from_millis(4100)));
+    }
+}
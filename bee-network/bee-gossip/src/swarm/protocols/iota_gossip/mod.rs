@@ -3,9 +3,10 @@
 
 mod event;
 mod handler;
-mod id;
+pub(crate) mod id;
 mod io;
+pub(crate) mod limiter;
 mod protocol;
-mod upgrade;
+pub(crate) mod upgrade;
 
 pub use self::{event::*, io::*, protocol::*};
@@ -7,8 +7,11 @@ use std::{
 };
 
 use libp2p::{
-    core::{connection::ConnectionId, ConnectedPoint},
-    swarm::{IntoConnectionHandler, NetworkBehaviour, NetworkBehaviourAction, NotifyHandler, PollParameters},
+    core::ConnectedPoint,
+    swarm::{
+        behaviour::{AddressChange, ConnectionClosed, ConnectionEstablished, FromSwarm},
+        ConnectionId, NetworkBehaviour, NotifyHandler, PollParameters, ToSwarm,
+    },
     Multiaddr, PeerId,
 };
 use log::debug;
@@ -21,19 +24,22 @@ use super::{
 use crate::{alias, init::global::network_id, network::origin::Origin};
 
 const IOTA_GOSSIP_NAME: &str = "iota-gossip";
-const IOTA_GOSSIP_VERSION: &str = "1.0.0";
+/// Latest gossip protocol version, speaking the versioned heartbeat.
+const IOTA_GOSSIP_VERSION: u8 = 2;
+/// Oldest gossip protocol version, only speaking the legacy packets.
+const IOTA_GOSSIP_VERSION_MIN: u8 = 1;
 
-type GossipBehaviourAction = NetworkBehaviourAction<IotaGossipEvent, GossipProtocolHandler, IotaGossipHandlerInEvent>;
+type GossipBehaviourAction = ToSwarm<IotaGossipEvent, IotaGossipHandlerInEvent>;
 
 struct ConnectionInfo {
     addr: Multiaddr,
     origin: Origin,
 }
 
-/// Substream upgrade protocol for `/iota-gossip/1.0.0`.
+/// Substream upgrade protocol for `/iota-gossip/2.0.0`, falling back to `/iota-gossip/1.0.0`.
 pub struct IotaGossipProtocol {
-    /// The gossip protocol identifier.
-    id: IotaGossipIdentifier,
+    /// The gossip protocol identifiers, from the latest to the oldest version.
+    ids: Vec<IotaGossipIdentifier>,
 
     /// Counts the number of handlers created.
     num_handlers: usize,
@@ -55,12 +61,52 @@ impl IotaGossipProtocol {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Registers a newly established connection to a peer and tells its handler where the connection originates.
+    fn on_connection_established(&mut self, peer_id: &PeerId, conn_id: &ConnectionId, endpoint: &ConnectedPoint) {
+        let (peer_addr, origin) = match endpoint {
+            ConnectedPoint::Dialer {
+                address,
+                role_override: _,
+            } => (address.clone(), Origin::Outbound),
+            ConnectedPoint::Listener { send_back_addr, .. } => (send_back_addr.clone(), Origin::Inbound),
+        };
+
+        match origin {
+            Origin::Inbound => self.num_inbounds += 1,
+            Origin::Outbound => self.num_outbounds += 1,
+        }
+        debug!(
+            "gossip protocol: connection established: inbound/outbound: {}/{}",
+            self.num_inbounds, self.num_outbounds
+        );
+
+        self.peers.insert(*peer_id, {
+            ConnectionInfo {
+                addr: peer_addr,
+                origin,
+            }
+        });
+
+        let handler_event = IotaGossipHandlerInEvent { origin };
+
+        let notify_handler = ToSwarm::NotifyHandler {
+            peer_id: *peer_id,
+            handler: NotifyHandler::One(*conn_id), // TODO: maybe better use ::Any ??
+            event: handler_event,
+        };
+
+        self.events.push_back(notify_handler);
+    }
 }
 
 impl Default for IotaGossipProtocol {
     fn default() -> Self {
         Self {
-            id: IotaGossipIdentifier::new(IOTA_GOSSIP_NAME, network_id(), IOTA_GOSSIP_VERSION),
+            ids: (IOTA_GOSSIP_VERSION_MIN..=IOTA_GOSSIP_VERSION)
+                .rev()
+                .map(|version| IotaGossipIdentifier::new(IOTA_GOSSIP_NAME, network_id(), version))
+                .collect(),
             num_handlers: 0,
             num_inbounds: 0,
             num_outbounds: 0,
@@ -92,7 +138,7 @@ impl NetworkBehaviour for IotaGossipProtocol {
         self.num_handlers += 1;
         debug!("gossip protocol: new handler ({}).", self.num_handlers);
 
-        GossipProtocolHandler::new(self.id.clone())
+        GossipProtocolHandler::new(self.ids.clone())
     }
 
     /// **libp2p docs**:
@@ -116,48 +162,23 @@ impl NetworkBehaviour for IotaGossipProtocol {
 
     /// **libp2p docs**:
     ///
-    /// Informs the behaviour about a newly established connection to a peer.
-    fn inject_connection_established(
-        &mut self,
-        peer_id: &PeerId,
-        conn_id: &ConnectionId,
-        endpoint: &ConnectedPoint,
-        _failed_addresses: Option<&Vec<Multiaddr>>,
-        _other_established: usize,
-    ) {
-        let (peer_addr, origin) = match endpoint {
-            ConnectedPoint::Dialer {
-                address,
-                role_override: _,
-            } => (address.clone(), Origin::Outbound),
-            ConnectedPoint::Listener { send_back_addr, .. } => (send_back_addr.clone(), Origin::Inbound),
-        };
-
-        match origin {
-            Origin::Inbound => self.num_inbounds += 1,
-            Origin::Outbound => self.num_outbounds += 1,
-        }
-        debug!(
-            "gossip protocol: connection established: inbound/outbound: {}/{}",
-            self.num_inbounds, self.num_outbounds
-        );
-
-        self.peers.insert(*peer_id, {
-            ConnectionInfo {
-                addr: peer_addr,
-                origin,
+    /// Informs the behaviour about an event from the [`Swarm`](libp2p::Swarm).
+    fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>) {
+        match event {
+            FromSwarm::ConnectionEstablished(ConnectionEstablished {
+                peer_id,
+                connection_id,
+                endpoint,
+                ..
+            }) => self.on_connection_established(&peer_id, &connection_id, endpoint),
+            FromSwarm::ConnectionClosed(ConnectionClosed { peer_id, .. }) => {
+                debug!("gossip behaviour: connection with {} closed.", alias!(peer_id));
             }
-        });
-
-        let handler_event = IotaGossipHandlerInEvent { origin };
-
-        let notify_handler = NetworkBehaviourAction::NotifyHandler {
-            peer_id: *peer_id,
-            handler: NotifyHandler::One(*conn_id), // TODO: maybe better use ::Any ??
-            event: handler_event,
-        };
-
-        self.events.push_back(notify_handler);
+            FromSwarm::AddressChange(AddressChange { peer_id, .. }) => {
+                debug!("gossip behaviour: address of {} changed.", alias!(peer_id));
+            }
+            _ => {}
+        }
     }
 
     /// **libp2p docs**:
@@ -165,30 +186,31 @@ impl NetworkBehaviour for IotaGossipProtocol {
     /// Informs the behaviour about an event generated by the handler dedicated to the peer identified by `peer_id`.
     /// for the behaviour.
     ///
-    /// The `peer_id` is guaranteed to be in a connected state. In other words, `inject_connected`
-    /// has previously been called with this `PeerId`.
-    fn inject_event(&mut self, peer_id: PeerId, _: ConnectionId, event: IotaGossipHandlerEvent) {
+    /// The `peer_id` is guaranteed to be in a connected state. In other words, [`FromSwarm::ConnectionEstablished`]
+    /// has previously been received with this `PeerId`.
+    fn on_connection_handler_event(&mut self, peer_id: PeerId, _: ConnectionId, event: IotaGossipHandlerEvent) {
         debug!("gossip protocol: handler event: {:?}", event);
 
         // Propagate events to the behaviour.
         let ev = match event {
             IotaGossipHandlerEvent::SentUpgradeRequest { to } => {
-                NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::SentUpgradeRequest { to })
+                ToSwarm::GenerateEvent(IotaGossipEvent::SentUpgradeRequest { to })
             }
-            IotaGossipHandlerEvent::UpgradeCompleted { substream } => {
+            IotaGossipHandlerEvent::UpgradeCompleted { substream, version } => {
                 if let Some(conn_info) = self.peers.remove(&peer_id) {
-                    NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::UpgradeCompleted {
+                    ToSwarm::GenerateEvent(IotaGossipEvent::UpgradeCompleted {
                         peer_id,
                         peer_addr: conn_info.addr,
                         origin: conn_info.origin,
                         substream,
+                        version,
                     })
                 } else {
                     return;
                 }
             }
             IotaGossipHandlerEvent::UpgradeError { peer_id, error } => {
-                NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::UpgradeError { peer_id, error })
+                ToSwarm::GenerateEvent(IotaGossipEvent::UpgradeError { peer_id, error })
             }
             _ => return,
         };
@@ -196,37 +218,6 @@ impl NetworkBehaviour for IotaGossipProtocol {
         self.events.push_back(ev);
     }
 
-    /// **libp2p docs**:
-    ///
-    /// Informs the behaviour about a closed connection to a peer.
-    ///
-    /// A call to this method is always paired with an earlier call to
-    /// `inject_connection_established` with the same peer ID, connection ID and
-    /// endpoint.
-    fn inject_connection_closed(
-        &mut self,
-        peer_id: &PeerId,
-        _: &ConnectionId,
-        _: &ConnectedPoint,
-        _: <Self::ConnectionHandler as IntoConnectionHandler>::Handler,
-        _remaining_established: usize,
-    ) {
-        debug!("gossip behaviour: connection with {} closed.", alias!(peer_id));
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Informs the behaviour that the [`ConnectedPoint`] of an existing connection has changed.
-    fn inject_address_change(
-        &mut self,
-        peer_id: &PeerId,
-        _: &ConnectionId,
-        _old: &ConnectedPoint,
-        _new: &ConnectedPoint,
-    ) {
-        debug!("gossip behaviour: address of {} changed.", alias!(peer_id));
-    }
-
     fn poll(&mut self, _: &mut Context<'_>, _: &mut impl PollParameters) -> Poll<GossipBehaviourAction> {
         if let Some(event) = self.events.pop_front() {
             Poll::Ready(event)
@@ -1,7 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{io, iter};
+use std::{io, vec};
 
 use futures::{future, AsyncRead, AsyncWrite};
 use libp2p::{core::UpgradeInfo, InboundUpgrade, OutboundUpgrade};
@@ -11,23 +11,28 @@ use super::id::IotaGossipIdentifier;
 
 #[derive(Debug, Clone)]
 pub struct IotaGossipProtocolUpgrade {
-    id: IotaGossipIdentifier,
+    /// The supported protocol identifiers, in order of preference.
+    ids: Vec<IotaGossipIdentifier>,
 }
 
 impl IotaGossipProtocolUpgrade {
     pub fn new(id: IotaGossipIdentifier) -> Self {
-        Self { id }
+        Self { ids: vec![id] }
+    }
+
+    pub fn with_versions(ids: Vec<IotaGossipIdentifier>) -> Self {
+        Self { ids }
     }
 }
 
 impl UpgradeInfo for IotaGossipProtocolUpgrade {
     type Info = IotaGossipIdentifier;
-    type InfoIter = iter::Once<Self::Info>;
+    type InfoIter = vec::IntoIter<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        trace!("gossip upgrade: protocol info query: {}", self.id);
+        trace!("gossip upgrade: protocol info query: {:?}", self.ids);
 
-        iter::once(self.id.clone())
+        self.ids.clone().into_iter()
     }
 }
 
@@ -35,14 +40,14 @@ impl<S> InboundUpgrade<S> for IotaGossipProtocolUpgrade
 where
     S: AsyncWrite + AsyncWrite + Unpin + Send,
 {
-    type Output = S;
+    type Output = (S, IotaGossipIdentifier);
     type Error = io::Error;
     type Future = future::Ready<Result<Self::Output, Self::Error>>;
 
     fn upgrade_inbound(self, stream: S, info: Self::Info) -> Self::Future {
         debug!("gossip upgrade: inbound: {}", info);
 
-        future::ok(stream)
+        future::ok((stream, info))
     }
 }
 
@@ -50,13 +55,13 @@ impl<S> OutboundUpgrade<S> for IotaGossipProtocolUpgrade
 where
     S: AsyncRead + AsyncWrite + Unpin + Send,
 {
-    type Output = S;
+    type Output = (S, IotaGossipIdentifier);
     type Error = io::Error;
     type Future = future::Ready<Result<Self::Output, Self::Error>>;
 
     fn upgrade_outbound(self, stream: S, info: Self::Info) -> Self::Future {
         debug!("gossip upgrade: outbound: {}", info);
 
-        future::ok(stream)
+        future::ok((stream, info))
     }
 }
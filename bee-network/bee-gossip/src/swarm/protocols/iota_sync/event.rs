@@ -0,0 +1,39 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io;
+
+use libp2p::{swarm::ConnectionHandlerUpgrErr, PeerId};
+
+use super::SyncStream;
+use crate::network::origin::Origin;
+
+/// Sync events that may occur while opening IOTA sync streams with a peer.
+#[derive(Debug)]
+pub enum IotaSyncEvent {
+    /// A sync stream was negotiated.
+    StreamOpened {
+        peer_id: PeerId,
+        origin: Origin,
+        stream: SyncStream,
+    },
+
+    /// A sync stream could not be opened because the peer is not connected.
+    PeerNotConnected { peer_id: PeerId },
+
+    /// An error occurred during negotiation.
+    UpgradeError {
+        peer_id: PeerId,
+        error: ConnectionHandlerUpgrErr<io::Error>,
+    },
+}
+
+/// Sync handler events that may occur while opening IOTA sync streams with a peer.
+#[derive(Debug)]
+pub enum IotaSyncHandlerEvent {
+    /// Successfully negotiated a sync stream.
+    StreamOpened { origin: Origin, stream: SyncStream },
+
+    /// An error occurred during the upgrade.
+    UpgradeError { error: ConnectionHandlerUpgrErr<io::Error> },
+}
@@ -0,0 +1,129 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    io,
+    task::{Context, Poll},
+};
+
+use libp2p::swarm::handler::{
+    ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, DialUpgradeError, FullyNegotiatedInbound,
+    FullyNegotiatedOutbound, KeepAlive, ListenUpgradeError, SubstreamProtocol,
+};
+use log::*;
+
+use super::event::IotaSyncHandlerEvent;
+use crate::{
+    network::origin::Origin,
+    swarm::protocols::iota_gossip::{id::IotaGossipIdentifier, upgrade::IotaGossipProtocolUpgrade},
+};
+
+pub struct SyncProtocolHandler {
+    /// Exchanged protocol information necessary during negotiation.
+    info: IotaGossipIdentifier,
+
+    /// All events produced by this handler.
+    events: VecDeque<ConnectionHandlerEvent<IotaGossipProtocolUpgrade, (), IotaSyncHandlerEvent, io::Error>>,
+}
+
+/// Asks the handler to open a new outbound sync stream.
+#[derive(Debug)]
+pub struct IotaSyncHandlerInEvent;
+
+impl SyncProtocolHandler {
+    pub fn new(info: IotaGossipIdentifier) -> Self {
+        Self {
+            info,
+            events: VecDeque::with_capacity(4),
+        }
+    }
+}
+
+impl ConnectionHandler for SyncProtocolHandler {
+    type InEvent = IotaSyncHandlerInEvent;
+    type OutEvent = IotaSyncHandlerEvent;
+    type Error = io::Error;
+    type InboundProtocol = IotaGossipProtocolUpgrade;
+    type OutboundProtocol = IotaGossipProtocolUpgrade;
+    type InboundOpenInfo = ();
+    type OutboundOpenInfo = ();
+
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        SubstreamProtocol::new(IotaGossipProtocolUpgrade::new(self.info.clone()), ())
+    }
+
+    fn on_behaviour_event(&mut self, _: IotaSyncHandlerInEvent) {
+        debug!("sync handler: sending stream request.");
+
+        self.events.push_back(ConnectionHandlerEvent::OutboundSubstreamRequest {
+            protocol: SubstreamProtocol::new(IotaGossipProtocolUpgrade::new(self.info.clone()), ()),
+        });
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        match event {
+            ConnectionEvent::FullyNegotiatedInbound(FullyNegotiatedInbound {
+                protocol: (new_inbound, _),
+                ..
+            }) => {
+                debug!("sync handler: fully negotiated inbound.");
+
+                self.events
+                    .push_back(ConnectionHandlerEvent::Custom(IotaSyncHandlerEvent::StreamOpened {
+                        origin: Origin::Inbound,
+                        stream: Box::new(new_inbound),
+                    }));
+            }
+            ConnectionEvent::FullyNegotiatedOutbound(FullyNegotiatedOutbound {
+                protocol: (new_outbound, _),
+                ..
+            }) => {
+                debug!("sync handler: fully negotiated outbound.");
+
+                self.events
+                    .push_back(ConnectionHandlerEvent::Custom(IotaSyncHandlerEvent::StreamOpened {
+                        origin: Origin::Outbound,
+                        stream: Box::new(new_outbound),
+                    }));
+            }
+            ConnectionEvent::DialUpgradeError(DialUpgradeError { error, .. }) => {
+                debug!("sync handler: outbound upgrade error: {:?}", error);
+
+                self.events
+                    .push_back(ConnectionHandlerEvent::Custom(IotaSyncHandlerEvent::UpgradeError {
+                        error,
+                    }));
+            }
+            ConnectionEvent::ListenUpgradeError(ListenUpgradeError { error, .. }) => {
+                debug!("sync handler: inbound upgrade error: {:?}", error);
+            }
+            ConnectionEvent::AddressChange(_) => {}
+        }
+    }
+
+    /// Sync streams never keep a connection alive on their own, the gossip protocol does.
+    fn connection_keep_alive(&self) -> KeepAlive {
+        KeepAlive::No
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn poll(
+        &mut self,
+        _: &mut Context<'_>,
+    ) -> Poll<ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::OutEvent, Self::Error>> {
+        if let Some(event) = self.events.pop_front() {
+            Poll::Ready(event)
+        } else {
+            Poll::Pending
+        }
+    }
+}
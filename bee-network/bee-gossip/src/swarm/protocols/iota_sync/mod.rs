@@ -0,0 +1,13 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod event;
+mod handler;
+mod protocol;
+
+use libp2p::swarm::NegotiatedSubstream;
+
+pub use self::{event::*, protocol::*};
+
+/// A dedicated substream to bulk synchronize the tangle with a peer.
+pub type SyncStream = Box<NegotiatedSubstream>;
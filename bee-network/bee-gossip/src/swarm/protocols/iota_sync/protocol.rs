@@ -0,0 +1,128 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, VecDeque},
+    task::{Context, Poll},
+};
+
+use libp2p::{
+    swarm::{
+        behaviour::{ConnectionClosed, ConnectionEstablished, FromSwarm},
+        ConnectionId, NetworkBehaviour, NotifyHandler, PollParameters, ToSwarm,
+    },
+    Multiaddr, PeerId,
+};
+use log::debug;
+
+use super::{
+    event::{IotaSyncEvent, IotaSyncHandlerEvent},
+    handler::{IotaSyncHandlerInEvent, SyncProtocolHandler},
+};
+use crate::{alias, init::global::network_id, swarm::protocols::iota_gossip::id::IotaGossipIdentifier};
+
+const IOTA_SYNC_NAME: &str = "iota-sync";
+const IOTA_SYNC_VERSION: u8 = 1;
+
+type SyncBehaviourAction = ToSwarm<IotaSyncEvent, IotaSyncHandlerInEvent>;
+
+/// Substream upgrade protocol for `/iota-sync/1.0.0`.
+///
+/// Other than the gossip protocol, sync streams are only opened on request and a peer may have many of them.
+pub struct IotaSyncProtocol {
+    /// The sync protocol identifier.
+    id: IotaGossipIdentifier,
+
+    /// Events produced for the behaviour and handlers.
+    events: VecDeque<SyncBehaviourAction>,
+
+    /// Counts the established connections per peer.
+    connections: HashMap<PeerId, usize>,
+}
+
+impl IotaSyncProtocol {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests a new outbound sync stream with a connected peer.
+    pub fn open_stream(&mut self, peer_id: PeerId) {
+        let event = if self.connections.contains_key(&peer_id) {
+            ToSwarm::NotifyHandler {
+                peer_id,
+                handler: NotifyHandler::Any,
+                event: IotaSyncHandlerInEvent,
+            }
+        } else {
+            ToSwarm::GenerateEvent(IotaSyncEvent::PeerNotConnected { peer_id })
+        };
+
+        self.events.push_back(event);
+    }
+}
+
+impl Default for IotaSyncProtocol {
+    fn default() -> Self {
+        Self {
+            id: IotaGossipIdentifier::new(IOTA_SYNC_NAME, network_id(), IOTA_SYNC_VERSION),
+            events: VecDeque::with_capacity(16),
+            connections: HashMap::with_capacity(8),
+        }
+    }
+}
+
+impl NetworkBehaviour for IotaSyncProtocol {
+    type ConnectionHandler = SyncProtocolHandler;
+    type OutEvent = IotaSyncEvent;
+
+    fn new_handler(&mut self) -> Self::ConnectionHandler {
+        SyncProtocolHandler::new(self.id.clone())
+    }
+
+    fn addresses_of_peer(&mut self, _: &PeerId) -> Vec<Multiaddr> {
+        Vec::new()
+    }
+
+    fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>) {
+        match event {
+            FromSwarm::ConnectionEstablished(ConnectionEstablished { peer_id, .. }) => {
+                *self.connections.entry(peer_id).or_default() += 1;
+            }
+            FromSwarm::ConnectionClosed(ConnectionClosed {
+                peer_id,
+                remaining_established,
+                ..
+            }) => {
+                if remaining_established == 0 {
+                    self.connections.remove(&peer_id);
+                } else {
+                    self.connections.insert(peer_id, remaining_established);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_connection_handler_event(&mut self, peer_id: PeerId, _: ConnectionId, event: IotaSyncHandlerEvent) {
+        debug!("sync protocol: handler event from {}: {:?}", alias!(peer_id), event);
+
+        let event = match event {
+            IotaSyncHandlerEvent::StreamOpened { origin, stream } => IotaSyncEvent::StreamOpened {
+                peer_id,
+                origin,
+                stream,
+            },
+            IotaSyncHandlerEvent::UpgradeError { error } => IotaSyncEvent::UpgradeError { peer_id, error },
+        };
+
+        self.events.push_back(ToSwarm::GenerateEvent(event));
+    }
+
+    fn poll(&mut self, _: &mut Context<'_>, _: &mut impl PollParameters) -> Poll<SyncBehaviourAction> {
+        if let Some(event) = self.events.pop_front() {
+            Poll::Ready(event)
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -25,6 +25,29 @@ pub async fn get_bind_address(rx: &mut NetworkEventReceiver) -> Multiaddr {
     }
 }
 
+#[cfg(feature = "quic")]
+pub async fn get_quic_bind_address(rx: &mut NetworkEventReceiver) -> Multiaddr {
+    use crate::Protocol;
+
+    let timeout = time::sleep(Duration::from_secs(5));
+    tokio::pin!(timeout);
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                if let Some(Event::AddressBound { address }) = event {
+                    if address.iter().any(|p| matches!(p, Protocol::QuicV1)) {
+                        return address;
+                    }
+                }
+            },
+            () = &mut timeout => {
+                panic!("timed out before receiving a QUIC `AddressBound` event");
+            }
+        }
+    }
+}
+
 pub async fn get_local_id(rx: &mut NetworkEventReceiver) -> PeerId {
     let timeout = time::sleep(Duration::from_secs(5));
     tokio::pin!(timeout);
@@ -96,3 +119,21 @@ pub async fn get_gossip_channels(rx: &mut NetworkEventReceiver) -> (GossipReceiv
         }
     }
 }
+
+pub async fn get_gossip_version(rx: &mut NetworkEventReceiver) -> u8 {
+    let timeout = time::sleep(Duration::from_secs(20));
+    tokio::pin!(timeout);
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                if let Some(Event::PeerConnected { gossip_version, .. }) = event {
+                    return gossip_version;
+                }
+            },
+            () = &mut timeout => {
+                panic!("timed out before receiving `PeerConnected` event");
+            }
+        }
+    }
+}
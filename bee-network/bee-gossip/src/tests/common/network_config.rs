@@ -20,3 +20,15 @@ pub fn get_in_memory_network_config(port: u64) -> NetworkConfig {
         })
         .finish()
 }
+
+#[cfg(feature = "quic")]
+pub fn get_quic_network_config() -> NetworkConfig {
+    // Port 0 lets the OS pick free ports, the bound addresses are announced with `Event::AddressBound`.
+    NetworkConfig::build()
+        .with_bind_multiaddr("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+        .unwrap()
+        .with_quic_bind_multiaddr("/ip4/127.0.0.1/udp/0/quic-v1".parse().unwrap())
+        .unwrap()
+        .finish()
+        .unwrap()
+}
@@ -4,7 +4,7 @@
 #![cfg(feature = "full")]
 
 use super::common::{await_events::*, keys_and_ids::*, network_config::*, shutdown::*};
-use crate::{standalone::init, Command, PeerRelation};
+use crate::{standalone::init, stores::InMemoryBanStore, Command, PeerRelation};
 
 #[tokio::test]
 #[serial_test::serial]
@@ -17,10 +17,10 @@ async fn connect_peer() {
 
     let network_id = gen_constant_net_id();
 
-    let (tx1, mut rx1) = init(config1, keys1, network_id, shutdown(10))
+    let (tx1, mut rx1) = init::<InMemoryBanStore>(config1, keys1, network_id, (), shutdown(10))
         .await
         .expect("init failed");
-    let (_tx2, mut rx2) = init(config2, keys2, network_id, shutdown(10))
+    let (_tx2, mut rx2) = init::<InMemoryBanStore>(config2, keys2, network_id, (), shutdown(10))
         .await
         .expect("init failed");
 
@@ -45,3 +45,74 @@ async fn connect_peer() {
 
     assert_eq!(get_connected_peer_id(&mut rx1).await, peer_id2);
 }
+
+#[tokio::test]
+#[serial_test::serial]
+async fn connect_peer_negotiates_latest_gossip_version() {
+    let config1 = get_in_memory_network_config(1341);
+    let keys1 = gen_random_keys();
+
+    let config2 = get_in_memory_network_config(4246);
+    let keys2 = gen_random_keys();
+
+    let network_id = gen_constant_net_id();
+
+    let (tx1, mut rx1) = init::<InMemoryBanStore>(config1, keys1, network_id, (), shutdown(10))
+        .await
+        .expect("init failed");
+    let (_tx2, mut rx2) = init::<InMemoryBanStore>(config2, keys2, network_id, (), shutdown(10))
+        .await
+        .expect("init failed");
+
+    let _ = get_local_id(&mut rx1).await;
+    let _ = get_bind_address(&mut rx1).await;
+
+    let peer_id2 = get_local_id(&mut rx2).await;
+    let address2 = get_bind_address(&mut rx2).await;
+
+    tx1.send(Command::AddPeer {
+        alias: Some("2".into()),
+        multiaddr: address2,
+        relation: PeerRelation::Known,
+        peer_id: peer_id2,
+    })
+    .expect("send command");
+
+    assert_eq!(get_gossip_version(&mut rx1).await, 2);
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+#[serial_test::serial]
+async fn connect_peer_over_quic() {
+    let config1 = get_quic_network_config();
+    let keys1 = gen_random_keys();
+
+    let config2 = get_quic_network_config();
+    let keys2 = gen_random_keys();
+
+    let network_id = gen_constant_net_id();
+
+    let (tx1, mut rx1) = init::<InMemoryBanStore>(config1, keys1, network_id, (), shutdown(10))
+        .await
+        .expect("init failed");
+    let (_tx2, mut rx2) = init::<InMemoryBanStore>(config2, keys2, network_id, (), shutdown(10))
+        .await
+        .expect("init failed");
+
+    let _ = get_local_id(&mut rx1).await;
+    let _ = get_quic_bind_address(&mut rx1).await;
+
+    let peer_id2 = get_local_id(&mut rx2).await;
+    let address2 = get_quic_bind_address(&mut rx2).await;
+
+    tx1.send(Command::AddPeer {
+        alias: Some("2".into()),
+        multiaddr: address2,
+        relation: PeerRelation::Known,
+        peer_id: peer_id2,
+    })
+    .expect("send command");
+
+    assert_eq!(get_connected_peer_id(&mut rx1).await, peer_id2);
+}
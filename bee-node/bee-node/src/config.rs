@@ -7,7 +7,10 @@
 //! All node types use a common config file (e.g. config.json), and simply ignore
 //! those parameters they don't actually require.
 
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use bee_autopeering::config::{AutopeeringConfig, AutopeeringConfigBuilder};
 use bee_gossip::{NetworkConfig, NetworkConfigBuilder};
@@ -15,13 +18,15 @@ use bee_ledger::{
     pruning::config::{PruningConfig, PruningConfigBuilder},
     snapshot::config::{SnapshotConfig, SnapshotConfigBuilder},
 };
+#[cfg(feature = "coordinator")]
+use bee_plugin_coordinator::config::{CoordinatorConfig, CoordinatorConfigBuilder};
 #[cfg(feature = "dashboard")]
 use bee_plugin_dashboard::config::{DashboardConfig, DashboardConfigBuilder};
 use bee_protocol::config::{ProtocolConfig, ProtocolConfigBuilder};
 use bee_rest_api::config::{RestApiConfig, RestApiConfigBuilder};
 use bee_tangle::config::{TangleConfig, TangleConfigBuilder};
 use fern_logger::{LoggerConfig, LoggerConfigBuilder, LOGGER_STDOUT_NAME};
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::{cli::ClArgs, storage::NodeStorageBackend, util, BECH32_HRP_DEFAULT, NETWORK_NAME_DEFAULT};
 
@@ -38,10 +43,13 @@ pub enum NodeConfigError {
     JsonConfigBuilderDeserialization(#[from] serde_json::Error),
     #[error("deserializing the toml config builder failed: {0}")]
     TomlConfigBuilderDeserialization(#[from] toml::de::Error),
+    #[error("invalid network configuration: {0}")]
+    InvalidNetworkConfig(String),
 }
 
 /// Entails all data that can be stored in a Bee config file.
 pub struct NodeConfig<S: NodeStorageBackend> {
+    pub(crate) path: Option<PathBuf>,
     pub(crate) alias: String,
     pub(crate) network_spec: NetworkSpec,
     pub(crate) logger: LoggerConfig,
@@ -55,6 +63,8 @@ pub struct NodeConfig<S: NodeStorageBackend> {
     pub(crate) tangle: TangleConfig,
     #[cfg(feature = "dashboard")]
     pub(crate) dashboard: DashboardConfig,
+    #[cfg(feature = "coordinator")]
+    pub(crate) coordinator: CoordinatorConfig,
 }
 
 impl<S: NodeStorageBackend> NodeConfig<S> {
@@ -84,6 +94,9 @@ pub struct NodeConfigBuilder<S: NodeStorageBackend> {
     #[deprecated(since = "0.3.0")]
     #[serde(alias = "identity")]
     _identity: Option<String>,
+    // The path of the file the builder was deserialized from, if any.
+    #[serde(skip)]
+    pub(crate) path: Option<PathBuf>,
     pub(crate) alias: Option<String>,
     #[serde(alias = "bech32Hrp")]
     pub(crate) bech32_hrp: Option<String>,
@@ -101,6 +114,8 @@ pub struct NodeConfigBuilder<S: NodeStorageBackend> {
     pub(crate) tangle: Option<TangleConfigBuilder>,
     #[cfg(feature = "dashboard")]
     pub(crate) dashboard: Option<DashboardConfigBuilder>,
+    #[cfg(feature = "coordinator")]
+    pub(crate) coordinator: Option<CoordinatorConfigBuilder>,
 }
 
 // This cannot be derived because `S` does not implement `PartialEq`.
@@ -109,11 +124,13 @@ where
     S: NodeStorageBackend,
     S::ConfigBuilder: PartialEq,
 {
-    #[allow(deprecated)]
+    // The comparison is extended by the optional features, which makes returning it directly impossible.
+    #[allow(deprecated, clippy::let_and_return)]
     fn eq(&self, other: &Self) -> bool {
         // We destructure `Self` so this implementation does not fail silently if fields are added or removed.
         let Self {
             _identity: self_identity,
+            path: _,
             alias: self_alias,
             bech32_hrp: self_bech32_hrp,
             network_id: self_network_id,
@@ -128,6 +145,8 @@ where
             tangle: self_tangle,
             #[cfg(feature = "dashboard")]
                 dashboard: self_dashboard,
+            #[cfg(feature = "coordinator")]
+                coordinator: self_coordinator,
         } = self;
 
         let cmp = (self_identity == &other._identity)
@@ -145,9 +164,11 @@ where
             && (self_tangle == &other.tangle);
 
         #[cfg(feature = "dashboard")]
-        return cmp && (self_dashboard == &other.dashboard);
-        #[cfg(not(feature = "dashboard"))]
-        return cmp;
+        let cmp = cmp && (self_dashboard == &other.dashboard);
+        #[cfg(feature = "coordinator")]
+        let cmp = cmp && (self_coordinator == &other.coordinator);
+
+        cmp
     }
 }
 
@@ -157,18 +178,11 @@ where
 {
     /// Creates a node config builder from a local config file.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, NodeConfigError> {
-        match fs::read_to_string(&path) {
-            Ok(string) => match path.as_ref().extension().and_then(|e| e.to_str()) {
-                Some("json") => {
-                    serde_json::from_str::<Self>(&string).map_err(NodeConfigError::JsonConfigBuilderDeserialization)
-                }
-                Some("toml") => {
-                    toml::from_str::<Self>(&string).map_err(NodeConfigError::TomlConfigBuilderDeserialization)
-                }
-                _ => Err(NodeConfigError::UnsupportedConfigType),
-            },
-            Err(e) => Err(NodeConfigError::FileRead(e)),
-        }
+        let mut builder = deserialize_file::<Self>(path.as_ref())?;
+
+        builder.path = Some(path.as_ref().to_owned());
+
+        Ok(builder)
     }
 
     /// Applies commandline arguments to the builder.
@@ -212,6 +226,7 @@ where
             #[allow(deprecated)]
             self._identity,
             NodeConfig {
+                path: self.path,
                 alias: self.alias.unwrap_or_else(|| ALIAS_DEFAULT.to_owned()),
                 network_spec,
                 logger: self.logger.unwrap_or_default().finish(),
@@ -229,11 +244,41 @@ where
                 tangle: self.tangle.unwrap_or_default().finish(),
                 #[cfg(feature = "dashboard")]
                 dashboard: self.dashboard.unwrap_or_default().finish(),
+                #[cfg(feature = "coordinator")]
+                coordinator: self.coordinator.unwrap_or_default().finish(),
             },
         )
     }
 }
 
+/// The network section of a Bee config file.
+#[derive(Default, Deserialize)]
+struct NetworkSection {
+    network: Option<NetworkConfigBuilder>,
+}
+
+/// Reads the network config (e.g. the manual peering) from a local config file.
+pub(crate) fn read_network_config(path: &Path) -> Result<NetworkConfig, NodeConfigError> {
+    deserialize_file::<NetworkSection>(path)?
+        .network
+        .unwrap_or_default()
+        .finish()
+        .map_err(|e| NodeConfigError::InvalidNetworkConfig(e.to_string()))
+}
+
+fn deserialize_file<T: DeserializeOwned>(path: &Path) -> Result<T, NodeConfigError> {
+    match fs::read_to_string(path) {
+        Ok(string) => match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => {
+                serde_json::from_str::<T>(&string).map_err(NodeConfigError::JsonConfigBuilderDeserialization)
+            }
+            Some("toml") => toml::from_str::<T>(&string).map_err(NodeConfigError::TomlConfigBuilderDeserialization),
+            _ => Err(NodeConfigError::UnsupportedConfigType),
+        },
+        Err(e) => Err(NodeConfigError::FileRead(e)),
+    }
+}
+
 /// Represents an IOTA network specification. It consists of:
 /// * a name, e.g. "chrysalis-mainnet";
 /// * an id number (hash of the name);
@@ -266,12 +311,14 @@ impl NetworkSpec {
 mod test {
 
     #[cfg(feature = "rocksdb")]
-    use bee_storage_rocksdb::storage::Storage;
+    use bee_storage_rocksdb::storage::Storage as Backend;
     #[cfg(all(feature = "sled", not(feature = "rocksdb")))]
-    use bee_storage_sled::storage::Storage;
+    use bee_storage_sled::storage::Storage as Backend;
 
     use super::*;
 
+    type Storage = bee_storage_instrumented::storage::Storage<Backend>;
+
     #[test]
     fn config_files_conformity() -> Result<(), NodeConfigError> {
         let json =
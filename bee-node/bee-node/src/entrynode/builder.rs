@@ -233,7 +233,8 @@ async fn initialize_autopeering(
 
     let quit_signal = tokio::signal::ctrl_c();
 
-    let autopeering_rx = bee_autopeering::init::<RocksDbPeerStore, _, _, _>(
+    // Entry nodes don't have neighbors to score or drop, so they don't send commands to the autopeering.
+    let (autopeering_rx, _, packet_metrics) = bee_autopeering::init::<RocksDbPeerStore, _, _, _>(
         builder.config().autopeering.clone(),
         AUTOPEERING_VERSION,
         network_name,
@@ -245,6 +246,9 @@ async fn initialize_autopeering(
     .await
     .map_err(|e| EntryNodeError::AutopeeringInitialization(e))?;
 
+    // Entry nodes are exposed to the internet, so the rejected packets are worth keeping track of.
+    let builder = builder.with_resource(packet_metrics);
+
     Ok((autopeering_rx, builder))
 }
 
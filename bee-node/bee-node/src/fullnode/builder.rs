@@ -9,9 +9,12 @@ use std::{
 use async_trait::async_trait;
 use bee_autopeering::{
     stores::{Options as RocksDbPeerStoreConfigOptions, RocksDbPeerStore, RocksDbPeerStoreConfig},
-    NeighborValidator, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
+    NeighborValidator, PacketMetrics, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
+};
+use bee_gossip::{
+    stores::{Options as RocksDbBanStoreConfigOptions, RocksDbBanStore, RocksDbBanStoreConfig},
+    Keypair, NetworkEventReceiver, Protocol,
 };
-use bee_gossip::{Keypair, NetworkEventReceiver, Protocol};
 use bee_plugin_version_checker::VersionCheckerPlugin;
 use bee_rest_api::InitFullNodeConfig;
 use bee_runtime::{
@@ -22,7 +25,7 @@ use bee_runtime::{
 use bee_storage::system::StorageHealth;
 use fxhash::FxBuildHasher;
 
-use super::{config::FullNodeConfig, FullNode, FullNodeError};
+use super::{config::FullNodeConfig, peering::PeeringReloader, FullNode, FullNodeError};
 use crate::{
     config::NetworkSpec,
     core::{Core, CoreError, ResourceRegister, TopologicalOrder, WorkerStart, WorkerStop},
@@ -146,15 +149,24 @@ impl<S: NodeStorageBackend> NodeBuilder<FullNode<S>> for FullNodeBuilder<S> {
 
         // Initialize everything.
         let (gossip_rx, builder) = initialize_gossip_layer(builder).await?;
-        let (autopeering_rx, builder) = initialize_autopeering(builder).await?;
-        let builder = initialize_ledger(builder);
-        let builder = initialize_protocol(builder, gossip_rx, autopeering_rx);
-        let builder = initialize_api(builder);
+        let (autopeering, packet_metrics, builder) = initialize_autopeering(builder).await?;
+        #[cfg(feature = "coordinator")]
+        bootstrap_genesis_snapshot(&builder)?;
+        let builder = initialize_ledger(builder)?;
+        let builder = initialize_protocol(builder, gossip_rx, autopeering);
+        let builder = initialize_api(builder, packet_metrics);
         let builder = initialize_tangle(builder);
 
+        // Start applying changes of the manual peering (if there's a config file).
+        let builder = initialize_peering_reloader(builder);
+
         // Start the version checker.
         let builder = builder.with_worker::<VersionCheckerPlugin>();
 
+        // Start issuing milestones (if enabled).
+        #[cfg(feature = "coordinator")]
+        let builder = initialize_coordinator(builder);
+
         // Start serving the dashboard (if enabled).
         #[cfg(feature = "dashboard")]
         let builder = initialize_dashboard(builder);
@@ -249,32 +261,50 @@ async fn initialize_gossip_layer<S: NodeStorageBackend>(
     let network_id = config.network_spec().id();
     let gossip_cfg = config.network.clone();
 
-    let (builder, network_events) =
-        bee_gossip::integrated::init::<FullNode<S>>(gossip_cfg, keypair, network_id, builder)
-            .await
-            .map_err(FullNodeError::GossipLayerInitialization)?;
+    // The ban store for persisting banned peers and addresses.
+    let mut banstore_options = RocksDbBanStoreConfigOptions::default();
+    banstore_options.create_if_missing(true);
+    banstore_options.create_missing_column_families(true);
+    let banstore_cfg = RocksDbBanStoreConfig::new(gossip_cfg.ban_storage_path(), banstore_options);
+
+    let (builder, network_events) = bee_gossip::integrated::init::<FullNode<S>, RocksDbBanStore>(
+        gossip_cfg,
+        keypair,
+        network_id,
+        banstore_cfg,
+        builder,
+    )
+    .await
+    .map_err(FullNodeError::GossipLayerInitialization)?;
 
     Ok((network_events, builder))
 }
 
 /// Initializes the ledger.
-fn initialize_ledger<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNodeBuilder<S> {
+fn initialize_ledger<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> Result<FullNodeBuilder<S>, FullNodeError> {
     log::info!("Initializing ledger...");
 
     let config = builder.config();
 
     let network_id = config.network_spec().id();
+    let protocol_parameters = config.protocol_parameters()?;
     let snapshot_cfg = config.snapshot.clone();
     let pruning_cfg = config.pruning.clone();
 
-    bee_ledger::init::<FullNode<S>>(builder, network_id, snapshot_cfg, pruning_cfg)
+    Ok(bee_ledger::init::<FullNode<S>>(
+        builder,
+        network_id,
+        protocol_parameters,
+        snapshot_cfg,
+        pruning_cfg,
+    ))
 }
 
 /// Initializes the protocol.
 fn initialize_protocol<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
     gossip_events: NetworkEventReceiver,
-    autopeering_events: Option<bee_autopeering::event::EventRx>,
+    autopeering: Option<(bee_autopeering::event::EventRx, bee_autopeering::command::CommandTx)>,
 ) -> FullNodeBuilder<S> {
     log::info!("Initializing protocol layer...");
 
@@ -292,19 +322,27 @@ fn initialize_protocol<S: NodeStorageBackend>(
         protocol_cfg,
         (network_name, network_id),
         gossip_events,
-        autopeering_events,
+        autopeering,
         builder,
     )
 }
 
 /// Initializes the (optional) autopeering service.
+#[allow(clippy::type_complexity)]
 async fn initialize_autopeering<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
-) -> Result<(Option<bee_autopeering::event::EventRx>, FullNodeBuilder<S>), FullNodeError> {
+) -> Result<
+    (
+        Option<(bee_autopeering::event::EventRx, bee_autopeering::command::CommandTx)>,
+        Option<PacketMetrics>,
+        FullNodeBuilder<S>,
+    ),
+    FullNodeError,
+> {
     let config = builder.config();
 
     if !config.autopeering.enabled() {
-        Ok((None, builder))
+        Ok((None, None, builder))
     } else {
         log::info!("Initializing autopeering...");
 
@@ -326,7 +364,7 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
 
         let quit_signal = tokio::signal::ctrl_c();
 
-        let autopeering_rx = bee_autopeering::init::<RocksDbPeerStore, _, _, _>(
+        let (event_rx, command_tx, packet_metrics) = bee_autopeering::init::<RocksDbPeerStore, _, _, _>(
             autopeering_cfg,
             AUTOPEERING_VERSION,
             network_name,
@@ -338,7 +376,9 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
         .await
         .map_err(|e| FullNodeError::AutopeeringInitialization(e))?;
 
-        Ok((Some(autopeering_rx), builder))
+        let builder = builder.with_resource(packet_metrics.clone());
+
+        Ok((Some((event_rx, command_tx)), Some(packet_metrics), builder))
     }
 }
 
@@ -373,7 +413,10 @@ fn create_local_autopeering_entity<S: NodeStorageBackend>(
 }
 
 /// Initializes the API.
-fn initialize_api<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNodeBuilder<S> {
+fn initialize_api<S: NodeStorageBackend>(
+    builder: FullNodeBuilder<S>,
+    autopeering_metrics: Option<PacketMetrics>,
+) -> FullNodeBuilder<S> {
     log::info!("Initializing REST API...");
 
     let config = builder.config();
@@ -388,9 +431,9 @@ fn initialize_api<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNod
         node_id: config.local.peer_id(),
         node_keypair: config.local.keypair().clone(),
         rest_api_config: config.rest_api.clone(),
-        protocol_config: config.protocol.clone(),
         network_name,
         bech32_hrp,
+        autopeering_metrics,
         #[cfg(feature = "dashboard")]
         dashboard_username: config.dashboard.auth().user().to_owned(),
     };
@@ -408,6 +451,17 @@ fn initialize_tangle<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> Full
     bee_tangle::init::<FullNode<S>>(&tangle_cfg, builder)
 }
 
+/// Initializes the reloading of the manual peering.
+fn initialize_peering_reloader<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNodeBuilder<S> {
+    match builder.config().path.clone() {
+        Some(config_path) => {
+            let network_config = builder.config().network.clone();
+            builder.with_worker_cfg::<PeeringReloader>((config_path, network_config))
+        }
+        None => builder,
+    }
+}
+
 #[cfg(feature = "dashboard")]
 fn initialize_dashboard<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNodeBuilder<S> {
     log::info!("Initializing dashboard...");
@@ -432,6 +486,49 @@ fn initialize_dashboard<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> F
     )
 }
 
+/// Writes a genesis snapshot for the coordinator to bootstrap a private network from (if enabled and missing).
+#[cfg(feature = "coordinator")]
+fn bootstrap_genesis_snapshot<S: NodeStorageBackend>(builder: &FullNodeBuilder<S>) -> Result<(), FullNodeError> {
+    let config = builder.config();
+
+    if !config.coordinator.enabled() || !config.coordinator.bootstrap() || config.snapshot.full_path().exists() {
+        return Ok(());
+    }
+
+    log::info!("Bootstrapping genesis snapshot...");
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as u32);
+
+    bee_plugin_coordinator::write_genesis_snapshot(
+        config.snapshot.full_path(),
+        config.network_spec().id(),
+        &config.protocol_parameters()?,
+        timestamp,
+        config.coordinator.genesis(),
+    )?;
+
+    Ok(())
+}
+
+/// Initializes the (optional) coordinator.
+#[cfg(feature = "coordinator")]
+fn initialize_coordinator<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> FullNodeBuilder<S> {
+    let config = builder.config();
+
+    if !config.coordinator.enabled() {
+        builder
+    } else {
+        log::info!("Initializing coordinator...");
+
+        let coordinator_cfg = config.coordinator.clone();
+        let protocol_coordinator_cfg = config.protocol.coordinator().clone();
+
+        bee_plugin_coordinator::init::<FullNode<S>>(coordinator_cfg, protocol_coordinator_cfg, builder)
+    }
+}
+
 #[derive(Clone)]
 struct FullNodeNeighborValidator {
     network_name: String,
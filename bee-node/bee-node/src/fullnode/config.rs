@@ -1,9 +1,14 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
 use bee_autopeering::config::AutopeeringConfig;
+use bee_block::protocol::ProtocolParameters;
 use bee_gossip::NetworkConfig;
 use bee_ledger::{pruning::config::PruningConfig, snapshot::config::SnapshotConfig};
+#[cfg(feature = "coordinator")]
+use bee_plugin_coordinator::config::CoordinatorConfig;
 #[cfg(feature = "dashboard")]
 use bee_plugin_dashboard::config::DashboardConfig;
 use bee_protocol::config::ProtocolConfig;
@@ -15,6 +20,8 @@ use crate::{config::NetworkSpec, local::Local, storage::NodeStorageBackend, Node
 
 /// The config of a Bee full node.
 pub struct FullNodeConfig<S: NodeStorageBackend> {
+    /// The path of the config file, if any.
+    pub path: Option<PathBuf>,
     /// The node alias.
     pub alias: String,
     /// The local entity.
@@ -42,6 +49,9 @@ pub struct FullNodeConfig<S: NodeStorageBackend> {
     /// Node dashboard.
     #[cfg(feature = "dashboard")]
     pub dashboard: DashboardConfig,
+    /// Coordinator.
+    #[cfg(feature = "coordinator")]
+    pub coordinator: CoordinatorConfig,
 }
 
 impl<S: NodeStorageBackend> FullNodeConfig<S> {
@@ -60,8 +70,15 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
         &self.network_spec
     }
 
+    /// Returns the protocol parameters of the network as configured by the protocol layer.
+    pub fn protocol_parameters(&self) -> Result<ProtocolParameters, bee_block::Error> {
+        self.protocol
+            .protocol_parameters(self.network_spec.name.clone(), self.network_spec.hrp.clone())
+    }
+
     pub fn from(local: Local, node_cfg: NodeConfig<S>) -> Self {
         Self {
+            path: node_cfg.path,
             alias: node_cfg.alias,
             local,
             network_spec: node_cfg.network_spec,
@@ -76,6 +93,8 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
             tangle: node_cfg.tangle,
             #[cfg(feature = "dashboard")]
             dashboard: node_cfg.dashboard,
+            #[cfg(feature = "coordinator")]
+            coordinator: node_cfg.coordinator,
         }
     }
 }
@@ -83,6 +102,7 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
 impl<S: NodeStorageBackend> Clone for FullNodeConfig<S> {
     fn clone(&self) -> Self {
         Self {
+            path: self.path.clone(),
             alias: self.alias.clone(),
             local: self.local.clone(),
             network_spec: self.network_spec.clone(),
@@ -97,6 +117,8 @@ impl<S: NodeStorageBackend> Clone for FullNodeConfig<S> {
             tangle: self.tangle.clone(),
             #[cfg(feature = "dashboard")]
             dashboard: self.dashboard.clone(),
+            #[cfg(feature = "coordinator")]
+            coordinator: self.coordinator.clone(),
         }
     }
 }
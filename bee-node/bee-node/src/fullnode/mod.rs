@@ -3,6 +3,7 @@
 
 pub mod builder;
 pub mod config;
+mod peering;
 
 use std::any::{type_name, Any, TypeId};
 
@@ -30,6 +31,11 @@ pub enum FullNodeError {
     AutopeeringInitialization(Box<dyn std::error::Error>),
     #[error("{0}")]
     Core(#[from] CoreError),
+    #[error("invalid protocol parameters: {0}")]
+    InvalidProtocolParameters(#[from] bee_block::Error),
+    #[cfg(feature = "coordinator")]
+    #[error("{0}")]
+    GenesisSnapshot(#[from] bee_plugin_coordinator::error::Error),
 }
 
 /// Represents a Bee full node.
@@ -0,0 +1,124 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    convert::Infallible,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use bee_gossip::{NetworkCommandSender, NetworkConfig, NetworkPeering};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use tokio_stream::wrappers::IntervalStream;
+
+use crate::config::read_network_config;
+
+/// The interval at which the config file is checked for modifications.
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Applies changes of the manual peering configuration without restarting the node.
+///
+/// A reload is triggered whenever the config file is modified or, on Unix platforms, the node receives a `SIGHUP`. The
+/// reloaded configuration is compared to the previously loaded one, starting with the one the node was started with.
+#[derive(Default)]
+pub(crate) struct PeeringReloader {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for PeeringReloader {
+    type Config = (PathBuf, NetworkConfig);
+    type Error = Infallible;
+
+    async fn start(node: &mut N, (config_path, mut network_config): Self::Config) -> Result<Self, Self::Error> {
+        let peering = node.resource::<NetworkPeering>();
+        let command_sender = node.resource::<NetworkCommandSender>();
+
+        node.spawn::<Self, _, _>(|shutdown_rx| async move {
+            log::info!("Running.");
+
+            let mut triggers = ShutdownStream::new(shutdown_rx, reload_triggers(config_path.clone()));
+
+            while triggers.next().await.is_some() {
+                if let Some(reloaded_network_config) =
+                    reload(&config_path, &network_config, &peering, &command_sender).await
+                {
+                    network_config = reloaded_network_config;
+                }
+            }
+
+            log::info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
+
+async fn reload(
+    config_path: &Path,
+    previous_network_config: &NetworkConfig,
+    peering: &NetworkPeering,
+    command_sender: &NetworkCommandSender,
+) -> Option<NetworkConfig> {
+    let network_config = match read_network_config(config_path) {
+        Ok(network_config) => network_config,
+        Err(e) => {
+            log::warn!("Reloading the peering configuration failed: {}.", e);
+            return None;
+        }
+    };
+
+    let diff = peering.diff(previous_network_config, &network_config).await;
+
+    if diff.is_empty() {
+        log::info!("Peering configuration reloaded, no changes.");
+        return Some(network_config);
+    }
+
+    log::info!("Peering configuration reloaded, applying changes: {}.", diff);
+
+    for command in diff.into_commands() {
+        if let Err(e) = command_sender.send(command) {
+            log::warn!("Applying a peering change failed: {}.", e);
+        }
+    }
+
+    Some(network_config)
+}
+
+fn reload_triggers(config_path: PathBuf) -> BoxStream<'static, ()> {
+    let mut last_modified_at = modified_at(&config_path);
+
+    let modifications = IntervalStream::new(tokio::time::interval(CONFIG_CHECK_INTERVAL)).filter_map(move |_| {
+        let now_modified_at = modified_at(&config_path);
+        let modified = now_modified_at != last_modified_at;
+
+        last_modified_at = now_modified_at;
+
+        future::ready(if modified { Some(()) } else { None })
+    });
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        // Panic: registering a signal handler only fails outside of a runtime or for forbidden signals.
+        let hangups = stream::unfold(signal(SignalKind::hangup()).unwrap(), |mut hangup| async move {
+            hangup.recv().await.map(|()| ((), hangup))
+        });
+
+        stream::select(modifications, hangups).boxed()
+    }
+
+    #[cfg(not(unix))]
+    modifications.boxed()
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
@@ -21,7 +21,7 @@ pub enum PemFileError {
 
 fn pem_entry_to_keypair(pem_entry: String) -> Result<Keypair, PemFileError> {
     let KeypairBytes { mut secret_key, .. } = KeypairBytes::from_pkcs8_pem(&pem_entry).or(Err(PemFileError::Parse))?;
-    let secret = SecretKey::from_bytes(&mut secret_key).or(Err(PemFileError::DecodeKeypair))?;
+    let secret = SecretKey::try_from_bytes(&mut secret_key).or(Err(PemFileError::DecodeKeypair))?;
     Ok(secret.into())
 }
 
@@ -86,7 +86,7 @@ mod test {
         let keypair = pem_entry_to_keypair(pem_entry.into()).unwrap();
         let mut decoded = [0u8; 64];
         hex::decode_to_slice("f43c8fdc4bd96bf15a4d99fec0a8711c72c305807c482f5f172b4927d7f6d507f3eef70378022bd42fe0cdb799a2b909d42eace03da33b63c4c32c695a9729c2", &mut decoded).unwrap();
-        let parsed = Keypair::decode(&mut decoded).unwrap();
+        let parsed = Keypair::try_from_bytes(&mut decoded).unwrap();
         assert_eq!(keypair.secret().as_ref(), parsed.secret().as_ref());
     }
 
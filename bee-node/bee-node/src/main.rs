@@ -12,12 +12,15 @@ use bee_node::{
 };
 use bee_plugin_mps::MpsPlugin;
 use bee_runtime::node::NodeBuilder as _;
+use bee_storage_instrumented::worker::StorageMetricsWorker;
 #[cfg(feature = "rocksdb")]
-use bee_storage_rocksdb::storage::Storage;
+use bee_storage_rocksdb::storage::Storage as Backend;
 #[cfg(all(feature = "sled", not(feature = "rocksdb")))]
-use bee_storage_sled::storage::Storage;
+use bee_storage_sled::storage::Storage as Backend;
 use log::{error, info, warn};
 
+type Storage = bee_storage_instrumented::storage::Storage<Backend>;
+
 const KEYPAIR_STR_LENGTH: usize = 128;
 
 const CONFIG_PATH_DEFAULT: &str = "./config.json";
@@ -127,7 +130,7 @@ fn migrate_keypair(encoded: String) -> Result<Keypair, IdentityMigrationError> {
         hex::decode_to_slice(&encoded[..], &mut decoded).map_err(|_| IdentityMigrationError::DecodeHex)?;
 
         // Decode the keypair from bytes.
-        Keypair::decode(&mut decoded).map_err(|_| IdentityMigrationError::DecodeKeypair)
+        Keypair::try_from_bytes(&mut decoded).map_err(|_| IdentityMigrationError::DecodeKeypair)
     } else {
         Err(IdentityMigrationError::InvalidKeypair)
     }
@@ -164,7 +167,12 @@ async fn start_fullnode(local: Local, config: NodeConfig<Storage>) {
     let node_builder = FullNodeBuilder::<Storage>::new(full_node_config);
 
     match node_builder {
-        Ok(builder) => match builder.with_plugin::<MpsPlugin>().finish().await {
+        Ok(builder) => match builder
+            .with_plugin::<MpsPlugin>()
+            .with_worker::<StorageMetricsWorker>()
+            .finish()
+            .await
+        {
             Ok(node) => {
                 if let Err(e) = node.run().await {
                     log::error!("Failed to run full node: {}", e);
@@ -0,0 +1,995 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod table;
+
+use std::{
+    collections::{HashSet, VecDeque},
+    io::{self, BufRead, Write},
+};
+
+use bee_block::{
+    address::Ed25519Address,
+    output::OutputId,
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
+    Block, BlockId,
+};
+use bee_ledger::types::{
+    snapshot::SnapshotInfo, ConsumedOutput, CreatedOutput, LedgerIndex, OutputDiff, Receipt, TreasuryOutput, Unspent,
+};
+use bee_storage::{
+    access::{AsIterator, Delete},
+    backend::StorageBackend,
+    system::{StorageHealth, System},
+};
+use bee_storage_instrumented::storage::Storage as InstrumentedStorage;
+#[cfg(feature = "rocksdb")]
+use bee_storage_rocksdb::config::OpenMode;
+use bee_tangle::{
+    block_metadata::BlockMetadata, milestone_metadata::MilestoneMetadata, solid_entry_point::SolidEntryPoint,
+    unreferenced_block::UnreferencedBlock,
+};
+use serde_json::json;
+use structopt::StructOpt;
+use thiserror::Error;
+
+pub use self::table::TableName;
+use self::table::{
+    AddressOutputTable, BlockChildrenTable, BlockMetadataTable, BlockTable, ConsumedOutputTable, CreatedOutputTable,
+    LedgerIndexTable, Lookup, MilestoneMetadataTable, MilestonePayloadTable, OutputDiffTable, ProtocolParametersTable,
+    ReceiptTable, SnapshotInfoTable, SolidEntryPointTable, SystemTable, Table, TreasuryOutputTable,
+    UnreferencedBlockTable, UnspentTable,
+};
+use crate::NodeConfig;
+
+/// Storage operations required to explore every table of the database.
+pub trait DatabaseStorageBackend:
+    ReadOnlyStorageBackend
+    + for<'a> AsIterator<'a, u8, System>
+    + for<'a> AsIterator<'a, BlockId, Block>
+    + for<'a> AsIterator<'a, BlockId, BlockMetadata>
+    + for<'a> AsIterator<'a, (BlockId, BlockId), ()>
+    + for<'a> AsIterator<'a, OutputId, CreatedOutput>
+    + for<'a> AsIterator<'a, OutputId, ConsumedOutput>
+    + for<'a> AsIterator<'a, Unspent, ()>
+    + for<'a> AsIterator<'a, (Ed25519Address, OutputId), ()>
+    + for<'a> AsIterator<'a, (), LedgerIndex>
+    + for<'a> AsIterator<'a, MilestoneIndex, MilestoneMetadata>
+    + for<'a> AsIterator<'a, MilestoneId, MilestonePayload>
+    + for<'a> AsIterator<'a, (), SnapshotInfo>
+    + for<'a> AsIterator<'a, SolidEntryPoint, MilestoneIndex>
+    + for<'a> AsIterator<'a, MilestoneIndex, OutputDiff>
+    + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
+    + for<'a> AsIterator<'a, (MilestoneIndex, UnreferencedBlock), ()>
+    + for<'a> AsIterator<'a, (MilestoneIndex, Receipt), ()>
+    + for<'a> AsIterator<'a, (bool, TreasuryOutput), ()>
+    + Lookup<SystemTable>
+    + Lookup<BlockTable>
+    + Lookup<BlockMetadataTable>
+    + Lookup<BlockChildrenTable>
+    + Lookup<CreatedOutputTable>
+    + Lookup<ConsumedOutputTable>
+    + Lookup<UnspentTable>
+    + Lookup<AddressOutputTable>
+    + Lookup<LedgerIndexTable>
+    + Lookup<MilestoneMetadataTable>
+    + Lookup<MilestonePayloadTable>
+    + Lookup<SnapshotInfoTable>
+    + Lookup<SolidEntryPointTable>
+    + Lookup<OutputDiffTable>
+    + Lookup<ProtocolParametersTable>
+    + Lookup<UnreferencedBlockTable>
+    + Lookup<ReceiptTable>
+    + Lookup<TreasuryOutputTable>
+    + Delete<BlockId, Block>
+    + Delete<BlockId, BlockMetadata>
+    + Delete<(BlockId, BlockId), ()>
+    + Delete<OutputId, CreatedOutput>
+    + Delete<OutputId, ConsumedOutput>
+    + Delete<Unspent, ()>
+    + Delete<(Ed25519Address, OutputId), ()>
+    + Delete<(), LedgerIndex>
+    + Delete<MilestoneIndex, MilestoneMetadata>
+    + Delete<MilestoneId, MilestonePayload>
+    + Delete<(), SnapshotInfo>
+    + Delete<SolidEntryPoint, MilestoneIndex>
+    + Delete<MilestoneIndex, OutputDiff>
+    + Delete<MilestoneIndex, ParametersMilestoneOption>
+    + Delete<(MilestoneIndex, UnreferencedBlock), ()>
+    + Delete<(MilestoneIndex, Receipt), ()>
+    + Delete<(bool, TreasuryOutput), ()>
+{
+}
+
+impl<T> DatabaseStorageBackend for T where
+    T: ReadOnlyStorageBackend
+        + for<'a> AsIterator<'a, u8, System>
+        + for<'a> AsIterator<'a, BlockId, Block>
+        + for<'a> AsIterator<'a, BlockId, BlockMetadata>
+        + for<'a> AsIterator<'a, (BlockId, BlockId), ()>
+        + for<'a> AsIterator<'a, OutputId, CreatedOutput>
+        + for<'a> AsIterator<'a, OutputId, ConsumedOutput>
+        + for<'a> AsIterator<'a, Unspent, ()>
+        + for<'a> AsIterator<'a, (Ed25519Address, OutputId), ()>
+        + for<'a> AsIterator<'a, (), LedgerIndex>
+        + for<'a> AsIterator<'a, MilestoneIndex, MilestoneMetadata>
+        + for<'a> AsIterator<'a, MilestoneId, MilestonePayload>
+        + for<'a> AsIterator<'a, (), SnapshotInfo>
+        + for<'a> AsIterator<'a, SolidEntryPoint, MilestoneIndex>
+        + for<'a> AsIterator<'a, MilestoneIndex, OutputDiff>
+        + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
+        + for<'a> AsIterator<'a, (MilestoneIndex, UnreferencedBlock), ()>
+        + for<'a> AsIterator<'a, (MilestoneIndex, Receipt), ()>
+        + for<'a> AsIterator<'a, (bool, TreasuryOutput), ()>
+        + Lookup<SystemTable>
+        + Lookup<BlockTable>
+        + Lookup<BlockMetadataTable>
+        + Lookup<BlockChildrenTable>
+        + Lookup<CreatedOutputTable>
+        + Lookup<ConsumedOutputTable>
+        + Lookup<UnspentTable>
+        + Lookup<AddressOutputTable>
+        + Lookup<LedgerIndexTable>
+        + Lookup<MilestoneMetadataTable>
+        + Lookup<MilestonePayloadTable>
+        + Lookup<SnapshotInfoTable>
+        + Lookup<SolidEntryPointTable>
+        + Lookup<OutputDiffTable>
+        + Lookup<ProtocolParametersTable>
+        + Lookup<UnreferencedBlockTable>
+        + Lookup<ReceiptTable>
+        + Lookup<TreasuryOutputTable>
+        + Delete<BlockId, Block>
+        + Delete<BlockId, BlockMetadata>
+        + Delete<(BlockId, BlockId), ()>
+        + Delete<OutputId, CreatedOutput>
+        + Delete<OutputId, ConsumedOutput>
+        + Delete<Unspent, ()>
+        + Delete<(Ed25519Address, OutputId), ()>
+        + Delete<(), LedgerIndex>
+        + Delete<MilestoneIndex, MilestoneMetadata>
+        + Delete<MilestoneId, MilestonePayload>
+        + Delete<(), SnapshotInfo>
+        + Delete<SolidEntryPoint, MilestoneIndex>
+        + Delete<MilestoneIndex, OutputDiff>
+        + Delete<MilestoneIndex, ParametersMilestoneOption>
+        + Delete<(MilestoneIndex, UnreferencedBlock), ()>
+        + Delete<(MilestoneIndex, Receipt), ()>
+        + Delete<(bool, TreasuryOutput), ()>
+{
+}
+
+/// Storage backends that can be opened without modifying the database.
+pub trait ReadOnlyStorageBackend: StorageBackend {
+    /// Returns a configuration opening the database for reading only or, given a path for its logs, as a secondary
+    /// instance alongside a running node.
+    fn read_only_config(config: &Self::Config, secondary_path: Option<&str>) -> Result<Self::Config, DatabaseError>;
+
+    /// Replays the latest writes of the running node, if the database was opened as a secondary instance.
+    fn catch_up_with_primary(&self) -> Result<(), DatabaseError>;
+}
+
+#[cfg(feature = "rocksdb")]
+impl ReadOnlyStorageBackend for InstrumentedStorage<bee_storage_rocksdb::storage::Storage> {
+    fn read_only_config(config: &Self::Config, secondary_path: Option<&str>) -> Result<Self::Config, DatabaseError> {
+        let mut config = config.clone();
+
+        config.backend_mut().set_mode(match secondary_path {
+            Some(secondary_path) => OpenMode::Secondary(secondary_path.into()),
+            None => OpenMode::ReadOnly,
+        });
+
+        Ok(config)
+    }
+
+    fn catch_up_with_primary(&self) -> Result<(), DatabaseError> {
+        self.inner().catch_up_with_primary().map_err(DatabaseError::storage)
+    }
+}
+
+#[cfg(feature = "sled")]
+impl ReadOnlyStorageBackend for InstrumentedStorage<bee_storage_sled::storage::Storage> {
+    fn read_only_config(config: &Self::Config, secondary_path: Option<&str>) -> Result<Self::Config, DatabaseError> {
+        if secondary_path.is_some() {
+            return Err(DatabaseError::UnsupportedSecondary);
+        }
+
+        let mut config = config.clone();
+
+        config.backend_mut().set_read_only(true);
+
+        Ok(config)
+    }
+
+    fn catch_up_with_primary(&self) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DatabaseError {
+    #[error("storage backend error: {0}")]
+    StorageBackend(Box<dyn std::error::Error + Send>),
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
+    #[error("table {0} can not be modified")]
+    ReadOnlyTable(TableName),
+    #[error("the storage backend can not be opened as a secondary instance")]
+    UnsupportedSecondary,
+    #[error("rows can not be deleted from a secondary instance")]
+    SecondaryDelete,
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+impl DatabaseError {
+    fn storage(error: impl std::error::Error + Send + 'static) -> Self {
+        Self::StorageBackend(Box::new(error))
+    }
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub enum DatabaseCommand {
+    /// Counts the rows of a table.
+    Count { table: TableName },
+    /// Fetches the rows of a table by key. Tables with composite keys are looked up by the first component of the key,
+    /// e.g. the parent for `block-children`, and return at most the number of rows configured in the storage fetch
+    /// limits.
+    Get { table: TableName, key: Option<String> },
+    /// Iterates over the rows of a table, optionally restricted to a range of keys. Tables with composite keys are
+    /// restricted by the first component of the key.
+    Scan {
+        table: TableName,
+        /// Smallest key to include.
+        #[structopt(long)]
+        from: Option<String>,
+        /// Largest key to include.
+        #[structopt(long)]
+        to: Option<String>,
+        /// Maximum number of rows to output.
+        #[structopt(long)]
+        limit: Option<usize>,
+    },
+    /// Follows the links of a block: its metadata, its parents and, recursively, its children.
+    Follow {
+        block_id: String,
+        /// Number of generations of children to follow.
+        #[structopt(long, default_value = "1")]
+        depth: usize,
+    },
+    /// Outputs the number of rows and the packed size of every table.
+    Stats,
+    /// Deletes the rows of a table by key, as looked up by `get`, after confirmation.
+    Delete {
+        table: TableName,
+        key: Option<String>,
+        /// Does not ask for confirmation.
+        #[structopt(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub struct DatabaseTool {
+    /// Outputs the rows as JSON objects, one per line.
+    #[structopt(long)]
+    json: bool,
+    /// Opens the database as a secondary instance keeping its logs in the given directory, which allows exploring the
+    /// database of a running node. Only supported by RocksDB: sled locks its database, which therefore can only be
+    /// explored once the node is stopped.
+    #[structopt(long)]
+    secondary: Option<String>,
+    #[structopt(subcommand)]
+    command: DatabaseCommand,
+}
+
+/// Calls a function generic over the table, with the table type matching a table name.
+macro_rules! with_table {
+    ($table:expr, $f:ident($($arg:expr),*)) => {
+        match $table {
+            TableName::System => $f::<SystemTable, _>($table, $($arg),*),
+            TableName::Block => $f::<BlockTable, _>($table, $($arg),*),
+            TableName::BlockMetadata => $f::<BlockMetadataTable, _>($table, $($arg),*),
+            TableName::BlockChildren => $f::<BlockChildrenTable, _>($table, $($arg),*),
+            TableName::CreatedOutput => $f::<CreatedOutputTable, _>($table, $($arg),*),
+            TableName::ConsumedOutput => $f::<ConsumedOutputTable, _>($table, $($arg),*),
+            TableName::Unspent => $f::<UnspentTable, _>($table, $($arg),*),
+            TableName::AddressOutput => $f::<AddressOutputTable, _>($table, $($arg),*),
+            TableName::LedgerIndex => $f::<LedgerIndexTable, _>($table, $($arg),*),
+            TableName::MilestoneMetadata => $f::<MilestoneMetadataTable, _>($table, $($arg),*),
+            TableName::MilestonePayload => $f::<MilestonePayloadTable, _>($table, $($arg),*),
+            TableName::SnapshotInfo => $f::<SnapshotInfoTable, _>($table, $($arg),*),
+            TableName::SolidEntryPoint => $f::<SolidEntryPointTable, _>($table, $($arg),*),
+            TableName::OutputDiff => $f::<OutputDiffTable, _>($table, $($arg),*),
+            TableName::ProtocolParameters => $f::<ProtocolParametersTable, _>($table, $($arg),*),
+            TableName::UnreferencedBlock => $f::<UnreferencedBlockTable, _>($table, $($arg),*),
+            TableName::Receipt => $f::<ReceiptTable, _>($table, $($arg),*),
+            TableName::TreasuryOutput => $f::<TreasuryOutputTable, _>($table, $($arg),*),
+        }
+    };
+}
+
+/// Same as `with_table` for the tables that can be modified.
+macro_rules! with_writable_table {
+    ($table:expr, $f:ident($($arg:expr),*)) => {
+        match $table {
+            TableName::System => Err(DatabaseError::ReadOnlyTable($table)),
+            TableName::Block => $f::<BlockTable, _>($table, $($arg),*),
+            TableName::BlockMetadata => $f::<BlockMetadataTable, _>($table, $($arg),*),
+            TableName::BlockChildren => $f::<BlockChildrenTable, _>($table, $($arg),*),
+            TableName::CreatedOutput => $f::<CreatedOutputTable, _>($table, $($arg),*),
+            TableName::ConsumedOutput => $f::<ConsumedOutputTable, _>($table, $($arg),*),
+            TableName::Unspent => $f::<UnspentTable, _>($table, $($arg),*),
+            TableName::AddressOutput => $f::<AddressOutputTable, _>($table, $($arg),*),
+            TableName::LedgerIndex => $f::<LedgerIndexTable, _>($table, $($arg),*),
+            TableName::MilestoneMetadata => $f::<MilestoneMetadataTable, _>($table, $($arg),*),
+            TableName::MilestonePayload => $f::<MilestonePayloadTable, _>($table, $($arg),*),
+            TableName::SnapshotInfo => $f::<SnapshotInfoTable, _>($table, $($arg),*),
+            TableName::SolidEntryPoint => $f::<SolidEntryPointTable, _>($table, $($arg),*),
+            TableName::OutputDiff => $f::<OutputDiffTable, _>($table, $($arg),*),
+            TableName::ProtocolParameters => $f::<ProtocolParametersTable, _>($table, $($arg),*),
+            TableName::UnreferencedBlock => $f::<UnreferencedBlockTable, _>($table, $($arg),*),
+            TableName::Receipt => $f::<ReceiptTable, _>($table, $($arg),*),
+            TableName::TreasuryOutput => $f::<TreasuryOutputTable, _>($table, $($arg),*),
+        }
+    };
+}
+
+fn print_row<T: Table>(
+    out: &mut dyn Write,
+    table: TableName,
+    key: &T::Key,
+    value: &T::Value,
+    json: bool,
+) -> Result<(), DatabaseError> {
+    if json {
+        writeln!(
+            out,
+            "{}",
+            json!({ "table": table.as_str(), "key": T::key_json(key), "value": T::value_json(value)? })
+        )?;
+    } else {
+        writeln!(out, "Key: {}\nValue: {:?}\n", T::key_json(key), value)?;
+    }
+
+    Ok(())
+}
+
+/// Returns whether a prefix lies within the optional inclusive bounds of a scan.
+fn in_range<P: Ord>(prefix: &P, from: Option<&P>, to: Option<&P>) -> bool {
+    !matches!(from, Some(from) if prefix < from) && !matches!(to, Some(to) if prefix > to)
+}
+
+fn count<T: Table, B>(table: TableName, out: &mut dyn Write, storage: &B, json: bool) -> Result<(), DatabaseError>
+where
+    B: StorageBackend + for<'a> AsIterator<'a, T::Key, T::Value>,
+{
+    let mut rows = 0;
+
+    for result in AsIterator::<T::Key, T::Value>::iter(storage).map_err(DatabaseError::storage)? {
+        result.map_err(DatabaseError::storage)?;
+        rows += 1;
+    }
+
+    if json {
+        writeln!(out, "{}", json!({ "table": table.as_str(), "rows": rows }))?;
+    } else {
+        writeln!(out, "{table}: {rows} rows")?;
+    }
+
+    Ok(())
+}
+
+fn get<T: Table, B: Lookup<T>>(
+    table: TableName,
+    out: &mut dyn Write,
+    storage: &B,
+    key: &str,
+    json: bool,
+) -> Result<(), DatabaseError> {
+    let rows = storage.lookup(&T::parse_prefix(key)?).map_err(DatabaseError::storage)?;
+
+    if rows.is_empty() && !json {
+        writeln!(out, "No rows found.")?;
+    }
+
+    for (key, value) in &rows {
+        print_row::<T>(out, table, key, value, json)?;
+    }
+
+    Ok(())
+}
+
+fn scan<T: Table, B>(
+    table: TableName,
+    out: &mut dyn Write,
+    storage: &B,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: Option<usize>,
+    json: bool,
+) -> Result<(), DatabaseError>
+where
+    B: StorageBackend + for<'a> AsIterator<'a, T::Key, T::Value>,
+{
+    let from = from.map(T::parse_prefix).transpose()?;
+    let to = to.map(T::parse_prefix).transpose()?;
+    let mut rows = 0;
+
+    // Backends do not iterate in key order, so the whole table is filtered rather than seeked.
+    for result in AsIterator::<T::Key, T::Value>::iter(storage).map_err(DatabaseError::storage)? {
+        if limit == Some(rows) {
+            break;
+        }
+
+        let (key, value) = result.map_err(DatabaseError::storage)?;
+
+        if in_range(&T::prefix(&key), from.as_ref(), to.as_ref()) {
+            print_row::<T>(out, table, &key, &value, json)?;
+            rows += 1;
+        }
+    }
+
+    if !json {
+        writeln!(out, "{rows} rows")?;
+    }
+
+    Ok(())
+}
+
+/// The number of rows and the packed size of a table.
+type TableSize = (usize, usize);
+
+/// Returns the number of rows and the packed size of a table.
+fn table_size<T: Table, B>(_: TableName, storage: &B) -> Result<TableSize, DatabaseError>
+where
+    B: StorageBackend + for<'a> AsIterator<'a, T::Key, T::Value>,
+{
+    let mut rows = 0;
+    let mut size = 0;
+
+    for result in AsIterator::<T::Key, T::Value>::iter(storage).map_err(DatabaseError::storage)? {
+        let (key, value) = result.map_err(DatabaseError::storage)?;
+
+        rows += 1;
+        size += T::packed_len(&key, &value);
+    }
+
+    Ok((rows, size))
+}
+
+/// Returns the number of rows and the packed size of every table.
+fn table_sizes<B: DatabaseStorageBackend>(storage: &B) -> Result<Vec<(TableName, TableSize)>, DatabaseError> {
+    TableName::ALL
+        .into_iter()
+        .map(|table| Ok((table, with_table!(table, table_size(storage))?)))
+        .collect()
+}
+
+fn stats<B: DatabaseStorageBackend>(out: &mut dyn Write, storage: &B, json: bool) -> Result<(), DatabaseError> {
+    let tables = table_sizes(storage)?;
+    let health = storage.get_health().map_err(DatabaseError::storage)?;
+    let size = storage.size().map_err(DatabaseError::storage)?;
+
+    print_stats(out, &tables, health, size, json)
+}
+
+fn print_stats(
+    out: &mut dyn Write,
+    tables: &[(TableName, TableSize)],
+    health: Option<StorageHealth>,
+    size: Option<usize>,
+    json: bool,
+) -> Result<(), DatabaseError> {
+    if json {
+        writeln!(
+            out,
+            "{}",
+            json!({
+                "tables": tables
+                    .iter()
+                    .map(|(table, (rows, size))| json!({ "table": table.as_str(), "rows": rows, "size": size }))
+                    .collect::<Vec<_>>(),
+                "health": health.map(|health| format!("{health:?}")),
+                "size": size,
+            })
+        )?;
+    } else {
+        writeln!(out, "{:<20}{:>14}{:>18}", "Table", "Rows", "Packed size")?;
+        for (table, (rows, size)) in tables {
+            writeln!(out, "{:<20}{:>14}{:>18}", table.as_str(), rows, size)?;
+        }
+        writeln!(out)?;
+        match health {
+            Some(health) => writeln!(out, "Storage health:\t{health:?}")?,
+            None => writeln!(out, "Storage health:\tunknown")?,
+        }
+        match size {
+            Some(size) => writeln!(out, "Storage size:\t{size}")?,
+            None => writeln!(out, "Storage size:\tunknown")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn follow<B>(out: &mut dyn Write, storage: &B, block_id: &str, depth: usize, json: bool) -> Result<(), DatabaseError>
+where
+    B: Lookup<BlockTable> + Lookup<BlockMetadataTable> + Lookup<BlockChildrenTable>,
+{
+    let block_id = BlockTable::parse_prefix(block_id)?;
+    let mut visited = HashSet::from([block_id]);
+    let mut queue = VecDeque::from([(block_id, 0)]);
+
+    while let Some((block_id, generation)) = queue.pop_front() {
+        let block = Lookup::<BlockTable>::lookup(storage, &block_id)
+            .map_err(DatabaseError::storage)?
+            .pop()
+            .map(|(_, block)| block);
+        let metadata = Lookup::<BlockMetadataTable>::lookup(storage, &block_id)
+            .map_err(DatabaseError::storage)?
+            .pop()
+            .map(|(_, metadata)| metadata);
+        let children = Lookup::<BlockChildrenTable>::lookup(storage, &block_id)
+            .map_err(DatabaseError::storage)?
+            .into_iter()
+            .map(|((_, child), ())| child)
+            .collect::<Vec<_>>();
+        let parents = block
+            .as_ref()
+            .map(|block| block.parents().iter().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        if json {
+            writeln!(
+                out,
+                "{}",
+                json!({
+                    "blockId": block_id.to_string(),
+                    "generation": generation,
+                    "block": block.as_ref().map(BlockTable::value_json).transpose()?,
+                    "metadata": metadata.as_ref().map(BlockMetadataTable::value_json).transpose()?,
+                    "parents": parents.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    "children": children.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                })
+            )?;
+        } else {
+            writeln!(out, "Block {block_id} (generation {generation})")?;
+            match &block {
+                Some(_) => writeln!(out, "Parents: {parents:?}")?,
+                None => writeln!(out, "Block: missing")?,
+            }
+            match &metadata {
+                Some(metadata) => writeln!(out, "Metadata: {metadata:?}")?,
+                None => writeln!(out, "Metadata: missing")?,
+            }
+            writeln!(out, "Children: {children:?}\n")?;
+        }
+
+        if generation < depth {
+            for child in children {
+                if visited.insert(child) {
+                    queue.push_back((child, generation + 1));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn confirm(prompt: &str) -> Result<bool, DatabaseError> {
+    // The prompt goes to the standard error so that the standard output only contains rows.
+    eprint!("{prompt} [y/N] ");
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn delete<T: Table, B>(
+    table: TableName,
+    out: &mut dyn Write,
+    storage: &B,
+    key: &str,
+    yes: bool,
+    json: bool,
+) -> Result<(), DatabaseError>
+where
+    B: Lookup<T> + Delete<T::Key, T::Value>,
+{
+    let rows = storage.lookup(&T::parse_prefix(key)?).map_err(DatabaseError::storage)?;
+
+    if rows.is_empty() {
+        eprintln!("No rows found.");
+        return Ok(());
+    }
+
+    for (key, value) in &rows {
+        print_row::<T>(out, table, key, value, json)?;
+    }
+
+    if !yes && !confirm(&format!("Delete {} rows from table {table}?", rows.len()))? {
+        eprintln!("Aborted.");
+        return Ok(());
+    }
+
+    for (key, _) in &rows {
+        Delete::<T::Key, T::Value>::delete(storage, key).map_err(DatabaseError::storage)?;
+    }
+
+    eprintln!("Deleted {} rows.", rows.len());
+
+    Ok(())
+}
+
+pub fn exec<B: DatabaseStorageBackend>(tool: &DatabaseTool, node_config: &NodeConfig<B>) -> Result<(), DatabaseError> {
+    // Only `delete` opens the database for writing, every other command can't modify it and can run alongside a node.
+    let config = match (&tool.command, &tool.secondary) {
+        (DatabaseCommand::Delete { .. }, Some(_)) => return Err(DatabaseError::SecondaryDelete),
+        (DatabaseCommand::Delete { .. }, None) => node_config.storage.clone(),
+        (_, secondary_path) => B::read_only_config(&node_config.storage, secondary_path.as_deref())?,
+    };
+    // The health checks are skipped so that corrupted databases can be inspected. The storage is dropped without being
+    // shut down, which leaves its health status untouched.
+    let storage = B::start_unchecked(config).map_err(DatabaseError::storage)?;
+    storage.catch_up_with_primary()?;
+    let json = tool.json;
+    let out = &mut io::stdout().lock();
+
+    match &tool.command {
+        DatabaseCommand::Count { table } => with_table!(*table, count(out, &storage, json)),
+        DatabaseCommand::Get { table, key } => {
+            with_table!(*table, get(out, &storage, key.as_deref().unwrap_or_default(), json))
+        }
+        DatabaseCommand::Scan { table, from, to, limit } => {
+            with_table!(
+                *table,
+                scan(out, &storage, from.as_deref(), to.as_deref(), *limit, json)
+            )
+        }
+        DatabaseCommand::Follow { block_id, depth } => follow(out, &storage, block_id, *depth, json),
+        DatabaseCommand::Stats => stats(out, &storage, json),
+        DatabaseCommand::Delete { table, key, yes } => {
+            with_writable_table!(
+                *table,
+                delete(out, &storage, key.as_deref().unwrap_or_default(), *yes, json)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bee_block::rand::block::{rand_block, rand_block_id};
+    use bee_ledger_types::rand::output_diff::rand_output_diff;
+    use bee_storage::{access::Insert, system::StorageVersion};
+    use bee_storage_memory::storage::Storage;
+    use serde_json::Value;
+
+    use super::*;
+
+    impl ReadOnlyStorageBackend for Storage {
+        fn read_only_config(_: &Self::Config, _: Option<&str>) -> Result<Self::Config, DatabaseError> {
+            Ok(())
+        }
+
+        fn catch_up_with_primary(&self) -> Result<(), DatabaseError> {
+            Ok(())
+        }
+    }
+
+    fn output(out: Vec<u8>) -> String {
+        String::from_utf8(out).unwrap()
+    }
+
+    fn json_lines(out: Vec<u8>) -> Vec<Value> {
+        output(out)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn storage_with_system_rows() -> Storage {
+        let storage = Storage::new();
+
+        Insert::<u8, System>::insert(&storage, &0, &System::Version(StorageVersion(2))).unwrap();
+        Insert::<u8, System>::insert(&storage, &1, &System::Health(StorageHealth::Healthy)).unwrap();
+
+        storage
+    }
+
+    fn insert_edge(storage: &Storage, parent: BlockId, child: BlockId) {
+        Insert::<(BlockId, BlockId), ()>::insert(storage, &(parent, child), &()).unwrap();
+    }
+
+    #[test]
+    fn range_bounds_are_inclusive() {
+        assert!(in_range(&3, None, None));
+        assert!(in_range(&3, Some(&3), Some(&3)));
+        assert!(in_range(&3, Some(&2), None));
+        assert!(!in_range(&3, Some(&4), None));
+        assert!(!in_range(&3, None, Some(&2)));
+    }
+
+    #[test]
+    fn count_output() {
+        let storage = storage_with_system_rows();
+        let mut out = Vec::new();
+
+        count::<SystemTable, _>(TableName::System, &mut out, &storage, false).unwrap();
+        assert_eq!(output(out), "system: 2 rows\n");
+
+        let mut out = Vec::new();
+
+        count::<SystemTable, _>(TableName::System, &mut out, &storage, true).unwrap();
+        assert_eq!(json_lines(out), vec![json!({ "table": "system", "rows": 2 })]);
+    }
+
+    #[test]
+    fn get_output() {
+        let storage = storage_with_system_rows();
+        let mut out = Vec::new();
+
+        get::<SystemTable, _>(TableName::System, &mut out, &storage, "0", true).unwrap();
+        assert_eq!(
+            json_lines(out),
+            vec![json!({ "table": "system", "key": 0, "value": { "version": 2 } })]
+        );
+
+        let mut out = Vec::new();
+
+        get::<SystemTable, _>(TableName::System, &mut out, &storage, "1", false).unwrap();
+        assert_eq!(output(out), "Key: 1\nValue: Health(Healthy)\n\n");
+
+        let mut out = Vec::new();
+
+        get::<SystemTable, _>(TableName::System, &mut out, &storage, "2", false).unwrap();
+        assert_eq!(output(out), "No rows found.\n");
+
+        assert!(matches!(
+            get::<SystemTable, _>(TableName::System, &mut Vec::new(), &storage, "system", false),
+            Err(DatabaseError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn get_composite_key_by_first_component() {
+        let storage = Storage::new();
+        let parent = rand_block_id();
+        let mut children = vec![rand_block_id(), rand_block_id()];
+
+        for child in &children {
+            insert_edge(&storage, parent, *child);
+        }
+        insert_edge(&storage, rand_block_id(), rand_block_id());
+
+        let mut out = Vec::new();
+
+        get::<BlockChildrenTable, _>(TableName::BlockChildren, &mut out, &storage, &parent.to_string(), true).unwrap();
+
+        let mut rows = json_lines(out);
+
+        rows.sort_by_key(|row| row["key"]["child"].as_str().unwrap().to_owned());
+        children.sort_by_key(ToString::to_string);
+
+        assert_eq!(
+            rows,
+            children
+                .iter()
+                .map(|child| json!({
+                    "table": "block-children",
+                    "key": { "parent": parent.to_string(), "child": child.to_string() },
+                    "value": null,
+                }))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn scan_filters_rows() {
+        let storage = Storage::new();
+
+        for index in 1..=5 {
+            Insert::<MilestoneIndex, OutputDiff>::insert(&storage, &MilestoneIndex(index), &rand_output_diff())
+                .unwrap();
+        }
+
+        let mut out = Vec::new();
+
+        scan::<OutputDiffTable, _>(
+            TableName::OutputDiff,
+            &mut out,
+            &storage,
+            Some("2"),
+            Some("4"),
+            None,
+            true,
+        )
+        .unwrap();
+
+        let mut keys = json_lines(out)
+            .iter()
+            .map(|row| row["key"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+
+        keys.sort_unstable();
+        assert_eq!(keys, vec![2, 3, 4]);
+
+        let mut out = Vec::new();
+
+        scan::<OutputDiffTable, _>(
+            TableName::OutputDiff,
+            &mut out,
+            &storage,
+            Some("2"),
+            None,
+            Some(2),
+            false,
+        )
+        .unwrap();
+        assert!(output(out).ends_with("\n2 rows\n"));
+
+        assert!(matches!(
+            scan::<OutputDiffTable, _>(
+                TableName::OutputDiff,
+                &mut Vec::new(),
+                &storage,
+                Some("x"),
+                None,
+                None,
+                false
+            ),
+            Err(DatabaseError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn follow_children_up_to_depth() {
+        let storage = Storage::new();
+        let block = rand_block(0);
+        let block_id = block.id();
+        let child = rand_block_id();
+        let grandchild = rand_block_id();
+
+        Insert::<BlockId, Block>::insert(&storage, &block_id, &block).unwrap();
+        insert_edge(&storage, block_id, child);
+        insert_edge(&storage, child, grandchild);
+        // A cycle back to the block must not be followed twice.
+        insert_edge(&storage, grandchild, block_id);
+
+        let follow_json = |depth| {
+            let mut out = Vec::new();
+
+            follow(&mut out, &storage, &block_id.to_string(), depth, true).unwrap();
+            json_lines(out)
+        };
+
+        let rows = follow_json(0);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["generation"], 0);
+        assert_eq!(rows[0]["children"], json!([child.to_string()]));
+        assert_eq!(
+            rows[0]["parents"],
+            json!(block.parents().iter().map(ToString::to_string).collect::<Vec<_>>())
+        );
+        assert!(rows[0]["block"].is_object());
+        assert!(rows[0]["metadata"].is_null());
+
+        let rows = follow_json(5);
+
+        assert_eq!(
+            rows.iter().map(|row| row["blockId"].clone()).collect::<Vec<_>>(),
+            json!([block_id.to_string(), child.to_string(), grandchild.to_string()])
+                .as_array()
+                .unwrap()
+                .clone()
+        );
+        assert!(rows[1]["block"].is_null());
+
+        let mut out = Vec::new();
+
+        follow(&mut out, &storage, &child.to_string(), 0, false).unwrap();
+        assert_eq!(
+            output(out),
+            format!("Block {child} (generation 0)\nBlock: missing\nMetadata: missing\nChildren: [{grandchild:?}]\n\n")
+        );
+    }
+
+    #[test]
+    fn table_sizes_cover_every_table() {
+        let storage = storage_with_system_rows();
+        let tables = table_sizes(&storage).unwrap();
+
+        assert_eq!(
+            tables.iter().map(|(table, _)| *table).collect::<Vec<_>>(),
+            TableName::ALL.to_vec()
+        );
+        assert_eq!(
+            tables[0],
+            (
+                TableName::System,
+                (
+                    2,
+                    SystemTable::packed_len(&0, &System::Version(StorageVersion(2)))
+                        + SystemTable::packed_len(&1, &System::Health(StorageHealth::Healthy))
+                )
+            )
+        );
+        assert!(tables[1..].iter().all(|(_, size)| *size == (0, 0)));
+    }
+
+    #[test]
+    fn stats_output() {
+        let tables = [(TableName::System, (2, 20)), (TableName::Block, (0, 0))];
+        let mut out = Vec::new();
+
+        print_stats(&mut out, &tables, Some(StorageHealth::Healthy), None, true).unwrap();
+        assert_eq!(
+            json_lines(out),
+            vec![json!({
+                "tables": [
+                    { "table": "system", "rows": 2, "size": 20 },
+                    { "table": "block", "rows": 0, "size": 0 },
+                ],
+                "health": "Healthy",
+                "size": null,
+            })]
+        );
+
+        let mut out = Vec::new();
+
+        print_stats(&mut out, &tables, None, Some(1024), false).unwrap();
+        assert_eq!(
+            output(out),
+            format!(
+                "{:<20}{:>14}{:>18}\n{:<20}{:>14}{:>18}\n{:<20}{:>14}{:>18}\n\n{}\n{}\n",
+                "Table",
+                "Rows",
+                "Packed size",
+                "system",
+                2,
+                20,
+                "block",
+                0,
+                0,
+                "Storage health:\tunknown",
+                "Storage size:\t1024"
+            )
+        );
+    }
+
+    #[test]
+    fn delete_rows_by_key() {
+        let storage = Storage::new();
+        let parent = rand_block_id();
+        let other = rand_block_id();
+
+        insert_edge(&storage, parent, rand_block_id());
+        insert_edge(&storage, parent, rand_block_id());
+        insert_edge(&storage, other, rand_block_id());
+
+        let mut out = Vec::new();
+
+        delete::<BlockChildrenTable, _>(
+            TableName::BlockChildren,
+            &mut out,
+            &storage,
+            &parent.to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+        assert_eq!(json_lines(out).len(), 2);
+
+        let mut out = Vec::new();
+
+        count::<BlockChildrenTable, _>(TableName::BlockChildren, &mut out, &storage, true).unwrap();
+        assert_eq!(json_lines(out)[0]["rows"], 1);
+    }
+}
@@ -0,0 +1,934 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, str::FromStr};
+
+use bee_block::{
+    address::Ed25519Address,
+    output::{
+        dto::{OutputDto, TreasuryOutputDto},
+        OutputId,
+    },
+    payload::milestone::{
+        dto::MilestonePayloadDto, option::dto::ParametersMilestoneOptionDto, MilestoneId, MilestoneIndex,
+        MilestonePayload, ParametersMilestoneOption,
+    },
+    Block, BlockDto, BlockId,
+};
+use bee_ledger::types::{
+    snapshot::SnapshotInfo, ConsumedOutput, CreatedOutput, LedgerIndex, OutputDiff, Receipt, TreasuryOutput, Unspent,
+};
+use bee_rest_api::types::dtos::ReceiptDto;
+use bee_storage::{
+    access::{Exist, Fetch},
+    backend::StorageBackend,
+    system::System,
+};
+use bee_tangle::{
+    block_metadata::BlockMetadata, milestone_metadata::MilestoneMetadata, solid_entry_point::SolidEntryPoint,
+    unreferenced_block::UnreferencedBlock,
+};
+use packable::PackableExt;
+use serde_json::{json, Value};
+
+use super::DatabaseError;
+
+/// The tables of the database.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableName {
+    System,
+    Block,
+    BlockMetadata,
+    BlockChildren,
+    CreatedOutput,
+    ConsumedOutput,
+    Unspent,
+    AddressOutput,
+    LedgerIndex,
+    MilestoneMetadata,
+    MilestonePayload,
+    SnapshotInfo,
+    SolidEntryPoint,
+    OutputDiff,
+    ProtocolParameters,
+    UnreferencedBlock,
+    Receipt,
+    TreasuryOutput,
+}
+
+impl TableName {
+    pub(super) const ALL: [TableName; 18] = [
+        Self::System,
+        Self::Block,
+        Self::BlockMetadata,
+        Self::BlockChildren,
+        Self::CreatedOutput,
+        Self::ConsumedOutput,
+        Self::Unspent,
+        Self::AddressOutput,
+        Self::LedgerIndex,
+        Self::MilestoneMetadata,
+        Self::MilestonePayload,
+        Self::SnapshotInfo,
+        Self::SolidEntryPoint,
+        Self::OutputDiff,
+        Self::ProtocolParameters,
+        Self::UnreferencedBlock,
+        Self::Receipt,
+        Self::TreasuryOutput,
+    ];
+
+    /// Returns the name of the table as given on the command line.
+    pub(super) fn as_str(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Block => "block",
+            Self::BlockMetadata => "block-metadata",
+            Self::BlockChildren => "block-children",
+            Self::CreatedOutput => "created-output",
+            Self::ConsumedOutput => "consumed-output",
+            Self::Unspent => "unspent",
+            Self::AddressOutput => "address-output",
+            Self::LedgerIndex => "ledger-index",
+            Self::MilestoneMetadata => "milestone-metadata",
+            Self::MilestonePayload => "milestone-payload",
+            Self::SnapshotInfo => "snapshot-info",
+            Self::SolidEntryPoint => "solid-entry-point",
+            Self::OutputDiff => "output-diff",
+            Self::ProtocolParameters => "protocol-parameters",
+            Self::UnreferencedBlock => "unreferenced-block",
+            Self::Receipt => "receipt",
+            Self::TreasuryOutput => "treasury-output",
+        }
+    }
+}
+
+impl FromStr for TableName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|table| table.as_str() == s).ok_or_else(|| {
+            format!(
+                "unknown table `{s}`, expected one of: {}",
+                Self::ALL.map(|table| table.as_str()).join(", ")
+            )
+        })
+    }
+}
+
+impl fmt::Display for TableName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Describes how the rows of a table are looked up and displayed.
+///
+/// Rows are looked up by a prefix of their key: the whole key for most tables, or its first component for the tables
+/// with composite keys, e.g. the parent of a parent/child edge.
+pub trait Table {
+    type Key;
+    type Value: fmt::Debug;
+    type Prefix: Ord;
+    /// The value returned by the storage when fetching the rows of a prefix.
+    type Fetched;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError>;
+
+    fn prefix(key: &Self::Key) -> Self::Prefix;
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)>;
+
+    fn key_json(key: &Self::Key) -> Value;
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error>;
+
+    /// Returns the size of a row once packed, which approximates the space it takes in the storage.
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize;
+}
+
+/// The rows of a table.
+pub type Rows<T> = Vec<(<T as Table>::Key, <T as Table>::Value)>;
+
+/// Looks up the rows of a table by prefix.
+pub trait Lookup<T: Table>: StorageBackend {
+    fn lookup(&self, prefix: &T::Prefix) -> Result<Rows<T>, Self::Error>;
+}
+
+macro_rules! impl_lookup {
+    ($table:ty) => {
+        impl<B> Lookup<$table> for B
+        where
+            B: StorageBackend + Fetch<<$table as Table>::Prefix, <$table as Table>::Fetched>,
+        {
+            fn lookup(&self, prefix: &<$table as Table>::Prefix) -> Result<Rows<$table>, Self::Error> {
+                Ok(self
+                    .fetch(prefix)?
+                    .map(|fetched| <$table>::rows(prefix, fetched))
+                    .unwrap_or_default())
+            }
+        }
+    };
+}
+
+impl_lookup!(SystemTable);
+impl_lookup!(BlockTable);
+impl_lookup!(BlockMetadataTable);
+impl_lookup!(BlockChildrenTable);
+impl_lookup!(CreatedOutputTable);
+impl_lookup!(ConsumedOutputTable);
+impl_lookup!(AddressOutputTable);
+impl_lookup!(LedgerIndexTable);
+impl_lookup!(MilestoneMetadataTable);
+impl_lookup!(MilestonePayloadTable);
+impl_lookup!(SnapshotInfoTable);
+impl_lookup!(OutputDiffTable);
+impl_lookup!(ProtocolParametersTable);
+impl_lookup!(UnreferencedBlockTable);
+impl_lookup!(ReceiptTable);
+impl_lookup!(TreasuryOutputTable);
+
+// Solid entry points are looked up by block ID as they are not ordered.
+impl<B: StorageBackend + Fetch<SolidEntryPoint, MilestoneIndex>> Lookup<SolidEntryPointTable> for B {
+    fn lookup(&self, prefix: &BlockId) -> Result<Vec<(SolidEntryPoint, MilestoneIndex)>, Self::Error> {
+        Ok(self
+            .fetch(&SolidEntryPoint::new(*prefix))?
+            .map(|index| SolidEntryPointTable::rows(prefix, index))
+            .unwrap_or_default())
+    }
+}
+
+// Unspent markers have no value, so they are looked up by existence.
+impl<B: StorageBackend + Exist<Unspent, ()>> Lookup<UnspentTable> for B {
+    fn lookup(&self, prefix: &OutputId) -> Result<Vec<(Unspent, ())>, Self::Error> {
+        Ok(UnspentTable::rows(prefix, self.exist(&Unspent::new(*prefix))?))
+    }
+}
+
+fn parse<T: FromStr>(key: &str) -> Result<T, DatabaseError> {
+    key.parse().map_err(|_| DatabaseError::InvalidKey(key.to_string()))
+}
+
+fn parse_milestone_index(key: &str) -> Result<MilestoneIndex, DatabaseError> {
+    parse::<u32>(key).map(MilestoneIndex::new)
+}
+
+fn created_output_json(output: &CreatedOutput) -> Value {
+    json!({
+        "blockId": output.block_id().to_string(),
+        "milestoneIndexBooked": *output.milestone_index(),
+        "milestoneTimestampBooked": output.milestone_timestamp(),
+        "output": OutputDto::from(output.inner()),
+    })
+}
+
+pub struct SystemTable;
+
+impl Table for SystemTable {
+    type Key = u8;
+    type Value = System;
+    type Prefix = u8;
+    type Fetched = System;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key)
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(match value {
+            System::Version(version) => json!({ "version": version.0 }),
+            System::Health(health) => json!({ "health": format!("{health:?}") }),
+        })
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct BlockTable;
+
+impl Table for BlockTable {
+    type Key = BlockId;
+    type Value = Block;
+    type Prefix = BlockId;
+    type Fetched = Block;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(BlockDto::from(value))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct BlockMetadataTable;
+
+impl Table for BlockMetadataTable {
+    type Key = BlockId;
+    type Value = BlockMetadata;
+    type Prefix = BlockId;
+    type Fetched = BlockMetadata;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(value)
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct BlockChildrenTable;
+
+impl Table for BlockChildrenTable {
+    type Key = (BlockId, BlockId);
+    type Value = ();
+    type Prefix = BlockId;
+    type Fetched = Vec<BlockId>;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        key.0
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        fetched.into_iter().map(|child| ((*prefix, child), ())).collect()
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!({ "parent": key.0.to_string(), "child": key.1.to_string() })
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.0.packed_len() + key.1.packed_len()
+    }
+}
+
+pub struct CreatedOutputTable;
+
+impl Table for CreatedOutputTable {
+    type Key = OutputId;
+    type Value = CreatedOutput;
+    type Prefix = OutputId;
+    type Fetched = CreatedOutput;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(created_output_json(value))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct ConsumedOutputTable;
+
+impl Table for ConsumedOutputTable {
+    type Key = OutputId;
+    type Value = ConsumedOutput;
+    type Prefix = OutputId;
+    type Fetched = ConsumedOutput;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(json!({
+            "transactionIdSpent": value.target().to_string(),
+            "milestoneIndexSpent": *value.milestone_index(),
+            "milestoneTimestampSpent": value.milestone_timestamp(),
+        }))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct UnspentTable;
+
+impl Table for UnspentTable {
+    type Key = Unspent;
+    type Value = ();
+    type Prefix = OutputId;
+    type Fetched = bool;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key.id()
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        if fetched {
+            vec![(Unspent::new(*prefix), ())]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.id().to_string())
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.packed_len()
+    }
+}
+
+pub struct AddressOutputTable;
+
+impl Table for AddressOutputTable {
+    type Key = (Ed25519Address, OutputId);
+    type Value = ();
+    type Prefix = Ed25519Address;
+    type Fetched = Vec<OutputId>;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        key.0
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        fetched
+            .into_iter()
+            .map(|output_id| ((*prefix, output_id), ()))
+            .collect()
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!({ "address": key.0.to_string(), "outputId": key.1.to_string() })
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.0.packed_len() + key.1.packed_len()
+    }
+}
+
+pub struct LedgerIndexTable;
+
+impl Table for LedgerIndexTable {
+    type Key = ();
+    type Value = LedgerIndex;
+    type Prefix = ();
+    type Fetched = LedgerIndex;
+
+    fn parse_prefix(_: &str) -> Result<Self::Prefix, DatabaseError> {
+        Ok(())
+    }
+
+    fn prefix(_: &Self::Key) -> Self::Prefix {}
+
+    fn rows(_: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![((), fetched)]
+    }
+
+    fn key_json(_: &Self::Key) -> Value {
+        Value::Null
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(json!(*value.0))
+    }
+
+    fn packed_len(_: &Self::Key, value: &Self::Value) -> usize {
+        value.packed_len()
+    }
+}
+
+pub struct MilestoneMetadataTable;
+
+impl Table for MilestoneMetadataTable {
+    type Key = MilestoneIndex;
+    type Value = MilestoneMetadata;
+    type Prefix = MilestoneIndex;
+    type Fetched = MilestoneMetadata;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse_milestone_index(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(**key)
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(json!({
+            "blockId": value.block_id().to_string(),
+            "milestoneId": value.milestone_id().to_string(),
+            "timestamp": value.timestamp(),
+        }))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct MilestonePayloadTable;
+
+impl Table for MilestonePayloadTable {
+    type Key = MilestoneId;
+    type Value = MilestonePayload;
+    type Prefix = MilestoneId;
+    type Fetched = MilestonePayload;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(MilestonePayloadDto::from(value))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct SnapshotInfoTable;
+
+impl Table for SnapshotInfoTable {
+    type Key = ();
+    type Value = SnapshotInfo;
+    type Prefix = ();
+    type Fetched = SnapshotInfo;
+
+    fn parse_prefix(_: &str) -> Result<Self::Prefix, DatabaseError> {
+        Ok(())
+    }
+
+    fn prefix(_: &Self::Key) -> Self::Prefix {}
+
+    fn rows(_: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![((), fetched)]
+    }
+
+    fn key_json(_: &Self::Key) -> Value {
+        Value::Null
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(json!({
+            "networkId": value.network_id().to_string(),
+            "snapshotIndex": *value.snapshot_index(),
+            "entryPointIndex": *value.entry_point_index(),
+            "pruningIndex": *value.pruning_index(),
+            "timestamp": value.timestamp(),
+        }))
+    }
+
+    fn packed_len(_: &Self::Key, value: &Self::Value) -> usize {
+        value.packed_len()
+    }
+}
+
+pub struct SolidEntryPointTable;
+
+impl Table for SolidEntryPointTable {
+    type Key = SolidEntryPoint;
+    type Value = MilestoneIndex;
+    type Prefix = BlockId;
+    type Fetched = MilestoneIndex;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key.block_id()
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(SolidEntryPoint::new(*prefix), fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(key.block_id().to_string())
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(json!(**value))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct OutputDiffTable;
+
+impl Table for OutputDiffTable {
+    type Key = MilestoneIndex;
+    type Value = OutputDiff;
+    type Prefix = MilestoneIndex;
+    type Fetched = OutputDiff;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse_milestone_index(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(**key)
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        let output_ids = |output_ids: &[OutputId]| output_ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        Ok(json!({
+            "createdOutputs": output_ids(value.created_outputs()),
+            "consumedOutputs": output_ids(value.consumed_outputs()),
+            "treasuryDiff": value.treasury_diff().map(|diff| json!({
+                "created": diff.created().to_string(),
+                "consumed": diff.consumed().to_string(),
+            })),
+        }))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct ProtocolParametersTable;
+
+impl Table for ProtocolParametersTable {
+    type Key = MilestoneIndex;
+    type Value = ParametersMilestoneOption;
+    type Prefix = MilestoneIndex;
+    type Fetched = ParametersMilestoneOption;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse_milestone_index(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        *key
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        vec![(*prefix, fetched)]
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!(**key)
+    }
+
+    fn value_json(value: &Self::Value) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(ParametersMilestoneOptionDto::from(value))
+    }
+
+    fn packed_len(key: &Self::Key, value: &Self::Value) -> usize {
+        key.packed_len() + value.packed_len()
+    }
+}
+
+pub struct UnreferencedBlockTable;
+
+impl Table for UnreferencedBlockTable {
+    type Key = (MilestoneIndex, UnreferencedBlock);
+    type Value = ();
+    type Prefix = MilestoneIndex;
+    type Fetched = Vec<UnreferencedBlock>;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse_milestone_index(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        key.0
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        fetched.into_iter().map(|block| ((*prefix, block), ())).collect()
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!({ "milestoneIndex": *key.0, "blockId": key.1.block_id().to_string() })
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.0.packed_len() + key.1.packed_len()
+    }
+}
+
+pub struct ReceiptTable;
+
+impl Table for ReceiptTable {
+    type Key = (MilestoneIndex, Receipt);
+    type Value = ();
+    type Prefix = MilestoneIndex;
+    type Fetched = Vec<Receipt>;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse_milestone_index(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        key.0
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        fetched.into_iter().map(|receipt| ((*prefix, receipt), ())).collect()
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!({ "milestoneIndex": *key.0, "receipt": ReceiptDto::from(key.1.clone()) })
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.0.packed_len() + key.1.packed_len()
+    }
+}
+
+pub struct TreasuryOutputTable;
+
+impl Table for TreasuryOutputTable {
+    type Key = (bool, TreasuryOutput);
+    type Value = ();
+    type Prefix = bool;
+    type Fetched = Vec<TreasuryOutput>;
+
+    fn parse_prefix(prefix: &str) -> Result<Self::Prefix, DatabaseError> {
+        parse(prefix)
+    }
+
+    fn prefix(key: &Self::Key) -> Self::Prefix {
+        key.0
+    }
+
+    fn rows(prefix: &Self::Prefix, fetched: Self::Fetched) -> Vec<(Self::Key, Self::Value)> {
+        fetched.into_iter().map(|output| ((*prefix, output), ())).collect()
+    }
+
+    fn key_json(key: &Self::Key) -> Value {
+        json!({
+            "spent": key.0,
+            "milestoneId": key.1.milestone_id().to_string(),
+            "output": TreasuryOutputDto::from(key.1.inner()),
+        })
+    }
+
+    fn value_json(_: &Self::Value) -> Result<Value, serde_json::Error> {
+        Ok(Value::Null)
+    }
+
+    fn packed_len(key: &Self::Key, _: &Self::Value) -> usize {
+        key.0.packed_len() + key.1.packed_len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bee_block::rand::{address::rand_ed25519_address, block::rand_block_id, output::rand_output_id};
+    use bee_storage::system::StorageHealth;
+
+    use super::*;
+
+    #[test]
+    fn table_names_round_trip() {
+        for table in TableName::ALL {
+            assert_eq!(table.as_str().parse::<TableName>().unwrap(), table);
+            assert_eq!(table.to_string(), table.as_str());
+        }
+
+        let error = "blocks".parse::<TableName>().unwrap_err();
+
+        assert!(error.starts_with("unknown table `blocks`"));
+        assert!(error.contains("block-children"));
+    }
+
+    #[test]
+    fn keys_decode_per_table() {
+        let block_id = rand_block_id();
+        let output_id = rand_output_id();
+        let address = rand_ed25519_address();
+
+        assert_eq!(BlockTable::parse_prefix(&block_id.to_string()).unwrap(), block_id);
+        assert_eq!(
+            CreatedOutputTable::parse_prefix(&output_id.to_string()).unwrap(),
+            output_id
+        );
+        assert_eq!(AddressOutputTable::parse_prefix(&address.to_string()).unwrap(), address);
+        assert_eq!(MilestoneMetadataTable::parse_prefix("42").unwrap(), MilestoneIndex(42));
+        assert!(TreasuryOutputTable::parse_prefix("true").unwrap());
+        // Singleton tables have a single row, whatever the key.
+        LedgerIndexTable::parse_prefix("anything").unwrap();
+
+        for (result, key) in [
+            (BlockTable::parse_prefix("0x12").map(drop), "0x12"),
+            (MilestoneMetadataTable::parse_prefix("-1").map(drop), "-1"),
+            (SystemTable::parse_prefix("256").map(drop), "256"),
+        ] {
+            assert!(matches!(result, Err(DatabaseError::InvalidKey(invalid)) if invalid == key));
+        }
+    }
+
+    #[test]
+    fn composite_keys_split_into_rows() {
+        let parent = rand_block_id();
+        let children = vec![rand_block_id(), rand_block_id()];
+        let rows = BlockChildrenTable::rows(&parent, children.clone());
+
+        assert_eq!(rows.len(), 2);
+
+        for ((key, ()), child) in rows.iter().zip(&children) {
+            assert_eq!(BlockChildrenTable::prefix(key), parent);
+            assert_eq!(
+                BlockChildrenTable::key_json(key),
+                json!({ "parent": parent.to_string(), "child": child.to_string() })
+            );
+        }
+
+        let output_id = rand_output_id();
+
+        assert!(UnspentTable::rows(&output_id, false).is_empty());
+        assert_eq!(
+            UnspentTable::rows(&output_id, true),
+            vec![(Unspent::new(output_id), ())]
+        );
+    }
+
+    #[test]
+    fn system_values_as_json() {
+        assert_eq!(
+            SystemTable::value_json(&System::Health(StorageHealth::Corrupted)).unwrap(),
+            json!({ "health": "Corrupted" })
+        );
+        assert_eq!(
+            SystemTable::packed_len(&0, &System::Health(StorageHealth::Healthy)),
+            0u8.packed_len() + System::Health(StorageHealth::Healthy).packed_len()
+        );
+    }
+}
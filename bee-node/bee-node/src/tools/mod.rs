@@ -1,67 +1,70 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod database;
 mod ed25519;
 mod jwt_api;
 mod password;
-#[cfg(feature = "rocksdb")]
-mod rocksdb;
-#[cfg(feature = "sled")]
-mod sled;
+mod peer_store;
+mod repair;
 mod snapshot_info;
 
 use structopt::StructOpt;
 use thiserror::Error;
 
-use crate::{Local, NodeConfig, NodeStorageBackend};
+pub use self::{database::{DatabaseStorageBackend, ReadOnlyStorageBackend}, repair::RepairStorageBackend};
+use crate::{Local, NodeConfig};
 
 #[non_exhaustive]
 #[derive(Clone, Debug, StructOpt)]
 pub enum Tool {
     /// Generates Ed25519 public/private keys and addresses.
     Ed25519(ed25519::Ed25519Tool),
-    /// Rocksdb database analyser.
-    #[cfg(feature = "rocksdb")]
-    Rocksdb(rocksdb::RocksdbTool),
-    /// Sled database analyser.
-    #[cfg(feature = "sled")]
-    Sled(sled::SledTool),
+    /// Explores the database of the configured storage backend.
+    Database(database::DatabaseTool),
     /// Outputs information about a snapshot file.
     SnapshotInfo(snapshot_info::SnapshotInfoTool),
     /// Generates password salt and hash.
     Password(password::PasswordTool),
     /// Generates a JWT for the Node API.
     JwtApi(jwt_api::JwtApiTool),
+    /// Checks the referential integrity of the database, repairs it and resets its health status.
+    Repair(repair::RepairTool),
+    /// Exports or imports the peers of the autopeering peer store as JSON.
+    PeerStore(peer_store::PeerStoreTool),
 }
 
 #[derive(Debug, Error)]
 pub enum ToolError {
     #[error("{0}")]
     Ed25519(#[from] ed25519::Ed25519Error),
-    #[cfg(feature = "rocksdb")]
     #[error("{0}")]
-    Rocksdb(#[from] rocksdb::RocksdbError),
-    #[cfg(feature = "sled")]
-    #[error("{0}")]
-    Sled(#[from] sled::SledError),
+    Database(#[from] database::DatabaseError),
     #[error("{0}")]
     SnapshotInfo(#[from] snapshot_info::SnapshotInfoError),
     #[error("{0}")]
     Password(#[from] password::PasswordError),
     #[error("{0}")]
     JwtApi(#[from] jwt_api::JwtApiError),
+    #[error("{0}")]
+    Repair(#[from] repair::RepairError),
+    #[error("{0}")]
+    PeerStore(#[from] peer_store::PeerStoreError),
 }
 
-pub fn exec<B: NodeStorageBackend>(tool: &Tool, local: &Local, node_config: &NodeConfig<B>) -> Result<(), ToolError> {
+pub fn exec<B: DatabaseStorageBackend + RepairStorageBackend>(
+    tool: &Tool,
+    local: &Local,
+    node_config: &NodeConfig<B>,
+) -> Result<(), ToolError> {
     match tool {
         Tool::Ed25519(tool) => ed25519::exec(tool)?,
-        #[cfg(feature = "rocksdb")]
-        Tool::Rocksdb(tool) => rocksdb::exec(tool)?,
-        #[cfg(feature = "sled")]
-        Tool::Sled(tool) => sled::exec(tool)?,
+        Tool::Database(tool) => database::exec(tool, node_config)?,
         Tool::SnapshotInfo(tool) => snapshot_info::exec(tool)?,
         Tool::Password(tool) => password::exec(tool)?,
         Tool::JwtApi(tool) => jwt_api::exec(tool, local, node_config)?,
+        Tool::Repair(tool) => repair::exec(tool, node_config)?,
+        Tool::PeerStore(tool) => peer_store::exec(tool, node_config)?,
     }
 
     Ok(())
@@ -0,0 +1,64 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf};
+
+use bee_autopeering::stores::{self, Options, PeerRecord, PeerStore, RocksDbPeerStore, RocksDbPeerStoreConfig};
+use structopt::StructOpt;
+use thiserror::Error;
+
+use crate::{NodeConfig, NodeStorageBackend};
+
+#[derive(Debug, Error)]
+pub enum PeerStoreError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid peers JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("peer store error: {0}")]
+    PeerStore(#[from] <RocksDbPeerStore as PeerStore>::Error),
+}
+
+/// Exports or imports the autopeering peer store of a node that is not running.
+#[derive(Clone, Debug, StructOpt)]
+pub enum PeerStoreTool {
+    /// Exports the stored peers as JSON.
+    Export {
+        /// Writes the peers to a file instead of the standard output.
+        #[structopt(long)]
+        file: Option<PathBuf>,
+    },
+    /// Imports peers from a JSON file, e.g. to seed a fresh entry node with a known-good set of peers.
+    Import { file: PathBuf },
+}
+
+pub fn exec<B: NodeStorageBackend>(tool: &PeerStoreTool, node_config: &NodeConfig<B>) -> Result<(), PeerStoreError> {
+    // Only an import may create the peer store, an export of a missing one fails instead of leaving an empty one behind.
+    let mut options = Options::default();
+    if let PeerStoreTool::Import { .. } = tool {
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+    }
+
+    let config = RocksDbPeerStoreConfig::new(node_config.autopeering.peer_storage_path(), options);
+    let peer_store = RocksDbPeerStore::new(config)?;
+
+    match tool {
+        PeerStoreTool::Export { file } => {
+            let json = serde_json::to_string_pretty(&stores::export_peers(&peer_store)?)?;
+
+            match file {
+                Some(file) => fs::write(file, json)?,
+                None => println!("{}", json),
+            }
+        }
+        PeerStoreTool::Import { file } => {
+            let records = serde_json::from_str::<Vec<PeerRecord>>(&fs::read_to_string(file)?)?;
+            let num_imported = stores::import_peers(&peer_store, &records)?;
+
+            println!("Imported {} peer/s.", num_imported);
+        }
+    }
+
+    Ok(())
+}
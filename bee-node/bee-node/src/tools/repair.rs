@@ -0,0 +1,423 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{address::Ed25519Address, output::OutputId, payload::milestone::MilestoneIndex, Block, BlockId};
+use bee_ledger::types::{CreatedOutput, LedgerIndex, OutputDiff, Unspent};
+use bee_storage::{
+    access::{AsIterator, Delete, Exist, Fetch, Insert, InsertStrict},
+    system::StorageHealth,
+};
+use bee_tangle::block_metadata::BlockMetadata;
+use structopt::StructOpt;
+use thiserror::Error;
+
+use crate::{NodeConfig, NodeStorageBackend};
+
+/// Storage operations required to check and repair the referential integrity of the database.
+pub trait RepairStorageBackend:
+    NodeStorageBackend
+    + Exist<BlockId, BlockMetadata>
+    + Exist<OutputId, CreatedOutput>
+    + Delete<(BlockId, BlockId), ()>
+    + Delete<Unspent, ()>
+    + Delete<(Ed25519Address, OutputId), ()>
+    + for<'a> AsIterator<'a, BlockId, Block>
+    + for<'a> AsIterator<'a, (BlockId, BlockId), ()>
+    + for<'a> AsIterator<'a, (Ed25519Address, OutputId), ()>
+    + for<'a> AsIterator<'a, MilestoneIndex, OutputDiff>
+{
+}
+
+impl<T> RepairStorageBackend for T where
+    T: NodeStorageBackend
+        + Exist<BlockId, BlockMetadata>
+        + Exist<OutputId, CreatedOutput>
+        + Delete<(BlockId, BlockId), ()>
+        + Delete<Unspent, ()>
+        + Delete<(Ed25519Address, OutputId), ()>
+        + for<'a> AsIterator<'a, BlockId, Block>
+        + for<'a> AsIterator<'a, (BlockId, BlockId), ()>
+        + for<'a> AsIterator<'a, (Ed25519Address, OutputId), ()>
+        + for<'a> AsIterator<'a, MilestoneIndex, OutputDiff>
+{
+}
+
+#[derive(Debug, Error)]
+pub enum RepairError {
+    #[error("storage backend error: {0}")]
+    StorageBackend(Box<dyn std::error::Error + Send>),
+    #[error(
+        "ledger index {ledger_index} is ahead of the latest output diff {diff_index}, the ledger state can't be repaired"
+    )]
+    LedgerIndexAheadOfOutputDiffs {
+        ledger_index: MilestoneIndex,
+        diff_index: MilestoneIndex,
+    },
+}
+
+impl RepairError {
+    fn storage(error: impl std::error::Error + Send + 'static) -> Self {
+        Self::StorageBackend(Box::new(error))
+    }
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub struct RepairTool {
+    /// Only reports the inconsistencies without repairing them.
+    #[structopt(long)]
+    dry_run: bool,
+}
+
+/// Counts the inconsistencies found by a check and repairs them unless it's a dry run.
+struct Check<'a, B> {
+    storage: &'a B,
+    dry_run: bool,
+}
+
+impl<'a, B: RepairStorageBackend> Check<'a, B> {
+    fn report(&self, description: &str, count: usize) {
+        if count == 0 {
+            println!("{description}: none");
+        } else if self.dry_run {
+            println!("{description}: {count} found");
+        } else {
+            println!("{description}: {count} repaired");
+        }
+    }
+
+    /// Blocks without metadata are given fresh metadata, the node will then solidify them again.
+    fn blocks_without_metadata(&self) -> Result<(), RepairError> {
+        let mut block_ids = Vec::new();
+
+        for result in AsIterator::<BlockId, Block>::iter(self.storage).map_err(RepairError::storage)? {
+            let (block_id, _) = result.map_err(RepairError::storage)?;
+
+            if !Exist::<BlockId, BlockMetadata>::exist(self.storage, &block_id).map_err(RepairError::storage)? {
+                block_ids.push(block_id);
+            }
+        }
+
+        if !self.dry_run {
+            for block_id in &block_ids {
+                InsertStrict::<BlockId, BlockMetadata>::insert_strict(
+                    self.storage,
+                    block_id,
+                    &BlockMetadata::arrived(),
+                )
+                .map_err(RepairError::storage)?;
+            }
+        }
+
+        self.report("Blocks without metadata", block_ids.len());
+
+        Ok(())
+    }
+
+    /// Edges to a child that is not stored are removed. Edges from a parent that is not stored are expected, as the
+    /// parent may have been pruned.
+    fn dangling_edges(&self) -> Result<(), RepairError> {
+        let mut edges = Vec::new();
+
+        for result in AsIterator::<(BlockId, BlockId), ()>::iter(self.storage).map_err(RepairError::storage)? {
+            let (edge, ()) = result.map_err(RepairError::storage)?;
+
+            if !Exist::<BlockId, Block>::exist(self.storage, &edge.1).map_err(RepairError::storage)? {
+                edges.push(edge);
+            }
+        }
+
+        if !self.dry_run {
+            for edge in &edges {
+                Delete::<(BlockId, BlockId), ()>::delete(self.storage, edge).map_err(RepairError::storage)?;
+            }
+        }
+
+        self.report("Dangling parent/child edges", edges.len());
+
+        Ok(())
+    }
+
+    fn unspent_without_output(&self) -> Result<(), RepairError> {
+        let mut unspents = Vec::new();
+
+        for result in AsIterator::<Unspent, ()>::iter(self.storage).map_err(RepairError::storage)? {
+            let (unspent, ()) = result.map_err(RepairError::storage)?;
+
+            if !Exist::<OutputId, CreatedOutput>::exist(self.storage, unspent.id()).map_err(RepairError::storage)? {
+                unspents.push(unspent);
+            }
+        }
+
+        if !self.dry_run {
+            for unspent in &unspents {
+                Delete::<Unspent, ()>::delete(self.storage, unspent).map_err(RepairError::storage)?;
+            }
+        }
+
+        self.report("Unspent markers without created output", unspents.len());
+
+        Ok(())
+    }
+
+    fn addresses_without_output(&self) -> Result<(), RepairError> {
+        let mut entries = Vec::new();
+
+        for result in AsIterator::<(Ed25519Address, OutputId), ()>::iter(self.storage).map_err(RepairError::storage)? {
+            let (entry, ()) = result.map_err(RepairError::storage)?;
+
+            if !Exist::<OutputId, CreatedOutput>::exist(self.storage, &entry.1).map_err(RepairError::storage)? {
+                entries.push(entry);
+            }
+        }
+
+        if !self.dry_run {
+            for entry in &entries {
+                Delete::<(Ed25519Address, OutputId), ()>::delete(self.storage, entry).map_err(RepairError::storage)?;
+            }
+        }
+
+        self.report("Address index entries without created output", entries.len());
+
+        Ok(())
+    }
+
+    /// The ledger index is written in the same batch as the output diff of a milestone, so it is expected to match the
+    /// latest output diff. An output diff beyond the ledger index means the ledger index was not persisted, so it is set
+    /// to the latest output diff. A ledger index beyond the latest output diff can't be repaired: the outputs of the
+    /// milestones up to it were already applied, and applying them again would conflict with the consumed outputs.
+    /// Without output diffs, e.g. after a full snapshot import, the ledger index can't be checked.
+    fn ledger_index(&self) -> Result<(), RepairError> {
+        let ledger_index = Fetch::<(), LedgerIndex>::fetch(self.storage, &()).map_err(RepairError::storage)?;
+        let mut latest_diff_index = None;
+
+        for result in AsIterator::<MilestoneIndex, OutputDiff>::iter(self.storage).map_err(RepairError::storage)? {
+            let (index, _) = result.map_err(RepairError::storage)?;
+
+            latest_diff_index = latest_diff_index.max(Some(index));
+        }
+
+        match (ledger_index, latest_diff_index) {
+            (Some(ledger_index), Some(diff_index)) if ledger_index.0 > diff_index => {
+                return Err(RepairError::LedgerIndexAheadOfOutputDiffs {
+                    ledger_index: ledger_index.0,
+                    diff_index,
+                });
+            }
+            (ledger_index, Some(diff_index)) if ledger_index.map(|ledger_index| ledger_index.0) != Some(diff_index) => {
+                if !self.dry_run {
+                    Insert::<(), LedgerIndex>::insert(self.storage, &(), &LedgerIndex::new(diff_index))
+                        .map_err(RepairError::storage)?;
+                }
+                self.report(
+                    &format!(
+                        "Ledger index {} inconsistent with the latest output diff {}",
+                        ledger_index.map_or_else(|| "none".to_string(), |ledger_index| ledger_index.0.to_string()),
+                        diff_index
+                    ),
+                    1,
+                );
+            }
+            _ => self.report("Ledger index inconsistent with the output diffs", 0),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn exec<B: RepairStorageBackend>(tool: &RepairTool, node_config: &NodeConfig<B>) -> Result<(), RepairError> {
+    let storage = B::start_unchecked(node_config.storage.clone()).map_err(RepairError::storage)?;
+
+    println!(
+        "Storage health: {:?}",
+        storage.get_health().map_err(RepairError::storage)?
+    );
+
+    let check = Check {
+        storage: &storage,
+        dry_run: tool.dry_run,
+    };
+
+    // On failure, the storage is dropped without being shut down so that its health status is left untouched.
+    check.blocks_without_metadata()?;
+    check.dangling_edges()?;
+    check.unspent_without_output()?;
+    check.addresses_without_output()?;
+    check.ledger_index()?;
+
+    if tool.dry_run {
+        return Ok(());
+    }
+
+    storage
+        .set_health(StorageHealth::Healthy)
+        .map_err(RepairError::storage)?;
+    storage.shutdown().map_err(RepairError::storage)?;
+
+    println!("Storage health reset to {:?}.", StorageHealth::Healthy);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use bee_block::rand::{
+        address::rand_ed25519_address,
+        block::{rand_block, rand_block_id},
+        output::rand_output_id,
+    };
+    use bee_ledger_types::rand::{output::rand_created_output, output_diff::rand_output_diff};
+    use bee_storage_memory::storage::Storage;
+
+    use super::*;
+
+    fn repair(storage: &Storage) {
+        let check = Check {
+            storage,
+            dry_run: false,
+        };
+
+        check.blocks_without_metadata().unwrap();
+        check.dangling_edges().unwrap();
+        check.unspent_without_output().unwrap();
+        check.addresses_without_output().unwrap();
+        check.ledger_index().unwrap();
+    }
+
+    fn insert_ledger_index(storage: &Storage, index: u32) {
+        Insert::<(), LedgerIndex>::insert(storage, &(), &LedgerIndex::new(MilestoneIndex(index))).unwrap();
+    }
+
+    fn insert_output_diff(storage: &Storage, index: u32) {
+        Insert::<MilestoneIndex, OutputDiff>::insert(storage, &MilestoneIndex(index), &rand_output_diff()).unwrap();
+    }
+
+    fn fetch_ledger_index(storage: &Storage) -> Option<LedgerIndex> {
+        Fetch::<(), LedgerIndex>::fetch(storage, &()).unwrap()
+    }
+
+    #[test]
+    fn block_without_metadata() {
+        let storage = Storage::new();
+        let block = rand_block(0);
+        let block_id = block.id();
+
+        Insert::<BlockId, Block>::insert(&storage, &block_id, &block).unwrap();
+
+        Check {
+            storage: &storage,
+            dry_run: true,
+        }
+        .blocks_without_metadata()
+        .unwrap();
+        assert!(!Exist::<BlockId, BlockMetadata>::exist(&storage, &block_id).unwrap());
+
+        repair(&storage);
+        assert!(Exist::<BlockId, BlockMetadata>::exist(&storage, &block_id).unwrap());
+    }
+
+    #[test]
+    fn dangling_edge() {
+        let storage = Storage::new();
+        let block = rand_block(0);
+        let (parent, child, dangling_child) = (rand_block_id(), block.id(), rand_block_id());
+
+        Insert::<BlockId, Block>::insert(&storage, &child, &block).unwrap();
+        Insert::<(BlockId, BlockId), ()>::insert(&storage, &(parent, child), &()).unwrap();
+        Insert::<(BlockId, BlockId), ()>::insert(&storage, &(parent, dangling_child), &()).unwrap();
+
+        repair(&storage);
+
+        assert!(Exist::<(BlockId, BlockId), ()>::exist(&storage, &(parent, child)).unwrap());
+        assert!(!Exist::<(BlockId, BlockId), ()>::exist(&storage, &(parent, dangling_child)).unwrap());
+    }
+
+    #[test]
+    fn unspent_without_output() {
+        let storage = Storage::new();
+        let (output_id, dangling_output_id) = (rand_output_id(), rand_output_id());
+
+        Insert::<OutputId, CreatedOutput>::insert(&storage, &output_id, &rand_created_output(u64::MAX)).unwrap();
+        Insert::<Unspent, ()>::insert(&storage, &output_id.into(), &()).unwrap();
+        Insert::<Unspent, ()>::insert(&storage, &dangling_output_id.into(), &()).unwrap();
+
+        repair(&storage);
+
+        assert!(Exist::<Unspent, ()>::exist(&storage, &output_id.into()).unwrap());
+        assert!(!Exist::<Unspent, ()>::exist(&storage, &dangling_output_id.into()).unwrap());
+    }
+
+    #[test]
+    fn address_without_output() {
+        let storage = Storage::new();
+        let address = rand_ed25519_address();
+        let (output_id, dangling_output_id) = (rand_output_id(), rand_output_id());
+
+        Insert::<OutputId, CreatedOutput>::insert(&storage, &output_id, &rand_created_output(u64::MAX)).unwrap();
+        Insert::<(Ed25519Address, OutputId), ()>::insert(&storage, &(address, output_id), &()).unwrap();
+        Insert::<(Ed25519Address, OutputId), ()>::insert(&storage, &(address, dangling_output_id), &()).unwrap();
+
+        repair(&storage);
+
+        assert!(Exist::<(Ed25519Address, OutputId), ()>::exist(&storage, &(address, output_id)).unwrap());
+        assert!(!Exist::<(Ed25519Address, OutputId), ()>::exist(&storage, &(address, dangling_output_id)).unwrap());
+    }
+
+    #[test]
+    fn ledger_index_missing() {
+        let storage = Storage::new();
+
+        insert_output_diff(&storage, 41);
+        insert_output_diff(&storage, 42);
+
+        repair(&storage);
+
+        assert_eq!(fetch_ledger_index(&storage), Some(LedgerIndex::new(MilestoneIndex(42))));
+    }
+
+    #[test]
+    fn ledger_index_behind_output_diffs() {
+        let storage = Storage::new();
+
+        insert_ledger_index(&storage, 41);
+        insert_output_diff(&storage, 41);
+        insert_output_diff(&storage, 42);
+
+        repair(&storage);
+
+        assert_eq!(fetch_ledger_index(&storage), Some(LedgerIndex::new(MilestoneIndex(42))));
+    }
+
+    #[test]
+    fn ledger_index_ahead_of_output_diffs() {
+        let storage = Storage::new();
+
+        insert_ledger_index(&storage, 43);
+        insert_output_diff(&storage, 41);
+        insert_output_diff(&storage, 42);
+
+        let check = Check {
+            storage: &storage,
+            dry_run: false,
+        };
+
+        assert!(matches!(
+            check.ledger_index(),
+            Err(RepairError::LedgerIndexAheadOfOutputDiffs {
+                ledger_index: MilestoneIndex(43),
+                diff_index: MilestoneIndex(42),
+            })
+        ));
+        assert_eq!(fetch_ledger_index(&storage), Some(LedgerIndex::new(MilestoneIndex(43))));
+    }
+
+    #[test]
+    fn ledger_index_without_output_diffs() {
+        let storage = Storage::new();
+
+        insert_ledger_index(&storage, 42);
+
+        repair(&storage);
+
+        assert_eq!(fetch_ledger_index(&storage), Some(LedgerIndex::new(MilestoneIndex(42))));
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use bee_block::{address::Address, parent::Parents};
+use serde::Deserialize;
+
+const DEFAULT_ENABLED: bool = false;
+const DEFAULT_INTERVAL: u64 = 10;
+// One parent is always the previous milestone.
+const DEFAULT_MAX_TIPS: usize = *Parents::COUNT_RANGE.end() as usize - 1;
+const DEFAULT_RECEIPTS: bool = false;
+const DEFAULT_BOOTSTRAP: bool = false;
+
+/// Builder struct for a funded output of the genesis snapshot.
+#[derive(Default, Deserialize, Eq, PartialEq)]
+pub struct GenesisOutputConfigBuilder {
+    address: String,
+    amount: u64,
+}
+
+/// Builder struct for creating a [`CoordinatorConfig`].
+#[derive(Default, Deserialize, Eq, PartialEq)]
+pub struct CoordinatorConfigBuilder {
+    enabled: Option<bool>,
+    interval: Option<u64>,
+    #[serde(alias = "privateKeys")]
+    private_keys: Option<Vec<String>>,
+    #[serde(alias = "maxTips")]
+    max_tips: Option<usize>,
+    receipts: Option<bool>,
+    bootstrap: Option<bool>,
+    genesis: Option<Vec<GenesisOutputConfigBuilder>>,
+}
+
+impl CoordinatorConfigBuilder {
+    /// Creates a new [`CoordinatorConfigBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables the coordinator.
+    #[must_use]
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled.replace(enabled);
+        self
+    }
+
+    /// Sets the interval between two milestones, in seconds, at least 1.
+    #[must_use]
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval.replace(interval);
+        self
+    }
+
+    /// Sets the hex encoded Ed25519 private keys signing the milestones.
+    #[must_use]
+    pub fn with_private_keys(mut self, private_keys: Vec<String>) -> Self {
+        self.private_keys.replace(private_keys);
+        self
+    }
+
+    /// Sets the maximum number of tips referenced by a milestone, on top of the previous milestone.
+    #[must_use]
+    pub fn with_max_tips(mut self, max_tips: usize) -> Self {
+        self.max_tips.replace(max_tips);
+        self
+    }
+
+    /// Enables or disables the issuance of receipts for queued migrated funds.
+    #[must_use]
+    pub fn with_receipts(mut self, receipts: bool) -> Self {
+        self.receipts.replace(receipts);
+        self
+    }
+
+    /// Enables or disables the bootstrapping of a genesis snapshot.
+    #[must_use]
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap.replace(bootstrap);
+        self
+    }
+
+    /// Adds a funded output, given as a bech32 address and an amount, to the genesis snapshot.
+    #[must_use]
+    pub fn with_genesis_output(mut self, address: String, amount: u64) -> Self {
+        self.genesis
+            .get_or_insert_with(Vec::new)
+            .push(GenesisOutputConfigBuilder { address, amount });
+        self
+    }
+
+    /// Creates a new [`CoordinatorConfig`], consuming the [`CoordinatorConfigBuilder`].
+    pub fn finish(self) -> CoordinatorConfig {
+        let private_keys = self
+            .private_keys
+            .unwrap_or_default()
+            .iter()
+            .map(|key| {
+                let mut bytes = [0u8; 32];
+
+                hex::decode_to_slice(key.strip_prefix("0x").unwrap_or(key), &mut bytes)
+                    .unwrap_or_else(|e| panic!("invalid coordinator private key '{}': {}", key, e));
+
+                bytes
+            })
+            .collect();
+        let genesis = self
+            .genesis
+            .unwrap_or_default()
+            .into_iter()
+            .map(|output| {
+                let (_, address) = Address::try_from_bech32(&output.address)
+                    .unwrap_or_else(|e| panic!("invalid genesis address '{}': {:?}", output.address, e));
+
+                (address, output.amount)
+            })
+            .collect();
+
+        CoordinatorConfig {
+            enabled: self.enabled.unwrap_or(DEFAULT_ENABLED),
+            interval: Duration::from_secs(self.interval.unwrap_or(DEFAULT_INTERVAL).max(1)),
+            private_keys,
+            max_tips: self.max_tips.unwrap_or(DEFAULT_MAX_TIPS).clamp(1, DEFAULT_MAX_TIPS),
+            receipts: self.receipts.unwrap_or(DEFAULT_RECEIPTS),
+            bootstrap: self.bootstrap.unwrap_or(DEFAULT_BOOTSTRAP),
+            genesis,
+        }
+    }
+}
+
+/// Coordinator configuration options.
+#[derive(Clone)]
+pub struct CoordinatorConfig {
+    enabled: bool,
+    interval: Duration,
+    private_keys: Vec<[u8; 32]>,
+    max_tips: usize,
+    receipts: bool,
+    bootstrap: bool,
+    genesis: Vec<(Address, u64)>,
+}
+
+impl CoordinatorConfig {
+    /// Returns a new [`CoordinatorConfigBuilder`].
+    pub fn build() -> CoordinatorConfigBuilder {
+        CoordinatorConfigBuilder::new()
+    }
+
+    /// Returns whether the coordinator is enabled.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the interval between two milestones.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the Ed25519 private keys signing the milestones.
+    pub fn private_keys(&self) -> &[[u8; 32]] {
+        &self.private_keys
+    }
+
+    /// Returns the maximum number of tips referenced by a milestone, on top of the previous milestone.
+    pub fn max_tips(&self) -> usize {
+        self.max_tips
+    }
+
+    /// Returns whether receipts are issued for queued migrated funds.
+    pub fn receipts(&self) -> bool {
+        self.receipts
+    }
+
+    /// Returns whether a genesis snapshot is bootstrapped if no full snapshot exists.
+    pub fn bootstrap(&self) -> bool {
+        self.bootstrap
+    }
+
+    /// Returns the funded outputs of the genesis snapshot.
+    pub fn genesis(&self) -> &[(Address, u64)] {
+        &self.genesis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_tips_leave_room_for_the_previous_milestone() {
+        assert_eq!(CoordinatorConfig::build().finish().max_tips(), 7);
+        assert_eq!(CoordinatorConfig::build().with_max_tips(42).finish().max_tips(), 7);
+        assert_eq!(CoordinatorConfig::build().with_max_tips(0).finish().max_tips(), 1);
+    }
+
+    #[test]
+    fn interval_is_never_zero() {
+        assert_eq!(CoordinatorConfig::build().finish().interval(), Duration::from_secs(10));
+        assert_eq!(
+            CoordinatorConfig::build().with_interval(0).finish().interval(),
+            Duration::from_secs(1)
+        );
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{
+    input::TreasuryInput,
+    output,
+    parent::Parents,
+    payload::{
+        milestone::{
+            option::MigratedFundsEntry, MilestoneEssence, MilestoneId, MilestoneIndex, MilestoneOption,
+            MilestoneOptions, MilestonePayload, ReceiptMilestoneOption,
+        },
+        Payload, TreasuryTransactionPayload,
+    },
+    protocol::ProtocolParameters,
+    signature::{Ed25519Signature, Signature},
+    Block, BlockBuilder, BlockId,
+};
+use bee_ledger::{
+    consensus::{white_flag, WhiteFlagMetadata},
+    storage::fetch_unspent_treasury_output,
+    StorageBackend,
+};
+use bee_protocol::types::milestone_key_manager::MilestoneKeyManager;
+use bee_tangle::Tangle;
+use crypto::signatures::ed25519;
+use packable::PackableExt;
+
+use crate::error::Error;
+
+/// The milestone a new milestone builds upon.
+#[derive(Clone, Debug)]
+pub struct PreviousMilestone {
+    /// The index of the milestone.
+    pub index: MilestoneIndex,
+    /// The id of the milestone.
+    pub milestone_id: MilestoneId,
+    /// The id of the block containing the milestone, none for the genesis.
+    pub block_id: Option<BlockId>,
+    /// The unix timestamp of the milestone, in seconds.
+    pub timestamp: u32,
+}
+
+impl PreviousMilestone {
+    /// Returns the genesis, which the first milestone builds upon.
+    pub fn genesis() -> Self {
+        Self {
+            index: MilestoneIndex(0),
+            milestone_id: MilestoneId::null(),
+            block_id: None,
+            timestamp: 0,
+        }
+    }
+
+    /// Returns the milestone contained in the given block, if any.
+    pub fn from_block(block: &Block) -> Option<Self> {
+        match block.payload() {
+            Some(Payload::Milestone(milestone)) => Some(Self {
+                index: milestone.essence().index(),
+                milestone_id: milestone.id(),
+                block_id: Some(block.id()),
+                timestamp: milestone.essence().timestamp(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the latest milestone known to the tangle, or the genesis if there is none yet.
+    pub fn latest<B: StorageBackend>(tangle: &Tangle<B>) -> Result<Self, Error> {
+        let index = tangle.get_latest_milestone_index();
+
+        match tangle.get_milestone_metadata(index) {
+            Some(metadata) => Ok(Self {
+                index,
+                milestone_id: *metadata.milestone_id(),
+                block_id: Some(*metadata.block_id()),
+                timestamp: metadata.timestamp(),
+            }),
+            None if index == MilestoneIndex(0) => Ok(Self::genesis()),
+            None => Err(Error::MilestoneNotFound(index)),
+        }
+    }
+}
+
+/// Issues milestones signed by locally held keys.
+pub struct Coordinator {
+    // Sorted by public key, as milestone signatures have to be.
+    keys: Vec<ed25519::SecretKey>,
+    key_manager: MilestoneKeyManager,
+}
+
+impl Coordinator {
+    /// Creates a new [`Coordinator`] signing with the given private keys, as far as the key manager allows them.
+    pub fn new(private_keys: &[[u8; 32]], key_manager: MilestoneKeyManager) -> Self {
+        let mut keys = private_keys
+            .iter()
+            .map(|bytes| ed25519::SecretKey::from_bytes(*bytes))
+            .collect::<Vec<_>>();
+
+        keys.sort_by_key(|key| key.public_key().to_bytes());
+
+        Self { keys, key_manager }
+    }
+
+    /// Returns the keys allowed to sign the milestone with the given index, failing if they are not enough.
+    pub fn signing_keys(&self, index: MilestoneIndex) -> Result<Vec<&ed25519::SecretKey>, Error> {
+        let public_keys = self.key_manager.get_public_keys(index);
+        let keys = self
+            .keys
+            .iter()
+            .filter(|key| public_keys.contains(&hex::encode(key.public_key().to_bytes())))
+            .collect::<Vec<_>>();
+
+        if keys.is_empty() || keys.len() < self.key_manager.min_threshold() {
+            return Err(Error::NotEnoughKeys(
+                keys.len(),
+                index,
+                self.key_manager.min_threshold(),
+            ));
+        }
+
+        Ok(keys)
+    }
+
+    /// Builds the milestone following the given previous milestone, referencing the given blocks.
+    ///
+    /// The tangle and the ledger need to be solid up to the given blocks and confirmed up to the previous milestone. If
+    /// migrated funds are given, the milestone carries a receipt for them, funded by the treasury.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn issue<B: StorageBackend>(
+        &self,
+        tangle: &Tangle<B>,
+        storage: &B,
+        protocol_parameters: &ProtocolParameters,
+        previous: &PreviousMilestone,
+        mut parents: Vec<BlockId>,
+        timestamp: u32,
+        mut funds: Vec<MigratedFundsEntry>,
+    ) -> Result<Block, Error> {
+        let index = previous.index + 1;
+        let keys = self.signing_keys(index)?;
+
+        parents.extend(previous.block_id);
+
+        if parents.is_empty() {
+            parents.push(BlockId::null());
+        }
+
+        let parents = Parents::new(parents)?;
+        // Milestone timestamps can't go backwards, even if the local clock does.
+        let timestamp = timestamp.max(previous.timestamp + 1);
+        let mut metadata = WhiteFlagMetadata::new(index, timestamp, Some(previous.milestone_id));
+
+        white_flag(tangle, storage, &parents, &mut metadata).await?;
+
+        let mut options = Vec::new();
+
+        if !funds.is_empty() {
+            let token_supply = protocol_parameters.token_supply();
+            let treasury = fetch_unspent_treasury_output(storage)?;
+            let migrated = funds
+                .iter()
+                .fold(0u64, |migrated, funds| migrated.saturating_add(funds.amount()));
+            let remaining = treasury
+                .inner()
+                .amount()
+                .checked_sub(migrated)
+                .ok_or_else(|| Error::InsufficientTreasury(treasury.inner().amount(), migrated))?;
+
+            // Receipt funds must be sorted by their serialised form.
+            funds.sort_by_cached_key(PackableExt::pack_to_vec);
+
+            let transaction = TreasuryTransactionPayload::new(
+                TreasuryInput::new(*treasury.milestone_id()),
+                output::TreasuryOutput::new(remaining, token_supply)?,
+            )?;
+
+            options.push(MilestoneOption::Receipt(ReceiptMilestoneOption::new(
+                index,
+                true,
+                funds,
+                transaction,
+                token_supply,
+            )?));
+        }
+
+        let essence = MilestoneEssence::new(
+            index,
+            timestamp,
+            protocol_parameters.protocol_version(),
+            previous.milestone_id,
+            parents.clone(),
+            *metadata.inclusion_merkle_root(),
+            *metadata.applied_merkle_root(),
+            Vec::new(),
+            MilestoneOptions::new(options)?,
+        )?;
+        let essence_hash = essence.hash();
+        let signatures = keys
+            .iter()
+            .map(|key| {
+                Signature::Ed25519(Ed25519Signature::new(
+                    key.public_key().to_bytes(),
+                    key.sign(&essence_hash).to_bytes(),
+                ))
+            })
+            .collect();
+        let milestone = MilestonePayload::new(essence, signatures)?;
+
+        // Milestones don't carry any proof of work.
+        Ok(BlockBuilder::<u64>::new(parents)
+            .with_protocol_version(protocol_parameters.protocol_version())
+            .with_payload(Payload::from(milestone))
+            .with_nonce_provider(0)
+            .finish(0)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_protocol::types::milestone_key_range::MilestoneKeyRange;
+
+    use super::*;
+
+    fn public_key(bytes: [u8; 32]) -> String {
+        hex::encode(ed25519::SecretKey::from_bytes(bytes).public_key().to_bytes())
+    }
+
+    #[test]
+    fn signing_keys_follow_key_ranges() {
+        let key_manager = MilestoneKeyManager::new(
+            2,
+            vec![
+                MilestoneKeyRange::new(public_key([1; 32]), MilestoneIndex(0), MilestoneIndex(10)),
+                MilestoneKeyRange::new(public_key([2; 32]), MilestoneIndex(0), MilestoneIndex(0)),
+                MilestoneKeyRange::new(public_key([3; 32]), MilestoneIndex(11), MilestoneIndex(20)),
+            ]
+            .into_boxed_slice(),
+        );
+        let coordinator = Coordinator::new(&[[3; 32], [2; 32], [1; 32]], key_manager);
+
+        let keys = coordinator.signing_keys(MilestoneIndex(5)).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].public_key().to_bytes() < keys[1].public_key().to_bytes());
+        assert_eq!(coordinator.signing_keys(MilestoneIndex(11)).unwrap().len(), 2);
+        assert!(matches!(
+            Coordinator::new(&[[1; 32]], coordinator.key_manager.clone()).signing_keys(MilestoneIndex(5)),
+            Err(Error::NotEnoughKeys(1, MilestoneIndex(5), 2))
+        ));
+    }
+}
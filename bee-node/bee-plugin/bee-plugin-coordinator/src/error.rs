@@ -0,0 +1,32 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::payload::milestone::MilestoneIndex;
+
+/// Errors occurring while issuing milestones or bootstrapping a genesis snapshot.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("block error: {0}")]
+    Block(#[from] bee_block::Error),
+    #[error("ledger error: {0}")]
+    Ledger(Box<bee_ledger::error::Error>),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0} private keys valid for milestone {1}, {2} signatures required")]
+    NotEnoughKeys(usize, MilestoneIndex, usize),
+    #[error("milestone {0} not found")]
+    MilestoneNotFound(MilestoneIndex),
+    #[error("treasury of {0} cannot fund migrations of {1}")]
+    InsufficientTreasury(u64, u64),
+    #[error("genesis outputs of {0} exceed the token supply of {1}")]
+    GenesisExceedsTokenSupply(u128, u64),
+    #[error("block submission failed: {0}")]
+    BlockSubmission(String),
+}
+
+impl From<bee_ledger::error::Error> for Error {
+    fn from(error: bee_ledger::error::Error) -> Self {
+        Self::Ledger(Box::new(error))
+    }
+}
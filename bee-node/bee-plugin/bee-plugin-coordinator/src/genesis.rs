@@ -0,0 +1,204 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use bee_block::{
+    address::Address,
+    output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, OutputId, OUTPUT_COUNT_MAX},
+    payload::{
+        milestone::{MilestoneId, MilestoneIndex},
+        transaction::TransactionId,
+    },
+    protocol::ProtocolParameters,
+    BlockId,
+};
+use bee_ledger::types::{
+    snapshot::{FullSnapshotHeader, SnapshotHeader, SnapshotKind},
+    CreatedOutput,
+};
+use bee_tangle::solid_entry_point::SolidEntryPoint;
+use log::info;
+use packable::{packer::IoPacker, Packable};
+
+use crate::error::Error;
+
+/// Builds the outputs funding the given addresses at milestone 0, along with the part of the token supply that isn't
+/// distributed and is left in the treasury.
+pub fn genesis_outputs(
+    protocol_parameters: &ProtocolParameters,
+    timestamp: u32,
+    funds: &[(Address, u64)],
+) -> Result<(Vec<(OutputId, CreatedOutput)>, u64), Error> {
+    let token_supply = protocol_parameters.token_supply();
+    let distributed = funds.iter().map(|(_, amount)| *amount as u128).sum::<u128>();
+
+    if distributed > token_supply as u128 {
+        return Err(Error::GenesisExceedsTokenSupply(distributed, token_supply));
+    }
+
+    let outputs = funds
+        .iter()
+        .filter(|(_, amount)| *amount != 0)
+        .enumerate()
+        .map(|(index, (address, amount))| {
+            // Outputs are spread over as many fake transactions as needed to keep their indexes in range.
+            let mut transaction_id = [0u8; TransactionId::LENGTH];
+            let transaction_index = (index / OUTPUT_COUNT_MAX as usize) as u32;
+
+            transaction_id[TransactionId::LENGTH - 4..].copy_from_slice(&transaction_index.to_be_bytes());
+
+            let output_id = OutputId::new(
+                TransactionId::new(transaction_id),
+                (index % OUTPUT_COUNT_MAX as usize) as u16,
+            )?;
+            let output = BasicOutputBuilder::new_with_amount(*amount)?
+                .add_unlock_condition(AddressUnlockCondition::new(*address).into())
+                .finish_output(token_supply)?;
+
+            Ok((
+                output_id,
+                CreatedOutput::new(BlockId::null(), MilestoneIndex(0), timestamp, output),
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok((outputs, token_supply - distributed as u64))
+}
+
+/// Writes a genesis full snapshot to the given path.
+///
+/// The snapshot has the null block as only solid entry point and funds the given addresses at milestone 0. The part of
+/// the token supply that isn't distributed is left in the treasury.
+pub fn write_genesis_snapshot(
+    path: &Path,
+    network_id: u64,
+    protocol_parameters: &ProtocolParameters,
+    timestamp: u32,
+    funds: &[(Address, u64)],
+) -> Result<(), Error> {
+    let (outputs, treasury) = genesis_outputs(protocol_parameters, timestamp, funds)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut packer = IoPacker::new(BufWriter::new(File::create(path)?));
+
+    SnapshotHeader::new(
+        SnapshotKind::Full,
+        timestamp,
+        network_id,
+        MilestoneIndex(0),
+        MilestoneIndex(0),
+    )
+    .pack(&mut packer)?;
+    FullSnapshotHeader::new(1, outputs.len() as u64, 0, MilestoneId::null(), treasury).pack(&mut packer)?;
+    SolidEntryPoint::null().pack(&mut packer)?;
+
+    for (output_id, output) in &outputs {
+        output_id.pack(&mut packer)?;
+        output.pack(&mut packer)?;
+    }
+
+    packer.into_inner().flush()?;
+
+    info!(
+        "Wrote genesis snapshot {} with {} outputs.",
+        path.to_string_lossy(),
+        outputs.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::{address::Ed25519Address, output::RentStructure};
+    use packable::unpacker::IoUnpacker;
+
+    use super::*;
+
+    // Differs from the default token supply to make sure the configured protocol parameters are used.
+    fn protocol_parameters() -> ProtocolParameters {
+        ProtocolParameters::new(
+            2,
+            String::from("private-network"),
+            String::from("rms"),
+            0,
+            15,
+            RentStructure::default(),
+            1_000_000_000_000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn genesis_snapshot_leaves_the_rest_in_the_treasury() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshots").join("full_snapshot.bin");
+        let protocol_parameters = protocol_parameters();
+        let funds = (0..200u8)
+            .map(|i| (Address::from(Ed25519Address::new([i; 32])), 1_000_000))
+            .collect::<Vec<_>>();
+
+        write_genesis_snapshot(&path, 42, &protocol_parameters, 1_662_000_000, &funds).unwrap();
+
+        let mut unpacker = IoUnpacker::new(File::open(&path).unwrap());
+        let header = SnapshotHeader::unpack::<_, true>(&mut unpacker, &()).unwrap();
+        let full_header = FullSnapshotHeader::unpack::<_, true>(&mut unpacker, &()).unwrap();
+
+        assert_eq!(header.kind(), SnapshotKind::Full);
+        assert_eq!(header.network_id(), 42);
+        assert_eq!(header.ledger_index(), MilestoneIndex(0));
+        assert_eq!(full_header.sep_count(), 1);
+        assert_eq!(full_header.output_count(), 200);
+        assert_eq!(full_header.milestone_diff_count(), 0);
+        assert_eq!(full_header.treasury_output_amount(), 1_000_000_000_000 - 200_000_000);
+
+        assert_eq!(
+            SolidEntryPoint::unpack::<_, true>(&mut unpacker, &()).unwrap(),
+            SolidEntryPoint::null()
+        );
+
+        let mut output_ids = Vec::new();
+
+        for _ in 0..full_header.output_count() {
+            output_ids.push(OutputId::unpack::<_, true>(&mut unpacker, &()).unwrap());
+            CreatedOutput::unpack::<_, true>(&mut unpacker, &protocol_parameters).unwrap();
+        }
+
+        output_ids.sort();
+        output_ids.dedup();
+
+        assert_eq!(output_ids.len(), 200);
+    }
+
+    #[test]
+    fn genesis_snapshot_exceeding_the_token_supply() {
+        let dir = tempfile::tempdir().unwrap();
+        let protocol_parameters = protocol_parameters();
+        let funds = [
+            (
+                Address::from(Ed25519Address::new([0; 32])),
+                protocol_parameters.token_supply(),
+            ),
+            (Address::from(Ed25519Address::new([1; 32])), 1),
+        ];
+
+        assert!(matches!(
+            write_genesis_snapshot(
+                &dir.path().join("full_snapshot.bin"),
+                42,
+                &protocol_parameters,
+                0,
+                &funds
+            ),
+            Err(Error::GenesisExceedsTokenSupply(..))
+        ));
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coordinator plugin for the Bee node.
+//!
+//! It issues milestones signed with locally held keys, turning a single node into the coordinator of a private network.
+
+#![warn(missing_docs)]
+
+/// Coordinator configuration.
+pub mod config;
+/// Coordinator errors.
+pub mod error;
+/// Coordinator storage.
+pub mod storage;
+
+mod coordinator;
+mod genesis;
+
+use std::any::TypeId;
+
+use async_trait::async_trait;
+use bee_block::{
+    payload::milestone::{option::MigratedFundsEntry, MilestoneIndex},
+    Block, BlockId,
+};
+use bee_ledger::{
+    consensus::ConsensusWorker,
+    parameters::{ProtocolParametersManager, ProtocolParametersWorker},
+};
+use bee_protocol::{
+    config::ProtocolCoordinatorConfig, types::milestone_key_manager::MilestoneKeyManager, BlockSubmitterWorker,
+    BlockSubmitterWorkerEvent,
+};
+use bee_runtime::{
+    node::{Node, NodeBuilder},
+    shutdown_stream::ShutdownStream,
+    worker::Worker,
+};
+use bee_tangle::{Tangle, TangleWorker};
+use futures::{channel::oneshot, StreamExt};
+use log::{debug, error, info, warn};
+use packable::PackableExt;
+use time_helper as time;
+use tokio::{sync::mpsc, time::interval};
+use tokio_stream::wrappers::IntervalStream;
+
+pub use self::{
+    coordinator::{Coordinator, PreviousMilestone},
+    genesis::{genesis_outputs, write_genesis_snapshot},
+};
+use crate::{config::CoordinatorConfig, error::Error, storage::StorageBackend};
+
+// A receipt can't hold more migrated funds than a transaction can hold outputs.
+const RECEIPT_FUNDS_MAX: usize = bee_block::output::OUTPUT_COUNT_MAX as usize;
+
+/// Initializes the coordinator plugin.
+pub fn init<N: Node>(
+    coordinator_config: CoordinatorConfig,
+    protocol_coordinator_config: ProtocolCoordinatorConfig,
+    node_builder: N::Builder,
+) -> N::Builder
+where
+    N::Backend: StorageBackend,
+{
+    node_builder.with_worker_cfg::<CoordinatorPlugin>((coordinator_config, protocol_coordinator_config))
+}
+
+async fn submit(submitter: &mpsc::UnboundedSender<BlockSubmitterWorkerEvent>, block: &Block) -> Result<BlockId, Error> {
+    let (notifier, waiter) = oneshot::channel();
+
+    submitter
+        .send(BlockSubmitterWorkerEvent {
+            block: block.pack_to_vec(),
+            notifier,
+        })
+        .map_err(|e| Error::BlockSubmission(e.to_string()))?;
+
+    match waiter.await {
+        Ok(Ok(block_id)) => Ok(block_id),
+        Ok(Err(e)) => Err(Error::BlockSubmission(e.0)),
+        Err(_) => Err(Error::BlockSubmission(format!("block {} dropped", block.id()))),
+    }
+}
+
+/// Coordinator plugin.
+pub struct CoordinatorPlugin {
+    /// Queues migrated funds to be included in the receipt of an upcoming milestone.
+    pub tx: mpsc::UnboundedSender<MigratedFundsEntry>,
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for CoordinatorPlugin
+where
+    N::Backend: StorageBackend,
+{
+    type Config = (CoordinatorConfig, ProtocolCoordinatorConfig);
+    type Error = Error;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
+            TypeId::of::<ConsensusWorker>(),
+            TypeId::of::<BlockSubmitterWorker>(),
+        ]
+        .leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (config, protocol_coordinator_config) = config;
+        let (tx, mut rx) = mpsc::unbounded_channel::<MigratedFundsEntry>();
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let storage = node.storage();
+        let protocol_parameters = node.resource::<ProtocolParametersManager>();
+        // Panic: the submitter is a dependency of the coordinator.
+        let submitter = node.worker::<BlockSubmitterWorker>().unwrap().tx.clone();
+
+        let coordinator = Coordinator::new(
+            config.private_keys(),
+            MilestoneKeyManager::new(
+                protocol_coordinator_config.public_key_count(),
+                protocol_coordinator_config.public_key_ranges().into(),
+            ),
+        );
+
+        // Misconfigured keys are better reported at startup than at every milestone.
+        coordinator.signing_keys(PreviousMilestone::latest(&tangle)?.index + 1)?;
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(config.interval())));
+            let mut issued = MilestoneIndex(0);
+            let mut funds = Vec::new();
+
+            while ticker.next().await.is_some() {
+                while let Ok(entry) = rx.try_recv() {
+                    if config.receipts() {
+                        funds.push(entry);
+                    } else {
+                        warn!(
+                            "Receipts are disabled, dropping migrated funds {}.",
+                            entry.tail_transaction_hash()
+                        );
+                    }
+                }
+
+                let latest = tangle.get_latest_milestone_index();
+
+                // The next milestone has to build upon the ledger state of the previous one.
+                if latest < issued || tangle.get_confirmed_milestone_index() < latest {
+                    debug!("Waiting for milestone {} to be confirmed.", issued.max(latest));
+                    continue;
+                }
+
+                let previous = match PreviousMilestone::latest(&tangle) {
+                    Ok(previous) => previous,
+                    Err(e) => {
+                        error!("Cannot find the previous milestone: {}.", e);
+                        continue;
+                    }
+                };
+                let index = previous.index + 1;
+                let mut receipt_funds = funds.len().min(RECEIPT_FUNDS_MAX);
+                let timestamp = time::now_utc().unix_timestamp() as u32;
+                let protocol_parameters = protocol_parameters.current();
+                let mut parents = tangle.get_blocks_to_approve().await.unwrap_or_default();
+
+                parents.truncate(config.max_tips());
+
+                let issued_block = coordinator
+                    .issue(
+                        &tangle,
+                        &*storage,
+                        &protocol_parameters,
+                        &previous,
+                        parents.clone(),
+                        timestamp,
+                        funds[..receipt_funds].to_vec(),
+                    )
+                    .await;
+
+                // The treasury may still be funded later on, the migrated funds are kept until then and the milestone
+                // is issued without a receipt in the meantime.
+                let issued_block = match issued_block {
+                    Err(Error::InsufficientTreasury(treasury, migrated)) => {
+                        warn!(
+                            "Milestone {} carries no receipt, treasury of {} cannot fund migrations of {}.",
+                            index, treasury, migrated
+                        );
+                        receipt_funds = 0;
+                        coordinator
+                            .issue(
+                                &tangle,
+                                &*storage,
+                                &protocol_parameters,
+                                &previous,
+                                parents,
+                                timestamp,
+                                Vec::new(),
+                            )
+                            .await
+                    }
+                    issued_block => issued_block,
+                };
+
+                let block = match issued_block {
+                    Ok(block) => block,
+                    Err(e @ Error::Block(_)) if receipt_funds != 0 => {
+                        error!(
+                            "Issuing milestone {} failed: {}, dropping {} migrated funds.",
+                            index, e, receipt_funds
+                        );
+                        funds.drain(..receipt_funds);
+                        continue;
+                    }
+                    Err(e) => {
+                        error!("Issuing milestone {} failed: {}.", index, e);
+                        continue;
+                    }
+                };
+
+                match submit(&submitter, &block).await {
+                    Ok(block_id) => {
+                        info!("Issued milestone {} in block {}.", index, block_id);
+                        funds.drain(..receipt_funds);
+                        issued = index;
+                    }
+                    Err(e) => error!("Submitting milestone {} failed: {}.", index, e),
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
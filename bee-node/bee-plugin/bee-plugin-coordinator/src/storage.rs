@@ -0,0 +1,10 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_ledger::StorageBackend as LedgerStorageBackend;
+use bee_protocol::storage::StorageBackend as ProtocolStorageBackend;
+
+/// Storage operations required by the coordinator plugin.
+pub trait StorageBackend: LedgerStorageBackend + ProtocolStorageBackend {}
+
+impl<T> StorageBackend for T where T: LedgerStorageBackend + ProtocolStorageBackend {}
@@ -35,6 +35,7 @@ use bee_runtime::{
     shutdown_stream::ShutdownStream,
     worker::Worker,
 };
+use bee_storage_instrumented::event::StorageMetricsUpdated;
 use bee_tangle::{event::LatestMilestoneChanged, Tangle, TangleWorker};
 use futures::stream::StreamExt;
 use log::{debug, error, info};
@@ -195,6 +196,13 @@ where
         );
         topic_handler(node, "TipInfo", &users, true, <WsEvent as From<TipAdded>>::from);
         topic_handler(node, "TipInfo", &users, true, <WsEvent as From<TipRemoved>>::from);
+        topic_handler(
+            node,
+            "DatabaseTableMetrics",
+            &users,
+            false,
+            <WsEvent as From<StorageMetricsUpdated>>::from,
+        );
 
         // run sub-workers
         confirmed_ms_metrics_worker(node, &users);
@@ -0,0 +1,77 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_storage_instrumented::event::StorageMetricsUpdated;
+use serde::Serialize;
+
+use crate::websocket::{
+    responses::{WsEvent, WsEventInner},
+    topics::WsTopic,
+};
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DatabaseTableMetricsResponse {
+    #[serde(rename = "intervalMillis")]
+    pub interval_millis: u64,
+    pub tables: Vec<DatabaseTableMetricsDto>,
+    #[serde(rename = "backendStatistics", skip_serializing_if = "Option::is_none")]
+    pub backend_statistics: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DatabaseTableMetricsDto {
+    pub table: String,
+    pub operations: Vec<DatabaseOperationMetricsDto>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DatabaseOperationMetricsDto {
+    pub operation: &'static str,
+    pub count: u64,
+    pub errors: u64,
+    pub items: u64,
+    #[serde(rename = "perSecond")]
+    pub per_second: f64,
+    #[serde(rename = "avgLatencyMicros")]
+    pub avg_latency_micros: u64,
+    #[serde(rename = "maxLatencyMicros")]
+    pub max_latency_micros: u64,
+}
+
+impl From<StorageMetricsUpdated> for WsEvent {
+    fn from(event: StorageMetricsUpdated) -> Self {
+        Self::new(
+            WsTopic::DatabaseTableMetrics,
+            WsEventInner::DatabaseTableMetrics(event.into()),
+        )
+    }
+}
+
+impl From<StorageMetricsUpdated> for DatabaseTableMetricsResponse {
+    fn from(event: StorageMetricsUpdated) -> Self {
+        Self {
+            interval_millis: event.interval.as_millis() as u64,
+            tables: event
+                .tables
+                .iter()
+                .map(|table| DatabaseTableMetricsDto {
+                    table: table.table.name(),
+                    operations: table
+                        .operations
+                        .iter()
+                        .map(|(operation, metrics)| DatabaseOperationMetricsDto {
+                            operation: operation.as_str(),
+                            count: metrics.count,
+                            errors: metrics.errors,
+                            items: metrics.items,
+                            per_second: metrics.throughput(event.interval),
+                            avg_latency_micros: metrics.average_latency().as_micros() as u64,
+                            max_latency_micros: metrics.max_latency.as_micros() as u64,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            backend_statistics: event.backend_statistics,
+        }
+    }
+}
@@ -4,6 +4,7 @@
 pub(crate) mod confirmed_info;
 pub(crate) mod confirmed_milestone_metrics;
 pub(crate) mod database_size_metrics;
+pub(crate) mod database_table_metrics;
 pub(crate) mod milestone;
 pub(crate) mod milestone_info;
 pub(crate) mod mps_metrics_updated;
@@ -20,10 +21,11 @@ use serde::Serialize;
 
 use self::{
     confirmed_info::ConfirmedInfoResponse, confirmed_milestone_metrics::ConfirmedMilestoneMetricsResponse,
-    database_size_metrics::DatabaseSizeMetricsResponse, milestone::MilestoneResponse,
-    milestone_info::MilestoneInfoResponse, mps_metrics_updated::MpsMetricsUpdatedResponse,
-    node_status::NodeStatusResponse, public_node_status::PublicNodeStatusResponse, solid_info::SolidInfoResponse,
-    sync_status::SyncStatusResponse, tip_info::TipInfoResponse, vertex::VertexResponse,
+    database_size_metrics::DatabaseSizeMetricsResponse, database_table_metrics::DatabaseTableMetricsResponse,
+    milestone::MilestoneResponse, milestone_info::MilestoneInfoResponse,
+    mps_metrics_updated::MpsMetricsUpdatedResponse, node_status::NodeStatusResponse,
+    public_node_status::PublicNodeStatusResponse, solid_info::SolidInfoResponse, sync_status::SyncStatusResponse,
+    tip_info::TipInfoResponse, vertex::VertexResponse,
 };
 use crate::websocket::topics::WsTopic;
 
@@ -53,6 +55,7 @@ pub(crate) enum WsEventInner {
     MilestoneInfo(MilestoneInfoResponse),
     Vertex(VertexResponse),
     DatabaseSizeMetrics(DatabaseSizeMetricsResponse),
+    DatabaseTableMetrics(DatabaseTableMetricsResponse),
     TipInfo(TipInfoResponse),
     PublicNodeStatus(PublicNodeStatusResponse),
     NodeStatus(Box<NodeStatusResponse>), // `NodeStatusResponse` is much larger than the rest.
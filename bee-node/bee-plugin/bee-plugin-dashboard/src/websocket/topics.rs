@@ -23,6 +23,7 @@ pub(crate) enum WsTopic {
     DatabaseCleanupEvent = 14,
     SpamMetrics = 15,
     AverageSpamMetrics = 16,
+    DatabaseTableMetrics = 17,
 }
 
 impl TryFrom<u8> for WsTopic {
@@ -47,6 +48,7 @@ impl TryFrom<u8> for WsTopic {
             14 => Ok(WsTopic::DatabaseCleanupEvent),
             15 => Ok(WsTopic::SpamMetrics),
             16 => Ok(WsTopic::AverageSpamMetrics),
+            17 => Ok(WsTopic::DatabaseTableMetrics),
             _ => Err(val),
         }
     }
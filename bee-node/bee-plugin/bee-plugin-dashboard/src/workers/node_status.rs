@@ -51,6 +51,7 @@ pub(crate) fn node_status_worker<N>(
         let uptime = Instant::now();
 
         while ticker.next().await.is_some() {
+            let cache_metrics = tangle.cache_metrics();
             let public_node_status = PublicNodeStatus {
                 snapshot_index: *tangle.get_snapshot_index(),
                 pruning_index: *tangle.get_pruning_index(),
@@ -103,9 +104,22 @@ pub(crate) fn node_status_worker<N>(
                 },
                 caches: Caches {
                     request_queue: RequestQueue { size: 0 },
-                    children: Children { size: 0 },
+                    children: Children {
+                        size: cache_metrics.children.len,
+                        hits: cache_metrics.children.hits,
+                        misses: cache_metrics.children.misses,
+                    },
                     milestones: Milestones { size: 0 },
-                    blocks: Blocks { size: 0 },
+                    blocks: Blocks {
+                        size: cache_metrics.blocks.len,
+                        hits: cache_metrics.blocks.hits,
+                        misses: cache_metrics.blocks.misses,
+                    },
+                    block_metadata: BlockMetadata {
+                        size: cache_metrics.metadata.len,
+                        hits: cache_metrics.metadata.hits,
+                        misses: cache_metrics.metadata.misses,
+                    },
                     incoming_block_work_units: IncomingBlockWorkUnits { size: 0 },
                 },
             };
@@ -185,6 +199,7 @@ pub struct Caches {
     pub children: Children,
     pub milestones: Milestones,
     pub blocks: Blocks,
+    pub block_metadata: BlockMetadata,
     pub incoming_block_work_units: IncomingBlockWorkUnits,
 }
 
@@ -196,6 +211,8 @@ pub struct RequestQueue {
 #[derive(Clone, Debug, Serialize)]
 pub struct Children {
     pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -206,6 +223,15 @@ pub struct Milestones {
 #[derive(Clone, Debug, Serialize)]
 pub struct Blocks {
     pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockMetadata {
+    pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -20,6 +20,14 @@ pub struct PeerMetrics {
     blocks_sent: AtomicU64,
     block_requests_sent: AtomicU64,
     heartbeats_sent: AtomicU64,
+    milestone_request_bytes_received: AtomicU64,
+    block_bytes_received: AtomicU64,
+    block_request_bytes_received: AtomicU64,
+    heartbeat_bytes_received: AtomicU64,
+    milestone_request_bytes_sent: AtomicU64,
+    block_bytes_sent: AtomicU64,
+    block_request_bytes_sent: AtomicU64,
+    heartbeat_bytes_sent: AtomicU64,
 }
 
 impl PeerMetrics {
@@ -142,6 +150,102 @@ impl PeerMetrics {
     pub fn heartbeats_sent_inc(&self) -> u64 {
         self.heartbeats_sent.fetch_add(1, Ordering::SeqCst)
     }
+
+    /// Returns the number of bytes of received milestone request packets of the `PeerMetrics`.
+    pub fn milestone_request_bytes_received(&self) -> u64 {
+        self.milestone_request_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of received milestone request packets of the `PeerMetrics`.
+    pub fn milestone_request_bytes_received_add(&self, bytes: u64) -> u64 {
+        self.milestone_request_bytes_received.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of received block packets of the `PeerMetrics`.
+    pub fn block_bytes_received(&self) -> u64 {
+        self.block_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of received block packets of the `PeerMetrics`.
+    pub fn block_bytes_received_add(&self, bytes: u64) -> u64 {
+        self.block_bytes_received.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of received block request packets of the `PeerMetrics`.
+    pub fn block_request_bytes_received(&self) -> u64 {
+        self.block_request_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of received block request packets of the `PeerMetrics`.
+    pub fn block_request_bytes_received_add(&self, bytes: u64) -> u64 {
+        self.block_request_bytes_received.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of received heartbeat packets of the `PeerMetrics`.
+    pub fn heartbeat_bytes_received(&self) -> u64 {
+        self.heartbeat_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of received heartbeat packets of the `PeerMetrics`.
+    pub fn heartbeat_bytes_received_add(&self, bytes: u64) -> u64 {
+        self.heartbeat_bytes_received.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of all received packets of the `PeerMetrics`.
+    pub fn bytes_received(&self) -> u64 {
+        self.milestone_request_bytes_received()
+            + self.block_bytes_received()
+            + self.block_request_bytes_received()
+            + self.heartbeat_bytes_received()
+    }
+
+    /// Returns the number of bytes of sent milestone request packets of the `PeerMetrics`.
+    pub fn milestone_request_bytes_sent(&self) -> u64 {
+        self.milestone_request_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of sent milestone request packets of the `PeerMetrics`.
+    pub fn milestone_request_bytes_sent_add(&self, bytes: u64) -> u64 {
+        self.milestone_request_bytes_sent.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of sent block packets of the `PeerMetrics`.
+    pub fn block_bytes_sent(&self) -> u64 {
+        self.block_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of sent block packets of the `PeerMetrics`.
+    pub fn block_bytes_sent_add(&self, bytes: u64) -> u64 {
+        self.block_bytes_sent.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of sent block request packets of the `PeerMetrics`.
+    pub fn block_request_bytes_sent(&self) -> u64 {
+        self.block_request_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of sent block request packets of the `PeerMetrics`.
+    pub fn block_request_bytes_sent_add(&self, bytes: u64) -> u64 {
+        self.block_request_bytes_sent.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of sent heartbeat packets of the `PeerMetrics`.
+    pub fn heartbeat_bytes_sent(&self) -> u64 {
+        self.heartbeat_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Adds to the number of bytes of sent heartbeat packets of the `PeerMetrics`.
+    pub fn heartbeat_bytes_sent_add(&self, bytes: u64) -> u64 {
+        self.heartbeat_bytes_sent.fetch_add(bytes, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes of all sent packets of the `PeerMetrics`.
+    pub fn bytes_sent(&self) -> u64 {
+        self.milestone_request_bytes_sent()
+            + self.block_bytes_sent()
+            + self.block_request_bytes_sent()
+            + self.heartbeat_bytes_sent()
+    }
 }
 
 #[cfg(test)]
@@ -208,4 +312,24 @@ mod tests {
         assert_eq!(metrics.block_requests_sent(), 1);
         assert_eq!(metrics.heartbeats_sent(), 1);
     }
+
+    #[test]
+    fn peer_metrics_bytes() {
+        let metrics = PeerMetrics::default();
+
+        assert_eq!(metrics.bytes_received(), 0);
+        assert_eq!(metrics.bytes_sent(), 0);
+
+        metrics.milestone_request_bytes_received_add(1);
+        metrics.block_bytes_received_add(2);
+        metrics.block_request_bytes_received_add(3);
+        metrics.heartbeat_bytes_received_add(4);
+        metrics.block_bytes_sent_add(5);
+        metrics.heartbeat_bytes_sent_add(6);
+
+        assert_eq!(metrics.block_bytes_received(), 2);
+        assert_eq!(metrics.bytes_received(), 10);
+        assert_eq!(metrics.block_request_bytes_sent(), 0);
+        assert_eq!(metrics.bytes_sent(), 11);
+    }
 }
@@ -4,8 +4,11 @@
 //! A module that provides a type describing peers.
 
 use std::{
-    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
+        RwLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use bee_block::payload::milestone::MilestoneIndex;
@@ -14,6 +17,66 @@ use bee_gossip::{Multiaddr, PeerId, PeerInfo, PeerRelation};
 use crate::metrics::PeerMetrics;
 
 const SYNCED_THRESHOLD: u32 = 2;
+// Weight of the latest sample in the success rate and latency averages.
+const RESPONSIVENESS_SMOOTHING: f64 = 0.2;
+// Average latency, in milliseconds, halving the responsiveness of a peer.
+const RESPONSIVENESS_LATENCY: f64 = 1000.0;
+// Time, in milliseconds, after which the recorded responsiveness of a peer is halfway back to neutral, so that peers
+// that answered poorly in the past are eventually tried again.
+const RESPONSIVENESS_HALF_LIFE: f64 = 60_000.0;
+// Protocol version assumed until a peer announces the versions it supports.
+const LEGACY_PROTOCOL_VERSION: u8 = 1;
+
+/// Capabilities announced by a peer in its versioned heartbeat.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerCapabilities {
+    node_version: String,
+    packet_types: Vec<u8>,
+    serves_fast_sync: bool,
+    snapshot_index: MilestoneIndex,
+}
+
+impl PeerCapabilities {
+    /// Creates a new `PeerCapabilities`.
+    pub fn new(
+        node_version: String,
+        packet_types: Vec<u8>,
+        serves_fast_sync: bool,
+        snapshot_index: MilestoneIndex,
+    ) -> Self {
+        Self {
+            node_version,
+            packet_types,
+            serves_fast_sync,
+            snapshot_index,
+        }
+    }
+
+    /// Returns the name and version of the node software of the peer.
+    pub fn node_version(&self) -> &str {
+        &self.node_version
+    }
+
+    /// Returns the types of the packets supported by the peer.
+    pub fn packet_types(&self) -> &[u8] {
+        &self.packet_types
+    }
+
+    /// Returns whether the peer supports a given packet type.
+    pub fn supports_packet_type(&self, packet_type: u8) -> bool {
+        self.packet_types.contains(&packet_type)
+    }
+
+    /// Returns whether the peer serves milestone cones in bulk over sync streams.
+    pub fn serves_fast_sync(&self) -> bool {
+        self.serves_fast_sync
+    }
+
+    /// Returns the snapshot index of the peer.
+    pub fn snapshot_index(&self) -> MilestoneIndex {
+        self.snapshot_index
+    }
+}
 
 /// A type holding information related to a peer.
 pub struct Peer {
@@ -28,6 +91,11 @@ pub struct Peer {
     synced_peers: AtomicU8,
     heartbeat_sent_timestamp: AtomicU64,
     heartbeat_received_timestamp: AtomicU64,
+    success_rate: AtomicU64,
+    average_latency: AtomicU64,
+    responsiveness_timestamp: AtomicU64,
+    protocol_version: AtomicU8,
+    capabilities: RwLock<Option<PeerCapabilities>>,
 }
 
 impl Peer {
@@ -45,6 +113,11 @@ impl Peer {
             synced_peers: AtomicU8::new(0),
             heartbeat_sent_timestamp: AtomicU64::new(0),
             heartbeat_received_timestamp: AtomicU64::new(0),
+            success_rate: AtomicU64::new(1f64.to_bits()),
+            average_latency: AtomicU64::new(0f64.to_bits()),
+            responsiveness_timestamp: AtomicU64::new(0),
+            protocol_version: AtomicU8::new(LEGACY_PROTOCOL_VERSION),
+            capabilities: RwLock::new(None),
         }
     }
 
@@ -165,6 +238,80 @@ impl Peer {
         self.heartbeat_received_timestamp.load(Ordering::Relaxed)
     }
 
+    /// Records that the `Peer` answered one of our requests after the given latency.
+    pub fn record_response(&self, latency: Duration) {
+        self.record(1.0, Some(latency.as_secs_f64() * 1000.0));
+    }
+
+    /// Records that the `Peer` didn't answer one of our requests in time.
+    pub fn record_timeout(&self) {
+        self.record(0.0, None);
+    }
+
+    // Moves the success rate and average latency, decayed so far, towards the samples.
+    fn record(&self, success: f64, latency: Option<f64>) {
+        let success_rate = self.success_rate();
+        let average_latency = self.average_latency();
+
+        self.success_rate.store(
+            (success_rate + RESPONSIVENESS_SMOOTHING * (success - success_rate)).to_bits(),
+            Ordering::Relaxed,
+        );
+        self.average_latency.store(
+            latency
+                .map_or(average_latency, |latency| {
+                    average_latency + RESPONSIVENESS_SMOOTHING * (latency - average_latency)
+                })
+                .to_bits(),
+            Ordering::Relaxed,
+        );
+        self.responsiveness_timestamp.store(now_millis(), Ordering::Relaxed);
+    }
+
+    // Returns the weight, between 0 and 1, the recorded responsiveness still has over the neutral one.
+    fn responsiveness_weight(&self) -> f64 {
+        let elapsed = now_millis().saturating_sub(self.responsiveness_timestamp.load(Ordering::Relaxed));
+
+        0.5f64.powf(elapsed as f64 / RESPONSIVENESS_HALF_LIFE)
+    }
+
+    /// Returns the rate, between 0 and 1, at which the `Peer` recently answered our requests, going back to 1 over
+    /// time.
+    pub fn success_rate(&self) -> f64 {
+        1.0 - self.responsiveness_weight() * (1.0 - f64::from_bits(self.success_rate.load(Ordering::Relaxed)))
+    }
+
+    /// Returns the recent average latency, in milliseconds, of the `Peer` answering our requests, going back to 0 over
+    /// time.
+    pub fn average_latency(&self) -> f64 {
+        self.responsiveness_weight() * f64::from_bits(self.average_latency.load(Ordering::Relaxed))
+    }
+
+    /// Returns the responsiveness of the `Peer`, between 0 and 1, combining its success rate and average latency.
+    pub fn responsiveness(&self) -> f64 {
+        self.success_rate() / (1.0 + self.average_latency() / RESPONSIVENESS_LATENCY)
+    }
+
+    /// Sets the protocol version negotiated with the `Peer`.
+    pub fn set_protocol_version(&self, version: u8) {
+        self.protocol_version.store(version, Ordering::Relaxed);
+    }
+
+    /// Returns the protocol version negotiated with the `Peer`, 1 until it announced the versions it supports.
+    pub fn protocol_version(&self) -> u8 {
+        self.protocol_version.load(Ordering::Relaxed)
+    }
+
+    /// Sets the capabilities announced by the `Peer`.
+    pub fn set_capabilities(&self, capabilities: Option<PeerCapabilities>) {
+        *self.capabilities.write().expect("Poisoned lock") = capabilities;
+    }
+
+    /// Returns the capabilities announced by the `Peer`, if any.
+    pub fn capabilities(&self) -> Option<PeerCapabilities> {
+        self.capabilities.read().expect("Poisoned lock").clone()
+    }
+
     /// Returns whether the `Peer` is synced or not.
     pub fn is_synced(&self) -> bool {
         self.is_synced_threshold(SYNCED_THRESHOLD)
@@ -187,3 +334,72 @@ impl Peer {
         index > self.pruned_index() && index <= self.latest_milestone_index() + MilestoneIndex(1)
     }
 }
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock may have gone backwards")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> Peer {
+        Peer::new(
+            PeerId::random(),
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: "peer".to_string(),
+                relation: PeerRelation::Known,
+            },
+        )
+    }
+
+    #[test]
+    fn responsiveness_is_neutral_initially() {
+        let peer = peer();
+
+        assert_eq!(peer.success_rate(), 1.0);
+        assert_eq!(peer.average_latency(), 0.0);
+        assert_eq!(peer.responsiveness(), 1.0);
+    }
+
+    #[test]
+    fn responsiveness_follows_responses() {
+        let peer = peer();
+
+        peer.record_timeout();
+        assert!((peer.success_rate() - 0.8).abs() < 1e-3);
+
+        peer.record_response(Duration::from_millis(1000));
+        assert!((peer.success_rate() - 0.84).abs() < 1e-3);
+        assert!((peer.average_latency() - 200.0).abs() < 1.0);
+        assert!(peer.responsiveness() < 0.8);
+    }
+
+    #[test]
+    fn responsiveness_decays_to_neutral() {
+        let peer = peer();
+
+        for _ in 0..10 {
+            peer.record_timeout();
+        }
+        peer.record_response(Duration::from_millis(5000));
+
+        let success_rate = f64::from_bits(peer.success_rate.load(Ordering::Relaxed));
+        let average_latency = f64::from_bits(peer.average_latency.load(Ordering::Relaxed));
+
+        // As if the last response was a half-life ago.
+        peer.responsiveness_timestamp
+            .fetch_sub(RESPONSIVENESS_HALF_LIFE as u64, Ordering::Relaxed);
+
+        assert!((peer.success_rate() - (1.0 - (1.0 - success_rate) / 2.0)).abs() < 1e-3);
+        assert!((peer.average_latency() - average_latency / 2.0).abs() < 1.0);
+
+        peer.responsiveness_timestamp.store(0, Ordering::Relaxed);
+
+        assert!((peer.responsiveness() - 1.0).abs() < 1e-9);
+    }
+}
@@ -5,17 +5,24 @@ mod hash_cache;
 mod hasher;
 mod payload;
 mod processor;
+mod promoter;
 mod submitter;
 mod unreferenced_inserter;
 
-pub use self::submitter::{BlockSubmitterError, BlockSubmitterWorker, BlockSubmitterWorkerEvent};
 pub(crate) use self::{
     hash_cache::HashCache,
     hasher::{HasherWorker, HasherWorkerEvent},
     payload::{
-        MilestonePayloadWorker, PayloadWorker, PayloadWorkerEvent, TaggedDataPayloadWorker,
+        validate_milestone, MilestonePayloadWorker, PayloadWorker, PayloadWorkerEvent, TaggedDataPayloadWorker,
         TaggedDataPayloadWorkerEvent, TransactionPayloadWorker,
     },
     processor::{ProcessorWorker, ProcessorWorkerEvent},
     unreferenced_inserter::{UnreferencedBlockInserterWorker, UnreferencedBlockInserterWorkerEvent},
 };
+pub use self::{
+    promoter::{
+        promotion_state, BlockPromoterCommand, BlockPromoterError, BlockPromoterWorker, BlockPromoterWorkerEvent,
+        PromotionState,
+    },
+    submitter::{BlockSubmitterError, BlockSubmitterWorker, BlockSubmitterWorkerEvent},
+};
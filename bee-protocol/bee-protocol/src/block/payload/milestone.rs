@@ -11,7 +11,13 @@ use bee_block::{
     },
     Block, BlockId,
 };
-use bee_runtime::{event::Bus, node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_gossip::PeerId;
+use bee_runtime::{
+    event::Bus,
+    node::{Node, NodeInfo},
+    shutdown_stream::ShutdownStream,
+    worker::Worker,
+};
 use bee_tangle::{event::LatestMilestoneChanged, milestone_metadata::MilestoneMetadata, Tangle, TangleWorker};
 use futures::{future::FutureExt, stream::StreamExt};
 use log::{debug, error, info};
@@ -37,13 +43,14 @@ pub(crate) enum Error {
 pub(crate) struct MilestonePayloadWorkerEvent {
     pub(crate) block_id: BlockId,
     pub(crate) block: Block,
+    pub(crate) from: Option<PeerId>,
 }
 
 pub(crate) struct MilestonePayloadWorker {
     pub(crate) tx: mpsc::UnboundedSender<MilestonePayloadWorkerEvent>,
 }
 
-fn validate(
+pub(crate) fn validate(
     block_id: BlockId,
     block: &Block,
     milestone: &MilestonePayload,
@@ -75,11 +82,13 @@ fn process<B: StorageBackend>(
     tangle: &Tangle<B>,
     block_id: BlockId,
     block: Block,
+    from: Option<PeerId>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     requested_milestones: &RequestedMilestones,
     milestone_solidifier: &mpsc::UnboundedSender<MilestoneSolidifierWorkerEvent>,
     key_manager: &MilestoneKeyManager,
+    node_info: &NodeInfo,
     bus: &Bus<'static>,
 ) {
     if let Some(Payload::Milestone(milestone_payload)) = block.payload() {
@@ -98,7 +107,7 @@ fn process<B: StorageBackend>(
                     info!("New milestone {} {}.", index, milestone_metadata.block_id());
                     tangle.update_latest_milestone_index(index);
 
-                    broadcast_heartbeat(tangle, peer_manager, metrics);
+                    broadcast_heartbeat(tangle, peer_manager, metrics, node_info);
 
                     bus.dispatch(LatestMilestoneChanged {
                         index,
@@ -108,7 +117,12 @@ fn process<B: StorageBackend>(
                     debug!("New milestone {} {}.", *index, milestone_metadata.block_id());
                 }
 
-                requested_milestones.remove(&index);
+                // Only the peer the milestone was requested from is credited, and only if it sent it.
+                if let Some(request) = requested_milestones.remove(&index) {
+                    if from.is_some() && from == request.peer_id {
+                        request.answered(peer_manager);
+                    }
+                }
 
                 if let Err(e) = milestone_solidifier.send(MilestoneSolidifierWorkerEvent(index)) {
                     error!("Sending solidification event failed: {}.", e);
@@ -153,6 +167,7 @@ where
             config.coordinator.public_key_count,
             config.coordinator.public_key_ranges.into_boxed_slice(),
         );
+        let node_info = node.info();
         let bus = node.bus();
         let (tx, rx) = mpsc::unbounded_channel();
 
@@ -161,16 +176,18 @@ where
 
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
-            while let Some(MilestonePayloadWorkerEvent { block_id, block }) = receiver.next().await {
+            while let Some(MilestonePayloadWorkerEvent { block_id, block, from }) = receiver.next().await {
                 process(
                     &tangle,
                     block_id,
                     block,
+                    from,
                     &peer_manager,
                     &metrics,
                     &requested_milestones,
                     &milestone_solidifier,
                     &key_manager,
+                    &node_info,
                     &bus,
                 );
             }
@@ -181,16 +198,19 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(MilestonePayloadWorkerEvent { block_id, block })) = receiver.next().now_or_never() {
+            while let Some(Some(MilestonePayloadWorkerEvent { block_id, block, from })) = receiver.next().now_or_never()
+            {
                 process(
                     &tangle,
                     block_id,
                     block,
+                    from,
                     &peer_manager,
                     &metrics,
                     &requested_milestones,
                     &milestone_solidifier,
                     &key_manager,
+                    &node_info,
                     &bus,
                 );
                 count += 1;
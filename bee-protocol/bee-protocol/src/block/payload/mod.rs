@@ -9,6 +9,7 @@ use std::{any::TypeId, convert::Infallible};
 
 use async_trait::async_trait;
 use bee_block::{payload::Payload, Block, BlockId};
+use bee_gossip::PeerId;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use futures::{future::FutureExt, stream::StreamExt};
 use log::{debug, error, info};
@@ -16,7 +17,7 @@ use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub(crate) use self::{
-    milestone::{MilestonePayloadWorker, MilestonePayloadWorkerEvent},
+    milestone::{validate as validate_milestone, MilestonePayloadWorker, MilestonePayloadWorkerEvent},
     tagged_data::{TaggedDataPayloadWorker, TaggedDataPayloadWorkerEvent},
     transaction::{TransactionPayloadWorker, TransactionPayloadWorkerEvent},
 };
@@ -25,6 +26,7 @@ use crate::storage::StorageBackend;
 pub(crate) struct PayloadWorkerEvent {
     pub(crate) block_id: BlockId,
     pub(crate) block: Block,
+    pub(crate) from: Option<PeerId>,
 }
 
 pub(crate) struct PayloadWorker {
@@ -34,6 +36,7 @@ pub(crate) struct PayloadWorker {
 fn process(
     block_id: BlockId,
     block: Block,
+    from: Option<PeerId>,
     transaction_payload_worker: &mpsc::UnboundedSender<TransactionPayloadWorkerEvent>,
     milestone_payload_worker: &mpsc::UnboundedSender<MilestonePayloadWorkerEvent>,
     tagged_data_payload_worker: &mpsc::UnboundedSender<TaggedDataPayloadWorkerEvent>,
@@ -49,7 +52,7 @@ fn process(
         }
         Some(Payload::Milestone(_)) => {
             if milestone_payload_worker
-                .send(MilestonePayloadWorkerEvent { block_id, block })
+                .send(MilestonePayloadWorkerEvent { block_id, block, from })
                 .is_err()
             {
                 error!("Sending block {} to milestone payload worker failed.", block_id);
@@ -96,10 +99,11 @@ where
 
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
-            while let Some(PayloadWorkerEvent { block_id, block }) = receiver.next().await {
+            while let Some(PayloadWorkerEvent { block_id, block, from }) = receiver.next().await {
                 process(
                     block_id,
                     block,
+                    from,
                     &transaction_payload_worker,
                     &milestone_payload_worker,
                     &tagged_data_payload_worker,
@@ -112,10 +116,11 @@ where
             let (_, mut receiver) = receiver.split();
             let mut count: usize = 0;
 
-            while let Some(Some(PayloadWorkerEvent { block_id, block })) = receiver.next().now_or_never() {
+            while let Some(Some(PayloadWorkerEvent { block_id, block, from })) = receiver.next().now_or_never() {
                 process(
                     block_id,
                     block,
+                    from,
                     &transaction_payload_worker,
                     &milestone_payload_worker,
                     &tagged_data_payload_worker,
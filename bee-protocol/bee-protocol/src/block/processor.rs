@@ -6,6 +6,7 @@ use std::{any::TypeId, convert::Infallible, time::Instant};
 use async_trait::async_trait;
 use bee_block::{payload::Payload, Block, BlockId};
 use bee_gossip::PeerId;
+use bee_ledger::parameters::{ProtocolParametersManager, ProtocolParametersWorker};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{block_metadata::BlockMetadata, Tangle, TangleWorker};
 use futures::{channel::oneshot::Sender, stream::StreamExt};
@@ -18,12 +19,14 @@ use crate::{
     event::{BlockProcessed, VertexCreated},
     packets::BlockPacket,
     peer::PeerManager,
+    penalizer::{penalize, Misbehaviour},
     requester::request_block,
     storage::StorageBackend,
     types::metrics::NodeMetrics,
-    BlockRequesterWorker, BlockSubmitterError, BroadcasterWorker, BroadcasterWorkerEvent, MetricsWorker, PayloadWorker,
-    PayloadWorkerEvent, PeerManagerResWorker, PropagatorWorker, PropagatorWorkerEvent, RequestedBlocks,
-    UnreferencedBlockInserterWorker, UnreferencedBlockInserterWorkerEvent,
+    BlockRequesterWorker, BlockSubmitterError, BroadcasterWorker, BroadcasterWorkerEvent, MetricsWorker,
+    MilestoneRequesterWorker, PayloadWorker, PayloadWorkerEvent, PeerManagerResWorker, PenalizerWorker,
+    PropagatorWorker, PropagatorWorkerEvent, RequestedBlocks, RequestedMilestones, UnreferencedBlockInserterWorker,
+    UnreferencedBlockInserterWorkerEvent,
 };
 
 pub(crate) struct ProcessorWorkerEvent {
@@ -51,10 +54,13 @@ where
             TypeId::of::<PropagatorWorker>(),
             TypeId::of::<BroadcasterWorker>(),
             TypeId::of::<BlockRequesterWorker>(),
+            TypeId::of::<MilestoneRequesterWorker>(),
             TypeId::of::<MetricsWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
             TypeId::of::<PayloadWorker>(),
             TypeId::of::<UnreferencedBlockInserterWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
+            TypeId::of::<PenalizerWorker>(),
         ]
         .leak()
     }
@@ -67,11 +73,14 @@ where
         let block_requester = node.worker::<BlockRequesterWorker>().unwrap().clone();
         let payload_worker = node.worker::<PayloadWorker>().unwrap().tx.clone();
         let unreferenced_inserted_worker = node.worker::<UnreferencedBlockInserterWorker>().unwrap().tx.clone();
+        let penalizer = node.worker::<PenalizerWorker>().unwrap().tx.clone();
 
         let tangle = node.resource::<Tangle<N::Backend>>();
         let requested_blocks = node.resource::<RequestedBlocks>();
+        let requested_milestones = node.resource::<RequestedMilestones>();
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
+        let protocol_parameters = node.resource::<ProtocolParametersManager>();
         let bus = node.bus();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
@@ -83,11 +92,6 @@ where
 
             let (tx, rx) = async_channel::unbounded();
 
-            // TODO: this is obviously wrong but can't be done properly until the snapshot PR is merged.
-            // The node can't work properly with this.
-            // @thibault-martinez.
-            let protocol_parameters = bee_block::protocol::ProtocolParameters::default();
-
             for _ in 0..16 {
                 let rx = rx.clone();
                 let propagator = propagator.clone();
@@ -95,8 +99,10 @@ where
                 let block_requester = block_requester.clone();
                 let payload_worker = payload_worker.clone();
                 let unreferenced_inserted_worker = unreferenced_inserted_worker.clone();
+                let penalizer = penalizer.clone();
                 let tangle = tangle.clone();
                 let requested_blocks = requested_blocks.clone();
+                let requested_milestones = requested_milestones.clone();
                 let metrics = metrics.clone();
                 let peer_manager = peer_manager.clone();
                 let bus = bus.clone();
@@ -112,10 +118,15 @@ where
                     {
                         trace!("Processing received block...");
 
+                        let protocol_parameters = protocol_parameters.current();
+
                         let block = match Block::unpack_strict(&block_packet.bytes[..], &protocol_parameters) {
                             Ok(block) => block,
                             Err(e) => {
                                 notify_invalid_block(format!("Invalid block: {:?}.", e), &metrics, notifier);
+                                if let Some(peer_id) = from {
+                                    penalize(&penalizer, peer_id, Misbehaviour::InvalidBlock);
+                                }
                                 continue;
                             }
                         };
@@ -127,6 +138,9 @@ where
                                     &metrics,
                                     notifier,
                                 );
+                                if let Some(peer_id) = from {
+                                    penalize(&penalizer, peer_id, Misbehaviour::InvalidBlock);
+                                }
                                 continue;
                             }
                         } else if pow_score < protocol_parameters.min_pow_score() as f64 {
@@ -139,6 +153,9 @@ where
                                 &metrics,
                                 notifier,
                             );
+                            if let Some(peer_id) = from {
+                                penalize(&penalizer, peer_id, Misbehaviour::InvalidBlock);
+                            }
                             continue;
                         }
 
@@ -173,17 +190,33 @@ where
 
                         match requested_blocks.remove(&block_id) {
                             // Block was requested.
-                            Some((index, instant)) => {
+                            Some((index, request)) => {
                                 latency_num += 1;
-                                latency_sum += (Instant::now() - instant).as_millis() as u64;
+                                latency_sum += (Instant::now() - request.requested_at).as_millis() as u64;
                                 metrics.blocks_average_latency_set(latency_sum / latency_num);
 
+                                if from.is_some() && from == request.peer_id {
+                                    request.answered(&peer_manager);
+                                }
+
                                 for parent in block.parents().iter() {
                                     request_block(&tangle, &block_requester, &requested_blocks, *parent, index).await;
                                 }
                             }
                             // Block was not requested.
                             None => {
+                                // Milestones up to the solid one were either requested or gossiped long ago, unless
+                                // they answer a request that was re-routed or dropped in the meantime.
+                                if let (Some(peer_id), Some(Payload::Milestone(milestone))) = (from, block.payload()) {
+                                    let index = milestone.essence().index();
+
+                                    if index <= tangle.get_solid_milestone_index()
+                                        && !requested_milestones.is_recent(&index)
+                                    {
+                                        penalize(&penalizer, peer_id, Misbehaviour::UnsolicitedResponse);
+                                    }
+                                }
+
                                 if let Err(e) = broadcaster.send(BroadcasterWorkerEvent {
                                     source: from,
                                     block: block_packet,
@@ -201,7 +234,10 @@ where
 
                         let parents = block.parents().to_vec();
 
-                        if payload_worker.send(PayloadWorkerEvent { block_id, block }).is_err() {
+                        if payload_worker
+                            .send(PayloadWorkerEvent { block_id, block, from })
+                            .is_err()
+                        {
                             error!("Sending block {} to payload worker failed.", block_id);
                         }
 
@@ -0,0 +1,364 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{any::TypeId, convert::Infallible};
+
+use async_trait::async_trait;
+use bee_block::{parent::Parents, Block, BlockBuilder, BlockId};
+use bee_ledger::parameters::{ProtocolParametersManager, ProtocolParametersWorker};
+use bee_pow::providers::{miner::MinerBuilder, NonceProviderBuilder};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_tangle::{Tangle, TangleWorker};
+use futures::{
+    channel::oneshot::{self, Sender},
+    stream::StreamExt,
+};
+use log::{debug, error, info};
+use packable::PackableExt;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::{storage::StorageBackend, BlockSubmitterError, BlockSubmitterWorker, BlockSubmitterWorkerEvent};
+
+// Every command requires PoW, so only a few of them are queued before they are rejected.
+const BLOCK_PROMOTER_QUEUE_SIZE: usize = 16;
+
+/// Errors occurring while promoting or reattaching a block.
+#[derive(Debug, thiserror::Error)]
+pub enum BlockPromoterError {
+    #[error("block {0} not found")]
+    BlockNotFound(BlockId),
+    #[error("block {0} does not need to be promoted: {1:?}")]
+    NotPromotable(BlockId, PromotionState),
+    #[error("block {0} does not need to be reattached: {1:?}")]
+    NotReattachable(BlockId, PromotionState),
+    #[error("no tips available")]
+    NoTips,
+    #[error("invalid block: {0}")]
+    InvalidBlock(#[from] bee_block::Error),
+    #[error("{0}")]
+    Submitter(#[from] BlockSubmitterError),
+    #[error("block promoter unavailable")]
+    Unavailable,
+}
+
+/// Whether a block needs help to get referenced by a milestone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PromotionState {
+    /// The block is already referenced by a milestone.
+    Referenced,
+    /// The block is non-lazy and is expected to be referenced by a milestone.
+    NonLazy,
+    /// The block is not solid yet, it can neither be promoted nor reattached until it is.
+    Unsolid,
+    /// The block is lazy or semi-lazy and should be promoted by approving it with new blocks.
+    Promote,
+    /// The block is below max depth and will never be referenced, its payload should be reattached.
+    Reattach,
+}
+
+impl PromotionState {
+    /// Returns whether the block should be promoted.
+    pub fn should_promote(&self) -> bool {
+        matches!(self, Self::Promote)
+    }
+
+    /// Returns whether the block should be reattached.
+    pub fn should_reattach(&self) -> bool {
+        matches!(self, Self::Reattach)
+    }
+}
+
+/// Computes the promotion state of a block, or `None` if the block is unknown.
+pub async fn promotion_state<B: StorageBackend>(tangle: &Tangle<B>, block_id: &BlockId) -> Option<PromotionState> {
+    let metadata = tangle.get_metadata(block_id)?;
+
+    if metadata.milestone_index().is_some() {
+        return Some(PromotionState::Referenced);
+    }
+
+    if !metadata.flags().is_solid() {
+        return Some(PromotionState::Unsolid);
+    }
+
+    let (omrsi, ymrsi) = match tangle.omrsi_and_ymrsi(block_id).await {
+        Some((omrsi, ymrsi)) => (*omrsi.index(), *ymrsi.index()),
+        None => return Some(PromotionState::Reattach),
+    };
+    let cmi = *tangle.get_confirmed_milestone_index();
+    let config = tangle.config();
+
+    Some(if cmi.saturating_sub(omrsi) > config.below_max_depth() {
+        PromotionState::Reattach
+    } else if cmi.saturating_sub(ymrsi) > config.tip_selection().ymrsi_delta()
+        || cmi.saturating_sub(omrsi) > config.tip_selection().omrsi_delta()
+    {
+        PromotionState::Promote
+    } else {
+        PromotionState::NonLazy
+    })
+}
+
+/// The action to perform on a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockPromoterCommand {
+    /// Issues an empty block approving the block and new tips.
+    Promote,
+    /// Issues a new block with the payload of the block and new tips as parents.
+    Reattach,
+}
+
+pub struct BlockPromoterWorkerEvent {
+    pub block_id: BlockId,
+    pub command: BlockPromoterCommand,
+    pub notifier: Sender<Result<BlockId, BlockPromoterError>>,
+}
+
+pub struct BlockPromoterWorker {
+    pub tx: mpsc::Sender<BlockPromoterWorkerEvent>,
+}
+
+async fn tips<B: StorageBackend>(tangle: &Tangle<B>) -> Result<Vec<BlockId>, BlockPromoterError> {
+    tangle.get_blocks_to_approve().await.ok_or(BlockPromoterError::NoTips)
+}
+
+async fn build_block(
+    mut parents: Vec<BlockId>,
+    block: Option<Block>,
+    min_pow_score: u32,
+) -> Result<Block, BlockPromoterError> {
+    parents.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    parents.dedup();
+    parents.truncate(*Parents::COUNT_RANGE.end() as usize);
+
+    let mut builder = BlockBuilder::new(Parents::new(parents)?)
+        .with_nonce_provider(MinerBuilder::new().with_num_workers(num_cpus::get()).finish());
+    if let Some(payload) = block.and_then(|block| block.payload().cloned()) {
+        builder = builder.with_payload(payload);
+    }
+
+    // Doing PoW is blocking, so it doesn't run on the async runtime.
+    tokio::task::spawn_blocking(move || builder.finish(min_pow_score))
+        .await
+        .map_err(|_| BlockPromoterError::Unavailable)?
+        .map_err(Into::into)
+}
+
+async fn process<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    protocol_parameters: &ProtocolParametersManager,
+    block_submitter: &mpsc::UnboundedSender<BlockSubmitterWorkerEvent>,
+    block_id: BlockId,
+    command: BlockPromoterCommand,
+) -> Result<BlockId, BlockPromoterError> {
+    let state = promotion_state(tangle, &block_id)
+        .await
+        .ok_or(BlockPromoterError::BlockNotFound(block_id))?;
+    let min_pow_score = protocol_parameters.current().min_pow_score();
+
+    let block = match command {
+        BlockPromoterCommand::Promote => {
+            if !state.should_promote() {
+                return Err(BlockPromoterError::NotPromotable(block_id, state));
+            }
+
+            let mut parents = tips(tangle).await?;
+            // Keeps room for the promoted block.
+            parents.truncate(*Parents::COUNT_RANGE.end() as usize - 1);
+            parents.push(block_id);
+
+            build_block(parents, None, min_pow_score).await?
+        }
+        BlockPromoterCommand::Reattach => {
+            if !state.should_reattach() {
+                return Err(BlockPromoterError::NotReattachable(block_id, state));
+            }
+
+            let block = tangle
+                .get(&block_id)
+                .ok_or(BlockPromoterError::BlockNotFound(block_id))?;
+
+            build_block(tips(tangle).await?, Some(block), min_pow_score).await?
+        }
+    };
+
+    let (notifier, waiter) = oneshot::channel();
+
+    block_submitter
+        .send(BlockSubmitterWorkerEvent {
+            block: block.pack_to_vec(),
+            notifier,
+        })
+        .map_err(|_| BlockPromoterError::Unavailable)?;
+
+    Ok(waiter.await.map_err(|_| BlockPromoterError::Unavailable)??)
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for BlockPromoterWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ();
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<BlockSubmitterWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
+        ]
+        .leak()
+    }
+
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::channel(BLOCK_PROMOTER_QUEUE_SIZE);
+
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let protocol_parameters = node.resource::<ProtocolParametersManager>();
+        let block_submitter = node.worker::<BlockSubmitterWorker>().unwrap().tx.clone();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, ReceiverStream::new(rx));
+
+            while let Some(BlockPromoterWorkerEvent {
+                block_id,
+                command,
+                notifier,
+            }) = receiver.next().await
+            {
+                let result = process(&tangle, &protocol_parameters, &block_submitter, block_id, command).await;
+
+                match &result {
+                    Ok(new_block_id) => debug!("{:?} of block {} issued block {}.", command, block_id, new_block_id),
+                    Err(e) => debug!("{:?} of block {} failed: {}.", command, block_id, e),
+                }
+
+                if let Err(e) = notifier.send(result) {
+                    error!("Failed to send promotion result: {:?}.", e);
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::{payload::milestone::MilestoneIndex, protocol::protocol_parameters, rand::block::rand_block};
+    use bee_runtime::resource::ResourceHandle;
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::{
+        block_metadata::{BlockMetadata, IndexId},
+        config::TangleConfig,
+    };
+
+    use super::*;
+
+    const CONFIRMED_MILESTONE_INDEX: u32 = 20;
+
+    fn tangle() -> Tangle<Storage> {
+        let tangle = Tangle::new(
+            TangleConfig::build().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        );
+        tangle.update_confirmed_milestone_index(MilestoneIndex(CONFIRMED_MILESTONE_INDEX));
+
+        tangle
+    }
+
+    fn insert_block(tangle: &Tangle<Storage>, metadata: impl FnOnce(&mut BlockMetadata, BlockId)) -> BlockId {
+        let block = rand_block(protocol_parameters().min_pow_score());
+        let block_id = block.id();
+        let mut block_metadata = BlockMetadata::arrived();
+
+        metadata(&mut block_metadata, block_id);
+        tangle.insert(&block, &block_id, &block_metadata);
+
+        block_id
+    }
+
+    fn insert_solid_block(tangle: &Tangle<Storage>, omrsi: u32, ymrsi: u32) -> BlockId {
+        insert_block(tangle, |metadata, block_id| {
+            metadata.mark_solid();
+            metadata.set_omrsi_and_ymrsi(
+                IndexId::new(MilestoneIndex(omrsi), block_id),
+                IndexId::new(MilestoneIndex(ymrsi), block_id),
+            );
+        })
+    }
+
+    #[tokio::test]
+    async fn unknown_block() {
+        let tangle = tangle();
+
+        assert_eq!(promotion_state(&tangle, &BlockId::null()).await, None);
+    }
+
+    #[tokio::test]
+    async fn referenced_block() {
+        let tangle = tangle();
+        let block_id = insert_block(&tangle, |metadata, _| {
+            metadata.mark_solid();
+            metadata.set_milestone_index(MilestoneIndex(CONFIRMED_MILESTONE_INDEX));
+        });
+
+        assert_eq!(
+            promotion_state(&tangle, &block_id).await,
+            Some(PromotionState::Referenced)
+        );
+    }
+
+    #[tokio::test]
+    async fn unsolid_block_is_neither_promoted_nor_reattached() {
+        let tangle = tangle();
+        let block_id = insert_block(&tangle, |_, _| ());
+        let state = promotion_state(&tangle, &block_id).await.unwrap();
+
+        assert_eq!(state, PromotionState::Unsolid);
+        assert!(!state.should_promote());
+        assert!(!state.should_reattach());
+    }
+
+    #[tokio::test]
+    async fn non_lazy_block() {
+        let tangle = tangle();
+        let block_id = insert_solid_block(&tangle, CONFIRMED_MILESTONE_INDEX - 2, CONFIRMED_MILESTONE_INDEX);
+
+        assert_eq!(promotion_state(&tangle, &block_id).await, Some(PromotionState::NonLazy));
+    }
+
+    #[tokio::test]
+    async fn lazy_blocks_are_promoted() {
+        let tangle = tangle();
+        // Youngest root too old.
+        let ymrsi = insert_solid_block(&tangle, CONFIRMED_MILESTONE_INDEX - 9, CONFIRMED_MILESTONE_INDEX - 9);
+        // Oldest root too old.
+        let omrsi = insert_solid_block(&tangle, CONFIRMED_MILESTONE_INDEX - 14, CONFIRMED_MILESTONE_INDEX);
+
+        for block_id in [ymrsi, omrsi] {
+            let state = promotion_state(&tangle, &block_id).await.unwrap();
+
+            assert_eq!(state, PromotionState::Promote);
+            assert!(state.should_promote());
+            assert!(!state.should_reattach());
+        }
+    }
+
+    #[tokio::test]
+    async fn block_below_max_depth_is_reattached() {
+        let tangle = tangle();
+        let block_id = insert_solid_block(&tangle, CONFIRMED_MILESTONE_INDEX - 16, CONFIRMED_MILESTONE_INDEX);
+        let state = promotion_state(&tangle, &block_id).await.unwrap();
+
+        assert_eq!(state, PromotionState::Reattach);
+        assert!(!state.should_promote());
+        assert!(state.should_reattach());
+    }
+}
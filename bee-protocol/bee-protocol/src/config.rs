@@ -4,6 +4,7 @@
 use bee_block::{
     output::{RentStructure, RentStructureBuilder},
     payload::milestone::MilestoneIndex,
+    protocol::ProtocolParameters,
 };
 use serde::Deserialize;
 
@@ -15,6 +16,25 @@ const DEFAULT_COO_PUBLIC_KEY_RANGES: [(&str, MilestoneIndex, MilestoneIndex); 0]
 const DEFAULT_BLOCK_WORKER_CACHE: usize = 10000;
 const DEFAULT_STATUS_INTERVAL: u64 = 10;
 const DEFAULT_MILESTONE_SYNC_COUNT: u32 = 200;
+const DEFAULT_SOLIDIFICATION_WINDOW: u32 = 8;
+const DEFAULT_SOLIDIFICATION_MAX_REQUESTS: usize = 5000;
+const DEFAULT_REQUEST_RETRY_INTERVAL: u64 = 2500;
+const DEFAULT_REQUEST_MAX_RETRY_INTERVAL: u64 = 30000;
+const DEFAULT_REQUEST_MAX_RETRIES: u32 = 10;
+const DEFAULT_FAST_SYNC_ENABLED: bool = false;
+const DEFAULT_FAST_SYNC_THRESHOLD: u32 = 50;
+const DEFAULT_FAST_SYNC_RANGE_SIZE: u32 = 100;
+const DEFAULT_FAST_SYNC_TIMEOUT: u64 = 30;
+const DEFAULT_FAST_SYNC_MAX_STREAMS: usize = 2;
+// Bounds the work a single fast sync request can ask for.
+const MAX_FAST_SYNC_RANGE_SIZE: u32 = 1000;
+const DEFAULT_MISBEHAVIOUR_BLOCK_RATE: u32 = 1000;
+const DEFAULT_MISBEHAVIOUR_REQUEST_RATE: u32 = 1000;
+const DEFAULT_MISBEHAVIOUR_HEARTBEAT_RATE: u32 = 1;
+const DEFAULT_MISBEHAVIOUR_BAN_THRESHOLD: u32 = 100;
+const DEFAULT_MISBEHAVIOUR_BAN_DURATION: u64 = 600;
+const DEFAULT_NEIGHBOR_SCORING_MIN_SCORE: u8 = 30;
+const DEFAULT_NEIGHBOR_SCORING_MAX_UNSYNCED_TIME: u64 = 300;
 
 #[derive(Default, Deserialize, PartialEq)]
 #[must_use]
@@ -34,6 +54,54 @@ struct ProtocolWorkersConfigBuilder {
     status_interval: Option<u64>,
     #[serde(alias = "milestoneSyncCount")]
     milestone_sync_count: Option<u32>,
+    #[serde(alias = "solidificationWindow")]
+    solidification_window: Option<u32>,
+    #[serde(alias = "solidificationMaxRequests")]
+    solidification_max_requests: Option<usize>,
+    #[serde(alias = "requestRetryInterval")]
+    request_retry_interval: Option<u64>,
+    #[serde(alias = "requestMaxRetryInterval")]
+    request_max_retry_interval: Option<u64>,
+    #[serde(alias = "requestMaxRetries")]
+    request_max_retries: Option<u32>,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolFastSyncConfigBuilder {
+    enabled: Option<bool>,
+    #[serde(alias = "trustedPeers")]
+    trusted_peers: Option<Vec<String>>,
+    threshold: Option<u32>,
+    #[serde(alias = "rangeSize")]
+    range_size: Option<u32>,
+    timeout: Option<u64>,
+    #[serde(alias = "maxStreams")]
+    max_streams: Option<usize>,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolMisbehaviourConfigBuilder {
+    #[serde(alias = "blockRate")]
+    block_rate: Option<u32>,
+    #[serde(alias = "requestRate")]
+    request_rate: Option<u32>,
+    #[serde(alias = "heartbeatRate")]
+    heartbeat_rate: Option<u32>,
+    #[serde(alias = "banThreshold")]
+    ban_threshold: Option<u32>,
+    #[serde(alias = "banDuration")]
+    ban_duration: Option<u64>,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolNeighborScoringConfigBuilder {
+    #[serde(alias = "minScore")]
+    min_score: Option<u8>,
+    #[serde(alias = "maxUnsyncedTime")]
+    max_unsynced_time: Option<u64>,
 }
 
 /// Builder for a `ProtocolConfig`.
@@ -44,6 +112,12 @@ pub struct ProtocolConfigBuilder {
     minimum_pow_score: Option<f64>,
     coordinator: ProtocolCoordinatorConfigBuilder,
     workers: ProtocolWorkersConfigBuilder,
+    #[serde(alias = "fastSync", default)]
+    fast_sync: ProtocolFastSyncConfigBuilder,
+    #[serde(default)]
+    misbehaviour: ProtocolMisbehaviourConfigBuilder,
+    #[serde(alias = "neighborScoring", default)]
+    neighbor_scoring: ProtocolNeighborScoringConfigBuilder,
     rent: RentStructureBuilder,
 }
 
@@ -89,6 +163,123 @@ impl ProtocolConfigBuilder {
         self
     }
 
+    /// Sets the number of milestone cones solidified in parallel of the `ProtocolConfigBuilder`.
+    pub fn solidification_window(mut self, solidification_window: u32) -> Self {
+        self.workers.solidification_window.replace(solidification_window);
+        self
+    }
+
+    /// Sets the maximum number of blocks requested at once by the solidification of the `ProtocolConfigBuilder`.
+    pub fn solidification_max_requests(mut self, solidification_max_requests: usize) -> Self {
+        self.workers
+            .solidification_max_requests
+            .replace(solidification_max_requests);
+        self
+    }
+
+    /// Sets the interval, in milliseconds, before a first retry of a block or milestone request of the
+    /// `ProtocolConfigBuilder`.
+    pub fn request_retry_interval(mut self, request_retry_interval: u64) -> Self {
+        self.workers.request_retry_interval.replace(request_retry_interval);
+        self
+    }
+
+    /// Sets the maximum interval, in milliseconds, between retries of a block or milestone request of the
+    /// `ProtocolConfigBuilder`.
+    pub fn request_max_retry_interval(mut self, request_max_retry_interval: u64) -> Self {
+        self.workers
+            .request_max_retry_interval
+            .replace(request_max_retry_interval);
+        self
+    }
+
+    /// Sets the number of retries after which a block or milestone request is dropped of the `ProtocolConfigBuilder`.
+    pub fn request_max_retries(mut self, request_max_retries: u32) -> Self {
+        self.workers.request_max_retries.replace(request_max_retries);
+        self
+    }
+
+    /// Enables or disables the fast sync of the `ProtocolConfigBuilder`.
+    pub fn fast_sync_enabled(mut self, fast_sync_enabled: bool) -> Self {
+        self.fast_sync.enabled.replace(fast_sync_enabled);
+        self
+    }
+
+    /// Sets the peers the fast sync of the `ProtocolConfigBuilder` downloads milestone cones from.
+    pub fn fast_sync_trusted_peers(mut self, fast_sync_trusted_peers: Vec<String>) -> Self {
+        self.fast_sync.trusted_peers.replace(fast_sync_trusted_peers);
+        self
+    }
+
+    /// Sets the number of milestones the node has to be behind to start the fast sync of the `ProtocolConfigBuilder`.
+    pub fn fast_sync_threshold(mut self, fast_sync_threshold: u32) -> Self {
+        self.fast_sync.threshold.replace(fast_sync_threshold);
+        self
+    }
+
+    /// Sets the number of milestones requested or served at once by the fast sync of the `ProtocolConfigBuilder`.
+    pub fn fast_sync_range_size(mut self, fast_sync_range_size: u32) -> Self {
+        self.fast_sync.range_size.replace(fast_sync_range_size);
+        self
+    }
+
+    /// Sets the timeout, in seconds, of the fast sync streams of the `ProtocolConfigBuilder`.
+    pub fn fast_sync_timeout(mut self, fast_sync_timeout: u64) -> Self {
+        self.fast_sync.timeout.replace(fast_sync_timeout);
+        self
+    }
+
+    /// Sets the maximum number of fast sync streams served at once of the `ProtocolConfigBuilder`.
+    pub fn fast_sync_max_streams(mut self, fast_sync_max_streams: usize) -> Self {
+        self.fast_sync.max_streams.replace(fast_sync_max_streams);
+        self
+    }
+
+    /// Sets the number of blocks per second a peer may send of the `ProtocolConfigBuilder`.
+    pub fn misbehaviour_block_rate(mut self, misbehaviour_block_rate: u32) -> Self {
+        self.misbehaviour.block_rate.replace(misbehaviour_block_rate);
+        self
+    }
+
+    /// Sets the number of block and milestone requests per second a peer may send of the `ProtocolConfigBuilder`.
+    pub fn misbehaviour_request_rate(mut self, misbehaviour_request_rate: u32) -> Self {
+        self.misbehaviour.request_rate.replace(misbehaviour_request_rate);
+        self
+    }
+
+    /// Sets the number of heartbeats per second a peer may send of the `ProtocolConfigBuilder`.
+    pub fn misbehaviour_heartbeat_rate(mut self, misbehaviour_heartbeat_rate: u32) -> Self {
+        self.misbehaviour.heartbeat_rate.replace(misbehaviour_heartbeat_rate);
+        self
+    }
+
+    /// Sets the penalty at which a peer is banned of the `ProtocolConfigBuilder`.
+    pub fn misbehaviour_ban_threshold(mut self, misbehaviour_ban_threshold: u32) -> Self {
+        self.misbehaviour.ban_threshold.replace(misbehaviour_ban_threshold);
+        self
+    }
+
+    /// Sets the duration, in seconds, of the bans of misbehaving peers of the `ProtocolConfigBuilder`.
+    pub fn misbehaviour_ban_duration(mut self, misbehaviour_ban_duration: u64) -> Self {
+        self.misbehaviour.ban_duration.replace(misbehaviour_ban_duration);
+        self
+    }
+
+    /// Sets the score below which autopeered neighbors are dropped of the `ProtocolConfigBuilder`.
+    pub fn neighbor_scoring_min_score(mut self, neighbor_scoring_min_score: u8) -> Self {
+        self.neighbor_scoring.min_score.replace(neighbor_scoring_min_score);
+        self
+    }
+
+    /// Sets the time, in seconds, after which unsynced autopeered neighbors are dropped of the
+    /// `ProtocolConfigBuilder`.
+    pub fn neighbor_scoring_max_unsynced_time(mut self, neighbor_scoring_max_unsynced_time: u64) -> Self {
+        self.neighbor_scoring
+            .max_unsynced_time
+            .replace(neighbor_scoring_max_unsynced_time);
+        self
+    }
+
     /// Finishes the `ProtocolConfigBuilder` into a `ProtocolConfig`.
     #[must_use]
     pub fn finish(self) -> ProtocolConfig {
@@ -113,6 +304,65 @@ impl ProtocolConfigBuilder {
                     .workers
                     .milestone_sync_count
                     .unwrap_or(DEFAULT_MILESTONE_SYNC_COUNT),
+                solidification_window: self
+                    .workers
+                    .solidification_window
+                    .unwrap_or(DEFAULT_SOLIDIFICATION_WINDOW),
+                solidification_max_requests: self
+                    .workers
+                    .solidification_max_requests
+                    .unwrap_or(DEFAULT_SOLIDIFICATION_MAX_REQUESTS),
+                request_retry_interval: self
+                    .workers
+                    .request_retry_interval
+                    .unwrap_or(DEFAULT_REQUEST_RETRY_INTERVAL)
+                    .max(1),
+                request_max_retry_interval: self
+                    .workers
+                    .request_max_retry_interval
+                    .unwrap_or(DEFAULT_REQUEST_MAX_RETRY_INTERVAL),
+                request_max_retries: self.workers.request_max_retries.unwrap_or(DEFAULT_REQUEST_MAX_RETRIES),
+            },
+            fast_sync: ProtocolFastSyncConfig {
+                enabled: self.fast_sync.enabled.unwrap_or(DEFAULT_FAST_SYNC_ENABLED),
+                trusted_peers: self.fast_sync.trusted_peers.unwrap_or_default(),
+                threshold: self.fast_sync.threshold.unwrap_or(DEFAULT_FAST_SYNC_THRESHOLD),
+                range_size: self
+                    .fast_sync
+                    .range_size
+                    .unwrap_or(DEFAULT_FAST_SYNC_RANGE_SIZE)
+                    .clamp(1, MAX_FAST_SYNC_RANGE_SIZE),
+                timeout: self.fast_sync.timeout.unwrap_or(DEFAULT_FAST_SYNC_TIMEOUT),
+                max_streams: self.fast_sync.max_streams.unwrap_or(DEFAULT_FAST_SYNC_MAX_STREAMS),
+            },
+            misbehaviour: ProtocolMisbehaviourConfig {
+                block_rate: self.misbehaviour.block_rate.unwrap_or(DEFAULT_MISBEHAVIOUR_BLOCK_RATE),
+                request_rate: self
+                    .misbehaviour
+                    .request_rate
+                    .unwrap_or(DEFAULT_MISBEHAVIOUR_REQUEST_RATE),
+                heartbeat_rate: self
+                    .misbehaviour
+                    .heartbeat_rate
+                    .unwrap_or(DEFAULT_MISBEHAVIOUR_HEARTBEAT_RATE),
+                ban_threshold: self
+                    .misbehaviour
+                    .ban_threshold
+                    .unwrap_or(DEFAULT_MISBEHAVIOUR_BAN_THRESHOLD),
+                ban_duration: self
+                    .misbehaviour
+                    .ban_duration
+                    .unwrap_or(DEFAULT_MISBEHAVIOUR_BAN_DURATION),
+            },
+            neighbor_scoring: ProtocolNeighborScoringConfig {
+                min_score: self
+                    .neighbor_scoring
+                    .min_score
+                    .unwrap_or(DEFAULT_NEIGHBOR_SCORING_MIN_SCORE),
+                max_unsynced_time: self
+                    .neighbor_scoring
+                    .max_unsynced_time
+                    .unwrap_or(DEFAULT_NEIGHBOR_SCORING_MAX_UNSYNCED_TIME),
             },
             rent: self.rent.finish(),
         }
@@ -126,12 +376,57 @@ pub struct ProtocolCoordinatorConfig {
     pub(crate) public_key_ranges: Vec<MilestoneKeyRange>,
 }
 
+impl ProtocolCoordinatorConfig {
+    /// Returns the number of signatures a milestone requires.
+    pub fn public_key_count(&self) -> usize {
+        self.public_key_count
+    }
+
+    /// Returns the public key ranges of the coordinator.
+    pub fn public_key_ranges(&self) -> &[MilestoneKeyRange] {
+        &self.public_key_ranges
+    }
+}
+
 /// Configuration for the protocol workers.
 #[derive(Clone)]
 pub struct ProtocolWorkersConfig {
     pub(crate) block_worker_cache: usize,
     pub(crate) status_interval: u64,
     pub(crate) milestone_sync_count: u32,
+    pub(crate) solidification_window: u32,
+    pub(crate) solidification_max_requests: usize,
+    pub(crate) request_retry_interval: u64,
+    pub(crate) request_max_retry_interval: u64,
+    pub(crate) request_max_retries: u32,
+}
+
+/// Configuration for the fast sync.
+#[derive(Clone)]
+pub struct ProtocolFastSyncConfig {
+    pub(crate) enabled: bool,
+    pub(crate) trusted_peers: Vec<String>,
+    pub(crate) threshold: u32,
+    pub(crate) range_size: u32,
+    pub(crate) timeout: u64,
+    pub(crate) max_streams: usize,
+}
+
+/// Configuration for the rate limits and penalties of misbehaving peers.
+#[derive(Clone)]
+pub struct ProtocolMisbehaviourConfig {
+    pub(crate) block_rate: u32,
+    pub(crate) request_rate: u32,
+    pub(crate) heartbeat_rate: u32,
+    pub(crate) ban_threshold: u32,
+    pub(crate) ban_duration: u64,
+}
+
+/// Configuration for the scoring of the autopeered neighbors.
+#[derive(Clone)]
+pub struct ProtocolNeighborScoringConfig {
+    pub(crate) min_score: u8,
+    pub(crate) max_unsynced_time: u64,
 }
 
 /// Configuration for the protocol.
@@ -140,6 +435,9 @@ pub struct ProtocolConfig {
     pub(crate) minimum_pow_score: f64,
     pub(crate) coordinator: ProtocolCoordinatorConfig,
     pub(crate) workers: ProtocolWorkersConfig,
+    pub(crate) fast_sync: ProtocolFastSyncConfig,
+    pub(crate) misbehaviour: ProtocolMisbehaviourConfig,
+    pub(crate) neighbor_scoring: ProtocolNeighborScoringConfig,
     pub(crate) rent: RentStructure,
 }
 
@@ -159,8 +457,43 @@ impl ProtocolConfig {
         &self.coordinator
     }
 
+    /// Returns the fast sync configuration of the `ProtocolConfig`.
+    pub fn fast_sync(&self) -> &ProtocolFastSyncConfig {
+        &self.fast_sync
+    }
+
+    /// Returns the misbehaviour configuration of the `ProtocolConfig`.
+    pub fn misbehaviour(&self) -> &ProtocolMisbehaviourConfig {
+        &self.misbehaviour
+    }
+
+    /// Returns the neighbor scoring configuration of the `ProtocolConfig`.
+    pub fn neighbor_scoring(&self) -> &ProtocolNeighborScoringConfig {
+        &self.neighbor_scoring
+    }
+
     /// Returns the rent configuration of the `ProtocolConfig`.
     pub fn rent(&self) -> &RentStructure {
         &self.rent
     }
+
+    /// Returns the protocol parameters of a network as configured by the `ProtocolConfig`, the parameters that are not
+    /// configurable keep their default values.
+    pub fn protocol_parameters(
+        &self,
+        network_name: String,
+        bech32_hrp: String,
+    ) -> Result<ProtocolParameters, bee_block::Error> {
+        let default = ProtocolParameters::default();
+
+        ProtocolParameters::new(
+            default.protocol_version(),
+            network_name,
+            bech32_hrp,
+            self.minimum_pow_score as u32,
+            default.below_max_depth(),
+            self.rent.clone(),
+            default.token_supply(),
+        )
+    }
 }
@@ -1,7 +1,9 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use bee_block::BlockId;
+use std::net::IpAddr;
+
+use bee_block::{payload::milestone::MilestoneIndex, BlockId};
 
 /// An event that indicates that a block was processed.
 #[derive(Clone)]
@@ -66,3 +68,25 @@ pub struct TipRemoved {
     /// Block identifier of the removed tip.
     pub block_id: BlockId,
 }
+
+/// An event that indicates the progress of the milestone solidification.
+#[derive(Clone)]
+pub struct MilestoneSolidificationProgress {
+    /// Index of the solid milestone.
+    pub solid_milestone_index: MilestoneIndex,
+    /// Index of the latest milestone.
+    pub latest_milestone_index: MilestoneIndex,
+    /// Indexes of the milestones whose cones are being solidified.
+    pub solidifying_milestones: Vec<MilestoneIndex>,
+    /// Number of blocks missing from the cones of the solidifying milestones.
+    pub missing_blocks: usize,
+    /// Number of blocks requested and not yet received.
+    pub requested_blocks: usize,
+}
+
+/// An event that indicates that the majority of the autopeered peers perceive the node under a new external address.
+#[derive(Clone)]
+pub struct ExternalAddressChanged {
+    /// The new external address.
+    pub address: IpAddr,
+}
@@ -5,7 +5,11 @@ use std::{any::TypeId, convert::Infallible, time::Duration};
 
 use async_trait::async_trait;
 use bee_gossip::PeerId;
-use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_runtime::{
+    node::{Node, NodeInfo},
+    shutdown_stream::ShutdownStream,
+    worker::Worker,
+};
 use bee_tangle::{Tangle, TangleWorker};
 use futures::stream::StreamExt;
 use log::info;
@@ -13,40 +17,85 @@ use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
-    packets::HeartbeatPacket, peer::PeerManager, sender::Sender, storage::StorageBackend, types::metrics::NodeMetrics,
+    packets::{
+        BlockPacket, BlockRequestPacket, HeartbeatPacket, HeartbeatV2Packet, MilestoneRequestPacket, Packet,
+        PROTOCOL_VERSION, PROTOCOL_VERSION_MIN,
+    },
+    peer::PeerManager,
+    sender::Sender,
+    storage::StorageBackend,
+    types::metrics::NodeMetrics,
     MetricsWorker, PeerManagerResWorker,
 };
 
 const HEARTBEAT_SEND_INTERVAL: Duration = Duration::from_secs(30);
 
-pub(crate) fn new_heartbeat<B: StorageBackend>(tangle: &Tangle<B>, peer_manager: &PeerManager) -> HeartbeatPacket {
+pub(crate) fn new_heartbeat<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    peer_manager: &PeerManager,
+    node_info: &NodeInfo,
+) -> HeartbeatV2Packet {
     let connected_peers = peer_manager.connected_peers();
     let synced_peers = peer_manager.synced_peers();
 
-    HeartbeatPacket::new(
+    HeartbeatV2Packet::new(
         *tangle.get_solid_milestone_index(),
         *tangle.get_pruning_index(),
         *tangle.get_latest_milestone_index(),
+        *tangle.get_snapshot_index(),
         connected_peers,
         synced_peers,
+        // Set for each recipient by `send_heartbeat`.
+        false,
+        vec![
+            MilestoneRequestPacket::ID,
+            BlockPacket::ID,
+            BlockRequestPacket::ID,
+            HeartbeatPacket::ID,
+            HeartbeatV2Packet::ID,
+        ],
+        format!("{}/{}", node_info.name, node_info.version),
     )
 }
 
+/// Sends a heartbeat in the latest format supported by the peer, the legacy one until a protocol version was
+/// negotiated.
+///
+/// Inbound sync streams are served whether fast sync is enabled or not, so the peer is told it is served fast sync
+/// whenever the sync worker would serve it.
 pub(crate) fn send_heartbeat(
-    heartbeat: &HeartbeatPacket,
+    heartbeat: &HeartbeatV2Packet,
     peer_id: &PeerId,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
 ) {
-    Sender::<HeartbeatPacket>::send(heartbeat, peer_id, peer_manager, metrics);
+    match peer_manager.get_map(peer_id, |peer| peer.0.protocol_version()) {
+        Some(version) if version > PROTOCOL_VERSION_MIN => {
+            let heartbeat = HeartbeatV2Packet {
+                serves_fast_sync: peer_manager.serves_fast_sync(peer_id),
+                ..heartbeat.clone()
+            };
+
+            Sender::<HeartbeatV2Packet>::send(&heartbeat, peer_id, peer_manager, metrics)
+        }
+        Some(_) => Sender::<HeartbeatPacket>::send(&heartbeat.into(), peer_id, peer_manager, metrics),
+        None => {}
+    }
+}
+
+/// Returns the protocol version a newly connected peer is known to support. The gossip protocol is versioned along
+/// with the packets, so legacy peers, only negotiating the oldest gossip protocol, never receive a versioned heartbeat.
+pub(crate) fn handshake_version(gossip_version: u8) -> u8 {
+    gossip_version.clamp(PROTOCOL_VERSION_MIN, PROTOCOL_VERSION)
 }
 
 pub(crate) fn broadcast_heartbeat<B: StorageBackend>(
     tangle: &Tangle<B>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
+    node_info: &NodeInfo,
 ) {
-    let heartbeat = new_heartbeat(tangle, peer_manager);
+    let heartbeat = new_heartbeat(tangle, peer_manager, node_info);
 
     peer_manager.for_each(|peer_id, _| send_heartbeat(&heartbeat, peer_id, peer_manager, metrics));
 }
@@ -75,6 +124,7 @@ where
         let tangle = node.resource::<Tangle<N::Backend>>();
         let peer_manager = node.resource::<PeerManager>();
         let metrics = node.resource::<NodeMetrics>();
+        let node_info = node.info();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -82,7 +132,7 @@ where
             let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(HEARTBEAT_SEND_INTERVAL)));
 
             while ticker.next().await.is_some() {
-                broadcast_heartbeat(&tangle, &peer_manager, &metrics);
+                broadcast_heartbeat(&tangle, &peer_manager, &metrics, &node_info);
             }
 
             info!("Stopped.");
@@ -91,3 +141,16 @@ where
         Ok(Self::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handshake_version_follows_gossip_version() {
+        assert_eq!(handshake_version(0), PROTOCOL_VERSION_MIN);
+        assert_eq!(handshake_version(1), PROTOCOL_VERSION_MIN);
+        assert_eq!(handshake_version(2), PROTOCOL_VERSION);
+        assert_eq!(handshake_version(u8::MAX), PROTOCOL_VERSION);
+    }
+}
@@ -74,7 +74,7 @@ async fn process<B: StorageBackend>(tangle: &Tangle<B>, milestone: MilestoneMeta
 
         // Note: For tip-selection only the most recent tangle is relevant. That means that during synchronization we do
         // not need to update xMRSI values or tip scores before (LATEST_MILESTONE_INDEX - BELOW_MAX_DEPTH).
-        if index > tangle.get_latest_milestone_index() - tangle.config().below_max_depth() {
+        if index + tangle.config().below_max_depth() > tangle.get_latest_milestone_index() {
             update_future_cone(tangle, roots);
 
             // Update tip pool after all values got updated.
@@ -20,21 +20,26 @@ mod metrics;
 mod mps;
 mod packets;
 mod peer;
+mod penalizer;
 mod propagator;
 mod requester;
 mod responder;
 mod sender;
 mod solidifier;
 mod status;
+mod sync;
 
-use bee_autopeering::event::EventRx as AutopeeringEventRx;
+use bee_autopeering::{command::CommandTx as AutopeeringCommandTx, event::EventRx as AutopeeringEventRx};
 use bee_gossip::NetworkEventReceiver as NetworkEventRx;
 pub use bee_protocol_types as types;
 use bee_runtime::node::{Node, NodeBuilder};
 
 use self::peer::PeerManagerConfig;
 pub use self::{
-    block::{BlockSubmitterError, BlockSubmitterWorker, BlockSubmitterWorkerEvent},
+    block::{
+        promotion_state, BlockPromoterCommand, BlockPromoterError, BlockPromoterWorker, BlockPromoterWorkerEvent,
+        BlockSubmitterError, BlockSubmitterWorker, BlockSubmitterWorkerEvent, PromotionState,
+    },
     metrics::MetricsWorker,
     peer::{PeerManager, PeerManagerResWorker},
     requester::{request_block, BlockRequesterWorker, RequestedBlocks, RequestedMilestones},
@@ -50,6 +55,7 @@ pub(crate) use self::{
     index_updater::{IndexUpdaterWorker, IndexUpdaterWorkerEvent},
     mps::MpsWorker,
     peer::{PeerManagerWorker, PeerWorker},
+    penalizer::PenalizerWorker,
     propagator::{PropagatorWorker, PropagatorWorkerEvent},
     requester::{MilestoneRequesterWorker, MilestoneRequesterWorkerEvent},
     responder::{
@@ -57,13 +63,14 @@ pub(crate) use self::{
     },
     solidifier::{MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent},
     status::StatusWorker,
+    sync::{FastSyncWorker, FastSyncWorkerEvent},
 };
 
 pub fn init<N: Node>(
     config: config::ProtocolConfig,
     network_id: (String, u64),
     network_events: NetworkEventRx,
-    autopeering_events: Option<AutopeeringEventRx>,
+    autopeering: Option<(AutopeeringEventRx, AutopeeringCommandTx)>,
     node_builder: N::Builder,
 ) -> N::Builder
 where
@@ -71,18 +78,22 @@ where
 {
     node_builder
         .with_worker::<MetricsWorker>()
-        .with_worker::<PeerManagerResWorker>()
+        .with_worker_cfg::<PeerManagerResWorker>(sync::trusted_peers(&config.fast_sync))
+        .with_worker_cfg::<PenalizerWorker>(config.misbehaviour.clone())
         .with_worker_cfg::<PeerManagerWorker>(PeerManagerConfig {
             network_rx: network_events,
-            peering_rx: autopeering_events,
+            peering: autopeering,
             network_name: network_id.0,
+            misbehaviour: config.misbehaviour.clone(),
+            neighbor_scoring: config.neighbor_scoring.clone(),
         })
         .with_worker_cfg::<HasherWorker>(config.clone())
+        .with_worker_cfg::<FastSyncWorker>(config.clone())
         .with_worker::<ProcessorWorker>()
         .with_worker::<BlockResponderWorker>()
         .with_worker::<MilestoneResponderWorker>()
-        .with_worker::<BlockRequesterWorker>()
-        .with_worker::<MilestoneRequesterWorker>()
+        .with_worker_cfg::<BlockRequesterWorker>(config.workers.clone())
+        .with_worker_cfg::<MilestoneRequesterWorker>(config.workers.clone())
         .with_worker::<PayloadWorker>()
         .with_worker::<TransactionPayloadWorker>()
         .with_worker_cfg::<MilestonePayloadWorker>(config.clone())
@@ -91,10 +102,11 @@ where
         .with_worker::<BroadcasterWorker>()
         .with_worker::<PropagatorWorker>()
         .with_worker::<MpsWorker>()
-        .with_worker_cfg::<MilestoneSolidifierWorker>(config.workers.milestone_sync_count)
+        .with_worker_cfg::<MilestoneSolidifierWorker>(config.workers.clone())
         .with_worker::<IndexUpdaterWorker>()
         .with_worker_cfg::<StatusWorker>(config.workers.status_interval)
         .with_worker::<HeartbeaterWorker>()
         .with_worker::<BlockSubmitterWorker>()
+        .with_worker::<BlockPromoterWorker>()
         .with_worker::<UnreferencedBlockInserterWorker>()
 }
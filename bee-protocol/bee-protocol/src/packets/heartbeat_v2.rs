@@ -0,0 +1,318 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioned heartbeat packet of the protocol.
+
+use std::{cmp, ops::Range};
+
+use crate::packets::{HeartbeatPacket, Packet};
+
+/// Oldest protocol version supported, only made of the legacy packets.
+pub(crate) const PROTOCOL_VERSION_MIN: u8 = 1;
+/// Latest protocol version supported, adding the versioned heartbeat.
+pub(crate) const PROTOCOL_VERSION: u8 = 2;
+
+const FLAG_SERVES_FAST_SYNC: u8 = 0b0000_0001;
+
+const PROTOCOL_VERSION_MIN_SIZE: usize = 1;
+const PROTOCOL_VERSION_MAX_SIZE: usize = 1;
+const SOLID_MILESTONE_INDEX_SIZE: usize = 4;
+const PRUNED_INDEX_SIZE: usize = 4;
+const LATEST_MILESTONE_INDEX_SIZE: usize = 4;
+const SNAPSHOT_INDEX_SIZE: usize = 4;
+const CONNECTED_PEERS_SIZE: usize = 1;
+const SYNCED_PEERS_SIZE: usize = 1;
+const FLAGS_SIZE: usize = 1;
+const PACKET_TYPES_LENGTH_SIZE: usize = 1;
+const CONSTANT_SIZE: usize = PROTOCOL_VERSION_MIN_SIZE
+    + PROTOCOL_VERSION_MAX_SIZE
+    + SOLID_MILESTONE_INDEX_SIZE
+    + PRUNED_INDEX_SIZE
+    + LATEST_MILESTONE_INDEX_SIZE
+    + SNAPSHOT_INDEX_SIZE
+    + CONNECTED_PEERS_SIZE
+    + SYNCED_PEERS_SIZE
+    + FLAGS_SIZE
+    + PACKET_TYPES_LENGTH_SIZE;
+const PACKET_TYPES_MAX_LENGTH: usize = u8::MAX as usize;
+const NODE_VERSION_MAX_LENGTH: usize = 64;
+
+/// A heartbeat also announcing the capabilities of a node and the range of protocol versions it supports.
+///
+/// It replaces the legacy heartbeat with peers having negotiated the gossip protocol version 2 or higher, legacy peers
+/// never receive it.
+#[derive(Clone)]
+pub(crate) struct HeartbeatV2Packet {
+    /// Oldest protocol version supported.
+    pub(crate) protocol_version_min: u8,
+    /// Latest protocol version supported.
+    pub(crate) protocol_version_max: u8,
+    /// Index of the latest solid milestone.
+    pub(crate) solid_milestone_index: u32,
+    /// Pruned index.
+    pub(crate) pruned_index: u32,
+    /// Index of the latest milestone.
+    pub(crate) latest_milestone_index: u32,
+    /// Snapshot index.
+    pub(crate) snapshot_index: u32,
+    /// Number of connected peers.
+    pub(crate) connected_peers: u8,
+    /// Number of synced peers.
+    pub(crate) synced_peers: u8,
+    /// Whether the node serves milestone cones in bulk over sync streams.
+    pub(crate) serves_fast_sync: bool,
+    /// Types of the packets the node supports.
+    pub(crate) packet_types: Vec<u8>,
+    /// Name and version of the node software.
+    pub(crate) node_version: String,
+}
+
+impl HeartbeatV2Packet {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        solid_milestone_index: u32,
+        pruned_index: u32,
+        latest_milestone_index: u32,
+        snapshot_index: u32,
+        connected_peers: u8,
+        synced_peers: u8,
+        serves_fast_sync: bool,
+        packet_types: Vec<u8>,
+        mut node_version: String,
+    ) -> Self {
+        let mut len = cmp::min(node_version.len(), NODE_VERSION_MAX_LENGTH);
+        while !node_version.is_char_boundary(len) {
+            len -= 1;
+        }
+        node_version.truncate(len);
+
+        Self {
+            protocol_version_min: PROTOCOL_VERSION_MIN,
+            protocol_version_max: PROTOCOL_VERSION,
+            solid_milestone_index,
+            pruned_index,
+            latest_milestone_index,
+            snapshot_index,
+            connected_peers,
+            synced_peers,
+            serves_fast_sync,
+            packet_types,
+            node_version,
+        }
+    }
+
+    /// Returns the highest protocol version supported by both this node and the sender of the packet, if any.
+    pub(crate) fn negotiate_version(&self) -> Option<u8> {
+        let version = cmp::min(self.protocol_version_max, PROTOCOL_VERSION);
+
+        (version >= cmp::max(self.protocol_version_min, PROTOCOL_VERSION_MIN)).then_some(version)
+    }
+}
+
+impl From<&HeartbeatV2Packet> for HeartbeatPacket {
+    fn from(packet: &HeartbeatV2Packet) -> Self {
+        HeartbeatPacket::new(
+            packet.solid_milestone_index,
+            packet.pruned_index,
+            packet.latest_milestone_index,
+            packet.connected_peers,
+            packet.synced_peers,
+        )
+    }
+}
+
+impl Packet for HeartbeatV2Packet {
+    const ID: u8 = 0x05;
+
+    fn size_range() -> Range<usize> {
+        (CONSTANT_SIZE)..(CONSTANT_SIZE + PACKET_TYPES_MAX_LENGTH + NODE_VERSION_MAX_LENGTH + 1)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let (bytes, next) = bytes.split_at(PROTOCOL_VERSION_MIN_SIZE);
+        let protocol_version_min = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(PROTOCOL_VERSION_MAX_SIZE);
+        let protocol_version_max = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(SOLID_MILESTONE_INDEX_SIZE);
+        let solid_milestone_index = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(PRUNED_INDEX_SIZE);
+        let pruned_index = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(LATEST_MILESTONE_INDEX_SIZE);
+        let latest_milestone_index = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(SNAPSHOT_INDEX_SIZE);
+        let snapshot_index = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(CONNECTED_PEERS_SIZE);
+        let connected_peers = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(SYNCED_PEERS_SIZE);
+        let synced_peers = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(FLAGS_SIZE);
+        let flags = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(PACKET_TYPES_LENGTH_SIZE);
+        let packet_types_length = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size")) as usize;
+
+        // The advertised length is not trusted, the node version takes whatever remains.
+        let (packet_types, node_version) = next.split_at(cmp::min(packet_types_length, next.len()));
+
+        Self {
+            protocol_version_min,
+            protocol_version_max,
+            solid_milestone_index,
+            pruned_index,
+            latest_milestone_index,
+            snapshot_index,
+            connected_peers,
+            synced_peers,
+            serves_fast_sync: flags & FLAG_SERVES_FAST_SYNC != 0,
+            packet_types: packet_types.to_vec(),
+            node_version: String::from_utf8_lossy(node_version).into_owned(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        CONSTANT_SIZE + self.packet_types.len() + self.node_version.len()
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        let (bytes, next) = bytes.split_at_mut(PROTOCOL_VERSION_MIN_SIZE);
+        bytes.copy_from_slice(&self.protocol_version_min.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(PROTOCOL_VERSION_MAX_SIZE);
+        bytes.copy_from_slice(&self.protocol_version_max.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(SOLID_MILESTONE_INDEX_SIZE);
+        bytes.copy_from_slice(&self.solid_milestone_index.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(PRUNED_INDEX_SIZE);
+        bytes.copy_from_slice(&self.pruned_index.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(LATEST_MILESTONE_INDEX_SIZE);
+        bytes.copy_from_slice(&self.latest_milestone_index.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(SNAPSHOT_INDEX_SIZE);
+        bytes.copy_from_slice(&self.snapshot_index.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(CONNECTED_PEERS_SIZE);
+        bytes.copy_from_slice(&self.connected_peers.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(SYNCED_PEERS_SIZE);
+        bytes.copy_from_slice(&self.synced_peers.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(FLAGS_SIZE);
+        let flags = if self.serves_fast_sync {
+            FLAG_SERVES_FAST_SYNC
+        } else {
+            0
+        };
+        bytes.copy_from_slice(&flags.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(PACKET_TYPES_LENGTH_SIZE);
+        bytes.copy_from_slice(&(self.packet_types.len() as u8).to_le_bytes());
+        let (bytes, next) = next.split_at_mut(self.packet_types.len());
+        bytes.copy_from_slice(&self.packet_types);
+        next.copy_from_slice(self.node_version.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const SOLID_MILESTONE_INDEX: u32 = 0x0118_1f9b;
+    const PRUNED_INDEX: u32 = 0x3dc2_97b4;
+    const LATEST_MILESTONE_INDEX: u32 = 0x60be_20c2;
+    const SNAPSHOT_INDEX: u32 = 0x3dc2_97a0;
+    const CONNECTED_PEERS: u8 = 12;
+    const SYNCED_PEERS: u8 = 5;
+    const PACKET_TYPES: [u8; 5] = [1, 2, 3, 4, 5];
+    const NODE_VERSION: &str = "Bee/0.4.0";
+
+    fn packet() -> HeartbeatV2Packet {
+        HeartbeatV2Packet::new(
+            SOLID_MILESTONE_INDEX,
+            PRUNED_INDEX,
+            LATEST_MILESTONE_INDEX,
+            SNAPSHOT_INDEX,
+            CONNECTED_PEERS,
+            SYNCED_PEERS,
+            true,
+            PACKET_TYPES.to_vec(),
+            NODE_VERSION.to_string(),
+        )
+    }
+
+    #[test]
+    fn id() {
+        assert_eq!(HeartbeatV2Packet::ID, 5);
+    }
+
+    #[test]
+    fn size_range() {
+        assert!(!HeartbeatV2Packet::size_range().contains(&(CONSTANT_SIZE - 1)));
+        assert!(HeartbeatV2Packet::size_range().contains(&CONSTANT_SIZE));
+        assert!(HeartbeatV2Packet::size_range()
+            .contains(&(CONSTANT_SIZE + PACKET_TYPES_MAX_LENGTH + NODE_VERSION_MAX_LENGTH)));
+        assert!(!HeartbeatV2Packet::size_range()
+            .contains(&(CONSTANT_SIZE + PACKET_TYPES_MAX_LENGTH + NODE_VERSION_MAX_LENGTH + 1)));
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(packet().size(), CONSTANT_SIZE + PACKET_TYPES.len() + NODE_VERSION.len());
+    }
+
+    #[test]
+    fn node_version_truncated() {
+        let packet = HeartbeatV2Packet::new(0, 0, 0, 0, 0, 0, false, Vec::new(), "é".repeat(NODE_VERSION_MAX_LENGTH));
+
+        assert!(packet.node_version.len() <= NODE_VERSION_MAX_LENGTH);
+        assert!(HeartbeatV2Packet::size_range().contains(&packet.size()));
+    }
+
+    #[test]
+    fn into_from() {
+        let packet_from = packet();
+        let mut bytes = vec![0u8; packet_from.size()];
+        packet_from.to_bytes(&mut bytes);
+        let packet_to = HeartbeatV2Packet::from_bytes(&bytes);
+
+        assert_eq!(packet_to.protocol_version_min, PROTOCOL_VERSION_MIN);
+        assert_eq!(packet_to.protocol_version_max, PROTOCOL_VERSION);
+        assert_eq!(packet_to.solid_milestone_index, SOLID_MILESTONE_INDEX);
+        assert_eq!(packet_to.pruned_index, PRUNED_INDEX);
+        assert_eq!(packet_to.latest_milestone_index, LATEST_MILESTONE_INDEX);
+        assert_eq!(packet_to.snapshot_index, SNAPSHOT_INDEX);
+        assert_eq!(packet_to.connected_peers, CONNECTED_PEERS);
+        assert_eq!(packet_to.synced_peers, SYNCED_PEERS);
+        assert!(packet_to.serves_fast_sync);
+        assert_eq!(packet_to.packet_types, PACKET_TYPES);
+        assert_eq!(packet_to.node_version, NODE_VERSION);
+    }
+
+    #[test]
+    fn from_invalid_packet_types_length() {
+        let mut bytes = vec![0u8; CONSTANT_SIZE + 2];
+        bytes[CONSTANT_SIZE - 1] = u8::MAX;
+        let packet = HeartbeatV2Packet::from_bytes(&bytes);
+
+        assert_eq!(packet.packet_types.len(), 2);
+        assert!(packet.node_version.is_empty());
+    }
+
+    #[test]
+    fn negotiate_version() {
+        let mut packet = packet();
+
+        assert_eq!(packet.negotiate_version(), Some(PROTOCOL_VERSION));
+
+        packet.protocol_version_max = PROTOCOL_VERSION + 1;
+        assert_eq!(packet.negotiate_version(), Some(PROTOCOL_VERSION));
+
+        packet.protocol_version_min = PROTOCOL_VERSION + 1;
+        assert_eq!(packet.negotiate_version(), None);
+
+        packet.protocol_version_min = PROTOCOL_VERSION_MIN;
+        packet.protocol_version_max = PROTOCOL_VERSION_MIN;
+        assert_eq!(packet.negotiate_version(), Some(PROTOCOL_VERSION_MIN));
+    }
+}
@@ -7,6 +7,7 @@ mod block;
 mod block_request;
 mod header;
 mod heartbeat;
+mod heartbeat_v2;
 mod milestone_request;
 mod tlv;
 
@@ -17,6 +18,7 @@ pub(crate) use self::{
     block_request::BlockRequestPacket,
     header::{HeaderPacket, HEADER_SIZE},
     heartbeat::HeartbeatPacket,
+    heartbeat_v2::{HeartbeatV2Packet, PROTOCOL_VERSION, PROTOCOL_VERSION_MIN},
     milestone_request::MilestoneRequestPacket,
     tlv::{tlv_from_bytes, tlv_to_bytes, Error as TlvError},
 };
@@ -1,33 +1,52 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{any::TypeId, convert::Infallible, sync::Arc};
+use std::{
+    any::TypeId,
+    convert::Infallible,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use bee_autopeering::event::{Event as AutopeeringEvent, EventRx as AutopeeringEventRx};
+use bee_autopeering::{
+    command::{Command as AutopeeringCommand, CommandTx as AutopeeringCommandTx},
+    event::{Event as AutopeeringEvent, EventRx as AutopeeringEventRx},
+};
 use bee_gossip::{
-    alias, Command, Event as NetworkEvent, NetworkCommandSender, NetworkEventReceiver as NetworkEventRx, PeerRelation,
-    ServiceHost,
+    alias, Command, Event as NetworkEvent, NetworkCommandSender, NetworkConnectionHistory,
+    NetworkEventReceiver as NetworkEventRx, PeerRelation, ServiceHost,
 };
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{Tangle, TangleWorker};
 use futures::{channel::oneshot, StreamExt};
 use log::{info, trace, warn};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use parking_lot::Mutex;
+use tokio::time::interval;
+use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
 
 use crate::{
-    heartbeater::{new_heartbeat, send_heartbeat},
-    peer::PeerManager,
+    config::{ProtocolMisbehaviourConfig, ProtocolNeighborScoringConfig},
+    event::ExternalAddressChanged,
+    heartbeater::{handshake_version, new_heartbeat, send_heartbeat},
+    peer::{
+        neighbors::{AutopeeredNeighbors, NeighborQuality},
+        PeerManager,
+    },
     storage::StorageBackend,
     types::{metrics::NodeMetrics, peer::Peer},
-    BlockResponderWorker, HasherWorker, MetricsWorker, MilestoneRequesterWorker, MilestoneResponderWorker,
-    PeerManagerResWorker, PeerWorker, RequestedMilestones,
+    BlockResponderWorker, FastSyncWorker, FastSyncWorkerEvent, HasherWorker, MetricsWorker, MilestoneRequesterWorker,
+    MilestoneResponderWorker, PeerManagerResWorker, PeerWorker, PenalizerWorker, RequestedMilestones,
 };
 
+const NEIGHBOR_SCORING_INTERVAL: Duration = Duration::from_secs(10);
+
 pub(crate) struct PeerManagerConfig {
     pub(crate) network_rx: NetworkEventRx,
-    pub(crate) peering_rx: Option<AutopeeringEventRx>,
+    pub(crate) peering: Option<(AutopeeringEventRx, AutopeeringCommandTx)>,
     pub(crate) network_name: String,
+    pub(crate) misbehaviour: ProtocolMisbehaviourConfig,
+    pub(crate) neighbor_scoring: ProtocolNeighborScoringConfig,
 }
 
 pub(crate) struct PeerManagerWorker {}
@@ -51,6 +70,8 @@ where
             TypeId::of::<MilestoneResponderWorker>(),
             TypeId::of::<MilestoneRequesterWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
+            TypeId::of::<FastSyncWorker>(),
+            TypeId::of::<PenalizerWorker>(),
         ]
         .leak()
     }
@@ -61,19 +82,34 @@ where
         let requested_milestones = node.resource::<RequestedMilestones>();
         let metrics = node.resource::<NodeMetrics>();
         let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        let node_info = node.info();
 
         let hasher = node.worker::<HasherWorker>().unwrap().tx.clone();
         let block_responder = node.worker::<BlockResponderWorker>().unwrap().tx.clone();
         let milestone_responder = node.worker::<MilestoneResponderWorker>().unwrap().tx.clone();
         let milestone_requester = node.worker::<MilestoneRequesterWorker>().unwrap().tx.clone();
+        let fast_sync = node.worker::<FastSyncWorker>().unwrap().tx.clone();
+        let penalizer = node.worker::<PenalizerWorker>().unwrap().tx.clone();
 
         let PeerManagerConfig {
             network_rx,
-            peering_rx,
+            peering,
             network_name,
+            misbehaviour,
+            neighbor_scoring,
         } = config;
 
+        let neighbors = Arc::new(Mutex::new(AutopeeredNeighbors::default()));
+        let (peering_rx, peering_tx) = match peering {
+            Some((peering_rx, peering_tx)) => (Some(peering_rx), Some(peering_tx)),
+            None => (None, None),
+        };
+
         if let Some(peering_rx) = peering_rx {
+            let bus = node.bus();
+            let gossip_command_tx = gossip_command_tx.clone();
+            let neighbors = neighbors.clone();
+
             node.spawn::<Self, _, _>(|shutdown| async move {
                 info!("Autopeering handler running.");
 
@@ -84,13 +120,16 @@ where
 
                     match event {
                         AutopeeringEvent::IncomingPeering { peer, .. } => {
-                            handle_new_peering(peer, &network_name, &gossip_command_tx);
+                            handle_new_peering(peer, &network_name, &gossip_command_tx, &neighbors);
                         }
                         AutopeeringEvent::OutgoingPeering { peer, .. } => {
-                            handle_new_peering(peer, &network_name, &gossip_command_tx);
+                            handle_new_peering(peer, &network_name, &gossip_command_tx, &neighbors);
                         }
                         AutopeeringEvent::PeeringDropped { peer_id } => {
-                            handle_peering_dropped(peer_id, &gossip_command_tx);
+                            handle_peering_dropped(peer_id, &gossip_command_tx, &neighbors);
+                        }
+                        AutopeeringEvent::ExternalAddressChanged { address } => {
+                            bus.dispatch(ExternalAddressChanged { address });
                         }
                         _ => {}
                     }
@@ -100,7 +139,53 @@ where
             });
         }
 
-        let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        if let Some(peering_tx) = peering_tx.clone() {
+            let peer_manager = peer_manager.clone();
+            let tangle = tangle.clone();
+            let connection_history = node.resource::<NetworkConnectionHistory>();
+            let neighbors = neighbors.clone();
+
+            node.spawn::<Self, _, _>(|shutdown| async move {
+                info!("Neighbor scorer running.");
+
+                let mut ticker =
+                    ShutdownStream::new(shutdown, IntervalStream::new(interval(NEIGHBOR_SCORING_INTERVAL)));
+
+                while ticker.next().await.is_some() {
+                    let peer_ids = neighbors.lock().peer_ids();
+
+                    for peer_id in peer_ids {
+                        let peer = match peer_manager.get_map(&peer_id, |peer| peer.0.clone()) {
+                            Some(peer) if peer.is_connected() => peer,
+                            _ => continue,
+                        };
+                        let quality = match NeighborQuality::new(
+                            &peer,
+                            connection_history.get(&peer_id).await.as_ref(),
+                            tangle.get_latest_milestone_index(),
+                        ) {
+                            Some(quality) => quality,
+                            None => continue,
+                        };
+                        let commands = neighbors
+                            .lock()
+                            .score(&peer_id, quality, &neighbor_scoring, Instant::now());
+
+                        for command in commands {
+                            if let AutopeeringCommand::DropNeighbor { .. } = command {
+                                info!("Dropping neighbor {}: {:?}.", peer.alias(), quality);
+                            }
+
+                            if let Err(e) = peering_tx.send(command) {
+                                warn!("Sending autopeering command failed: {}.", e);
+                            }
+                        }
+                    }
+                }
+
+                info!("Neighbor scorer stopped.");
+            });
+        }
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Network handler running.");
@@ -128,6 +213,7 @@ where
                         info: _,
                         gossip_in: receiver,
                         gossip_out: sender,
+                        gossip_version,
                     } => {
                         {
                             let metrics = metrics.clone();
@@ -135,13 +221,18 @@ where
                             let block_responder = block_responder.clone();
                             let milestone_responder = milestone_responder.clone();
                             let milestone_requester = milestone_requester.clone();
+                            let penalizer = penalizer.clone();
                             let tangle = tangle.clone();
                             let requested_milestones = requested_milestones.clone();
+                            let misbehaviour = &misbehaviour;
 
                             peer_manager
                                 .get_mut_map(&peer_id, move |peer| {
                                     let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
+                                    // The protocol version is negotiated again on every connection, the peer may have
+                                    // been updated meanwhile.
+                                    peer.0.set_protocol_version(handshake_version(gossip_version));
                                     peer.0.set_connected(true);
                                     peer.1 = Some((sender, shutdown_tx));
 
@@ -153,6 +244,8 @@ where
                                             block_responder,
                                             milestone_responder,
                                             milestone_requester,
+                                            penalizer,
+                                            misbehaviour,
                                         )
                                         .run(
                                             tangle,
@@ -169,7 +262,7 @@ where
 
                         // TODO can't do it in the if because of deadlock, but it's not really right to do it here.
                         send_heartbeat(
-                            &new_heartbeat(&*tangle, &peer_manager),
+                            &new_heartbeat(&*tangle, &peer_manager, &node_info),
                             &peer_id,
                             &peer_manager,
                             &metrics,
@@ -195,7 +288,29 @@ where
                                 .send(Command::RemovePeer { peer_id })
                                 .expect("send gossip command");
 
-                            // TODO: tell the autopeering to remove that peer from the neighborhood.
+                            // Remove that peer from the neighborhood of the autopeering as well.
+                            if let (Some(autopeering_peer_id), Some(peering_tx)) =
+                                (neighbors.lock().remove(&peer_id), &peering_tx)
+                            {
+                                if let Err(e) = peering_tx.send(AutopeeringCommand::DropNeighbor {
+                                    peer_id: autopeering_peer_id,
+                                }) {
+                                    warn!("Sending autopeering command failed: {}.", e);
+                                }
+                            }
+                        }
+                    }
+                    NetworkEvent::SyncStreamOpened {
+                        peer_id,
+                        origin,
+                        stream,
+                    } => {
+                        if let Err(e) = fast_sync.send(FastSyncWorkerEvent {
+                            peer_id,
+                            origin,
+                            stream,
+                        }) {
+                            warn!("Sending sync stream of {} to the fast sync failed: {}.", peer_id, e);
                         }
                     }
                     _ => (), // Ignore all other events for now
@@ -209,10 +324,17 @@ where
     }
 }
 
-fn handle_new_peering(peer: bee_autopeering::Peer, network_name: &str, gossip_command_tx: &NetworkCommandSender) {
+fn handle_new_peering(
+    peer: bee_autopeering::Peer,
+    network_name: &str,
+    gossip_command_tx: &NetworkCommandSender,
+    neighbors: &Mutex<AutopeeredNeighbors>,
+) {
     if let Some(multiaddr) = peer.service_multiaddr(network_name) {
         let peer_id = peer.peer_id().libp2p_peer_id();
 
+        neighbors.lock().insert(peer_id, *peer.peer_id());
+
         // Panic: sending commands cannot fail due to worker dependencies: because the "Peer Manager" depends on
         // the `bee-gossip` "ServiceHost", it is guaranteed that the receiver of this channel is not dropped
         // before the sender.
@@ -227,9 +349,18 @@ fn handle_new_peering(peer: bee_autopeering::Peer, network_name: &str, gossip_co
     }
 }
 
-fn handle_peering_dropped(peer_id: bee_autopeering::PeerId, gossip_command_tx: &NetworkCommandSender) {
+fn handle_peering_dropped(
+    peer_id: bee_autopeering::PeerId,
+    gossip_command_tx: &NetworkCommandSender,
+    neighbors: &Mutex<AutopeeredNeighbors>,
+) {
     let peer_id = peer_id.libp2p_peer_id();
 
+    // The peer was already removed if the gossip layer couldn't reach it.
+    if neighbors.lock().remove(&peer_id).is_none() {
+        return;
+    }
+
     // Panic: sending commands cannot fail: same explanation as in other sender usages.
     gossip_command_tx
         .send(Command::RemovePeer { peer_id })
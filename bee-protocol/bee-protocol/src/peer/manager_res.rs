@@ -12,7 +12,7 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bee_gossip::{GossipSender, PeerId};
+use bee_gossip::{GossipSender, PeerId, PeerRelation};
 use bee_runtime::{node::Node, worker::Worker};
 use futures::channel::oneshot;
 use log::debug;
@@ -24,11 +24,12 @@ pub struct PeerManagerResWorker {}
 
 #[async_trait]
 impl<N: Node> Worker<N> for PeerManagerResWorker {
-    type Config = ();
+    /// The peers trusted for fast sync.
+    type Config = Vec<PeerId>;
     type Error = Infallible;
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
-        node.register_resource(PeerManager::new());
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        node.register_resource(PeerManager::with_trusted_peers(config));
 
         Ok(Self {})
     }
@@ -89,11 +90,29 @@ impl PeerManagerInner {
 pub struct PeerManager {
     inner: RwLock<PeerManagerInner>,
     counter: AtomicUsize,
+    trusted_peers: Vec<PeerId>,
 }
 
 impl PeerManager {
-    pub(crate) fn new() -> Self {
-        Self::default()
+    pub(crate) fn with_trusted_peers(trusted_peers: Vec<PeerId>) -> Self {
+        Self {
+            trusted_peers,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the peers trusted for fast sync.
+    pub(crate) fn trusted_peers(&self) -> &[PeerId] {
+        &self.trusted_peers
+    }
+
+    /// Returns whether milestone cones are served to a peer over sync streams, i.e. whether it was manually added or
+    /// is trusted.
+    pub(crate) fn serves_fast_sync(&self, id: &PeerId) -> bool {
+        self.trusted_peers.contains(id)
+            || self
+                .get_map(id, |peer| peer.0.relation() == PeerRelation::Known)
+                .unwrap_or(false)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -136,7 +155,7 @@ impl PeerManager {
         lock.remove(id)
     }
 
-    pub(crate) fn for_each<F: Fn(&PeerId, &Peer)>(&self, f: F) {
+    pub(crate) fn for_each<F: FnMut(&PeerId, &Peer)>(&self, mut f: F) {
         self.inner.read().peers.iter().for_each(|(id, (peer, _))| f(id, peer));
     }
 
@@ -185,3 +204,43 @@ impl PeerManager {
         self.inner.read().peers.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bee_gossip::PeerInfo;
+
+    use super::*;
+
+    fn add_peer(peer_manager: &PeerManager, peer_id: PeerId, relation: PeerRelation) {
+        peer_manager.add(Arc::new(Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: peer_id.to_string(),
+                relation,
+            },
+        )));
+    }
+
+    #[test]
+    fn serves_fast_sync_to_known_and_trusted_peers() {
+        let trusted = PeerId::random();
+        let peer_manager = PeerManager::with_trusted_peers(vec![trusted]);
+        let known = PeerId::random();
+        let unknown = PeerId::random();
+        let discovered = PeerId::random();
+
+        add_peer(&peer_manager, known, PeerRelation::Known);
+        add_peer(&peer_manager, unknown, PeerRelation::Unknown);
+        add_peer(&peer_manager, discovered, PeerRelation::Discovered);
+
+        assert!(peer_manager.serves_fast_sync(&known));
+        assert!(peer_manager.serves_fast_sync(&trusted));
+        assert!(!peer_manager.serves_fast_sync(&unknown));
+        assert!(!peer_manager.serves_fast_sync(&discovered));
+
+        // Trusted peers are served whatever their relation.
+        add_peer(&peer_manager, trusted, PeerRelation::Discovered);
+        assert!(peer_manager.serves_fast_sync(&trusted));
+    }
+}
@@ -3,7 +3,9 @@
 
 mod manager;
 mod manager_res;
+mod neighbors;
 mod packet_handler;
+mod rate_limiter;
 
 use std::sync::Arc;
 
@@ -11,21 +13,26 @@ use bee_block::payload::milestone::MilestoneIndex;
 use bee_runtime::resource::ResourceHandle;
 use bee_tangle::Tangle;
 use futures::{channel::oneshot, future::FutureExt};
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub(crate) use self::manager::{PeerManagerConfig, PeerManagerWorker};
 pub use self::manager_res::{PeerManager, PeerManagerResWorker};
 use crate::{
+    config::ProtocolMisbehaviourConfig,
     packets::{
-        tlv_from_bytes, BlockPacket, BlockRequestPacket, HeaderPacket, HeartbeatPacket, MilestoneRequestPacket, Packet,
-        TlvError,
+        tlv_from_bytes, BlockPacket, BlockRequestPacket, HeaderPacket, HeartbeatPacket, HeartbeatV2Packet,
+        MilestoneRequestPacket, Packet, TlvError, HEADER_SIZE, PROTOCOL_VERSION, PROTOCOL_VERSION_MIN,
     },
-    peer::packet_handler::PacketHandler,
+    peer::{packet_handler::PacketHandler, rate_limiter::RateLimiter},
+    penalizer::{penalize, Misbehaviour, PenalizerWorkerEvent},
     requester::request_latest_milestone,
     storage::StorageBackend,
-    types::{metrics::NodeMetrics, peer::Peer},
+    types::{
+        metrics::NodeMetrics,
+        peer::{Peer, PeerCapabilities},
+    },
     BlockResponderWorkerEvent, HasherWorkerEvent, MilestoneRequesterWorkerEvent, MilestoneResponderWorkerEvent,
     RequestedMilestones,
 };
@@ -49,9 +56,12 @@ pub struct PeerWorker {
     block_responder: mpsc::UnboundedSender<BlockResponderWorkerEvent>,
     milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
     milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+    penalizer: mpsc::UnboundedSender<PenalizerWorkerEvent>,
+    rate_limiter: RateLimiter,
 }
 
 impl PeerWorker {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         peer: Arc<Peer>,
         metrics: ResourceHandle<NodeMetrics>,
@@ -59,6 +69,8 @@ impl PeerWorker {
         block_responder: mpsc::UnboundedSender<BlockResponderWorkerEvent>,
         milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
         milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+        penalizer: mpsc::UnboundedSender<PenalizerWorkerEvent>,
+        misbehaviour: &ProtocolMisbehaviourConfig,
     ) -> Self {
         Self {
             peer,
@@ -67,6 +79,8 @@ impl PeerWorker {
             block_responder,
             milestone_responder,
             milestone_requester,
+            penalizer,
+            rate_limiter: RateLimiter::new(misbehaviour),
         }
     }
 
@@ -79,6 +93,8 @@ impl PeerWorker {
     ) {
         info!("[{}] Running.", self.peer.alias());
 
+        self.peer.set_capabilities(None);
+
         let shutdown_fused = shutdown.fuse();
 
         let mut packet_handler = PacketHandler::new(receiver, shutdown_fused, self.peer.address().clone());
@@ -96,16 +112,43 @@ impl PeerWorker {
         while let Some((header, bytes)) = packet_handler.fetch_packet().await {
             let tangle = tangle.upgrade().expect("Needed Tangle resource but it was removed");
 
+            self.record_bytes_received(header.packet_type, HEADER_SIZE + bytes.len());
+
+            if !self.rate_limiter.allows(header.packet_type) {
+                trace!(
+                    "[{}] Dropping packet of type {}: rate limit exceeded.",
+                    self.peer.alias(),
+                    header.packet_type
+                );
+                penalize(&self.penalizer, *self.peer.id(), Misbehaviour::RateLimitExceeded);
+                continue;
+            }
+
             if let Err(e) = self.process_packet(&tangle, &header, bytes) {
                 error!("[{}] Processing packet failed: {:?}.", self.peer.alias(), e);
                 self.peer.metrics().invalid_packets_inc();
                 self.metrics.invalid_packets_inc();
+                penalize(&self.penalizer, *self.peer.id(), Misbehaviour::InvalidPacket);
             }
         }
 
         info!("[{}] Stopped.", self.peer.alias());
     }
 
+    fn record_bytes_received(&self, packet_type: u8, len: usize) {
+        let metrics = self.peer.metrics();
+        let len = len as u64;
+
+        match packet_type {
+            MilestoneRequestPacket::ID => metrics.milestone_request_bytes_received_add(len),
+            BlockPacket::ID => metrics.block_bytes_received_add(len),
+            BlockRequestPacket::ID => metrics.block_request_bytes_received_add(len),
+            HeartbeatPacket::ID | HeartbeatV2Packet::ID => metrics.heartbeat_bytes_received_add(len),
+            // Unsupported packets are counted as invalid when processed.
+            _ => return,
+        };
+    }
+
     fn process_packet<B: StorageBackend>(
         &mut self,
         tangle: &Tangle<B>,
@@ -158,34 +201,69 @@ impl PeerWorker {
 
                 let packet = tlv_from_bytes::<HeartbeatPacket>(header, bytes)?;
 
-                self.peer.set_solid_milestone_index(packet.solid_milestone_index.into());
-                self.peer.set_pruned_index(packet.pruned_index.into());
-                self.peer
-                    .set_latest_milestone_index(packet.latest_milestone_index.into());
-                self.peer.set_connected_peers(packet.connected_peers);
-                self.peer.set_synced_peers(packet.synced_peers);
-                self.peer.set_heartbeat_received_timestamp();
-
-                if !tangle.is_synced()
-                    && !self
-                        .peer
-                        .has_data(MilestoneIndex(*tangle.get_solid_milestone_index() + 1))
-                {
-                    debug!(
-                        "The peer {} can't help syncing because the required index {} is not in its database [{};{}].",
+                self.process_heartbeat(tangle, &packet);
+            }
+            HeartbeatV2Packet::ID => {
+                trace!("[{}] Reading HeartbeatV2Packet...", self.peer.alias());
+
+                let packet = tlv_from_bytes::<HeartbeatV2Packet>(header, bytes)?;
+
+                match packet.negotiate_version() {
+                    Some(version) => {
+                        if version != self.peer.protocol_version() {
+                            debug!("[{}] Negotiated protocol version {}.", self.peer.alias(), version);
+                        }
+                        self.peer.set_protocol_version(version);
+                    }
+                    None => warn!(
+                        "[{}] No common protocol version: peer supports [{};{}], node supports [{};{}].",
                         self.peer.alias(),
-                        *tangle.get_solid_milestone_index() + 1,
-                        packet.pruned_index,
-                        packet.solid_milestone_index
-                    );
+                        packet.protocol_version_min,
+                        packet.protocol_version_max,
+                        PROTOCOL_VERSION_MIN,
+                        PROTOCOL_VERSION
+                    ),
                 }
 
-                self.peer.metrics().heartbeats_received_inc();
-                self.metrics.heartbeats_received_inc();
+                self.process_heartbeat(tangle, &(&packet).into());
+
+                self.peer.set_capabilities(Some(PeerCapabilities::new(
+                    packet.node_version,
+                    packet.packet_types,
+                    packet.serves_fast_sync,
+                    packet.snapshot_index.into(),
+                )));
             }
             _ => return Err(Error::UnsupportedPacketType(header.packet_type)),
         };
 
         Ok(())
     }
+
+    fn process_heartbeat<B: StorageBackend>(&self, tangle: &Tangle<B>, packet: &HeartbeatPacket) {
+        self.peer.set_solid_milestone_index(packet.solid_milestone_index.into());
+        self.peer.set_pruned_index(packet.pruned_index.into());
+        self.peer
+            .set_latest_milestone_index(packet.latest_milestone_index.into());
+        self.peer.set_connected_peers(packet.connected_peers);
+        self.peer.set_synced_peers(packet.synced_peers);
+        self.peer.set_heartbeat_received_timestamp();
+
+        if !tangle.is_synced()
+            && !self
+                .peer
+                .has_data(MilestoneIndex(*tangle.get_solid_milestone_index() + 1))
+        {
+            debug!(
+                "The peer {} can't help syncing because the required index {} is not in its database [{};{}].",
+                self.peer.alias(),
+                *tangle.get_solid_milestone_index() + 1,
+                packet.pruned_index,
+                packet.solid_milestone_index
+            );
+        }
+
+        self.peer.metrics().heartbeats_received_inc();
+        self.metrics.heartbeats_received_inc();
+    }
 }
@@ -0,0 +1,244 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scores of the autopeered neighbors, combining the quality signals observed by the gossip and protocol layers.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bee_autopeering::{Command as AutopeeringCommand, PeerId as AutopeeringPeerId, Score, MAX_SCORE};
+use bee_block::payload::milestone::MilestoneIndex;
+use bee_gossip::{ConnectionHistory, PeerId};
+
+use crate::{config::ProtocolNeighborScoringConfig, types::peer::Peer};
+
+// Number of milestones a neighbor may lag behind and still be considered synced.
+const SYNCED_THRESHOLD: u32 = 2;
+// Weights of the quality signals, summing up to `MAX_SCORE`.
+const SYNC_WEIGHT: f64 = 40.0;
+const RESPONSIVENESS_WEIGHT: f64 = 30.0;
+const UPTIME_WEIGHT: f64 = 20.0;
+const VALIDITY_WEIGHT: f64 = 10.0;
+// Number of invalid packets that halve the validity of a neighbor.
+const INVALID_PACKETS_HALVING: f64 = 10.0;
+
+/// The quality signals of a neighbor.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NeighborQuality {
+    /// Whether the neighbor recently reported in a heartbeat that it is synced.
+    pub(crate) synced: bool,
+    /// The responsiveness of the neighbor, between 0 and 1, combining its success rate and latency.
+    pub(crate) responsiveness: f64,
+    /// The percentage of time the neighbor was connected.
+    pub(crate) uptime: u8,
+    /// The number of invalid packets the neighbor sent.
+    pub(crate) invalid_packets: u64,
+}
+
+impl NeighborQuality {
+    /// Collects the quality signals of a neighbor, or returns `None` if it didn't send a heartbeat yet, as its sync
+    /// status is unknown until then.
+    pub(crate) fn new(
+        peer: &Peer,
+        connection_history: Option<&ConnectionHistory>,
+        latest_milestone_index: MilestoneIndex,
+    ) -> Option<Self> {
+        if peer.heartbeat_received_timestamp() == 0 {
+            return None;
+        }
+
+        Some(Self {
+            synced: *peer.solid_milestone_index() >= (*latest_milestone_index).saturating_sub(SYNCED_THRESHOLD),
+            responsiveness: peer.responsiveness(),
+            // Neighbors are scored while they are connected, so they start out with full uptime.
+            uptime: connection_history.map_or(100, |history| history.uptime),
+            invalid_packets: peer.metrics().invalid_packets(),
+        })
+    }
+
+    /// Returns the score of the neighbor.
+    pub(crate) fn score(&self) -> Score {
+        let sync = if self.synced { 1.0 } else { 0.0 };
+        let responsiveness = self.responsiveness.clamp(0.0, 1.0);
+        let uptime = f64::from(self.uptime.min(100)) / 100.0;
+        let validity = 1.0 / (1.0 + self.invalid_packets as f64 / INVALID_PACKETS_HALVING);
+
+        let score = SYNC_WEIGHT * sync
+            + RESPONSIVENESS_WEIGHT * responsiveness
+            + UPTIME_WEIGHT * uptime
+            + VALIDITY_WEIGHT * validity;
+
+        (score.round() as Score).min(MAX_SCORE)
+    }
+}
+
+struct Neighbor {
+    peer_id: AutopeeringPeerId,
+    unsynced_since: Option<Instant>,
+}
+
+/// The neighbors peered through the autopeering, by their gossip identity.
+#[derive(Default)]
+pub(crate) struct AutopeeredNeighbors {
+    neighbors: HashMap<PeerId, Neighbor>,
+}
+
+impl AutopeeredNeighbors {
+    /// Adds a neighbor.
+    pub(crate) fn insert(&mut self, peer_id: PeerId, autopeering_peer_id: AutopeeringPeerId) {
+        self.neighbors.insert(
+            peer_id,
+            Neighbor {
+                peer_id: autopeering_peer_id,
+                unsynced_since: None,
+            },
+        );
+    }
+
+    /// Removes a neighbor, returning its autopeering identity.
+    pub(crate) fn remove(&mut self, peer_id: &PeerId) -> Option<AutopeeringPeerId> {
+        self.neighbors.remove(peer_id).map(|neighbor| neighbor.peer_id)
+    }
+
+    /// Returns the gossip identities of the neighbors.
+    pub(crate) fn peer_ids(&self) -> Vec<PeerId> {
+        self.neighbors.keys().copied().collect()
+    }
+
+    /// Scores a neighbor, and returns the commands telling the autopeering about its score and whether to drop it.
+    pub(crate) fn score(
+        &mut self,
+        peer_id: &PeerId,
+        quality: NeighborQuality,
+        config: &ProtocolNeighborScoringConfig,
+        now: Instant,
+    ) -> Vec<AutopeeringCommand> {
+        let neighbor = match self.neighbors.get_mut(peer_id) {
+            Some(neighbor) => neighbor,
+            None => return Vec::new(),
+        };
+
+        let score = quality.score();
+        let mut commands = vec![AutopeeringCommand::SetScore {
+            peer_id: neighbor.peer_id,
+            score,
+        }];
+
+        let unsynced_for = if quality.synced {
+            neighbor.unsynced_since = None;
+            Duration::ZERO
+        } else {
+            now.saturating_duration_since(*neighbor.unsynced_since.get_or_insert(now))
+        };
+
+        if score < config.min_score || unsynced_for >= Duration::from_secs(config.max_unsynced_time) {
+            commands.push(AutopeeringCommand::DropNeighbor {
+                peer_id: neighbor.peer_id,
+            });
+        }
+
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_gossip::{PeerInfo, PeerRelation};
+
+    use super::*;
+
+    const CONFIG: ProtocolNeighborScoringConfig = ProtocolNeighborScoringConfig {
+        min_score: 30,
+        max_unsynced_time: 300,
+    };
+
+    fn quality(synced: bool, responsiveness: f64, uptime: u8, invalid_packets: u64) -> NeighborQuality {
+        NeighborQuality {
+            synced,
+            responsiveness,
+            uptime,
+            invalid_packets,
+        }
+    }
+
+    fn drops(neighbors: &mut AutopeeredNeighbors, peer_id: &PeerId, quality: NeighborQuality, now: Instant) -> bool {
+        neighbors
+            .score(peer_id, quality, &CONFIG, now)
+            .iter()
+            .any(|command| matches!(command, AutopeeringCommand::DropNeighbor { .. }))
+    }
+
+    #[test]
+    fn neighbor_score() {
+        assert_eq!(quality(true, 1.0, 100, 0).score(), MAX_SCORE);
+        assert_eq!(quality(false, 0.0, 0, u64::MAX).score(), 0);
+        assert_eq!(quality(false, 1.0, 100, 0).score(), 60);
+        assert_eq!(quality(true, 0.5, 50, 10).score(), 70);
+    }
+
+    #[test]
+    fn drop_neighbors() {
+        let mut neighbors = AutopeeredNeighbors::default();
+        let (peer_id, autopeering_peer_id) = (PeerId::random(), AutopeeringPeerId::generate());
+        let (synced, unsynced) = (quality(true, 1.0, 100, 0), quality(false, 1.0, 100, 0));
+        let now = Instant::now();
+
+        // Unknown neighbors are not scored.
+        assert!(neighbors.score(&peer_id, synced, &CONFIG, now).is_empty());
+
+        neighbors.insert(peer_id, autopeering_peer_id);
+
+        assert!(!drops(&mut neighbors, &peer_id, synced, now));
+        // Bad neighbors are dropped right away.
+        assert!(drops(&mut neighbors, &peer_id, quality(false, 0.0, 50, 100), now));
+
+        // Unsynced neighbors are given some time to catch up.
+        assert!(!drops(&mut neighbors, &peer_id, unsynced, now));
+        assert!(!drops(
+            &mut neighbors,
+            &peer_id,
+            unsynced,
+            now + Duration::from_secs(299)
+        ));
+        assert!(drops(
+            &mut neighbors,
+            &peer_id,
+            unsynced,
+            now + Duration::from_secs(300)
+        ));
+
+        // Catching up resets the clock.
+        assert!(!drops(&mut neighbors, &peer_id, synced, now + Duration::from_secs(300)));
+        assert!(!drops(
+            &mut neighbors,
+            &peer_id,
+            unsynced,
+            now + Duration::from_secs(400)
+        ));
+
+        assert_eq!(neighbors.remove(&peer_id), Some(autopeering_peer_id));
+        assert!(neighbors.peer_ids().is_empty());
+    }
+
+    #[test]
+    fn neighbors_without_heartbeat_are_not_scored() {
+        let peer_id = PeerId::random();
+        let peer = Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: peer_id.to_string(),
+                relation: PeerRelation::Discovered,
+            },
+        );
+
+        assert!(NeighborQuality::new(&peer, None, MilestoneIndex(10)).is_none());
+
+        peer.set_heartbeat_received_timestamp();
+        peer.set_solid_milestone_index(MilestoneIndex(10));
+
+        assert!(NeighborQuality::new(&peer, None, MilestoneIndex(10)).unwrap().synced);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Instant;
+
+use crate::{
+    config::ProtocolMisbehaviourConfig,
+    packets::{BlockPacket, BlockRequestPacket, HeartbeatPacket, HeartbeatV2Packet, MilestoneRequestPacket, Packet},
+};
+
+// Number of seconds worth of packets a peer may send in a burst.
+const BURST_SECONDS: f64 = 5.0;
+
+/// A token bucket refilled at a constant rate.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        let rate = f64::from(rate);
+
+        Self {
+            rate,
+            tokens: rate * BURST_SECONDS,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    fn try_take_at(&mut self, now: Instant) -> bool {
+        // A rate of 0 disables the limit.
+        if self.rate == 0.0 {
+            return true;
+        }
+
+        self.tokens = (self.tokens + self.rate * (now - self.refilled_at).as_secs_f64()).min(self.rate * BURST_SECONDS);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Inbound rate limits of a peer, per packet type.
+pub(super) struct RateLimiter {
+    blocks: TokenBucket,
+    requests: TokenBucket,
+    heartbeats: TokenBucket,
+}
+
+impl RateLimiter {
+    pub(super) fn new(config: &ProtocolMisbehaviourConfig) -> Self {
+        Self {
+            blocks: TokenBucket::new(config.block_rate),
+            requests: TokenBucket::new(config.request_rate),
+            heartbeats: TokenBucket::new(config.heartbeat_rate),
+        }
+    }
+
+    /// Returns whether a packet of the given type is within the rate limits.
+    pub(super) fn allows(&mut self, packet_type: u8) -> bool {
+        match packet_type {
+            BlockPacket::ID => self.blocks.try_take(),
+            BlockRequestPacket::ID | MilestoneRequestPacket::ID => self.requests.try_take(),
+            HeartbeatPacket::ID | HeartbeatV2Packet::ID => self.heartbeats.try_take(),
+            // Unsupported packets are rejected and penalized when processed.
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::config::ProtocolConfig;
+
+    #[test]
+    fn bucket_allows_burst() {
+        let mut bucket = TokenBucket::new(2);
+        let now = bucket.refilled_at;
+
+        for _ in 0..10 {
+            assert!(bucket.try_take_at(now));
+        }
+        assert!(!bucket.try_take_at(now));
+    }
+
+    #[test]
+    fn bucket_refills() {
+        let mut bucket = TokenBucket::new(2);
+        let now = bucket.refilled_at;
+
+        while bucket.try_take_at(now) {}
+
+        assert!(!bucket.try_take_at(now + Duration::from_millis(400)));
+
+        let later = now + Duration::from_millis(1100);
+
+        assert!(bucket.try_take_at(later));
+        assert!(bucket.try_take_at(later));
+        assert!(!bucket.try_take_at(later));
+    }
+
+    #[test]
+    fn bucket_refill_is_capped_by_burst() {
+        let mut bucket = TokenBucket::new(2);
+        let now = bucket.refilled_at + Duration::from_secs(100);
+
+        for _ in 0..10 {
+            assert!(bucket.try_take_at(now));
+        }
+        assert!(!bucket.try_take_at(now));
+    }
+
+    #[test]
+    fn bucket_without_rate_is_unlimited() {
+        let mut bucket = TokenBucket::new(0);
+
+        for _ in 0..1000 {
+            assert!(bucket.try_take());
+        }
+    }
+
+    #[test]
+    fn limits_per_packet_type() {
+        let mut rate_limiter = RateLimiter::new(
+            &ProtocolConfig::build()
+                .misbehaviour_heartbeat_rate(1)
+                .misbehaviour_request_rate(0)
+                .finish()
+                .misbehaviour,
+        );
+
+        for _ in 0..5 {
+            assert!(rate_limiter.allows(HeartbeatPacket::ID));
+        }
+        assert!(!rate_limiter.allows(HeartbeatV2Packet::ID));
+
+        // Other packet types have their own limits.
+        assert!(rate_limiter.allows(BlockPacket::ID));
+        for _ in 0..1000 {
+            assert!(rate_limiter.allows(MilestoneRequestPacket::ID));
+        }
+        assert!(rate_limiter.allows(u8::MAX));
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Penalties of misbehaving peers, temporarily banned once their penalty crosses a threshold.
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    convert::Infallible,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bee_gossip::{alias, Command, NetworkCommandSender, PeerId, ServiceHost};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::StreamExt;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use tokio::{sync::mpsc, time::interval};
+use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
+
+use crate::config::ProtocolMisbehaviourConfig;
+
+const DECAY_INTERVAL: Duration = Duration::from_secs(1);
+// Penalty forgiven every decay interval, so that occasional misbehaviour never leads to a ban.
+const PENALTY_DECAY: u32 = 1;
+
+/// Kinds of misbehaviour a peer is penalized for.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Misbehaviour {
+    /// The peer sent a packet that couldn't be parsed.
+    InvalidPacket,
+    /// The peer sent a block that is invalid or doesn't have enough PoW.
+    InvalidBlock,
+    /// The peer sent a response that wasn't requested.
+    UnsolicitedResponse,
+    /// The peer sent packets faster than its rate limit.
+    RateLimitExceeded,
+}
+
+impl Misbehaviour {
+    fn penalty(&self) -> u32 {
+        match self {
+            Self::InvalidPacket => 10,
+            Self::InvalidBlock => 20,
+            Self::UnsolicitedResponse => 5,
+            Self::RateLimitExceeded => 1,
+        }
+    }
+}
+
+pub(crate) struct PenalizerWorkerEvent {
+    pub(crate) peer_id: PeerId,
+    pub(crate) misbehaviour: Misbehaviour,
+}
+
+pub(crate) struct PenalizerWorker {
+    pub(crate) tx: mpsc::UnboundedSender<PenalizerWorkerEvent>,
+}
+
+pub(crate) fn penalize(
+    penalizer: &mpsc::UnboundedSender<PenalizerWorkerEvent>,
+    peer_id: PeerId,
+    misbehaviour: Misbehaviour,
+) {
+    if let Err(e) = penalizer.send(PenalizerWorkerEvent { peer_id, misbehaviour }) {
+        warn!("Penalizing {} failed: {}.", alias!(peer_id), e);
+    }
+}
+
+#[derive(Default)]
+struct Penalties {
+    penalties: HashMap<PeerId, u32>,
+    bans: HashMap<PeerId, Instant>,
+}
+
+impl Penalties {
+    /// Adds the penalty of a misbehaviour to a peer and bans it if its penalty reaches the threshold, a threshold of 0
+    /// disabling bans. Returns the penalty of the peer if it got banned.
+    fn penalize(
+        &mut self,
+        peer_id: PeerId,
+        misbehaviour: Misbehaviour,
+        config: &ProtocolMisbehaviourConfig,
+        now: Instant,
+    ) -> Option<u32> {
+        // Banned peers aren't penalized until their ban expires.
+        if self.bans.contains_key(&peer_id) {
+            return None;
+        }
+
+        let penalty = self.penalties.entry(peer_id).or_default();
+        *penalty = penalty.saturating_add(misbehaviour.penalty());
+
+        debug!("Penalized {} for {:?}: {}.", alias!(peer_id), misbehaviour, penalty);
+
+        if config.ban_threshold == 0 || *penalty < config.ban_threshold {
+            return None;
+        }
+
+        let penalty = *penalty;
+
+        self.penalties.remove(&peer_id);
+        self.bans
+            .insert(peer_id, now + Duration::from_secs(config.ban_duration));
+
+        Some(penalty)
+    }
+
+    /// Forgives part of the penalties and lifts the expired bans.
+    fn decay(&mut self, now: Instant) {
+        self.penalties.retain(|_, penalty| {
+            *penalty = penalty.saturating_sub(PENALTY_DECAY);
+            *penalty > 0
+        });
+        self.bans.retain(|_, until| *until > now);
+    }
+}
+
+fn send_command(gossip_command_tx: &NetworkCommandSender, command: Command) {
+    if let Err(e) = gossip_command_tx.send(command) {
+        warn!("Sending gossip command failed: {}.", e);
+    }
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for PenalizerWorker {
+    type Config = ProtocolMisbehaviourConfig;
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<ServiceHost>()].leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        let penalties = Arc::new(Mutex::new(Penalties::default()));
+        let ban_duration = Duration::from_secs(config.ban_duration);
+
+        node.spawn::<Self, _, _>({
+            let penalties = penalties.clone();
+
+            |shutdown| async move {
+                info!("Running.");
+
+                let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+
+                while let Some(PenalizerWorkerEvent { peer_id, misbehaviour }) = receiver.next().await {
+                    let penalty = match penalties
+                        .lock()
+                        .penalize(peer_id, misbehaviour, &config, Instant::now())
+                    {
+                        Some(penalty) => penalty,
+                        None => continue,
+                    };
+
+                    warn!(
+                        "Banning {} for {}s after {:?}, penalty {} reached the threshold.",
+                        alias!(peer_id),
+                        config.ban_duration,
+                        misbehaviour,
+                        penalty
+                    );
+
+                    // The gossip layer lifts the ban once it expires, even across restarts.
+                    let expires_at = (SystemTime::now() + ban_duration)
+                        .duration_since(UNIX_EPOCH)
+                        .map(|expires_at| expires_at.as_secs())
+                        .ok();
+
+                    send_command(
+                        &gossip_command_tx,
+                        Command::BanPeer {
+                            peer_id,
+                            expires_at,
+                            reason: Some(format!("{:?}", misbehaviour)),
+                        },
+                    );
+                    send_command(&gossip_command_tx, Command::DisconnectPeer { peer_id });
+                }
+
+                info!("Stopped.");
+            }
+        });
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Decayer running.");
+
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(DECAY_INTERVAL)));
+
+            while ticker.next().await.is_some() {
+                penalties.lock().decay(Instant::now());
+            }
+
+            info!("Decayer stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProtocolConfig;
+
+    fn config(ban_threshold: u32) -> ProtocolMisbehaviourConfig {
+        ProtocolConfig::build()
+            .misbehaviour_ban_threshold(ban_threshold)
+            .misbehaviour_ban_duration(60)
+            .finish()
+            .misbehaviour
+    }
+
+    #[test]
+    fn ban_at_threshold() {
+        let config = config(20);
+        let mut penalties = Penalties::default();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+
+        assert_eq!(
+            penalties.penalize(peer_id, Misbehaviour::InvalidPacket, &config, now),
+            None
+        );
+        assert_eq!(penalties.penalties[&peer_id], 10);
+        assert_eq!(
+            penalties.penalize(peer_id, Misbehaviour::InvalidPacket, &config, now),
+            Some(20)
+        );
+        assert!(!penalties.penalties.contains_key(&peer_id));
+        assert_eq!(penalties.bans[&peer_id], now + Duration::from_secs(60));
+
+        // Banned peers aren't penalized any further.
+        assert_eq!(
+            penalties.penalize(peer_id, Misbehaviour::InvalidBlock, &config, now),
+            None
+        );
+        assert!(!penalties.penalties.contains_key(&peer_id));
+    }
+
+    #[test]
+    fn no_ban_without_threshold() {
+        let config = config(0);
+        let mut penalties = Penalties::default();
+        let peer_id = PeerId::random();
+
+        for _ in 0..100 {
+            assert_eq!(
+                penalties.penalize(peer_id, Misbehaviour::InvalidBlock, &config, Instant::now()),
+                None
+            );
+        }
+
+        assert!(penalties.bans.is_empty());
+        assert_eq!(penalties.penalties[&peer_id], 2000);
+    }
+
+    #[test]
+    fn penalties_decay() {
+        let config = config(20);
+        let mut penalties = Penalties::default();
+        let (peer_1, peer_2) = (PeerId::random(), PeerId::random());
+        let now = Instant::now();
+
+        penalties.penalize(peer_1, Misbehaviour::RateLimitExceeded, &config, now);
+        penalties.penalize(peer_2, Misbehaviour::UnsolicitedResponse, &config, now);
+        penalties.decay(now);
+
+        assert!(!penalties.penalties.contains_key(&peer_1));
+        assert_eq!(penalties.penalties[&peer_2], 4);
+
+        // Occasional misbehaviour never leads to a ban.
+        for _ in 0..100 {
+            assert_eq!(
+                penalties.penalize(peer_1, Misbehaviour::RateLimitExceeded, &config, now),
+                None
+            );
+            penalties.decay(now);
+        }
+    }
+
+    #[test]
+    fn bans_expire() {
+        let config = config(10);
+        let mut penalties = Penalties::default();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+
+        assert_eq!(
+            penalties.penalize(peer_id, Misbehaviour::InvalidPacket, &config, now),
+            Some(10)
+        );
+
+        penalties.decay(now + Duration::from_secs(59));
+        assert!(penalties.bans.contains_key(&peer_id));
+
+        penalties.decay(now + Duration::from_secs(60));
+        assert!(penalties.bans.is_empty());
+
+        // The peer is penalized again once its ban expired.
+        assert_eq!(
+            penalties.penalize(peer_id, Misbehaviour::UnsolicitedResponse, &config, now),
+            None
+        );
+        assert_eq!(penalties.penalties[&peer_id], 5);
+    }
+}
@@ -7,12 +7,13 @@ use std::{
     collections::HashMap,
     convert::Infallible,
     sync::Arc,
-    time::{Duration, Instant},
+    time::Instant,
 };
 
 use async_priority_queue::PriorityQueue;
 use async_trait::async_trait;
 use bee_block::{payload::milestone::MilestoneIndex, BlockId};
+use bee_gossip::PeerId;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{Tangle, TangleWorker};
 use futures::StreamExt;
@@ -23,12 +24,16 @@ use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
-    packets::BlockRequestPacket, peer::PeerManager, sender::Sender, storage::StorageBackend,
-    types::metrics::NodeMetrics, MetricsWorker, PeerManagerResWorker,
+    config::ProtocolWorkersConfig,
+    packets::BlockRequestPacket,
+    peer::PeerManager,
+    requester::request::{select_peer, Request, RetryPolicy},
+    sender::Sender,
+    storage::StorageBackend,
+    types::metrics::NodeMetrics,
+    MetricsWorker, PeerManagerResWorker,
 };
 
-const RETRY_INTERVAL: Duration = Duration::from_millis(2500);
-
 pub async fn request_block<B: StorageBackend>(
     tangle: &Tangle<B>,
     block_requester: &BlockRequesterWorker,
@@ -45,7 +50,7 @@ pub async fn request_block<B: StorageBackend>(
 }
 
 #[derive(Default)]
-pub struct RequestedBlocks(RwLock<HashMap<BlockId, (MilestoneIndex, Instant), FxBuildHasher>>);
+pub struct RequestedBlocks(RwLock<HashMap<BlockId, (MilestoneIndex, Request), FxBuildHasher>>);
 
 #[allow(clippy::len_without_is_empty)]
 impl RequestedBlocks {
@@ -53,9 +58,8 @@ impl RequestedBlocks {
         self.0.read().contains_key(block_id)
     }
 
-    pub(crate) fn insert(&self, block_id: BlockId, index: MilestoneIndex) {
-        let now = Instant::now();
-        self.0.write().insert(block_id, (index, now));
+    pub(crate) fn insert(&self, block_id: BlockId, index: MilestoneIndex, peer_id: Option<PeerId>) {
+        self.0.write().insert(block_id, (index, Request::new(peer_id)));
     }
 
     pub fn len(&self) -> usize {
@@ -66,9 +70,26 @@ impl RequestedBlocks {
         self.0.read().is_empty()
     }
 
-    pub(crate) fn remove(&self, block_id: &BlockId) -> Option<(MilestoneIndex, Instant)> {
+    pub(crate) fn remove(&self, block_id: &BlockId) -> Option<(MilestoneIndex, Request)> {
         self.0.write().remove(block_id)
     }
+
+    fn retried(&self, block_id: &BlockId, peer_id: Option<PeerId>) {
+        if let Some((_, request)) = self.0.write().get_mut(block_id) {
+            request.retried(peer_id);
+        }
+    }
+
+    fn due(&self, retry_policy: &RetryPolicy) -> Vec<(BlockId, MilestoneIndex, Request)> {
+        let now = Instant::now();
+
+        self.0
+            .read()
+            .iter()
+            .filter(|(_, (_, request))| retry_policy.is_due(request, now))
+            .map(|(block_id, (index, request))| (*block_id, *index, *request))
+            .collect()
+    }
 }
 
 #[derive(Eq, PartialEq)]
@@ -112,29 +133,31 @@ fn process_request(
         return;
     }
 
-    requested_blocks.insert(block_id, index);
+    let peer_id = process_request_unchecked(block_id, index, None, peer_manager, metrics);
 
-    process_request_unchecked(block_id, index, peer_manager, metrics);
+    requested_blocks.insert(block_id, index, peer_id);
 }
 
 fn process_request_unchecked(
     block_id: BlockId,
     index: MilestoneIndex,
+    failed: Option<&PeerId>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
-) {
+) -> Option<PeerId> {
     let block_request = BlockRequestPacket::new(block_id);
+    let peer_id = select_peer(peer_manager, index, failed);
 
-    if let Some(peer_id) = peer_manager
-        .fair_find(|peer| peer.has_data(index))
-        .or_else(|| peer_manager.fair_find(|peer| peer.maybe_has_data(index)))
-    {
+    if let Some(peer_id) = peer_id {
         Sender::<BlockRequestPacket>::send(&block_request, &peer_id, peer_manager, metrics)
     }
+
+    peer_id
 }
 
 fn retry_requests<B: StorageBackend>(
     requested_blocks: &RequestedBlocks,
+    retry_policy: &RetryPolicy,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     tangle: &Tangle<B>,
@@ -143,32 +166,39 @@ fn retry_requests<B: StorageBackend>(
         return;
     }
 
-    let now = Instant::now();
     let mut retry_counts: usize = 0;
-    let mut to_retry = Vec::with_capacity(1024);
-
-    // TODO this needs abstraction
-    for (block_id, (index, instant)) in requested_blocks.0.read().iter() {
-        if now
-            .checked_duration_since(*instant)
-            .map_or(false, |d| d > RETRY_INTERVAL)
-        {
-            to_retry.push((*block_id, *index));
-            retry_counts += 1;
-        }
-    }
+    let mut drop_counts: usize = 0;
 
-    for (block_id, index) in to_retry {
+    for (block_id, index, request) in requested_blocks.due(retry_policy) {
         if tangle.contains(&block_id) {
             requested_blocks.remove(&block_id);
+            continue;
+        }
+
+        if let Some(peer_id) = request.peer_id {
+            peer_manager
+                .get_map(&peer_id, |peer| peer.0.record_timeout())
+                .unwrap_or_default();
+        }
+
+        if retry_policy.is_exhausted(&request) {
+            // The solidifier requests the block again if it is still missing.
+            requested_blocks.remove(&block_id);
+            drop_counts += 1;
         } else {
-            process_request_unchecked(block_id, index, peer_manager, metrics);
+            let peer_id = process_request_unchecked(block_id, index, request.peer_id.as_ref(), peer_manager, metrics);
+            requested_blocks.retried(&block_id, peer_id);
+            retry_counts += 1;
         }
     }
 
     if retry_counts > 0 {
         debug!("Retried {} blocks.", retry_counts);
     }
+
+    if drop_counts > 0 {
+        debug!("Dropped {} block requests after too many retries.", drop_counts);
+    }
 }
 
 #[async_trait]
@@ -176,7 +206,7 @@ impl<N: Node> Worker<N> for BlockRequesterWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = ProtocolWorkersConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -188,8 +218,9 @@ where
         .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
         let req_queue = Arc::new(PriorityQueue::new());
+        let retry_policy = RetryPolicy::new(&config);
 
         let requested_blocks: RequestedBlocks = Default::default();
         node.register_resource(requested_blocks);
@@ -223,10 +254,10 @@ where
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Retryer running.");
 
-            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_INTERVAL)));
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(retry_policy.interval())));
 
             while ticker.next().await.is_some() {
-                retry_requests(&requested_blocks, &peer_manager, &metrics, &tangle);
+                retry_requests(&requested_blocks, &retry_policy, &peer_manager, &metrics, &tangle);
             }
 
             info!("Retryer stopped.");
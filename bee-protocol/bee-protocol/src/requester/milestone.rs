@@ -21,12 +21,16 @@ use tokio::{sync::mpsc, time::interval};
 use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
 
 use crate::{
-    packets::MilestoneRequestPacket, peer::PeerManager, sender::Sender, storage::StorageBackend,
-    types::metrics::NodeMetrics, MetricsWorker, PeerManagerResWorker,
+    config::ProtocolWorkersConfig,
+    packets::MilestoneRequestPacket,
+    peer::PeerManager,
+    requester::request::{select_peer, Request, RetryPolicy},
+    sender::Sender,
+    storage::StorageBackend,
+    types::metrics::NodeMetrics,
+    MetricsWorker, PeerManagerResWorker,
 };
 
-const RETRY_INTERVAL: Duration = Duration::from_millis(2500);
-
 pub(crate) fn request_milestone<B: StorageBackend>(
     tangle: &Tangle<B>,
     milestone_requester: &mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
@@ -50,30 +54,86 @@ pub(crate) fn request_latest_milestone<B: StorageBackend>(
     request_milestone(tangle, milestone_requester, requested_milestones, MilestoneIndex(0), to)
 }
 
-#[derive(Default)]
-pub struct RequestedMilestones(RwLock<HashMap<MilestoneIndex, Instant, FxBuildHasher>>);
+/// Outstanding milestone requests, along with the recently closed ones that peers may still answer late.
+pub struct RequestedMilestones {
+    requests: RwLock<HashMap<MilestoneIndex, Request, FxBuildHasher>>,
+    closed: RwLock<HashMap<MilestoneIndex, Instant, FxBuildHasher>>,
+    late_answer_window: Duration,
+}
 
 #[allow(clippy::len_without_is_empty)]
 impl RequestedMilestones {
+    /// Creates a new [`RequestedMilestones`], expecting late answers to closed requests for the given window.
+    pub(crate) fn new(late_answer_window: Duration) -> Self {
+        Self {
+            requests: Default::default(),
+            closed: Default::default(),
+            late_answer_window,
+        }
+    }
+
     pub fn contains(&self, index: &MilestoneIndex) -> bool {
-        self.0.read().contains_key(index)
+        self.requests.read().contains_key(index)
     }
 
-    pub(crate) fn insert(&self, index: MilestoneIndex) {
-        let now = Instant::now();
-        self.0.write().insert(index, now);
+    pub(crate) fn insert(&self, index: MilestoneIndex, peer_id: Option<PeerId>) {
+        self.requests.write().insert(index, Request::new(peer_id));
     }
 
     pub fn len(&self) -> usize {
-        self.0.read().len()
+        self.requests.read().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.read().is_empty()
+        self.requests.read().is_empty()
+    }
+
+    /// Closes a request, whether it was answered or dropped.
+    pub(crate) fn remove(&self, index: &MilestoneIndex) -> Option<Request> {
+        let request = self.requests.write().remove(index);
+
+        if request.is_some() {
+            self.closed.write().insert(*index, Instant::now());
+        }
+
+        request
+    }
+
+    /// Returns whether a milestone is requested or was until recently, peers that were sent a request that got
+    /// re-routed or dropped in the meantime possibly still answering it.
+    pub(crate) fn is_recent(&self, index: &MilestoneIndex) -> bool {
+        self.is_recent_at(index, Instant::now())
     }
 
-    pub(crate) fn remove(&self, index: &MilestoneIndex) -> Option<Instant> {
-        self.0.write().remove(index)
+    fn is_recent_at(&self, index: &MilestoneIndex, now: Instant) -> bool {
+        self.contains(index)
+            || matches!(
+                self.closed.read().get(index),
+                Some(closed_at) if now.saturating_duration_since(*closed_at) <= self.late_answer_window
+            )
+    }
+
+    fn prune_closed(&self, now: Instant) {
+        self.closed
+            .write()
+            .retain(|_, closed_at| now.saturating_duration_since(*closed_at) <= self.late_answer_window);
+    }
+
+    fn retried(&self, index: &MilestoneIndex, peer_id: Option<PeerId>) {
+        if let Some(request) = self.requests.write().get_mut(index) {
+            request.retried(peer_id);
+        }
+    }
+
+    fn due(&self, retry_policy: &RetryPolicy) -> Vec<(MilestoneIndex, Request)> {
+        let now = Instant::now();
+
+        self.requests
+            .read()
+            .iter()
+            .filter(|(_, request)| retry_policy.is_due(request, now))
+            .map(|(index, request)| (*index, *request))
+            .collect()
     }
 }
 
@@ -98,69 +158,76 @@ fn process_request(
         return;
     }
 
+    let peer_id = process_request_unchecked(index, peer_id, None, peer_manager, metrics);
+
     if index.0 != 0 {
-        requested_milestones.insert(index);
+        requested_milestones.insert(index, peer_id);
     }
-
-    process_request_unchecked(index, peer_id, peer_manager, metrics);
 }
 
 fn process_request_unchecked(
     index: MilestoneIndex,
     peer_id: Option<PeerId>,
+    failed: Option<&PeerId>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
-) {
+) -> Option<PeerId> {
     let milestone_request = MilestoneRequestPacket::new(*index);
+    let peer_id = peer_id.or_else(|| select_peer(peer_manager, index, failed));
 
-    match peer_id {
-        Some(peer_id) => {
-            Sender::<MilestoneRequestPacket>::send(&milestone_request, &peer_id, peer_manager, metrics);
-        }
-        None => {
-            if let Some(peer_id) = peer_manager.fair_find(|peer| peer.maybe_has_data(index)) {
-                Sender::<MilestoneRequestPacket>::send(&milestone_request, &peer_id, peer_manager, metrics);
-            }
-        }
+    if let Some(peer_id) = peer_id {
+        Sender::<MilestoneRequestPacket>::send(&milestone_request, &peer_id, peer_manager, metrics);
     }
+
+    peer_id
 }
 
 fn retry_requests<B: StorageBackend>(
     requested_milestones: &RequestedMilestones,
+    retry_policy: &RetryPolicy,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
     tangle: &Tangle<B>,
 ) {
+    requested_milestones.prune_closed(Instant::now());
+
     if peer_manager.is_empty() {
         return;
     }
 
-    let now = Instant::now();
     let mut retry_counts: usize = 0;
-    let mut to_retry = Vec::with_capacity(1024);
-
-    // TODO this needs abstraction
-    for (index, instant) in requested_milestones.0.read().iter() {
-        if now
-            .checked_duration_since(*instant)
-            .map_or(false, |d| d > RETRY_INTERVAL)
-        {
-            to_retry.push(*index);
-            retry_counts += 1;
-        };
-    }
+    let mut drop_counts: usize = 0;
 
-    for index in to_retry {
+    for (index, request) in requested_milestones.due(retry_policy) {
         if tangle.contains_milestone_metadata(index) {
             requested_milestones.remove(&index);
+            continue;
+        }
+
+        if let Some(peer_id) = request.peer_id {
+            peer_manager
+                .get_map(&peer_id, |peer| peer.0.record_timeout())
+                .unwrap_or_default();
+        }
+
+        if retry_policy.is_exhausted(&request) {
+            // The solidifier requests the milestone again if it is still missing.
+            requested_milestones.remove(&index);
+            drop_counts += 1;
         } else {
-            process_request_unchecked(index, None, peer_manager, metrics);
+            let peer_id = process_request_unchecked(index, None, request.peer_id.as_ref(), peer_manager, metrics);
+            requested_milestones.retried(&index, peer_id);
+            retry_counts += 1;
         }
     }
 
     if retry_counts > 0 {
         debug!("Retried {} milestones.", retry_counts);
     }
+
+    if drop_counts > 0 {
+        debug!("Dropped {} milestone requests after too many retries.", drop_counts);
+    }
 }
 
 #[async_trait]
@@ -168,7 +235,7 @@ impl<N: Node> Worker<N> for MilestoneRequesterWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = ProtocolWorkersConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -180,10 +247,12 @@ where
         .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = mpsc::unbounded_channel();
+        let retry_policy = RetryPolicy::new(&config);
 
-        let requested_milestones: RequestedMilestones = Default::default();
+        // An answer can't be expected later than the longest wait between two retries.
+        let requested_milestones = RequestedMilestones::new(retry_policy.max_interval());
         node.register_resource(requested_milestones);
 
         let tangle = node.resource::<Tangle<N::Backend>>();
@@ -214,10 +283,10 @@ where
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Retryer running.");
 
-            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_INTERVAL)));
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(retry_policy.interval())));
 
             while ticker.next().await.is_some() {
-                retry_requests(&requested_milestones, &peer_manager, &metrics, &tangle);
+                retry_requests(&requested_milestones, &retry_policy, &peer_manager, &metrics, &tangle);
             }
 
             info!("Retryer stopped.");
@@ -226,3 +295,35 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_requests_stay_recent_within_the_window() {
+        let window = Duration::from_secs(10);
+        let requested_milestones = RequestedMilestones::new(window);
+        let index = MilestoneIndex(42);
+
+        assert!(!requested_milestones.is_recent(&index));
+
+        requested_milestones.insert(index, None);
+
+        assert!(requested_milestones.is_recent(&index));
+        assert!(requested_milestones.remove(&index).is_some());
+        assert!(!requested_milestones.contains(&index));
+
+        let now = Instant::now();
+
+        assert!(requested_milestones.is_recent_at(&index, now));
+        assert!(!requested_milestones.is_recent_at(&index, now + window + Duration::from_secs(1)));
+        assert!(!requested_milestones.is_recent(&MilestoneIndex(43)));
+
+        requested_milestones.prune_closed(now);
+        assert!(requested_milestones.is_recent_at(&index, now));
+
+        requested_milestones.prune_closed(now + window + Duration::from_secs(1));
+        assert!(!requested_milestones.is_recent_at(&index, now));
+    }
+}
@@ -3,6 +3,7 @@
 
 mod block;
 mod milestone;
+mod request;
 
 pub(crate) use self::milestone::{
     request_latest_milestone, request_milestone, MilestoneRequesterWorker, MilestoneRequesterWorkerEvent,
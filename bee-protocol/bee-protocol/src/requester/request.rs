@@ -0,0 +1,289 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retry policy and peer selection shared by the block and milestone requesters.
+
+use std::time::{Duration, Instant};
+
+use bee_block::payload::milestone::MilestoneIndex;
+use bee_gossip::PeerId;
+
+use crate::{config::ProtocolWorkersConfig, peer::PeerManager, types::peer::Peer};
+
+// Peers at least this fraction as responsive as the most responsive one are used in turn, which spreads requests
+// instead of sending all of them to a single peer.
+const RESPONSIVENESS_TOLERANCE: f64 = 0.8;
+
+/// An outstanding request.
+#[derive(Clone, Copy)]
+pub(crate) struct Request {
+    /// When the request was first made.
+    pub(crate) requested_at: Instant,
+    /// When the request was last sent.
+    pub(crate) sent_at: Instant,
+    /// The peer the request was last sent to, if any.
+    pub(crate) peer_id: Option<PeerId>,
+    /// How many times the request has been retried.
+    pub(crate) retries: u32,
+}
+
+impl Request {
+    pub(crate) fn new(peer_id: Option<PeerId>) -> Self {
+        let now = Instant::now();
+
+        Self {
+            requested_at: now,
+            sent_at: now,
+            peer_id,
+            retries: 0,
+        }
+    }
+
+    pub(crate) fn retried(&mut self, peer_id: Option<PeerId>) {
+        self.sent_at = Instant::now();
+        self.peer_id = peer_id;
+        self.retries += 1;
+    }
+
+    /// Credits the peer the request was last sent to with an answer.
+    pub(crate) fn answered(&self, peer_manager: &PeerManager) {
+        if let Some(peer_id) = self.peer_id {
+            peer_manager
+                .get_map(&peer_id, |peer| peer.0.record_response(self.sent_at.elapsed()))
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// Exponential back-off of the retries of a request.
+#[derive(Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    interval: Duration,
+    max_interval: Duration,
+    max_retries: u32,
+}
+
+impl RetryPolicy {
+    pub(crate) fn new(config: &ProtocolWorkersConfig) -> Self {
+        let interval = Duration::from_millis(config.request_retry_interval);
+
+        Self {
+            interval,
+            max_interval: Duration::from_millis(config.request_max_retry_interval).max(interval),
+            max_retries: config.request_max_retries,
+        }
+    }
+
+    /// Returns the interval at which outstanding requests are checked.
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the longest wait between two retries of a request.
+    pub(crate) fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    /// Returns whether a request waited long enough to be retried, the wait doubling with every retry.
+    pub(crate) fn is_due(&self, request: &Request, now: Instant) -> bool {
+        let backoff = self
+            .interval
+            .saturating_mul(2u32.saturating_pow(request.retries))
+            .min(self.max_interval);
+
+        now.saturating_duration_since(request.sent_at) > backoff
+    }
+
+    /// Returns whether a request was retried too many times and should be dropped.
+    pub(crate) fn is_exhausted(&self, request: &Request) -> bool {
+        request.retries >= self.max_retries
+    }
+}
+
+/// Selects a peer to send a request for the data referenced by a milestone index to.
+///
+/// Peers whose heartbeat says they hold the index are preferred over peers that may only hold it. Among them, the most
+/// responsive ones are used in turn. The peer that last failed to answer the request is only selected again if it is
+/// the only one holding the index.
+pub(crate) fn select_peer(
+    peer_manager: &PeerManager,
+    index: MilestoneIndex,
+    failed: Option<&PeerId>,
+) -> Option<PeerId> {
+    select_peer_with(peer_manager, failed, |peer| peer.has_data(index))
+        .or_else(|| select_peer_with(peer_manager, failed, |peer| peer.maybe_has_data(index)))
+}
+
+fn select_peer_with(
+    peer_manager: &PeerManager,
+    failed: Option<&PeerId>,
+    holds: impl Fn(&Peer) -> bool,
+) -> Option<PeerId> {
+    let is_candidate = |peer: &Peer| peer.is_connected() && holds(peer);
+    let mut best: Option<f64> = None;
+    let mut failed_is_candidate = false;
+
+    peer_manager.for_each(|peer_id, peer| {
+        if is_candidate(peer) {
+            if Some(peer_id) == failed {
+                failed_is_candidate = true;
+            } else {
+                best = Some(best.map_or(peer.responsiveness(), |best| best.max(peer.responsiveness())));
+            }
+        }
+    });
+
+    match best {
+        Some(best) => peer_manager.fair_find(|peer| {
+            Some(peer.id()) != failed && is_candidate(peer) && peer.responsiveness() >= best * RESPONSIVENESS_TOLERANCE
+        }),
+        None if failed_is_candidate => failed.copied(),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bee_gossip::{PeerInfo, PeerRelation};
+
+    use super::*;
+    use crate::config::ProtocolConfig;
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy::new(
+            &ProtocolConfig::build()
+                .request_retry_interval(100)
+                .request_max_retry_interval(350)
+                .request_max_retries(3)
+                .finish()
+                .workers,
+        )
+    }
+
+    fn add_peer(peer_manager: &PeerManager, solid_index: u32, latest_index: u32) -> PeerId {
+        let peer_id = PeerId::random();
+        let peer = Peer::new(
+            peer_id,
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: peer_id.to_string(),
+                relation: PeerRelation::Known,
+            },
+        );
+
+        peer.set_connected(true);
+        peer.set_solid_milestone_index(MilestoneIndex(solid_index));
+        peer.set_latest_milestone_index(MilestoneIndex(latest_index));
+        peer_manager.add(Arc::new(peer));
+
+        peer_id
+    }
+
+    #[test]
+    fn retries_back_off_exponentially() {
+        let retry_policy = retry_policy();
+        let mut request = Request::new(None);
+        let sent_at = request.sent_at;
+
+        assert!(!retry_policy.is_due(&request, sent_at + Duration::from_millis(100)));
+        assert!(retry_policy.is_due(&request, sent_at + Duration::from_millis(101)));
+
+        request.retries = 1;
+        assert!(!retry_policy.is_due(&request, sent_at + Duration::from_millis(200)));
+        assert!(retry_policy.is_due(&request, sent_at + Duration::from_millis(201)));
+
+        // Capped by the maximum interval.
+        request.retries = 2;
+        assert!(!retry_policy.is_due(&request, sent_at + Duration::from_millis(350)));
+        assert!(retry_policy.is_due(&request, sent_at + Duration::from_millis(351)));
+
+        request.retries = u32::MAX;
+        assert!(retry_policy.is_due(&request, sent_at + Duration::from_millis(351)));
+    }
+
+    #[test]
+    fn retries_are_exhausted() {
+        let retry_policy = retry_policy();
+        let mut request = Request::new(None);
+
+        for _ in 0..3 {
+            assert!(!retry_policy.is_exhausted(&request));
+            request.retried(None);
+        }
+
+        assert!(retry_policy.is_exhausted(&request));
+        assert_eq!(request.retries, 3);
+    }
+
+    #[test]
+    fn select_peer_prefers_peers_holding_the_index() {
+        let peer_manager = PeerManager::default();
+        let holding = add_peer(&peer_manager, 10, 10);
+        let maybe_holding = add_peer(&peer_manager, 5, 20);
+
+        for _ in 0..4 {
+            assert_eq!(select_peer(&peer_manager, MilestoneIndex(10), None), Some(holding));
+        }
+        assert_eq!(
+            select_peer(&peer_manager, MilestoneIndex(15), None),
+            Some(maybe_holding)
+        );
+        assert_eq!(select_peer(&peer_manager, MilestoneIndex(30), None), None);
+    }
+
+    #[test]
+    fn select_peer_avoids_failed_peer() {
+        let peer_manager = PeerManager::default();
+        let failed = add_peer(&peer_manager, 10, 10);
+
+        // The failed peer is the only candidate.
+        assert_eq!(
+            select_peer(&peer_manager, MilestoneIndex(10), Some(&failed)),
+            Some(failed)
+        );
+
+        let other = add_peer(&peer_manager, 10, 10);
+
+        for _ in 0..4 {
+            assert_eq!(
+                select_peer(&peer_manager, MilestoneIndex(10), Some(&failed)),
+                Some(other)
+            );
+        }
+    }
+
+    #[test]
+    fn select_peer_prefers_responsive_peers() {
+        let peer_manager = PeerManager::default();
+        let unresponsive = add_peer(&peer_manager, 10, 10);
+        let responsive = [add_peer(&peer_manager, 10, 10), add_peer(&peer_manager, 10, 10)];
+
+        for _ in 0..5 {
+            peer_manager
+                .get_map(&unresponsive, |peer| peer.0.record_timeout())
+                .unwrap();
+        }
+
+        let selected = (0..6)
+            .map(|_| select_peer(&peer_manager, MilestoneIndex(10), None).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(!selected.contains(&unresponsive));
+        // Equally responsive peers are used in turn.
+        assert!(responsive.iter().all(|peer_id| selected.contains(peer_id)));
+    }
+
+    #[test]
+    fn select_peer_ignores_disconnected_peers() {
+        let peer_manager = PeerManager::default();
+        let peer_id = add_peer(&peer_manager, 10, 10);
+
+        peer_manager
+            .get_map(&peer_id, |peer| peer.0.set_connected(false))
+            .unwrap();
+
+        assert_eq!(select_peer(&peer_manager, MilestoneIndex(10), None), None);
+    }
+}
@@ -3,11 +3,14 @@
 
 use std::marker::PhantomData;
 
-use bee_gossip::PeerId;
+use bee_gossip::{GossipMessage, PeerId};
 use log::warn;
 
 use crate::{
-    packets::{tlv_to_bytes, BlockPacket, BlockRequestPacket, HeartbeatPacket, MilestoneRequestPacket, Packet},
+    packets::{
+        tlv_to_bytes, BlockPacket, BlockRequestPacket, HeartbeatPacket, HeartbeatV2Packet, MilestoneRequestPacket,
+        Packet,
+    },
     peer::PeerManager,
     types::metrics::NodeMetrics,
 };
@@ -26,7 +29,14 @@ impl Sender<MilestoneRequestPacket> {
         peer_manager
             .get_map(id, |peer| {
                 if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
+                    let bytes = tlv_to_bytes(packet);
+                    let len = bytes.len() as u64;
+                    let sent_to = peer.0.clone();
+                    let message = GossipMessage::new(bytes).on_sent(move || {
+                        sent_to.metrics().milestone_request_bytes_sent_add(len);
+                    });
+
+                    match sender.0.send(message) {
                         Ok(_) => {
                             peer.0.metrics().milestone_requests_sent_inc();
                             metrics.milestone_requests_sent_inc();
@@ -46,7 +56,14 @@ impl Sender<BlockPacket> {
         peer_manager
             .get_map(id, |peer| {
                 if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
+                    let bytes = tlv_to_bytes(packet);
+                    let len = bytes.len() as u64;
+                    let sent_to = peer.0.clone();
+                    let message = GossipMessage::new(bytes).droppable().on_sent(move || {
+                        sent_to.metrics().block_bytes_sent_add(len);
+                    });
+
+                    match sender.0.send(message) {
                         Ok(_) => {
                             peer.0.metrics().blocks_sent_inc();
                             metrics.blocks_sent_inc();
@@ -66,7 +83,14 @@ impl Sender<BlockRequestPacket> {
         peer_manager
             .get_map(id, |peer| {
                 if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
+                    let bytes = tlv_to_bytes(packet);
+                    let len = bytes.len() as u64;
+                    let sent_to = peer.0.clone();
+                    let message = GossipMessage::new(bytes).on_sent(move || {
+                        sent_to.metrics().block_request_bytes_sent_add(len);
+                    });
+
+                    match sender.0.send(message) {
                         Ok(_) => {
                             peer.0.metrics().block_requests_sent_inc();
                             metrics.block_requests_sent_inc();
@@ -86,7 +110,14 @@ impl Sender<HeartbeatPacket> {
         peer_manager
             .get_map(id, |peer| {
                 if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
+                    let bytes = tlv_to_bytes(packet);
+                    let len = bytes.len() as u64;
+                    let sent_to = peer.0.clone();
+                    let message = GossipMessage::new(bytes).droppable().on_sent(move || {
+                        sent_to.metrics().heartbeat_bytes_sent_add(len);
+                    });
+
+                    match sender.0.send(message) {
                         Ok(_) => {
                             peer.0.metrics().heartbeats_sent_inc();
                             peer.0.set_heartbeat_sent_timestamp();
@@ -101,3 +132,31 @@ impl Sender<HeartbeatPacket> {
             .unwrap_or_default();
     }
 }
+
+impl Sender<HeartbeatV2Packet> {
+    pub(crate) fn send(packet: &HeartbeatV2Packet, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) {
+        peer_manager
+            .get_map(id, |peer| {
+                if let Some(ref sender) = peer.1 {
+                    let bytes = tlv_to_bytes(packet);
+                    let len = bytes.len() as u64;
+                    let sent_to = peer.0.clone();
+                    let message = GossipMessage::new(bytes).droppable().on_sent(move || {
+                        sent_to.metrics().heartbeat_bytes_sent_add(len);
+                    });
+
+                    match sender.0.send(message) {
+                        Ok(_) => {
+                            peer.0.metrics().heartbeats_sent_inc();
+                            peer.0.set_heartbeat_sent_timestamp();
+                            metrics.heartbeats_sent_inc();
+                        }
+                        Err(e) => {
+                            warn!("Sending HeartbeatV2Packet to {} failed: {:?}.", id, e);
+                        }
+                    }
+                }
+            })
+            .unwrap_or_default();
+    }
+}
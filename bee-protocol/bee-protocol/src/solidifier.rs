@@ -1,24 +1,35 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{any::TypeId, cmp, convert::Infallible};
+use std::{
+    any::TypeId,
+    cmp,
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use bee_block::{
-    payload::milestone::{MilestoneId, MilestoneIndex},
-    BlockId,
-};
+use bee_block::{payload::milestone::MilestoneIndex, BlockId};
 use bee_ledger::consensus::{ConsensusWorker, ConsensusWorkerCommand};
-use bee_runtime::{event::Bus, node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_runtime::{
+    event::Bus,
+    node::{Node, NodeInfo},
+    resource::ResourceHandle,
+    shutdown_stream::ShutdownStream,
+    worker::Worker,
+};
 use bee_tangle::{
     event::SolidMilestoneChanged, milestone_metadata::MilestoneMetadata, traversal, Tangle, TangleWorker,
 };
-use futures::StreamExt;
+use futures::{future::join_all, StreamExt};
 use log::{debug, error, info, warn};
-use tokio::sync::mpsc;
+use tokio::{sync::mpsc, time::sleep};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
+    config::ProtocolWorkersConfig,
+    event::MilestoneSolidificationProgress,
     heartbeater::broadcast_heartbeat,
     peer::PeerManager,
     requester::{request_block, request_milestone},
@@ -28,38 +39,174 @@ use crate::{
     PeerManagerResWorker, RequestedBlocks, RequestedMilestones,
 };
 
+const MAX_CONE_WALK_RETRIES: u32 = 5;
+const CONE_WALK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 pub(crate) struct MilestoneSolidifierWorkerEvent(pub MilestoneIndex);
 
 pub(crate) struct MilestoneSolidifierWorker {
     pub(crate) tx: mpsc::UnboundedSender<MilestoneSolidifierWorkerEvent>,
 }
 
+/// Walks the cones of the given milestones in parallel and collects their missing blocks, in the order of `targets`.
+/// Also returns the milestones whose cone could not be walked.
+async fn missing_blocks<B: StorageBackend>(
+    tangle: &ResourceHandle<Tangle<B>>,
+    requested_blocks: &ResourceHandle<RequestedBlocks>,
+    targets: &[(MilestoneIndex, BlockId)],
+) -> (Vec<(MilestoneIndex, Vec<BlockId>)>, Vec<MilestoneIndex>) {
+    let cones = join_all(targets.iter().map(|(_, target_id)| {
+        let tangle = tangle.clone();
+        let requested_blocks = requested_blocks.clone();
+        let target_id = *target_id;
+
+        // The traversal is synchronous, hence the blocking tasks.
+        tokio::task::spawn_blocking(move || {
+            let mut missing = Vec::new();
+
+            traversal::visit_parents_depth_first(
+                &tangle,
+                target_id,
+                |id, _, metadata| !metadata.flags().is_solid() && !requested_blocks.contains(id),
+                |_, _, _| {},
+                |_, _, _| {},
+                |missing_id| missing.push(*missing_id),
+            );
+
+            missing
+        })
+    }))
+    .await;
+
+    let mut missing_blocks = Vec::with_capacity(targets.len());
+    let mut failed = Vec::new();
+
+    for ((target_index, _), cone) in targets.iter().zip(cones) {
+        match cone {
+            Ok(missing) => missing_blocks.push((*target_index, missing)),
+            Err(e) => {
+                error!("Walking the cone of milestone {} failed: {}.", target_index, e);
+                failed.push(*target_index);
+            }
+        }
+    }
+
+    (missing_blocks, failed)
+}
+
+/// Milestones whose cone could not be walked, walked again with an exponential back-off and given up on after
+/// `MAX_CONE_WALK_RETRIES` retries.
+#[derive(Default)]
+struct ConeWalkRetries(HashMap<MilestoneIndex, (u32, Instant)>);
+
+impl ConeWalkRetries {
+    /// Returns whether the cone of a milestone can be walked, i.e. it never failed or its back-off elapsed.
+    fn is_due(&self, index: &MilestoneIndex, now: Instant) -> bool {
+        match self.0.get(index) {
+            Some((failures, next_attempt)) => *failures <= MAX_CONE_WALK_RETRIES && *next_attempt <= now,
+            None => true,
+        }
+    }
+
+    /// Records a failed walk of the cone of a milestone and returns the delay before walking it again, if at all.
+    fn failed(&mut self, index: MilestoneIndex, now: Instant) -> Option<Duration> {
+        let (failures, next_attempt) = self.0.entry(index).or_insert((0, now));
+
+        *failures += 1;
+
+        if *failures > MAX_CONE_WALK_RETRIES {
+            return None;
+        }
+
+        let delay = CONE_WALK_RETRY_INTERVAL * 2u32.pow(*failures - 1);
+        *next_attempt = now + delay;
+
+        Some(delay)
+    }
+
+    /// Forgets the milestones up to the solid one.
+    fn prune(&mut self, smi: MilestoneIndex) {
+        self.0.retain(|index, _| *index > smi);
+    }
+}
+
+/// Deduplicates the missing blocks of the cones and selects at most `budget` of them to be requested, oldest milestones
+/// first. Also returns the number of missing blocks.
+fn select_requests(
+    cones: Vec<(MilestoneIndex, Vec<BlockId>)>,
+    mut budget: usize,
+) -> (Vec<(BlockId, MilestoneIndex)>, usize) {
+    let mut seen = HashSet::new();
+    let mut requests = Vec::new();
+    let mut missing_len = 0;
+
+    for (target_index, missing) in cones {
+        for missing_id in missing {
+            if !seen.insert(missing_id) {
+                continue;
+            }
+
+            missing_len += 1;
+
+            if budget > 0 {
+                requests.push((missing_id, target_index));
+                budget -= 1;
+            }
+        }
+    }
+
+    (requests, missing_len)
+}
+
+/// Requests the missing blocks of the cones of the given milestones, oldest milestones first, without exceeding
+/// `max_requests` blocks in flight. Returns the number of missing blocks and the milestones whose cone could not be
+/// walked.
 async fn heavy_solidification<B: StorageBackend>(
-    tangle: &Tangle<B>,
+    tangle: &ResourceHandle<Tangle<B>>,
     block_requester: &BlockRequesterWorker,
-    requested_blocks: &RequestedBlocks,
-    target_index: MilestoneIndex,
-    target_id: BlockId,
-) -> usize {
-    // TODO: This wouldn't be necessary if the traversal code wasn't closure-driven
-    let mut missing = Vec::new();
+    requested_blocks: &ResourceHandle<RequestedBlocks>,
+    targets: &[(MilestoneIndex, BlockId)],
+    max_requests: usize,
+) -> (usize, Vec<MilestoneIndex>) {
+    let (cones, failed) = missing_blocks(tangle, requested_blocks, targets).await;
+    // `targets` is sorted by index, so are the cones.
+    let (requests, missing_len) = select_requests(cones, max_requests.saturating_sub(requested_blocks.len()));
 
-    traversal::visit_parents_depth_first(
-        tangle,
-        target_id,
-        |id, _, metadata| !metadata.flags().is_solid() && !requested_blocks.contains(id),
-        |_, _, _| {},
-        |_, _, _| {},
-        |missing_id| missing.push(*missing_id),
-    );
+    for (block_id, index) in requests {
+        request_block(tangle, block_requester, requested_blocks, block_id, index).await;
+    }
+
+    (missing_len, failed)
+}
 
-    let missing_len = missing.len();
+/// Splits the milestones following the solid milestone index up to the latest milestone index into the ones directly
+/// following it that are already solid, and the first `window` ones that are not.
+async fn solidification_targets<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    smi: MilestoneIndex,
+    lmi: MilestoneIndex,
+    window: usize,
+) -> (Vec<(MilestoneIndex, MilestoneMetadata)>, Vec<(MilestoneIndex, BlockId)>) {
+    let mut target = smi + MilestoneIndex(1);
+    let mut solid = Vec::new();
+    let mut solidifying = Vec::new();
 
-    for missing_id in missing {
-        request_block(tangle, block_requester, requested_blocks, missing_id, target_index).await;
+    while target <= lmi && solidifying.len() < window {
+        if let Some(milestone) = tangle.get_milestone_metadata(target) {
+            let id = *milestone.block_id();
+
+            if !tangle.is_solid_block(&id).await {
+                solidifying.push((target, id));
+            } else if solidifying.is_empty() {
+                solid.push((target, milestone));
+            }
+        } else {
+            break;
+        }
+        target = target + MilestoneIndex(1);
     }
 
-    missing_len
+    (solid, solidifying)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -69,35 +216,26 @@ fn solidify<B: StorageBackend>(
     index_updater_worker: &mpsc::UnboundedSender<IndexUpdaterWorkerEvent>,
     peer_manager: &PeerManager,
     metrics: &NodeMetrics,
+    node_info: &NodeInfo,
     bus: &Bus<'static>,
-    id: BlockId,
     index: MilestoneIndex,
+    milestone: MilestoneMetadata,
 ) {
     debug!("New solid milestone {}.", *index);
 
     tangle.update_solid_milestone_index(index);
 
-    if let Err(e) = consensus_worker.send(ConsensusWorkerCommand::ConfirmMilestone(id)) {
+    if let Err(e) = consensus_worker.send(ConsensusWorkerCommand::ConfirmMilestone(*milestone.block_id())) {
         warn!("Sending block_id to consensus worker failed: {}.", e);
     }
 
-    if let Err(e) = index_updater_worker
-        // TODO get MS
-        .send(IndexUpdaterWorkerEvent(
-            index,
-            MilestoneMetadata::new(id, MilestoneId::null(), 0),
-        ))
-    {
+    if let Err(e) = index_updater_worker.send(IndexUpdaterWorkerEvent(index, milestone.clone())) {
         warn!("Sending block_id to `IndexUpdater` failed: {:?}.", e);
     }
 
-    broadcast_heartbeat(tangle, peer_manager, metrics);
+    broadcast_heartbeat(tangle, peer_manager, metrics, node_info);
 
-    bus.dispatch(SolidMilestoneChanged {
-        index,
-        // TODO get MS
-        milestone: MilestoneMetadata::new(id, MilestoneId::null(), 0),
-    });
+    bus.dispatch(SolidMilestoneChanged { index, milestone });
 }
 
 #[async_trait]
@@ -105,7 +243,7 @@ impl<N: Node> Worker<N> for MilestoneSolidifierWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = u32;
+    type Config = ProtocolWorkersConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -132,8 +270,16 @@ where
         let requested_milestones = node.resource::<RequestedMilestones>();
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
+        let node_info = node.info();
         let bus = node.bus();
-        let milestone_sync_count = config;
+        let ProtocolWorkersConfig {
+            milestone_sync_count,
+            solidification_window,
+            solidification_max_requests,
+            ..
+        } = config;
+
+        let retry_tx = tx.clone();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -141,6 +287,7 @@ where
             let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
 
             let mut next = tangle.get_solid_milestone_index() + MilestoneIndex(1);
+            let mut cone_walk_retries = ConeWalkRetries::default();
 
             while let Some(MilestoneSolidifierWorkerEvent(index)) = receiver.next().await {
                 let smi = tangle.get_solid_milestone_index();
@@ -173,39 +320,80 @@ where
                     }
                 }
 
-                let mut target = smi + MilestoneIndex(1);
-
-                while target <= lmi {
-                    if let Some(id) = tangle.get_milestone_block_id(target) {
-                        if tangle.is_solid_block(&id).await {
-                            solidify(
-                                &tangle,
-                                &consensus_worker,
-                                &milestone_cone_updater,
-                                &peer_manager,
-                                &metrics,
-                                &bus,
-                                id,
-                                target,
-                            );
-                        } else {
-                            // TODO Is this actually necessary ?
-                            let missing_len =
-                                heavy_solidification(&tangle, &block_requester, &requested_blocks, target, id).await;
-                            debug!(
-                                "Heavy solidification of milestone {} {}: {} blocks requested in [{};{}].",
-                                target,
-                                id,
-                                missing_len,
-                                *smi + 1,
-                                *next - 1
-                            );
-                            break;
+                let (solid, solidifying) =
+                    solidification_targets(&tangle, smi, lmi, solidification_window as usize).await;
+
+                for (target, milestone) in solid {
+                    solidify(
+                        &tangle,
+                        &consensus_worker,
+                        &milestone_cone_updater,
+                        &peer_manager,
+                        &metrics,
+                        &node_info,
+                        &bus,
+                        target,
+                        milestone,
+                    );
+                }
+
+                cone_walk_retries.prune(tangle.get_solid_milestone_index());
+
+                if !solidifying.is_empty() {
+                    let now = Instant::now();
+                    let walkable = solidifying
+                        .iter()
+                        .filter(|(index, _)| cone_walk_retries.is_due(index, now))
+                        .copied()
+                        .collect::<Vec<_>>();
+
+                    let (missing_blocks, failed) = heavy_solidification(
+                        &tangle,
+                        &block_requester,
+                        &requested_blocks,
+                        &walkable,
+                        solidification_max_requests,
+                    )
+                    .await;
+
+                    // Walks the failed cones again once their back-off elapsed.
+                    for index in failed {
+                        match cone_walk_retries.failed(index, now) {
+                            Some(delay) => {
+                                let retry_tx = retry_tx.clone();
+
+                                tokio::spawn(async move {
+                                    sleep(delay).await;
+
+                                    if let Err(e) = retry_tx.send(MilestoneSolidifierWorkerEvent(index)) {
+                                        warn!("Re-queuing milestone {} failed: {}.", index, e);
+                                    }
+                                });
+                            }
+                            None => error!(
+                                "Giving up walking the cone of milestone {} after {} retries.",
+                                index, MAX_CONE_WALK_RETRIES
+                            ),
                         }
-                    } else {
-                        break;
                     }
-                    target = target + MilestoneIndex(1);
+                    let solidifying_milestones = solidifying.into_iter().map(|(index, _)| index).collect::<Vec<_>>();
+
+                    debug!(
+                        "Heavy solidification of milestones {:?}: {} blocks missing, {} requested in [{};{}].",
+                        solidifying_milestones,
+                        missing_blocks,
+                        requested_blocks.len(),
+                        *tangle.get_solid_milestone_index() + 1,
+                        *next - 1
+                    );
+
+                    bus.dispatch(MilestoneSolidificationProgress {
+                        solid_milestone_index: tangle.get_solid_milestone_index(),
+                        latest_milestone_index: lmi,
+                        solidifying_milestones,
+                        missing_blocks,
+                        requested_blocks: requested_blocks.len(),
+                    });
                 }
             }
 
@@ -217,3 +405,193 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bee_block::{
+        parent::Parents,
+        protocol::protocol_parameters,
+        rand::{
+            block::{rand_block_id, rand_block_with_parents},
+            milestone::rand_milestone_id,
+            payload::rand_milestone_payload,
+        },
+    };
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::{block_metadata::BlockMetadata, config::TangleConfig};
+
+    use super::*;
+
+    fn tangle() -> ResourceHandle<Tangle<Storage>> {
+        ResourceHandle::new(Tangle::new(
+            TangleConfig::build().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        ))
+    }
+
+    fn insert_block(tangle: &Tangle<Storage>, parents: Vec<BlockId>, solid: bool) -> BlockId {
+        let block = rand_block_with_parents(Parents::new(parents).unwrap(), protocol_parameters().min_pow_score());
+        let block_id = block.id();
+        let mut metadata = BlockMetadata::arrived();
+
+        if solid {
+            metadata.mark_solid();
+        }
+        tangle.insert(&block, &block_id, &metadata);
+
+        block_id
+    }
+
+    fn insert_milestone(tangle: &Tangle<Storage>, index: u32, solid: bool) -> BlockId {
+        let block_id = insert_block(tangle, vec![rand_block_id()], solid);
+
+        tangle.add_milestone(
+            MilestoneIndex(index),
+            MilestoneMetadata::new(block_id, rand_milestone_id(), 0),
+            rand_milestone_payload(protocol_parameters().protocol_version()),
+        );
+
+        block_id
+    }
+
+    #[tokio::test]
+    async fn missing_blocks_of_cones() {
+        let tangle = tangle();
+        let requested_blocks = ResourceHandle::new(RequestedBlocks::default());
+        let (missing_1, missing_2, shared, requested) =
+            (rand_block_id(), rand_block_id(), rand_block_id(), rand_block_id());
+        let solid = insert_block(&tangle, vec![rand_block_id()], true);
+        let requested_parent = insert_block(&tangle, vec![rand_block_id()], false);
+        let parent = insert_block(&tangle, vec![missing_1, shared, solid], false);
+        let target_1 = insert_block(&tangle, vec![parent, requested_parent], false);
+        let target_2 = insert_block(&tangle, vec![missing_2, shared, requested], false);
+
+        requested_blocks.insert(requested_parent, MilestoneIndex(1), None);
+
+        let (mut cones, failed) = missing_blocks(
+            &tangle,
+            &requested_blocks,
+            &[(MilestoneIndex(1), target_1), (MilestoneIndex(2), target_2)],
+        )
+        .await;
+
+        assert!(failed.is_empty());
+        assert_eq!(cones.len(), 2);
+        assert_eq!(cones[0].0, MilestoneIndex(1));
+        assert_eq!(cones[1].0, MilestoneIndex(2));
+
+        for (_, missing) in &mut cones {
+            missing.sort();
+        }
+
+        let mut expected_1 = vec![missing_1, shared];
+        let mut expected_2 = vec![missing_2, shared, requested];
+        expected_1.sort();
+        expected_2.sort();
+
+        assert_eq!(cones[0].1, expected_1);
+        assert_eq!(cones[1].1, expected_2);
+    }
+
+    #[test]
+    fn select_requests_deduplicates_oldest_first() {
+        let (a, b, c) = (rand_block_id(), rand_block_id(), rand_block_id());
+        let cones = vec![(MilestoneIndex(1), vec![a, b]), (MilestoneIndex(2), vec![b, c])];
+
+        let (requests, missing_len) = select_requests(cones, 10);
+
+        assert_eq!(missing_len, 3);
+        assert_eq!(
+            requests,
+            vec![(a, MilestoneIndex(1)), (b, MilestoneIndex(1)), (c, MilestoneIndex(2))]
+        );
+    }
+
+    #[test]
+    fn select_requests_respects_budget() {
+        let (a, b, c) = (rand_block_id(), rand_block_id(), rand_block_id());
+        let cones = vec![(MilestoneIndex(1), vec![a]), (MilestoneIndex(2), vec![b, c])];
+
+        let (requests, missing_len) = select_requests(cones.clone(), 2);
+
+        assert_eq!(missing_len, 3);
+        assert_eq!(requests, vec![(a, MilestoneIndex(1)), (b, MilestoneIndex(2))]);
+
+        let (requests, missing_len) = select_requests(cones, 0);
+
+        assert_eq!(missing_len, 3);
+        assert!(requests.is_empty());
+    }
+
+    #[test]
+    fn cone_walk_retries_back_off_then_give_up() {
+        let mut retries = ConeWalkRetries::default();
+        let (index, other) = (MilestoneIndex(3), MilestoneIndex(4));
+        let mut now = Instant::now();
+
+        assert!(retries.is_due(&index, now));
+
+        for retry in 0..MAX_CONE_WALK_RETRIES {
+            let delay = retries.failed(index, now).unwrap();
+
+            assert_eq!(delay, CONE_WALK_RETRY_INTERVAL * 2u32.pow(retry));
+            assert!(!retries.is_due(&index, now));
+            assert!(retries.is_due(&other, now));
+
+            now += delay;
+
+            assert!(retries.is_due(&index, now));
+        }
+
+        assert_eq!(retries.failed(index, now), None);
+        assert!(!retries.is_due(&index, now + Duration::from_secs(3600)));
+
+        retries.prune(index);
+
+        assert!(retries.is_due(&index, now));
+    }
+
+    #[tokio::test]
+    async fn solidification_targets_window() {
+        let tangle = tangle();
+        let solid = [insert_milestone(&tangle, 1, true), insert_milestone(&tangle, 2, true)];
+        let unsolid_3 = insert_milestone(&tangle, 3, false);
+        // Solid milestones following an unsolid one can't be solidified yet.
+        insert_milestone(&tangle, 4, true);
+        let unsolid_5 = insert_milestone(&tangle, 5, false);
+        insert_milestone(&tangle, 6, false);
+
+        let (solid_targets, solidifying) =
+            solidification_targets(&tangle, MilestoneIndex(0), MilestoneIndex(6), 2).await;
+
+        assert_eq!(
+            solid_targets
+                .iter()
+                .map(|(index, milestone)| (*index, *milestone.block_id()))
+                .collect::<Vec<_>>(),
+            vec![(MilestoneIndex(1), solid[0]), (MilestoneIndex(2), solid[1])]
+        );
+        assert_eq!(
+            solidifying,
+            vec![(MilestoneIndex(3), unsolid_3), (MilestoneIndex(5), unsolid_5)]
+        );
+    }
+
+    #[tokio::test]
+    async fn solidification_targets_stop_at_missing_milestone() {
+        let tangle = tangle();
+        let unsolid = insert_milestone(&tangle, 1, false);
+        insert_milestone(&tangle, 3, false);
+
+        let (solid, solidifying) = solidification_targets(&tangle, MilestoneIndex(0), MilestoneIndex(3), 10).await;
+
+        assert!(solid.is_empty());
+        assert_eq!(solidifying, vec![(MilestoneIndex(1), unsolid)]);
+
+        let (solid, solidifying) = solidification_targets(&tangle, MilestoneIndex(0), MilestoneIndex(0), 10).await;
+
+        assert!(solid.is_empty());
+        assert!(solidifying.is_empty());
+    }
+}
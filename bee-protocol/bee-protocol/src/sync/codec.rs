@@ -0,0 +1,267 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Framing of the fast sync streams.
+//!
+//! The requester sends a single request holding an inclusive range of milestone indexes. For every milestone of the
+//! range, the responder answers with a cone header followed by the blocks of the past cone of the milestone in
+//! topological order, the milestone block being the last one. The response ends with a done frame, which may come
+//! early if the responder doesn't have the whole range.
+
+use std::time::Duration;
+
+use bee_block::{payload::milestone::MilestoneIndex, Block, BlockId};
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Future};
+
+const FRAME_CONE: u8 = 0;
+const FRAME_BLOCK: u8 = 1;
+const FRAME_DONE: u8 = 2;
+
+/// Errors occurring during a fast sync.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SyncError {
+    #[error("i/o error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("timed out")]
+    Timeout,
+    #[error("invalid frame tag {0}")]
+    InvalidFrame(u8),
+    #[error("unexpected frame")]
+    UnexpectedFrame,
+    #[error("block of {0} bytes exceeds the maximum block length")]
+    BlockTooLarge(usize),
+    #[error("invalid milestone range {0}..={1}")]
+    InvalidRange(MilestoneIndex, MilestoneIndex),
+    #[error("unexpected cone of milestone {0}")]
+    UnexpectedCone(MilestoneIndex),
+    #[error("cone of milestone {0} has too many blocks: {1}")]
+    ConeTooLarge(MilestoneIndex, u32),
+    #[error("invalid block: {0}")]
+    InvalidBlock(String),
+    #[error("cone of milestone {0} doesn't end with the milestone")]
+    MissingMilestone(MilestoneIndex),
+    #[error("invalid milestone {0}: {1}")]
+    InvalidMilestone(MilestoneIndex, String),
+    #[error("duplicate block {0}")]
+    DuplicateBlock(BlockId),
+    #[error("parent {1} of block {0} is unknown")]
+    UnknownParent(BlockId, BlockId),
+    #[error("cone of milestone {0} contains blocks the milestone doesn't reference")]
+    UnreferencedBlocks(MilestoneIndex),
+    #[error("inclusion merkle root mismatch for milestone {0}")]
+    InclusionMerkleRootMismatch(MilestoneIndex),
+}
+
+/// A request for the cones of an inclusive range of milestones.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct SyncRequest {
+    pub(crate) start: MilestoneIndex,
+    pub(crate) end: MilestoneIndex,
+}
+
+/// A frame of a fast sync response.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum SyncFrame {
+    /// Announces the cone of a milestone made of `num_blocks` block frames.
+    Cone { index: MilestoneIndex, num_blocks: u32 },
+    /// A packed block.
+    Block(Vec<u8>),
+    /// Ends the response.
+    Done,
+}
+
+async fn with_timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, SyncError>>,
+) -> Result<T, SyncError> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| SyncError::Timeout)?
+}
+
+async fn read_u32<R: AsyncRead + Unpin>(reader: &mut R) -> Result<u32, SyncError> {
+    let mut buf = [0u8; 4];
+
+    reader.read_exact(&mut buf).await?;
+
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub(crate) async fn write_request<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    request: &SyncRequest,
+) -> Result<(), SyncError> {
+    writer.write_all(&request.start.to_le_bytes()).await?;
+    writer.write_all(&request.end.to_le_bytes()).await?;
+    writer.flush().await?;
+
+    Ok(())
+}
+
+pub(crate) async fn read_request<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    timeout: Duration,
+) -> Result<SyncRequest, SyncError> {
+    with_timeout(timeout, async {
+        let start = MilestoneIndex(read_u32(reader).await?);
+        let end = MilestoneIndex(read_u32(reader).await?);
+
+        if end < start {
+            return Err(SyncError::InvalidRange(start, end));
+        }
+
+        Ok(SyncRequest { start, end })
+    })
+    .await
+}
+
+pub(crate) async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &SyncFrame) -> Result<(), SyncError> {
+    match frame {
+        SyncFrame::Cone { index, num_blocks } => {
+            writer.write_all(&[FRAME_CONE]).await?;
+            writer.write_all(&index.to_le_bytes()).await?;
+            writer.write_all(&num_blocks.to_le_bytes()).await?;
+        }
+        SyncFrame::Block(bytes) => {
+            writer.write_all(&[FRAME_BLOCK]).await?;
+            writer.write_all(&(bytes.len() as u32).to_le_bytes()).await?;
+            writer.write_all(bytes).await?;
+        }
+        SyncFrame::Done => {
+            writer.write_all(&[FRAME_DONE]).await?;
+            writer.flush().await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    timeout: Duration,
+) -> Result<SyncFrame, SyncError> {
+    with_timeout(timeout, async {
+        let mut tag = [0u8; 1];
+
+        reader.read_exact(&mut tag).await?;
+
+        match tag[0] {
+            FRAME_CONE => Ok(SyncFrame::Cone {
+                index: MilestoneIndex(read_u32(reader).await?),
+                num_blocks: read_u32(reader).await?,
+            }),
+            FRAME_BLOCK => {
+                let len = read_u32(reader).await? as usize;
+
+                if len > Block::LENGTH_MAX {
+                    return Err(SyncError::BlockTooLarge(len));
+                }
+
+                let mut bytes = vec![0u8; len];
+                reader.read_exact(&mut bytes).await?;
+
+                Ok(SyncFrame::Block(bytes))
+            }
+            FRAME_DONE => Ok(SyncFrame::Done),
+            tag => Err(SyncError::InvalidFrame(tag)),
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[tokio::test]
+    async fn request_round_trip() {
+        let request = SyncRequest {
+            start: MilestoneIndex(42),
+            end: MilestoneIndex(141),
+        };
+        let mut writer = Cursor::new(Vec::new());
+
+        write_request(&mut writer, &request).await.unwrap();
+
+        let mut reader = Cursor::new(writer.into_inner());
+
+        assert_eq!(read_request(&mut reader, TIMEOUT).await.unwrap(), request);
+    }
+
+    #[tokio::test]
+    async fn invalid_request_range() {
+        let mut writer = Cursor::new(Vec::new());
+
+        write_request(
+            &mut writer,
+            &SyncRequest {
+                start: MilestoneIndex(42),
+                end: MilestoneIndex(41),
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut reader = Cursor::new(writer.into_inner());
+
+        assert!(matches!(
+            read_request(&mut reader, TIMEOUT).await,
+            Err(SyncError::InvalidRange(MilestoneIndex(42), MilestoneIndex(41)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn frames_round_trip() {
+        let frames = [
+            SyncFrame::Cone {
+                index: MilestoneIndex(42),
+                num_blocks: 2,
+            },
+            SyncFrame::Block(vec![1, 2, 3]),
+            SyncFrame::Block(vec![0; Block::LENGTH_MAX]),
+            SyncFrame::Done,
+        ];
+        let mut writer = Cursor::new(Vec::new());
+
+        for frame in &frames {
+            write_frame(&mut writer, frame).await.unwrap();
+        }
+
+        let mut reader = Cursor::new(writer.into_inner());
+
+        for frame in &frames {
+            assert_eq!(&read_frame(&mut reader, TIMEOUT).await.unwrap(), frame);
+        }
+        assert!(matches!(read_frame(&mut reader, TIMEOUT).await, Err(SyncError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn oversized_block_frame() {
+        let mut writer = Cursor::new(Vec::new());
+
+        write_frame(&mut writer, &SyncFrame::Block(vec![0; Block::LENGTH_MAX + 1]))
+            .await
+            .unwrap();
+
+        let mut reader = Cursor::new(writer.into_inner());
+
+        assert!(matches!(
+            read_frame(&mut reader, TIMEOUT).await,
+            Err(SyncError::BlockTooLarge(len)) if len == Block::LENGTH_MAX + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_frame_tag() {
+        let mut reader = Cursor::new(vec![FRAME_DONE + 1]);
+
+        assert!(matches!(
+            read_frame(&mut reader, TIMEOUT).await,
+            Err(SyncError::InvalidFrame(tag)) if tag == FRAME_DONE + 1
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use bee_block::{
+    payload::{milestone::MilestoneIndex, Payload},
+    Block, BlockId,
+};
+use bee_ledger::consensus::merkle_root;
+use bee_tangle::Tangle;
+
+use crate::{
+    block::validate_milestone, storage::StorageBackend, sync::SyncError,
+    types::milestone_key_manager::MilestoneKeyManager,
+};
+
+/// Orders the blocks of a cone like the white flag traversal does, starting from the parents of its milestone.
+/// Blocks outside of the cone are considered already visited.
+fn white_flag_order(parents: &[BlockId], cone: &HashMap<BlockId, &Block>) -> Vec<BlockId> {
+    let mut block_ids = parents.iter().rev().copied().collect::<Vec<_>>();
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::with_capacity(cone.len());
+
+    while let Some(block_id) = block_ids.last() {
+        if let Some(block) = cone.get(block_id) {
+            if let Some(unvisited) = block.parents().iter().find(|p| !visited.contains(*p)) {
+                block_ids.push(*unvisited);
+            } else {
+                if visited.insert(*block_id) {
+                    ordered.push(*block_id);
+                }
+                block_ids.pop();
+            }
+        } else {
+            visited.insert(*block_id);
+            block_ids.pop();
+        }
+    }
+
+    ordered
+}
+
+/// Collects the past cone of a confirmed milestone in white flag order, followed by the milestone block itself.
+/// Returns `None` if the milestone or part of its cone is not available.
+pub(crate) fn collect_cone<B: StorageBackend>(tangle: &Tangle<B>, index: MilestoneIndex) -> Option<Vec<Block>> {
+    let milestone_block_id = tangle.get_milestone_block_id(index)?;
+    let milestone_block = tangle.get(&milestone_block_id)?;
+    let mut cone = HashMap::new();
+    let mut block_ids = milestone_block.parents().to_vec();
+
+    while let Some(block_id) = block_ids.pop() {
+        if cone.contains_key(&block_id) {
+            continue;
+        }
+
+        // Blocks referenced by previous milestones and solid entry points don't belong to the cone.
+        if let Some((block, metadata)) = tangle.get_block_and_metadata(&block_id) {
+            if metadata.milestone_index() == Some(index) {
+                block_ids.extend(block.parents().iter().copied());
+                cone.insert(block_id, block);
+            }
+        }
+    }
+
+    let blocks = cone.iter().map(|(id, block)| (*id, block)).collect::<HashMap<_, _>>();
+    let mut ordered = white_flag_order(milestone_block.parents(), &blocks)
+        .iter()
+        .filter_map(|block_id| cone.get(block_id).cloned())
+        .collect::<Vec<_>>();
+
+    ordered.push(milestone_block);
+
+    Some(ordered)
+}
+
+/// Checks that the parents of a block were either sent before it or are already known.
+async fn check_parents<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    block_id: &BlockId,
+    block: &Block,
+    cone: &HashMap<BlockId, &Block>,
+    known: &HashSet<BlockId>,
+) -> Result<(), SyncError> {
+    for parent in block.parents().iter() {
+        if !cone.contains_key(parent)
+            && !known.contains(parent)
+            && !tangle.contains(parent)
+            && !tangle.is_solid_entry_point(parent).await
+        {
+            return Err(SyncError::UnknownParent(*block_id, *parent));
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies a cone received from a peer before any of its blocks is stored: the cone has to be topologically ordered,
+/// end with a valid milestone of the expected index and match the inclusion merkle root of that milestone.
+/// `known` holds the blocks of the cones already verified but possibly not yet stored.
+pub(crate) async fn verify_cone<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    key_manager: &MilestoneKeyManager,
+    index: MilestoneIndex,
+    blocks: &[(BlockId, Block)],
+    known: &HashSet<BlockId>,
+) -> Result<(), SyncError> {
+    let ((milestone_block_id, milestone_block), blocks) =
+        blocks.split_last().ok_or(SyncError::MissingMilestone(index))?;
+
+    let milestone = match milestone_block.payload() {
+        Some(Payload::Milestone(milestone)) if milestone.essence().index() == index => milestone,
+        _ => return Err(SyncError::MissingMilestone(index)),
+    };
+
+    validate_milestone(*milestone_block_id, milestone_block, milestone, key_manager)
+        .map_err(|e| SyncError::InvalidMilestone(index, format!("{:?}", e)))?;
+
+    let mut cone = HashMap::with_capacity(blocks.len());
+
+    for (block_id, block) in blocks {
+        check_parents(tangle, block_id, block, &cone, known).await?;
+
+        if cone.insert(*block_id, block).is_some() {
+            return Err(SyncError::DuplicateBlock(*block_id));
+        }
+    }
+
+    check_parents(tangle, milestone_block_id, milestone_block, &cone, known).await?;
+
+    let referenced = white_flag_order(milestone_block.parents(), &cone);
+
+    if referenced.len() != cone.len() {
+        return Err(SyncError::UnreferencedBlocks(index));
+    }
+
+    if merkle_root(&referenced) != *milestone.essence().inclusion_merkle_root() {
+        return Err(SyncError::InclusionMerkleRootMismatch(index));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::{
+        parent::Parents,
+        payload::milestone::{MerkleRoot, MilestoneEssence, MilestoneId, MilestoneOptions, MilestonePayload},
+        protocol::protocol_parameters,
+        rand::block::{rand_block_id, rand_block_with_parents},
+        signature::{Ed25519Signature, Signature},
+        BlockBuilder,
+    };
+    use bee_runtime::resource::ResourceHandle;
+    use bee_storage::backend::StorageBackend as _;
+    use bee_storage_memory::storage::Storage;
+    use bee_tangle::config::TangleConfig;
+    use crypto::signatures::ed25519::SecretKey;
+
+    use super::*;
+    use crate::types::milestone_key_range::MilestoneKeyRange;
+
+    const INDEX: MilestoneIndex = MilestoneIndex(42);
+
+    fn tangle() -> Tangle<Storage> {
+        Tangle::new(
+            TangleConfig::build().finish(),
+            ResourceHandle::new(Storage::start(()).unwrap()),
+        )
+    }
+
+    fn key_manager(key: &SecretKey) -> MilestoneKeyManager {
+        MilestoneKeyManager::new(
+            1,
+            vec![MilestoneKeyRange::new(
+                hex::encode(key.public_key().to_bytes()),
+                MilestoneIndex(0),
+                MilestoneIndex(0),
+            )]
+            .into_boxed_slice(),
+        )
+    }
+
+    fn block(parents: Vec<BlockId>) -> (BlockId, Block) {
+        let block = rand_block_with_parents(Parents::new(parents).unwrap(), protocol_parameters().min_pow_score());
+
+        (block.id(), block)
+    }
+
+    fn milestone(parents: Vec<BlockId>, inclusion_merkle_root: MerkleRoot, key: &SecretKey) -> (BlockId, Block) {
+        let parents = Parents::new(parents).unwrap();
+        let essence = MilestoneEssence::new(
+            INDEX,
+            0,
+            protocol_parameters().protocol_version(),
+            MilestoneId::null(),
+            parents.clone(),
+            inclusion_merkle_root,
+            MerkleRoot::null(),
+            Vec::new(),
+            MilestoneOptions::new(Vec::new()).unwrap(),
+        )
+        .unwrap();
+        let signature = Signature::Ed25519(Ed25519Signature::new(
+            key.public_key().to_bytes(),
+            key.sign(&essence.hash()).to_bytes(),
+        ));
+        let block = BlockBuilder::<u64>::new(parents)
+            .with_payload(MilestonePayload::new(essence, vec![signature]).unwrap().into())
+            .with_nonce_provider(0)
+            .finish(0)
+            .unwrap();
+
+        (block.id(), block)
+    }
+
+    /// A cone on top of a known block: `first` <- `second` <- milestone.
+    fn cone(key: &SecretKey, known: BlockId) -> Vec<(BlockId, Block)> {
+        let first = block(vec![known]);
+        let second = block(vec![first.0]);
+        let milestone = milestone(vec![second.0], merkle_root(&[first.0, second.0]), key);
+
+        vec![first, second, milestone]
+    }
+
+    #[tokio::test]
+    async fn valid_cone() {
+        let key = SecretKey::generate().unwrap();
+        let known = rand_block_id();
+        let cone = cone(&key, known);
+
+        verify_cone(&tangle(), &key_manager(&key), INDEX, &cone, &HashSet::from([known]))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn wrong_signature() {
+        let key = SecretKey::generate().unwrap();
+        let known = rand_block_id();
+        let cone = cone(&SecretKey::generate().unwrap(), known);
+
+        assert!(matches!(
+            verify_cone(&tangle(), &key_manager(&key), INDEX, &cone, &HashSet::from([known])).await,
+            Err(SyncError::InvalidMilestone(INDEX, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn wrong_merkle_root() {
+        let key = SecretKey::generate().unwrap();
+        let known = rand_block_id();
+        let mut cone = cone(&key, known);
+        let second = cone[1].0;
+
+        cone[2] = milestone(vec![second], merkle_root(&[second]), &key);
+
+        assert!(matches!(
+            verify_cone(&tangle(), &key_manager(&key), INDEX, &cone, &HashSet::from([known])).await,
+            Err(SyncError::InclusionMerkleRootMismatch(INDEX))
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_block() {
+        let key = SecretKey::generate().unwrap();
+        let known = rand_block_id();
+        let mut cone = cone(&key, known);
+        let (first, _) = cone.remove(0);
+
+        assert!(matches!(
+            verify_cone(&tangle(), &key_manager(&key), INDEX, &cone, &HashSet::from([known])).await,
+            Err(SyncError::UnknownParent(_, parent)) if parent == first
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_milestone() {
+        let key = SecretKey::generate().unwrap();
+        let known = rand_block_id();
+        let mut cone = cone(&key, known);
+
+        cone.pop();
+
+        assert!(matches!(
+            verify_cone(&tangle(), &key_manager(&key), INDEX, &cone, &HashSet::from([known])).await,
+            Err(SyncError::MissingMilestone(INDEX))
+        ));
+        assert!(matches!(
+            verify_cone(&tangle(), &key_manager(&key), INDEX, &[], &HashSet::new()).await,
+            Err(SyncError::MissingMilestone(INDEX))
+        ));
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bulk synchronization of milestone cones with trusted peers over dedicated streams.
+
+mod codec;
+mod cone;
+mod worker;
+
+pub(crate) use self::{
+    codec::SyncError,
+    worker::{trusted_peers, FastSyncWorker, FastSyncWorkerEvent},
+};
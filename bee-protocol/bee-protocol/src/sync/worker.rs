@@ -0,0 +1,429 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    any::TypeId,
+    cmp,
+    collections::HashSet,
+    convert::Infallible,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use bee_block::{payload::milestone::MilestoneIndex, Block};
+use bee_gossip::{alias, Command, NetworkCommandSender, Origin, PeerId, SyncStream};
+use bee_ledger::parameters::{ProtocolParametersManager, ProtocolParametersWorker};
+use bee_runtime::{node::Node, resource::ResourceHandle, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_tangle::{Tangle, TangleWorker};
+use futures::{AsyncWriteExt, StreamExt};
+use log::{debug, info, warn};
+use packable::PackableExt;
+use parking_lot::Mutex;
+use tokio::{
+    sync::{mpsc, Semaphore},
+    time::interval,
+};
+use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
+
+use crate::{
+    block::{HasherWorker, HasherWorkerEvent},
+    config::{ProtocolConfig, ProtocolFastSyncConfig},
+    packets::BlockPacket,
+    peer::PeerManager,
+    storage::StorageBackend,
+    sync::{
+        codec::{read_frame, read_request, write_frame, write_request, SyncFrame, SyncRequest},
+        cone::{collect_cone, verify_cone},
+        SyncError,
+    },
+    types::milestone_key_manager::MilestoneKeyManager,
+    PeerManagerResWorker,
+};
+
+const SYNC_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+// Bounds the memory a peer can make us allocate for a single cone.
+const MAX_CONE_BLOCKS: u32 = 1 << 16;
+
+/// Parses the peers trusted for fast sync, skipping invalid ones.
+pub(crate) fn trusted_peers(config: &ProtocolFastSyncConfig) -> Vec<PeerId> {
+    config
+        .trusted_peers
+        .iter()
+        .filter_map(|peer_id| match PeerId::from_str(peer_id) {
+            Ok(peer_id) => Some(peer_id),
+            Err(e) => {
+                warn!("Invalid trusted peer {}: {}.", peer_id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+pub(crate) struct FastSyncWorkerEvent {
+    pub(crate) peer_id: PeerId,
+    pub(crate) origin: Origin,
+    pub(crate) stream: SyncStream,
+}
+
+pub(crate) struct FastSyncWorker {
+    pub(crate) tx: mpsc::UnboundedSender<FastSyncWorkerEvent>,
+}
+
+struct PendingRequest {
+    peer_id: PeerId,
+    request: SyncRequest,
+    requested_at: Instant,
+}
+
+#[derive(Default)]
+struct SyncState {
+    /// The request waiting for its stream to be opened.
+    pending: Option<PendingRequest>,
+    /// Whether cones are being downloaded.
+    running: bool,
+    /// The highest milestone index whose cone was downloaded and verified.
+    synced_index: MilestoneIndex,
+}
+
+async fn serve<B: StorageBackend>(
+    tangle: ResourceHandle<Tangle<B>>,
+    mut stream: SyncStream,
+    range_size: u32,
+    timeout: Duration,
+) -> Result<u32, SyncError> {
+    let SyncRequest { start, end } = read_request(&mut stream, timeout).await?;
+    let end = cmp::min(
+        *end,
+        cmp::min(
+            start.saturating_add(range_size - 1),
+            *tangle.get_confirmed_milestone_index(),
+        ),
+    );
+    let mut served = 0;
+
+    if start > tangle.get_pruning_index() {
+        for index in *start..=end {
+            let tangle = tangle.clone();
+
+            // Collecting a cone is synchronous, hence the blocking task.
+            let cone = match tokio::task::spawn_blocking(move || collect_cone(&tangle, MilestoneIndex(index))).await {
+                Ok(Some(cone)) => cone,
+                _ => break,
+            };
+
+            write_frame(
+                &mut stream,
+                &SyncFrame::Cone {
+                    index: MilestoneIndex(index),
+                    num_blocks: cone.len() as u32,
+                },
+            )
+            .await?;
+
+            for block in cone {
+                write_frame(&mut stream, &SyncFrame::Block(block.pack_to_vec())).await?;
+            }
+
+            served += 1;
+        }
+    }
+
+    write_frame(&mut stream, &SyncFrame::Done).await?;
+    stream.close().await?;
+
+    Ok(served)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn fetch<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    protocol_parameters: &ProtocolParametersManager,
+    key_manager: &MilestoneKeyManager,
+    hasher: &mpsc::UnboundedSender<HasherWorkerEvent>,
+    peer_id: PeerId,
+    mut stream: SyncStream,
+    request: SyncRequest,
+    timeout: Duration,
+) -> Result<Option<MilestoneIndex>, SyncError> {
+    write_request(&mut stream, &request).await?;
+
+    // Blocks of the cones already verified, they may not have reached the tangle yet.
+    let mut known = HashSet::new();
+    let mut synced_index = None;
+    let mut next_index = request.start;
+
+    loop {
+        let (index, num_blocks) = match read_frame(&mut stream, timeout).await? {
+            SyncFrame::Cone { index, num_blocks } => (index, num_blocks),
+            SyncFrame::Done => break,
+            SyncFrame::Block(_) => return Err(SyncError::UnexpectedFrame),
+        };
+
+        if index != next_index || index > request.end {
+            return Err(SyncError::UnexpectedCone(index));
+        }
+        if num_blocks > MAX_CONE_BLOCKS {
+            return Err(SyncError::ConeTooLarge(index, num_blocks));
+        }
+
+        let protocol_parameters = protocol_parameters.current();
+        let mut blocks = Vec::with_capacity(num_blocks as usize);
+        let mut packets = Vec::with_capacity(num_blocks as usize);
+
+        for _ in 0..num_blocks {
+            let bytes = match read_frame(&mut stream, timeout).await? {
+                SyncFrame::Block(bytes) => bytes,
+                _ => return Err(SyncError::UnexpectedFrame),
+            };
+            let block = Block::unpack_strict(&bytes[..], &protocol_parameters)
+                .map_err(|e| SyncError::InvalidBlock(format!("{:?}", e)))?;
+
+            blocks.push((block.id(), block));
+            packets.push(bytes);
+        }
+
+        verify_cone(tangle, key_manager, index, &blocks, &known).await?;
+
+        // The cone is only handed over to the regular block processing once fully verified, parents first.
+        for ((block_id, _), bytes) in blocks.iter().zip(packets) {
+            known.insert(*block_id);
+
+            if let Err(e) = hasher.send(HasherWorkerEvent {
+                from: Some(peer_id),
+                block_packet: BlockPacket::new(bytes),
+                notifier: None,
+            }) {
+                warn!("Sending block {} to the hasher failed: {}.", block_id, e);
+            }
+        }
+
+        synced_index.replace(index);
+        next_index = MilestoneIndex(*index + 1);
+    }
+
+    Ok(synced_index)
+}
+
+#[async_trait]
+impl<N: Node> Worker<N> for FastSyncWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ProtocolConfig;
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![
+            TypeId::of::<TangleWorker>(),
+            TypeId::of::<HasherWorker>(),
+            TypeId::of::<PeerManagerResWorker>(),
+            TypeId::of::<ProtocolParametersWorker>(),
+        ]
+        .leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let protocol_parameters = node.resource::<ProtocolParametersManager>();
+        let peer_manager = node.resource::<PeerManager>();
+        let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        let hasher = node.worker::<HasherWorker>().unwrap().tx.clone();
+        let key_manager = Arc::new(MilestoneKeyManager::new(
+            config.coordinator.public_key_count,
+            config.coordinator.public_key_ranges.into_boxed_slice(),
+        ));
+
+        let fast_sync = config.fast_sync;
+        let timeout = Duration::from_secs(fast_sync.timeout);
+        let range_size = fast_sync.range_size;
+        let state = Arc::new(Mutex::new(SyncState::default()));
+        // Serving cones is expensive, only a few streams are served at once.
+        let serving = Arc::new(Semaphore::new(fast_sync.max_streams));
+
+        if fast_sync.enabled && !peer_manager.trusted_peers().is_empty() {
+            let tangle = tangle.clone();
+            let state = state.clone();
+            let peer_manager = peer_manager.clone();
+
+            node.spawn::<Self, _, _>(|shutdown| async move {
+                info!("Requester running.");
+
+                let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(SYNC_CHECK_INTERVAL)));
+
+                while ticker.next().await.is_some() {
+                    let mut state = state.lock();
+
+                    if state.running
+                        || matches!(&state.pending, Some(pending) if pending.requested_at.elapsed() < timeout)
+                    {
+                        continue;
+                    }
+
+                    state.pending = None;
+
+                    let smi = *tangle.get_solid_milestone_index();
+                    let start = cmp::max(smi, *state.synced_index) + 1;
+
+                    // Lets the node process the downloaded cones before fetching more.
+                    if start > smi.saturating_add(2 * range_size) {
+                        continue;
+                    }
+
+                    let peer = peer_manager.trusted_peers().iter().find_map(|peer_id| {
+                        peer_manager
+                            .get_map(peer_id, |peer| {
+                                let peer_smi = *peer.0.solid_milestone_index();
+
+                                // Peers that didn't announce their capabilities may predate them, they are still tried.
+                                let serves_fast_sync = !matches!(
+                                    peer.0.capabilities(),
+                                    Some(capabilities) if !capabilities.serves_fast_sync()
+                                );
+
+                                (peer.0.is_connected()
+                                    && serves_fast_sync
+                                    && peer.0.has_data(MilestoneIndex(start))
+                                    && peer_smi > smi.saturating_add(fast_sync.threshold))
+                                .then_some((*peer_id, peer_smi))
+                            })
+                            .flatten()
+                    });
+
+                    if let Some((peer_id, peer_smi)) = peer {
+                        let end = cmp::min(start.saturating_add(range_size - 1), peer_smi);
+
+                        if end < start {
+                            continue;
+                        }
+
+                        debug!(
+                            "Requesting cones of milestones {} to {} from {}.",
+                            start,
+                            end,
+                            alias!(peer_id)
+                        );
+
+                        if let Err(e) = gossip_command_tx.send(Command::OpenSyncStream { peer_id }) {
+                            warn!("Requesting a sync stream from {} failed: {}.", alias!(peer_id), e);
+                            continue;
+                        }
+
+                        state.pending = Some(PendingRequest {
+                            peer_id,
+                            request: SyncRequest {
+                                start: MilestoneIndex(start),
+                                end: MilestoneIndex(end),
+                            },
+                            requested_at: Instant::now(),
+                        });
+                    }
+                }
+
+                info!("Requester stopped.");
+            });
+        }
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+
+            while let Some(FastSyncWorkerEvent {
+                peer_id,
+                origin,
+                stream,
+            }) = receiver.next().await
+            {
+                match origin {
+                    Origin::Inbound => {
+                        // Cones are only served to manually added and trusted peers, dropping the stream closes it.
+                        if !peer_manager.serves_fast_sync(&peer_id) {
+                            debug!("Refusing to serve milestone cones to unknown peer {}.", alias!(peer_id));
+                            continue;
+                        }
+
+                        let permit = match serving.clone().try_acquire_owned() {
+                            Ok(permit) => permit,
+                            Err(_) => {
+                                debug!("Too many fast sync streams, refusing to serve {}.", alias!(peer_id));
+                                continue;
+                            }
+                        };
+                        let tangle = tangle.clone();
+
+                        tokio::spawn(async move {
+                            match serve(tangle, stream, range_size, timeout).await {
+                                Ok(served) => debug!("Served {} milestone cones to {}.", served, alias!(peer_id)),
+                                Err(e) => debug!("Serving milestone cones to {} failed: {}.", alias!(peer_id), e),
+                            }
+
+                            drop(permit);
+                        });
+                    }
+                    Origin::Outbound => {
+                        let request = {
+                            let mut state = state.lock();
+
+                            match state.pending.take() {
+                                Some(pending) if pending.peer_id == peer_id => {
+                                    state.running = true;
+                                    pending.request
+                                }
+                                pending => {
+                                    // Dropping the stream closes it.
+                                    state.pending = pending;
+                                    continue;
+                                }
+                            }
+                        };
+
+                        let tangle = tangle.clone();
+                        let protocol_parameters = protocol_parameters.clone();
+                        let key_manager = key_manager.clone();
+                        let hasher = hasher.clone();
+                        let state = state.clone();
+
+                        tokio::spawn(async move {
+                            let result = fetch(
+                                &tangle,
+                                &protocol_parameters,
+                                &key_manager,
+                                &hasher,
+                                peer_id,
+                                stream,
+                                request,
+                                timeout,
+                            )
+                            .await;
+                            let mut state = state.lock();
+
+                            match result {
+                                Ok(Some(synced_index)) => {
+                                    info!(
+                                        "Fast synced milestones {} to {} from {}.",
+                                        request.start,
+                                        synced_index,
+                                        alias!(peer_id)
+                                    );
+                                    state.synced_index = cmp::max(state.synced_index, synced_index);
+                                }
+                                Ok(None) => debug!("{} had no milestone cone to serve.", alias!(peer_id)),
+                                Err(e) => warn!("Fast sync with {} failed: {}.", alias!(peer_id), e),
+                            }
+
+                            state.running = false;
+                        });
+                    }
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self { tx })
+    }
+}
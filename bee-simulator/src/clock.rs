@@ -0,0 +1,32 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A simulated clock.
+
+use std::time::Duration;
+
+/// A clock that only advances when told to.
+///
+/// It timestamps the genesis and the milestones of a simulation, so that the ledger state doesn't depend on when or how
+/// fast a scenario runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Clock {
+    timestamp: u32,
+}
+
+impl Clock {
+    /// Creates a new [`Clock`] starting at the given unix timestamp, in seconds.
+    pub fn new(timestamp: u32) -> Self {
+        Self { timestamp }
+    }
+
+    /// Returns the current unix timestamp of the [`Clock`], in seconds.
+    pub fn now(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Advances the [`Clock`] by the given duration, truncated to seconds.
+    pub fn advance(&mut self, duration: Duration) {
+        self.timestamp = self.timestamp.saturating_add(duration.as_secs() as u32);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A fake coordinator issuing signed milestones.
+
+use bee_block::{payload::milestone::MilestoneIndex, protocol::ProtocolParameters, Block, BlockId};
+use bee_plugin_coordinator::PreviousMilestone;
+use bee_protocol::types::{milestone_key_manager::MilestoneKeyManager, milestone_key_range::MilestoneKeyRange};
+use bee_storage_memory::storage::Storage;
+use bee_tangle::Tangle;
+use crypto::signatures::ed25519;
+use rand::Rng;
+
+use crate::error::Error;
+
+/// A fake coordinator issuing milestones signed by keys derived from the simulation seed.
+///
+/// All its keys are valid for all milestone indexes and all of them sign every milestone.
+pub struct Coordinator {
+    inner: bee_plugin_coordinator::Coordinator,
+    key_ranges: Vec<MilestoneKeyRange>,
+    protocol_parameters: ProtocolParameters,
+    latest_milestone: PreviousMilestone,
+}
+
+impl Coordinator {
+    /// Creates a new [`Coordinator`] with the given number of keys, generated from the given random number generator.
+    pub fn new(rng: &mut impl Rng, key_count: usize, protocol_parameters: ProtocolParameters) -> Self {
+        let private_keys = (0..key_count).map(|_| rng.gen()).collect::<Vec<[u8; 32]>>();
+        let key_ranges = private_keys
+            .iter()
+            .map(|bytes| {
+                MilestoneKeyRange::new(
+                    hex::encode(ed25519::SecretKey::from_bytes(*bytes).public_key().to_bytes()),
+                    MilestoneIndex(0),
+                    MilestoneIndex(0),
+                )
+            })
+            .collect::<Vec<_>>();
+        let key_manager = MilestoneKeyManager::new(key_count, key_ranges.clone().into_boxed_slice());
+
+        Self {
+            inner: bee_plugin_coordinator::Coordinator::new(&private_keys, key_manager),
+            key_ranges,
+            protocol_parameters,
+            latest_milestone: PreviousMilestone::genesis(),
+        }
+    }
+
+    /// Returns the number of keys of the [`Coordinator`], which is also the number of signatures required by nodes.
+    pub fn public_key_count(&self) -> usize {
+        self.key_ranges.len()
+    }
+
+    /// Returns the key ranges nodes need to validate the milestones of the [`Coordinator`].
+    pub fn public_key_ranges(&self) -> Vec<MilestoneKeyRange> {
+        self.key_ranges.clone()
+    }
+
+    /// Returns the index of the latest milestone issued by the [`Coordinator`].
+    pub fn latest_milestone_index(&self) -> MilestoneIndex {
+        self.latest_milestone.index
+    }
+
+    /// Returns the id of the block containing the latest milestone issued by the [`Coordinator`], if any.
+    pub fn latest_milestone_block_id(&self) -> Option<BlockId> {
+        self.latest_milestone.block_id
+    }
+
+    /// Issues the next milestone, referencing the given blocks and the previous milestone.
+    ///
+    /// The merkle roots are computed against the given tangle and storage, which therefore need to be solid up to the
+    /// given blocks and confirmed up to the previous milestone.
+    pub async fn issue(
+        &mut self,
+        tangle: &Tangle<Storage>,
+        storage: &Storage,
+        parents: Vec<BlockId>,
+        timestamp: u32,
+    ) -> Result<(BlockId, Block), Error> {
+        let block = self
+            .inner
+            .issue(
+                tangle,
+                storage,
+                &self.protocol_parameters,
+                &self.latest_milestone,
+                parents,
+                timestamp,
+                Vec::new(),
+            )
+            .await?;
+
+        // Panic: the coordinator only issues blocks containing a milestone.
+        self.latest_milestone = PreviousMilestone::from_block(&block).unwrap();
+
+        Ok((block.id(), block))
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Errors raised while building or running a simulation.
+
+use bee_block::{output::OutputId, payload::milestone::MilestoneIndex, BlockId};
+
+/// Errors raised while building or running a simulation.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A block, payload or output couldn't be built.
+    #[error("block error: {0}")]
+    Block(#[from] bee_block::Error),
+    /// The coordinator couldn't issue a milestone or build the genesis.
+    #[error("coordinator error: {0}")]
+    Coordinator(#[from] bee_plugin_coordinator::error::Error),
+    /// The gossip layer couldn't be initialized.
+    #[error("gossip error: {0}")]
+    Gossip(#[from] bee_gossip::Error),
+    /// The ledger state couldn't be computed.
+    #[error("ledger error: {0}")]
+    Ledger(#[from] bee_ledger::error::Error),
+    /// A storage operation failed.
+    #[error("storage error: {0}")]
+    Storage(#[from] bee_storage_memory::storage::Error),
+    /// A node doesn't exist.
+    #[error("unknown node {0}")]
+    UnknownNode(usize),
+    /// An account doesn't exist.
+    #[error("unknown account {0}")]
+    UnknownAccount(usize),
+    /// An account doesn't own enough funds for a transfer.
+    #[error("insufficient funds: {0} available, {1} required")]
+    InsufficientFunds(u64, u64),
+    /// A node rejected a submitted block.
+    #[error("block submission failed: {0}")]
+    BlockSubmission(String),
+    /// A block didn't become solid on a node in time.
+    #[error("block {0} not solid on node {1} in time")]
+    BlockNotSolid(BlockId, usize),
+    /// A node didn't confirm a milestone in time.
+    #[error("milestone {0} not confirmed on node {1} in time, ledger index {2}")]
+    MilestoneNotConfirmed(MilestoneIndex, usize, MilestoneIndex),
+    /// Two nodes didn't connect in time.
+    #[error("nodes {0} and {1} not connected in time")]
+    NotConnected(usize, usize),
+    /// Two nodes didn't disconnect in time.
+    #[error("nodes {0} and {1} still connected")]
+    StillConnected(usize, usize),
+    /// An unspent output is missing from the storage.
+    #[error("missing unspent output {0}")]
+    MissingUnspentOutput(OutputId),
+    /// A node failed to shut down.
+    #[error("node shutdown failed: {0}")]
+    Shutdown(String),
+}
@@ -0,0 +1,181 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The initial ledger state shared by all the nodes of a simulation.
+
+use bee_block::{
+    address::Address,
+    output::{self, OutputId},
+    payload::milestone::{MilestoneId, MilestoneIndex, ParametersMilestoneOption},
+    protocol::ProtocolParameters,
+};
+use bee_ledger::types::{snapshot::SnapshotInfo, CreatedOutput, LedgerIndex, TreasuryOutput, Unspent};
+use bee_plugin_coordinator::genesis_outputs;
+use bee_storage::access::{Batch, BatchBuilder, Insert};
+use bee_storage_memory::storage::Storage;
+use bee_tangle::solid_entry_point::SolidEntryPoint;
+use packable::PackableExt;
+
+use crate::error::Error;
+
+/// The initial ledger state shared by all the nodes of a simulation.
+///
+/// It replaces the snapshot a real node would start from: a single solid entry point, the null block, one basic output
+/// per funded address and the treasury holding the rest of the token supply.
+#[derive(Clone, Debug)]
+pub struct Genesis {
+    protocol_parameters: ProtocolParameters,
+    timestamp: u32,
+    outputs: Vec<(OutputId, CreatedOutput)>,
+    treasury: u64,
+}
+
+impl Genesis {
+    /// Creates a new [`Genesis`] funding the given addresses.
+    ///
+    /// The funds can't exceed the token supply of the protocol parameters, the part of it they don't distribute is left
+    /// in the treasury.
+    pub fn new(
+        protocol_parameters: ProtocolParameters,
+        timestamp: u32,
+        funds: impl IntoIterator<Item = (Address, u64)>,
+    ) -> Result<Self, Error> {
+        let funds = funds.into_iter().collect::<Vec<_>>();
+        let (outputs, treasury) = genesis_outputs(&protocol_parameters, timestamp, &funds)?;
+
+        Ok(Self {
+            protocol_parameters,
+            timestamp,
+            outputs,
+            treasury,
+        })
+    }
+
+    /// Returns the protocol parameters of the [`Genesis`].
+    pub fn protocol_parameters(&self) -> &ProtocolParameters {
+        &self.protocol_parameters
+    }
+
+    /// Returns the unix timestamp of the [`Genesis`], in seconds.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Returns the outputs of the [`Genesis`].
+    pub fn outputs(&self) -> &[(OutputId, CreatedOutput)] {
+        &self.outputs
+    }
+
+    /// Returns the amount held by the treasury of the [`Genesis`].
+    pub fn treasury(&self) -> u64 {
+        self.treasury
+    }
+
+    /// Writes the [`Genesis`] to the storage of a node that hasn't started yet, as a snapshot import would.
+    pub(crate) fn seed(&self, storage: &Storage) -> Result<(), Error> {
+        let network_id = self.protocol_parameters.network_id();
+        let mut batch = Storage::batch_begin();
+
+        Batch::<SolidEntryPoint, MilestoneIndex>::batch_insert(
+            storage,
+            &mut batch,
+            &SolidEntryPoint::null(),
+            &MilestoneIndex(0),
+        )?;
+
+        for (output_id, created) in &self.outputs {
+            Batch::<OutputId, CreatedOutput>::batch_insert(storage, &mut batch, output_id, created)?;
+            Batch::<Unspent, ()>::batch_insert(storage, &mut batch, &Unspent::new(*output_id), &())?;
+        }
+
+        Batch::<MilestoneIndex, ParametersMilestoneOption>::batch_insert(
+            storage,
+            &mut batch,
+            &MilestoneIndex(0),
+            &ParametersMilestoneOption::new(
+                MilestoneIndex(0),
+                self.protocol_parameters.protocol_version(),
+                self.protocol_parameters.pack_to_vec(),
+            )?,
+        )?;
+
+        storage.batch_commit(batch, true)?;
+
+        let treasury = output::TreasuryOutput::new(self.treasury, self.protocol_parameters.token_supply())?;
+
+        Insert::<(bool, TreasuryOutput), ()>::insert(
+            storage,
+            &(false, TreasuryOutput::new(treasury, MilestoneId::null())),
+            &(),
+        )?;
+        Insert::<(), LedgerIndex>::insert(storage, &(), &LedgerIndex::new(MilestoneIndex(0)))?;
+        Insert::<(), SnapshotInfo>::insert(
+            storage,
+            &(),
+            &SnapshotInfo::new(
+                network_id,
+                MilestoneIndex(0),
+                MilestoneIndex(0),
+                MilestoneIndex(0),
+                self.timestamp,
+            ),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_block::address::{Address, Ed25519Address};
+    use bee_ledger::storage::fetch_unspent_treasury_output;
+    use bee_storage::backend::StorageBackend;
+
+    use super::*;
+
+    fn protocol_parameters(token_supply: u64) -> ProtocolParameters {
+        let defaults = ProtocolParameters::default();
+
+        ProtocolParameters::new(
+            defaults.protocol_version(),
+            String::from("simulation"),
+            String::from("sim"),
+            0,
+            defaults.below_max_depth(),
+            defaults.rent_structure().clone(),
+            token_supply,
+        )
+        .unwrap()
+    }
+
+    fn funds() -> Vec<(Address, u64)> {
+        (0..3u8)
+            .map(|i| (Address::from(Ed25519Address::new([i; 32])), 1_000_000))
+            .collect()
+    }
+
+    #[test]
+    fn undistributed_supply_seeds_the_treasury() {
+        let genesis = Genesis::new(protocol_parameters(10_000_000), 0, funds()).unwrap();
+        let storage = Storage::start(()).unwrap();
+
+        genesis.seed(&storage).unwrap();
+
+        assert_eq!(genesis.outputs().len(), 3);
+        assert_eq!(genesis.treasury(), 7_000_000);
+        assert_eq!(
+            fetch_unspent_treasury_output(&storage).unwrap().inner().amount(),
+            7_000_000
+        );
+    }
+
+    #[test]
+    fn funds_exceeding_the_supply() {
+        assert!(matches!(
+            Genesis::new(protocol_parameters(2_000_000), 0, funds()),
+            Err(Error::Coordinator(
+                bee_plugin_coordinator::error::Error::GenesisExceedsTokenSupply(3_000_000, 2_000_000)
+            ))
+        ));
+    }
+}
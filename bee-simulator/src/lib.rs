@@ -0,0 +1,23 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A crate that simulates a network of in-process Bee nodes, to test protocol and consensus changes.
+//!
+//! Nodes run the regular gossip, protocol, ledger and tangle workers on memory storage and talk to each other over the
+//! in-memory gossip transport. A fake coordinator signs the milestones and a seeded random number generator drives the
+//! scenario, so that a given seed always builds the same blocks and ledger states.
+
+#![cfg_attr(doc_cfg, feature(doc_cfg))]
+#![deny(missing_docs)]
+
+pub mod clock;
+pub mod coordinator;
+pub mod error;
+pub mod genesis;
+pub mod node;
+pub mod simulation;
+
+pub use self::{
+    error::Error,
+    simulation::{Simulation, SimulationBuilder},
+};
@@ -0,0 +1,440 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process Bee node running on memory storage and the in-memory gossip transport.
+
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::{HashMap, HashSet},
+    pin::Pin,
+};
+
+use anymap::{any::Any as AnyMapAny, Map};
+use async_trait::async_trait;
+use bee_block::{output::OutputId, payload::milestone::MilestoneIndex, semantic::ConflictReason, Block, BlockId};
+use bee_gossip::{
+    stores::InMemoryBanStore, Command, Keypair, Multiaddr, NetworkCommandSender, NetworkConfig, PeerId, PublicKey,
+};
+use bee_ledger::{
+    pruning::config::PruningConfig,
+    snapshot::config::SnapshotConfig,
+    types::{CreatedOutput, LedgerIndex, Unspent},
+};
+use bee_protocol::{config::ProtocolConfig, BlockSubmitterWorker, BlockSubmitterWorkerEvent, PeerManager};
+use bee_runtime::{
+    event::Bus,
+    node::{Node, NodeBuilder, NodeInfo},
+    resource::ResourceHandle,
+    worker::Worker,
+};
+use bee_storage::{
+    access::{AsIterator, Fetch},
+    backend::StorageBackend,
+};
+use bee_storage_memory::storage::Storage;
+use bee_tangle::{config::TangleConfig, Tangle};
+use futures::{channel::oneshot, Future};
+use fxhash::FxBuildHasher;
+use log::{debug, warn};
+use packable::PackableExt;
+use tokio::task;
+
+use crate::{error::Error, genesis::Genesis};
+
+type ShutdownTx = oneshot::Sender<()>;
+type WorkerStart = dyn for<'a> FnOnce(&'a mut SimNode) -> Pin<Box<dyn Future<Output = ()> + 'a>>;
+type WorkerStop = dyn for<'a> FnOnce(&'a mut SimNode) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> + Send;
+type ResourceRegister = dyn for<'a> FnOnce(&'a mut SimNode);
+type Task = Box<dyn Future<Output = Result<(), task::JoinError>> + Send + Sync + Unpin>;
+
+/// The configuration of a [`SimNode`].
+#[derive(Clone)]
+pub struct SimNodeConfig {
+    pub(crate) alias: String,
+    pub(crate) keypair: Keypair,
+    pub(crate) genesis: Genesis,
+    pub(crate) network: NetworkConfig,
+    pub(crate) protocol: ProtocolConfig,
+    pub(crate) tangle: TangleConfig,
+}
+
+impl SimNodeConfig {
+    /// Creates a new [`SimNodeConfig`].
+    pub fn new(
+        alias: String,
+        keypair: Keypair,
+        genesis: Genesis,
+        network: NetworkConfig,
+        protocol: ProtocolConfig,
+        tangle: TangleConfig,
+    ) -> Self {
+        Self {
+            alias,
+            keypair,
+            genesis,
+            network,
+            protocol,
+            tangle,
+        }
+    }
+}
+
+/// An in-process Bee node running the gossip, protocol, ledger and tangle workers on memory storage.
+pub struct SimNode {
+    alias: String,
+    peer_id: PeerId,
+    address: Multiaddr,
+    workers: Map<dyn AnyMapAny + Send + Sync>,
+    tasks: HashMap<TypeId, Vec<(ShutdownTx, Task)>>,
+    resources: Map<dyn AnyMapAny + Send + Sync>,
+    worker_stops: HashMap<TypeId, Box<WorkerStop>>,
+    worker_order: Vec<TypeId>,
+    worker_names: HashMap<TypeId, &'static str>,
+}
+
+impl SimNode {
+    /// Returns the alias of the [`SimNode`].
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// Returns the peer id of the [`SimNode`].
+    pub fn peer_id(&self) -> &PeerId {
+        &self.peer_id
+    }
+
+    /// Returns the in-memory address the [`SimNode`] is bound to.
+    pub fn address(&self) -> &Multiaddr {
+        &self.address
+    }
+
+    /// Returns the tangle of the [`SimNode`].
+    pub fn tangle(&self) -> ResourceHandle<Tangle<Storage>> {
+        self.resource()
+    }
+
+    /// Returns the index of the latest milestone confirmed by the [`SimNode`].
+    pub fn ledger_index(&self) -> Result<MilestoneIndex, Error> {
+        Ok(Fetch::<(), LedgerIndex>::fetch(&*self.storage(), &())?
+            .map(|index| index.0)
+            .unwrap_or_default())
+    }
+
+    /// Returns the unspent outputs of the ledger of the [`SimNode`], sorted by output id.
+    pub fn unspent_outputs(&self) -> Result<Vec<(OutputId, CreatedOutput)>, Error> {
+        let storage = self.storage();
+        let mut outputs = Vec::new();
+
+        for result in AsIterator::<Unspent, ()>::iter(&*storage)? {
+            let (unspent, ()) = result?;
+            let output_id = *unspent.id();
+            let output = Fetch::<OutputId, CreatedOutput>::fetch(&*storage, &output_id)?
+                .ok_or(Error::MissingUnspentOutput(output_id))?;
+
+            outputs.push((output_id, output));
+        }
+
+        outputs.sort_unstable_by_key(|(output_id, _)| *output_id);
+
+        Ok(outputs)
+    }
+
+    /// Returns whether a block is solid on the [`SimNode`].
+    pub async fn is_solid(&self, block_id: &BlockId) -> bool {
+        self.tangle().is_solid_block(block_id).await
+    }
+
+    /// Returns the reason a confirmed block was excluded from the ledger, if it is known to the [`SimNode`].
+    pub fn conflict(&self, block_id: &BlockId) -> Option<ConflictReason> {
+        self.tangle().get_metadata(block_id).map(|metadata| metadata.conflict())
+    }
+
+    /// Returns whether the [`SimNode`] is connected to the given peer.
+    pub fn is_connected(&self, peer_id: &PeerId) -> bool {
+        self.resource::<PeerManager>().is_connected(peer_id)
+    }
+
+    /// Sends a command to the gossip layer of the [`SimNode`].
+    pub fn command(&self, command: Command) {
+        if let Err(e) = self.resource::<NetworkCommandSender>().send(command) {
+            warn!("[{}] Sending gossip command failed: {}.", self.alias, e);
+        }
+    }
+
+    /// Submits a block to the [`SimNode`] and waits for it to be processed.
+    pub async fn submit_block(&self, block: &Block) -> Result<BlockId, Error> {
+        let (notifier, waiter) = oneshot::channel();
+
+        // Panic: the submitter is part of the protocol workers every node starts.
+        self.worker::<BlockSubmitterWorker>()
+            .unwrap()
+            .tx
+            .send(BlockSubmitterWorkerEvent {
+                block: block.pack_to_vec(),
+                notifier,
+            })
+            .map_err(|e| Error::BlockSubmission(e.to_string()))?;
+
+        match waiter.await {
+            Ok(Ok(block_id)) => Ok(block_id),
+            Ok(Err(e)) => Err(Error::BlockSubmission(e.0)),
+            Err(_) => Err(Error::BlockSubmission(format!("block {} dropped", block.id()))),
+        }
+    }
+}
+
+#[async_trait]
+impl Node for SimNode {
+    type Builder = SimNodeBuilder;
+    type Backend = Storage;
+    type Error = Error;
+
+    fn register_resource<R: Any + Send + Sync>(&mut self, res: R) {
+        self.resources.insert(ResourceHandle::new(res));
+    }
+
+    fn remove_resource<R: Any + Send + Sync>(&mut self) -> Option<R> {
+        self.resources.remove::<ResourceHandle<R>>()?.try_unwrap()
+    }
+
+    #[track_caller]
+    fn resource<R: Any + Send + Sync>(&self) -> ResourceHandle<R> {
+        match self.resources.get::<ResourceHandle<R>>() {
+            Some(res) => res.clone(),
+            None => panic!("Unable to fetch node resource {}.", type_name::<R>()),
+        }
+    }
+
+    #[track_caller]
+    fn spawn<W, G, F>(&mut self, g: G)
+    where
+        W: Worker<Self>,
+        G: FnOnce(oneshot::Receiver<()>) -> F,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let task = tokio::spawn(g(rx));
+
+        self.tasks
+            .entry(TypeId::of::<W>())
+            .or_default()
+            .push((tx, Box::new(task)));
+    }
+
+    fn worker<W>(&self) -> Option<&W>
+    where
+        W: Worker<Self> + Send + Sync,
+    {
+        self.workers.get::<W>()
+    }
+
+    async fn stop(mut self) -> Result<(), Self::Error> {
+        for worker_id in self.worker_order.clone().into_iter().rev() {
+            debug!("[{}] Stopping worker {}.", self.alias, self.worker_names[&worker_id]);
+
+            for (shutdown, task) in self.tasks.remove(&worker_id).unwrap_or_default() {
+                let _ = shutdown.send(());
+                let _ = task.await;
+            }
+
+            // Panic: unwrapping cannot fail by design.
+            self.worker_stops.remove(&worker_id).unwrap()(&mut self).await;
+            self.resource::<Bus>().remove_listeners_by_id(worker_id);
+        }
+
+        // Panic: unwrapping is fine since the builder registers the backend itself.
+        self.remove_resource::<Self::Backend>()
+            .unwrap()
+            .shutdown()
+            .map_err(|e| Error::Shutdown(e.to_string()))
+    }
+}
+
+/// A builder to create a [`SimNode`].
+#[must_use]
+pub struct SimNodeBuilder {
+    config: SimNodeConfig,
+    deps: HashMap<TypeId, &'static [TypeId], FxBuildHasher>,
+    worker_starts: HashMap<TypeId, Box<WorkerStart>>,
+    worker_stops: HashMap<TypeId, Box<WorkerStop>>,
+    worker_names: HashMap<TypeId, &'static str>,
+    resource_registers: Vec<Box<ResourceRegister>>,
+}
+
+#[async_trait(?Send)]
+impl NodeBuilder<SimNode> for SimNodeBuilder {
+    type Error = Error;
+    type Config = SimNodeConfig;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            config,
+            deps: HashMap::default(),
+            worker_starts: HashMap::default(),
+            worker_stops: HashMap::default(),
+            worker_names: HashMap::default(),
+            resource_registers: Vec::default(),
+        })
+    }
+
+    fn with_worker<W: Worker<SimNode> + 'static>(self) -> Self
+    where
+        W::Config: Default,
+    {
+        self.with_worker_cfg::<W>(W::Config::default())
+    }
+
+    fn with_worker_cfg<W: Worker<SimNode> + 'static>(mut self, config: W::Config) -> Self {
+        self.deps.insert(TypeId::of::<W>(), W::dependencies());
+        self.worker_starts.insert(
+            TypeId::of::<W>(),
+            Box::new(|node| {
+                Box::pin(async move {
+                    match W::start(node, config).await {
+                        Ok(w) => {
+                            node.workers.insert(w);
+                        }
+                        Err(e) => panic!("Worker `{}` failed to start: {:?}.", type_name::<W>(), e),
+                    }
+                })
+            }),
+        );
+        self.worker_stops.insert(
+            TypeId::of::<W>(),
+            Box::new(|node| {
+                Box::pin(async move {
+                    // Panic: the worker was added when it started.
+                    let worker = node.workers.remove::<W>().unwrap();
+
+                    if let Err(e) = worker.stop(node).await {
+                        panic!("Worker `{}` failed to stop: {:?}.", type_name::<W>(), e);
+                    }
+                })
+            }),
+        );
+        self.worker_names.insert(TypeId::of::<W>(), type_name::<W>());
+        self
+    }
+
+    fn with_resource<R: Any + Send + Sync>(mut self, res: R) -> Self {
+        self.resource_registers.push(Box::new(move |node| {
+            node.register_resource(res);
+        }));
+        self
+    }
+
+    async fn finish(self) -> Result<SimNode, Self::Error> {
+        let config = self.config.clone();
+        let protocol_parameters = config.genesis.protocol_parameters();
+        let network_id = protocol_parameters.network_id();
+        let network_name = protocol_parameters.network_name().to_owned();
+        let peer_id = PeerId::from_public_key(&PublicKey::from(config.keypair.public()));
+        let address = config.network.bind_multiaddr().clone();
+
+        let storage = Storage::start(())?;
+
+        config.genesis.seed(&storage)?;
+
+        let builder = self
+            .with_resource(NodeInfo {
+                name: config.alias.clone(),
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+            })
+            .with_resource(storage)
+            .with_resource(Bus::<TypeId>::default());
+
+        let (builder, network_events) = bee_gossip::integrated::init::<SimNode, InMemoryBanStore>(
+            config.network,
+            config.keypair,
+            network_id,
+            (),
+            builder,
+        )
+        .await?;
+        let builder = bee_ledger::init::<SimNode>(
+            builder,
+            network_id,
+            protocol_parameters.clone(),
+            SnapshotConfig::build().finish(),
+            PruningConfig::build().enabled(false).finish(),
+        );
+        let builder = bee_protocol::init::<SimNode>(
+            config.protocol,
+            (network_name, network_id),
+            network_events,
+            None,
+            builder,
+        );
+        let builder = bee_tangle::init::<SimNode>(&config.tangle, builder);
+
+        let SimNodeBuilder {
+            deps,
+            mut worker_starts,
+            worker_stops,
+            worker_names,
+            resource_registers,
+            ..
+        } = builder;
+
+        let mut node = SimNode {
+            alias: config.alias,
+            peer_id,
+            address,
+            workers: Map::new(),
+            tasks: HashMap::new(),
+            resources: Map::new(),
+            worker_stops,
+            worker_order: topological_order(deps),
+            worker_names,
+        };
+
+        for register in resource_registers {
+            register(&mut node);
+        }
+
+        // Start all workers in topological order.
+        for id in node.worker_order.clone() {
+            // Panic: the order was computed from the workers that were added.
+            worker_starts.remove(&id).unwrap()(&mut node).await;
+        }
+
+        Ok(node)
+    }
+}
+
+// Sorts workers so that every worker comes after its dependencies.
+fn topological_order(graph: HashMap<TypeId, &'static [TypeId], FxBuildHasher>) -> Vec<TypeId> {
+    fn visit(
+        id: TypeId,
+        graph: &HashMap<TypeId, &'static [TypeId], FxBuildHasher>,
+        visited: &mut HashSet<TypeId>,
+        being_visited: &mut HashSet<TypeId>,
+        order: &mut Vec<TypeId>,
+    ) {
+        if visited.contains(&id) {
+            return;
+        }
+
+        if !being_visited.insert(id) {
+            panic!("Cyclic dependency detected.");
+        }
+
+        for &dependency in graph[&id] {
+            visit(dependency, graph, visited, being_visited, order);
+        }
+
+        being_visited.remove(&id);
+        visited.insert(id);
+        order.push(id);
+    }
+
+    let mut visited = HashSet::new();
+    let mut being_visited = HashSet::new();
+    let mut order = Vec::with_capacity(graph.len());
+
+    for &id in graph.keys() {
+        visit(id, &graph, &mut visited, &mut being_visited, &mut order);
+    }
+
+    order
+}
@@ -0,0 +1,714 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A network of in-process nodes driven by a scenario.
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use bee_block::{
+    address::{Address, Ed25519Address},
+    input::{Input, UtxoInput},
+    output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, InputsCommitment, Output, OutputId},
+    parent::Parents,
+    payload::{
+        milestone::MilestoneIndex,
+        transaction::{RegularTransactionEssence, TransactionEssence, TransactionPayload},
+        Payload, TaggedDataPayload,
+    },
+    protocol::ProtocolParameters,
+    semantic::ConflictReason,
+    signature::{Ed25519Signature, Signature},
+    unlock::{ReferenceUnlock, SignatureUnlock, Unlock, Unlocks},
+    Block, BlockBuilder, BlockId,
+};
+use bee_gossip::{Command, Keypair, Multiaddr, NetworkConfig, PeerId, Protocol, PublicKey, SecretKey};
+use bee_ledger::types::CreatedOutput;
+use bee_protocol::config::ProtocolConfig;
+use bee_runtime::node::{Node, NodeBuilder};
+use bee_tangle::config::TangleConfig;
+use crypto::{
+    hashes::{blake2b::Blake2b256, Digest},
+    signatures::ed25519,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    clock::Clock,
+    coordinator::Coordinator,
+    error::Error,
+    genesis::Genesis,
+    node::{SimNode, SimNodeBuilder, SimNodeConfig},
+};
+
+const DEFAULT_NODES: usize = 3;
+const DEFAULT_COORDINATOR_KEYS: usize = 3;
+const DEFAULT_COORDINATOR_NODE: usize = 0;
+const DEFAULT_GENESIS_TIMESTAMP: u32 = 1_660_000_000;
+const DEFAULT_MILESTONE_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const NETWORK_NAME: &str = "simulator";
+const BECH32_HRP: &str = "sim";
+const SPAM_TAG: &[u8] = b"simulator";
+const MAX_SPAM_PARENTS: usize = 2;
+// One parent slot is taken by the previous milestone.
+const MAX_MILESTONE_TIPS: usize = 7;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// Memory transport ports are global to the process, every simulation gets its own.
+static NEXT_PORT: AtomicU64 = AtomicU64::new(100_000);
+
+/// A builder to create a [`Simulation`].
+#[must_use]
+pub struct SimulationBuilder {
+    seed: u64,
+    nodes: usize,
+    links: Option<Vec<(usize, usize)>>,
+    coordinator_keys: usize,
+    coordinator_node: usize,
+    accounts: Vec<u64>,
+    genesis_timestamp: u32,
+    milestone_interval: Duration,
+    timeout: Duration,
+}
+
+impl SimulationBuilder {
+    /// Creates a new [`SimulationBuilder`] from the seed all keys, accounts and block parents are derived from.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            nodes: DEFAULT_NODES,
+            links: None,
+            coordinator_keys: DEFAULT_COORDINATOR_KEYS,
+            coordinator_node: DEFAULT_COORDINATOR_NODE,
+            accounts: Vec::new(),
+            genesis_timestamp: DEFAULT_GENESIS_TIMESTAMP,
+            milestone_interval: DEFAULT_MILESTONE_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the number of nodes of the simulation.
+    pub fn with_nodes(mut self, nodes: usize) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    /// Sets the links between the nodes of the simulation, which are fully meshed otherwise.
+    pub fn with_links(mut self, links: Vec<(usize, usize)>) -> Self {
+        self.links.replace(links);
+        self
+    }
+
+    /// Sets the number of keys signing the milestones of the simulation.
+    pub fn with_coordinator_keys(mut self, coordinator_keys: usize) -> Self {
+        self.coordinator_keys = coordinator_keys;
+        self
+    }
+
+    /// Sets the node the milestones of the simulation are issued on.
+    pub fn with_coordinator_node(mut self, coordinator_node: usize) -> Self {
+        self.coordinator_node = coordinator_node;
+        self
+    }
+
+    /// Adds an account funded with the given balance at genesis.
+    pub fn with_account(mut self, balance: u64) -> Self {
+        self.accounts.push(balance);
+        self
+    }
+
+    /// Sets the unix timestamp of the genesis, in seconds.
+    pub fn with_genesis_timestamp(mut self, genesis_timestamp: u32) -> Self {
+        self.genesis_timestamp = genesis_timestamp;
+        self
+    }
+
+    /// Sets the simulated time elapsing between two milestones.
+    pub fn with_milestone_interval(mut self, milestone_interval: Duration) -> Self {
+        self.milestone_interval = milestone_interval;
+        self
+    }
+
+    /// Sets how long to wait for the nodes to connect, solidify or confirm before failing.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Starts the nodes of the [`Simulation`] and connects them.
+    pub async fn finish(self) -> Result<Simulation, Error> {
+        if self.coordinator_node >= self.nodes {
+            return Err(Error::UnknownNode(self.coordinator_node));
+        }
+
+        let links = match self.links {
+            Some(links) => links
+                .into_iter()
+                .filter(|(a, b)| a != b)
+                .map(|(a, b)| (a.min(b), a.max(b)))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            None => (0..self.nodes)
+                .flat_map(|a| (a + 1..self.nodes).map(move |b| (a, b)))
+                .collect(),
+        };
+
+        if let Some(&(a, b)) = links.iter().find(|(a, b)| *a >= self.nodes || *b >= self.nodes) {
+            return Err(Error::UnknownNode(a.max(b)));
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|_| ed25519::SecretKey::from_bytes(rng.gen()))
+            .collect::<Vec<_>>();
+        let funds = accounts
+            .iter()
+            .map(account_address)
+            .zip(self.accounts.iter().copied())
+            // Accounts starting empty own no output.
+            .filter(|(_, balance)| *balance > 0)
+            .collect::<Vec<_>>();
+        let defaults = ProtocolParameters::default();
+        let protocol_parameters = ProtocolParameters::new(
+            defaults.protocol_version(),
+            NETWORK_NAME.to_owned(),
+            BECH32_HRP.to_owned(),
+            0,
+            defaults.below_max_depth(),
+            defaults.rent_structure().clone(),
+            self.accounts.iter().sum(),
+        )?;
+        let genesis = Genesis::new(protocol_parameters.clone(), self.genesis_timestamp, funds)?;
+        let coordinator = Coordinator::new(&mut rng, self.coordinator_keys, protocol_parameters.clone());
+        let protocol_config = ProtocolConfig::build()
+            .minimum_pow_score(0.0)
+            .coo_public_key_count(coordinator.public_key_count())
+            .coo_public_key_ranges(coordinator.public_key_ranges())
+            .finish();
+
+        let identities = (0..self.nodes)
+            .map(|index| {
+                let mut secret: [u8; 32] = rng.gen();
+                // Panic: any 32 bytes are a valid ed25519 secret key.
+                let keypair = Keypair::from(SecretKey::try_from_bytes(&mut secret).unwrap());
+                let peer_id = PeerId::from_public_key(&PublicKey::from(keypair.public()));
+                let address = Multiaddr::empty().with(Protocol::Memory(NEXT_PORT.fetch_add(1, Ordering::Relaxed)));
+
+                (format!("node-{}", index), keypair, peer_id, address)
+            })
+            .collect::<Vec<_>>();
+        let mut nodes = Vec::with_capacity(self.nodes);
+
+        for (index, (alias, keypair, _, address)) in identities.iter().enumerate() {
+            let mut network = NetworkConfig::build_in_memory()
+                .with_bind_multiaddr(address.clone())
+                .finish();
+
+            // Linked nodes know each other from the start, the latest to start connecting to the others.
+            for &(a, b) in &links {
+                let peer = if index == a {
+                    b
+                } else if index == b {
+                    a
+                } else {
+                    continue;
+                };
+                let (peer_alias, _, peer_id, peer_address) = &identities[peer];
+
+                // Panic: links are deduplicated so a peer can't be added twice.
+                network
+                    .add_static_peer(*peer_id, peer_address.clone(), Some(peer_alias.clone()))
+                    .unwrap();
+            }
+
+            let config = SimNodeConfig::new(
+                alias.clone(),
+                keypair.clone(),
+                genesis.clone(),
+                network,
+                protocol_config.clone(),
+                TangleConfig::build().finish(),
+            );
+
+            nodes.push(SimNodeBuilder::new(config)?.finish().await?);
+        }
+
+        let simulation = Simulation {
+            tips: vec![BTreeSet::new(); nodes.len()],
+            groups: vec![0; nodes.len()],
+            nodes,
+            links,
+            coordinator,
+            coordinator_node: self.coordinator_node,
+            accounts,
+            genesis,
+            clock: Clock::new(self.genesis_timestamp),
+            rng,
+            blocks: HashMap::new(),
+            issued: 0,
+            milestone_interval: self.milestone_interval,
+            timeout: self.timeout,
+        };
+
+        simulation.wait_connected().await?;
+
+        Ok(simulation)
+    }
+}
+
+/// A network of in-process nodes sharing a genesis, driven by a scenario.
+///
+/// Keys, accounts, block parents and milestone timestamps all derive from the seed and the simulated [`Clock`], so
+/// that a scenario builds the same blocks and ledger states on every run. Message delivery between the nodes is still
+/// subject to the scheduling of the runtime, which is why every step waits for the nodes to catch up before going on.
+pub struct Simulation {
+    nodes: Vec<SimNode>,
+    links: Vec<(usize, usize)>,
+    // The partition each node belongs to, all nodes are in partition 0 when the network is whole.
+    groups: Vec<usize>,
+    // The tips each node is known to have, as blocks issued through the simulation.
+    tips: Vec<BTreeSet<BlockId>>,
+    coordinator: Coordinator,
+    coordinator_node: usize,
+    accounts: Vec<ed25519::SecretKey>,
+    genesis: Genesis,
+    clock: Clock,
+    rng: StdRng,
+    blocks: HashMap<BlockId, Block>,
+    issued: u64,
+    milestone_interval: Duration,
+    timeout: Duration,
+}
+
+impl Simulation {
+    /// Creates a [`SimulationBuilder`] from the given seed.
+    pub fn build(seed: u64) -> SimulationBuilder {
+        SimulationBuilder::new(seed)
+    }
+
+    /// Returns the nodes of the [`Simulation`].
+    pub fn nodes(&self) -> &[SimNode] {
+        &self.nodes
+    }
+
+    /// Returns a node of the [`Simulation`].
+    pub fn node(&self, node: usize) -> Result<&SimNode, Error> {
+        self.nodes.get(node).ok_or(Error::UnknownNode(node))
+    }
+
+    /// Returns the genesis of the [`Simulation`].
+    pub fn genesis(&self) -> &Genesis {
+        &self.genesis
+    }
+
+    /// Returns the simulated clock of the [`Simulation`].
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Returns the random number generator of the [`Simulation`], to derive scenario decisions from the seed.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Returns the coordinator of the [`Simulation`].
+    pub fn coordinator(&self) -> &Coordinator {
+        &self.coordinator
+    }
+
+    /// Returns the address of an account.
+    pub fn account_address(&self, account: usize) -> Result<Address, Error> {
+        self.accounts
+            .get(account)
+            .map(account_address)
+            .ok_or(Error::UnknownAccount(account))
+    }
+
+    /// Waits until all the linked nodes of a partition are connected.
+    pub async fn wait_connected(&self) -> Result<(), Error> {
+        for &(a, b) in &self.links {
+            if self.groups[a] != self.groups[b] {
+                continue;
+            }
+
+            let (peer_a, peer_b) = (self.nodes[a].peer_id(), self.nodes[b].peer_id());
+
+            if !self
+                .poll(|| async { self.nodes[a].is_connected(peer_b) && self.nodes[b].is_connected(peer_a) })
+                .await
+            {
+                return Err(Error::NotConnected(a, b));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splits the network into the given partitions, the nodes not listed forming one more partition.
+    ///
+    /// Links crossing partitions are cut by having both ends ban each other.
+    pub async fn partition(&mut self, partitions: &[&[usize]]) -> Result<(), Error> {
+        let mut groups = vec![0; self.nodes.len()];
+
+        for (group, nodes) in partitions.iter().enumerate() {
+            for &node in *nodes {
+                *groups.get_mut(node).ok_or(Error::UnknownNode(node))? = group + 1;
+            }
+        }
+
+        self.heal().await?;
+        self.groups = groups;
+
+        for &(a, b) in &self.links {
+            if self.groups[a] == self.groups[b] {
+                continue;
+            }
+
+            for (from, to) in [(a, b), (b, a)] {
+                let peer_id = *self.nodes[to].peer_id();
+
+                self.nodes[from].command(Command::BanPeer {
+                    peer_id,
+                    expires_at: None,
+                    reason: Some("partition".to_string()),
+                });
+                self.nodes[from].command(Command::DisconnectPeer { peer_id });
+            }
+
+            let (peer_a, peer_b) = (self.nodes[a].peer_id(), self.nodes[b].peer_id());
+
+            if !self
+                .poll(|| async { !self.nodes[a].is_connected(peer_b) && !self.nodes[b].is_connected(peer_a) })
+                .await
+            {
+                return Err(Error::StillConnected(a, b));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rejoins all the partitions of the network.
+    ///
+    /// The tips of all partitions are merged, so that the next milestones reference what was issued on every side.
+    pub async fn heal(&mut self) -> Result<(), Error> {
+        for &(a, b) in &self.links {
+            if self.groups[a] == self.groups[b] {
+                continue;
+            }
+
+            self.nodes[a].command(Command::UnbanPeer {
+                peer_id: *self.nodes[b].peer_id(),
+            });
+            self.nodes[b].command(Command::UnbanPeer {
+                peer_id: *self.nodes[a].peer_id(),
+            });
+            self.nodes[a].command(Command::DialPeer {
+                peer_id: *self.nodes[b].peer_id(),
+            });
+        }
+
+        let tips = self.tips.iter().flatten().copied().collect::<BTreeSet<_>>();
+
+        self.groups.iter_mut().for_each(|group| *group = 0);
+        self.tips.iter_mut().for_each(|node_tips| node_tips.clone_from(&tips));
+
+        self.wait_connected().await
+    }
+
+    /// Submits a block to a node, the block becoming a tip of the partition of the node.
+    pub async fn submit_block(&mut self, node: usize, block: Block) -> Result<BlockId, Error> {
+        let block_id = self.node(node)?.submit_block(&block).await?;
+        let group = self.groups[node];
+
+        for (tips, _) in self.tips.iter_mut().zip(&self.groups).filter(|(_, g)| **g == group) {
+            for parent in block.parents().iter() {
+                tips.remove(parent);
+            }
+            tips.insert(block_id);
+        }
+
+        self.blocks.insert(block_id, block);
+
+        Ok(block_id)
+    }
+
+    /// Issues a block with the given payload on a node, on top of random tips of the partition of the node.
+    pub async fn issue_block(&mut self, node: usize, payload: Option<Payload>) -> Result<BlockId, Error> {
+        let mut tips = self.node_tips(node)?;
+
+        tips.shuffle(&mut self.rng);
+        tips.truncate(MAX_SPAM_PARENTS);
+
+        if tips.is_empty() {
+            tips.push(BlockId::null());
+        }
+
+        let mut builder = BlockBuilder::<u64>::new(Parents::new(tips)?)
+            .with_protocol_version(self.genesis.protocol_parameters().protocol_version());
+
+        if let Some(payload) = payload {
+            builder = builder.with_payload(payload);
+        }
+
+        // The simulated network requires no proof of work.
+        let block = builder.with_nonce_provider(0).finish(0)?;
+
+        self.submit_block(node, block).await
+    }
+
+    /// Issues a number of tagged data blocks on a node.
+    pub async fn spam(&mut self, node: usize, count: usize) -> Result<Vec<BlockId>, Error> {
+        let mut block_ids = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            // Makes blocks with the same parents differ.
+            self.issued += 1;
+
+            let payload = TaggedDataPayload::new(SPAM_TAG.to_vec(), self.issued.to_le_bytes().to_vec())?;
+
+            block_ids.push(self.issue_block(node, Some(Payload::from(payload))).await?);
+        }
+
+        Ok(block_ids)
+    }
+
+    /// Issues a transaction on a node, transferring funds between two accounts.
+    ///
+    /// The inputs are picked from the ledger state the node confirmed, regardless of the transactions still pending,
+    /// which is how double spends are made.
+    pub async fn transfer(&mut self, node: usize, from: usize, to: usize, amount: u64) -> Result<BlockId, Error> {
+        let key = self.accounts.get(from).ok_or(Error::UnknownAccount(from))?;
+        let from_address = account_address(key);
+        let to_address = self.account_address(to)?;
+        let protocol_parameters = self.genesis.protocol_parameters();
+
+        let mut inputs = Vec::new();
+        let mut consumed = Vec::new();
+        let mut available = 0;
+
+        for (output_id, output) in self.unspent_outputs(node, &from_address)? {
+            if available >= amount {
+                break;
+            }
+
+            available += output.inner().amount();
+            inputs.push(Input::Utxo(UtxoInput::from(output_id)));
+            consumed.push(output.inner().clone());
+        }
+
+        if available < amount {
+            return Err(Error::InsufficientFunds(available, amount));
+        }
+
+        let mut outputs = vec![basic_output(to_address, amount, protocol_parameters)?];
+
+        if available > amount {
+            outputs.push(basic_output(from_address, available - amount, protocol_parameters)?);
+        }
+
+        let essence = TransactionEssence::Regular(
+            RegularTransactionEssence::builder(InputsCommitment::new(consumed.iter()))
+                .with_inputs(inputs)
+                .with_outputs(outputs)
+                .finish(protocol_parameters)?,
+        );
+        let signature = Ed25519Signature::new(key.public_key().to_bytes(), key.sign(&essence.hash()).to_bytes());
+        let mut unlocks = vec![Unlock::Signature(SignatureUnlock::new(Signature::Ed25519(signature)))];
+
+        // All inputs belong to the same address, the first signature unlocks them all.
+        for _ in 1..consumed.len() {
+            unlocks.push(Unlock::Reference(ReferenceUnlock::new(0)?));
+        }
+
+        let payload = TransactionPayload::new(essence, Unlocks::new(unlocks)?)?;
+
+        self.issue_block(node, Some(Payload::from(payload))).await
+    }
+
+    /// Issues the next milestone on the coordinator node, referencing tips of its partition.
+    ///
+    /// The coordinator node first has to confirm the previous milestone and then solidify the referenced tips.
+    pub async fn issue_milestone(&mut self) -> Result<(MilestoneIndex, BlockId), Error> {
+        let node = self.coordinator_node;
+        let previous_index = self.coordinator.latest_milestone_index();
+        let previous_block_id = self.coordinator.latest_milestone_block_id();
+
+        self.wait_for_ledger_index(node, previous_index).await?;
+
+        let mut tips = self.node_tips(node)?;
+
+        tips.retain(|tip| Some(*tip) != previous_block_id);
+        tips.shuffle(&mut self.rng);
+        tips.truncate(MAX_MILESTONE_TIPS);
+        tips.sort_unstable();
+
+        // Blocks issued in another partition never reached the coordinator node, hand them over.
+        for tip in &tips {
+            self.attach(node, *tip).await;
+        }
+
+        for tip in &tips {
+            if !self.poll(|| self.nodes[node].is_solid(tip)).await {
+                return Err(Error::BlockNotSolid(*tip, node));
+            }
+        }
+
+        self.clock.advance(self.milestone_interval);
+
+        let (_, block) = self
+            .coordinator
+            .issue(
+                &self.nodes[node].tangle(),
+                &self.nodes[node].storage(),
+                tips,
+                self.clock.now(),
+            )
+            .await?;
+        let block_id = self.submit_block(node, block).await?;
+
+        Ok((self.coordinator.latest_milestone_index(), block_id))
+    }
+
+    /// Waits until a node confirmed the milestone with the given index.
+    pub async fn wait_for_ledger_index(&self, node: usize, index: MilestoneIndex) -> Result<(), Error> {
+        let sim_node = self.node(node)?;
+
+        if self
+            .poll(|| async { matches!(sim_node.ledger_index(), Ok(ledger_index) if ledger_index >= index) })
+            .await
+        {
+            Ok(())
+        } else {
+            Err(Error::MilestoneNotConfirmed(index, node, sim_node.ledger_index()?))
+        }
+    }
+
+    /// Waits until all nodes confirmed the milestone with the given index.
+    pub async fn wait_for_ledger_index_everywhere(&self, index: MilestoneIndex) -> Result<(), Error> {
+        for node in 0..self.nodes.len() {
+            self.wait_for_ledger_index(node, index).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the latest milestone confirmed by a node.
+    pub fn ledger_index(&self, node: usize) -> Result<MilestoneIndex, Error> {
+        self.node(node)?.ledger_index()
+    }
+
+    /// Returns the balance of an account in the ledger state of a node.
+    pub fn balance(&self, node: usize, account: usize) -> Result<u64, Error> {
+        Ok(self
+            .unspent_outputs(node, &self.account_address(account)?)?
+            .iter()
+            .map(|(_, output)| output.inner().amount())
+            .sum())
+    }
+
+    /// Returns the unspent outputs owned by an address in the ledger state of a node, sorted by output id.
+    pub fn unspent_outputs(&self, node: usize, address: &Address) -> Result<Vec<(OutputId, CreatedOutput)>, Error> {
+        Ok(self
+            .node(node)?
+            .unspent_outputs()?
+            .into_iter()
+            .filter(|(_, output)| matches!(output.inner(), Output::Basic(basic) if basic.address() == address))
+            .collect())
+    }
+
+    /// Returns the reason a block was excluded from the ledger of a node, if the node knows the block.
+    pub fn conflict(&self, node: usize, block_id: &BlockId) -> Result<Option<ConflictReason>, Error> {
+        Ok(self.node(node)?.conflict(block_id))
+    }
+
+    /// Stops all the nodes of the [`Simulation`].
+    pub async fn shutdown(self) -> Result<(), Error> {
+        for node in self.nodes {
+            node.stop().await?;
+        }
+
+        Ok(())
+    }
+
+    fn node_tips(&self, node: usize) -> Result<Vec<BlockId>, Error> {
+        Ok(self
+            .tips
+            .get(node)
+            .ok_or(Error::UnknownNode(node))?
+            .iter()
+            .copied()
+            .collect())
+    }
+
+    // Submits a block and the ancestors issued through the simulation a node doesn't know, parents first.
+    async fn attach(&self, node: usize, block_id: BlockId) {
+        let tangle = self.nodes[node].tangle();
+        let mut visited = HashSet::new();
+        let mut missing = Vec::new();
+        let mut stack = vec![(block_id, false)];
+
+        while let Some((block_id, parents_visited)) = stack.pop() {
+            if parents_visited {
+                missing.push(block_id);
+                continue;
+            }
+
+            if tangle.contains(&block_id) || !visited.insert(block_id) {
+                continue;
+            }
+
+            if let Some(block) = self.blocks.get(&block_id) {
+                stack.push((block_id, true));
+                stack.extend(block.parents().iter().map(|parent| (*parent, false)));
+            }
+        }
+
+        for block_id in missing {
+            // The block may be on its way to the node by gossip already, its solidity is awaited anyway.
+            let _ = self.nodes[node].submit_block(&self.blocks[&block_id]).await;
+        }
+    }
+
+    // Polls a condition until it holds or the timeout elapses.
+    async fn poll<'a, F, Fut>(&self, condition: F) -> bool
+    where
+        F: Fn() -> Fut + 'a,
+        Fut: Future<Output = bool> + 'a,
+    {
+        let deadline = tokio::time::Instant::now() + self.timeout;
+
+        loop {
+            if condition().await {
+                return true;
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+fn account_address(key: &ed25519::SecretKey) -> Address {
+    Address::Ed25519(Ed25519Address::new(
+        Blake2b256::digest(key.public_key().to_bytes()).into(),
+    ))
+}
+
+fn basic_output(address: Address, amount: u64, protocol_parameters: &ProtocolParameters) -> Result<Output, Error> {
+    Ok(BasicOutputBuilder::new_with_amount(amount)?
+        .add_unlock_condition(AddressUnlockCondition::new(address).into())
+        .finish_output(protocol_parameters.token_supply())?)
+}
@@ -0,0 +1,120 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{payload::milestone::MilestoneIndex, semantic::ConflictReason};
+use bee_simulator::Simulation;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn milestones_confirmed_everywhere() {
+    let mut simulation = Simulation::build(1).with_account(1_000_000).finish().await.unwrap();
+
+    for _ in 0..3 {
+        simulation.spam(1, 5).await.unwrap();
+        simulation.spam(2, 5).await.unwrap();
+
+        let (index, _) = simulation.issue_milestone().await.unwrap();
+
+        simulation.wait_for_ledger_index_everywhere(index).await.unwrap();
+    }
+
+    for node in 0..simulation.nodes().len() {
+        assert_eq!(simulation.ledger_index(node).unwrap(), MilestoneIndex(3));
+    }
+
+    simulation.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn transfer_confirmed_everywhere() {
+    let mut simulation = Simulation::build(2)
+        .with_account(1_000_000)
+        .with_account(1_000_000)
+        .finish()
+        .await
+        .unwrap();
+
+    simulation.transfer(1, 0, 1, 400_000).await.unwrap();
+
+    let (index, _) = simulation.issue_milestone().await.unwrap();
+
+    simulation.wait_for_ledger_index_everywhere(index).await.unwrap();
+
+    for node in 0..simulation.nodes().len() {
+        assert_eq!(simulation.balance(node, 0).unwrap(), 600_000);
+        assert_eq!(simulation.balance(node, 1).unwrap(), 1_400_000);
+    }
+
+    simulation.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn double_spend_across_partitions() {
+    let mut simulation = Simulation::build(3)
+        .with_nodes(4)
+        .with_account(1_000_000)
+        .with_account(0)
+        .with_account(0)
+        .finish()
+        .await
+        .unwrap();
+
+    simulation.partition(&[&[0, 1], &[2, 3]]).await.unwrap();
+
+    let spend = simulation.transfer(1, 0, 1, 1_000_000).await.unwrap();
+    let double_spend = simulation.transfer(3, 0, 2, 1_000_000).await.unwrap();
+
+    let (index, _) = simulation.issue_milestone().await.unwrap();
+
+    simulation.wait_for_ledger_index(1, index).await.unwrap();
+    simulation.heal().await.unwrap();
+
+    let (index, _) = simulation.issue_milestone().await.unwrap();
+
+    simulation.wait_for_ledger_index_everywhere(index).await.unwrap();
+
+    let unspent = simulation.nodes()[0].unspent_outputs().unwrap();
+
+    for node in 0..simulation.nodes().len() {
+        assert_eq!(simulation.balance(node, 0).unwrap(), 0);
+        assert_eq!(simulation.balance(node, 1).unwrap(), 1_000_000);
+        assert_eq!(simulation.balance(node, 2).unwrap(), 0);
+        assert_eq!(simulation.conflict(node, &spend).unwrap(), Some(ConflictReason::None));
+        assert_eq!(
+            simulation.conflict(node, &double_spend).unwrap(),
+            Some(ConflictReason::InputUtxoAlreadySpent)
+        );
+        assert_eq!(simulation.nodes()[node].unspent_outputs().unwrap(), unspent);
+    }
+
+    simulation.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn same_seed_same_ledger() {
+    let mut milestones = Vec::new();
+    let mut ledgers = Vec::new();
+
+    for _ in 0..2 {
+        let mut simulation = Simulation::build(4)
+            .with_account(1_000_000)
+            .with_account(1_000_000)
+            .finish()
+            .await
+            .unwrap();
+
+        simulation.spam(2, 5).await.unwrap();
+        simulation.transfer(1, 1, 0, 250_000).await.unwrap();
+
+        let (index, block_id) = simulation.issue_milestone().await.unwrap();
+
+        simulation.wait_for_ledger_index_everywhere(index).await.unwrap();
+
+        milestones.push(block_id);
+        ledgers.push(simulation.nodes()[2].unspent_outputs().unwrap());
+
+        simulation.shutdown().await.unwrap();
+    }
+
+    assert_eq!(milestones[0], milestones[1]);
+    assert_eq!(ledgers[0], ledgers[1]);
+}
@@ -0,0 +1,29 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batch access operations.
+
+use bee_storage::{
+    access::{Batch, BatchBuilder},
+    backend::StorageBackend,
+};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<B: BatchBuilder> BatchBuilder for Storage<B> {
+    type Batch = B::Batch;
+
+    fn batch_commit(&self, batch: Self::Batch, durability: bool) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument_batch(|inner| inner.batch_commit(batch, durability))
+    }
+}
+
+impl<K, V, B: Batch<K, V>> Batch<K, V> for Storage<B> {
+    fn batch_insert(&self, batch: &mut Self::Batch, k: &K, v: &V) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::BatchInsert, |inner| inner.batch_insert(batch, k, v))
+    }
+
+    fn batch_delete(&self, batch: &mut Self::Batch, k: &K) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::BatchDelete, |inner| inner.batch_delete(batch, k))
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Delete access operations.
+
+use bee_storage::{access::Delete, backend::StorageBackend};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Delete<K, V>> Delete<K, V> for Storage<B> {
+    fn delete(&self, k: &K) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Delete, |inner| inner.delete(k))
+    }
+}
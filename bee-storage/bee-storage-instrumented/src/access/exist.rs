@@ -0,0 +1,14 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exist access operations.
+
+use bee_storage::{access::Exist, backend::StorageBackend};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Exist<K, V>> Exist<K, V> for Storage<B> {
+    fn exist(&self, k: &K) -> Result<bool, <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Exist, |inner| inner.exist(k))
+    }
+}
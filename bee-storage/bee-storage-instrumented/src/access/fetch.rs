@@ -0,0 +1,14 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fetch access operations.
+
+use bee_storage::{access::Fetch, backend::StorageBackend};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Fetch<K, V>> Fetch<K, V> for Storage<B> {
+    fn fetch(&self, k: &K) -> Result<Option<V>, <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Fetch, |inner| inner.fetch(k))
+    }
+}
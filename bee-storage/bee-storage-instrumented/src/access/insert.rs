@@ -0,0 +1,23 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Insert access operations.
+
+use bee_storage::{
+    access::{Insert, InsertStrict},
+    backend::StorageBackend,
+};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Insert<K, V>> Insert<K, V> for Storage<B> {
+    fn insert(&self, k: &K, v: &V) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Insert, |inner| inner.insert(k, v))
+    }
+}
+
+impl<K, V, B: InsertStrict<K, V>> InsertStrict<K, V> for Storage<B> {
+    fn insert_strict(&self, k: &K, v: &V) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::InsertStrict, |inner| inner.insert_strict(k, v))
+    }
+}
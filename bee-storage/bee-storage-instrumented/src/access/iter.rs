@@ -0,0 +1,81 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Iter access operations.
+
+use std::time::{Duration, Instant};
+
+use bee_storage::{access::AsIterator, backend::StorageBackend};
+
+use crate::{
+    metrics::{Operation, StorageMetrics, Table},
+    storage::Storage,
+};
+
+/// An iterator recording the number of yielded items and the time spent yielding them.
+///
+/// The call is recorded once the iterator is dropped.
+pub struct StorageIterator<'a, I> {
+    inner: I,
+    metrics: Option<&'a StorageMetrics>,
+    table: Table,
+    elapsed: Duration,
+    items: u64,
+    failed: bool,
+}
+
+impl<'a, K, V, E, I: Iterator<Item = Result<(K, V), E>>> Iterator for StorageIterator<'a, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.metrics.is_none() {
+            return self.inner.next();
+        }
+
+        let start = Instant::now();
+        let item = self.inner.next();
+        self.elapsed += start.elapsed();
+
+        match &item {
+            Some(Ok(_)) => self.items += 1,
+            Some(Err(_)) => self.failed = true,
+            None => {}
+        }
+
+        item
+    }
+}
+
+impl<'a, I> Drop for StorageIterator<'a, I> {
+    fn drop(&mut self) {
+        if let Some(metrics) = self.metrics {
+            metrics.record(self.table, Operation::Iter, self.elapsed, self.items, self.failed);
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a, B: AsIterator<'a, K, V>> AsIterator<'a, K, V> for Storage<B> {
+    type AsIter = StorageIterator<'a, B::AsIter>;
+
+    fn iter(&'a self) -> Result<Self::AsIter, <Self as StorageBackend>::Error> {
+        let start = self.start_timer();
+        let table = Table::of::<K, V>();
+
+        match self.inner.iter() {
+            Ok(inner) => Ok(StorageIterator {
+                inner,
+                metrics: self.config.enabled.then_some(&self.metrics),
+                table,
+                elapsed: start.map(|start| start.elapsed()).unwrap_or_default(),
+                items: 0,
+                failed: false,
+            }),
+            Err(e) => {
+                if let Some(start) = start {
+                    self.metrics.record(table, Operation::Iter, start.elapsed(), 0, true);
+                }
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Multi-fetch access operations.
+
+use bee_storage::{access::MultiFetch, backend::StorageBackend};
+
+use crate::{
+    metrics::{Operation, Table},
+    storage::Storage,
+};
+
+impl<'a, K, V, B: MultiFetch<'a, K, V>> MultiFetch<'a, K, V> for Storage<B> {
+    type Iter = B::Iter;
+
+    fn multi_fetch(&'a self, keys: &'a [K]) -> Result<Self::Iter, <Self as StorageBackend>::Error> {
+        let start = self.start_timer();
+        let res = self.inner.multi_fetch(keys);
+
+        if let Some(start) = start {
+            self.metrics.record(
+                Table::of::<K, V>(),
+                Operation::MultiFetch,
+                start.elapsed(),
+                keys.len() as u64,
+                res.is_err(),
+            );
+        }
+
+        res
+    }
+}
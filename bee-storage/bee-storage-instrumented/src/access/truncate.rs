@@ -0,0 +1,14 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Truncate access operations.
+
+use bee_storage::{access::Truncate, backend::StorageBackend};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Truncate<K, V>> Truncate<K, V> for Storage<B> {
+    fn truncate(&self) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Truncate, |inner| inner.truncate())
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Update access operations.
+
+use bee_storage::{access::Update, backend::StorageBackend};
+
+use crate::{metrics::Operation, storage::Storage};
+
+impl<K, V, B: Update<K, V>> Update<K, V> for Storage<B> {
+    fn update(&self, k: &K, f: impl FnMut(&mut V)) -> Result<(), <Self as StorageBackend>::Error> {
+        self.instrument::<K, V, _>(Operation::Update, |inner| inner.update(k, f))
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configuration of the instrumented storage backend.
+
+use std::time::Duration;
+
+use bee_storage::backend::StorageBackend;
+use serde::Deserialize;
+
+const DEFAULT_ENABLED: bool = false;
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+const DEFAULT_BACKEND_STATISTICS: bool = false;
+
+/// Builder for an [`InstrumentationConfig`].
+#[derive(Default, Debug, Deserialize, Eq, PartialEq)]
+#[must_use]
+pub struct InstrumentationConfigBuilder {
+    enabled: Option<bool>,
+    #[serde(alias = "intervalSecs")]
+    interval_secs: Option<u64>,
+    #[serde(alias = "backendStatistics")]
+    backend_statistics: Option<bool>,
+}
+
+impl InstrumentationConfigBuilder {
+    /// Creates a new [`InstrumentationConfigBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables the instrumentation.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Sets the interval, in seconds, at which metrics are published. It is at least one second.
+    pub fn with_interval_secs(mut self, interval_secs: u64) -> Self {
+        self.interval_secs = Some(interval_secs);
+        self
+    }
+
+    /// Enables or disables the export of the internal statistics of the wrapped backend.
+    pub fn with_backend_statistics(mut self, backend_statistics: bool) -> Self {
+        self.backend_statistics = Some(backend_statistics);
+        self
+    }
+
+    /// Finishes the builder into an [`InstrumentationConfig`].
+    pub fn finish(self) -> InstrumentationConfig {
+        InstrumentationConfig {
+            enabled: self.enabled.unwrap_or(DEFAULT_ENABLED),
+            // A zero period would make the metrics worker panic.
+            interval: Duration::from_secs(self.interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS).max(1)),
+            backend_statistics: self.backend_statistics.unwrap_or(DEFAULT_BACKEND_STATISTICS),
+        }
+    }
+}
+
+/// Configuration of the instrumentation.
+#[derive(Clone, Debug)]
+pub struct InstrumentationConfig {
+    pub(crate) enabled: bool,
+    pub(crate) interval: Duration,
+    pub(crate) backend_statistics: bool,
+}
+
+impl InstrumentationConfig {
+    /// Returns whether the instrumentation is enabled.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the interval at which metrics are published.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns whether the internal statistics of the wrapped backend are exported.
+    pub fn backend_statistics(&self) -> bool {
+        self.backend_statistics
+    }
+}
+
+/// Builder for a [`StorageConfig`].
+///
+/// The configuration of the wrapped backend is flattened so that existing configuration files keep working.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+#[must_use]
+pub struct StorageConfigBuilder<B: StorageBackend> {
+    #[serde(flatten)]
+    backend: B::ConfigBuilder,
+    instrumentation: Option<InstrumentationConfigBuilder>,
+}
+
+impl<B: StorageBackend> StorageConfigBuilder<B> {
+    /// Creates a new [`StorageConfigBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the configuration builder of the wrapped backend.
+    pub fn with_backend(mut self, backend: B::ConfigBuilder) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Sets the instrumentation configuration builder.
+    pub fn with_instrumentation(mut self, instrumentation: InstrumentationConfigBuilder) -> Self {
+        self.instrumentation = Some(instrumentation);
+        self
+    }
+
+    /// Finishes the builder into a [`StorageConfig`].
+    pub fn finish(self) -> StorageConfig<B> {
+        StorageConfig::from(self)
+    }
+}
+
+// This cannot be derived because `B` does not implement `Default`.
+impl<B: StorageBackend> Default for StorageConfigBuilder<B> {
+    fn default() -> Self {
+        Self {
+            backend: B::ConfigBuilder::default(),
+            instrumentation: None,
+        }
+    }
+}
+
+// This cannot be derived because `B` does not implement `Debug`.
+impl<B: StorageBackend> std::fmt::Debug for StorageConfigBuilder<B>
+where
+    B::ConfigBuilder: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StorageConfigBuilder")
+            .field("backend", &self.backend)
+            .field("instrumentation", &self.instrumentation)
+            .finish()
+    }
+}
+
+// This cannot be derived because `B` does not implement `PartialEq`.
+impl<B: StorageBackend> PartialEq for StorageConfigBuilder<B>
+where
+    B::ConfigBuilder: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.backend == other.backend && self.instrumentation == other.instrumentation
+    }
+}
+
+impl<B: StorageBackend> From<StorageConfigBuilder<B>> for StorageConfig<B> {
+    fn from(builder: StorageConfigBuilder<B>) -> Self {
+        Self {
+            backend: builder.backend.into(),
+            instrumentation: builder.instrumentation.unwrap_or_default().finish(),
+        }
+    }
+}
+
+/// Configuration of the instrumented storage backend.
+pub struct StorageConfig<B: StorageBackend> {
+    pub(crate) backend: B::Config,
+    pub(crate) instrumentation: InstrumentationConfig,
+}
+
+impl<B: StorageBackend> StorageConfig<B> {
+    /// Returns the configuration of the wrapped backend.
+    pub fn backend(&self) -> &B::Config {
+        &self.backend
+    }
+
+    /// Returns a mutable reference to the configuration of the wrapped backend.
+    pub fn backend_mut(&mut self) -> &mut B::Config {
+        &mut self.backend
+    }
+
+    /// Returns the instrumentation configuration.
+    pub fn instrumentation(&self) -> &InstrumentationConfig {
+        &self.instrumentation
+    }
+}
+
+// This cannot be derived because `B` does not implement `Clone`.
+impl<B: StorageBackend> Clone for StorageConfig<B> {
+    fn clone(&self) -> Self {
+        Self {
+            backend: self.backend.clone(),
+            instrumentation: self.instrumentation.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_is_at_least_one_second() {
+        assert_eq!(
+            InstrumentationConfigBuilder::new().finish().interval(),
+            Duration::from_secs(DEFAULT_INTERVAL_SECS)
+        );
+        assert_eq!(
+            InstrumentationConfigBuilder::new()
+                .with_interval_secs(5)
+                .finish()
+                .interval(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            InstrumentationConfigBuilder::new()
+                .with_interval_secs(0)
+                .finish()
+                .interval(),
+            Duration::from_secs(1)
+        );
+    }
+}
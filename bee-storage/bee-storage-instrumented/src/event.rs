@@ -0,0 +1,19 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Events related to the instrumented storage.
+
+use std::time::Duration;
+
+use crate::metrics::TableMetrics;
+
+/// An event that indicates that the storage metrics of the last interval were taken.
+#[derive(Clone, Debug)]
+pub struct StorageMetricsUpdated {
+    /// Length of the interval the metrics were accumulated over.
+    pub interval: Duration,
+    /// Per-table metrics, sorted by decreasing accumulated latency.
+    pub tables: Vec<TableMetrics>,
+    /// Internal statistics of the wrapped backend, if exported.
+    pub backend_statistics: Option<String>,
+}
@@ -0,0 +1,19 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Bee storage backend wrapping any other backend to record per-table latency and throughput metrics.
+//!
+//! Every access operation going through the wrapper is timed and counted against the table it targets, a table being
+//! identified by the key and value types of the operation. The collected metrics are periodically published on the
+//! event bus by the [`StorageMetricsWorker`](worker::StorageMetricsWorker).
+
+#![cfg_attr(doc_cfg, feature(doc_cfg))]
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+pub mod access;
+pub mod config;
+pub mod event;
+pub mod metrics;
+pub mod storage;
+pub mod worker;
@@ -0,0 +1,337 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-table metrics of the access operations.
+
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, PoisonError, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// The access operations that are being recorded.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operation {
+    /// `Fetch` operation.
+    Fetch,
+    /// `MultiFetch` operation.
+    MultiFetch,
+    /// `Exist` operation.
+    Exist,
+    /// `Insert` operation.
+    Insert,
+    /// `InsertStrict` operation.
+    InsertStrict,
+    /// `Update` operation.
+    Update,
+    /// `Delete` operation.
+    Delete,
+    /// `Truncate` operation.
+    Truncate,
+    /// `Batch::batch_insert` operation.
+    BatchInsert,
+    /// `Batch::batch_delete` operation.
+    BatchDelete,
+    /// `BatchBuilder::batch_commit` operation.
+    BatchCommit,
+    /// `AsIterator` operation, including the time spent yielding items.
+    Iter,
+}
+
+impl Operation {
+    const COUNT: usize = 12;
+
+    const ALL: [Operation; Self::COUNT] = [
+        Self::Fetch,
+        Self::MultiFetch,
+        Self::Exist,
+        Self::Insert,
+        Self::InsertStrict,
+        Self::Update,
+        Self::Delete,
+        Self::Truncate,
+        Self::BatchInsert,
+        Self::BatchDelete,
+        Self::BatchCommit,
+        Self::Iter,
+    ];
+
+    /// Returns the name of the operation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fetch => "fetch",
+            Self::MultiFetch => "multi_fetch",
+            Self::Exist => "exist",
+            Self::Insert => "insert",
+            Self::InsertStrict => "insert_strict",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Truncate => "truncate",
+            Self::BatchInsert => "batch_insert",
+            Self::BatchDelete => "batch_delete",
+            Self::BatchCommit => "batch_commit",
+            Self::Iter => "iter",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Identifies a table by the key and value types it stores.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Table {
+    key: &'static str,
+    value: &'static str,
+}
+
+impl Table {
+    /// Pseudo table under which batch commits are recorded, as a batch can span several tables.
+    pub const BATCH: Self = Self {
+        key: "batch",
+        value: "",
+    };
+
+    /// Returns the table storing `(K, V)` pairs.
+    pub fn of<K, V>() -> Self {
+        Self {
+            key: type_name::<K>(),
+            value: type_name::<V>(),
+        }
+    }
+
+    /// Returns the name of the table, e.g. `BlockId => BlockMetadata`.
+    pub fn name(&self) -> String {
+        if self.value.is_empty() {
+            self.key.to_string()
+        } else {
+            format!("{} => {}", short_type_name(self.key), short_type_name(self.value))
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+/// Strips the module paths from a type name, e.g. `alloc::vec::Vec<bee_block::BlockId>` becomes `Vec<BlockId>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment = String::new();
+
+    for c in name.chars() {
+        match c {
+            '<' | '>' | '(' | ')' | ',' | ' ' | '[' | ']' | ';' | '&' => {
+                short.push_str(segment.rsplit("::").next().unwrap_or_default());
+                segment.clear();
+                short.push(c);
+            }
+            c => segment.push(c),
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+
+    short
+}
+
+#[derive(Default)]
+struct OperationCounters {
+    count: AtomicU64,
+    errors: AtomicU64,
+    items: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl OperationCounters {
+    fn record(&self, elapsed: Duration, items: u64, failed: bool) {
+        let nanos = elapsed.as_nanos().try_into().unwrap_or(u64::MAX);
+
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.items.fetch_add(items, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        if failed {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn take(&self) -> OperationMetrics {
+        OperationMetrics {
+            count: self.count.swap(0, Ordering::Relaxed),
+            errors: self.errors.swap(0, Ordering::Relaxed),
+            items: self.items.swap(0, Ordering::Relaxed),
+            total_latency: Duration::from_nanos(self.total_nanos.swap(0, Ordering::Relaxed)),
+            max_latency: Duration::from_nanos(self.max_nanos.swap(0, Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TableCounters {
+    operations: [OperationCounters; Operation::COUNT],
+}
+
+/// Metrics of a single operation on a single table, accumulated since they were last taken.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperationMetrics {
+    /// Number of calls.
+    pub count: u64,
+    /// Number of calls that returned an error.
+    pub errors: u64,
+    /// Number of items read or written, e.g. rows yielded by an iterator or values fetched by a `MultiFetch`.
+    pub items: u64,
+    /// Accumulated latency of all calls.
+    pub total_latency: Duration,
+    /// Highest latency of a single call.
+    pub max_latency: Duration,
+}
+
+impl OperationMetrics {
+    /// Returns the average latency of a call.
+    pub fn average_latency(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total_latency.as_nanos() / self.count as u128) as u64)
+        }
+    }
+
+    /// Returns the number of calls per second over the given interval.
+    pub fn throughput(&self, interval: Duration) -> f64 {
+        if interval.is_zero() {
+            0.0
+        } else {
+            self.count as f64 / interval.as_secs_f64()
+        }
+    }
+}
+
+/// Metrics of all the operations on a single table, accumulated since they were last taken.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableMetrics {
+    /// The table.
+    pub table: Table,
+    /// The metrics of the operations that were called at least once.
+    pub operations: Vec<(Operation, OperationMetrics)>,
+}
+
+impl TableMetrics {
+    /// Returns the accumulated latency of all the operations on the table.
+    pub fn total_latency(&self) -> Duration {
+        self.operations.iter().map(|(_, metrics)| metrics.total_latency).sum()
+    }
+}
+
+/// Records the metrics of the access operations of a backend.
+#[derive(Default)]
+pub struct StorageMetrics {
+    tables: RwLock<HashMap<Table, Arc<TableCounters>>>,
+}
+
+impl StorageMetrics {
+    /// Creates a new [`StorageMetrics`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn counters(&self, table: Table) -> Arc<TableCounters> {
+        if let Some(counters) = self.tables.read().unwrap_or_else(PoisonError::into_inner).get(&table) {
+            return counters.clone();
+        }
+
+        self.tables
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(table)
+            .or_default()
+            .clone()
+    }
+
+    /// Records a call of `operation` on `table`.
+    pub fn record(&self, table: Table, operation: Operation, elapsed: Duration, items: u64, failed: bool) {
+        self.counters(table).operations[operation as usize].record(elapsed, items, failed);
+    }
+
+    /// Times `f` and records it as a call of `operation` on `table`.
+    pub fn time<T, E>(&self, table: Table, operation: Operation, f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let start = Instant::now();
+        let res = f();
+        self.record(table, operation, start.elapsed(), 0, res.is_err());
+        res
+    }
+
+    /// Takes the metrics accumulated since the last call and resets them.
+    /// Tables and operations without any call in that period are omitted.
+    pub fn take(&self) -> Vec<TableMetrics> {
+        let tables = self.tables.read().unwrap_or_else(PoisonError::into_inner);
+        let mut metrics = tables
+            .iter()
+            .filter_map(|(table, counters)| {
+                let operations = Operation::ALL
+                    .iter()
+                    .map(|operation| (*operation, counters.operations[*operation as usize].take()))
+                    .filter(|(_, metrics)| metrics.count > 0)
+                    .collect::<Vec<_>>();
+
+                (!operations.is_empty()).then_some(TableMetrics {
+                    table: *table,
+                    operations,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        metrics.sort_by_key(|table| std::cmp::Reverse(table.total_latency()));
+
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_type_names() {
+        assert_eq!(short_type_name("u8"), "u8");
+        assert_eq!(short_type_name("bee_block::block::BlockId"), "BlockId");
+        assert_eq!(short_type_name("alloc::vec::Vec<bee_block::BlockId>"), "Vec<BlockId>");
+        assert_eq!(
+            short_type_name("(bee_block::BlockId, bee_block::BlockId)"),
+            "(BlockId, BlockId)"
+        );
+    }
+
+    #[test]
+    fn take_resets() {
+        let metrics = StorageMetrics::new();
+        let table = Table::of::<u8, u32>();
+
+        metrics.record(table, Operation::Fetch, Duration::from_millis(2), 0, false);
+        metrics.record(table, Operation::Fetch, Duration::from_millis(4), 0, true);
+
+        let taken = metrics.take();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].table.name(), "u8 => u32");
+        assert_eq!(taken[0].operations.len(), 1);
+
+        let (operation, fetch) = &taken[0].operations[0];
+        assert_eq!(*operation, Operation::Fetch);
+        assert_eq!(fetch.count, 2);
+        assert_eq!(fetch.errors, 1);
+        assert_eq!(fetch.average_latency(), Duration::from_millis(3));
+        assert_eq!(fetch.max_latency, Duration::from_millis(4));
+
+        assert!(metrics.take().is_empty());
+    }
+}
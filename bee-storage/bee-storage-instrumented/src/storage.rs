@@ -0,0 +1,108 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The instrumented storage backend.
+
+use std::time::Instant;
+
+use bee_storage::{backend::StorageBackend, system::StorageHealth};
+
+use crate::{
+    config::{InstrumentationConfig, StorageConfig, StorageConfigBuilder},
+    metrics::{Operation, StorageMetrics, Table},
+};
+
+/// A storage backend wrapping another backend `B` and recording metrics of its access operations.
+pub struct Storage<B> {
+    pub(crate) inner: B,
+    pub(crate) config: InstrumentationConfig,
+    pub(crate) metrics: StorageMetrics,
+}
+
+impl<B: StorageBackend> Storage<B> {
+    /// Wraps an already started backend.
+    pub fn new(inner: B, config: InstrumentationConfig) -> Self {
+        Self {
+            inner,
+            config,
+            metrics: StorageMetrics::new(),
+        }
+    }
+
+    /// Returns the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the instrumentation configuration.
+    pub fn config(&self) -> &InstrumentationConfig {
+        &self.config
+    }
+
+    /// Returns the recorded metrics.
+    pub fn metrics(&self) -> &StorageMetrics {
+        &self.metrics
+    }
+
+    /// Runs `f` on the wrapped backend and, if enabled, records it as a call of `operation` on the `(K, V)` table.
+    pub(crate) fn instrument<K, V, T>(
+        &self,
+        operation: Operation,
+        f: impl FnOnce(&B) -> Result<T, B::Error>,
+    ) -> Result<T, B::Error> {
+        if self.config.enabled {
+            self.metrics.time(Table::of::<K, V>(), operation, || f(&self.inner))
+        } else {
+            f(&self.inner)
+        }
+    }
+
+    /// Same as [`Storage::instrument`] but for operations that are not bound to a single table.
+    pub(crate) fn instrument_batch<T>(&self, f: impl FnOnce(&B) -> Result<T, B::Error>) -> Result<T, B::Error> {
+        if self.config.enabled {
+            self.metrics
+                .time(Table::BATCH, Operation::BatchCommit, || f(&self.inner))
+        } else {
+            f(&self.inner)
+        }
+    }
+
+    /// Returns the current instant if the instrumentation is enabled.
+    pub(crate) fn start_timer(&self) -> Option<Instant> {
+        self.config.enabled.then(Instant::now)
+    }
+}
+
+impl<B: StorageBackend> StorageBackend for Storage<B> {
+    type ConfigBuilder = StorageConfigBuilder<B>;
+    type Config = StorageConfig<B>;
+    type Error = B::Error;
+
+    fn start(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self::new(B::start(config.backend)?, config.instrumentation))
+    }
+
+    fn start_unchecked(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self::new(B::start_unchecked(config.backend)?, config.instrumentation))
+    }
+
+    fn shutdown(self) -> Result<(), Self::Error> {
+        self.inner.shutdown()
+    }
+
+    fn size(&self) -> Result<Option<usize>, Self::Error> {
+        self.inner.size()
+    }
+
+    fn get_health(&self) -> Result<Option<StorageHealth>, Self::Error> {
+        self.inner.get_health()
+    }
+
+    fn set_health(&self, health: StorageHealth) -> Result<(), Self::Error> {
+        self.inner.set_health(health)
+    }
+
+    fn statistics(&self) -> Result<Option<String>, Self::Error> {
+        self.inner.statistics()
+    }
+}
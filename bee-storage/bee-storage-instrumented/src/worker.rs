@@ -0,0 +1,79 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A worker periodically publishing the storage metrics.
+
+use std::{convert::Infallible, time::Instant};
+
+use async_trait::async_trait;
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_storage::backend::StorageBackend;
+use futures::StreamExt;
+use log::{debug, info, warn};
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+
+use crate::{event::StorageMetricsUpdated, storage::Storage};
+
+/// A worker that takes the storage metrics at the configured interval and dispatches them as
+/// [`StorageMetricsUpdated`] events.
+#[derive(Default)]
+pub struct StorageMetricsWorker {}
+
+#[async_trait]
+impl<N: Node<Backend = Storage<B>>, B: StorageBackend> Worker<N> for StorageMetricsWorker {
+    type Config = ();
+    type Error = Infallible;
+
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        let storage = node.storage();
+        let bus = node.bus();
+
+        if !storage.config().enabled() {
+            return Ok(Self::default());
+        }
+
+        let period = storage.config().interval();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(period)));
+            let mut last = Instant::now();
+
+            while ticker.next().await.is_some() {
+                let tables = storage.metrics().take();
+                let interval = last.elapsed();
+                last = Instant::now();
+
+                let backend_statistics = if storage.config().backend_statistics() {
+                    storage.statistics().unwrap_or_else(|e| {
+                        warn!("Failed to export backend statistics: {:?}.", e);
+                        None
+                    })
+                } else {
+                    None
+                };
+
+                if let Some(table) = tables.first() {
+                    debug!(
+                        "Slowest table `{}`: {:?} spent over {:?}.",
+                        table.table,
+                        table.total_latency(),
+                        interval
+                    );
+                }
+
+                bus.dispatch(StorageMetricsUpdated {
+                    interval,
+                    tables,
+                    backend_statistics,
+                });
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_storage::backend::StorageBackend;
+use bee_storage_instrumented::{
+    config::{InstrumentationConfigBuilder, StorageConfigBuilder},
+    metrics::{Operation, Table},
+    storage::Storage,
+};
+use bee_storage_memory::storage::Storage as MemoryStorage;
+
+fn start(enabled: bool) -> Storage<MemoryStorage> {
+    Storage::start(
+        StorageConfigBuilder::new()
+            .with_instrumentation(InstrumentationConfigBuilder::new().with_enabled(enabled))
+            .finish(),
+    )
+    .unwrap()
+}
+
+macro_rules! impl_access_test {
+    ($name_instrumented:ident, $name:ident) => {
+        #[test]
+        fn $name_instrumented() {
+            bee_storage_test::$name(&start(true));
+            bee_storage_test::$name(&start(false));
+        }
+    };
+}
+
+impl_access_test!(block_id_to_block_access_instrumented, block_id_to_block_access);
+impl_access_test!(block_id_to_block_id_access_instrumented, block_id_to_block_id_access);
+impl_access_test!(block_id_to_metadata_access_instrumented, block_id_to_metadata_access);
+impl_access_test!(
+    output_id_to_created_output_access_instrumented,
+    output_id_to_created_output_access
+);
+impl_access_test!(ledger_index_access_instrumented, ledger_index_access);
+
+#[test]
+fn records_per_table() {
+    let storage = start(true);
+
+    bee_storage_test::block_id_to_block_access(&storage);
+
+    let tables = storage.metrics().take();
+    let blocks = tables
+        .iter()
+        .find(|table| table.table.name() == "BlockId => Block")
+        .unwrap();
+    let count = |operation| {
+        blocks
+            .operations
+            .iter()
+            .find(|(op, _)| *op == operation)
+            .map(|(_, metrics)| metrics.count)
+            .unwrap_or_default()
+    };
+
+    assert!(count(Operation::Fetch) > 0);
+    assert!(count(Operation::Insert) > 0);
+    assert!(count(Operation::Delete) > 0);
+    assert!(count(Operation::BatchInsert) > 0);
+    assert!(count(Operation::Iter) > 0);
+    assert!(tables.iter().any(|table| table.table == Table::BATCH));
+
+    assert!(storage.metrics().take().is_empty());
+}
+
+#[test]
+fn records_nothing_when_disabled() {
+    let storage = start(false);
+
+    bee_storage_test::block_id_to_block_access(&storage);
+
+    assert!(storage.metrics().take().is_empty());
+}
+
+#[test]
+fn flattened_config() {
+    let builder: StorageConfigBuilder<MemoryStorage> =
+        serde_json::from_str(r#"{"instrumentation":{"enabled":true,"intervalSecs":5}}"#).unwrap();
+    let config = builder.finish();
+
+    assert!(config.instrumentation().enabled());
+    assert_eq!(config.instrumentation().interval().as_secs(), 5);
+    assert!(!config.instrumentation().backend_statistics());
+}
@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -40,6 +40,7 @@ pub struct StorageBatch {
     snapshot_info: TableBatch<(), SnapshotInfo>,
     solid_entry_point_to_milestone_index: TableBatch<SolidEntryPoint, MilestoneIndex>,
     milestone_index_to_output_diff: TableBatch<MilestoneIndex, OutputDiff>,
+    milestone_index_to_protocol_parameters: TableBatch<MilestoneIndex, ParametersMilestoneOption>,
     milestone_index_to_unreferenced_block: TableBatch<(MilestoneIndex, UnreferencedBlock), ()>,
     milestone_index_to_receipt: TableBatch<(MilestoneIndex, Receipt), ()>,
     spent_to_treasury_output: TableBatch<(bool, TreasuryOutput), ()>,
@@ -74,6 +75,7 @@ impl BatchBuilder for Storage {
         apply_batch!(snapshot_info);
         apply_batch!(solid_entry_point_to_milestone_index);
         apply_batch!(milestone_index_to_output_diff);
+        apply_batch!(milestone_index_to_protocol_parameters);
         apply_batch!(milestone_index_to_unreferenced_block);
         apply_batch!(milestone_index_to_receipt);
         apply_batch!(spent_to_treasury_output);
@@ -118,6 +120,11 @@ impl_batch!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_batch!((), SnapshotInfo, snapshot_info);
 impl_batch!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_batch!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_batch!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_batch!(
     (MilestoneIndex, UnreferencedBlock),
     (),
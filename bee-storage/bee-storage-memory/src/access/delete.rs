@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -46,6 +46,11 @@ impl_delete!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_delete!((), SnapshotInfo, snapshot_info);
 impl_delete!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_delete!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_delete!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_delete!(
     (MilestoneIndex, UnreferencedBlock),
     (),
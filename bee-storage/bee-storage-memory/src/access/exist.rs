@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -44,6 +44,11 @@ impl_exist!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_exist!((), SnapshotInfo, snapshot_info);
 impl_exist!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_exist!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_exist!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_exist!(
     (MilestoneIndex, UnreferencedBlock),
     (),
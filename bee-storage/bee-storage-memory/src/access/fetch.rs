@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -43,6 +43,11 @@ impl_fetch!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_fetch!((), SnapshotInfo, snapshot_info);
 impl_fetch!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_fetch!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_fetch!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_fetch!(
     MilestoneIndex,
     Vec<UnreferencedBlock>,
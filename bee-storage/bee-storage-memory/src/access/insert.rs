@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -50,6 +50,11 @@ impl_insert!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_insert!((), SnapshotInfo, snapshot_info);
 impl_insert!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_insert!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_insert!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_insert!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -68,6 +68,11 @@ impl_iter!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_iter!((), SnapshotInfo, snapshot_info);
 impl_iter!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_iter!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_iter!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_iter!(
     (MilestoneIndex, UnreferencedBlock),
     (),
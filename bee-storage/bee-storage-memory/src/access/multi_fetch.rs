@@ -7,7 +7,7 @@ use std::{iter::Map, vec::IntoIter};
 
 use bee_block::{
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{ConsumedOutput, CreatedOutput, OutputDiff};
@@ -42,3 +42,8 @@ impl_multi_fetch!(MilestoneIndex, MilestoneMetadata, milestone_index_to_mileston
 impl_multi_fetch!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload);
 impl_multi_fetch!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_multi_fetch!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_multi_fetch!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -45,6 +45,11 @@ impl_truncate!(MilestoneId, MilestonePayload, milestone_id_to_milestone_payload)
 impl_truncate!((), SnapshotInfo, snapshot_info);
 impl_truncate!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_truncate!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
+impl_truncate!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    milestone_index_to_protocol_parameters
+);
 impl_truncate!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -4,7 +4,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -150,6 +150,17 @@ impl Delete<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Delete<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn delete(&self, index: &MilestoneIndex) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner.delete_cf(
+            self.cf_handle(CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?,
+            index.pack_to_vec(),
+        )?;
+
+        Ok(())
+    }
+}
+
 impl Delete<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn delete(
         &self,
@@ -4,7 +4,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -164,6 +164,18 @@ impl Exist<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Exist<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn exist(&self, index: &MilestoneIndex) -> Result<bool, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .get_pinned_cf(
+                self.cf_handle(CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?,
+                index.pack_to_vec(),
+            )?
+            .is_some())
+    }
+}
+
 impl Exist<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn exist(
         &self,
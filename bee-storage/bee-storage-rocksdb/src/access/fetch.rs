@@ -4,7 +4,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -181,6 +181,22 @@ impl Fetch<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Fetch<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn fetch(
+        &self,
+        index: &MilestoneIndex,
+    ) -> Result<Option<ParametersMilestoneOption>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .get_pinned_cf(
+                self.cf_handle(CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?,
+                index.pack_to_vec(),
+            )?
+            // Unpacking from storage is fine.
+            .map(|v| ParametersMilestoneOption::unpack_unverified(&*v).unwrap()))
+    }
+}
+
 impl Fetch<MilestoneIndex, Vec<UnreferencedBlock>> for Storage {
     fn fetch(&self, index: &MilestoneIndex) -> Result<Option<Vec<UnreferencedBlock>>, <Self as StorageBackend>::Error> {
         Ok(Some(
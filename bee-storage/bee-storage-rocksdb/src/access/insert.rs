@@ -4,7 +4,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -194,6 +194,22 @@ impl Insert<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Insert<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn insert(
+        &self,
+        index: &MilestoneIndex,
+        parameters: &ParametersMilestoneOption,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner.put_cf(
+            self.cf_handle(CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?,
+            index.pack_to_vec(),
+            parameters.pack_to_vec(),
+        )?;
+
+        Ok(())
+    }
+}
+
 impl Insert<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn insert(
         &self,
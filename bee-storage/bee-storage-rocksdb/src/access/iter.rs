@@ -6,7 +6,7 @@ use std::marker::PhantomData;
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -231,6 +231,17 @@ impl<'a> StorageIterator<'a, MilestoneIndex, OutputDiff> {
     }
 }
 
+impl<'a> StorageIterator<'a, MilestoneIndex, ParametersMilestoneOption> {
+    fn unpack_key_value(key: &[u8], value: &[u8]) -> (MilestoneIndex, ParametersMilestoneOption) {
+        (
+            // Unpacking from storage is fine.
+            MilestoneIndex::unpack_unverified(key).unwrap(),
+            // Unpacking from storage is fine.
+            ParametersMilestoneOption::unpack_unverified(value).unwrap(),
+        )
+    }
+}
+
 impl<'a> StorageIterator<'a, (MilestoneIndex, UnreferencedBlock), ()> {
     fn unpack_key_value(key: &[u8], _: &[u8]) -> ((MilestoneIndex, UnreferencedBlock), ()) {
         let (index, unreferenced_block) = key.split_at(std::mem::size_of::<MilestoneIndex>());
@@ -296,6 +307,11 @@ impl_iter!(MilestoneId, MilestonePayload, CF_MILESTONE_ID_TO_MILESTONE_PAYLOAD);
 impl_iter!((), SnapshotInfo, CF_SNAPSHOT_INFO);
 impl_iter!(SolidEntryPoint, MilestoneIndex, CF_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX);
 impl_iter!(MilestoneIndex, OutputDiff, CF_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_iter!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
 impl_iter!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -5,7 +5,7 @@ use std::{marker::PhantomData, vec::IntoIter};
 
 use bee_block::{
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{ConsumedOutput, CreatedOutput, OutputDiff};
@@ -73,6 +73,11 @@ impl_multi_fetch!(
 impl_multi_fetch!(MilestoneId, MilestonePayload, CF_MILESTONE_ID_TO_MILESTONE_PAYLOAD);
 impl_multi_fetch!(SolidEntryPoint, MilestoneIndex, CF_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX);
 impl_multi_fetch!(MilestoneIndex, OutputDiff, CF_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_multi_fetch!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
 
 impl<'a> MultiFetch<'a, BlockId, BlockMetadata> for Storage {
     type Iter = MultiIter<'a, BlockMetadata, <Self as StorageBackend>::Error>;
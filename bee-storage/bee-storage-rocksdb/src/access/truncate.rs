@@ -4,7 +4,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -76,6 +76,11 @@ impl_truncate!(MilestoneId, MilestonePayload, CF_MILESTONE_ID_TO_MILESTONE_PAYLO
 impl_truncate!((), SnapshotInfo, CF_SNAPSHOT_INFO);
 impl_truncate!(SolidEntryPoint, MilestoneIndex, CF_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX);
 impl_truncate!(MilestoneIndex, OutputDiff, CF_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_truncate!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
 impl_truncate!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -35,6 +35,8 @@ const DEFAULT_SET_USE_DIRECT_IO_FOR_FLUSH_AND_COMPACTION: bool = true;
 
 const DEFAULT_SET_HIGH_PRIORITY_BACKGROUND_THREADS: i32 = 2;
 
+const DEFAULT_READ_ONLY: bool = false;
+
 #[derive(Default, Debug, Deserialize, Eq, PartialEq)]
 #[must_use]
 pub struct StorageConfigBuilder {
@@ -131,6 +133,10 @@ pub struct RocksDbConfigBuilder {
     #[serde(alias = "setUseDirectIoForFlushAndCompaction")]
     set_use_direct_io_for_flush_and_compaction: Option<bool>,
     env: Option<RocksDbEnvConfigBuilder>,
+    #[serde(alias = "readOnly")]
+    read_only: Option<bool>,
+    #[serde(alias = "secondaryPath")]
+    secondary_path: Option<String>,
 }
 
 impl RocksDbConfigBuilder {
@@ -143,6 +149,20 @@ impl RocksDbConfigBuilder {
         self
     }
 
+    /// Opens the database in read-only mode, which does not require the database to be unused but only sees the data
+    /// that was persisted when it was opened.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Opens the database as a secondary instance keeping its own logs in `secondary_path`. Such an instance can be
+    /// opened alongside a running node and catch up with its writes. Takes precedence over the read-only mode.
+    pub fn with_secondary_path(mut self, secondary_path: String) -> Self {
+        self.secondary_path = Some(secondary_path);
+        self
+    }
+
     pub fn finish(self) -> RocksDbConfig {
         RocksDbConfig::from(self)
     }
@@ -198,10 +218,33 @@ impl From<RocksDbConfigBuilder> for RocksDbConfig {
                 .set_use_direct_io_for_flush_and_compaction
                 .unwrap_or(DEFAULT_SET_USE_DIRECT_IO_FOR_FLUSH_AND_COMPACTION),
             env: builder.env.unwrap_or_default().finish(),
+            mode: match builder.secondary_path {
+                Some(secondary_path) => OpenMode::Secondary(PathBuf::from(secondary_path)),
+                None if builder.read_only.unwrap_or(DEFAULT_READ_ONLY) => OpenMode::ReadOnly,
+                None => OpenMode::Primary,
+            },
         }
     }
 }
 
+/// The mode in which the database is opened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OpenMode {
+    /// The database is opened for reading and writing, and can't be opened by any other process.
+    Primary,
+    /// The database is opened for reading only, as of the moment it was opened.
+    ReadOnly,
+    /// The database is opened for reading only, alongside a primary instance it can catch up with.
+    Secondary(PathBuf),
+}
+
+impl OpenMode {
+    /// Returns whether write operations are allowed in this mode.
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Self::Primary)
+    }
+}
+
 #[derive(Clone)]
 pub struct StorageConfig {
     pub(crate) fetch_edge_limit: usize,
@@ -241,4 +284,12 @@ pub struct RocksDbConfig {
     pub(crate) set_unordered_write: bool,
     pub(crate) set_use_direct_io_for_flush_and_compaction: bool,
     pub(crate) env: RocksDbEnvConfig,
+    pub(crate) mode: OpenMode,
+}
+
+impl RocksDbConfig {
+    /// Sets the mode in which the database is opened.
+    pub fn set_mode(&mut self, mode: OpenMode) {
+        self.mode = mode;
+    }
 }
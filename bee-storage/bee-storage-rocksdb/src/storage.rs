@@ -15,11 +15,11 @@ use rocksdb::{
 
 use super::{
     column_families::*,
-    config::{RocksDbConfig, RocksDbConfigBuilder, StorageConfig},
+    config::{OpenMode, RocksDbConfig, RocksDbConfigBuilder, StorageConfig},
     error::Error,
 };
 
-pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion(10);
+pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion(11);
 
 pub struct Locks {
     pub(crate) block_id_to_metadata: RwLock<()>,
@@ -27,6 +27,8 @@ pub struct Locks {
 
 pub struct Storage {
     pub(crate) config: StorageConfig,
+    pub(crate) mode: OpenMode,
+    pub(crate) options: Options,
     pub(crate) inner: DB,
     pub(crate) locks: Locks,
 }
@@ -78,6 +80,9 @@ impl Storage {
         let cf_milestone_index_to_output_diff =
             ColumnFamilyDescriptor::new(CF_MILESTONE_INDEX_TO_OUTPUT_DIFF, Options::default());
 
+        let cf_milestone_index_to_protocol_parameters =
+            ColumnFamilyDescriptor::new(CF_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS, Options::default());
+
         let mut options = Options::default();
         options.set_prefix_extractor(SliceTransform::create_fixed_prefix(
             std::mem::size_of::<MilestoneIndex>(),
@@ -126,37 +131,50 @@ impl Storage {
         env.set_high_priority_background_threads(config.env.set_high_priority_background_threads);
         opts.set_env(&env);
 
-        let db = DB::open_cf_descriptors(
-            &opts,
-            config.path,
-            vec![
-                cf_system,
-                cf_block_id_to_block,
-                cf_block_id_to_metadata,
-                cf_block_id_to_block_id,
-                cf_output_id_to_created_output,
-                cf_output_id_to_consumed_output,
-                cf_output_id_unspent,
-                cf_ed25519_address_to_output_id,
-                cf_ledger_index,
-                cf_milestone_index_to_milestone_metadata,
-                cf_milestone_id_to_milestone_payload,
-                cf_snapshot_info,
-                cf_solid_entry_point_to_milestone_index,
-                cf_milestone_index_to_output_diff,
-                cf_milestone_index_to_unreferenced_block,
-                cf_milestone_index_to_receipt,
-                cf_spent_to_treasury,
-            ],
-        )?;
-
-        let mut flushopts = FlushOptions::new();
-        flushopts.set_wait(true);
-        db.flush_opt(&flushopts)?;
-        db.flush_cf_opt(db.cf_handle(CF_SYSTEM).unwrap(), &flushopts)?;
+        let cfs = vec![
+            cf_system,
+            cf_block_id_to_block,
+            cf_block_id_to_metadata,
+            cf_block_id_to_block_id,
+            cf_output_id_to_created_output,
+            cf_output_id_to_consumed_output,
+            cf_output_id_unspent,
+            cf_ed25519_address_to_output_id,
+            cf_ledger_index,
+            cf_milestone_index_to_milestone_metadata,
+            cf_milestone_id_to_milestone_payload,
+            cf_snapshot_info,
+            cf_solid_entry_point_to_milestone_index,
+            cf_milestone_index_to_output_diff,
+            cf_milestone_index_to_protocol_parameters,
+            cf_milestone_index_to_unreferenced_block,
+            cf_milestone_index_to_receipt,
+            cf_spent_to_treasury,
+        ];
+
+        let db = match &config.mode {
+            OpenMode::Primary => {
+                let db = DB::open_cf_descriptors(&opts, config.path, cfs)?;
+
+                let mut flushopts = FlushOptions::new();
+                flushopts.set_wait(true);
+                db.flush_opt(&flushopts)?;
+                db.flush_cf_opt(db.cf_handle(CF_SYSTEM).unwrap(), &flushopts)?;
+
+                db
+            }
+            OpenMode::ReadOnly => DB::open_cf_descriptors_read_only(&opts, config.path, cfs, false)?,
+            OpenMode::Secondary(secondary_path) => {
+                // Secondary instances have to keep all the files of the primary instance open.
+                opts.set_max_open_files(-1);
+                DB::open_cf_descriptors_as_secondary(&opts, config.path.as_path(), secondary_path.as_path(), cfs)?
+            }
+        };
 
         Ok(Storage {
             config: config.storage,
+            mode: config.mode,
+            options: opts,
             inner: db,
             locks: Locks {
                 block_id_to_metadata: RwLock::new(()),
@@ -164,17 +182,26 @@ impl Storage {
         })
     }
 
+    /// Returns the mode in which the database was opened.
+    pub fn mode(&self) -> &OpenMode {
+        &self.mode
+    }
+
+    /// Replays the latest writes of the primary instance, if the database was opened as a secondary instance.
+    pub fn catch_up_with_primary(&self) -> Result<(), Error> {
+        if let OpenMode::Secondary(_) = self.mode {
+            self.inner.try_catch_up_with_primary()?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn cf_handle(&self, cf_str: &'static str) -> Result<&ColumnFamily, Error> {
         self.inner.cf_handle(cf_str).ok_or(Error::UnknownColumnFamily(cf_str))
     }
-}
 
-impl StorageBackend for Storage {
-    type ConfigBuilder = RocksDbConfigBuilder;
-    type Config = RocksDbConfig;
-    type Error = Error;
-
-    fn start(config: Self::Config) -> Result<Self, Self::Error> {
+    /// Opens the database and checks its version.
+    fn open(config: RocksDbConfig) -> Result<Self, Error> {
         let storage = Self::new(config)?;
 
         match Fetch::<u8, System>::fetch(&storage, &SYSTEM_VERSION_KEY)? {
@@ -183,10 +210,28 @@ impl StorageBackend for Storage {
                     return Err(Error::VersionMismatch(version, STORAGE_VERSION));
                 }
             }
+            None if !storage.mode.is_writable() => {}
             None => Insert::<u8, System>::insert(&storage, &SYSTEM_VERSION_KEY, &System::Version(STORAGE_VERSION))?,
             _ => panic!("Another system value was inserted on the version key."),
         }
 
+        Ok(storage)
+    }
+}
+
+impl StorageBackend for Storage {
+    type ConfigBuilder = RocksDbConfigBuilder;
+    type Config = RocksDbConfig;
+    type Error = Error;
+
+    fn start(config: Self::Config) -> Result<Self, Self::Error> {
+        let storage = Self::open(config)?;
+
+        // The health is neither checked nor changed so that the database can be inspected whatever its state.
+        if !storage.mode.is_writable() {
+            return Ok(storage);
+        }
+
         if let Some(health) = storage.get_health()? {
             if health != StorageHealth::Healthy {
                 return Err(Self::Error::UnhealthyStorage(health));
@@ -198,7 +243,15 @@ impl StorageBackend for Storage {
         Ok(storage)
     }
 
+    fn start_unchecked(config: Self::Config) -> Result<Self, Self::Error> {
+        Self::open(config)
+    }
+
     fn shutdown(self) -> Result<(), Self::Error> {
+        if !self.mode.is_writable() {
+            return Ok(());
+        }
+
         self.set_health(StorageHealth::Healthy)?;
 
         Ok(self.inner.flush()?)
@@ -221,4 +274,15 @@ impl StorageBackend for Storage {
     fn set_health(&self, health: StorageHealth) -> Result<(), Self::Error> {
         Insert::<u8, System>::insert(self, &SYSTEM_HEALTH_KEY, &System::Health(health))
     }
+
+    fn statistics(&self) -> Result<Option<String>, Self::Error> {
+        let mut statistics = self.inner.property_value(rocksdb::properties::STATS)?;
+
+        // Only available if `enable_statistics` is set.
+        if let Some(tickers) = self.options.get_statistics() {
+            statistics.get_or_insert_with(String::new).push_str(&tickers);
+        }
+
+        Ok(statistics)
+    }
 }
@@ -8,7 +8,7 @@ use std::{collections::BTreeMap, convert::Infallible};
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -40,10 +40,11 @@ impl BatchBuilder for Storage {
     type Batch = StorageBatch;
 
     fn batch_commit(&self, batch: Self::Batch, _durability: bool) -> Result<(), <Self as StorageBackend>::Error> {
+        let db = self.writable()?;
         let trees = batch
             .inner
             .keys()
-            .map(|tree| self.inner.open_tree(tree))
+            .map(|tree| db.open_tree(tree))
             .collect::<Result<Vec<_>, _>>()?;
 
         let transaction_result = Transactional::<Infallible>::transaction::<_, ()>(trees.as_slice(), |trees| {
@@ -535,6 +536,48 @@ impl Batch<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Batch<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        index: &MilestoneIndex,
+        parameters: &ParametersMilestoneOption,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        // Packing to bytes can't fail.
+        index.pack(&mut batch.key_buf).unwrap();
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        parameters.pack(&mut batch.value_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)
+            .or_default()
+            .insert(batch.key_buf.as_slice(), batch.value_buf.as_slice());
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        index: &MilestoneIndex,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.key_buf.clear();
+        // Packing to bytes can't fail.
+        index.pack(&mut batch.key_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)
+            .or_default()
+            .remove(batch.key_buf.as_slice());
+
+        Ok(())
+    }
+}
+
 impl Batch<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn batch_insert(
         &self,
@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -24,7 +24,7 @@ use crate::{storage::Storage, trees::*};
 
 impl Delete<BlockId, Block> for Storage {
     fn delete(&self, block_id: &BlockId) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner.open_tree(TREE_BLOCK_ID_TO_BLOCK)?.remove(block_id)?;
+        self.writable()?.open_tree(TREE_BLOCK_ID_TO_BLOCK)?.remove(block_id)?;
 
         Ok(())
     }
@@ -32,7 +32,9 @@ impl Delete<BlockId, Block> for Storage {
 
 impl Delete<BlockId, BlockMetadata> for Storage {
     fn delete(&self, block_id: &BlockId) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner.open_tree(TREE_BLOCK_ID_TO_METADATA)?.remove(block_id)?;
+        self.writable()?
+            .open_tree(TREE_BLOCK_ID_TO_METADATA)?
+            .remove(block_id)?;
 
         Ok(())
     }
@@ -43,7 +45,7 @@ impl Delete<(BlockId, BlockId), ()> for Storage {
         let mut key = parent.as_ref().to_vec();
         key.extend_from_slice(child.as_ref());
 
-        self.inner.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?.remove(key)?;
+        self.writable()?.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?.remove(key)?;
 
         Ok(())
     }
@@ -51,7 +53,7 @@ impl Delete<(BlockId, BlockId), ()> for Storage {
 
 impl Delete<OutputId, CreatedOutput> for Storage {
     fn delete(&self, output_id: &OutputId) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_TO_CREATED_OUTPUT)?
             .remove(output_id.pack_to_vec())?;
 
@@ -61,7 +63,7 @@ impl Delete<OutputId, CreatedOutput> for Storage {
 
 impl Delete<OutputId, ConsumedOutput> for Storage {
     fn delete(&self, output_id: &OutputId) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT)?
             .remove(output_id.pack_to_vec())?;
 
@@ -71,7 +73,7 @@ impl Delete<OutputId, ConsumedOutput> for Storage {
 
 impl Delete<Unspent, ()> for Storage {
     fn delete(&self, unspent: &Unspent) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_UNSPENT)?
             .remove(unspent.pack_to_vec())?;
 
@@ -84,7 +86,9 @@ impl Delete<(Ed25519Address, OutputId), ()> for Storage {
         let mut key = address.as_ref().to_vec();
         key.extend_from_slice(&output_id.pack_to_vec());
 
-        self.inner.open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?.remove(key)?;
+        self.writable()?
+            .open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?
+            .remove(key)?;
 
         Ok(())
     }
@@ -92,7 +96,7 @@ impl Delete<(Ed25519Address, OutputId), ()> for Storage {
 
 impl Delete<(), LedgerIndex> for Storage {
     fn delete(&self, (): &()) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner.open_tree(TREE_LEDGER_INDEX)?.remove([0x00u8])?;
+        self.writable()?.open_tree(TREE_LEDGER_INDEX)?.remove([0x00u8])?;
 
         Ok(())
     }
@@ -100,7 +104,7 @@ impl Delete<(), LedgerIndex> for Storage {
 
 impl Delete<MilestoneIndex, MilestoneMetadata> for Storage {
     fn delete(&self, index: &MilestoneIndex) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_MILESTONE_METADATA)?
             .remove(index.pack_to_vec())?;
 
@@ -110,7 +114,7 @@ impl Delete<MilestoneIndex, MilestoneMetadata> for Storage {
 
 impl Delete<MilestoneId, MilestonePayload> for Storage {
     fn delete(&self, id: &MilestoneId) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_ID_TO_MILESTONE_PAYLOAD)?
             .remove(id.pack_to_vec())?;
 
@@ -120,7 +124,7 @@ impl Delete<MilestoneId, MilestonePayload> for Storage {
 
 impl Delete<(), SnapshotInfo> for Storage {
     fn delete(&self, (): &()) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner.open_tree(TREE_SNAPSHOT_INFO)?.remove([0x00u8])?;
+        self.writable()?.open_tree(TREE_SNAPSHOT_INFO)?.remove([0x00u8])?;
 
         Ok(())
     }
@@ -128,7 +132,7 @@ impl Delete<(), SnapshotInfo> for Storage {
 
 impl Delete<SolidEntryPoint, MilestoneIndex> for Storage {
     fn delete(&self, sep: &SolidEntryPoint) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX)?
             .remove(sep.as_ref())?;
 
@@ -138,7 +142,7 @@ impl Delete<SolidEntryPoint, MilestoneIndex> for Storage {
 
 impl Delete<MilestoneIndex, OutputDiff> for Storage {
     fn delete(&self, index: &MilestoneIndex) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF)?
             .remove(index.pack_to_vec())?;
 
@@ -146,6 +150,16 @@ impl Delete<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Delete<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn delete(&self, index: &MilestoneIndex) -> Result<(), <Self as StorageBackend>::Error> {
+        self.writable()?
+            .open_tree(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?
+            .remove(index.pack_to_vec())?;
+
+        Ok(())
+    }
+}
+
 impl Delete<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn delete(
         &self,
@@ -154,7 +168,7 @@ impl Delete<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
         let mut key = index.pack_to_vec();
         key.extend_from_slice(unreferenced_block.as_ref());
 
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK)?
             .remove(key)?;
 
@@ -167,7 +181,9 @@ impl Delete<(MilestoneIndex, Receipt), ()> for Storage {
         let mut key = index.pack_to_vec();
         key.extend_from_slice(&receipt.pack_to_vec());
 
-        self.inner.open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?.remove(key)?;
+        self.writable()?
+            .open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?
+            .remove(key)?;
 
         Ok(())
     }
@@ -178,7 +194,7 @@ impl Delete<(bool, TreasuryOutput), ()> for Storage {
         let mut key = spent.pack_to_vec();
         key.extend_from_slice(&output.pack_to_vec());
 
-        self.inner.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.remove(key)?;
+        self.writable()?.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.remove(key)?;
 
         Ok(())
     }
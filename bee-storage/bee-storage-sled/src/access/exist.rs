@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -24,16 +24,13 @@ use crate::{storage::Storage, trees::*};
 
 impl Exist<BlockId, Block> for Storage {
     fn exist(&self, block_id: &BlockId) -> Result<bool, <Self as StorageBackend>::Error> {
-        Ok(self.inner.open_tree(TREE_BLOCK_ID_TO_BLOCK)?.contains_key(block_id)?)
+        Ok(self.open_tree(TREE_BLOCK_ID_TO_BLOCK)?.contains_key(block_id)?)
     }
 }
 
 impl Exist<BlockId, BlockMetadata> for Storage {
     fn exist(&self, block_id: &BlockId) -> Result<bool, <Self as StorageBackend>::Error> {
-        Ok(self
-            .inner
-            .open_tree(TREE_BLOCK_ID_TO_METADATA)?
-            .contains_key(block_id)?)
+        Ok(self.open_tree(TREE_BLOCK_ID_TO_METADATA)?.contains_key(block_id)?)
     }
 }
 
@@ -42,14 +39,13 @@ impl Exist<(BlockId, BlockId), ()> for Storage {
         let mut key = parent.as_ref().to_vec();
         key.extend_from_slice(child.as_ref());
 
-        Ok(self.inner.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?.contains_key(key)?)
+        Ok(self.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?.contains_key(key)?)
     }
 }
 
 impl Exist<OutputId, CreatedOutput> for Storage {
     fn exist(&self, output_id: &OutputId) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_OUTPUT_ID_TO_CREATED_OUTPUT)?
             .contains_key(output_id.pack_to_vec())?)
     }
@@ -58,7 +54,6 @@ impl Exist<OutputId, CreatedOutput> for Storage {
 impl Exist<OutputId, ConsumedOutput> for Storage {
     fn exist(&self, output_id: &OutputId) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT)?
             .contains_key(output_id.pack_to_vec())?)
     }
@@ -67,7 +62,6 @@ impl Exist<OutputId, ConsumedOutput> for Storage {
 impl Exist<Unspent, ()> for Storage {
     fn exist(&self, unspent: &Unspent) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_OUTPUT_ID_UNSPENT)?
             .contains_key(unspent.pack_to_vec())?)
     }
@@ -81,23 +75,19 @@ impl Exist<(Ed25519Address, OutputId), ()> for Storage {
         let mut key = address.as_ref().to_vec();
         key.extend_from_slice(&output_id.pack_to_vec());
 
-        Ok(self
-            .inner
-            .open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?
-            .contains_key(key)?)
+        Ok(self.open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?.contains_key(key)?)
     }
 }
 
 impl Exist<(), LedgerIndex> for Storage {
     fn exist(&self, (): &()) -> Result<bool, <Self as StorageBackend>::Error> {
-        Ok(self.inner.open_tree(TREE_LEDGER_INDEX)?.contains_key([0x00u8])?)
+        Ok(self.open_tree(TREE_LEDGER_INDEX)?.contains_key([0x00u8])?)
     }
 }
 
 impl Exist<MilestoneIndex, MilestoneMetadata> for Storage {
     fn exist(&self, index: &MilestoneIndex) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_INDEX_TO_MILESTONE_METADATA)?
             .contains_key(index.pack_to_vec())?)
     }
@@ -106,7 +96,6 @@ impl Exist<MilestoneIndex, MilestoneMetadata> for Storage {
 impl Exist<MilestoneId, MilestonePayload> for Storage {
     fn exist(&self, index: &MilestoneId) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_ID_TO_MILESTONE_PAYLOAD)?
             .contains_key(index.pack_to_vec())?)
     }
@@ -114,14 +103,13 @@ impl Exist<MilestoneId, MilestonePayload> for Storage {
 
 impl Exist<(), SnapshotInfo> for Storage {
     fn exist(&self, (): &()) -> Result<bool, <Self as StorageBackend>::Error> {
-        Ok(self.inner.open_tree(TREE_SNAPSHOT_INFO)?.contains_key([0x00u8])?)
+        Ok(self.open_tree(TREE_SNAPSHOT_INFO)?.contains_key([0x00u8])?)
     }
 }
 
 impl Exist<SolidEntryPoint, MilestoneIndex> for Storage {
     fn exist(&self, sep: &SolidEntryPoint) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX)?
             .contains_key(sep.pack_to_vec())?)
     }
@@ -130,12 +118,19 @@ impl Exist<SolidEntryPoint, MilestoneIndex> for Storage {
 impl Exist<MilestoneIndex, OutputDiff> for Storage {
     fn exist(&self, index: &MilestoneIndex) -> Result<bool, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF)?
             .contains_key(index.pack_to_vec())?)
     }
 }
 
+impl Exist<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn exist(&self, index: &MilestoneIndex) -> Result<bool, <Self as StorageBackend>::Error> {
+        Ok(self
+            .open_tree(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?
+            .contains_key(index.pack_to_vec())?)
+    }
+}
+
 impl Exist<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn exist(
         &self,
@@ -145,7 +140,6 @@ impl Exist<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
         key.extend_from_slice(unreferenced_block.as_ref());
 
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK)?
             .contains_key(key)?)
     }
@@ -156,10 +150,7 @@ impl Exist<(MilestoneIndex, Receipt), ()> for Storage {
         let mut key = index.pack_to_vec();
         key.extend_from_slice(&receipt.pack_to_vec());
 
-        Ok(self
-            .inner
-            .open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?
-            .contains_key(key)?)
+        Ok(self.open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?.contains_key(key)?)
     }
 }
 
@@ -168,6 +159,6 @@ impl Exist<(bool, TreasuryOutput), ()> for Storage {
         let mut key = spent.pack_to_vec();
         key.extend_from_slice(&output.pack_to_vec());
 
-        Ok(self.inner.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.contains_key(key)?)
+        Ok(self.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.contains_key(key)?)
     }
 }
@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -34,7 +34,6 @@ impl Fetch<u8, System> for Storage {
 impl Fetch<BlockId, Block> for Storage {
     fn fetch(&self, block_id: &BlockId) -> Result<Option<Block>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_BLOCK_ID_TO_BLOCK)?
             .get(block_id)?
             // Unpacking from storage is fine.
@@ -45,7 +44,6 @@ impl Fetch<BlockId, Block> for Storage {
 impl Fetch<BlockId, BlockMetadata> for Storage {
     fn fetch(&self, block_id: &BlockId) -> Result<Option<BlockMetadata>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_BLOCK_ID_TO_METADATA)?
             .get(block_id)?
             // Unpacking from storage is fine.
@@ -56,8 +54,7 @@ impl Fetch<BlockId, BlockMetadata> for Storage {
 impl Fetch<BlockId, Vec<BlockId>> for Storage {
     fn fetch(&self, parent: &BlockId) -> Result<Option<Vec<BlockId>>, <Self as StorageBackend>::Error> {
         Ok(Some(
-            self.inner
-                .open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?
+            self.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?
                 .scan_prefix(parent)
                 .map(|result| {
                     let (key, _) = result?;
@@ -75,7 +72,6 @@ impl Fetch<BlockId, Vec<BlockId>> for Storage {
 impl Fetch<OutputId, CreatedOutput> for Storage {
     fn fetch(&self, output_id: &OutputId) -> Result<Option<CreatedOutput>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_OUTPUT_ID_TO_CREATED_OUTPUT)?
             .get(output_id.pack_to_vec())?
             // Unpacking from storage is fine.
@@ -86,7 +82,6 @@ impl Fetch<OutputId, CreatedOutput> for Storage {
 impl Fetch<OutputId, ConsumedOutput> for Storage {
     fn fetch(&self, output_id: &OutputId) -> Result<Option<ConsumedOutput>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT)?
             .get(output_id.pack_to_vec())?
             // Unpacking from storage is fine.
@@ -97,8 +92,7 @@ impl Fetch<OutputId, ConsumedOutput> for Storage {
 impl Fetch<Ed25519Address, Vec<OutputId>> for Storage {
     fn fetch(&self, address: &Ed25519Address) -> Result<Option<Vec<OutputId>>, <Self as StorageBackend>::Error> {
         Ok(Some(
-            self.inner
-                .open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?
+            self.open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?
                 .scan_prefix(address)
                 .map(|result| {
                     let (key, _) = result?;
@@ -117,7 +111,6 @@ impl Fetch<Ed25519Address, Vec<OutputId>> for Storage {
 impl Fetch<(), LedgerIndex> for Storage {
     fn fetch(&self, (): &()) -> Result<Option<LedgerIndex>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_LEDGER_INDEX)?
             .get([0x00u8])?
             // Unpacking from storage is fine.
@@ -128,7 +121,6 @@ impl Fetch<(), LedgerIndex> for Storage {
 impl Fetch<MilestoneIndex, MilestoneMetadata> for Storage {
     fn fetch(&self, index: &MilestoneIndex) -> Result<Option<MilestoneMetadata>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_INDEX_TO_MILESTONE_METADATA)?
             .get(index.pack_to_vec())?
             // Unpacking from storage is fine.
@@ -139,7 +131,6 @@ impl Fetch<MilestoneIndex, MilestoneMetadata> for Storage {
 impl Fetch<MilestoneId, MilestonePayload> for Storage {
     fn fetch(&self, id: &MilestoneId) -> Result<Option<MilestonePayload>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_ID_TO_MILESTONE_PAYLOAD)?
             .get(id.pack_to_vec())?
             // Unpacking from storage is fine.
@@ -150,7 +141,6 @@ impl Fetch<MilestoneId, MilestonePayload> for Storage {
 impl Fetch<(), SnapshotInfo> for Storage {
     fn fetch(&self, (): &()) -> Result<Option<SnapshotInfo>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_SNAPSHOT_INFO)?
             .get([0x00u8])?
             // Unpacking from storage is fine.
@@ -161,7 +151,6 @@ impl Fetch<(), SnapshotInfo> for Storage {
 impl Fetch<SolidEntryPoint, MilestoneIndex> for Storage {
     fn fetch(&self, sep: &SolidEntryPoint) -> Result<Option<MilestoneIndex>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX)?
             .get(sep.as_ref())?
             // Unpacking from storage is fine.
@@ -172,7 +161,6 @@ impl Fetch<SolidEntryPoint, MilestoneIndex> for Storage {
 impl Fetch<MilestoneIndex, OutputDiff> for Storage {
     fn fetch(&self, index: &MilestoneIndex) -> Result<Option<OutputDiff>, <Self as StorageBackend>::Error> {
         Ok(self
-            .inner
             .open_tree(TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF)?
             .get(index.pack_to_vec())?
             // Unpacking from storage is fine.
@@ -180,11 +168,23 @@ impl Fetch<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Fetch<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn fetch(
+        &self,
+        index: &MilestoneIndex,
+    ) -> Result<Option<ParametersMilestoneOption>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .open_tree(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?
+            .get(index.pack_to_vec())?
+            // Unpacking from storage is fine.
+            .map(|v| ParametersMilestoneOption::unpack_unverified(v.as_ref()).unwrap()))
+    }
+}
+
 impl Fetch<MilestoneIndex, Vec<UnreferencedBlock>> for Storage {
     fn fetch(&self, index: &MilestoneIndex) -> Result<Option<Vec<UnreferencedBlock>>, <Self as StorageBackend>::Error> {
         Ok(Some(
-            self.inner
-                .open_tree(TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK)?
+            self.open_tree(TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK)?
                 .scan_prefix(index.pack_to_vec())
                 .map(|result| {
                     let (key, _) = result?;
@@ -201,8 +201,7 @@ impl Fetch<MilestoneIndex, Vec<UnreferencedBlock>> for Storage {
 impl Fetch<MilestoneIndex, Vec<Receipt>> for Storage {
     fn fetch(&self, index: &MilestoneIndex) -> Result<Option<Vec<Receipt>>, <Self as StorageBackend>::Error> {
         Ok(Some(
-            self.inner
-                .open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?
+            self.open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?
                 .scan_prefix(index.pack_to_vec())
                 .map(|result| {
                     let (mut key, _) = result?;
@@ -219,8 +218,7 @@ impl Fetch<MilestoneIndex, Vec<Receipt>> for Storage {
 impl Fetch<bool, Vec<TreasuryOutput>> for Storage {
     fn fetch(&self, spent: &bool) -> Result<Option<Vec<TreasuryOutput>>, <Self as StorageBackend>::Error> {
         Ok(Some(
-            self.inner
-                .open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?
+            self.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?
                 .scan_prefix(spent.pack_to_vec())
                 .map(|result| {
                     let (mut key, _) = result?;
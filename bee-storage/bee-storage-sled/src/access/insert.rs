@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -28,7 +28,7 @@ use crate::{storage::Storage, trees::*};
 
 impl Insert<u8, System> for Storage {
     fn insert(&self, key: &u8, value: &System) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner.insert([*key], value.pack_to_vec())?;
+        self.writable()?.insert([*key], value.pack_to_vec())?;
 
         Ok(())
     }
@@ -36,7 +36,7 @@ impl Insert<u8, System> for Storage {
 
 impl Insert<BlockId, Block> for Storage {
     fn insert(&self, block_id: &BlockId, block: &Block) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_BLOCK_ID_TO_BLOCK)?
             .insert(block_id, block.pack_to_vec())?;
 
@@ -50,7 +50,7 @@ impl InsertStrict<BlockId, BlockMetadata> for Storage {
         block_id: &BlockId,
         metadata: &BlockMetadata,
     ) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_BLOCK_ID_TO_METADATA)?
             .update_and_fetch(block_id, |old_metadata| {
                 old_metadata
@@ -67,7 +67,9 @@ impl Insert<(BlockId, BlockId), ()> for Storage {
         let mut key = parent.as_ref().to_vec();
         key.extend_from_slice(child.as_ref());
 
-        self.inner.open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?.insert(key, &[])?;
+        self.writable()?
+            .open_tree(TREE_BLOCK_ID_TO_BLOCK_ID)?
+            .insert(key, &[])?;
 
         Ok(())
     }
@@ -75,7 +77,7 @@ impl Insert<(BlockId, BlockId), ()> for Storage {
 
 impl Insert<OutputId, CreatedOutput> for Storage {
     fn insert(&self, output_id: &OutputId, output: &CreatedOutput) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_TO_CREATED_OUTPUT)?
             .insert(output_id.pack_to_vec(), output.pack_to_vec())?;
 
@@ -85,7 +87,7 @@ impl Insert<OutputId, CreatedOutput> for Storage {
 
 impl Insert<OutputId, ConsumedOutput> for Storage {
     fn insert(&self, output_id: &OutputId, output: &ConsumedOutput) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_TO_CONSUMED_OUTPUT)?
             .insert(output_id.pack_to_vec(), output.pack_to_vec())?;
 
@@ -95,7 +97,7 @@ impl Insert<OutputId, ConsumedOutput> for Storage {
 
 impl Insert<Unspent, ()> for Storage {
     fn insert(&self, unspent: &Unspent, (): &()) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_OUTPUT_ID_UNSPENT)?
             .insert(unspent.pack_to_vec(), &[])?;
 
@@ -112,7 +114,7 @@ impl Insert<(Ed25519Address, OutputId), ()> for Storage {
         let mut key = address.as_ref().to_vec();
         key.extend_from_slice(&output_id.pack_to_vec());
 
-        self.inner
+        self.writable()?
             .open_tree(TREE_ED25519_ADDRESS_TO_OUTPUT_ID)?
             .insert(key, &[])?;
 
@@ -122,7 +124,7 @@ impl Insert<(Ed25519Address, OutputId), ()> for Storage {
 
 impl Insert<(), LedgerIndex> for Storage {
     fn insert(&self, (): &(), index: &LedgerIndex) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_LEDGER_INDEX)?
             .insert([0x00u8], index.pack_to_vec())?;
 
@@ -136,7 +138,7 @@ impl Insert<MilestoneIndex, MilestoneMetadata> for Storage {
         index: &MilestoneIndex,
         milestone: &MilestoneMetadata,
     ) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_MILESTONE_METADATA)?
             .insert(index.pack_to_vec(), milestone.pack_to_vec())?;
 
@@ -146,7 +148,7 @@ impl Insert<MilestoneIndex, MilestoneMetadata> for Storage {
 
 impl Insert<MilestoneId, MilestonePayload> for Storage {
     fn insert(&self, id: &MilestoneId, payload: &MilestonePayload) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_ID_TO_MILESTONE_PAYLOAD)?
             .insert(id.pack_to_vec(), payload.pack_to_vec())?;
 
@@ -156,7 +158,7 @@ impl Insert<MilestoneId, MilestonePayload> for Storage {
 
 impl Insert<(), SnapshotInfo> for Storage {
     fn insert(&self, (): &(), info: &SnapshotInfo) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_SNAPSHOT_INFO)?
             .insert([0x00u8], info.pack_to_vec())?;
 
@@ -166,7 +168,7 @@ impl Insert<(), SnapshotInfo> for Storage {
 
 impl Insert<SolidEntryPoint, MilestoneIndex> for Storage {
     fn insert(&self, sep: &SolidEntryPoint, index: &MilestoneIndex) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX)?
             .insert(sep.as_ref(), index.pack_to_vec())?;
 
@@ -176,7 +178,7 @@ impl Insert<SolidEntryPoint, MilestoneIndex> for Storage {
 
 impl Insert<MilestoneIndex, OutputDiff> for Storage {
     fn insert(&self, index: &MilestoneIndex, diff: &OutputDiff) -> Result<(), <Self as StorageBackend>::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF)?
             .insert(index.pack_to_vec(), diff.pack_to_vec())?;
 
@@ -184,6 +186,20 @@ impl Insert<MilestoneIndex, OutputDiff> for Storage {
     }
 }
 
+impl Insert<MilestoneIndex, ParametersMilestoneOption> for Storage {
+    fn insert(
+        &self,
+        index: &MilestoneIndex,
+        parameters: &ParametersMilestoneOption,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        self.writable()?
+            .open_tree(TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS)?
+            .insert(index.pack_to_vec(), parameters.pack_to_vec())?;
+
+        Ok(())
+    }
+}
+
 impl Insert<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
     fn insert(
         &self,
@@ -193,7 +209,7 @@ impl Insert<(MilestoneIndex, UnreferencedBlock), ()> for Storage {
         let mut key = index.pack_to_vec();
         key.extend_from_slice(unreferenced_block.as_ref());
 
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK)?
             .insert(key, &[])?;
 
@@ -210,7 +226,7 @@ impl Insert<(MilestoneIndex, Receipt), ()> for Storage {
         let mut key = index.pack_to_vec();
         key.extend_from_slice(&receipt.pack_to_vec());
 
-        self.inner
+        self.writable()?
             .open_tree(TREE_MILESTONE_INDEX_TO_RECEIPT)?
             .insert(key, &[])?;
 
@@ -223,7 +239,9 @@ impl Insert<(bool, TreasuryOutput), ()> for Storage {
         let mut key = spent.pack_to_vec();
         key.extend_from_slice(&output.pack_to_vec());
 
-        self.inner.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.insert(key, &[])?;
+        self.writable()?
+            .open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?
+            .insert(key, &[])?;
 
         Ok(())
     }
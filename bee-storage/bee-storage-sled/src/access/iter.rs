@@ -8,7 +8,7 @@ use std::marker::PhantomData;
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -44,7 +44,7 @@ macro_rules! impl_iter {
             type AsIter = StorageIterator<'a, $key, $value>;
 
             fn iter(&'a self) -> Result<Self::AsIter, <Self as StorageBackend>::Error> {
-                Ok(StorageIterator::new(self.inner.open_tree($cf)?.iter()))
+                Ok(StorageIterator::new(self.open_tree($cf)?.iter()))
             }
         }
 
@@ -224,6 +224,17 @@ impl<'a> StorageIterator<'a, MilestoneIndex, OutputDiff> {
     }
 }
 
+impl<'a> StorageIterator<'a, MilestoneIndex, ParametersMilestoneOption> {
+    fn unpack_key_value(key: &[u8], value: &[u8]) -> (MilestoneIndex, ParametersMilestoneOption) {
+        (
+            // Unpacking from storage is fine.
+            MilestoneIndex::unpack_unverified(key).unwrap(),
+            // Unpacking from storage is fine.
+            ParametersMilestoneOption::unpack_unverified(value).unwrap(),
+        )
+    }
+}
+
 impl<'a> StorageIterator<'a, (MilestoneIndex, UnreferencedBlock), ()> {
     fn unpack_key_value(key: &[u8], _: &[u8]) -> ((MilestoneIndex, UnreferencedBlock), ()) {
         let (index, unreferenced_block) = key.split_at(std::mem::size_of::<MilestoneIndex>());
@@ -314,6 +325,11 @@ impl_iter!(
     TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX
 );
 impl_iter!(MilestoneIndex, OutputDiff, TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_iter!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
 impl_iter!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -7,7 +7,7 @@ use std::{marker::PhantomData, slice::Iter};
 
 use bee_block::{
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{ConsumedOutput, CreatedOutput, OutputDiff};
@@ -82,7 +82,7 @@ macro_rules! impl_multi_fetch {
 
             fn multi_fetch(&'a self, keys: &'a [$key]) -> Result<Self::Iter, <Self as StorageBackend>::Error> {
                 Ok(TreeIter {
-                    tree: self.inner.open_tree($cf)?,
+                    tree: self.open_tree($cf)?,
                     keys: keys.iter(),
                     marker: PhantomData,
                 })
@@ -107,3 +107,8 @@ impl_multi_fetch!(
     TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX
 );
 impl_multi_fetch!(MilestoneIndex, OutputDiff, TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_multi_fetch!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
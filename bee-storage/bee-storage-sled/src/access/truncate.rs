@@ -6,7 +6,7 @@
 use bee_block::{
     address::Ed25519Address,
     output::OutputId,
-    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload},
+    payload::milestone::{MilestoneId, MilestoneIndex, MilestonePayload, ParametersMilestoneOption},
     Block, BlockId,
 };
 use bee_ledger::types::{
@@ -24,7 +24,7 @@ macro_rules! impl_truncate {
     ($key:ty, $value:ty, $cf:expr) => {
         impl Truncate<$key, $value> for Storage {
             fn truncate(&self) -> Result<(), <Self as StorageBackend>::Error> {
-                self.inner.drop_tree($cf)?;
+                self.writable()?.drop_tree($cf)?;
 
                 Ok(())
             }
@@ -53,6 +53,11 @@ impl_truncate!(
     TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX
 );
 impl_truncate!(MilestoneIndex, OutputDiff, TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF);
+impl_truncate!(
+    MilestoneIndex,
+    ParametersMilestoneOption,
+    TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS
+);
 impl_truncate!(
     (MilestoneIndex, UnreferencedBlock),
     (),
@@ -12,7 +12,7 @@ use crate::{storage::Storage, trees::*};
 
 impl Update<BlockId, BlockMetadata> for Storage {
     fn update(&self, block_id: &BlockId, mut f: impl FnMut(&mut BlockMetadata)) -> Result<(), Self::Error> {
-        self.inner
+        self.writable()?
             .open_tree(TREE_BLOCK_ID_TO_METADATA)?
             .fetch_and_update(block_id, move |opt_bytes| {
                 opt_bytes.map(|bytes| {
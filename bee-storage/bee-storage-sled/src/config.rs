@@ -13,6 +13,7 @@ const DEFAULT_CACHE_CAPACITY: usize = 1_024 * 1_024 * 1_024;
 const DEFAULT_FAST_MODE: bool = false;
 const DEFAULT_TEMPORARY: bool = false;
 const DEFAULT_CREATE_NEW: bool = false;
+const DEFAULT_READ_ONLY: bool = false;
 const DEFAULT_FETCH_EDGE_LIMIT: usize = 1_000;
 const DEFAULT_FETCH_OUTPUT_ID_LIMIT: usize = 1_000;
 
@@ -26,6 +27,17 @@ pub struct SledConfig {
     pub(crate) fast_mode: bool,
     pub(crate) temporary: bool,
     pub(crate) create_new: bool,
+    pub(crate) read_only: bool,
+}
+
+impl SledConfig {
+    /// Sets whether the database is opened for reading only.
+    ///
+    /// Sled locks the database even then, so it still can't be opened while another process, e.g. a running node, uses
+    /// it.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
 }
 
 /// Configuration builder for the sled storage backend.
@@ -43,6 +55,8 @@ pub struct SledConfigBuilder {
     temporary: Option<bool>,
     #[serde(alias = "createNew")]
     create_new: Option<bool>,
+    #[serde(alias = "readOnly")]
+    read_only: Option<bool>,
 }
 
 impl SledConfigBuilder {
@@ -89,6 +103,14 @@ impl SledConfigBuilder {
         self
     }
 
+    /// Specify if the database should be opened in read-only mode: the version and health are neither written nor
+    /// checked and every write operation fails. Sled holds an exclusive lock on the database, so this does not allow
+    /// opening a database that is in use by another process.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
     /// Build the configuration.
     #[must_use]
     pub fn finish(self) -> SledConfig {
@@ -100,6 +122,7 @@ impl SledConfigBuilder {
             fast_mode: self.fast_mode.unwrap_or(DEFAULT_FAST_MODE),
             temporary: self.temporary.unwrap_or(DEFAULT_TEMPORARY),
             create_new: self.create_new.unwrap_or(DEFAULT_CREATE_NEW),
+            read_only: self.read_only.unwrap_or(DEFAULT_READ_ONLY),
         }
     }
 }
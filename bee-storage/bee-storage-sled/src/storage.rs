@@ -25,9 +25,15 @@ pub enum Error {
     /// The storage was not closed properly.
     #[error("unhealthy storage: {0:?}, remove storage folder and restart")]
     UnhealthyStorage(StorageHealth),
+    /// A write operation was attempted on a storage opened in read-only mode.
+    #[error("storage is opened in read-only mode")]
+    ReadOnly,
+    /// A tree that does not exist was accessed on a storage opened in read-only mode.
+    #[error("tree {0} does not exist and can't be created in read-only mode")]
+    MissingTree(&'static str),
 }
 
-pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion(1);
+pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion(2);
 
 /// The sled database.
 pub struct Storage {
@@ -49,20 +55,39 @@ impl Storage {
             .use_compression(config.compression_factor.is_some())
             .compression_factor(config.compression_factor.unwrap_or(1) as i32)
             .temporary(config.temporary)
-            .create_new(!config.create_new);
+            // A read-only database is only ever opened, never created.
+            .create_new(!config.read_only && config.create_new);
 
         let inner = sled_cfg.open()?;
 
         Ok(Self { inner, config })
     }
-}
 
-impl StorageBackend for Storage {
-    type ConfigBuilder = SledConfigBuilder;
-    type Config = SledConfig;
-    type Error = Error;
+    /// Returns whether the database was opened in read-only mode.
+    pub fn is_read_only(&self) -> bool {
+        self.config.read_only
+    }
 
-    fn start(config: Self::Config) -> Result<Self, Self::Error> {
+    /// Returns the database if it may be written to.
+    pub(crate) fn writable(&self) -> Result<&sled::Db, Error> {
+        if self.config.read_only {
+            Err(Error::ReadOnly)
+        } else {
+            Ok(&self.inner)
+        }
+    }
+
+    /// Opens a tree of the database, without creating it if the database was opened in read-only mode.
+    pub(crate) fn open_tree(&self, name: &'static str) -> Result<sled::Tree, Error> {
+        if self.config.read_only && !self.inner.tree_names().iter().any(|tree| tree == name.as_bytes()) {
+            return Err(Error::MissingTree(name));
+        }
+
+        Ok(self.inner.open_tree(name)?)
+    }
+
+    /// Opens the database and checks its version.
+    fn open(config: SledConfig) -> Result<Self, Error> {
         let storage = Self::new(config)?;
 
         match Fetch::<u8, System>::fetch(&storage, &SYSTEM_VERSION_KEY)? {
@@ -71,10 +96,28 @@ impl StorageBackend for Storage {
                     return Err(Error::VersionMismatch(version, STORAGE_VERSION));
                 }
             }
+            None if storage.is_read_only() => {}
             None => Insert::<u8, System>::insert(&storage, &SYSTEM_VERSION_KEY, &System::Version(STORAGE_VERSION))?,
             _ => panic!("Another system value was inserted on the version key."),
         }
 
+        Ok(storage)
+    }
+}
+
+impl StorageBackend for Storage {
+    type ConfigBuilder = SledConfigBuilder;
+    type Config = SledConfig;
+    type Error = Error;
+
+    fn start(config: Self::Config) -> Result<Self, Self::Error> {
+        let storage = Self::open(config)?;
+
+        // The health is neither checked nor changed so that the database can be inspected whatever its state.
+        if storage.is_read_only() {
+            return Ok(storage);
+        }
+
         if let Some(health) = storage.get_health()? {
             if health != StorageHealth::Healthy {
                 return Err(Self::Error::UnhealthyStorage(health));
@@ -86,7 +129,15 @@ impl StorageBackend for Storage {
         Ok(storage)
     }
 
+    fn start_unchecked(config: Self::Config) -> Result<Self, Self::Error> {
+        Self::open(config)
+    }
+
     fn shutdown(self) -> Result<(), Self::Error> {
+        if self.is_read_only() {
+            return Ok(());
+        }
+
         self.set_health(StorageHealth::Healthy)?;
         self.inner.flush()?;
         Ok(())
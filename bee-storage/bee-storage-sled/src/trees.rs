@@ -32,6 +32,8 @@ pub const TREE_SNAPSHOT_INFO: &str = "snapshot_info";
 pub const TREE_SOLID_ENTRY_POINT_TO_MILESTONE_INDEX: &str = "solid_entry_point_to_milestone_index";
 /// Identifier for the `MilestoneIndex` to `OutputDiff` tree.
 pub const TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF: &str = "milestone_index_to_output_diff";
+/// Identifier for the `MilestoneIndex` to `ParametersMilestoneOption` tree.
+pub const TREE_MILESTONE_INDEX_TO_PROTOCOL_PARAMETERS: &str = "milestone_index_to_protocol_parameters";
 /// Identifier for the `MilestoneIndex` to `Vec<UnreferencedBlock>` tree.
 pub const TREE_MILESTONE_INDEX_TO_UNREFERENCED_BLOCK: &str = "milestone_index_to_unreferenced_block";
 /// Identifier for the `MilestoneIndex` to `Vec<Receipt>` tree.
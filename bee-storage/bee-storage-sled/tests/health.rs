@@ -0,0 +1,33 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_storage::{backend::StorageBackend, system::StorageHealth};
+use bee_storage_sled::{
+    config::SledConfigBuilder,
+    storage::{Error, Storage},
+};
+
+#[test]
+fn start_unchecked_opens_unhealthy_storage() {
+    let path = String::from("./tests/database/start_unchecked_opens_unhealthy_storage");
+    let _ = std::fs::remove_dir_all(&path);
+    let config = SledConfigBuilder::default().with_path(path.clone()).finish();
+
+    let storage = Storage::start(config.clone()).unwrap();
+    storage.set_health(StorageHealth::Corrupted).unwrap();
+    drop(storage);
+
+    assert!(matches!(
+        Storage::start(config.clone()),
+        Err(Error::UnhealthyStorage(StorageHealth::Corrupted))
+    ));
+
+    let storage = Storage::start_unchecked(config.clone()).unwrap();
+    assert_eq!(storage.get_health().unwrap(), Some(StorageHealth::Corrupted));
+    storage.shutdown().unwrap();
+
+    let storage = Storage::start(config).unwrap();
+    storage.shutdown().unwrap();
+
+    let _ = std::fs::remove_dir_all(&path);
+}
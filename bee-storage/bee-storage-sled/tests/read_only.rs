@@ -0,0 +1,108 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{Block, BlockId};
+use bee_storage::{
+    access::{Fetch, Insert},
+    backend::StorageBackend,
+    system::{StorageHealth, System, SYSTEM_HEALTH_KEY},
+};
+use bee_storage_sled::{
+    config::SledConfigBuilder,
+    storage::{Error, Storage},
+};
+
+#[test]
+fn read_only_rejects_writes() {
+    let path = String::from("./tests/database/read_only_rejects_writes");
+    let _ = std::fs::remove_dir_all(&path);
+
+    let storage = Storage::start(SledConfigBuilder::default().with_path(path.clone()).finish()).unwrap();
+    storage.shutdown().unwrap();
+
+    let storage = Storage::start(
+        SledConfigBuilder::default()
+            .with_path(path.clone())
+            .with_read_only(true)
+            .finish(),
+    )
+    .unwrap();
+
+    assert!(storage.is_read_only());
+    assert!(matches!(
+        Fetch::<u8, System>::fetch(&storage, &SYSTEM_HEALTH_KEY).unwrap(),
+        Some(System::Health(StorageHealth::Healthy))
+    ));
+    assert!(matches!(
+        Insert::<u8, System>::insert(&storage, &SYSTEM_HEALTH_KEY, &System::Health(StorageHealth::Corrupted)),
+        Err(Error::ReadOnly)
+    ));
+
+    storage.shutdown().unwrap();
+
+    let _ = std::fs::remove_dir_all(&path);
+}
+
+#[test]
+fn read_only_does_not_create_trees() {
+    let path = String::from("./tests/database/read_only_does_not_create_trees");
+    let _ = std::fs::remove_dir_all(&path);
+
+    let storage = Storage::start(SledConfigBuilder::default().with_path(path.clone()).finish()).unwrap();
+    storage.shutdown().unwrap();
+
+    let storage = Storage::start(
+        SledConfigBuilder::default()
+            .with_path(path.clone())
+            .with_read_only(true)
+            .finish(),
+    )
+    .unwrap();
+
+    let block_id = BlockId::null();
+
+    // The second fetch would succeed if the first one had created the tree.
+    for _ in 0..2 {
+        assert!(matches!(
+            Fetch::<_, Block>::fetch(&storage, &block_id),
+            Err(Error::MissingTree(_))
+        ));
+    }
+
+    storage.shutdown().unwrap();
+
+    let _ = std::fs::remove_dir_all(&path);
+}
+
+#[test]
+fn read_only_fails_on_locked_database() {
+    let path = String::from("./tests/database/read_only_fails_on_locked_database");
+    let _ = std::fs::remove_dir_all(&path);
+
+    let storage = Storage::start(SledConfigBuilder::default().with_path(path.clone()).finish()).unwrap();
+
+    // Sled holds an exclusive lock on the database until it is closed, even against read-only openings.
+    assert!(matches!(
+        Storage::start(
+            SledConfigBuilder::default()
+                .with_path(path.clone())
+                .with_read_only(true)
+                .finish(),
+        ),
+        Err(Error::Sled(_))
+    ));
+
+    storage.shutdown().unwrap();
+
+    let storage = Storage::start(
+        SledConfigBuilder::default()
+            .with_path(path.clone())
+            .with_read_only(true)
+            .finish(),
+    )
+    .unwrap();
+
+    storage.shutdown().unwrap();
+
+    let _ = std::fs::remove_dir_all(&path);
+}
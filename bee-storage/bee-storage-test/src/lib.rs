@@ -11,6 +11,7 @@ mod ledger_index;
 mod milestone_id_to_milestone_payload;
 mod milestone_index_to_milestone_metadata;
 mod milestone_index_to_output_diff;
+mod milestone_index_to_protocol_parameters;
 mod milestone_index_to_receipt;
 mod milestone_index_to_unreferenced_block;
 mod output_id_to_consumed_output;
@@ -27,6 +28,7 @@ pub use self::{
     milestone_id_to_milestone_payload::milestone_id_to_milestone_payload_access,
     milestone_index_to_milestone_metadata::milestone_index_to_milestone_metadata_access,
     milestone_index_to_output_diff::milestone_index_to_output_diff_access,
+    milestone_index_to_protocol_parameters::milestone_index_to_protocol_parameters_access,
     milestone_index_to_receipt::milestone_index_to_receipt_access,
     milestone_index_to_unreferenced_block::milestone_index_to_unreferenced_block_access,
     output_id_to_consumed_output::output_id_to_consumed_output_access,
@@ -0,0 +1,134 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{
+    payload::milestone::{MilestoneIndex, ParametersMilestoneOption},
+    rand::{milestone::rand_milestone_index, milestone_option::rand_parameters_milestone_option},
+};
+use bee_storage::{
+    access::{AsIterator, Batch, BatchBuilder, Delete, Exist, Fetch, Insert, MultiFetch, Truncate},
+    backend,
+};
+use packable::PackableExt;
+
+pub trait StorageBackend:
+    backend::StorageBackend
+    + Exist<MilestoneIndex, ParametersMilestoneOption>
+    + Fetch<MilestoneIndex, ParametersMilestoneOption>
+    + for<'a> MultiFetch<'a, MilestoneIndex, ParametersMilestoneOption>
+    + Insert<MilestoneIndex, ParametersMilestoneOption>
+    + Delete<MilestoneIndex, ParametersMilestoneOption>
+    + BatchBuilder
+    + Batch<MilestoneIndex, ParametersMilestoneOption>
+    + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
+    + Truncate<MilestoneIndex, ParametersMilestoneOption>
+{
+}
+
+impl<T> StorageBackend for T where
+    T: backend::StorageBackend
+        + Exist<MilestoneIndex, ParametersMilestoneOption>
+        + Fetch<MilestoneIndex, ParametersMilestoneOption>
+        + for<'a> MultiFetch<'a, MilestoneIndex, ParametersMilestoneOption>
+        + Insert<MilestoneIndex, ParametersMilestoneOption>
+        + Delete<MilestoneIndex, ParametersMilestoneOption>
+        + BatchBuilder
+        + Batch<MilestoneIndex, ParametersMilestoneOption>
+        + for<'a> AsIterator<'a, MilestoneIndex, ParametersMilestoneOption>
+        + Truncate<MilestoneIndex, ParametersMilestoneOption>
+{
+}
+
+pub fn milestone_index_to_protocol_parameters_access<B: StorageBackend>(storage: &B) {
+    let (index, option) = (rand_milestone_index(), rand_parameters_milestone_option());
+
+    assert!(!Exist::<MilestoneIndex, ParametersMilestoneOption>::exist(storage, &index).unwrap());
+    assert!(
+        Fetch::<MilestoneIndex, ParametersMilestoneOption>::fetch(storage, &index)
+            .unwrap()
+            .is_none()
+    );
+    let results = MultiFetch::<MilestoneIndex, ParametersMilestoneOption>::multi_fetch(storage, &[index])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    Insert::<MilestoneIndex, ParametersMilestoneOption>::insert(storage, &index, &option).unwrap();
+
+    assert!(Exist::<MilestoneIndex, ParametersMilestoneOption>::exist(storage, &index).unwrap());
+    assert_eq!(
+        Fetch::<MilestoneIndex, ParametersMilestoneOption>::fetch(storage, &index)
+            .unwrap()
+            .unwrap()
+            .pack_to_vec(),
+        option.pack_to_vec()
+    );
+    let results = MultiFetch::<MilestoneIndex, ParametersMilestoneOption>::multi_fetch(storage, &[index])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(Some(v))) if v == &option));
+
+    Delete::<MilestoneIndex, ParametersMilestoneOption>::delete(storage, &index).unwrap();
+
+    assert!(!Exist::<MilestoneIndex, ParametersMilestoneOption>::exist(storage, &index).unwrap());
+    assert!(
+        Fetch::<MilestoneIndex, ParametersMilestoneOption>::fetch(storage, &index)
+            .unwrap()
+            .is_none()
+    );
+    let results = MultiFetch::<MilestoneIndex, ParametersMilestoneOption>::multi_fetch(storage, &[index])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    let mut batch = B::batch_begin();
+    let mut indexes = Vec::new();
+    let mut options = Vec::new();
+
+    for _ in 0..10 {
+        let (index, option) = (rand_milestone_index(), rand_parameters_milestone_option());
+        Insert::<MilestoneIndex, ParametersMilestoneOption>::insert(storage, &index, &option).unwrap();
+        Batch::<MilestoneIndex, ParametersMilestoneOption>::batch_delete(storage, &mut batch, &index).unwrap();
+        indexes.push(index);
+        options.push((index, None));
+    }
+
+    for _ in 0..10 {
+        let (index, option) = (rand_milestone_index(), rand_parameters_milestone_option());
+        Batch::<MilestoneIndex, ParametersMilestoneOption>::batch_insert(storage, &mut batch, &index, &option).unwrap();
+        indexes.push(index);
+        options.push((index, Some(option)));
+    }
+
+    storage.batch_commit(batch, true).unwrap();
+
+    let iter = AsIterator::<MilestoneIndex, ParametersMilestoneOption>::iter(storage).unwrap();
+    let mut count = 0;
+
+    for result in iter {
+        let (index, option) = result.unwrap();
+        assert!(options.contains(&(index, Some(option))));
+        count += 1;
+    }
+
+    assert_eq!(count, 10);
+
+    let results = MultiFetch::<MilestoneIndex, ParametersMilestoneOption>::multi_fetch(storage, &indexes)
+        .unwrap()
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.len(), indexes.len());
+
+    for ((_, option), result) in options.into_iter().zip(results.into_iter()) {
+        assert_eq!(option, result.unwrap());
+    }
+
+    Truncate::<MilestoneIndex, ParametersMilestoneOption>::truncate(storage).unwrap();
+
+    let mut iter = AsIterator::<MilestoneIndex, ParametersMilestoneOption>::iter(storage).unwrap();
+
+    assert!(iter.next().is_none());
+}
@@ -21,6 +21,13 @@ pub trait StorageBackend: Send + Sized + Sync + 'static {
     /// Initializes and starts the backend.
     fn start(config: Self::Config) -> Result<Self, Self::Error>;
 
+    /// Initializes and starts the backend without rejecting an unhealthy database nor changing its health status, so
+    /// that it can be inspected or repaired.
+    /// Backends that don't check their health status when starting can rely on the default implementation.
+    fn start_unchecked(config: Self::Config) -> Result<Self, Self::Error> {
+        Self::start(config)
+    }
+
     /// Shutdowns the backend.
     fn shutdown(self) -> Result<(), Self::Error>;
 
@@ -35,4 +42,10 @@ pub trait StorageBackend: Send + Sized + Sync + 'static {
     /// Sets the health status of the database.
     /// Not all backends may be able to provide this operation.
     fn set_health(&self, health: StorageHealth) -> Result<(), Self::Error>;
+
+    /// Returns the internal statistics of the backend in a human readable form.
+    /// Not all backends may be able to provide this operation.
+    fn statistics(&self) -> Result<Option<String>, Self::Error> {
+        Ok(None)
+    }
 }
@@ -0,0 +1,114 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bee_block::BlockId;
+use lru::LruCache;
+use parking_lot::{Mutex, MutexGuard};
+
+/// Hit and miss statistics of a single cache.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    /// Number of lookups that were served by the cache.
+    pub hits: u64,
+    /// Number of lookups that had to query the storage.
+    pub misses: u64,
+    /// Number of entries currently in the cache.
+    pub len: usize,
+    /// Maximum number of entries of the cache.
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the ratio of lookups that were served by the cache.
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Hit and miss statistics of the tangle caches, accumulated since the tangle was created.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheMetrics {
+    /// Statistics of the block cache.
+    pub blocks: CacheStats,
+    /// Statistics of the block metadata cache.
+    pub metadata: CacheStats,
+    /// Statistics of the block children cache.
+    pub children: CacheStats,
+}
+
+/// Locks serializing the storage accesses concerning the same block, spread over a fixed number of mutexes.
+pub(crate) struct BlockLocks(Box<[Mutex<()>]>);
+
+impl BlockLocks {
+    pub(crate) fn new() -> Self {
+        Self((0..=u8::MAX).map(|_| Mutex::new(())).collect())
+    }
+
+    pub(crate) fn lock(&self, block_id: &BlockId) -> MutexGuard<'_, ()> {
+        // Block IDs are hashes, so their first byte spreads them evenly over the mutexes.
+        self.0[block_id[0] as usize].lock()
+    }
+}
+
+/// A bounded LRU cache keyed by block IDs that counts its hits and misses.
+pub(crate) struct Cache<V> {
+    entries: Mutex<LruCache<BlockId, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<V> Cache<V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, LruCache<BlockId, V>> {
+        self.entries.lock()
+    }
+
+    pub(crate) fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        let entries = self.entries.lock();
+
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: entries.len(),
+            capacity: entries.cap(),
+        }
+    }
+}
+
+impl<V: Clone> Cache<V> {
+    /// Returns a copy of the cached value, counting the lookup as a hit or a miss.
+    pub(crate) fn get(&self, block_id: &BlockId) -> Option<V> {
+        let value = self.entries.lock().get(block_id).cloned();
+
+        if value.is_some() {
+            self.hit();
+        } else {
+            self.miss();
+        }
+
+        value
+    }
+}
@@ -4,6 +4,179 @@
 use serde::Deserialize;
 
 const DEFAULT_BELOW_MAX_DEPTH: u32 = 15;
+const DEFAULT_CACHE_SIZE: usize = 50_000;
+const DEFAULT_TIP_SELECTION_STRATEGY: TipSelectionStrategy = TipSelectionStrategy::Urts;
+const DEFAULT_YMRSI_DELTA: u32 = 8;
+const DEFAULT_OMRSI_DELTA: u32 = 13;
+const DEFAULT_MAX_LIMIT_NON_LAZY: usize = 100;
+const DEFAULT_MAX_LIMIT_SEMI_LAZY: usize = 20;
+const DEFAULT_MAX_AGE_SECONDS_AFTER_FIRST_CHILD: u64 = 3;
+const DEFAULT_MAX_NUM_CHILDREN: usize = 2;
+const DEFAULT_NUM_TIPS: usize = 4;
+
+/// The strategy used to select tips from the tip pool.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum TipSelectionStrategy {
+    /// Uniform random tip selection among non-lazy tips.
+    #[serde(alias = "urts")]
+    Urts,
+    /// Uniform random tip selection that favours semi-lazy tips, in order to promote them.
+    #[serde(alias = "promotion")]
+    Promotion,
+}
+
+/// A builder type for a tip selection configuration.
+#[derive(Default, Deserialize, Eq, PartialEq)]
+#[must_use]
+pub struct TipSelectionConfigBuilder {
+    strategy: Option<TipSelectionStrategy>,
+    #[serde(alias = "ymrsiDelta")]
+    ymrsi_delta: Option<u32>,
+    #[serde(alias = "omrsiDelta")]
+    omrsi_delta: Option<u32>,
+    #[serde(alias = "maxLimitNonLazy")]
+    max_limit_non_lazy: Option<usize>,
+    #[serde(alias = "maxLimitSemiLazy")]
+    max_limit_semi_lazy: Option<usize>,
+    #[serde(alias = "maxAgeSecondsAfterFirstChild")]
+    max_age_seconds_after_first_child: Option<u64>,
+    #[serde(alias = "maxNumChildren")]
+    max_num_children: Option<usize>,
+    #[serde(alias = "numTips")]
+    num_tips: Option<usize>,
+}
+
+impl TipSelectionConfigBuilder {
+    /// Create a new [`TipSelectionConfigBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the strategy used to select tips.
+    pub fn with_strategy(mut self, strategy: TipSelectionStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Set the maximum delta between the YMRSI of a tip and the solid milestone index before the tip gets lazy.
+    pub fn with_ymrsi_delta(mut self, ymrsi_delta: u32) -> Self {
+        self.ymrsi_delta = Some(ymrsi_delta);
+        self
+    }
+
+    /// Set the maximum delta between the OMRSI of a tip and the solid milestone index before the tip gets semi-lazy.
+    pub fn with_omrsi_delta(mut self, omrsi_delta: u32) -> Self {
+        self.omrsi_delta = Some(omrsi_delta);
+        self
+    }
+
+    /// Set the number of non-lazy tips above which the parents of newly inserted tips are removed from the pool.
+    pub fn with_max_limit_non_lazy(mut self, max_limit_non_lazy: usize) -> Self {
+        self.max_limit_non_lazy = Some(max_limit_non_lazy);
+        self
+    }
+
+    /// Set the maximum number of semi-lazy tips kept for promotion.
+    pub fn with_max_limit_semi_lazy(mut self, max_limit_semi_lazy: usize) -> Self {
+        self.max_limit_semi_lazy = Some(max_limit_semi_lazy);
+        self
+    }
+
+    /// Set the maximum time, in seconds, a tip remains in the pool after having its first child.
+    pub fn with_max_age_seconds_after_first_child(mut self, max_age_seconds_after_first_child: u64) -> Self {
+        self.max_age_seconds_after_first_child = Some(max_age_seconds_after_first_child);
+        self
+    }
+
+    /// Set the maximum number of children a tip can have before being removed from the pool.
+    pub fn with_max_num_children(mut self, max_num_children: usize) -> Self {
+        self.max_num_children = Some(max_num_children);
+        self
+    }
+
+    /// Set the number of tips to select.
+    pub fn with_num_tips(mut self, num_tips: usize) -> Self {
+        self.num_tips = Some(num_tips);
+        self
+    }
+
+    /// Finish building tip selection configuration, to create a [`TipSelectionConfig`].
+    #[must_use]
+    pub fn finish(self) -> TipSelectionConfig {
+        TipSelectionConfig {
+            strategy: self.strategy.unwrap_or(DEFAULT_TIP_SELECTION_STRATEGY),
+            ymrsi_delta: self.ymrsi_delta.unwrap_or(DEFAULT_YMRSI_DELTA),
+            omrsi_delta: self.omrsi_delta.unwrap_or(DEFAULT_OMRSI_DELTA),
+            max_limit_non_lazy: self.max_limit_non_lazy.unwrap_or(DEFAULT_MAX_LIMIT_NON_LAZY),
+            max_limit_semi_lazy: self.max_limit_semi_lazy.unwrap_or(DEFAULT_MAX_LIMIT_SEMI_LAZY),
+            max_age_seconds_after_first_child: self
+                .max_age_seconds_after_first_child
+                .unwrap_or(DEFAULT_MAX_AGE_SECONDS_AFTER_FIRST_CHILD),
+            max_num_children: self.max_num_children.unwrap_or(DEFAULT_MAX_NUM_CHILDREN),
+            num_tips: self.num_tips.unwrap_or(DEFAULT_NUM_TIPS),
+        }
+    }
+}
+
+/// The configuration state of the tip selection.
+#[derive(Clone, Debug)]
+pub struct TipSelectionConfig {
+    strategy: TipSelectionStrategy,
+    ymrsi_delta: u32,
+    omrsi_delta: u32,
+    max_limit_non_lazy: usize,
+    max_limit_semi_lazy: usize,
+    max_age_seconds_after_first_child: u64,
+    max_num_children: usize,
+    num_tips: usize,
+}
+
+impl TipSelectionConfig {
+    /// Begin building a new [`TipSelectionConfig`].
+    pub fn build() -> TipSelectionConfigBuilder {
+        TipSelectionConfigBuilder::new()
+    }
+
+    /// Get the value of `strategy`.
+    pub fn strategy(&self) -> TipSelectionStrategy {
+        self.strategy
+    }
+
+    /// Get the value of `ymrsi_delta`.
+    pub fn ymrsi_delta(&self) -> u32 {
+        self.ymrsi_delta
+    }
+
+    /// Get the value of `omrsi_delta`.
+    pub fn omrsi_delta(&self) -> u32 {
+        self.omrsi_delta
+    }
+
+    /// Get the value of `max_limit_non_lazy`.
+    pub fn max_limit_non_lazy(&self) -> usize {
+        self.max_limit_non_lazy
+    }
+
+    /// Get the value of `max_limit_semi_lazy`.
+    pub fn max_limit_semi_lazy(&self) -> usize {
+        self.max_limit_semi_lazy
+    }
+
+    /// Get the value of `max_age_seconds_after_first_child`.
+    pub fn max_age_seconds_after_first_child(&self) -> u64 {
+        self.max_age_seconds_after_first_child
+    }
+
+    /// Get the value of `max_num_children`.
+    pub fn max_num_children(&self) -> usize {
+        self.max_num_children
+    }
+
+    /// Get the value of `num_tips`.
+    pub fn num_tips(&self) -> usize {
+        self.num_tips
+    }
+}
 
 /// A builder type for a tangle configuration.
 #[derive(Default, Deserialize, Eq, PartialEq)]
@@ -11,6 +184,10 @@ const DEFAULT_BELOW_MAX_DEPTH: u32 = 15;
 pub struct TangleConfigBuilder {
     #[serde(alias = "belowMaxDepth")]
     below_max_depth: Option<u32>,
+    #[serde(alias = "cacheSize")]
+    cache_size: Option<usize>,
+    #[serde(alias = "tipSelection", default)]
+    tip_selection: TipSelectionConfigBuilder,
 }
 
 impl TangleConfigBuilder {
@@ -19,11 +196,26 @@ impl TangleConfigBuilder {
         Self::default()
     }
 
+    /// Set the number of entries kept in each of the block, metadata and children caches. A size of 0 disables
+    /// caching.
+    pub fn with_cache_size(mut self, cache_size: usize) -> Self {
+        self.cache_size = Some(cache_size);
+        self
+    }
+
+    /// Set the tip selection configuration.
+    pub fn with_tip_selection(mut self, tip_selection: TipSelectionConfigBuilder) -> Self {
+        self.tip_selection = tip_selection;
+        self
+    }
+
     /// Finish building tangle configuration, to create a [`TangleConfig`].
     #[must_use]
     pub fn finish(self) -> TangleConfig {
         TangleConfig {
             below_max_depth: self.below_max_depth.unwrap_or(DEFAULT_BELOW_MAX_DEPTH),
+            cache_size: self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE),
+            tip_selection: self.tip_selection.finish(),
         }
     }
 }
@@ -32,6 +224,8 @@ impl TangleConfigBuilder {
 #[derive(Clone)]
 pub struct TangleConfig {
     below_max_depth: u32,
+    cache_size: usize,
+    tip_selection: TipSelectionConfig,
 }
 
 impl TangleConfig {
@@ -44,4 +238,14 @@ impl TangleConfig {
     pub fn below_max_depth(&self) -> u32 {
         self.below_max_depth
     }
+
+    /// Get the value of `cache_size`.
+    pub fn cache_size(&self) -> usize {
+        self.cache_size
+    }
+
+    /// Get the tip selection configuration.
+    pub fn tip_selection(&self) -> &TipSelectionConfig {
+        &self.tip_selection
+    }
 }
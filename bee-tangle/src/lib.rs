@@ -8,6 +8,8 @@
 
 /// Block metadata, including block flags.
 pub mod block_metadata;
+/// Caches of blocks, metadata and children.
+pub mod cache;
 /// Types used for tangle configuration.
 pub mod config;
 /// Types that represent tangle events.
@@ -16,6 +18,8 @@ pub mod event;
 pub mod flags;
 /// Milestone metadata.
 pub mod milestone_metadata;
+/// The promotion tips pool, favouring semi-lazy tips.
+pub mod promotion;
 /// A module that provides utilities for random generation of types.
 #[cfg(feature = "rand")]
 pub mod rand;
@@ -29,6 +33,8 @@ pub mod tangle;
 pub mod tangle_worker;
 /// A worker that periodically cleans the tip pool.
 pub mod tip_pool_cleaner_worker;
+/// The tip selection strategy abstraction.
+pub mod tip_selection;
 /// Common tangle traversal functionality.
 pub mod traversal;
 /// Types used to represent unreferenced blocks.
@@ -0,0 +1,110 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use bee_block::BlockId;
+use hashbrown::HashSet;
+use rand::seq::IteratorRandom;
+
+use crate::{
+    config::TangleConfig,
+    storage::StorageBackend,
+    tangle::Tangle,
+    tip_selection::TipSelector,
+    urts::{Score, UrtsTipPool},
+};
+
+/// A tip pool that retains semi-lazy tips on top of the URTS non-lazy ones and selects them first, so that new blocks
+/// promote them before they become lazy.
+pub struct PromotionTipPool {
+    urts: UrtsTipPool,
+    semi_lazy_tips: HashSet<BlockId>,
+    max_limit_semi_lazy: usize,
+}
+
+impl PromotionTipPool {
+    /// Creates a new `PromotionTipPool` with the thresholds of the given configuration.
+    pub fn new(config: &TangleConfig) -> Self {
+        Self {
+            urts: UrtsTipPool::new(config),
+            semi_lazy_tips: HashSet::default(),
+            max_limit_semi_lazy: config.tip_selection().max_limit_semi_lazy(),
+        }
+    }
+
+    /// Returns the semi-lazy tips of the pool.
+    pub fn semi_lazy_tips(&self) -> &HashSet<BlockId> {
+        &self.semi_lazy_tips
+    }
+
+    fn insert_semi_lazy(&mut self, block_id: BlockId) {
+        if self.semi_lazy_tips.len() < self.max_limit_semi_lazy {
+            self.semi_lazy_tips.insert(block_id);
+        }
+    }
+}
+
+#[async_trait]
+impl<B: StorageBackend> TipSelector<B> for PromotionTipPool {
+    async fn insert(&mut self, tangle: &Tangle<B>, block_id: BlockId, parents: Vec<BlockId>) {
+        // Semi-lazy tips that got approved have been promoted and don't need to be selected anymore.
+        for parent in &parents {
+            self.semi_lazy_tips.remove(parent);
+        }
+
+        match self.urts.tip_score::<B>(tangle, &block_id).await {
+            Score::NonLazy => self.urts.insert_non_lazy(block_id, &parents),
+            Score::SemiLazy => self.insert_semi_lazy(block_id),
+            Score::Lazy => {}
+        }
+    }
+
+    async fn update_scores(&mut self, tangle: &Tangle<B>) {
+        let mut to_remove = Vec::new();
+
+        for tip in &self.semi_lazy_tips {
+            if let Score::Lazy = self.urts.tip_score::<B>(tangle, tip).await {
+                to_remove.push(*tip);
+            }
+        }
+
+        for tip in to_remove {
+            self.semi_lazy_tips.remove(&tip);
+        }
+
+        for tip in self.urts.remove_lazy_tips(tangle).await {
+            self.insert_semi_lazy(tip);
+        }
+    }
+
+    fn select_tips(&self) -> Option<Vec<BlockId>> {
+        let num_tips = self.urts.num_tips();
+        // Always leave room for at least one non-lazy tip.
+        let num_semi_lazy_tips = num_tips.saturating_sub(1);
+
+        let mut tips = if self.semi_lazy_tips.len() <= num_semi_lazy_tips {
+            self.semi_lazy_tips.iter().copied().collect::<Vec<_>>()
+        } else {
+            self.semi_lazy_tips
+                .iter()
+                .copied()
+                .choose_multiple(&mut rand::thread_rng(), num_semi_lazy_tips)
+        };
+
+        tips.extend(self.urts.choose_non_lazy_tips(num_tips - tips.len()));
+
+        if tips.is_empty() {
+            None
+        } else {
+            Some(tips)
+        }
+    }
+
+    fn reduce_tips(&mut self) {
+        TipSelector::<B>::reduce_tips(&mut self.urts);
+    }
+
+    fn non_lazy_tips_num(&self) -> usize {
+        self.urts.non_lazy_tips().len()
+    }
+}
@@ -9,17 +9,18 @@ use bee_block::{
 };
 use bee_runtime::resource::ResourceHandle;
 use hashbrown::HashMap;
-use log::warn;
+use log::{info, warn};
 use ref_cast::RefCast;
 use tokio::sync::Mutex;
 
 use crate::{
     block_metadata::{BlockMetadata, IndexId},
+    cache::{BlockLocks, Cache, CacheMetrics},
     config::TangleConfig,
     milestone_metadata::MilestoneMetadata,
     solid_entry_point::SolidEntryPoint,
     storage::StorageBackend,
-    urts::UrtsTipPool,
+    tip_selection::{tip_selector, TipSelector},
 };
 
 const SYNCED_THRESHOLD: u32 = 2;
@@ -36,7 +37,12 @@ pub struct Tangle<B> {
     snapshot_index: AtomicU32,
     pruning_index: AtomicU32,
     entry_point_index: AtomicU32,
-    tip_pool: Mutex<UrtsTipPool>,
+    tip_pool: Mutex<Box<dyn TipSelector<B>>>,
+    blocks: Cache<Block>,
+    metadata: Cache<BlockMetadata>,
+    metadata_locks: BlockLocks,
+    children: Cache<Vec<BlockId>>,
+    children_locks: BlockLocks,
 }
 
 impl<B: StorageBackend> Tangle<B> {
@@ -51,14 +57,56 @@ impl<B: StorageBackend> Tangle<B> {
             snapshot_index: Default::default(),
             pruning_index: Default::default(),
             entry_point_index: Default::default(),
-            tip_pool: Mutex::new(UrtsTipPool::new(&config)),
+            tip_pool: Mutex::new(tip_selector(&config)),
+            blocks: Cache::new(config.cache_size()),
+            metadata: Cache::new(config.cache_size()),
+            metadata_locks: BlockLocks::new(),
+            children: Cache::new(config.cache_size()),
+            children_locks: BlockLocks::new(),
             config,
         }
     }
 
-    /// Shut down the tangle, terminating any and all worker tasks.
+    /// Shut down the tangle, dropping its caches.
+    ///
+    /// All changes are written through to the storage, so there is nothing left to write back.
     pub async fn shutdown(self) {
-        // TODO: Write back changes by calling self.inner.shutdown().await
+        let metrics = self.cache_metrics();
+
+        info!(
+            "Cache hit ratios: blocks {:.2}, metadata {:.2}, children {:.2}.",
+            metrics.blocks.hit_ratio(),
+            metrics.metadata.hit_ratio(),
+            metrics.children.hit_ratio()
+        );
+
+        self.blocks.lock().clear();
+        self.metadata.lock().clear();
+        self.children.lock().clear();
+    }
+
+    /// Drop the cached data of the given blocks.
+    /// This must be called for blocks that are removed from, or whose children or metadata are changed in, the storage
+    /// by other means than the tangle.
+    pub fn invalidate<'a>(&self, block_ids: impl IntoIterator<Item = &'a BlockId>) {
+        for block_id in block_ids {
+            self.blocks.lock().pop(block_id);
+
+            let _children_guard = self.children_locks.lock(block_id);
+            self.children.lock().pop(block_id);
+
+            let _metadata_guard = self.metadata_locks.lock(block_id);
+            self.metadata.lock().pop(block_id);
+        }
+    }
+
+    /// Get the hit and miss statistics of the block, metadata and children caches.
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            blocks: self.blocks.stats(),
+            metadata: self.metadata.stats(),
+            children: self.children.stats(),
+        }
     }
 
     /// Get the configuration of this tangle.
@@ -72,13 +120,26 @@ impl<B: StorageBackend> Tangle<B> {
             .insert(block_id, block)
             .ok()
             .and_then(|()| {
+                self.blocks.lock().put(*block_id, block.clone());
+
                 self.storage.insert_strict(block_id, metadata).ok()?;
 
                 let block_id = *block_id;
                 for &parent in block.parents().iter() {
-                    self.storage
-                        .insert(&(parent, block_id), &())
-                        .unwrap_or_else(|e| warn!("Failed to update approvers for block {:?}", e));
+                    // The parent lock is held while writing so that a concurrent lookup can't cache children that are
+                    // missing this block.
+                    let _guard = self.children_locks.lock(&parent);
+
+                    match self.storage.insert(&(parent, block_id), &()) {
+                        Ok(()) => {
+                            if let Some(children) = self.children.lock().peek_mut(&parent) {
+                                if !children.contains(&block_id) {
+                                    children.push(block_id);
+                                }
+                            }
+                        }
+                        Err(e) => warn!("Failed to update approvers for block {:?}", e),
+                    }
                 }
                 Some(())
             })
@@ -304,6 +365,11 @@ impl<B: StorageBackend> Tangle<B> {
         }
     }
 
+    /// Replace the tip selector of this tangle, dropping the tips of the current one.
+    pub async fn set_tip_selector(&self, tip_selector: impl TipSelector<B> + 'static) {
+        *self.tip_pool.lock().await = Box::new(tip_selector);
+    }
+
     /// Insert the given block ID and parents as a tip.
     pub async fn insert_tip(&self, block_id: BlockId, parents: Vec<BlockId>) {
         self.tip_pool.lock().await.insert(self, block_id, parents).await;
@@ -316,7 +382,7 @@ impl<B: StorageBackend> Tangle<B> {
 
     /// Return blocks that require approving.
     pub async fn get_blocks_to_approve(&self) -> Option<Vec<BlockId>> {
-        self.tip_pool.lock().await.choose_non_lazy_tips()
+        self.tip_pool.lock().await.select_tips()
     }
 
     /// Reduce tips.
@@ -326,49 +392,106 @@ impl<B: StorageBackend> Tangle<B> {
 
     /// Return the number of non-lazy tips.
     pub async fn non_lazy_tips_num(&self) -> usize {
-        self.tip_pool.lock().await.non_lazy_tips().len()
+        self.tip_pool.lock().await.non_lazy_tips_num()
     }
 
     /// Get the data of a vertex associated with the given `block_id`.
     pub fn get(&self, block_id: &BlockId) -> Option<Block> {
-        self.storage.fetch(block_id).unwrap_or_default()
+        if let Some(block) = self.blocks.get(block_id) {
+            return Some(block);
+        }
+
+        // Blocks are immutable, so the storage can be queried without holding the lock.
+        let block: Block = self.storage.fetch(block_id).unwrap_or_default()?;
+        self.blocks.lock().put(*block_id, block.clone());
+
+        Some(block)
     }
 
     /// Get the data and metadata of a vertex associated with the given `block_id`.
     pub fn get_block_and_metadata(&self, block_id: &BlockId) -> Option<(Block, BlockMetadata)> {
-        let block = self.storage.fetch(block_id).unwrap_or_default()?;
-        let metadata = self.storage.fetch(block_id).unwrap_or_default()?;
+        let block = self.get(block_id)?;
+        let metadata = self.get_metadata(block_id)?;
 
         Some((block, metadata))
     }
 
     /// Returns whether the block is stored in the Tangle.
     pub fn contains(&self, block_id: &BlockId) -> bool {
-        self.storage.exist(block_id).unwrap_or_default()
+        if self.blocks.lock().contains(block_id) {
+            self.blocks.hit();
+            true
+        } else {
+            self.blocks.miss();
+            self.storage.exist(block_id).unwrap_or_default()
+        }
     }
 
     /// Get the metadata of a vertex associated with the given `block_id`.
     pub fn get_metadata(&self, block_id: &BlockId) -> Option<BlockMetadata> {
-        self.storage.fetch(block_id).unwrap_or_default()
+        if let Some(metadata) = self.metadata.get(block_id) {
+            return Some(metadata);
+        }
+
+        // The block lock is held while querying the storage so that a concurrent update can't be overwritten by a
+        // stale value in the cache.
+        let _guard = self.metadata_locks.lock(block_id);
+
+        if let Some(metadata) = self.metadata.lock().peek(block_id) {
+            return Some(*metadata);
+        }
+
+        let fetched: BlockMetadata = self.storage.fetch(block_id).unwrap_or_default()?;
+        self.metadata.lock().put(*block_id, fetched);
+
+        Some(fetched)
     }
 
     /// Updates the metadata of a vertex.
+    ///
+    /// The change is written to the storage before the cache is updated.
     pub fn update_metadata<R>(
         &self,
         block_id: &BlockId,
         update: impl FnOnce(&mut BlockMetadata) -> R + Copy,
     ) -> Option<R> {
+        let _guard = self.metadata_locks.lock(block_id);
+
         let mut output = None;
+        let mut updated = None;
+
+        if let Err(e) = self.storage.update(block_id, |metadata| {
+            output = Some(update(metadata));
+            updated = Some(*metadata);
+        }) {
+            warn!("Failed to update metadata for block {}: {:?}", block_id, e);
+            self.metadata.lock().pop(block_id);
+            return None;
+        }
 
-        self.storage
-            .update(block_id, |metadata| output = Some(update(metadata)))
-            .unwrap_or_default();
+        if let Some(metadata) = updated {
+            self.metadata.lock().put(*block_id, metadata);
+        }
 
         output
     }
 
     /// Returns the children of a vertex, if we know about them.
     pub fn get_children(&self, block_id: &BlockId) -> Option<Vec<BlockId>> {
-        self.storage.fetch(block_id).unwrap_or_default()
+        if let Some(children) = self.children.get(block_id) {
+            return Some(children);
+        }
+
+        // The block lock is held while querying the storage so that a concurrently inserted child can't be missed.
+        let _guard = self.children_locks.lock(block_id);
+
+        if let Some(children) = self.children.lock().peek(block_id) {
+            return Some(children.clone());
+        }
+
+        let fetched: Vec<BlockId> = self.storage.fetch(block_id).unwrap_or_default()?;
+        self.children.lock().put(*block_id, fetched.clone());
+
+        Some(fetched)
     }
 }
@@ -0,0 +1,40 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use bee_block::BlockId;
+
+use crate::{
+    config::{TangleConfig, TipSelectionStrategy},
+    promotion::PromotionTipPool,
+    storage::StorageBackend,
+    tangle::Tangle,
+    urts::UrtsTipPool,
+};
+
+/// A strategy maintaining a pool of tips and selecting the parents of new blocks from it.
+#[async_trait]
+pub trait TipSelector<B: StorageBackend>: Send + Sync {
+    /// Inserts the given block ID and parents as a tip.
+    async fn insert(&mut self, tangle: &Tangle<B>, block_id: BlockId, parents: Vec<BlockId>);
+
+    /// Updates the scores of the tips, removing the ones that can't be selected anymore.
+    async fn update_scores(&mut self, tangle: &Tangle<B>);
+
+    /// Selects tips to be approved by a new block, if any.
+    fn select_tips(&self) -> Option<Vec<BlockId>>;
+
+    /// Removes the tips that have been retained for too long.
+    fn reduce_tips(&mut self);
+
+    /// Returns the number of non-lazy tips.
+    fn non_lazy_tips_num(&self) -> usize;
+}
+
+/// Creates the tip selector of the strategy set in the given configuration.
+pub fn tip_selector<B: StorageBackend>(config: &TangleConfig) -> Box<dyn TipSelector<B>> {
+    match config.tip_selection().strategy() {
+        TipSelectionStrategy::Urts => Box::new(UrtsTipPool::new(config)),
+        TipSelectionStrategy::Promotion => Box::new(PromotionTipPool::new(config)),
+    }
+}
@@ -3,36 +3,21 @@
 
 use std::time::Instant;
 
+use async_trait::async_trait;
 use bee_block::BlockId;
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use log::debug;
 use rand::seq::IteratorRandom;
 
-use crate::{config::TangleConfig, storage::StorageBackend, tangle::Tangle};
+use crate::{config::TangleConfig, storage::StorageBackend, tangle::Tangle, tip_selection::TipSelector};
 
 #[allow(clippy::enum_variant_names)]
-enum Score {
+pub(crate) enum Score {
     NonLazy,
     SemiLazy,
     Lazy,
 }
 
-// C1: the maximum allowed delta value for the YMRSI of a given block in relation to the current SMI before it
-// gets lazy.
-const YMRSI_DELTA: u32 = 8;
-// C2: the maximum allowed delta value between OMRSI of a given block in relation to the current SMI before it
-// gets semi-lazy.
-const OMRSI_DELTA: u32 = 13;
-// If the amount of non-lazy tips exceed this limit, remove the parent(s) of the inserted tip to compensate for the
-// excess. This rule helps to reduce the amount of tips in the network.
-const MAX_LIMIT_NON_LAZY: u8 = 100;
-// The maximum time a tip remains in the tip pool after having the first child.
-// This rule helps to widen the tangle.
-const MAX_AGE_SECONDS_AFTER_FIRST_CHILD: u8 = 3;
-// The maximum amount of children a tip is allowed to have before the tip is removed from the tip pool. This rule is
-// used to widen the cone of the tangle.
-const MAX_NUM_CHILDREN: u8 = 2;
-
 #[derive(Default)]
 struct TipMetadata {
     children: HashSet<BlockId>,
@@ -45,38 +30,58 @@ impl TipMetadata {
     }
 }
 
-pub(crate) struct UrtsTipPool {
+/// A tip pool implementing the Uniform Random Tip Selection, selecting tips uniformly among the non-lazy ones.
+pub struct UrtsTipPool {
     tips: HashMap<BlockId, TipMetadata>,
     non_lazy_tips: HashSet<BlockId>,
     below_max_depth: u32,
+    // C1: the maximum allowed delta value for the YMRSI of a given block in relation to the current SMI before it
+    // gets lazy.
+    ymrsi_delta: u32,
+    // C2: the maximum allowed delta value between OMRSI of a given block in relation to the current SMI before it
+    // gets semi-lazy.
+    omrsi_delta: u32,
+    // If the amount of non-lazy tips exceed this limit, remove the parent(s) of the inserted tip to compensate for the
+    // excess. This rule helps to reduce the amount of tips in the network.
+    max_limit_non_lazy: usize,
+    // The maximum time a tip remains in the tip pool after having the first child.
+    // This rule helps to widen the tangle.
+    max_age_seconds_after_first_child: u64,
+    // The maximum amount of children a tip is allowed to have before the tip is removed from the tip pool. This rule
+    // is used to widen the cone of the tangle.
+    max_num_children: usize,
+    num_tips: usize,
 }
 
 impl UrtsTipPool {
-    pub(crate) fn new(config: &TangleConfig) -> Self {
+    /// Creates a new `UrtsTipPool` with the thresholds of the given configuration.
+    pub fn new(config: &TangleConfig) -> Self {
+        let tip_selection = config.tip_selection();
+
         Self {
             tips: HashMap::default(),
             non_lazy_tips: HashSet::default(),
             below_max_depth: config.below_max_depth(),
+            ymrsi_delta: tip_selection.ymrsi_delta(),
+            omrsi_delta: tip_selection.omrsi_delta(),
+            max_limit_non_lazy: tip_selection.max_limit_non_lazy(),
+            max_age_seconds_after_first_child: tip_selection.max_age_seconds_after_first_child(),
+            max_num_children: tip_selection.max_num_children(),
+            num_tips: tip_selection.num_tips(),
         }
     }
 
-    pub(crate) fn non_lazy_tips(&self) -> &HashSet<BlockId> {
+    /// Returns the non-lazy tips of the pool.
+    pub fn non_lazy_tips(&self) -> &HashSet<BlockId> {
         &self.non_lazy_tips
     }
 
-    pub(crate) async fn insert<B: StorageBackend>(
-        &mut self,
-        tangle: &Tangle<B>,
-        block_id: BlockId,
-        parents: Vec<BlockId>,
-    ) {
-        if let Score::NonLazy = self.tip_score::<B>(tangle, &block_id).await {
-            self.non_lazy_tips.insert(block_id);
-            self.tips.insert(block_id, TipMetadata::new());
-            for parent in &parents {
-                self.add_child(*parent, block_id);
-                self.check_retention_rules_for_parent(parent);
-            }
+    pub(crate) fn insert_non_lazy(&mut self, block_id: BlockId, parents: &[BlockId]) {
+        self.non_lazy_tips.insert(block_id);
+        self.tips.insert(block_id, TipMetadata::new());
+        for parent in parents {
+            self.add_child(*parent, block_id);
+            self.check_retention_rules_for_parent(parent);
         }
     }
 
@@ -102,8 +107,8 @@ impl UrtsTipPool {
         // For every tip we add to the pool we call `add_child()`. `add_child()` makes sure that the parents of the tip
         // are present in the pool. Since `check_retention_rules_for_parent()` will be called after `add_child()` we
         // can be sure that the parents do exist. Therefore, unwrapping the parents here is fine.
-        if self.non_lazy_tips.len() > MAX_LIMIT_NON_LAZY as usize
-            || self.tips.get(parent).unwrap().children.len() > MAX_NUM_CHILDREN as usize
+        if self.non_lazy_tips.len() > self.max_limit_non_lazy
+            || self.tips.get(parent).unwrap().children.len() > self.max_num_children
             || self
                 .tips
                 .get(parent)
@@ -112,19 +117,27 @@ impl UrtsTipPool {
                 .unwrap()
                 .elapsed()
                 .as_secs()
-                > MAX_AGE_SECONDS_AFTER_FIRST_CHILD as u64
+                > self.max_age_seconds_after_first_child
         {
             self.tips.remove(parent);
             self.non_lazy_tips.remove(parent);
         }
     }
 
-    pub(crate) async fn update_scores<B: StorageBackend>(&mut self, tangle: &Tangle<B>) {
+    /// Removes the tips that are not non-lazy anymore and returns the ones that became semi-lazy.
+    pub(crate) async fn remove_lazy_tips<B: StorageBackend>(&mut self, tangle: &Tangle<B>) -> Vec<BlockId> {
         let mut to_remove = Vec::new();
+        let mut semi_lazy = Vec::new();
 
         for tip in self.tips.keys() {
             match self.tip_score::<B>(tangle, tip).await {
-                Score::SemiLazy | Score::Lazy => {
+                Score::SemiLazy => {
+                    to_remove.push(*tip);
+                    if self.non_lazy_tips.contains(tip) {
+                        semi_lazy.push(*tip);
+                    }
+                }
+                Score::Lazy => {
                     to_remove.push(*tip);
                 }
                 _ => continue,
@@ -137,9 +150,11 @@ impl UrtsTipPool {
         }
 
         debug!("Non-lazy tips {}", self.non_lazy_tips.len());
+
+        semi_lazy
     }
 
-    async fn tip_score<B: StorageBackend>(&self, tangle: &Tangle<B>, block_id: &BlockId) -> Score {
+    pub(crate) async fn tip_score<B: StorageBackend>(&self, tangle: &Tangle<B>, block_id: &BlockId) -> Score {
         // in case the tip was pruned by the node, consider tip as lazy
         if !tangle.contains(block_id) {
             Score::Lazy
@@ -155,9 +170,9 @@ impl UrtsTipPool {
                 .map(|(o, y)| (*o.index(), *y.index()))
                 .unwrap();
 
-            if smi > ymrsi + YMRSI_DELTA || smi > omrsi + self.below_max_depth {
+            if smi > ymrsi + self.ymrsi_delta || smi > omrsi + self.below_max_depth {
                 Score::Lazy
-            } else if smi > omrsi + OMRSI_DELTA {
+            } else if smi > omrsi + self.omrsi_delta {
                 Score::SemiLazy
             } else {
                 Score::NonLazy
@@ -165,36 +180,55 @@ impl UrtsTipPool {
         }
     }
 
-    pub fn choose_non_lazy_tips(&self) -> Option<Vec<BlockId>> {
-        if self.non_lazy_tips.is_empty() {
-            None
+    pub(crate) fn choose_non_lazy_tips(&self, amount: usize) -> Vec<BlockId> {
+        if self.non_lazy_tips.len() <= amount {
+            self.non_lazy_tips.iter().copied().collect()
         } else {
-            Some(if self.non_lazy_tips.len() < self.optimal_num_tips() {
-                self.non_lazy_tips.iter().copied().collect()
-            } else {
-                self.non_lazy_tips
-                    .iter()
-                    .choose_multiple(&mut rand::thread_rng(), self.optimal_num_tips())
-                    .iter()
-                    .map(|t| **t)
-                    .collect()
-            })
+            self.non_lazy_tips
+                .iter()
+                .copied()
+                .choose_multiple(&mut rand::thread_rng(), amount)
         }
     }
 
-    pub(crate) fn optimal_num_tips(&self) -> usize {
-        // TODO: hardcoded at the moment
-        4
+    pub(crate) fn num_tips(&self) -> usize {
+        self.num_tips
     }
+}
 
-    pub(crate) fn reduce_tips(&mut self) {
+#[async_trait]
+impl<B: StorageBackend> TipSelector<B> for UrtsTipPool {
+    async fn insert(&mut self, tangle: &Tangle<B>, block_id: BlockId, parents: Vec<BlockId>) {
+        if let Score::NonLazy = self.tip_score::<B>(tangle, &block_id).await {
+            self.insert_non_lazy(block_id, &parents);
+        }
+    }
+
+    async fn update_scores(&mut self, tangle: &Tangle<B>) {
+        self.remove_lazy_tips(tangle).await;
+    }
+
+    fn select_tips(&self) -> Option<Vec<BlockId>> {
+        if self.non_lazy_tips.is_empty() {
+            None
+        } else {
+            Some(self.choose_non_lazy_tips(self.num_tips))
+        }
+    }
+
+    fn reduce_tips(&mut self) {
         let non_lazy_tips = &mut self.non_lazy_tips;
+        let max_age_seconds_after_first_child = self.max_age_seconds_after_first_child;
         self.tips.retain(|tip, metadata| {
             metadata
                 .time_first_child
-                .filter(|age| age.elapsed().as_secs() > MAX_AGE_SECONDS_AFTER_FIRST_CHILD as u64)
+                .filter(|age| age.elapsed().as_secs() > max_age_seconds_after_first_child)
                 .map(|_| non_lazy_tips.remove(tip))
                 .is_none()
         });
     }
+
+    fn non_lazy_tips_num(&self) -> usize {
+        self.non_lazy_tips.len()
+    }
 }
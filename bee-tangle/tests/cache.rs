@@ -0,0 +1,116 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{
+    parent::Parents,
+    protocol::protocol_parameters,
+    rand::block::{rand_block, rand_block_with_parents},
+    Block, BlockId,
+};
+use bee_runtime::resource::ResourceHandle;
+use bee_storage::{access::Fetch, backend::StorageBackend};
+use bee_storage_memory::storage::Storage;
+use bee_tangle::{block_metadata::BlockMetadata, config::TangleConfig, Tangle};
+
+fn tangle(cache_size: usize) -> (Tangle<Storage>, ResourceHandle<Storage>) {
+    let storage = ResourceHandle::new(Storage::start(()).unwrap());
+    let tangle = Tangle::new(
+        TangleConfig::build().with_cache_size(cache_size).finish(),
+        storage.clone(),
+    );
+
+    (tangle, storage)
+}
+
+fn insert_block(tangle: &Tangle<Storage>, block: Block) -> BlockId {
+    let block_id = block.id();
+    tangle.insert(&block, &block_id, &BlockMetadata::arrived());
+
+    block_id
+}
+
+fn is_solid_in_storage(storage: &Storage, block_id: &BlockId) -> bool {
+    Fetch::<BlockId, BlockMetadata>::fetch(storage, block_id)
+        .unwrap()
+        .unwrap()
+        .flags()
+        .is_solid()
+}
+
+#[test]
+fn metadata_written_through() {
+    let (tangle, storage) = tangle(10);
+    let block_id = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+
+    tangle.update_metadata(&block_id, |metadata| metadata.flags_mut().set_solid(true));
+
+    assert!(is_solid_in_storage(&storage, &block_id));
+    assert!(tangle.get_metadata(&block_id).unwrap().flags().is_solid());
+    assert_eq!(tangle.cache_metrics().metadata.hits, 1);
+}
+
+#[test]
+fn metadata_cached_after_eviction() {
+    let (tangle, storage) = tangle(1);
+    let first = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+    let second = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+
+    tangle.update_metadata(&first, |metadata| metadata.flags_mut().set_solid(true));
+    tangle.get_metadata(&second);
+
+    assert!(is_solid_in_storage(&storage, &first));
+    assert!(tangle.get_metadata(&first).unwrap().flags().is_solid());
+    assert_eq!(tangle.cache_metrics().metadata.misses, 2);
+}
+
+#[test]
+fn metadata_written_through_when_disabled() {
+    let (tangle, storage) = tangle(0);
+    let block_id = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+
+    tangle.update_metadata(&block_id, |metadata| metadata.flags_mut().set_solid(true));
+
+    assert!(is_solid_in_storage(&storage, &block_id));
+}
+
+#[test]
+fn children_updated_on_insert() {
+    let (tangle, _storage) = tangle(10);
+    let parent = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+    let parents = Parents::new(vec![parent]).unwrap();
+
+    let first = insert_block(
+        &tangle,
+        rand_block_with_parents(parents.clone(), protocol_parameters().min_pow_score()),
+    );
+    assert_eq!(tangle.get_children(&parent).unwrap(), vec![first]);
+
+    let second = insert_block(
+        &tangle,
+        rand_block_with_parents(parents, protocol_parameters().min_pow_score()),
+    );
+    let mut children = tangle.get_children(&parent).unwrap();
+    children.sort();
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(children, expected);
+
+    let metrics = tangle.cache_metrics().children;
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hits, 1);
+}
+
+#[test]
+fn invalidate_drops_cached_data() {
+    let (tangle, _storage) = tangle(10);
+    let block_id = insert_block(&tangle, rand_block(protocol_parameters().min_pow_score()));
+
+    assert!(tangle.get(&block_id).is_some());
+    assert_eq!(tangle.cache_metrics().blocks.hits, 1);
+
+    tangle.invalidate(&[block_id]);
+
+    assert_eq!(tangle.cache_metrics().blocks.len, 0);
+    assert!(tangle.get(&block_id).is_some());
+    assert_eq!(tangle.cache_metrics().blocks.misses, 1);
+}
@@ -0,0 +1,119 @@
+// Copyright 2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_block::{payload::milestone::MilestoneIndex, protocol::protocol_parameters, rand::block::rand_block, BlockId};
+use bee_runtime::resource::ResourceHandle;
+use bee_storage::backend::StorageBackend;
+use bee_storage_memory::storage::Storage;
+use bee_tangle::{
+    block_metadata::{BlockMetadata, IndexId},
+    config::{TangleConfig, TipSelectionConfig, TipSelectionConfigBuilder, TipSelectionStrategy},
+    Tangle,
+};
+use tokio::runtime::Runtime;
+
+const SOLID_MILESTONE_INDEX: u32 = 20;
+
+fn tangle(tip_selection: TipSelectionConfigBuilder) -> Tangle<Storage> {
+    let tangle = Tangle::new(
+        TangleConfig::build()
+            .with_tip_selection(tip_selection.with_num_tips(2))
+            .finish(),
+        ResourceHandle::new(Storage::start(()).unwrap()),
+    );
+    tangle.update_solid_milestone_index(MilestoneIndex(SOLID_MILESTONE_INDEX));
+
+    tangle
+}
+
+async fn insert_tip(tangle: &Tangle<Storage>, omrsi: u32) -> BlockId {
+    let block = rand_block(protocol_parameters().min_pow_score());
+    let block_id = block.id();
+    let mut metadata = BlockMetadata::arrived();
+
+    metadata.mark_solid();
+    metadata.set_omrsi_and_ymrsi(
+        IndexId::new(MilestoneIndex(omrsi), block_id),
+        IndexId::new(MilestoneIndex(SOLID_MILESTONE_INDEX), block_id),
+    );
+    tangle.insert(&block, &block_id, &metadata);
+    tangle.insert_tip(block_id, Vec::new()).await;
+
+    block_id
+}
+
+#[test]
+fn urts_selects_non_lazy_tips() {
+    Runtime::new().unwrap().block_on(async {
+        let tangle = tangle(TipSelectionConfig::build());
+
+        assert!(tangle.get_blocks_to_approve().await.is_none());
+
+        for _ in 0..3 {
+            insert_tip(&tangle, SOLID_MILESTONE_INDEX).await;
+        }
+        // Semi-lazy.
+        insert_tip(&tangle, SOLID_MILESTONE_INDEX - 14).await;
+
+        assert_eq!(tangle.non_lazy_tips_num().await, 3);
+        assert_eq!(tangle.get_blocks_to_approve().await.unwrap().len(), 2);
+    });
+}
+
+#[test]
+fn promotion_favours_semi_lazy_tips() {
+    Runtime::new().unwrap().block_on(async {
+        let tangle = tangle(TipSelectionConfig::build().with_strategy(TipSelectionStrategy::Promotion));
+
+        let non_lazy = insert_tip(&tangle, SOLID_MILESTONE_INDEX).await;
+        let semi_lazy = insert_tip(&tangle, SOLID_MILESTONE_INDEX - 14).await;
+        // Lazy.
+        insert_tip(&tangle, SOLID_MILESTONE_INDEX - 16).await;
+
+        assert_eq!(tangle.non_lazy_tips_num().await, 1);
+        assert_eq!(tangle.get_blocks_to_approve().await.unwrap(), vec![semi_lazy, non_lazy]);
+    });
+}
+
+#[test]
+fn promotion_leaves_room_for_non_lazy_tips() {
+    Runtime::new().unwrap().block_on(async {
+        let tangle = tangle(TipSelectionConfig::build().with_strategy(TipSelectionStrategy::Promotion));
+
+        let non_lazy = insert_tip(&tangle, SOLID_MILESTONE_INDEX).await;
+        let semi_lazy = [
+            insert_tip(&tangle, SOLID_MILESTONE_INDEX - 14).await,
+            insert_tip(&tangle, SOLID_MILESTONE_INDEX - 14).await,
+        ];
+
+        let tips = tangle.get_blocks_to_approve().await.unwrap();
+
+        assert_eq!(tips.len(), 2);
+        assert!(semi_lazy.contains(&tips[0]));
+        assert_eq!(tips[1], non_lazy);
+    });
+}
+
+#[test]
+fn promotion_retains_tips_becoming_semi_lazy() {
+    Runtime::new().unwrap().block_on(async {
+        let tangle = tangle(
+            TipSelectionConfig::build()
+                .with_strategy(TipSelectionStrategy::Promotion)
+                .with_ymrsi_delta(14),
+        );
+
+        let tip = insert_tip(&tangle, SOLID_MILESTONE_INDEX).await;
+
+        tangle.update_solid_milestone_index(MilestoneIndex(SOLID_MILESTONE_INDEX + 14));
+        tangle.update_tip_scores().await;
+
+        assert_eq!(tangle.non_lazy_tips_num().await, 0);
+        assert_eq!(tangle.get_blocks_to_approve().await.unwrap(), vec![tip]);
+
+        tangle.update_solid_milestone_index(MilestoneIndex(SOLID_MILESTONE_INDEX + 16));
+        tangle.update_tip_scores().await;
+
+        assert!(tangle.get_blocks_to_approve().await.is_none());
+    });
+}